@@ -1,3 +1,6 @@
 fn main() {
-    tauri_build::build()
+    // Long-path aware manifest on Windows (ignored on other platforms)
+    let windows = tauri_build::WindowsAttributes::new().app_manifest(include_str!("windows-app-manifest.xml"));
+    tauri_build::try_build(tauri_build::Attributes::new().windows_attributes(windows))
+        .expect("failed to run tauri build script");
 }
@@ -0,0 +1,375 @@
+//! Command registry: every command is registered here once. The names feed the API manifest
+//! (commands::api), and argument and result types are exported to `src/lib/bindings.ts`.
+
+use crate::commands::{
+    api, attachments, backup, chat, config, console, contacts, crash_reports, diagnostics, history,
+    installer, locks, placeholders, process, service, sessions, skills, snapshots, tasks,
+    telemetry, trash,
+};
+use crate::events;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use tauri_specta::{collect_commands, Builder};
+
+/// Where the generated bindings go, relative to src-tauri
+pub const BINDINGS_PATH: &str = "../src/lib/bindings.ts";
+
+/// Expands the command list into the builder and the list of command names
+macro_rules! register_commands {
+    ($($module:ident::$command:ident),* $(,)?) => {
+        /// Names of all registered commands, as the frontend invokes them
+        pub const COMMAND_NAMES: &[&str] = &[$(stringify!($command)),*];
+
+        /// All commands the frontend can invoke
+        pub fn builder() -> Builder<tauri::Wry> {
+            Builder::<tauri::Wry>::new()
+                .commands(collect_commands![$($module::$command),*])
+                // Pushed over manager://events rather than returned by a command
+                .typ::<events::EventEnvelope>()
+        }
+    };
+}
+
+// Deprecated aliases (api::DEPRECATED_ALIASES) are registered here like any other command
+register_commands![
+    // API manifest
+    api::get_api_manifest,
+    // Service management
+    service::get_service_status,
+    service::start_service,
+    service::stop_service,
+    service::restart_service,
+    service::get_start_readiness,
+    service::get_channel_pause_status,
+    service::pause_all_channels,
+    service::resume_all_channels,
+    service::get_logs,
+    service::configure_log_rotation,
+    service::get_log_storage_stats,
+    service::get_crash_loop_status,
+    service::reset_crash_loop,
+    service::get_restart_diffs,
+    service::get_resource_alert_config,
+    service::save_resource_alert_config,
+    service::get_resource_floor_config,
+    service::save_resource_floor_config,
+    service::get_gateway_limits,
+    service::save_gateway_limits,
+    service::get_network_stats,
+    service::capture_diagnostics_and_restart,
+    service::kill_all_port_processes,
+    service::detect_external_gateway,
+    service::adopt_gateway,
+    service::probe_gateway,
+    // Gateway instances
+    service::list_gateway_instances,
+    service::save_gateway_instance,
+    service::delete_gateway_instance,
+    service::get_gateway_instance_status,
+    service::start_gateway_instance,
+    service::stop_gateway_instance,
+    service::get_gateway_instance_logs,
+    // Process management
+    process::check_openclaw_installed,
+    process::get_openclaw_version,
+    process::check_secure_version,
+    process::check_port_in_use,
+    process::check_ollama_installed,
+    process::get_ollama_models,
+    process::install_ollama_model,
+    process::get_gateway_process_tree,
+    process::kill_gateway_child,
+    // Console (advanced mode) and audit log
+    console::get_advanced_mode,
+    console::set_advanced_mode,
+    console::start_console_command,
+    console::write_console_input,
+    console::close_console_input,
+    console::cancel_console_command,
+    console::get_audit_log,
+    // Configuration management
+    config::get_config,
+    config::save_config,
+    config::save_raw_config,
+    config::get_config_with_notes,
+    config::set_config_note,
+    // Config history
+    history::get_config_history,
+    history::set_active_view,
+    config::get_tools_profile,
+    config::save_tools_profile,
+    config::get_pdf_config,
+    config::save_pdf_config,
+    config::get_memory_config,
+    config::save_memory_config,
+    config::get_embedding_config,
+    config::save_embedding_config,
+    config::get_voice_config,
+    config::save_voice_config,
+    config::get_env_value,
+    config::save_env_value,
+    config::copy_secret_to_clipboard,
+    config::get_ai_providers,
+    // Manager storage (portable mode, config directory)
+    config::get_portable_mode,
+    config::set_portable_mode,
+    config::set_config_dir,
+    // Backups
+    backup::create_backup,
+    backup::list_backups,
+    backup::verify_backup,
+    backup::preview_restore_section,
+    backup::restore_backup_section,
+    // Config snapshots
+    snapshots::create_config_snapshot,
+    snapshots::list_config_snapshots,
+    snapshots::diff_config,
+    snapshots::restore_config_snapshot,
+    config::get_channels_config,
+    config::save_channel_config,
+    config::clear_channel_config,
+    // Gateway Token
+    config::get_or_create_gateway_token,
+    config::get_dashboard_url,
+    config::repair_device_token,
+    config::get_token_rotation_settings,
+    config::save_token_rotation_settings,
+    config::rotate_gateway_token_now,
+    config::list_gateway_clients,
+    config::create_gateway_client,
+    config::revoke_gateway_client,
+    // AI configuration management
+    config::get_official_providers,
+    config::list_provider_key_expiry,
+    config::set_provider_key_expiry,
+    config::get_ai_config,
+    config::discover_gemini_models,
+    config::save_vertex_credentials,
+    config::save_provider,
+    config::delete_provider,
+    config::set_primary_model,
+    config::switch_primary_model,
+    config::add_available_model,
+    config::remove_available_model,
+    config::set_available_models,
+    config::bulk_add_models,
+    // Feishu plugin management
+    config::check_feishu_plugin,
+    config::install_feishu_plugin,
+    // MCP management
+    config::get_mcp_config,
+    config::save_mcp_config,
+    config::install_mcp_from_git,
+    config::install_mcp_from_npm,
+    config::install_mcps,
+    config::uninstall_mcp,
+    config::check_mcp_updates,
+    config::update_mcp,
+    config::import_from_mcporter,
+    config::get_mcporter_conflicts,
+    config::resolve_mcporter_conflict,
+    config::preview_mcp_export,
+    config::export_mcp_to_client,
+    config::check_mcporter_installed,
+    config::install_mcporter,
+    config::uninstall_mcporter,
+    config::review_plugin_install,
+    config::install_mcp_plugin,
+    config::openclaw_config_set,
+    config::validate_openclaw_config,
+    config::validate_config_schema,
+    config::test_mcp_server,
+    // Diagnostic tests
+    diagnostics::run_doctor,
+    diagnostics::security_audit,
+    diagnostics::check_write_access,
+    locks::get_config_lock_status,
+    config::get_config_cache_status,
+    config::restore_config_from_backup,
+    diagnostics::test_ai_connection,
+    diagnostics::test_gemini_connection,
+    diagnostics::test_vertex_connection,
+    diagnostics::test_providers,
+    diagnostics::test_embeddings,
+    diagnostics::test_tts,
+    diagnostics::run_self_test,
+    diagnostics::test_channel,
+    diagnostics::send_test_message,
+    diagnostics::list_message_templates,
+    diagnostics::save_message_template,
+    diagnostics::delete_message_template,
+    diagnostics::preview_message_template,
+    diagnostics::get_canary_config,
+    diagnostics::save_canary_config,
+    diagnostics::run_canary_now,
+    diagnostics::get_heartbeat_status,
+    diagnostics::get_system_info,
+    diagnostics::get_mock_mode,
+    diagnostics::start_channel_login,
+    diagnostics::diagnose_path,
+    diagnostics::fix_path,
+    diagnostics::find_sync_conflicts,
+    diagnostics::resolve_sync_conflict,
+    // Installer
+    installer::check_environment,
+    installer::get_package_managers,
+    installer::get_node_package_manager,
+    installer::set_node_package_manager,
+    installer::get_wsl_mode,
+    installer::set_wsl_mode,
+    installer::upgrade_package,
+    installer::install_nodejs,
+    installer::install_git,
+    installer::install_openclaw,
+    installer::init_openclaw_config,
+    installer::open_install_terminal,
+    installer::uninstall_openclaw,
+    installer::install_gateway_service,
+    // Version update
+    installer::check_openclaw_update,
+    installer::update_openclaw,
+    // Interrupted operations
+    tasks::list_interrupted_tasks,
+    tasks::resume_task,
+    tasks::dismiss_task,
+    // Skills management
+    skills::get_skills,
+    skills::check_clawhub_installed,
+    skills::install_clawhub,
+    skills::install_skill,
+    skills::install_skills,
+    skills::dry_run_skill,
+    skills::link_local_skill,
+    skills::watch_local_skill,
+    skills::unwatch_local_skill,
+    skills::uninstall_skill,
+    skills::uninstall_clawhub,
+    // Multi-Agent Routing
+    config::get_openclaw_home_dir,
+    config::get_agents_config,
+    config::save_agent,
+    config::save_subagent_defaults,
+    config::delete_agent,
+    config::save_agent_binding,
+    config::delete_agent_binding,
+    config::get_paused_agents,
+    config::pause_agent,
+    config::resume_agent,
+    config::get_agent_system_prompt,
+    config::save_agent_system_prompt,
+    config::save_agent_workspace_file,
+    config::estimate_prompt_cost,
+    config::test_agent_routing,
+    config::get_fallback_agent,
+    config::set_fallback_agent,
+    // Telegram Multi-Account
+    config::get_telegram_accounts,
+    config::save_telegram_account,
+    config::import_telegram_accounts,
+    config::set_account_enabled,
+    config::delete_telegram_account,
+    config::verify_telegram_group,
+    config::get_topic_routing,
+    config::save_topic_routing,
+    // Heartbeat & Compaction
+    config::get_heartbeat_config,
+    config::save_heartbeat_config,
+    config::get_compaction_config,
+    config::save_compaction_config,
+    // Workspace & Personality
+    config::get_workspace_config,
+    config::save_workspace_config,
+    config::get_personality_file,
+    config::save_personality_file,
+    config::list_workspace_templates,
+    config::insert_workspace_template,
+    config::render_markdown,
+    // Session replay
+    sessions::export_replay,
+    // Browser Control
+    config::get_browser_config,
+    config::save_browser_config,
+    // Web Search
+    config::get_web_config,
+    config::save_web_config,
+    // Channel Pairing
+    config::list_pending_pairings,
+    config::approve_pairing,
+    config::reject_pairing,
+    // Contacts (address book of channel peers)
+    contacts::list_contacts,
+    contacts::save_contact,
+    contacts::delete_contact,
+    contacts::suggest_contacts,
+    contacts::import_contacts,
+    // Messages sent as a bot
+    chat::send_channel_message,
+    chat::list_broadcast_targets,
+    chat::broadcast_channel_message,
+    // Trash (restorable deletions)
+    trash::list_deleted_items,
+    trash::restore_deleted_item,
+    // Attachments
+    attachments::get_attachment_policies,
+    attachments::save_attachment_policy,
+    attachments::get_attachment_storage,
+    attachments::get_attachment_retention,
+    attachments::save_attachment_retention,
+    attachments::clean_attachments_now,
+    attachments::get_last_attachment_cleanup,
+    // Gateway Configuration
+    config::get_gateway_config,
+    config::save_gateway_config,
+    config::get_rate_limits,
+    config::save_rate_limits,
+    config::suggest_rate_limits,
+    // Configuration Management
+    config::scan_config_secrets,
+    placeholders::validate_config_placeholders,
+    placeholders::list_config_secrets,
+    placeholders::set_config_secret,
+    placeholders::delete_config_secret,
+    placeholders::get_keychain_status,
+    config::export_config,
+    config::import_config,
+    config::get_config_sources,
+    config::compare_with,
+    config::resolve_setting_location,
+    config::find_orphans,
+    config::fix_orphans,
+    config::explain_model_resolution,
+    // Git sync
+    config::link_git_repo,
+    config::unlink_git_repo,
+    config::get_git_sync_status,
+    config::preview_git_pull,
+    config::apply_git_pull,
+    config::push_git_changes,
+    // Configuration Draft
+    config::get_draft_status,
+    config::begin_draft,
+    config::preview_draft_diff,
+    config::commit_draft,
+    config::discard_draft,
+    // Telemetry
+    telemetry::get_telemetry_status,
+    telemetry::set_telemetry,
+    telemetry::preview_telemetry,
+    // Crash reports of the Manager
+    crash_reports::get_crash_report_status,
+    crash_reports::list_crash_reports,
+    crash_reports::submit_crash_reports,
+    crash_reports::dismiss_crash_reports,
+];
+
+/// Write the TypeScript types and typed invoke client for `builder` to `path`
+pub fn export(builder: &Builder<tauri::Wry>, path: &str) -> Result<(), String> {
+    builder
+        .export(
+            Typescript::default()
+                // Sizes and byte counts are u64; they fit comfortably in a JS number
+                .bigint(BigIntExportBehavior::Number)
+                .header("// Generated from the Rust command signatures. Do not edit by hand.\n/* eslint-disable */\n// @ts-nocheck"),
+            path,
+        )
+        .map_err(|e| format!("Failed to export TypeScript bindings: {}", e))
+}
@@ -0,0 +1,120 @@
+use crate::bindings;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+/// Version of the command API as a whole; bumped when a command is removed
+pub const API_VERSION: u32 = 1;
+
+/// Commands whose arguments or result changed shape; all others are at schema version 1.
+/// Bump the entry whenever a payload changes so frontends can feature-check.
+pub(crate) const SCHEMA_VERSIONS: &[(&str, u32)] = &[
+    // force: skip the start readiness checks
+    ("start_service", 2),
+    ("restart_service", 2),
+    // secrets: how secrets are written to the export
+    ("export_config", 2),
+    ("preview_mcp_export", 2),
+    ("export_mcp_to_client", 2),
+    // acknowledge_risk: confirm importing tokens in plain text
+    ("save_telegram_account", 2),
+    ("import_telegram_accounts", 2),
+    // template_id: message template to send
+    ("send_test_message", 2),
+    // channel, peer_id, topic_id: route a specific conversation
+    ("test_agent_routing", 2),
+    // docker: test a container-based server
+    ("test_mcp_server", 2),
+    // copy_from (2), headers, auth_header, azure and verify (3), keychain: store the key in the OS keychain (4)
+    ("save_provider", 4),
+    // returns PromptFileSaved (token count and cost) instead of a message
+    ("save_agent_system_prompt", 2),
+    // confirmed: install a package whose review needs confirmation
+    ("install_mcp_plugin", 2),
+    ("install_feishu_plugin", 2),
+    // returns AvailableModelsSaved (models of unknown providers are reported, not dropped)
+    ("set_available_models", 2),
+    ("bulk_add_models", 2),
+];
+
+/// An old command name kept for frontends built against it. The alias is a thin command
+/// that calls `warn_deprecated` and then its replacement; it is dropped with the next
+/// API_VERSION bump.
+pub struct CommandAlias {
+    pub name: &'static str,
+    pub replaced_by: &'static str,
+    /// Manager version that deprecated the name
+    pub deprecated_in: &'static str,
+}
+
+/// Deprecated command names that still answer
+pub const DEPRECATED_ALIASES: &[CommandAlias] = &[];
+
+/// One invokable command
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CommandInfo {
+    pub name: String,
+    pub schema_version: u32,
+    pub deprecated: bool,
+    /// Command to call instead (deprecated aliases only)
+    pub replaced_by: Option<String>,
+    pub deprecated_in: Option<String>,
+}
+
+/// Commands this backend answers, for frontends to check before calling
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ApiManifest {
+    pub api_version: u32,
+    pub app_version: String,
+    pub commands: Vec<CommandInfo>,
+}
+
+/// Schema version of a command (1 unless listed in SCHEMA_VERSIONS)
+pub(crate) fn schema_version(name: &str) -> u32 {
+    SCHEMA_VERSIONS
+        .iter()
+        .find(|(command, _)| *command == name)
+        .map(|(_, version)| *version)
+        .unwrap_or(1)
+}
+
+/// Manifest for the registered `names`, marking the ones listed in `aliases`
+pub(crate) fn build_manifest(names: &[&str], aliases: &[CommandAlias]) -> ApiManifest {
+    let commands = names
+        .iter()
+        .map(|name| {
+            let alias = aliases.iter().find(|a| a.name == *name);
+            CommandInfo {
+                name: name.to_string(),
+                // An alias answers with its replacement's payload
+                schema_version: schema_version(alias.map(|a| a.replaced_by).unwrap_or(name)),
+                deprecated: alias.is_some(),
+                replaced_by: alias.map(|a| a.replaced_by.to_string()),
+                deprecated_in: alias.map(|a| a.deprecated_in.to_string()),
+            }
+        })
+        .collect();
+    ApiManifest {
+        api_version: API_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        commands,
+    }
+}
+
+/// Log a call to a deprecated alias (called first thing by every alias command)
+pub(crate) fn warn_deprecated(name: &str) {
+    if let Some(alias) = DEPRECATED_ALIASES.iter().find(|a| a.name == name) {
+        warn!(
+            "[API] {} is deprecated since {}, use {}",
+            alias.name, alias.deprecated_in, alias.replaced_by
+        );
+    }
+}
+
+/// Get the available commands with their schema versions and deprecations
+#[command]
+#[specta::specta]
+pub async fn get_api_manifest() -> Result<ApiManifest, String> {
+    info!("[API] Getting API manifest...");
+    Ok(build_manifest(bindings::COMMAND_NAMES, DEPRECATED_ALIASES))
+}
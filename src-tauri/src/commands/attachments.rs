@@ -0,0 +1,401 @@
+use crate::commands::config::{
+    load_manager_config, load_openclaw_config, save_manager_config, save_openclaw_config,
+};
+use crate::commands::locks::{self, ConfigResource};
+use crate::commands::service;
+use crate::events::{self, AlertLevel, ManagerEvent};
+use crate::utils::retention::{self, RemovalReason, RetainedFile};
+use crate::utils::{platform, shell};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::command;
+
+/// Channels whose config accepts `mediaMaxMb`
+const MEDIA_LIMIT_CHANNELS: &[&str] = &[
+    "telegram", "whatsapp", "discord", "slack", "signal", "imessage",
+];
+
+/// Below this much free space the download directory is flagged
+const LOW_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Attachment handling for one channel. `max_file_mb` is the core's channels.<id>.mediaMaxMb;
+/// `cleanup_after_days` is a Manager setting (manager.json -> attachmentPolicies.<id>).
+/// The gateway always stores received files in <config dir>/media/inbound and has no
+/// per-channel MIME filter, so neither is offered here.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AttachmentPolicy {
+    pub channel: String,
+    /// Largest file the channel sends or accepts, in MB
+    pub max_file_mb: Option<u32>,
+    /// Delete received files older than this many days (None follows the global retention)
+    pub cleanup_after_days: Option<u32>,
+}
+
+/// Disk usage of one download directory
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AttachmentStorage {
+    pub dir: String,
+    /// Channels storing files here
+    pub channels: Vec<String>,
+    pub file_count: u32,
+    pub total_bytes: u64,
+    /// Free space on the volume, if known
+    pub free_bytes: Option<u64>,
+    pub low_space: bool,
+}
+
+/// Default directory the gateway saves received media to
+pub(crate) fn default_download_dir() -> String {
+    platform::path_string(&platform::join_path(
+        platform::get_config_dir(),
+        &["media", "inbound"],
+    ))
+}
+
+fn policy_for(config: &Value, manager_config: &Value, channel: &str) -> AttachmentPolicy {
+    let stored = manager_config.pointer(&format!("/attachmentPolicies/{}", channel));
+    let get = |key: &str| stored.and_then(|p| p.get(key));
+    AttachmentPolicy {
+        channel: channel.to_string(),
+        max_file_mb: config
+            .pointer(&format!("/channels/{}/mediaMaxMb", channel))
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u32),
+        cleanup_after_days: get("cleanupAfterDays")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u32),
+    }
+}
+
+/// Policies of every configured channel
+pub(crate) fn load_attachment_policies() -> Result<Vec<AttachmentPolicy>, String> {
+    let config = load_openclaw_config()?;
+    let manager_config = load_manager_config()?;
+    let mut channels: Vec<&String> = config
+        .get("channels")
+        .and_then(|c| c.as_object())
+        .map(|c| c.keys().collect())
+        .unwrap_or_default();
+    channels.sort();
+    Ok(channels
+        .into_iter()
+        .map(|c| policy_for(&config, &manager_config, c))
+        .collect())
+}
+
+/// Attachment policies for all configured channels
+#[command]
+#[specta::specta]
+pub async fn get_attachment_policies() -> Result<Vec<AttachmentPolicy>, String> {
+    load_attachment_policies()
+}
+
+/// Save one channel's attachment policy
+#[command]
+#[specta::specta]
+pub async fn save_attachment_policy(policy: AttachmentPolicy) -> Result<AttachmentPolicy, String> {
+    let _lock = locks::lock(
+        &[ConfigResource::OpenClaw, ConfigResource::Manager],
+        "save_attachment_policy",
+    )
+    .await;
+    let channel = policy.channel.trim().to_lowercase();
+    info!("[Attachments] Saving policy for {}", channel);
+
+    let mut config = load_openclaw_config()?;
+    if config
+        .pointer(&format!("/channels/{}", channel))
+        .map(|c| c.is_object())
+        != Some(true)
+    {
+        return Err(format!("Channel '{}' is not configured", channel));
+    }
+    if policy.max_file_mb.is_some() && !MEDIA_LIMIT_CHANNELS.contains(&channel.as_str()) {
+        return Err(format!("Channel '{}' has no file size setting", channel));
+    }
+    if policy.max_file_mb == Some(0) {
+        return Err("Maximum file size must be at least 1 MB".to_string());
+    }
+    if policy.cleanup_after_days == Some(0) {
+        return Err("Cleanup must keep files for at least 1 day".to_string());
+    }
+
+    if let Some(channel_cfg) = config["channels"][&channel].as_object_mut() {
+        match policy.max_file_mb {
+            Some(mb) => channel_cfg.insert("mediaMaxMb".to_string(), json!(mb)),
+            None => channel_cfg.remove("mediaMaxMb"),
+        };
+    }
+    save_openclaw_config(&config, "save_attachment_policy")?;
+
+    let mut manager_config = load_manager_config()?;
+    if !manager_config
+        .get("attachmentPolicies")
+        .map(|v| v.is_object())
+        .unwrap_or(false)
+    {
+        manager_config["attachmentPolicies"] = json!({});
+    }
+    manager_config["attachmentPolicies"][&channel] = json!({
+        "cleanupAfterDays": policy.cleanup_after_days,
+    });
+    save_manager_config(&manager_config)?;
+
+    Ok(policy_for(&config, &manager_config, &channel))
+}
+
+/// Size of the download directory and the free space left on its volume
+#[command]
+#[specta::specta]
+pub async fn get_attachment_storage() -> Result<Vec<AttachmentStorage>, String> {
+    let mut storage = vec![AttachmentStorage {
+        dir: default_download_dir(),
+        channels: load_attachment_policies()?
+            .into_iter()
+            .map(|p| p.channel)
+            .collect(),
+        file_count: 0,
+        total_bytes: 0,
+        free_bytes: None,
+        low_space: false,
+    }];
+
+    for entry in storage.iter_mut() {
+        let (count, bytes) = service::dir_stats(std::path::Path::new(&entry.dir));
+        entry.file_count = count;
+        entry.total_bytes = bytes;
+        // The directory may not exist yet; measure the volume of its nearest existing parent
+        let existing = std::path::Path::new(&entry.dir)
+            .ancestors()
+            .find(|p| p.exists());
+        entry.free_bytes = existing.and_then(|p| shell::disk_free_bytes(&platform::path_string(p)));
+        entry.low_space = entry
+            .free_bytes
+            .map(|free| free < LOW_SPACE_BYTES)
+            .unwrap_or(false);
+    }
+    Ok(storage)
+}
+
+// ============ Retention ============
+
+/// Global limits for received files (manager.json -> attachmentRetention)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct AttachmentRetention {
+    /// Delete files older than this many days; a channel's cleanup_after_days wins, and
+    /// the longest one applies as channels share the directory
+    pub max_age_days: Option<u32>,
+    /// Delete the oldest files once all download directories together exceed this size
+    pub max_total_gb: Option<f64>,
+}
+
+/// A file removed by a cleanup run
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct RemovedAttachment {
+    pub path: String,
+    pub bytes: u64,
+    /// "expired" or "quota"
+    pub reason: String,
+}
+
+/// Outcome of a cleanup run
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AttachmentCleanupReport {
+    pub ran_at: String,
+    pub dry_run: bool,
+    pub removed: Vec<RemovedAttachment>,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+    /// Files that matched but could not be deleted
+    pub errors: Vec<String>,
+}
+
+static LAST_CLEANUP: std::sync::Mutex<Option<AttachmentCleanupReport>> =
+    std::sync::Mutex::new(None);
+
+fn load_retention() -> AttachmentRetention {
+    load_manager_config()
+        .ok()
+        .and_then(|c| c.get("attachmentRetention").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Regular files under `dir`. Symlinks are skipped, not followed: cleanup must never reach
+/// outside the gateway's media directory.
+fn collect_files(dir: &std::path::Path, max_age_days: Option<u32>, files: &mut Vec<RetainedFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let now = std::time::SystemTime::now();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            collect_files(&path, max_age_days, files);
+        } else if meta.is_file() {
+            let age_secs = meta
+                .modified()
+                .ok()
+                .and_then(|m| now.duration_since(m).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            files.push(RetainedFile {
+                path: platform::path_string(&path),
+                bytes: meta.len(),
+                age_secs,
+                max_age_days,
+            });
+        }
+    }
+}
+
+/// Apply the retention limits to the gateway's download directory
+pub(crate) fn run_attachment_cleanup(dry_run: bool) -> Result<AttachmentCleanupReport, String> {
+    let retention = load_retention();
+    let dir = default_download_dir();
+
+    // Channels share the directory, so the longest limit among them applies
+    let channel_limits: Vec<Option<u32>> = load_attachment_policies()?
+        .iter()
+        .map(|p| p.cleanup_after_days)
+        .collect();
+    let max_age_days = retention::shared_age_limit(&channel_limits, retention.max_age_days);
+
+    let mut files = Vec::new();
+    if std::fs::symlink_metadata(&dir)
+        .map(|m| m.is_dir())
+        .unwrap_or(false)
+    {
+        collect_files(std::path::Path::new(&dir), max_age_days, &mut files);
+    }
+    let max_total_bytes = retention
+        .max_total_gb
+        .map(|gb| (gb * 1024.0 * 1024.0 * 1024.0) as u64);
+    let selected = retention::select_for_removal(&files, retention.max_age_days, max_total_bytes);
+
+    let mut report = AttachmentCleanupReport {
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        dry_run,
+        removed: Vec::new(),
+        freed_bytes: 0,
+        remaining_bytes: files.iter().map(|f| f.bytes).sum(),
+        errors: Vec::new(),
+    };
+    for (index, reason) in selected {
+        let file = &files[index];
+        if !dry_run {
+            if let Err(e) = std::fs::remove_file(&file.path) {
+                report.errors.push(format!("{}: {}", file.path, e));
+                continue;
+            }
+        }
+        report.freed_bytes += file.bytes;
+        report.remaining_bytes -= file.bytes;
+        report.removed.push(RemovedAttachment {
+            path: file.path.clone(),
+            bytes: file.bytes,
+            reason: match reason {
+                RemovalReason::Expired => "expired",
+                RemovalReason::OverQuota => "quota",
+            }
+            .to_string(),
+        });
+    }
+
+    if !dry_run {
+        if !report.removed.is_empty() {
+            info!(
+                "[Attachments] Cleanup removed {} files ({} bytes)",
+                report.removed.len(),
+                report.freed_bytes
+            );
+        }
+        if let Ok(mut last) = LAST_CLEANUP.lock() {
+            *last = Some(report.clone());
+        }
+    }
+    Ok(report)
+}
+
+/// Start a background thread that applies attachment retention every hour
+pub fn start_attachment_cleanup_task() {
+    std::thread::spawn(|| loop {
+        match run_attachment_cleanup(false) {
+            Ok(report) if !report.errors.is_empty() => {
+                warn!(
+                    "[Attachments] Cleanup could not remove {} files",
+                    report.errors.len()
+                );
+                events::emit(ManagerEvent::alert(
+                    "attachment-cleanup-failed",
+                    AlertLevel::Warning,
+                    format!(
+                        "Attachment cleanup could not remove {} files",
+                        report.errors.len()
+                    ),
+                    &report,
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[Attachments] Cleanup skipped: {}", e),
+        }
+        std::thread::sleep(std::time::Duration::from_secs(60 * 60));
+    });
+}
+
+/// Get the global attachment retention limits
+#[command]
+#[specta::specta]
+pub async fn get_attachment_retention() -> Result<AttachmentRetention, String> {
+    Ok(load_retention())
+}
+
+/// Save the global attachment retention limits
+#[command]
+#[specta::specta]
+pub async fn save_attachment_retention(
+    retention: AttachmentRetention,
+) -> Result<AttachmentRetention, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_attachment_retention").await;
+    info!(
+        "[Attachments] Saving retention: max_age_days={:?}, max_total_gb={:?}",
+        retention.max_age_days, retention.max_total_gb
+    );
+    if retention.max_age_days == Some(0) {
+        return Err("Retention must be at least 1 day".to_string());
+    }
+    if let Some(gb) = retention.max_total_gb {
+        if !gb.is_finite() || gb <= 0.0 {
+            return Err("Maximum size must be greater than 0 GB".to_string());
+        }
+    }
+
+    let mut manager_config = load_manager_config()?;
+    manager_config["attachmentRetention"] = json!({
+        "maxAgeDays": retention.max_age_days,
+        "maxTotalGb": retention.max_total_gb,
+    });
+    save_manager_config(&manager_config)?;
+    Ok(retention)
+}
+
+/// Apply the retention limits now; `dry_run` only reports what would be removed
+#[command]
+#[specta::specta]
+pub async fn clean_attachments_now(
+    dry_run: Option<bool>,
+) -> Result<AttachmentCleanupReport, String> {
+    info!("[Attachments] Manual cleanup (dry_run={:?})", dry_run);
+    run_attachment_cleanup(dry_run.unwrap_or(false))
+}
+
+/// Report of the last cleanup that deleted files, if any ran since the Manager started
+#[command]
+#[specta::specta]
+pub async fn get_last_attachment_cleanup() -> Result<Option<AttachmentCleanupReport>, String> {
+    Ok(LAST_CLEANUP.lock().ok().and_then(|last| last.clone()))
+}
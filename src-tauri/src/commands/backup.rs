@@ -0,0 +1,756 @@
+use crate::commands::config::{load_mcp_config_file, load_openclaw_config, save_mcp_config_file, save_openclaw_config};
+use crate::commands::telemetry;
+use crate::commands::locks::{self, ConfigResource};
+use crate::models::MCPConfig;
+use crate::utils::crypto::{self, EncryptedBlob};
+use crate::utils::json_diff::{self, JsonChange};
+use crate::utils::{config_include, file, platform};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Identifies Manager backup files
+const BACKUP_FORMAT: &str = "openclaw-manager-backup";
+const BACKUP_VERSION: u32 = 1;
+
+/// Workspace files larger than this are not backed up
+const MAX_WORKSPACE_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_WORKSPACE_DEPTH: usize = 4;
+const WORKSPACE_SKIP_DIRS: &[&str] = &["node_modules", ".git", "dist", "build"];
+const WORKSPACE_TEXT_EXTENSIONS: &[&str] = &["md", "txt", "json", "yaml", "yml", "toml"];
+
+/// Agent workspace text files (persona, instructions, memory notes)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub(crate) struct WorkspaceBackup {
+    pub dir: String,
+    /// Relative path -> content
+    pub files: BTreeMap<String, String>,
+}
+
+/// Everything captured by a backup
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupPayload {
+    pub created_at: String,
+    pub manager_version: String,
+    pub config_dir: String,
+    /// Files relative to the config dir (openclaw.json, $include files, env, mcps.json)
+    pub files: BTreeMap<String, String>,
+    /// Agent id -> workspace
+    pub workspaces: BTreeMap<String, WorkspaceBackup>,
+}
+
+/// On-disk backup file; exactly one of `payload` / `encryption` is set
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct BackupFile {
+    format: String,
+    version: u32,
+    created_at: String,
+    encrypted: bool,
+    /// SHA-256 of the serialized payload (plain backups; GCM authenticates encrypted ones)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload: Option<BackupPayload>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<EncryptedBlob>,
+}
+
+/// Backup listing entry
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct BackupInfo {
+    pub id: String,
+    pub path: String,
+    pub created_at: String,
+    pub encrypted: bool,
+    pub size_bytes: u64,
+}
+
+/// Result of `verify_backup`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct BackupVerification {
+    pub valid: bool,
+    pub encrypted: bool,
+    /// Whether the contents were checked (encrypted backups need the passphrase)
+    pub contents_checked: bool,
+    pub file_count: usize,
+    pub workspace_count: usize,
+    pub message: String,
+}
+
+fn get_backups_dir() -> String {
+    if platform::is_windows() {
+        format!("{}\\backups", platform::get_manager_data_dir())
+    } else {
+        format!("{}/backups", platform::get_manager_data_dir())
+    }
+}
+
+/// Accept either a backup id from `list_backups` or a path to a backup file
+fn resolve_backup_path(id_or_path: &str) -> Result<PathBuf, String> {
+    let direct = PathBuf::from(id_or_path);
+    if direct.is_file() {
+        return Ok(direct);
+    }
+    if id_or_path.contains(['/', '\\']) || id_or_path.contains("..") {
+        return Err(format!("Backup not found: {}", id_or_path));
+    }
+    let in_store = Path::new(&get_backups_dir()).join(format!("{}.json", id_or_path));
+    if in_store.is_file() {
+        Ok(in_store)
+    } else {
+        Err(format!("Backup not found: {}", id_or_path))
+    }
+}
+
+/// Relative path of `path` below `base`, with forward slashes
+fn relative_to(base: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(base)
+        .ok()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+}
+
+fn collect_workspace_files(root: &Path, dir: &Path, depth: usize, files: &mut BTreeMap<String, String>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if depth < MAX_WORKSPACE_DEPTH && !WORKSPACE_SKIP_DIRS.contains(&name.as_str()) {
+                collect_workspace_files(root, &path, depth + 1, files);
+            }
+            continue;
+        }
+        let is_text = path
+            .extension()
+            .map(|e| WORKSPACE_TEXT_EXTENSIONS.contains(&e.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false);
+        let small = entry.metadata().map(|m| m.len() <= MAX_WORKSPACE_FILE_BYTES).unwrap_or(false);
+        if !is_text || !small {
+            continue;
+        }
+        if let (Some(rel), Ok(content)) = (relative_to(root, &path), std::fs::read_to_string(&path)) {
+            files.insert(rel, content);
+        }
+    }
+}
+
+/// Workspace directory of every configured agent
+pub(crate) fn agent_workspaces(config: &Value) -> Vec<(String, PathBuf)> {
+    let config_dir = platform::get_config_dir();
+    let entries: Vec<&Value> = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr.iter().collect(),
+        Some(Value::Object(map)) => map.values().collect(),
+        _ => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter_map(|agent| {
+            let id = agent.get("id").and_then(|v| v.as_str())?;
+            let dir = match agent.get("workspace").and_then(|v| v.as_str()) {
+                Some(ws) if !ws.is_empty() => PathBuf::from(ws),
+                _ if id == "main" => Path::new(&config_dir).join("workspace"),
+                _ => Path::new(&config_dir).join(format!("workspace-{}", id)),
+            };
+            Some((id.to_string(), dir))
+        })
+        .collect()
+}
+
+fn build_payload() -> Result<BackupPayload, String> {
+    let config_dir = platform::get_config_dir();
+    let base = Path::new(&config_dir);
+    let mut files = BTreeMap::new();
+
+    let config_path = platform::get_config_file_path();
+    let mut paths = vec![config_path.clone(), platform::get_env_file_path(), platform::get_mcp_config_file_path()];
+    let live_config = match config_include::load(&config_path) {
+        Ok((config, sources)) => {
+            paths.extend(sources.into_iter().map(|s| s.file));
+            Some(config)
+        }
+        Err(e) => {
+            warn!("[Backup] Config could not be parsed, backing up raw files only: {}", e);
+            None
+        }
+    };
+    for path in paths {
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            continue;
+        }
+        match relative_to(base, &path) {
+            Some(rel) => {
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                files.insert(rel, content);
+            }
+            None => warn!("[Backup] Skipping {} (outside the config directory)", path.display()),
+        }
+    }
+
+    let mut workspaces = BTreeMap::new();
+    if let Some(config) = live_config {
+        for (id, dir) in agent_workspaces(&config) {
+            let mut ws_files = BTreeMap::new();
+            collect_workspace_files(&dir, &dir, 0, &mut ws_files);
+            workspaces.insert(id, WorkspaceBackup { dir: dir.display().to_string(), files: ws_files });
+        }
+    }
+
+    Ok(BackupPayload {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        manager_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_dir,
+        files,
+        workspaces,
+    })
+}
+
+fn read_backup_file(path: &Path) -> Result<BackupFile, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read backup: {}", e))?;
+    parse_backup_file(&content)
+}
+
+fn parse_backup_file(content: &str) -> Result<BackupFile, String> {
+    let backup: BackupFile = serde_json::from_str(content).map_err(|e| format!("Not a valid backup file: {}", e))?;
+    if backup.format != BACKUP_FORMAT {
+        return Err("Not an OpenClaw Manager backup".to_string());
+    }
+    if backup.version > BACKUP_VERSION {
+        return Err(format!("Backup version {} is newer than this Manager supports", backup.version));
+    }
+    Ok(backup)
+}
+
+/// Extract the payload, decrypting and checking integrity
+fn open_backup(backup: BackupFile, passphrase: Option<&str>) -> Result<BackupPayload, String> {
+    if backup.encrypted {
+        let blob = backup.encryption.ok_or("Encrypted backup has no encrypted data")?;
+        let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or("This backup is encrypted, a passphrase is required")?;
+        let plaintext = crypto::decrypt(&blob, passphrase)?;
+        serde_json::from_slice(&plaintext).map_err(|e| format!("Decrypted backup is not valid: {}", e))
+    } else {
+        let payload = backup.payload.ok_or("Backup has no payload")?;
+        let serialized = serde_json::to_vec(&payload).map_err(|e| format!("Failed to serialize payload: {}", e))?;
+        match backup.sha256 {
+            Some(expected) if expected == crypto::sha256_hex(&serialized) => Ok(payload),
+            Some(_) => Err("Checksum mismatch, the backup is corrupted or was modified".to_string()),
+            None => Err("Backup has no checksum".to_string()),
+        }
+    }
+}
+
+/// Load and verify a backup by id or path
+fn load_backup(id_or_path: &str, passphrase: Option<&str>) -> Result<BackupPayload, String> {
+    let path = resolve_backup_path(id_or_path)?;
+    open_backup(read_backup_file(&path)?, passphrase)
+}
+
+/// Write `files` as a plain and an encrypted backup in `dir` and read both back (used by the self-test)
+pub(crate) fn roundtrip_check(dir: &Path, files: BTreeMap<String, String>) -> Result<(), String> {
+    let payload = BackupPayload {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        manager_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_dir: dir.display().to_string(),
+        files,
+        workspaces: BTreeMap::new(),
+    };
+    let serialized = serde_json::to_vec(&payload).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    let passphrase = "self-test";
+    let variants = [
+        BackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: payload.created_at.clone(),
+            encrypted: false,
+            sha256: Some(crypto::sha256_hex(&serialized)),
+            payload: Some(payload.clone()),
+            encryption: None,
+        },
+        BackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: payload.created_at.clone(),
+            encrypted: true,
+            sha256: None,
+            payload: None,
+            encryption: Some(crypto::encrypt(&serialized, passphrase)?),
+        },
+    ];
+
+    for (i, backup) in variants.into_iter().enumerate() {
+        let path = dir.join(format!("backup-{}.json", i));
+        let content = serde_json::to_string_pretty(&backup).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+        file::write_file(&path.to_string_lossy(), &content).map_err(|e| format!("Failed to write backup: {}", e))?;
+        let restored = open_backup(read_backup_file(&path)?, Some(passphrase))?;
+        if restored.files != payload.files {
+            return Err(format!("Restored files differ from the backup (encrypted={})", backup.encrypted));
+        }
+        if backup.encrypted && open_backup(read_backup_file(&path)?, Some("wrong")).is_ok() {
+            return Err("Encrypted backup opened with a wrong passphrase".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Create a backup of the OpenClaw configuration, env, MCP config and agent workspaces.
+/// With a passphrase the backup is encrypted (AES-256-GCM, Argon2id key) and safe for cloud drives.
+#[command]
+#[specta::specta]
+pub async fn create_backup(passphrase: Option<String>) -> Result<BackupInfo, String> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    info!("[Backup] Creating backup (encrypted={})...", passphrase.is_some());
+    telemetry::record_feature("create_backup");
+
+    let payload = build_payload()?;
+    let serialized = serde_json::to_vec(&payload).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    let created_at = payload.created_at.clone();
+
+    let backup = match &passphrase {
+        Some(pass) => BackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: created_at.clone(),
+            encrypted: true,
+            sha256: None,
+            payload: None,
+            encryption: Some(crypto::encrypt(&serialized, pass)?),
+        },
+        None => BackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: created_at.clone(),
+            encrypted: false,
+            sha256: Some(crypto::sha256_hex(&serialized)),
+            payload: Some(payload),
+            encryption: None,
+        },
+    };
+
+    let id = format!("backup-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let path = Path::new(&get_backups_dir()).join(format!("{}.json", id));
+    let content = serde_json::to_string_pretty(&backup).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    file::write_file(&path.to_string_lossy(), &content).map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    info!("[Backup] Backup written to {}", path.display());
+    Ok(BackupInfo {
+        id,
+        path: path.display().to_string(),
+        created_at,
+        encrypted: backup.encrypted,
+        size_bytes: content.len() as u64,
+    })
+}
+
+/// List backups in the Manager backup directory, newest first
+#[command]
+#[specta::specta]
+pub async fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    let dir = get_backups_dir();
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+        .filter_map(|e| {
+            let path = e.path();
+            let backup = read_backup_file(&path).ok()?;
+            Some(BackupInfo {
+                id: path.file_stem()?.to_string_lossy().to_string(),
+                path: path.display().to_string(),
+                created_at: backup.created_at,
+                encrypted: backup.encrypted,
+                size_bytes: e.metadata().map(|m| m.len()).unwrap_or(0),
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+/// Check a backup's integrity without restoring anything.
+/// Encrypted backups are fully checked only when the passphrase is given.
+#[command]
+#[specta::specta]
+pub async fn verify_backup(path: String, passphrase: Option<String>) -> Result<BackupVerification, String> {
+    info!("[Backup] Verifying backup {}", path);
+    let backup_path = resolve_backup_path(&path)?;
+    let backup = match read_backup_file(&backup_path) {
+        Ok(b) => b,
+        Err(e) => {
+            return Ok(BackupVerification {
+                valid: false,
+                encrypted: false,
+                contents_checked: false,
+                file_count: 0,
+                workspace_count: 0,
+                message: e,
+            })
+        }
+    };
+    let encrypted = backup.encrypted;
+
+    if encrypted && passphrase.as_deref().map(|p| p.is_empty()).unwrap_or(true) {
+        let structure_ok = backup.encryption.is_some();
+        return Ok(BackupVerification {
+            valid: structure_ok,
+            encrypted,
+            contents_checked: false,
+            file_count: 0,
+            workspace_count: 0,
+            message: if structure_ok {
+                "Backup structure is valid; enter the passphrase to verify its contents".to_string()
+            } else {
+                "Encrypted backup has no encrypted data".to_string()
+            },
+        });
+    }
+
+    Ok(match open_backup(backup, passphrase.as_deref()) {
+        Ok(payload) => {
+            let valid_json = payload
+                .files
+                .iter()
+                .filter(|(name, _)| name.ends_with(".json"))
+                .all(|(_, content)| serde_json::from_str::<Value>(content).is_ok());
+            BackupVerification {
+                valid: valid_json,
+                encrypted,
+                contents_checked: true,
+                file_count: payload.files.len(),
+                workspace_count: payload.workspaces.len(),
+                message: if valid_json {
+                    format!("Backup is intact ({} files, {} workspaces)", payload.files.len(), payload.workspaces.len())
+                } else {
+                    "Backup is intact but contains an unparsable JSON file".to_string()
+                },
+            }
+        }
+        Err(e) => BackupVerification {
+            valid: false,
+            encrypted,
+            contents_checked: true,
+            file_count: 0,
+            workspace_count: 0,
+            message: e,
+        },
+    })
+}
+
+// ============ Selective Restore ============
+
+/// Sections that can be restored on their own
+const RESTORE_SECTIONS: &[&str] = &["providers", "channels", "agents", "mcps"];
+
+/// What restoring a section would change
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct RestorePreview {
+    pub section: String,
+    /// Config (or mcps.json) changes, secrets masked
+    pub changes: Vec<JsonChange>,
+    /// Workspace files that would be created or overwritten (agents section)
+    pub workspace_files: Vec<String>,
+}
+
+/// Reject backup-provided relative paths that could escape their directory
+fn safe_relative(rel: &str) -> bool {
+    !rel.is_empty() && !Path::new(rel).is_absolute() && !rel.split(['/', '\\']).any(|c| c == "..")
+}
+
+/// Rebuild the backed-up openclaw.json (with its $include files) in a temp dir and load it
+fn backup_config(payload: &BackupPayload) -> Result<Value, String> {
+    if !payload.files.contains_key("openclaw.json") {
+        return Err("Backup does not contain openclaw.json".to_string());
+    }
+    // The files hold secrets: stage them in an owner-only dir of the Manager, removed on drop
+    // (also when staging fails halfway)
+    let data_dir = platform::get_manager_data_dir();
+    std::fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create the Manager data directory: {}", e))?;
+    let temp_dir = tempfile::Builder::new()
+        .prefix("restore-")
+        .tempdir_in(&data_dir)
+        .map_err(|e| format!("Failed to create a staging directory: {}", e))?;
+    for (rel, content) in &payload.files {
+        if !safe_relative(rel) {
+            continue;
+        }
+        let path = temp_dir.path().join(rel);
+        file::write_file(&path.to_string_lossy(), content)
+            .map_err(|e| format!("Failed to stage backup file {}: {}", rel, e))?;
+    }
+    config_include::load(&temp_dir.path().join("openclaw.json").to_string_lossy()).map(|(config, _)| config)
+}
+
+/// The openclaw.json inside a backup file's content; None when `content` is not a backup
+pub(crate) fn config_from_backup_content(content: &str, passphrase: Option<&str>) -> Result<Option<Value>, String> {
+    let is_backup = serde_json::from_str::<Value>(content)
+        .ok()
+        .and_then(|v| v.get("format").and_then(|f| f.as_str()).map(|f| f == BACKUP_FORMAT))
+        .unwrap_or(false);
+    if !is_backup {
+        return Ok(None);
+    }
+    let payload = open_backup(parse_backup_file(content)?, passphrase)?;
+    backup_config(&payload).map(Some)
+}
+
+/// Object at `pointer`, created (with parents) if missing
+fn ensure_object<'a>(root: &'a mut Value, pointer: &str) -> &'a mut Map<String, Value> {
+    let mut node = root;
+    for token in pointer.split('/').skip(1) {
+        if !node.is_object() {
+            *node = json!({});
+        }
+        node = node
+            .as_object_mut()
+            .map(|m| m.entry(token.to_string()).or_insert_with(|| json!({})))
+            .unwrap();
+    }
+    if !node.is_object() {
+        *node = json!({});
+    }
+    node.as_object_mut().unwrap()
+}
+
+/// Copy every key of the backup object at `pointer` over the live one (live-only keys are kept)
+fn merge_keys_at(live: &mut Value, backup: &Value, pointer: &str) {
+    if let Some(source) = backup.pointer(pointer).and_then(|v| v.as_object()) {
+        let target = ensure_object(live, pointer);
+        for (k, v) in source {
+            target.insert(k.clone(), v.clone());
+        }
+    }
+}
+
+/// Merge agents.list by agent id (array or object form), then agents.defaults by key
+fn merge_agents(live: &mut Value, backup: &Value) {
+    merge_keys_at(live, backup, "/agents/defaults");
+
+    match (live.pointer("/agents/list").cloned(), backup.pointer("/agents/list")) {
+        (Some(Value::Array(mut live_list)), Some(Value::Array(backup_list))) => {
+            for agent in backup_list {
+                let id = agent.get("id").and_then(|v| v.as_str());
+                match live_list.iter_mut().find(|a| a.get("id").and_then(|v| v.as_str()) == id) {
+                    Some(existing) => *existing = agent.clone(),
+                    None => live_list.push(agent.clone()),
+                }
+            }
+            ensure_object(live, "/agents").insert("list".to_string(), Value::Array(live_list));
+        }
+        (Some(Value::Object(_)), Some(Value::Object(_))) => merge_keys_at(live, backup, "/agents/list"),
+        (_, Some(list)) => {
+            ensure_object(live, "/agents").insert("list".to_string(), list.clone());
+        }
+        _ => {}
+    }
+}
+
+/// Agent entries of agents.list (array or object form)
+fn agent_entries_mut(config: &mut Value) -> Vec<&mut Value> {
+    match config.pointer_mut("/agents/list") {
+        Some(Value::Array(arr)) => arr.iter_mut().collect(),
+        Some(Value::Object(map)) => map.values_mut().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Absolute path inside the config directory, without `..`
+fn inside_config_dir(path: &str) -> bool {
+    let path = Path::new(path);
+    path.is_absolute()
+        && !path.components().any(|c| c == std::path::Component::ParentDir)
+        && path.starts_with(platform::get_config_dir())
+}
+
+/// A backup may only point an agent's workspace into the config directory; any other path
+/// it brings is replaced by the live value (or dropped, giving the agent its default workspace)
+/// so restoring cannot make the Manager or the gateway write elsewhere
+fn confine_workspaces(merged: &mut Value, live: &Value) {
+    let live_workspaces: HashMap<String, Value> = agent_workspace_values(live);
+    for agent in agent_entries_mut(merged) {
+        let Some(id) = agent.get("id").and_then(|v| v.as_str()).map(String::from) else {
+            continue;
+        };
+        let Some(obj) = agent.as_object_mut() else {
+            continue;
+        };
+        let Some(workspace) = obj.get("workspace").cloned() else {
+            continue;
+        };
+        if live_workspaces.get(&id) == Some(&workspace)
+            || workspace.as_str().map(inside_config_dir).unwrap_or(false)
+        {
+            continue;
+        }
+        warn!(
+            "[Restore] Ignoring workspace {} of agent '{}' from the backup (outside the config directory)",
+            workspace, id
+        );
+        match live_workspaces.get(&id) {
+            Some(value) => obj.insert("workspace".to_string(), value.clone()),
+            None => obj.remove("workspace"),
+        };
+    }
+}
+
+/// Agent id -> its `workspace` value, for agents that set one
+fn agent_workspace_values(config: &Value) -> HashMap<String, Value> {
+    let entries: Vec<&Value> = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr.iter().collect(),
+        Some(Value::Object(map)) => map.values().collect(),
+        _ => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter_map(|agent| {
+            let id = agent.get("id").and_then(|v| v.as_str())?;
+            Some((id.to_string(), agent.get("workspace")?.clone()))
+        })
+        .collect()
+}
+
+/// Live config with one section merged in from the backup
+fn merged_config(section: &str, live: &Value, backup: &Value) -> Value {
+    let mut merged = live.clone();
+    match section {
+        "providers" => merge_keys_at(&mut merged, backup, "/models/providers"),
+        "channels" => merge_keys_at(&mut merged, backup, "/channels"),
+        "agents" => {
+            merge_agents(&mut merged, backup);
+            confine_workspaces(&mut merged, live);
+        }
+        _ => {}
+    }
+    merged
+}
+
+/// Workspace files (target path, content) the agents section would write. Targets come from
+/// the merged config (see confine_workspaces), never from the directory recorded in the backup.
+fn workspace_writes(payload: &BackupPayload, merged: &Value) -> Vec<(PathBuf, String)> {
+    let dirs: HashMap<String, PathBuf> = agent_workspaces(merged).into_iter().collect();
+    let mut writes = Vec::new();
+    for (id, ws) in &payload.workspaces {
+        let Some(dir) = dirs.get(id) else {
+            warn!("[Restore] Skipping workspace of '{}' (not an agent in the config)", id);
+            continue;
+        };
+        for (rel, content) in &ws.files {
+            if !safe_relative(rel) {
+                continue;
+            }
+            let path = dir.join(rel);
+            if std::fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                writes.push((path, content.clone()));
+            }
+        }
+    }
+    writes
+}
+
+/// MCP server configs by name (mcps.json)
+type McpConfigs = HashMap<String, MCPConfig>;
+
+/// Live mcps.json and the result of merging the backed-up servers into it
+fn merged_mcps(payload: &BackupPayload) -> Result<(McpConfigs, McpConfigs), String> {
+    let content = payload.files.get("mcps.json").ok_or("Backup does not contain mcps.json")?;
+    let backup: McpConfigs =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse backed-up mcps.json: {}", e))?;
+    let live = load_mcp_config_file()?;
+    let mut merged = live.clone();
+    merged.extend(backup);
+    Ok((live, merged))
+}
+
+fn build_restore_preview(payload: &BackupPayload, section: &str) -> Result<RestorePreview, String> {
+    if !RESTORE_SECTIONS.contains(&section) {
+        return Err(format!("Unknown section '{}', expected one of: {}", section, RESTORE_SECTIONS.join(", ")));
+    }
+
+    if section == "mcps" {
+        let (live, merged) = merged_mcps(payload)?;
+        let to_value = |m: &McpConfigs| serde_json::to_value(m).unwrap_or_default();
+        return Ok(RestorePreview {
+            section: section.to_string(),
+            changes: json_diff::diff_masked(&to_value(&live), &to_value(&merged)),
+            workspace_files: Vec::new(),
+        });
+    }
+
+    let live = load_openclaw_config()?;
+    let merged = merged_config(section, &live, &backup_config(payload)?);
+    let workspace_files = if section == "agents" {
+        workspace_writes(payload, &merged).into_iter().map(|(p, _)| p.display().to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    Ok(RestorePreview {
+        section: section.to_string(),
+        changes: json_diff::diff_masked(&live, &merged),
+        workspace_files,
+    })
+}
+
+/// Show what restoring one section of a backup would change
+#[command]
+#[specta::specta]
+pub async fn preview_restore_section(
+    backup_id: String,
+    section: String,
+    passphrase: Option<String>,
+) -> Result<RestorePreview, String> {
+    info!("[Restore] Previewing section '{}' from {}", section, backup_id);
+    let payload = load_backup(&backup_id, passphrase.as_deref())?;
+    build_restore_preview(&payload, &section)
+}
+
+/// Restore one section of a backup (providers, channels, agents incl. workspace files, or mcps),
+/// merging it into the live config. Entries that exist only in the live config are kept.
+#[command]
+#[specta::specta]
+pub async fn restore_backup_section(
+    backup_id: String,
+    section: String,
+    passphrase: Option<String>,
+) -> Result<RestorePreview, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Mcps], "restore_backup_section").await;
+    info!("[Restore] Restoring section '{}' from {}", section, backup_id);
+    telemetry::record_feature("restore_backup_section");
+    let payload = load_backup(&backup_id, passphrase.as_deref())?;
+    let preview = build_restore_preview(&payload, &section)?;
+
+    if section == "mcps" {
+        let (_, merged) = merged_mcps(&payload)?;
+        save_mcp_config_file(&merged)?;
+    } else {
+        let live = load_openclaw_config()?;
+        let merged = merged_config(&section, &live, &backup_config(&payload)?);
+        save_openclaw_config(&merged, "restore_backup_section")?;
+
+        if section == "agents" {
+            for (path, content) in workspace_writes(&payload, &merged) {
+                file::write_file(&path.to_string_lossy(), &content)
+                    .map_err(|e| format!("Failed to restore {}: {}", path.display(), e))?;
+            }
+        }
+    }
+
+    info!(
+        "[Restore] Section '{}' restored ({} config changes, {} workspace files)",
+        section,
+        preview.changes.len(),
+        preview.workspace_files.len()
+    );
+    Ok(preview)
+}
@@ -0,0 +1,263 @@
+use crate::commands::config::load_openclaw_config;
+use crate::commands::contacts::{load_contacts, resolve_peer};
+use crate::commands::diagnostics::extract_json_from_output;
+use crate::utils::chat::{self, DeliveryResult, InvokeOutcome, MessageTarget};
+use crate::utils::shell;
+use log::{info, warn};
+use std::process::Command;
+use tauri::command;
+
+/// Gateway port and token from openclaw.json
+fn gateway_endpoint() -> Result<(u16, String), String> {
+    let config = load_openclaw_config()?;
+    let port = config
+        .pointer("/gateway/port")
+        .and_then(|v| v.as_u64())
+        .map(|p| p as u16)
+        .unwrap_or(18789);
+    let token = config
+        .pointer("/gateway/auth/token")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    Ok((port, token))
+}
+
+/// POST a tool call to the gateway, returning the HTTP status (0 when nothing answered) and body
+fn invoke_gateway_tool(
+    port: u16,
+    token: &str,
+    body: &serde_json::Value,
+) -> Result<(u16, String), String> {
+    let url = format!("http://127.0.0.1:{}/tools/invoke", port);
+    let payload = body.to_string();
+    let mut cmd = Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args([
+        "-s",
+        "--max-time",
+        "60",
+        "-X",
+        "POST",
+        "-H",
+        "Content-Type: application/json",
+    ]);
+    let _headers =
+        shell::curl_secret_headers(&mut cmd, &[format!("Authorization: Bearer {}", token)])?;
+    cmd.args(["-d", &payload, "-w", "\n%{http_code}", &url]);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    // curl prints the status on the last line (000 when nothing answered)
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", stdout.as_str()));
+    Ok((status.trim().parse().unwrap_or(0), body.to_string()))
+}
+
+/// Send one part through `openclaw message send`, for gateways without the HTTP tools API
+fn send_part_via_cli(
+    target: &MessageTarget,
+    text: &str,
+    media: Option<&str>,
+) -> Result<Option<String>, String> {
+    let mut args = vec![
+        "message",
+        "send",
+        "--channel",
+        target.channel.as_str(),
+        "--target",
+        target.target.as_str(),
+    ];
+    if let Some(account) = target.account_id.as_deref().filter(|a| !a.is_empty()) {
+        args.extend(["--account", account]);
+    }
+    if !text.is_empty() {
+        args.extend(["--message", text]);
+    }
+    if let Some(media) = media {
+        args.extend(["--media", media]);
+    }
+    args.push("--json");
+    let output = shell::run_openclaw(&args)?;
+    match extract_json_from_output(&output)
+        .and_then(|j| serde_json::from_str::<serde_json::Value>(&j).ok())
+    {
+        Some(json) => {
+            let failed = json.get("ok").and_then(|v| v.as_bool()) == Some(false)
+                || json.get("success").and_then(|v| v.as_bool()) == Some(false);
+            if failed {
+                return Err(json
+                    .get("error")
+                    .and_then(|e| {
+                        e.as_str()
+                            .map(String::from)
+                            .or_else(|| e.get("message").and_then(|m| m.as_str()).map(String::from))
+                    })
+                    .unwrap_or_else(|| "Message send failed".to_string()));
+            }
+            Ok(chat::find_message_id(&json))
+        }
+        None if output.to_lowercase().contains("error")
+            || output.to_lowercase().contains("failed") =>
+        {
+            Err(output.trim().to_string())
+        }
+        None => Ok(None),
+    }
+}
+
+/// Check attachments before anything is sent: URLs are passed on, local files must exist
+/// and stay under the size cap
+fn validate_attachments(attachments: &[String]) -> Result<(), String> {
+    for attachment in attachments {
+        if attachment.starts_with("http://") || attachment.starts_with("https://") {
+            continue;
+        }
+        let meta = std::fs::metadata(attachment)
+            .map_err(|e| format!("Attachment {}: {}", attachment, e))?;
+        if !meta.is_file() {
+            return Err(format!("Attachment {} is not a file", attachment));
+        }
+        if meta.len() > chat::MAX_ATTACHMENT_MB * 1024 * 1024 {
+            return Err(format!(
+                "Attachment {} is larger than {} MB",
+                attachment,
+                chat::MAX_ATTACHMENT_MB
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Send text and attachments to one target, through the gateway API when it offers the
+/// message tool and `openclaw message send` otherwise
+fn deliver(
+    endpoint: &(u16, String),
+    target: MessageTarget,
+    text: &str,
+    attachments: &[String],
+) -> DeliveryResult {
+    let target = MessageTarget {
+        target: resolve_peer(&target.channel, &target.target),
+        ..target
+    };
+    let mut result = DeliveryResult {
+        target,
+        delivered: false,
+        message_ids: Vec::new(),
+        via: "gateway".to_string(),
+        error: None,
+    };
+    for (part_text, media) in chat::message_parts(text, attachments) {
+        let sent = if result.via == "gateway" {
+            let body = chat::invoke_body(&result.target, part_text, media);
+            match invoke_gateway_tool(endpoint.0, &endpoint.1, &body)
+                .map(|(status, body)| chat::parse_invoke_response(status, &body))
+            {
+                Ok(InvokeOutcome::Sent(id)) => Ok(id),
+                Ok(InvokeOutcome::Unavailable) => {
+                    info!("[Chat] Gateway has no message tool over HTTP, using the CLI");
+                    result.via = "cli".to_string();
+                    send_part_via_cli(&result.target, part_text, media)
+                }
+                Ok(InvokeOutcome::Failed(e)) | Err(e) => Err(e),
+            }
+        } else {
+            send_part_via_cli(&result.target, part_text, media)
+        };
+        match sent {
+            Ok(id) => result.message_ids.extend(id),
+            Err(e) => {
+                warn!(
+                    "[Chat] Send to {}:{} failed: {}",
+                    result.target.channel, result.target.target, e
+                );
+                result.error = Some(e);
+                return result;
+            }
+        }
+    }
+    result.delivered = true;
+    result
+}
+
+fn check_message(text: &str, attachments: &[String]) -> Result<(), String> {
+    if text.trim().is_empty() && attachments.is_empty() {
+        return Err("Message needs text or an attachment".to_string());
+    }
+    validate_attachments(attachments)
+}
+
+/// Send a message as a bot: `target` is a peer id or contact name on `channel`, `account` the
+/// bot account (None for the channel's default), `attachments` local paths or URLs
+#[command]
+#[specta::specta]
+pub async fn send_channel_message(
+    channel: String,
+    account: Option<String>,
+    target: String,
+    text: String,
+    attachments: Option<Vec<String>>,
+) -> Result<DeliveryResult, String> {
+    let attachments = attachments.unwrap_or_default();
+    check_message(&text, &attachments)?;
+    info!(
+        "[Chat] Sending message to {}:{} ({} attachment(s))",
+        channel,
+        target,
+        attachments.len()
+    );
+    let endpoint = gateway_endpoint()?;
+    let target = MessageTarget {
+        channel,
+        account_id: account,
+        target,
+        label: None,
+    };
+    Ok(deliver(&endpoint, target, &text, &attachments))
+}
+
+/// Groups each bot account is configured for, plus group and channel contacts
+#[command]
+#[specta::specta]
+pub async fn list_broadcast_targets() -> Result<Vec<MessageTarget>, String> {
+    Ok(chat::group_targets(
+        &load_openclaw_config()?,
+        &load_contacts(),
+    ))
+}
+
+/// Send the same announcement to several targets, one after another; a failed target does
+/// not stop the others
+#[command]
+#[specta::specta]
+pub async fn broadcast_channel_message(
+    targets: Vec<MessageTarget>,
+    text: String,
+    attachments: Option<Vec<String>>,
+) -> Result<Vec<DeliveryResult>, String> {
+    let attachments = attachments.unwrap_or_default();
+    check_message(&text, &attachments)?;
+    if targets.is_empty() {
+        return Err("Choose at least one target".to_string());
+    }
+    info!("[Chat] Broadcasting to {} target(s)", targets.len());
+    let endpoint = gateway_endpoint()?;
+    let results: Vec<DeliveryResult> = targets
+        .into_iter()
+        .map(|target| deliver(&endpoint, target, &text, &attachments))
+        .collect();
+    let failed = results.iter().filter(|r| !r.delivered).count();
+    if failed > 0 {
+        warn!(
+            "[Chat] Broadcast failed for {} of {} target(s)",
+            failed,
+            results.len()
+        );
+    }
+    Ok(results)
+}
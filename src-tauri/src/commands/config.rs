@@ -1,43 +1,212 @@
 use crate::models::{
-    AIConfigOverview, ChannelConfig, ConfiguredModel, ConfiguredProvider,
-    MCPConfig, ModelConfig, OfficialProvider, SuggestedModel,
+    AIConfigOverview, AzureProviderOptions, ChannelConfig, ConfiguredModel, ConfiguredProvider,
+    DockerMCPConfig, MCPConfig, MCPInstallInfo, ModelConfig, OfficialProvider, SuggestedModel,
 };
-use crate::utils::{file, platform, shell, log_sanitizer};
+use crate::commands::{diagnostics, history, snapshots, trash};
+use crate::commands::locks::{self, ConfigResource};
+use crate::events::{self, AlertLevel, ManagerEvent};
+use crate::state::{self, ConfigCacheStatus};
+use crate::utils::{clipboard, config_drift, config_include, config_schema, config_watch, contacts, file, gateway_token, gemini, json_diff, key_expiry, node_pm, platform, plugin_review, prompt_cost, provider_headers, secrets_scan, session_replay, shell, log_sanitizer};
+use crate::utils::prompt_cost::PromptCostEstimate;
+use crate::utils::config_schema::SchemaError;
+use crate::utils::key_expiry::KeyExpiry;
+use crate::utils::plugin_review::PluginReview;
+use crate::utils::secrets_scan::SecretFinding;
+use crate::utils::setting_location::{self, SettingLocation};
+use crate::utils::task_pool::{self, BulkItemResult};
 use log::{debug, error, info, warn};
 use serde_json::{json, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::command;
 
+/// Draft copy of openclaw.json used while a draft is open
+fn get_draft_file_path() -> String {
+    platform::path_string(&platform::join_path(platform::get_manager_data_dir(), &["openclaw.draft.json"]))
+}
+
+/// Whether a configuration draft is open (mutating commands then write to the draft)
+fn draft_active() -> bool {
+    file::file_exists(&get_draft_file_path())
+}
+
+/// Path that config reads/writes go to: the draft if open, the live file otherwise
+fn get_active_config_path() -> String {
+    if draft_active() {
+        get_draft_file_path()
+    } else {
+        platform::get_config_file_path()
+    }
+}
+
 /// Load openclaw.json configuration
-fn load_openclaw_config() -> Result<Value, String> {
-    let config_path = platform::get_config_file_path();
+pub(crate) fn load_openclaw_config() -> Result<Value, String> {
+    let config_path = get_active_config_path();
+    read_config_file(&config_path).or_else(|e| recover_config_file(&config_path, e))
+}
 
-    if !file::file_exists(&config_path) {
-        return Ok(json!({}));
+/// Read and parse a configuration file, resolving `$include` directives. Served from the
+/// AppState cache while the file and its includes are unchanged on disk.
+fn read_config_file(config_path: &str) -> Result<Value, String> {
+    let cache = &state::app_state().config_cache;
+    if let Some(config) = cache.get(config_path) {
+        return Ok(config);
+    }
+    let read = cache.stamp(config_path);
+    let (config, sources) = config_include::load(config_path)?;
+    let files: Vec<String> = sources.into_iter().map(|s| s.file).collect();
+    cache.insert(read, &config, &files);
+    Ok(config)
+}
+
+/// Damaged config files the user was already alerted about (one alert per damage)
+static DAMAGE_REPORTED: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+/// A usable `.bak` copy of a config file that no longer parses (e.g. after a crash mid-write).
+/// Errors in `$include` files, or without a usable backup, give None.
+fn config_backup_for(config_path: &str) -> Option<String> {
+    let parses = |content: &str| serde_json::from_str::<Value>(content.trim_start_matches('\u{FEFF}')).is_ok();
+    match file::read_file(config_path) {
+        Ok(content) if !parses(&content) => {}
+        _ => return None,
     }
+    file::read_file(&file::backup_path(config_path)).ok().filter(|backup| parses(backup))
+}
 
-    let content =
-        file::read_file(&config_path).map_err(|e| format!("Failed to read configuration file: {}", e))?;
+/// A config read failed: when the last good copy (`.bak`, kept by every save) could replace the
+/// file, alert the user and offer `restore_config_from_backup`. Reads never write the file.
+fn recover_config_file(config_path: &str, error: String) -> Result<Value, String> {
+    if config_backup_for(config_path).is_none() {
+        return Err(error);
+    }
+    let first_report = DAMAGE_REPORTED
+        .lock()
+        .map(|mut reported| {
+            let new = !reported.iter().any(|p| p == config_path);
+            if new {
+                reported.push(config_path.to_string());
+            }
+            new
+        })
+        .unwrap_or(false);
+    if first_report {
+        warn!("[Config] {} could not be parsed ({}), a backup is available", config_path, error);
+        events::emit(ManagerEvent::alert(
+            "config-damaged",
+            AlertLevel::Error,
+            "The configuration file is damaged. Restore it from its last backup?",
+            &json!({ "path": config_path, "backupPath": file::backup_path(config_path), "error": error }),
+        ));
+    }
+    Err(format!("{} (a backup is available, restore it from Settings)", error))
+}
 
-    // Strip UTF-8 BOM if present (Windows editors sometimes add this)
-    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+/// Put the last good copy of a damaged openclaw.json (or draft) back, keeping the broken file
+/// next to it
+#[command]
+#[specta::specta]
+pub async fn restore_config_from_backup() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "restore_config_from_backup").await;
+    let config_path = get_active_config_path();
+    let backup = config_backup_for(&config_path)
+        .ok_or_else(|| "The configuration file is not damaged or has no usable backup".to_string())?;
+    ensure_writable()?;
+
+    let corrupt_path = format!("{}.corrupt-{}", config_path, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    if let Err(e) = std::fs::copy(&config_path, &corrupt_path) {
+        warn!("[Config] Failed to keep the broken config as {}: {}", corrupt_path, e);
+    }
+    file::write_file_atomic(&config_path, &backup, false)
+        .map_err(|e| format!("Failed to restore {} from its backup: {}", config_path, e))?;
+    if let Ok(mut reported) = DAMAGE_REPORTED.lock() {
+        reported.retain(|p| p != &config_path);
+    }
+    warn!("[Config] {} restored from {}", config_path, file::backup_path(&config_path));
+    events::emit(ManagerEvent::alert(
+        "config-recovered",
+        AlertLevel::Warning,
+        "The configuration file has been restored from its last backup",
+        &json!({ "path": config_path, "corruptCopy": corrupt_path }),
+    ));
+    Ok(format!("Configuration restored, the damaged file was kept as {}", corrupt_path))
+}
+
+/// Config cache version and hit counters, for debugging
+#[command]
+#[specta::specta]
+pub async fn get_config_cache_status() -> Result<ConfigCacheStatus, String> {
+    Ok(state::app_state().config_cache.status())
+}
 
-    serde_json::from_str(content).map_err(|e| format!("Failed to parse configuration file: {}", e))
+/// Save openclaw.json configuration. `command` names the Manager command making the
+/// change for the config history (draft edits are recorded once, on commit).
+pub(crate) fn save_openclaw_config(config: &Value, command: &str) -> Result<(), String> {
+    if draft_active() {
+        locks::warn_if_unlocked(ConfigResource::OpenClaw, command);
+        return write_config_file(&get_draft_file_path(), config);
+    }
+    save_live_openclaw_config(config, command)
 }
 
-/// Save openclaw.json configuration
-fn save_openclaw_config(config: &Value) -> Result<(), String> {
+/// Load the live openclaw.json, ignoring an open draft (for emergency actions)
+pub(crate) fn load_live_openclaw_config() -> Result<Value, String> {
     let config_path = platform::get_config_file_path();
+    read_config_file(&config_path).or_else(|e| recover_config_file(&config_path, e))
+}
 
-    let content =
-        serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+/// Save straight to the live openclaw.json, bypassing an open draft
+pub(crate) fn save_live_openclaw_config(config: &Value, command: &str) -> Result<(), String> {
+    locks::warn_if_unlocked(ConfigResource::OpenClaw, command);
+    let config_path = platform::get_config_file_path();
+    let previous = read_config_file(&config_path).unwrap_or_else(|_| json!({}));
+    write_config_file(&config_path, config)?;
+    history::record_config_change(command, &previous, config);
+    Ok(())
+}
 
-    file::write_file(&config_path, &content).map_err(|e| format!("Failed to write configuration file: {}", e))
+/// Refuse writes while the Manager is in read-only mode
+fn ensure_writable() -> Result<(), String> {
+    match diagnostics::read_only_reason() {
+        Some(reason) => Err(format!(
+            "Manager is in read-only mode ({}). Fix the permissions and run the write access check again",
+            reason
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Format a write failure, switching to read-only mode on permission errors
+fn write_error(what: &str, path: &str, e: std::io::Error) -> String {
+    if diagnostics::is_permission_error(&e) {
+        diagnostics::enter_read_only_mode(&format!("{} is not writable: {}", path, e));
+    }
+    format!("Failed to write {}: {}", what, e)
+}
+
+/// Write a configuration file, routing keys that came from `$include` files back to them
+fn write_config_file(config_path: &str, config: &Value) -> Result<(), String> {
+    ensure_writable()?;
+    // Only a config that still parses is worth keeping as the `.bak` recovery copy
+    let (keep_backup, sources) = match config_include::load(config_path) {
+        Ok((_, sources)) => (true, sources),
+        Err(_) => (false, Vec::new()),
+    };
+
+    let result = config_include::split(config_path, config, &sources).into_iter().try_for_each(|(path, value)| {
+        let content =
+            serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+
+        // Atomic, so the gateway never sees a partial file and a crash cannot corrupt it
+        file::write_file_atomic(&path, &content, keep_backup).map_err(|e| write_error("configuration file", &path, e))
+    });
+    // Even a partial write changes what is on disk
+    state::app_state().config_cache.invalidate();
+    events::emit(ManagerEvent::ConfigChanged { scope: "openclaw".to_string(), path: config_path.to_string(), detail: None });
+    result
 }
 
 /// Load manager.json configuration (manager-specific settings)
-fn load_manager_config() -> Result<Value, String> {
+pub(crate) fn load_manager_config() -> Result<Value, String> {
     let config_path = platform::get_manager_config_file_path();
 
     if !file::file_exists(&config_path) {
@@ -54,17 +223,23 @@ fn load_manager_config() -> Result<Value, String> {
 }
 
 /// Save manager.json configuration
-fn save_manager_config(config: &Value) -> Result<(), String> {
+pub(crate) fn save_manager_config(config: &Value) -> Result<(), String> {
+    locks::warn_if_unlocked(ConfigResource::Manager, "save_manager_config");
     let config_path = platform::get_manager_config_file_path();
 
     let content =
         serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize manager configuration: {}", e))?;
 
-    file::write_file(&config_path, &content).map_err(|e| format!("Failed to write manager configuration file: {}", e))
+    ensure_writable()?;
+    file::write_file_atomic(&config_path, &content, false)
+        .map_err(|e| write_error("manager configuration file", &config_path, e))?;
+    events::emit(ManagerEvent::ConfigChanged { scope: "manager".to_string(), path: config_path, detail: None });
+    Ok(())
 }
 
 /// Get complete configuration
 #[command]
+#[specta::specta]
 pub async fn get_config() -> Result<Value, String> {
     info!("[Get Config] Reading openclaw.json configuration...");
     let result = load_openclaw_config();
@@ -77,13 +252,16 @@ pub async fn get_config() -> Result<Value, String> {
 
 /// Save configuration
 #[command]
+#[specta::specta]
 pub async fn save_config(config: Value) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_config").await;
     info!("[Save Config] Saving openclaw.json configuration...");
     debug!(
         "[Save Config] Configuration content: {}",
         log_sanitizer::sanitize(&serde_json::to_string_pretty(&config).unwrap_or_default())
     );
-    match save_openclaw_config(&config) {
+    check_config_schema(&config)?;
+    match save_openclaw_config(&config, "save_config") {
         Ok(_) => {
             info!("[Save Config] Configuration saved successfully");
             Ok("Configuration saved".to_string())
@@ -95,8 +273,48 @@ pub async fn save_config(config: Value) -> Result<String, String> {
     }
 }
 
+/// Share of the existing keys a raw save may remove without `allow_removals`
+const RAW_SAVE_MAX_REMOVED_PERCENT: f64 = 20.0;
+
+/// Save the full openclaw.json text from the advanced JSON editor. The text must parse and pass
+/// `openclaw config validate`, the current config is backed up first, and a save that removes
+/// more than 20% of the existing keys is refused unless `allow_removals` is set.
+#[command]
+#[specta::specta]
+pub async fn save_raw_config(text: String, allow_removals: Option<bool>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_raw_config").await;
+    info!("[Save Config] Saving raw openclaw.json text ({} bytes)...", text.len());
+
+    let config: Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid JSON at line {}, column {}: {}", e.line(), e.column(), e))?;
+    if !config.is_object() {
+        return Err("The configuration must be a JSON object".to_string());
+    }
+
+    let current = load_openclaw_config()?;
+    let (removed, total) = json_diff::removed_keys(&current, &config);
+    let removed_percent = if total == 0 { 0.0 } else { removed as f64 * 100.0 / total as f64 };
+    if removed_percent > RAW_SAVE_MAX_REMOVED_PERCENT && !allow_removals.unwrap_or(false) {
+        warn!("[Save Config] Raw save would remove {} of {} keys, refused", removed, total);
+        return Err(format!(
+            "This save removes {} of {} keys ({:.0}%). Confirm the removal to save anyway",
+            removed, total, removed_percent
+        ));
+    }
+
+    validate_openclaw_config(text)
+        .await
+        .map_err(|e| format!("The configuration does not pass validation: {}", e))?;
+
+    let backup = crate::commands::backup::create_backup(None).await?;
+    save_openclaw_config(&config, "save_raw_config")?;
+    info!("[Save Config] Raw configuration saved ({} key(s) removed, backup {})", removed, backup.id);
+    Ok(format!("Configuration saved, previous config backed up as {}", backup.id))
+}
+
 /// Get environment variable value
 #[command]
+#[specta::specta]
 pub async fn get_env_value(key: String) -> Result<Option<String>, String> {
     info!("[Get Env] Reading environment variable: {}", key);
     let env_path = platform::get_env_file_path();
@@ -114,6 +332,7 @@ pub async fn get_env_value(key: String) -> Result<Option<String>, String> {
 
 /// Save environment variable value
 #[command]
+#[specta::specta]
 pub async fn save_env_value(key: String, value: String) -> Result<String, String> {
     info!("[Save Env] Saving environment variable: {}", key);
     let env_path = platform::get_env_file_path();
@@ -131,29 +350,137 @@ pub async fn save_env_value(key: String, value: String) -> Result<String, String
     }
 }
 
-// ============ Gateway Token Commands ============
+/// Seconds before a copied secret is cleared from the clipboard
+const CLIPBOARD_CLEAR_SECS: u64 = 30;
 
-/// Generate random token
-fn generate_token() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
+/// Copy a secret to the clipboard from the backend, so the plaintext never crosses IPC.
+/// `path` is a dotted config path (e.g. "models.providers.openai.apiKey") or `env:KEY`
+/// for values stored in ~/.openclaw/env.
+#[command]
+#[specta::specta]
+pub async fn copy_secret_to_clipboard(path: String) -> Result<String, String> {
+    info!("[Clipboard] Copying secret at {} to clipboard", path);
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-
-    // Generate token using timestamp and random number
-    let random_part: u64 = (timestamp as u64) ^ 0x5DEECE66Du64;
-    format!("{:016x}{:016x}{:016x}",
-        random_part,
-        random_part.wrapping_mul(0x5DEECE66Du64),
-        timestamp as u64
-    )
+    let secret = if let Some(key) = path.strip_prefix("env:") {
+        file::read_env_value(&platform::get_env_file_path(), key)
+    } else {
+        let config = load_openclaw_config()?;
+        let pointer = format!("/{}", path.trim_start_matches('/').replace('.', "/"));
+        // A `${secret:...}` / `${OPENCLAW_SECRET_...}` reference is copied as the value behind it
+        config
+            .pointer(&pointer)
+            .and_then(|v| v.as_str())
+            .and_then(crate::commands::placeholders::resolve_value)
+    }
+    .filter(|s| !s.is_empty())
+    .ok_or(format!("No secret found at {}", path))?;
+
+    clipboard::set_text(&secret)?;
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(CLIPBOARD_CLEAR_SECS));
+        clipboard::clear_if_unchanged(&secret);
+        debug!("[Clipboard] Auto-clear check done");
+    });
+
+    Ok(format!("Copied to clipboard, it will be cleared in {} seconds", CLIPBOARD_CLEAR_SECS))
+}
+
+// ============ Config Notes ============
+
+/// A free-text note attached to a config location (stored in manager.json `configNotes`,
+/// since openclaw.json cannot hold comments)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ConfigNote {
+    /// JSON pointer into openclaw.json, e.g. /channels/telegram/groupPolicy
+    pub pointer: String,
+    pub text: String,
+    pub updated_at: Option<String>,
+    /// The annotated setting no longer exists in the config
+    pub stale: bool,
+}
+
+/// Configuration together with its notes
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AnnotatedConfig {
+    pub config: Value,
+    pub notes: Vec<ConfigNote>,
+}
+
+fn load_config_notes(config: &Value) -> Vec<ConfigNote> {
+    let manager_config = load_manager_config().unwrap_or_else(|_| json!({}));
+    let mut notes: Vec<ConfigNote> = manager_config
+        .get("configNotes")
+        .and_then(|v| v.as_object())
+        .map(|map| {
+            map.iter()
+                .map(|(pointer, note)| ConfigNote {
+                    pointer: pointer.clone(),
+                    text: note.get("text").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    updated_at: note.get("updatedAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    stale: config.pointer(pointer).is_none(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    notes.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    notes
+}
+
+/// Get configuration with the notes attached to its settings
+#[command]
+#[specta::specta]
+pub async fn get_config_with_notes() -> Result<AnnotatedConfig, String> {
+    info!("[Config Notes] Reading configuration with notes...");
+    let config = load_openclaw_config()?;
+    let notes = load_config_notes(&config);
+    Ok(AnnotatedConfig { config, notes })
+}
+
+/// Attach a note to a config location; empty text removes the note
+#[command]
+#[specta::specta]
+pub async fn set_config_note(pointer: String, text: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_config_note").await;
+    info!("[Config Notes] Setting note for {}", pointer);
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return Err("Pointer must be a JSON pointer starting with '/'".to_string());
+    }
+
+    let mut manager_config = load_manager_config()?;
+    if !manager_config.is_object() {
+        manager_config = json!({});
+    }
+    if !manager_config.get("configNotes").map(|v| v.is_object()).unwrap_or(false) {
+        manager_config["configNotes"] = json!({});
+    }
+    let notes = manager_config["configNotes"].as_object_mut().unwrap();
+
+    let text = text.trim();
+    if text.is_empty() {
+        notes.remove(&pointer);
+    } else {
+        notes.insert(
+            pointer.clone(),
+            json!({ "text": text, "updatedAt": chrono::Utc::now().to_rfc3339() }),
+        );
+    }
+
+    save_manager_config(&manager_config)?;
+    Ok(if text.is_empty() {
+        format!("Note removed from {}", pointer)
+    } else {
+        format!("Note saved for {}", pointer)
+    })
 }
 
+// ============ Gateway Token Commands ============
+
 /// Get or create Gateway Token
 #[command]
+#[specta::specta]
 pub async fn get_or_create_gateway_token() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "get_or_create_gateway_token").await;
     info!("[Gateway Token] Getting or creating Gateway Token...");
 
     let mut config = load_openclaw_config()?;
@@ -162,15 +489,16 @@ pub async fn get_or_create_gateway_token() -> Result<String, String> {
     if let Some(token) = config
         .pointer("/gateway/auth/token")
         .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
     {
-        if !token.is_empty() {
-            info!("[Gateway Token] Using existing Token");
-            return Ok(token.to_string());
-        }
+        // A weak legacy token is replaced by the token rotation task, not on read
+        info!("[Gateway Token] Using existing Token");
+        return Ok(token);
     }
 
     // Generate new token
-    let new_token = generate_token();
+    let new_token = gateway_token::generate_configured()?;
     info!("[Gateway Token] Generated new Token");
 
     // Ensure path exists
@@ -187,14 +515,40 @@ pub async fn get_or_create_gateway_token() -> Result<String, String> {
     config["gateway"]["mode"] = json!("local");
 
     // Save configuration
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, "get_or_create_gateway_token")?;
 
     info!("[Gateway Token] Token saved to configuration");
     Ok(new_token)
 }
 
+/// Make sure the live openclaw.json has a gateway token (generated on first start) and lets the
+/// Control UI connect with it. Called with the OpenClaw lock held, right before a gateway starts.
+pub(crate) fn ensure_gateway_auth() -> Result<(), String> {
+    let mut config = load_live_openclaw_config()?;
+    let has_token = config
+        .pointer("/gateway/auth/token")
+        .and_then(|v| v.as_str())
+        .is_some_and(|t| !t.is_empty());
+    let insecure_auth = config.pointer("/gateway/controlUi/allowInsecureAuth").and_then(|v| v.as_bool());
+    if has_token && insecure_auth == Some(true) {
+        return Ok(());
+    }
+    if !has_token {
+        info!("[Gateway Token] No Token found, generating one before start");
+        set_at_pointer(&mut config, "/gateway/auth/token", Some(json!(gateway_token::generate_configured()?)));
+        set_at_pointer(&mut config, "/gateway/auth/mode", Some(json!("token")));
+        if config.pointer("/gateway/mode").is_none() {
+            set_at_pointer(&mut config, "/gateway/mode", Some(json!("local")));
+        }
+    }
+    // Token-only auth for the Control UI (skips device pairing for the local Manager)
+    set_at_pointer(&mut config, "/gateway/controlUi/allowInsecureAuth", Some(json!(true)));
+    save_live_openclaw_config(&config, "ensure_gateway_auth")
+}
+
 /// Get Dashboard URL (with token)
 #[command]
+#[specta::specta]
 pub async fn get_dashboard_url() -> Result<String, String> {
     info!("[Dashboard URL] Getting Dashboard URL...");
 
@@ -208,22 +562,13 @@ pub async fn get_dashboard_url() -> Result<String, String> {
 /// Repair device token mismatch by deleting stale identity and paired device files.
 /// After calling this, the gateway should be restarted to regenerate fresh device identity.
 #[command]
+#[specta::specta]
 pub async fn repair_device_token() -> Result<String, String> {
     info!("[Device Token Repair] Starting device token repair...");
 
     let config_dir = platform::get_config_dir();
-    let identity_file = format!(
-        "{}{}identity{}device.json",
-        config_dir,
-        std::path::MAIN_SEPARATOR,
-        std::path::MAIN_SEPARATOR
-    );
-    let paired_file = format!(
-        "{}{}devices{}paired.json",
-        config_dir,
-        std::path::MAIN_SEPARATOR,
-        std::path::MAIN_SEPARATOR
-    );
+    let identity_file = platform::path_string(&platform::join_path(&config_dir, &["identity", "device.json"]));
+    let paired_file = platform::path_string(&platform::join_path(&config_dir, &["devices", "paired.json"]));
 
     let mut deleted = Vec::new();
 
@@ -256,12 +601,7 @@ pub async fn repair_device_token() -> Result<String, String> {
     }
 
     // Delete identity/device-auth.json (stale device auth token)
-    let device_auth_file = format!(
-        "{}{}identity{}device-auth.json",
-        config_dir,
-        std::path::MAIN_SEPARATOR,
-        std::path::MAIN_SEPARATOR
-    );
+    let device_auth_file = platform::path_string(&platform::join_path(&config_dir, &["identity", "device-auth.json"]));
     match std::fs::remove_file(&device_auth_file) {
         Ok(_) => {
             info!("[Device Token Repair] Deleted: {}", device_auth_file);
@@ -284,10 +624,472 @@ pub async fn repair_device_token() -> Result<String, String> {
     }
 }
 
+// ============ Gateway Token Rotation ============
+
+/// Rotations kept in the history
+const TOKEN_ROTATION_HISTORY: usize = 20;
+
+/// One replacement of the shared gateway token
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRotationRecord {
+    pub at: String,
+    /// "scheduled", "manual" or "weak-token"
+    pub reason: String,
+    /// Last characters of the replaced and the new token
+    pub old_token_hint: String,
+    pub new_token_hint: String,
+    pub restarted: bool,
+    /// The restarted gateway refused the old token (None when it could not be checked)
+    pub old_token_rejected: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// Automatic rotation of gateway.auth.token (manager.json -> tokenRotation)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TokenRotationSettings {
+    pub enabled: bool,
+    pub every_days: u32,
+    pub last_rotated_at: Option<String>,
+    /// Random bytes in generated tokens (hex-encoded, so twice as many characters)
+    pub token_bytes: u32,
+    /// Most recent first
+    pub history: Vec<TokenRotationRecord>,
+}
+
+impl Default for TokenRotationSettings {
+    fn default() -> Self {
+        TokenRotationSettings {
+            enabled: false,
+            every_days: 30,
+            last_rotated_at: None,
+            token_bytes: gateway_token::DEFAULT_BYTES,
+            history: Vec::new(),
+        }
+    }
+}
+
+fn load_token_rotation_settings() -> TokenRotationSettings {
+    load_manager_config()
+        .ok()
+        .and_then(|m| m.get("tokenRotation").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn save_token_rotation_file(settings: &TokenRotationSettings) -> Result<(), String> {
+    let mut manager_config = load_manager_config()?;
+    manager_config["tokenRotation"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize token rotation settings: {}", e))?;
+    save_manager_config(&manager_config)
+}
+
+/// Whether a scheduled rotation is due: enabled and `every_days` since the last rotation
+pub(crate) fn token_rotation_due(settings: &TokenRotationSettings, now: chrono::DateTime<chrono::Utc>) -> bool {
+    if !settings.enabled || settings.every_days == 0 {
+        return false;
+    }
+    settings
+        .last_rotated_at
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|last| now.signed_duration_since(last) >= chrono::Duration::days(settings.every_days as i64))
+        .unwrap_or(false)
+}
+
+/// Add a rotation to the front of the history, keeping the most recent ones. The new token
+/// was written even when the restart failed, so the interval counts from it either way.
+pub(crate) fn record_token_rotation(settings: &mut TokenRotationSettings, record: TokenRotationRecord) {
+    settings.last_rotated_at = Some(record.at.clone());
+    settings.history.insert(0, record);
+    settings.history.truncate(TOKEN_ROTATION_HISTORY);
+}
+
+/// Last four characters of a token, enough to tell tokens apart in the history
+pub(crate) fn token_hint(token: &str) -> String {
+    let tail: String = token.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("...{}", tail)
+}
+
+/// Replace gateway.auth.token (and gateway.remote.token when it pointed at the same gateway),
+/// restart a running gateway so the old token stops working, and check that it is refused.
+/// Nothing is written when a running gateway could not be restarted (readiness blocks it).
+/// With `only_weak` the token is kept when it is no longer weak once the lock is held.
+async fn rotate_gateway_token(reason: &str, only_weak: bool) -> Result<TokenRotationRecord, String> {
+    if crate::commands::service::gateway_pid().is_some() {
+        crate::commands::service::check_start_readiness(false)
+            .map_err(|e| format!("Gateway token not rotated, the gateway could not be restarted with it: {}", e))?;
+    }
+    let (old_token, new_token) = {
+        let _lock = locks::lock(&[ConfigResource::OpenClaw], "rotate_gateway_token").await;
+        if draft_active() {
+            return Err("Close the configuration draft before rotating the gateway token".to_string());
+        }
+        let mut config = load_live_openclaw_config()?;
+        let old_token = config.pointer("/gateway/auth/token").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        if only_weak && gateway_token::weakness(&old_token).is_none() {
+            return Err("Gateway token is no longer weak".to_string());
+        }
+        let new_token = gateway_token::generate_configured()?;
+        set_at_pointer(&mut config, "/gateway/auth/token", Some(json!(new_token)));
+        set_at_pointer(&mut config, "/gateway/auth/mode", Some(json!("token")));
+        if !old_token.is_empty() && config.pointer("/gateway/remote/token").and_then(|v| v.as_str()) == Some(old_token.as_str()) {
+            set_at_pointer(&mut config, "/gateway/remote/token", Some(json!(new_token)));
+        }
+        save_live_openclaw_config(&config, "rotate_gateway_token")?;
+        (old_token, new_token)
+    };
+    info!("[Token Rotation] Gateway token replaced ({})", reason);
+
+    let mut record = TokenRotationRecord {
+        at: chrono::Utc::now().to_rfc3339(),
+        reason: reason.to_string(),
+        old_token_hint: token_hint(&old_token),
+        new_token_hint: token_hint(&new_token),
+        restarted: false,
+        old_token_rejected: None,
+        error: None,
+    };
+    // A stopped gateway picks up the new token when it starts
+    if crate::commands::service::gateway_pid().is_some() {
+        match crate::commands::service::restart_service(None).await {
+            Ok(_) => {
+                record.restarted = true;
+                if !old_token.is_empty() {
+                    let port = load_openclaw_config()
+                        .ok()
+                        .and_then(|c| c.pointer("/gateway/port").and_then(|v| v.as_u64()))
+                        .map(|p| p as u16)
+                        .unwrap_or(18789);
+                    let probe = crate::commands::service::probe_gateway_http(shell::executor(), port, &old_token);
+                    record.old_token_rejected = probe.token_accepted.map(|accepted| !accepted);
+                }
+            }
+            Err(e) => {
+                warn!("[Token Rotation] Gateway restart failed: {}", e);
+                record.error = Some(format!("Token replaced but the gateway did not restart: {}", e));
+            }
+        }
+    }
+
+    let _lock = locks::lock(&[ConfigResource::Manager], "rotate_gateway_token").await;
+    let mut settings = load_token_rotation_settings();
+    record_token_rotation(&mut settings, record.clone());
+    save_token_rotation_file(&settings)?;
+    // Dashboard URLs embed the token; the frontend asks for a fresh one on this event
+    events::emit(ManagerEvent::alert(
+        "gateway-token-rotated",
+        if record.error.is_some() { AlertLevel::Warning } else { AlertLevel::Info },
+        format!("Gateway token rotated ({})", reason),
+        &record,
+    ));
+    Ok(record)
+}
+
+/// Background task that replaces a weak gateway token and rotates the token when the configured
+/// interval has passed
+pub fn start_token_rotation_task() {
+    std::thread::spawn(|| loop {
+        replace_weak_gateway_token();
+        std::thread::sleep(std::time::Duration::from_secs(60 * 60));
+
+        if !token_rotation_due(&load_token_rotation_settings(), chrono::Utc::now()) {
+            continue;
+        }
+        if draft_active() {
+            debug!("[Token Rotation] Rotation due, waiting for the configuration draft to close");
+            continue;
+        }
+        match tauri::async_runtime::block_on(rotate_gateway_token("scheduled", false)) {
+            Ok(record) => info!("[Token Rotation] Scheduled rotation done (restarted: {})", record.restarted),
+            Err(e) => warn!("[Token Rotation] Scheduled rotation failed: {}", e),
+        }
+    });
+}
+
+/// Rotate a token written by an older Manager version (or the built-in fallback), so the
+/// upgrade does not leave a guessable token in place. The token is checked again under the
+/// config lock before it is replaced.
+fn replace_weak_gateway_token() {
+    let Some(weakness) = load_openclaw_config()
+        .ok()
+        .and_then(|c| c.pointer("/gateway/auth/token").and_then(|v| v.as_str()).and_then(gateway_token::weakness))
+    else {
+        return;
+    };
+    if draft_active() {
+        debug!("[Token Rotation] Token is {}, waiting for the configuration draft to close", weakness);
+        return;
+    }
+    warn!("[Token Rotation] Gateway token is {}, replacing it", weakness);
+    match tauri::async_runtime::block_on(rotate_gateway_token("weak-token", true)) {
+        Ok(record) => info!("[Token Rotation] Weak token replaced (restarted: {})", record.restarted),
+        Err(e) => warn!("[Token Rotation] Replacing weak token failed: {}", e),
+    }
+}
+
+/// Get token rotation settings and history
+#[command]
+#[specta::specta]
+pub async fn get_token_rotation_settings() -> Result<TokenRotationSettings, String> {
+    Ok(load_token_rotation_settings())
+}
+
+/// Turn scheduled token rotation on or off. The interval counts from now when no rotation was
+/// recorded yet. `token_bytes` sets the length of tokens generated from now on.
+#[command]
+#[specta::specta]
+pub async fn save_token_rotation_settings(
+    enabled: bool,
+    every_days: u32,
+    token_bytes: Option<u32>,
+) -> Result<TokenRotationSettings, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_token_rotation_settings").await;
+    if every_days == 0 {
+        return Err("Rotation interval must be at least 1 day".to_string());
+    }
+    if let Some(bytes) = token_bytes {
+        gateway_token::validate_bytes(bytes)?;
+    }
+    info!("[Token Rotation] Saving settings (enabled: {}, every {} days, token bytes: {:?})", enabled, every_days, token_bytes);
+    let mut settings = load_token_rotation_settings();
+    settings.enabled = enabled;
+    settings.every_days = every_days;
+    if let Some(bytes) = token_bytes {
+        settings.token_bytes = bytes;
+    }
+    if enabled && settings.last_rotated_at.is_none() {
+        settings.last_rotated_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    save_token_rotation_file(&settings)?;
+    gateway_token::set_token_bytes(settings.token_bytes);
+    Ok(settings)
+}
+
+/// Rotate the gateway token now
+#[command]
+#[specta::specta]
+pub async fn rotate_gateway_token_now() -> Result<TokenRotationRecord, String> {
+    rotate_gateway_token("manual", false).await
+}
+
+// ============ Gateway Clients ============
+
+/// Operator scopes the gateway understands for device tokens
+const GATEWAY_CLIENT_SCOPES: &[&str] = &[
+    "operator.read",
+    "operator.write",
+    "operator.admin",
+    "operator.approvals",
+    "operator.pairing",
+];
+
+/// Id used for the shared `gateway.auth.token` entry in the client list
+const SHARED_TOKEN_CLIENT_ID: &str = "shared-token";
+
+/// A credential that can talk to the gateway. Besides the shared token, the core issues
+/// one token per paired device, each with its own role and scopes.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GatewayClient {
+    pub id: String,
+    pub name: String,
+    /// "shared_token" or "device"
+    pub kind: String,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+    pub created_at: Option<String>,
+    pub last_used_at: Option<String>,
+    /// Waiting for approval (only devices)
+    pub pending: bool,
+    /// Pairing request id (pending devices only)
+    pub request_id: Option<String>,
+}
+
+fn read_device_store(name: &str) -> Vec<Value> {
+    let path = std::path::Path::new(&platform::get_config_dir()).join("devices").join(name);
+    let value: Value = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(Value::Null);
+    match value {
+        Value::Array(items) => items,
+        Value::Object(map) => map
+            .into_iter()
+            .map(|(key, mut entry)| {
+                if entry.get("deviceId").is_none() && entry.get("requestId").is_none() {
+                    entry["deviceId"] = json!(key);
+                }
+                entry
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn ms_to_rfc3339(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|d| d.to_rfc3339())
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+fn device_to_client(entry: &Value, pending: bool) -> Option<GatewayClient> {
+    let id = entry.get("deviceId").and_then(|v| v.as_str())?.to_string();
+    let name = ["displayName", "clientId", "name"]
+        .iter()
+        .find_map(|k| entry.get(*k).and_then(|v| v.as_str()))
+        .unwrap_or(&id)
+        .to_string();
+
+    let mut roles = string_list(entry.get("roles"));
+    roles.extend(string_list(entry.get("role")));
+    let mut scopes = string_list(entry.get("scopes"));
+    let mut last_used: Option<i64> = None;
+    if let Some(tokens) = entry.get("tokens").and_then(|v| v.as_object()) {
+        for (role, token) in tokens {
+            roles.push(role.clone());
+            scopes.extend(string_list(token.get("scopes")));
+            if let Some(ms) = token.get("lastUsedAtMs").and_then(|v| v.as_i64()) {
+                last_used = Some(last_used.map_or(ms, |l| l.max(ms)));
+            }
+        }
+    }
+    roles.sort();
+    roles.dedup();
+    scopes.sort();
+    scopes.dedup();
+
+    Some(GatewayClient {
+        id,
+        name,
+        kind: "device".to_string(),
+        roles,
+        scopes,
+        created_at: ms_to_rfc3339(entry.get("approvedAtMs").or(entry.get("createdAtMs")).or(entry.get("ts"))),
+        last_used_at: ms_to_rfc3339(last_used.map(Value::from).as_ref()),
+        pending,
+        request_id: entry.get("requestId").and_then(|v| v.as_str()).map(|s| s.to_string()),
+    })
+}
+
+fn collect_gateway_clients() -> Result<Vec<GatewayClient>, String> {
+    let config = load_openclaw_config()?;
+    let mut clients = Vec::new();
+
+    if config.pointer("/gateway/auth/token").and_then(|v| v.as_str()).map(|t| !t.is_empty()).unwrap_or(false) {
+        clients.push(GatewayClient {
+            id: SHARED_TOKEN_CLIENT_ID.to_string(),
+            name: "Shared gateway token".to_string(),
+            kind: "shared_token".to_string(),
+            roles: vec!["operator".to_string()],
+            scopes: vec!["operator.admin".to_string()],
+            created_at: None,
+            last_used_at: None,
+            pending: false,
+            request_id: None,
+        });
+    }
+    clients.extend(read_device_store("paired.json").iter().filter_map(|d| device_to_client(d, false)));
+    clients.extend(read_device_store("pending.json").iter().filter_map(|d| device_to_client(d, true)));
+    Ok(clients)
+}
+
+/// List gateway clients: the shared token plus every paired or pending device
+#[command]
+#[specta::specta]
+pub async fn list_gateway_clients() -> Result<Vec<GatewayClient>, String> {
+    info!("[Gateway Clients] Listing gateway clients...");
+    let clients = collect_gateway_clients()?;
+    info!("[Gateway Clients] Found {} clients", clients.len());
+    Ok(clients)
+}
+
+/// Give a client its own credential. The core only issues per-client tokens through
+/// device pairing, so the client (e.g. a Shortcuts automation) must first connect and
+/// request pairing; this approves that request and limits its token to `scopes`.
+#[command]
+#[specta::specta]
+pub async fn create_gateway_client(name: String, scopes: Vec<String>) -> Result<GatewayClient, String> {
+    info!("[Gateway Clients] Creating client '{}' with scopes {:?}", name, scopes);
+    if let Some(bad) = scopes.iter().find(|s| !GATEWAY_CLIENT_SCOPES.contains(&s.as_str())) {
+        return Err(format!("Unknown scope '{}', expected one of: {}", bad, GATEWAY_CLIENT_SCOPES.join(", ")));
+    }
+
+    let pending = collect_gateway_clients()?
+        .into_iter()
+        .find(|c| c.pending && (c.name.eq_ignore_ascii_case(&name) || c.id == name))
+        .ok_or(format!(
+            "No pending pairing request from '{}'. Connect the client to the gateway first so it requests pairing, then try again.",
+            name
+        ))?;
+    let request_id = pending.request_id.clone().unwrap_or_else(|| pending.id.clone());
+
+    shell::run_openclaw(&["devices", "approve", &request_id])
+        .map_err(|e| format!("Failed to approve pairing request: {}", e))?;
+
+    if !scopes.is_empty() {
+        let mut args = vec!["devices", "rotate", "--device", pending.id.as_str(), "--role", "operator"];
+        for scope in &scopes {
+            args.push("--scope");
+            args.push(scope);
+        }
+        shell::run_openclaw(&args).map_err(|e| format!("Client approved, but limiting its scopes failed: {}", e))?;
+    }
+
+    info!("[Gateway Clients] Client '{}' approved as device {}", name, pending.id);
+    Ok(GatewayClient {
+        pending: false,
+        request_id: None,
+        roles: vec!["operator".to_string()],
+        scopes,
+        created_at: Some(chrono::Utc::now().to_rfc3339()),
+        ..pending
+    })
+}
+
+/// Revoke a client's tokens (all roles). The shared token is rotated via the Gateway Token page instead.
+#[command]
+#[specta::specta]
+pub async fn revoke_gateway_client(client_id: String) -> Result<String, String> {
+    info!("[Gateway Clients] Revoking client {}", client_id);
+    if client_id == SHARED_TOKEN_CLIENT_ID {
+        return Err("The shared gateway token cannot be revoked, rotate it instead".to_string());
+    }
+    let client = collect_gateway_clients()?
+        .into_iter()
+        .find(|c| c.id == client_id)
+        .ok_or(format!("Gateway client '{}' not found", client_id))?;
+
+    if client.pending {
+        let request_id = client.request_id.unwrap_or(client.id);
+        shell::run_openclaw(&["devices", "reject", &request_id])
+            .map_err(|e| format!("Failed to reject pairing request: {}", e))?;
+        return Ok(format!("Pairing request from {} rejected", client.name));
+    }
+
+    let roles = if client.roles.is_empty() { vec!["operator".to_string()] } else { client.roles };
+    for role in &roles {
+        shell::run_openclaw(&["devices", "revoke", "--device", &client.id, "--role", role])
+            .map_err(|e| format!("Failed to revoke {} token: {}", role, e))?;
+    }
+    info!("[Gateway Clients] Revoked {} ({} roles)", client.name, roles.len());
+    Ok(format!("Access revoked for {}", client.name))
+}
+
 // ============ AI Configuration Commands ============
 
 /// Get official Provider list (preset templates)
 #[command]
+#[specta::specta]
 pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
     info!("[Official Provider] Getting official Provider preset list...");
 
@@ -534,13 +1336,13 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
         },
         OfficialProvider {
             id: "google".to_string(),
-            name: "Google Gemini".to_string(),
+            name: "Google Gemini (AI Studio)".to_string(),
             icon: "✨".to_string(),
-            default_base_url: Some("https://generativelanguage.googleapis.com/v1beta/openai/".to_string()),
-            api_type: "openai-completions".to_string(),
+            default_base_url: Some(GEMINI_API_BASE.to_string()),
+            api_type: "google-generative-ai".to_string(),
             requires_api_key: true,
             default_api_key: None,
-            docs_url: Some("https://ai.google.dev/gemini-api/docs/openai".to_string()),
+            docs_url: Some("https://docs.openclaw.ai/providers/google".to_string()),
             suggested_models: vec![
                 SuggestedModel {
                     id: "gemini-3-flash-preview".to_string(),
@@ -560,6 +1362,36 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                 },
             ],
         },
+        OfficialProvider {
+            id: "google-vertex".to_string(),
+            name: "Google Vertex AI".to_string(),
+            icon: "☁️".to_string(),
+            // Endpoint is derived from GOOGLE_CLOUD_PROJECT / GOOGLE_CLOUD_LOCATION
+            default_base_url: None,
+            api_type: "google-vertex".to_string(),
+            // Authenticates with a service account key, see save_vertex_credentials
+            requires_api_key: false,
+            default_api_key: None,
+            docs_url: Some("https://cloud.google.com/vertex-ai/generative-ai/docs/start/quickstarts".to_string()),
+            suggested_models: vec![
+                SuggestedModel {
+                    id: "gemini-2.5-flash".to_string(),
+                    name: "Gemini 2.5 Flash".to_string(),
+                    description: Some("Fast multimodal model on Vertex AI".to_string()),
+                    context_window: Some(1048576),
+                    max_tokens: Some(65536),
+                    recommended: true,
+                },
+                SuggestedModel {
+                    id: "gemini-2.5-pro".to_string(),
+                    name: "Gemini 2.5 Pro".to_string(),
+                    description: Some("Complex reasoning tasks on Vertex AI".to_string()),
+                    context_window: Some(1048576),
+                    max_tokens: Some(65536),
+                    recommended: false,
+                },
+            ],
+        },
     ];
 
     info!(
@@ -569,34 +1401,303 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
     Ok(providers)
 }
 
-/// Get AI configuration overview
-#[command]
-pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
-    info!("[AI Config] Getting AI configuration overview...");
-
-    let config_path = platform::get_config_file_path();
-    info!("[AI Config] Configuration file path: {}", config_path);
+// ============ Provider Key Expiry ============
+
+/// Expiration metadata of one configured provider's key
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderKeyExpiryStatus {
+    pub provider: String,
+    pub expiry: Option<KeyExpiry>,
+    /// Negative once expired
+    pub days_left: Option<i64>,
+    /// Where to create a new key
+    pub manage_url: Option<String>,
+}
 
-    let config = load_openclaw_config()?;
-    debug!("[AI Config] Configuration content: {}", serde_json::to_string_pretty(&config).unwrap_or_default());
+fn load_key_expiry() -> HashMap<String, KeyExpiry> {
+    load_manager_config()
+        .ok()
+        .and_then(|m| m.get("providerKeyExpiry").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
 
-    // Parse primary model
-    let primary_model = config
-        .pointer("/agents/defaults/model/primary")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    info!("[AI Config] Primary model: {:?}", primary_model);
+fn save_key_expiry(entries: &HashMap<String, KeyExpiry>) -> Result<(), String> {
+    let mut manager_config = load_manager_config()?;
+    manager_config["providerKeyExpiry"] = serde_json::to_value(entries)
+        .map_err(|e| format!("Failed to serialize key expiry dates: {}", e))?;
+    save_manager_config(&manager_config)
+}
 
-    // Parse available model list
-    let available_models: Vec<String> = config
-        .pointer("/agents/defaults/models")
+/// Configured providers with their API base URL
+fn configured_providers(config: &Value) -> Vec<(String, Option<String>)> {
+    config
+        .pointer("/models/providers")
         .and_then(|v| v.as_object())
-        .map(|obj| obj.keys().cloned().collect())
-        .unwrap_or_default();
-    info!("[AI Config] Number of available models: {}", available_models.len());
+        .map(|providers| {
+            providers
+                .iter()
+                .map(|(name, p)| (name.clone(), p.get("baseUrl").and_then(|v| v.as_str()).map(String::from)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-    // Parse configured Providers
-    let mut configured_providers: Vec<ConfiguredProvider> = Vec::new();
+fn key_expiry_statuses(config: &Value, entries: &HashMap<String, KeyExpiry>) -> Vec<ProviderKeyExpiryStatus> {
+    let today = chrono::Local::now().date_naive();
+    let mut statuses: Vec<ProviderKeyExpiryStatus> = configured_providers(config)
+        .into_iter()
+        .map(|(provider, base_url)| {
+            let expiry = entries.get(&provider).cloned();
+            ProviderKeyExpiryStatus {
+                days_left: expiry.as_ref().and_then(|e| key_expiry::days_left(e, today)),
+                manage_url: key_expiry::key_management_url(&provider, base_url.as_deref(), expiry.as_ref()),
+                provider,
+                expiry,
+            }
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.provider.cmp(&b.provider));
+    statuses
+}
+
+/// List configured providers with their key expiration date and days left
+#[command]
+#[specta::specta]
+pub async fn list_provider_key_expiry() -> Result<Vec<ProviderKeyExpiryStatus>, String> {
+    let config = load_openclaw_config()?;
+    Ok(key_expiry_statuses(&config, &load_key_expiry()))
+}
+
+/// Record when a provider's key expires or should be rotated (`expires_on` YYYY-MM-DD, None to
+/// forget it). A reminder is shown each day from `remind_days_before` days before the date.
+#[command]
+#[specta::specta]
+pub async fn set_provider_key_expiry(
+    provider: String,
+    expires_on: Option<String>,
+    remind_days_before: Option<u32>,
+    manage_url: Option<String>,
+) -> Result<ProviderKeyExpiryStatus, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_provider_key_expiry").await;
+    let config = load_openclaw_config()?;
+    if !configured_providers(&config).iter().any(|(name, _)| *name == provider) {
+        return Err(format!("Provider '{}' is not configured", provider));
+    }
+    let mut entries = load_key_expiry();
+    match expires_on.filter(|d| !d.trim().is_empty()) {
+        Some(day) => {
+            key_expiry::parse_day(&day)?;
+            info!("[Key Expiry] {} key expires on {}", provider, day);
+            entries.insert(
+                provider.clone(),
+                KeyExpiry {
+                    expires_on: day.trim().to_string(),
+                    remind_days_before: remind_days_before.unwrap_or(key_expiry::DEFAULT_REMIND_DAYS),
+                    manage_url: manage_url.filter(|u| !u.trim().is_empty()),
+                    last_reminded_on: None,
+                },
+            );
+        }
+        None => {
+            info!("[Key Expiry] Expiration date of {} removed", provider);
+            entries.remove(&provider);
+        }
+    }
+    save_key_expiry(&entries)?;
+    key_expiry_statuses(&config, &entries)
+        .into_iter()
+        .find(|s| s.provider == provider)
+        .ok_or_else(|| format!("Provider '{}' is not configured", provider))
+}
+
+/// Remind about provider keys close to their expiration date, once a day per key
+pub fn start_key_expiry_watcher(app: tauri::AppHandle) {
+    use tauri_plugin_notification::NotificationExt;
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(60 * 60));
+
+        let Ok(config) = load_openclaw_config() else {
+            continue;
+        };
+        let mut entries = load_key_expiry();
+        let today = chrono::Local::now().date_naive();
+        let mut reminded = false;
+        for status in key_expiry_statuses(&config, &entries) {
+            let (Some(expiry), Some(left)) = (status.expiry.as_ref(), status.days_left) else {
+                continue;
+            };
+            if !key_expiry::reminder_due(expiry, today) {
+                continue;
+            }
+            let message = key_expiry::reminder_message(&status.provider, left);
+            warn!("[Key Expiry] {}", message);
+            events::emit(ManagerEvent::alert(
+                "provider-key-expiry",
+                if left < 0 { AlertLevel::Error } else { AlertLevel::Warning },
+                message.clone(),
+                &status,
+            ));
+            let body = match &status.manage_url {
+                Some(url) => format!("{}. Create a new key at {}", message, url),
+                None => message,
+            };
+            if let Err(e) = app.notification().builder().title("Provider API key expiring").body(body).show() {
+                debug!("[Key Expiry] Failed to show notification: {}", e);
+            }
+            if let Some(entry) = entries.get_mut(&status.provider) {
+                entry.last_reminded_on = Some(today.format("%Y-%m-%d").to_string());
+                reminded = true;
+            }
+        }
+        if reminded {
+            // Re-read under the lock so dates edited meanwhile are not overwritten
+            let _lock = locks::lock_blocking(&[ConfigResource::Manager], "key_expiry_watcher");
+            let mut latest = load_key_expiry();
+            for (provider, entry) in &entries {
+                if let Some(current) = latest.get_mut(provider) {
+                    current.last_reminded_on = entry.last_reminded_on.clone();
+                }
+            }
+            if let Err(e) = save_key_expiry(&latest) {
+                warn!("[Key Expiry] Failed to record reminders: {}", e);
+            }
+        }
+    });
+}
+
+// ============ Google Gemini / Vertex ============
+
+/// Gemini API (AI Studio) base URL
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Default Vertex AI region
+const VERTEX_DEFAULT_LOCATION: &str = "us-central1";
+
+/// Gemini key to use: the given one, else the google provider's apiKey, else GEMINI_API_KEY
+pub(crate) fn resolve_gemini_key(api_key: Option<String>) -> Result<String, String> {
+    if let Some(key) = api_key.filter(|k| !k.trim().is_empty()) {
+        return Ok(key.trim().to_string());
+    }
+    let from_config = load_openclaw_config()
+        .ok()
+        .and_then(|c| c.pointer("/models/providers/google/apiKey").and_then(|v| v.as_str()).map(String::from))
+        .filter(|k| !k.is_empty());
+    from_config
+        .or_else(|| std::env::var("GEMINI_API_KEY").ok().filter(|k| !k.is_empty()))
+        .or_else(|| file::read_env_value(&platform::get_env_file_path(), "GEMINI_API_KEY"))
+        .ok_or("No Gemini API key: enter one or set GEMINI_API_KEY".to_string())
+}
+
+/// Call the Gemini REST API via curl (GET, or POST when `body` is given) and return the JSON response
+pub(crate) fn gemini_api_call(api_key: &str, path: &str, body: Option<&Value>) -> Result<Value, String> {
+    let url = format!("{}/{}", GEMINI_API_BASE, path);
+    let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args(["-s", "--max-time", "30"]);
+    let _headers = shell::curl_secret_headers(&mut cmd, &[format!("x-goog-api-key: {}", api_key)])?;
+    if let Some(body) = body {
+        cmd.args(["-X", "POST", "-H", "Content-Type: application/json", "-d", &body.to_string()]);
+    }
+    cmd.arg(&url);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+    let response = String::from_utf8_lossy(&output.stdout).to_string();
+    let json: Value = serde_json::from_str(&response)
+        .map_err(|_| "Gemini API returned an invalid response (check network access)".to_string())?;
+    match json.pointer("/error/message").and_then(|v| v.as_str()) {
+        Some(message) => Err(format!("Gemini API error: {}", message)),
+        None => Ok(json),
+    }
+}
+
+/// List the chat models available to a Gemini API key (also validates the key)
+#[command]
+#[specta::specta]
+pub async fn discover_gemini_models(api_key: Option<String>) -> Result<Vec<gemini::GeminiModel>, String> {
+    info!("[Gemini] Discovering models...");
+    let key = resolve_gemini_key(api_key)?;
+    let body = gemini_api_call(&key, "models?pageSize=1000", None)?;
+    let models = gemini::parse_model_list(&body);
+    info!("[Gemini] Found {} chat models", models.len());
+    Ok(models)
+}
+
+/// Store a Vertex AI service account key in credentials/ and point the gateway at it
+/// (GOOGLE_APPLICATION_CREDENTIALS, GOOGLE_CLOUD_PROJECT, GOOGLE_CLOUD_LOCATION in the env file)
+#[command]
+#[specta::specta]
+pub async fn save_vertex_credentials(content: String, location: Option<String>) -> Result<gemini::ServiceAccountInfo, String> {
+    let info = gemini::validate_service_account(&content)?;
+    info!("[Vertex] Saving service account {} (project {})", info.client_email, info.project_id);
+
+    let credentials_dir = platform::get_credentials_dir();
+    std::fs::create_dir_all(&credentials_dir)
+        .map_err(|e| write_error("credentials directory", &credentials_dir, e))?;
+    let key_path = platform::path_string(&platform::join_path(&credentials_dir, &["google-vertex-service-account.json"]));
+    ensure_writable()?;
+    file::write_file(&key_path, content.trim_start_matches('\u{FEFF}'))
+        .map_err(|e| write_error("service account key", &key_path, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600)) {
+            warn!("[Vertex] Failed to restrict key file permissions: {}", e);
+        }
+    }
+
+    let location = location
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| VERTEX_DEFAULT_LOCATION.to_string());
+    let env_path = platform::get_env_file_path();
+    for (key, value) in [
+        ("GOOGLE_APPLICATION_CREDENTIALS", key_path.as_str()),
+        ("GOOGLE_CLOUD_PROJECT", info.project_id.as_str()),
+        ("GOOGLE_CLOUD_LOCATION", location.as_str()),
+    ] {
+        file::set_env_value(&env_path, key, value)
+            .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+    }
+    Ok(info)
+}
+
+/// Get AI configuration overview
+#[command]
+#[specta::specta]
+pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
+    info!("[AI Config] Getting AI configuration overview...");
+
+    let config_path = platform::get_config_file_path();
+    info!("[AI Config] Configuration file path: {}", config_path);
+
+    let config = load_openclaw_config()?;
+    debug!("[AI Config] Configuration content: {}", serde_json::to_string_pretty(&config).unwrap_or_default());
+
+    // Parse primary model
+    let primary_model = config
+        .pointer("/agents/defaults/model/primary")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    info!("[AI Config] Primary model: {:?}", primary_model);
+
+    // Parse available model list
+    let available_models: Vec<String> = config
+        .pointer("/agents/defaults/models")
+        .and_then(|v| v.as_object())
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default();
+    info!("[AI Config] Number of available models: {}", available_models.len());
+
+    // Parse configured Providers
+    let mut configured_providers: Vec<ConfiguredProvider> = Vec::new();
 
     let providers_value = config.pointer("/models/providers");
     info!("[AI Config] providers node exists: {}", providers_value.is_some());
@@ -667,11 +1768,20 @@ pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
 
             info!("[AI Config] Provider {} parsing complete: {} models", provider_name, models.len());
 
+            let mut header_names: Vec<String> = provider_config
+                .get("headers")
+                .and_then(|v| v.as_object())
+                .map(|h| h.keys().cloned().collect())
+                .unwrap_or_default();
+            header_names.sort();
+
             configured_providers.push(ConfiguredProvider {
                 name: provider_name.clone(),
                 base_url,
                 api_key_masked,
                 has_api_key: api_key.is_some(),
+                header_names,
+                auth_header: provider_config.get("authHeader").and_then(|v| v.as_bool()),
                 models,
             });
         }
@@ -693,23 +1803,69 @@ pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
     })
 }
 
-/// Add or update Provider
+/// String map at `key` of a JSON object (provider or model `headers`)
+fn string_map(value: Option<&Value>) -> HashMap<String, String> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|m| m.iter().filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string()))).collect())
+        .unwrap_or_default()
+}
+
+/// Add or update Provider.
+/// `headers` replaces the provider's custom headers (None keeps them, an empty value keeps that
+/// header's stored value). `azure` derives the base URL from an Azure OpenAI resource and sends the
+/// key as `api-key`. With `verify`, a test completion runs with the new settings before saving.
 #[command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub async fn save_provider(
     provider_name: String,
     base_url: String,
     api_key: Option<String>,
     api_type: String,
     models: Vec<ModelConfig>,
+    copy_from: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    auth_header: Option<bool>,
+    azure: Option<AzureProviderOptions>,
+    verify: Option<bool>,
+    keychain: Option<bool>,
 ) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "save_provider").await;
     info!(
         "[Save Provider] Saving Provider: {} ({} models)",
         provider_name,
         models.len()
     );
+    validate_provider_name(&provider_name)?;
 
     let mut config = load_openclaw_config()?;
 
+    // Named profile of an existing provider (e.g. anthropic -> anthropic-work):
+    // base URL and models are copied when not given, the API key never is
+    let copy_source = match copy_from.as_deref().filter(|s| !s.is_empty() && *s != provider_name) {
+        Some(source) => {
+            info!("[Save Provider] Copying settings from Provider: {}", source);
+            Some(
+                config
+                    .pointer(&format!("/models/providers/{}", source))
+                    .cloned()
+                    .ok_or(format!("Provider {} to copy from not found", source))?,
+            )
+        }
+        None => None,
+    };
+    let base_url = match &copy_source {
+        Some(source) if base_url.is_empty() => source.get("baseUrl").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        _ => base_url,
+    };
+    let base_url = match &azure {
+        Some(azure) => provider_headers::azure_base_url(&azure.resource)?,
+        None => base_url,
+    };
+    let api_type = if azure.is_some() { "openai-completions".to_string() } else { api_type };
+    let existing_provider = config.pointer(&format!("/models/providers/{}", provider_name)).cloned();
+
     // Ensure paths exist
     if config.get("models").is_none() {
         config["models"] = json!({});
@@ -747,6 +1903,9 @@ pub async fn save_provider(
             if let Some(r) = m.reasoning {
                 model_obj["reasoning"] = json!(r);
             }
+            if !m.headers.is_empty() {
+                model_obj["headers"] = json!(m.headers);
+            }
             if let Some(cost) = &m.cost {
                 model_obj["cost"] = json!({
                     "input": cost.input,
@@ -767,11 +1926,23 @@ pub async fn save_provider(
         })
         .collect();
 
+    let models_json = match &copy_source {
+        Some(source) if models_json.is_empty() => source
+            .get("models")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        _ => models_json,
+    };
+
     // Build Provider configuration
     let mut provider_config = json!({
         "baseUrl": base_url,
         "models": models_json,
     });
+    if let Some(api) = copy_source.as_ref().and_then(|s| s.get("api")) {
+        provider_config["api"] = api.clone();
+    }
 
     // Handle API Key: if a new non-empty key is provided, use it; otherwise preserve the existing one
     if let Some(key) = api_key {
@@ -800,12 +1971,77 @@ pub async fn save_provider(
         }
     }
 
+    // Keychain storage: openclaw.json only keeps an env reference in the native form the
+    // gateway substitutes (see normalize_placeholders). A plaintext key already in the config is
+    // moved there too. Stored once the provider is about to be saved.
+    let mut keychain_secret: Option<(String, String)> = None;
+    if keychain.unwrap_or(false) {
+        if let Some(key) = provider_config.get("apiKey").and_then(|v| v.as_str()).filter(|k| !k.contains("${")) {
+            let secret = crate::utils::placeholders::provider_key_secret_name(&provider_name);
+            keychain_secret = Some((secret.clone(), key.to_string()));
+            provider_config["apiKey"] = json!(format!("${{{}}}", crate::utils::placeholders::secret_env_var(&secret)));
+            info!("[Save Provider] API Key goes to the keychain as '{}'", secret);
+        }
+    }
+
+    // Custom headers: merged onto the stored ones, kept as they are when not given
+    let stored_headers = string_map(existing_provider.as_ref().and_then(|p| p.get("headers")));
+    let mut provider_headers_map = match &headers {
+        Some(incoming) => provider_headers::merge_headers(&stored_headers, incoming),
+        None if existing_provider.is_some() => stored_headers,
+        None => string_map(copy_source.as_ref().and_then(|s| s.get("headers"))),
+    };
+    let mut auth_header = auth_header.or_else(|| {
+        existing_provider.as_ref().and_then(|p| p.get("authHeader")).and_then(|v| v.as_bool())
+    });
+    if azure.is_some() {
+        // Azure wants the key in `api-key`, not as a bearer token
+        if let Some(key) = provider_config.get("apiKey").and_then(|v| v.as_str()) {
+            provider_headers_map.insert(provider_headers::AZURE_API_KEY_HEADER.to_string(), key.to_string());
+        }
+        auth_header = Some(false);
+    }
+    for (name, value) in provider_headers_map.iter() {
+        provider_headers::validate_header(name, value)?;
+    }
+    for model in &models {
+        for (name, value) in &model.headers {
+            provider_headers::validate_header(name, value)?;
+        }
+    }
+    if !provider_headers_map.is_empty() {
+        provider_config["headers"] = json!(provider_headers_map);
+    }
+    if let Some(auth_header) = auth_header {
+        provider_config["authHeader"] = json!(auth_header);
+    }
+
     // Save Provider configuration
     config["models"]["providers"][&provider_name] = provider_config;
 
+    // Live test request with the new settings; nothing is written if it fails
+    if verify.unwrap_or(false) {
+        let first_model = models_json
+            .iter()
+            .find_map(|m| m.get("id").and_then(|v| v.as_str()))
+            .ok_or("Add at least one model to verify the provider")?;
+        let mut probe = config.clone();
+        apply_primary_model(&mut probe, &format!("{}/{}", provider_name, first_model));
+        let probe_env: Vec<(String, String)> = keychain_secret
+            .iter()
+            .map(|(secret, key)| (crate::utils::placeholders::secret_env_var(secret), key.clone()))
+            .collect();
+        let probe = crate::utils::placeholders::to_native(&probe).unwrap_or(probe);
+        run_model_probe_with_env(&probe, &probe_env).map_err(|e| {
+            warn!("[Save Provider] Verification of {} failed, provider not saved", provider_name);
+            format!("Test request failed, provider was not saved:\n{}", e)
+        })?;
+        info!("[Save Provider] Verification of {} succeeded", provider_name);
+    }
+
     // Add models to agents.defaults.models
-    for model in &models {
-        let full_id = format!("{}/{}", provider_name, model.id);
+    for model_id in models_json.iter().filter_map(|m| m.get("id").and_then(|v| v.as_str())) {
+        let full_id = format!("{}/{}", provider_name, model_id);
         config["agents"]["defaults"]["models"][&full_id] = json!({});
     }
 
@@ -816,15 +2052,158 @@ pub async fn save_provider(
     }
     config["meta"]["lastTouchedAt"] = json!(now);
 
-    save_openclaw_config(&config)?;
+    check_config_schema(&config)?;
+    if let Some((secret, key)) = &keychain_secret {
+        crate::commands::placeholders::store_secret(secret, key, true)?;
+    }
+    save_openclaw_config(&config, "save_provider")?;
     info!("[Save Provider] Provider {} saved successfully", provider_name);
 
+    // A running gateway only gets keychain values when it starts. The restart takes the config
+    // locks itself.
+    let restart_needed = keychain_secret.is_some() && !draft_active() && crate::commands::service::gateway_pid().is_some();
+    drop(_lock);
+    if restart_needed {
+        info!("[Save Provider] Restarting the gateway to pass it the key from the keychain");
+        if let Err(e) = crate::commands::service::restart_service(None).await {
+            warn!("[Save Provider] Gateway restart failed: {}", e);
+            return Err(format!(
+                "Provider {} saved, but the gateway could not be restarted to pick up the key from the keychain. Restart it before using the provider: {}",
+                provider_name, e
+            ));
+        }
+    }
+
     Ok(format!("Provider {} saved", provider_name))
 }
 
+/// Providers the core knows without a models.providers entry (key from env). Not the full
+/// list: the rest comes from OpenClaw's model catalog (see is_known_provider).
+const BUILTIN_PROVIDERS: &[&str] = &[
+    "anthropic", "openai", "google", "google-vertex", "openrouter", "ollama", "deepseek", "moonshot", "qwen", "glm", "minimax", "venice",
+];
+
+/// How long to wait before asking openclaw for its model catalog again after it failed
+const CATALOG_RETRY_SECS: u64 = 600;
+
+/// Providers of OpenClaw's model catalog and when it was last looked for
+static CATALOG_PROVIDERS: std::sync::Mutex<(Option<Vec<String>>, Option<std::time::Instant>)> =
+    std::sync::Mutex::new((None, None));
+
+/// Provider names in `openclaw models list --all --json` output, from the "provider/model"
+/// keys and any explicit provider fields
+fn parse_catalog_providers(output: &str) -> Option<Vec<String>> {
+    fn walk(value: &Value, providers: &mut Vec<String>) {
+        match value {
+            Value::Array(items) => items.iter().for_each(|item| walk(item, providers)),
+            Value::Object(map) => {
+                let key = ["key", "id", "ref"]
+                    .iter()
+                    .find_map(|k| map.get(*k).and_then(|v| v.as_str()))
+                    .and_then(|k| k.split_once('/'))
+                    .map(|(provider, _)| provider);
+                let provider = map.get("provider").and_then(|v| v.as_str()).or(key);
+                if let Some(provider) = provider.filter(|p| !p.is_empty()) {
+                    if !providers.iter().any(|p| p == provider) {
+                        providers.push(provider.to_string());
+                    }
+                }
+                map.values().for_each(|item| walk(item, providers));
+            }
+            _ => {}
+        }
+    }
+    let start = output.find(['{', '['])?;
+    let value: Value = serde_json::from_str(output[start..].trim()).ok()?;
+    let mut providers = Vec::new();
+    walk(&value, &mut providers);
+    (!providers.is_empty()).then_some(providers)
+}
+
+/// Providers in OpenClaw's model catalog (xai, groq, mistral, bedrock... resolve from env or
+/// auth profiles without a models.providers entry), loaded once per session; None while
+/// openclaw can't list them
+fn catalog_providers() -> Option<Vec<String>> {
+    {
+        let mut state = CATALOG_PROVIDERS.lock().ok()?;
+        let (providers, checked_at) = &mut *state;
+        let retry = checked_at.map(|t| t.elapsed().as_secs() >= CATALOG_RETRY_SECS).unwrap_or(true);
+        if providers.is_some() || !retry {
+            return providers.clone();
+        }
+        *checked_at = Some(std::time::Instant::now());
+    }
+    let loaded = match shell::run_openclaw(&["models", "list", "--all", "--json"]) {
+        Ok(output) => parse_catalog_providers(&output),
+        Err(e) => {
+            debug!("[Models] openclaw models list failed: {}", e);
+            None
+        }
+    };
+    if let Ok(mut state) = CATALOG_PROVIDERS.lock() {
+        state.0 = loaded.clone();
+    }
+    loaded
+}
+
+/// Whether model refs of `provider` can resolve: configured, built in or in OpenClaw's
+/// catalog. Without a catalog nothing is called unknown, since the core may still know it.
+fn is_known_provider(config: &Value, provider: &str) -> bool {
+    config.pointer(&format!("/models/providers/{}", provider)).is_some()
+        || BUILTIN_PROVIDERS.contains(&provider)
+        || catalog_providers().map(|providers| providers.iter().any(|p| p == provider)).unwrap_or(true)
+}
+
+/// Provider names end up in model refs ("provider/model"), so keep them simple
+fn validate_provider_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Provider name cannot be empty".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_' || c == '.') {
+        return Err(format!(
+            "Invalid provider name '{}': use lowercase letters, digits, '-', '_' or '.' (e.g. anthropic-work)",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Check that an agent's model ref points to a provider that exists.
+/// Custom provider instances must also list the model.
+fn validate_agent_model(config: &Value, model: &str) -> Result<(), String> {
+    let (provider, model_id) = model
+        .split_once('/')
+        .ok_or(format!("Model '{}' must be in 'provider/model' format", model))?;
+
+    match config.pointer(&format!("/models/providers/{}", provider)) {
+        Some(provider_cfg) => {
+            let listed = provider_cfg
+                .get("models")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().any(|m| m.get("id").and_then(|v| v.as_str()) == Some(model_id)))
+                .unwrap_or(false);
+            if listed {
+                Ok(())
+            } else {
+                Err(format!("Model '{}' is not configured for provider '{}'", model_id, provider))
+            }
+        }
+        None => {
+            if is_known_provider(config, provider) {
+                Ok(())
+            } else {
+                Err(format!("Provider '{}' is not configured", provider))
+            }
+        }
+    }
+}
+
 /// Delete Provider
 #[command]
+#[specta::specta]
 pub async fn delete_provider(provider_name: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "delete_provider").await;
+    snapshots::snapshot_before("delete_provider")?;
     info!("[Delete Provider] Deleting Provider: {}", provider_name);
 
     let mut config = load_openclaw_config()?;
@@ -863,7 +2242,7 @@ pub async fn delete_provider(provider_name: String) -> Result<String, String> {
         }
     }
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, "delete_provider")?;
     info!("[Delete Provider] Provider {} deleted", provider_name);
 
     Ok(format!("Provider {} deleted", provider_name))
@@ -871,11 +2250,21 @@ pub async fn delete_provider(provider_name: String) -> Result<String, String> {
 
 /// Set primary model
 #[command]
+#[specta::specta]
 pub async fn set_primary_model(model_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "set_primary_model").await;
     info!("[Set Primary Model] Setting primary model: {}", model_id);
 
     let mut config = load_openclaw_config()?;
+    apply_primary_model(&mut config, &model_id);
+
+    save_openclaw_config(&config, "set_primary_model")?;
+    info!("[Set Primary Model] Primary model set to: {}", model_id);
+
+    Ok(format!("Primary model set to {}", model_id))
+}
 
+pub(crate) fn apply_primary_model(config: &mut Value, model_id: &str) {
     // Ensure paths exist
     if config.get("agents").is_none() {
         config["agents"] = json!({});
@@ -889,16 +2278,127 @@ pub async fn set_primary_model(model_id: String) -> Result<String, String> {
 
     // Set primary model
     config["agents"]["defaults"]["model"]["primary"] = json!(model_id);
+}
 
-    save_openclaw_config(&config)?;
-    info!("[Set Primary Model] Primary model set to: {}", model_id);
+/// Result of switch_primary_model
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ModelSwitchResult {
+    pub model_id: String,
+    pub previous: Option<String>,
+    pub verified: bool,
+    /// Reply from the verification completion
+    pub response: Option<String>,
+    pub latency_ms: Option<u64>,
+}
 
-    Ok(format!("Primary model set to {}", model_id))
+/// Run a one-shot local completion with `config` (written to a temp file) and return the reply text
+pub(crate) fn run_model_probe(config: &Value) -> Result<String, String> {
+    run_model_probe_with_env(config, &[])
+}
+
+/// Same as run_model_probe, with extra environment variables (e.g. cloud credentials)
+pub(crate) fn run_model_probe_with_env(config: &Value, env: &[(String, String)]) -> Result<String, String> {
+    let openclaw_path = shell::get_openclaw_path().ok_or("Cannot find openclaw command")?;
+    // OpenClaw only substitutes the native `${VAR}` form
+    let native = crate::utils::placeholders::to_native(config);
+    let config = native.as_ref().unwrap_or(config);
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    // The probe config holds API keys: owner-only, in the Manager's own directory, removed on drop
+    let data_dir = platform::get_manager_data_dir();
+    std::fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create the Manager data directory: {}", e))?;
+    let mut probe_file = tempfile::Builder::new()
+        .prefix("model-probe-")
+        .suffix(".json")
+        .tempfile_in(&data_dir)
+        .map_err(|e| format!("Failed to create probe config: {}", e))?;
+    std::io::Write::write_all(&mut probe_file, content.as_bytes())
+        .map_err(|e| format!("Failed to write probe config: {}", e))?;
+    let probe_path = probe_file.path().to_path_buf();
+
+    let mut cmd = shell::openclaw_command(&openclaw_path);
+    cmd.args(["agent", "--local", "--to", "+1234567890", "--message", "Reply with the single word OK", "--json", "--timeout", "60"])
+        .env("OPENCLAW_CONFIG_PATH", &probe_path)
+        .env("PATH", shell::get_extended_path())
+        // Keychain secrets and the values behind the probe config's placeholders, which a
+        // command built here doesn't inherit; `env` comes last so callers can override them
+        .envs(crate::utils::keychain::gateway_env())
+        .envs(crate::commands::placeholders::config_env(config))
+        .envs(env.iter().cloned());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+    let output = shell::output(&mut cmd);
+    drop(probe_file);
+    let output = output.map_err(|e| format!("Failed to run openclaw agent: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let reply = serde_json::from_str::<Value>(stdout.trim())
+        .ok()
+        .and_then(|v| v.pointer("/result/payloads/0/text").and_then(|t| t.as_str()).map(String::from));
+    match reply {
+        Some(text) if output.status.success() && !text.trim().is_empty() => Ok(text),
+        _ => Err(log_sanitizer::sanitize(format!("{}\n{}", stdout, stderr).trim())),
+    }
+}
+
+/// Switch the primary model. With `verify`, a quick completion runs against the target model first
+/// and the config is only changed if it succeeds.
+#[command]
+#[specta::specta]
+pub async fn switch_primary_model(model_id: String, verify: bool) -> Result<ModelSwitchResult, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "switch_primary_model").await;
+    info!("[Switch Model] Switching primary model to {} (verify: {})", model_id, verify);
+
+    let (provider, model) = model_id
+        .split_once('/')
+        .ok_or(format!("Model id must be 'provider/model', got '{}'", model_id))?;
+    if provider.is_empty() || model.is_empty() {
+        return Err(format!("Model id must be 'provider/model', got '{}'", model_id));
+    }
+
+    let mut config = load_openclaw_config()?;
+    if !is_known_provider(&config, provider) {
+        return Err(format!("Unknown provider '{}'. Configure it first", provider));
+    }
+    let previous = config
+        .pointer("/agents/defaults/model/primary")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    apply_primary_model(&mut config, &model_id);
+
+    let mut result = ModelSwitchResult {
+        model_id: model_id.clone(),
+        previous,
+        verified: false,
+        response: None,
+        latency_ms: None,
+    };
+    if verify {
+        let start = std::time::Instant::now();
+        let reply = run_model_probe(&config).map_err(|e| {
+            warn!("[Switch Model] Verification of {} failed, primary model unchanged", model_id);
+            format!("Verification failed, primary model was not changed:\n{}", e)
+        })?;
+        result.verified = true;
+        result.response = Some(reply);
+        result.latency_ms = Some(start.elapsed().as_millis() as u64);
+        info!("[Switch Model] {} answered in {}ms", model_id, result.latency_ms.unwrap_or(0));
+    }
+
+    save_openclaw_config(&config, "switch_primary_model")?;
+    info!("[Switch Model] Primary model switched: {:?} -> {}", result.previous, model_id);
+    Ok(result)
 }
 
 /// Add model to available list
 #[command]
+#[specta::specta]
 pub async fn add_available_model(model_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "add_available_model").await;
     info!("[Add Model] Adding model to available list: {}", model_id);
 
     let mut config = load_openclaw_config()?;
@@ -917,7 +2417,7 @@ pub async fn add_available_model(model_id: String) -> Result<String, String> {
     // Add model
     config["agents"]["defaults"]["models"][&model_id] = json!({});
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, "add_available_model")?;
     info!("[Add Model] Model {} added", model_id);
 
     Ok(format!("Model {} added", model_id))
@@ -925,7 +2425,9 @@ pub async fn add_available_model(model_id: String) -> Result<String, String> {
 
 /// Remove model from available list
 #[command]
+#[specta::specta]
 pub async fn remove_available_model(model_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "remove_available_model").await;
     info!("[Remove Model] Removing model from available list: {}", model_id);
 
     let mut config = load_openclaw_config()?;
@@ -937,16 +2439,140 @@ pub async fn remove_available_model(model_id: String) -> Result<String, String>
         models.remove(&model_id);
     }
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, "remove_available_model")?;
     info!("[Remove Model] Model {} removed", model_id);
 
     Ok(format!("Model {} removed", model_id))
 }
 
+/// Available-list entries whose provider is neither configured nor known to OpenClaw.
+/// Only reported: models of a deleted provider are dropped by delete_provider itself.
+fn unknown_provider_models(config: &Value) -> Vec<String> {
+    config
+        .pointer("/agents/defaults/models")
+        .and_then(|v| v.as_object())
+        .map(|models| {
+            models
+                .keys()
+                .filter(|model_id| !is_known_provider(config, model_id.split('/').next().unwrap_or("")))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Result of a bulk change to the available model list
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AvailableModelsSaved {
+    pub message: String,
+    /// Listed models whose provider isn't configured or known, kept as they are; remove them
+    /// with remove_available_model or fix_orphans
+    pub unknown_provider_models: Vec<String>,
+}
+
+/// Replace the available model list in one write (existing per-model settings are kept)
+#[command]
+#[specta::specta]
+pub async fn set_available_models(model_ids: Vec<String>) -> Result<AvailableModelsSaved, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "set_available_models").await;
+    info!("[Set Models] Setting available models: {} entries", model_ids.len());
+
+    let mut config = load_openclaw_config()?;
+    let existing = config
+        .pointer("/agents/defaults/models")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut models = serde_json::Map::new();
+    for model_id in model_ids {
+        let model_id = model_id.trim().to_string();
+        if model_id.is_empty() || models.contains_key(&model_id) {
+            continue;
+        }
+        let settings = existing.get(&model_id).cloned().unwrap_or(json!({}));
+        models.insert(model_id, settings);
+    }
+
+    if config.get("agents").is_none() {
+        config["agents"] = json!({});
+    }
+    if config["agents"].get("defaults").is_none() {
+        config["agents"]["defaults"] = json!({});
+    }
+    config["agents"]["defaults"]["models"] = Value::Object(models);
+
+    let unknown = unknown_provider_models(&config);
+    if !unknown.is_empty() {
+        warn!("[Set Models] Models of unknown providers: {:?}", unknown);
+    }
+    let count = config.pointer("/agents/defaults/models").and_then(|v| v.as_object()).map(|m| m.len()).unwrap_or(0);
+
+    save_openclaw_config(&config, "set_available_models")?;
+    info!("[Set Models] {} available models saved", count);
+
+    Ok(AvailableModelsSaved {
+        message: format!("{} models available", count),
+        unknown_provider_models: unknown,
+    })
+}
+
+/// Add several models of one provider to the available list in one write
+#[command]
+#[specta::specta]
+pub async fn bulk_add_models(provider: String, model_ids: Vec<String>) -> Result<AvailableModelsSaved, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "bulk_add_models").await;
+    info!("[Bulk Add Models] Adding {} models for provider {}", model_ids.len(), provider);
+
+    let mut config = load_openclaw_config()?;
+
+    if !is_known_provider(&config, &provider) {
+        return Err(format!("Provider '{}' is not configured", provider));
+    }
+
+    if config.get("agents").is_none() {
+        config["agents"] = json!({});
+    }
+    if config["agents"].get("defaults").is_none() {
+        config["agents"]["defaults"] = json!({});
+    }
+    if config["agents"]["defaults"].get("models").is_none() {
+        config["agents"]["defaults"]["models"] = json!({});
+    }
+
+    let mut added = 0;
+    for model_id in &model_ids {
+        let model_id = model_id.trim();
+        // Accept both "model" and "provider/model"
+        let model_id = model_id.strip_prefix(&format!("{}/", provider)).unwrap_or(model_id);
+        if model_id.is_empty() {
+            continue;
+        }
+        let full_id = format!("{}/{}", provider, model_id);
+        if config["agents"]["defaults"]["models"].get(&full_id).is_none() {
+            config["agents"]["defaults"]["models"][&full_id] = json!({});
+            added += 1;
+        }
+    }
+
+    let unknown = unknown_provider_models(&config);
+    if !unknown.is_empty() {
+        warn!("[Bulk Add Models] Models of unknown providers: {:?}", unknown);
+    }
+
+    save_openclaw_config(&config, "bulk_add_models")?;
+    info!("[Bulk Add Models] {} new models added", added);
+
+    Ok(AvailableModelsSaved {
+        message: format!("{} models added", added),
+        unknown_provider_models: unknown,
+    })
+}
+
 // ============ MCP Configuration Commands ============
 
 /// Load MCP config from separate mcps.json file
-fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, String> {
+pub(crate) fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, String> {
     let config_path = platform::get_mcp_config_file_path();
     let path = std::path::Path::new(&config_path);
     
@@ -964,13 +2590,14 @@ fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, String> {
 }
 
 /// Save MCP config to separate mcps.json file AND sync to ~/.mcporter/mcporter.json
-fn save_mcp_config_file(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
+pub(crate) fn save_mcp_config_file(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
+    locks::warn_if_unlocked(ConfigResource::Mcps, "save_mcp_config_file");
     // 1. Save to Manager's private config (mcps.json)
     let config_path = platform::get_mcp_config_file_path();
     let content = serde_json::to_string_pretty(configs)
         .map_err(|e| format!("Failed to serialize MCP config: {}", e))?;
     
-    std::fs::write(&config_path, content)
+    file::write_file(&config_path, &content)
         .map_err(|e| format!("Failed to write mcps.json: {}", e))?;
     
     // 2. Sync enabled servers to system mcporter config (~/.mcporter/mcporter.json)
@@ -982,9 +2609,68 @@ fn save_mcp_config_file(configs: &HashMap<String, MCPConfig>) -> Result<(), Stri
     Ok(())
 }
 
-fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
-    let mcporter_path = platform::get_mcporter_config_file_path();
-    let path = std::path::Path::new(&mcporter_path);
+/// Check that the Docker CLI is installed and the daemon is reachable
+pub(crate) fn check_docker_available() -> Result<(), String> {
+    if !shell::command_exists("docker") {
+        return Err("Docker is not installed. Install Docker Desktop (or docker engine) to use Docker MCP servers".to_string());
+    }
+    let output = shell::run_command("docker", &["info", "--format", "{{.ServerVersion}}"])
+        .map_err(|e| format!("Failed to run docker: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err("Docker is installed but not running. Start Docker and try again".to_string())
+    }
+}
+
+/// stdio command that runs a Docker MCP server. Env values are not put on the command
+/// line: `-e KEY` forwards them from the process env, which MCP clients set from `env`.
+fn docker_mcp_command(docker: &DockerMCPConfig) -> (String, Vec<String>) {
+    let mut args = vec!["run".to_string(), "-i".to_string(), "--rm".to_string()];
+    if let Some(network) = docker.network.as_ref().filter(|n| !n.is_empty()) {
+        args.push("--network".to_string());
+        args.push(network.clone());
+    }
+    for volume in &docker.volumes {
+        args.push("-v".to_string());
+        args.push(volume.clone());
+    }
+    let mut env_keys: Vec<&String> = docker.env.keys().collect();
+    env_keys.sort();
+    for key in env_keys {
+        args.push("-e".to_string());
+        args.push(key.clone());
+    }
+    args.push(docker.image.clone());
+    args.extend(docker.args.iter().cloned());
+    ("docker".to_string(), args)
+}
+
+/// Config as seen by MCP clients: Docker servers become a `docker run` stdio entry
+fn resolve_mcp_client_config(config: &MCPConfig) -> MCPConfig {
+    match &config.docker {
+        Some(docker) => {
+            let (command, args) = docker_mcp_command(docker);
+            let mut env = config.env.clone();
+            env.extend(docker.env.clone());
+            MCPConfig {
+                command,
+                args,
+                env,
+                url: String::new(),
+                enabled: config.enabled,
+                docker: None,
+                install: None,
+                external: None,
+            }
+        }
+        None => MCPConfig { install: None, external: None, ..config.clone() },
+    }
+}
+
+fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
+    let mcporter_path = platform::get_mcporter_config_file_path();
+    let path = std::path::Path::new(&mcporter_path);
 
     // Create ~/.mcporter directory if missing
     if let Some(parent) = path.parent() {
@@ -1016,7 +2702,7 @@ fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String>
         if config.enabled {
             // Convert MCPConfig to serde_json::Value
             // Note: We skip 'enabled' field as mcporter doesn't use it (presence = enabled)
-            let mut server_val = serde_json::to_value(config)
+            let mut server_val = serde_json::to_value(resolve_mcp_client_config(config))
                 .map_err(|e| format!("Failed to serialize config for {}: {}", name, e))?;
             
             if let Some(obj) = server_val.as_object_mut() {
@@ -1045,6 +2731,7 @@ fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String>
 
 /// Get MCP configuration
 #[command]
+#[specta::specta]
 pub async fn get_mcp_config() -> Result<HashMap<String, MCPConfig>, String> {
     info!("[MCP Config] Getting MCP configuration...");
     
@@ -1056,15 +2743,25 @@ pub async fn get_mcp_config() -> Result<HashMap<String, MCPConfig>, String> {
 
 /// Save MCP configuration
 #[command]
+#[specta::specta]
 pub async fn save_mcp_config(
     name: String,
     config: Option<MCPConfig>,
 ) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "save_mcp_config").await;
     info!("[Save MCP] Saving MCP configuration for: {}", name);
     
     let mut configs = load_mcp_config_file()?;
     
     if let Some(mcp) = config {
+        if let Some(docker) = &mcp.docker {
+            if docker.image.trim().is_empty() {
+                return Err("Docker MCP server needs an image".to_string());
+            }
+            if !shell::command_exists("docker") {
+                return Err("Docker is not installed. Install Docker to use Docker MCP servers".to_string());
+            }
+        }
         configs.insert(name.clone(), mcp);
         info!("[Save MCP] Updated configuration for {}", name);
     } else {
@@ -1076,36 +2773,303 @@ pub async fn save_mcp_config(
     Ok(format!("MCP configuration saved for {}", name))
 }
 
+/// A server defined in both mcps.json and mcporter.json with different definitions
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MCPSyncConflict {
+    pub name: String,
+    /// Definition the Manager would sync
+    pub manager: serde_json::Value,
+    /// Definition currently in mcporter.json
+    pub mcporter: serde_json::Value,
+}
+
+/// Result of importing servers from mcporter.json
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MCPImportResult {
+    pub imported: Vec<String>,
+    pub conflicts: Vec<MCPSyncConflict>,
+}
+
+fn load_mcporter_servers() -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let mcporter_path = platform::get_mcporter_config_file_path();
+    if !std::path::Path::new(&mcporter_path).exists() {
+        return Ok(serde_json::Map::new());
+    }
+    let content = std::fs::read_to_string(&mcporter_path)
+        .map_err(|e| format!("Failed to read mcporter.json: {}", e))?;
+    let root: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse mcporter.json: {}", e))?;
+    Ok(root
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Definition as written to mcporter.json (client view, without 'enabled')
+fn mcporter_entry(config: &MCPConfig) -> serde_json::Value {
+    let mut value = serde_json::to_value(resolve_mcp_client_config(config)).unwrap_or_default();
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("enabled");
+    }
+    value
+}
+
+fn find_mcporter_conflicts(
+    configs: &HashMap<String, MCPConfig>,
+    servers: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<MCPSyncConflict> {
+    let mut conflicts: Vec<MCPSyncConflict> = servers
+        .iter()
+        .filter_map(|(name, external)| {
+            let manager = mcporter_entry(configs.get(name)?);
+            // Compare through MCPConfig so key order and defaults don't count as differences
+            let parsed = serde_json::from_value::<MCPConfig>(external.clone())
+                .map(|c| mcporter_entry(&c))
+                .unwrap_or_else(|_| external.clone());
+            (parsed != manager).then(|| MCPSyncConflict {
+                name: name.clone(),
+                manager,
+                mcporter: external.clone(),
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.name.cmp(&b.name));
+    conflicts
+}
+
+/// List servers whose mcps.json and mcporter.json definitions differ
+#[command]
+#[specta::specta]
+pub async fn get_mcporter_conflicts() -> Result<Vec<MCPSyncConflict>, String> {
+    let configs = load_mcp_config_file()?;
+    let servers = load_mcporter_servers()?;
+    Ok(find_mcporter_conflicts(&configs, &servers))
+}
+
+/// Pull servers defined by other tools in mcporter.json into mcps.json.
+/// Names that already exist in mcps.json are never overwritten; differing ones are reported.
+#[command]
+#[specta::specta]
+pub async fn import_from_mcporter() -> Result<MCPImportResult, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "import_from_mcporter").await;
+    info!("[MCP Import] Importing servers from mcporter.json...");
+    let mut configs = load_mcp_config_file()?;
+    let servers = load_mcporter_servers()?;
+    let conflicts = find_mcporter_conflicts(&configs, &servers);
+
+    let mut imported = Vec::new();
+    for (name, value) in &servers {
+        if configs.contains_key(name) {
+            continue;
+        }
+        match serde_json::from_value::<MCPConfig>(value.clone()) {
+            Ok(mut config) => {
+                config.enabled = true;
+                config.external = Some("mcporter".to_string());
+                configs.insert(name.clone(), config);
+                imported.push(name.clone());
+            }
+            Err(e) => warn!("[MCP Import] Skipping {}: {}", name, e),
+        }
+    }
+    imported.sort();
+
+    if !imported.is_empty() {
+        save_mcp_config_file(&configs)?;
+    }
+    info!(
+        "[MCP Import] Imported {} servers, {} conflicts",
+        imported.len(),
+        conflicts.len()
+    );
+    Ok(MCPImportResult { imported, conflicts })
+}
+
+/// Resolve a conflict by keeping the Manager ("manager") or the mcporter ("mcporter") definition
+#[command]
+#[specta::specta]
+pub async fn resolve_mcporter_conflict(name: String, keep: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "resolve_mcporter_conflict").await;
+    info!("[MCP Import] Resolving conflict for {}: keep {}", name, keep);
+    let mut configs = load_mcp_config_file()?;
+    match keep.as_str() {
+        "manager" => {}
+        "mcporter" => {
+            let value = load_mcporter_servers()?
+                .remove(&name)
+                .ok_or(format!("{} is not defined in mcporter.json", name))?;
+            let mut config: MCPConfig = serde_json::from_value(value)
+                .map_err(|e| format!("Invalid mcporter definition for {}: {}", name, e))?;
+            config.enabled = true;
+            config.external = Some("mcporter".to_string());
+            configs.insert(name.clone(), config);
+        }
+        other => return Err(format!("Unknown choice '{}', expected 'manager' or 'mcporter'", other)),
+    }
+    // Saving re-syncs, so mcporter.json ends up matching the kept definition
+    save_mcp_config_file(&configs)?;
+    Ok(format!("Conflict resolved for {}", name))
+}
+
+/// Result of exporting mcps.json to another MCP client's config file
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MCPClientExport {
+    pub client: String,
+    pub path: String,
+    pub exported: Vec<String>,
+    /// Servers the client can't run (with reason)
+    pub skipped: Vec<String>,
+    pub changes: Vec<json_diff::JsonChange>,
+    /// Credentials found in the exported server definitions
+    pub secrets: Vec<SecretFinding>,
+    pub written: bool,
+}
+
+fn mcp_client_config_path(client: &str) -> Result<String, String> {
+    match client {
+        "claude-desktop" => Ok(platform::get_claude_desktop_config_file_path()),
+        "cursor" => Ok(platform::get_cursor_mcp_config_file_path()),
+        other => Err(format!("Unknown MCP client '{}', expected 'claude-desktop' or 'cursor'", other)),
+    }
+}
+
+/// Build the merged client config. Both clients use { "mcpServers": { name: {command,args,env} | {url} } };
+/// other keys and servers not managed here are kept, disabled servers are removed.
+/// `secrets` is the secrets_scan policy; None refuses when credentials are found.
+fn build_mcp_client_export(client: &str, secrets: Option<&str>) -> Result<(MCPClientExport, serde_json::Value), String> {
+    let path = mcp_client_config_path(client)?;
+    let configs = load_mcp_config_file()?;
+
+    let current: serde_json::Value = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path, e))?,
+        Err(_) => serde_json::json!({}),
+    };
+    let mut merged = current.clone();
+    if !merged.is_object() {
+        merged = serde_json::json!({});
+    }
+    if !merged.get("mcpServers").map(|v| v.is_object()).unwrap_or(false) {
+        merged["mcpServers"] = serde_json::json!({});
+    }
+    let servers = merged["mcpServers"].as_object_mut().unwrap();
+
+    let mut names: Vec<&String> = configs.keys().collect();
+    names.sort();
+    let mut entries = serde_json::Map::new();
+    let mut skipped = Vec::new();
+    for name in names {
+        let config = &configs[name];
+        if !config.enabled {
+            servers.remove(name);
+            continue;
+        }
+        // Claude Desktop only launches local stdio servers from its config file
+        if client == "claude-desktop" && config.docker.is_none() && config.command.is_empty() {
+            skipped.push(format!("{} (remote URL servers are not supported by Claude Desktop)", name));
+            continue;
+        }
+        entries.insert(name.clone(), mcporter_entry(config));
+    }
+
+    let entries = serde_json::Value::Object(entries);
+    let found = secrets_scan::scan(&entries);
+    let entries = secrets_scan::apply_policy(&entries, secrets)?;
+    let exported: Vec<String> = entries.as_object().map(|m| m.keys().cloned().collect()).unwrap_or_default();
+    if let Some(map) = entries.as_object() {
+        servers.extend(map.clone());
+    }
+
+    let export = MCPClientExport {
+        client: client.to_string(),
+        path,
+        exported,
+        skipped,
+        changes: json_diff::diff_masked(&current, &merged),
+        secrets: found,
+        written: false,
+    };
+    Ok((export, merged))
+}
+
+/// Preview what export_mcp_to_client would change in the client's config file
+/// (secrets are listed, and kept in the diff unless `secrets` is "redact")
+#[command]
+#[specta::specta]
+pub async fn preview_mcp_export(client: String, secrets: Option<String>) -> Result<MCPClientExport, String> {
+    let (export, _) = build_mcp_client_export(&client, Some(secrets.as_deref().unwrap_or("include")))?;
+    Ok(export)
+}
+
+/// Write/merge enabled MCP servers into another client's config ("claude-desktop" or "cursor").
+/// `secrets` is "redact" or "include"; required when the servers carry credentials.
+#[command]
+#[specta::specta]
+pub async fn export_mcp_to_client(client: String, secrets: Option<String>) -> Result<MCPClientExport, String> {
+    info!("[MCP Export] Exporting MCP servers to {}", client);
+    let (mut export, merged) = build_mcp_client_export(&client, secrets.as_deref())?;
+    if export.changes.is_empty() {
+        info!("[MCP Export] {} is already up to date", export.path);
+        return Ok(export);
+    }
+
+    let path = std::path::Path::new(&export.path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    if path.exists() {
+        let backup = format!("{}.bak", export.path);
+        std::fs::copy(path, &backup)
+            .map_err(|e| format!("Failed to back up {}: {}", export.path, e))?;
+    }
+    let content = serde_json::to_string_pretty(&merged)
+        .map_err(|e| format!("Failed to serialize client config: {}", e))?;
+    std::fs::write(path, content)
+        .map_err(|e| format!("Failed to write {}: {}", export.path, e))?;
+
+    export.written = true;
+    info!(
+        "[MCP Export] Wrote {} servers to {} ({} skipped)",
+        export.exported.len(),
+        export.path,
+        export.skipped.len()
+    );
+    Ok(export)
+}
+
 /// Install MCP server from a Git repository URL
 #[command]
+#[specta::specta]
 pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "install_mcp_from_git").await;
     info!("[MCP Install] Installing MCP from: {}", url);
+    let (repo_name, entry) = prepare_git_mcp(&url)?;
 
-    // Extract repo name from URL (e.g. "excalidraw-mcp" from "https://github.com/excalidraw/excalidraw-mcp")
-    let repo_name = url
-        .trim_end_matches('/')
-        .trim_end_matches(".git")
-        .rsplit('/')
-        .next()
-        .ok_or_else(|| "Invalid repository URL".to_string())?
-        .to_string();
-
-    if repo_name.is_empty() {
-        return Err("Could not extract repository name from URL".to_string());
-    }
+    info!("[MCP Install] Configuring MCP in mcps.json...");
+    let mut configs = load_mcp_config_file()?;
+    configs.insert(repo_name.clone(), entry);
+    save_mcp_config_file(&configs)?;
+    info!("[MCP Install] Installation complete for {}", repo_name);
+    Ok(format!("Successfully installed MCP: {}", repo_name))
+}
 
+/// Clone and build a git MCP server into the mcps dir; returns its name and mcps.json entry
+fn prepare_git_mcp(url: &str) -> Result<(String, MCPConfig), String> {
+    let repo_name = git_repo_name(url)?;
     info!("[MCP Install] Repository name: {}", repo_name);
 
+    if !shell::command_exists("git") {
+        return Err("Git is not installed. Install Git first (Settings > Environment > Install Git)".to_string());
+    }
+
     // Create mcps directory if it doesn't exist
     let mcps_dir = platform::get_mcp_install_dir();
     std::fs::create_dir_all(&mcps_dir)
         .map_err(|e| format!("Failed to create mcps directory: {}", e))?;
 
-    let install_path = if platform::is_windows() {
-        format!("{}\\{}", mcps_dir, repo_name)
-    } else {
-        format!("{}/{}", mcps_dir, repo_name)
-    };
+    let install_path = platform::path_string(&platform::join_path(&mcps_dir, &[&repo_name]));
 
     // Remove existing directory if present (re-install)
     if std::path::Path::new(&install_path).exists() {
@@ -1125,101 +3089,329 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
     }
     info!("[MCP Install] Clone successful");
 
-    // Step 2: npm install
-    info!("[MCP Install] Running npm install...");
-    let npm_cmd = if platform::is_windows() { "npm.cmd" } else { "npm" };
+    // Step 2/3: npm install + build
+    npm_install_and_build(&install_path)?;
 
-    let mut npm_install = std::process::Command::new(npm_cmd);
-    npm_install.args(&["install"]).current_dir(&install_path);
+    // Step 4: Entry for mcps.json
+    // Determine the entry point (dist/index.js or index.js)
+    let dist_index = platform::path_string(&platform::join_path(&install_path, &["dist", "index.js"]));
 
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-        npm_install.creation_flags(0x08000000); // CREATE_NO_WINDOW
-    }
+    let entry_point = if std::path::Path::new(&dist_index).exists() {
+        dist_index
+    } else {
+        let root_index = platform::path_string(&platform::join_path(&install_path, &["index.js"]));
+        if std::path::Path::new(&root_index).exists() {
+            root_index
+        } else {
+            dist_index
+        }
+    };
+
+    let entry = MCPConfig {
+        command: "node".to_string(),
+        args: vec![entry_point, "--stdio".to_string()],
+        env: HashMap::new(),
+        url: String::new(),
+        enabled: true,
+        docker: None,
+        install: Some(MCPInstallInfo {
+            source: "git".to_string(),
+            origin: url.to_string(),
+            commit: git_head_commit(&install_path),
+            installed_at: Some(chrono::Utc::now().to_rfc3339()),
+        }),
+        external: None,
+    };
+    Ok((repo_name, entry))
+}
+
+/// Run `<pm> install` and `<pm> run build` in a git-installed MCP server (build is optional)
+fn npm_install_and_build(install_path: &str) -> Result<(), String> {
+    let pm = node_pm::active();
+    info!("[MCP Install] Running {} install...", pm.name());
 
-    let install_output = npm_install.output()
-        .map_err(|e| format!("Failed to run npm install: {}", e))?;
+    let mut npm_install = node_pm::command(&format!("{} install", pm.name()));
+    npm_install.current_dir(install_path);
+
+    let install_output = shell::output(&mut npm_install)
+        .map_err(|e| format!("Failed to run {} install: {}", pm.name(), e))?;
 
     if !install_output.status.success() {
         let stderr = String::from_utf8_lossy(&install_output.stderr);
-        return Err(format!("npm install failed: {}", stderr));
+        return Err(format!("{} install failed: {}", pm.name(), stderr));
     }
-    info!("[MCP Install] npm install successful");
-
-    // Step 3: npm run build
-    info!("[MCP Install] Running npm run build...");
-    let mut npm_build = std::process::Command::new(npm_cmd);
-    npm_build.args(&["run", "build"]).current_dir(&install_path);
+    info!("[MCP Install] {} install successful", pm.name());
 
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-        npm_build.creation_flags(0x08000000);
-    }
+    info!("[MCP Install] Running {} run build...", pm.name());
+    let mut npm_build = node_pm::command(&format!("{} run build", pm.name()));
+    npm_build.current_dir(install_path);
 
-    let build_output = npm_build.output()
-        .map_err(|e| format!("Failed to run npm run build: {}", e))?;
+    let build_output = shell::output(&mut npm_build)
+        .map_err(|e| format!("Failed to run {} run build: {}", pm.name(), e))?;
 
     if !build_output.status.success() {
         let stderr = String::from_utf8_lossy(&build_output.stderr);
-        warn!("[MCP Install] npm run build failed (may not have a build step): {}", stderr);
+        warn!("[MCP Install] {} run build failed (may not have a build step): {}", pm.name(), stderr);
         // Don't fail — some MCPs don't need a build step
     } else {
-        info!("[MCP Install] npm run build successful");
+        info!("[MCP Install] {} run build successful", pm.name());
     }
+    Ok(())
+}
+
+/// Current commit of a git checkout
+fn git_head_commit(repo_path: &str) -> Option<String> {
+    shell::run_command_output("git", &["-C", repo_path, "rev-parse", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Split an npm package spec into (name without version, MCP server name)
+fn parse_npm_package_spec(spec: &str) -> Result<(String, String), String> {
+    let re = regex::Regex::new(r"^((?:@[a-z0-9][a-z0-9._~-]*/)?[a-z0-9][a-z0-9._~-]*)(?:@[A-Za-z0-9.^~<>=*+-]+)?$").unwrap();
+    let caps = re
+        .captures(spec)
+        .ok_or(format!("Invalid npm package name: {}", spec))?;
+    let package = caps[1].to_string();
+    let server_name = package.rsplit('/').next().unwrap_or(&package).to_string();
+    Ok((package, server_name))
+}
+
+/// Entry script of an installed package: its first `bin`, else `main`, else index.js
+fn npm_package_entry(package_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    let manifest: Value = serde_json::from_str(&std::fs::read_to_string(package_dir.join("package.json")).ok()?).ok()?;
+    let relative = match manifest.get("bin") {
+        Some(Value::String(bin)) => Some(bin.clone()),
+        Some(Value::Object(bins)) => bins.values().find_map(|v| v.as_str().map(|s| s.to_string())),
+        _ => None,
+    }
+    .or_else(|| manifest.get("main").and_then(|v| v.as_str()).map(|s| s.to_string()))
+    .unwrap_or_else(|| "index.js".to_string());
+    Some(package_dir.join(relative))
+}
+
+/// Install an MCP server published to npm (e.g. @modelcontextprotocol/server-filesystem).
+/// `mode` "npx" (default) configures an `npx -y <package>` entry (`pnpm dlx` when pnpm is the
+/// package manager) that fetches on first run; "local" installs the package into the mcps dir
+/// and runs it with node.
+/// `args_preset` is appended to the server command (e.g. allowed directories).
+#[command]
+#[specta::specta]
+pub async fn install_mcp_from_npm(
+    package: String,
+    args_preset: Option<Vec<String>>,
+    mode: Option<String>,
+) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "install_mcp_from_npm").await;
+    let (server_name, entry) = prepare_npm_mcp(&package, args_preset.unwrap_or_default(), mode.as_deref())?;
 
-    // Step 4: Auto-configure in mcps.json
-    info!("[MCP Install] Configuring MCP in mcps.json...");
     let mut configs = load_mcp_config_file()?;
+    configs.insert(server_name.clone(), entry);
+    save_mcp_config_file(&configs)?;
 
-    // Determine the entry point (dist/index.js or index.js)
-    let dist_index = if platform::is_windows() {
-        format!("{}\\dist\\index.js", install_path)
-    } else {
-        format!("{}/dist/index.js", install_path)
-    };
+    info!("[MCP Install] Installation complete for {}", server_name);
+    Ok(format!("Successfully installed MCP: {}", server_name))
+}
 
-    let entry_point = if std::path::Path::new(&dist_index).exists() {
-        dist_index
-    } else {
-        let root_index = if platform::is_windows() {
-            format!("{}\\index.js", install_path)
-        } else {
-            format!("{}/index.js", install_path)
-        };
-        if std::path::Path::new(&root_index).exists() {
-            root_index
-        } else {
-            dist_index
+/// Extract repo name from URL (e.g. "excalidraw-mcp" from "https://github.com/excalidraw/excalidraw-mcp"),
+/// which names both the install directory and the mcps.json entry
+fn git_repo_name(url: &str) -> Result<String, String> {
+    let repo_name = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit('/')
+        .next()
+        .ok_or_else(|| "Invalid repository URL".to_string())?
+        .to_string();
+    if repo_name.is_empty() {
+        return Err("Could not extract repository name from URL".to_string());
+    }
+    Ok(repo_name)
+}
+
+/// Server name a bulk install request ends up under (its install directory for git and local
+/// npm installs); None when the target cannot be parsed, which the install itself reports
+fn mcp_request_name(request: &MCPInstallRequest) -> Option<String> {
+    match request.source.as_str() {
+        "git" => git_repo_name(request.target.trim()).ok(),
+        "npm" => parse_npm_package_spec(request.target.trim()).ok().map(|(_, server_name)| server_name),
+        _ => None,
+    }
+}
+
+/// One server of a bulk MCP install
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MCPInstallRequest {
+    /// "git" (repository URL) or "npm" (package spec)
+    pub source: String,
+    pub target: String,
+    /// npm install mode, see `install_mcp_from_npm`
+    pub mode: Option<String>,
+    pub args_preset: Option<Vec<String>>,
+}
+
+/// Install several MCP servers in parallel, at most `concurrency` at a time (default 3), with
+/// `task-progress` events per server. The servers that installed are added to mcps.json in one save.
+/// Requests that resolve to the same server name are installed once.
+#[command]
+#[specta::specta]
+pub async fn install_mcps(
+    requests: Vec<MCPInstallRequest>,
+    concurrency: Option<usize>,
+) -> Result<Vec<BulkItemResult>, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "install_mcps").await;
+    // Two requests for the same server would clone or install into the same directory at once
+    let mut seen = std::collections::HashMap::new();
+    let mut duplicates = Vec::new();
+    let mut items: Vec<(String, MCPInstallRequest)> = Vec::new();
+    for request in requests {
+        let target = request.target.trim().to_string();
+        if target.is_empty() {
+            continue;
         }
-    };
+        let key = mcp_request_name(&request).unwrap_or_else(|| target.clone());
+        match seen.get(&key) {
+            Some(first) if *first == target => {}
+            Some(first) => duplicates.push(BulkItemResult::new(
+                &target,
+                Err(format!("Installs into '{}' like {}, skipped", key, first)),
+            )),
+            None => {
+                seen.insert(key, target.clone());
+                items.push((target, request));
+            }
+        }
+    }
+    if items.is_empty() {
+        return Err("No MCP servers to install".to_string());
+    }
+    let workers = task_pool::workers_for(concurrency, items.len());
+    info!("[MCP Install] Installing {} MCP server(s), {} at a time", items.len(), workers);
 
-    configs.insert(repo_name.clone(), MCPConfig {
-        command: "node".to_string(),
-        args: vec![entry_point, "--stdio".to_string()],
-        env: HashMap::new(),
-        url: String::new(),
-        enabled: true,
-    });
+    let targets: Vec<String> = items.iter().map(|(target, _)| target.clone()).collect();
+    let results = task_pool::run(
+        items,
+        workers,
+        |request| match request.source.as_str() {
+            "git" => prepare_git_mcp(request.target.trim()),
+            "npm" => prepare_npm_mcp(&request.target, request.args_preset.unwrap_or_default(), request.mode.as_deref()),
+            other => Err(format!("Unknown MCP source '{}', expected 'git' or 'npm'", other)),
+        },
+        |progress| events::emit(ManagerEvent::item_progress("install-mcps", &progress)),
+    );
 
-    save_mcp_config_file(&configs)?;
-    info!("[MCP Install] Installation complete for {}", repo_name);
-    Ok(format!("Successfully installed MCP: {}", repo_name))
+    let mut configs = load_mcp_config_file()?;
+    let mut installed = 0;
+    let report: Vec<BulkItemResult> = targets
+        .iter()
+        .zip(results)
+        .map(|(target, result)| {
+            let result = result.map(|(name, entry)| {
+                configs.insert(name.clone(), entry);
+                installed += 1;
+                format!("Successfully installed MCP: {}", name)
+            });
+            BulkItemResult::new(target, result)
+        })
+        .chain(duplicates)
+        .collect();
+    if installed > 0 {
+        save_mcp_config_file(&configs)?;
+    }
+    info!("[MCP Install] Bulk install finished: {} of {} installed", installed, report.len());
+    Ok(report)
+}
+
+/// Build the mcps.json entry of an npm MCP server (installing it first in "local" mode);
+/// returns the server name and entry
+fn prepare_npm_mcp(package: &str, preset: Vec<String>, mode: Option<&str>) -> Result<(String, MCPConfig), String> {
+    let mode = mode.unwrap_or("npx");
+    info!("[MCP Install] Installing MCP from npm: {} (mode={})", package, mode);
+    let spec = package.trim();
+    let (package_name, server_name) = parse_npm_package_spec(spec)?;
+
+    let entry = match mode {
+        "npx" => {
+            let mut runner = node_pm::active().exec_prefix().split_whitespace();
+            let command = runner.next().unwrap_or("npx").to_string();
+            let mut args: Vec<String> = runner.map(String::from).collect();
+            args.push(spec.to_string());
+            args.extend(preset);
+            MCPConfig {
+                command,
+                args,
+                env: HashMap::new(),
+                url: String::new(),
+                enabled: true,
+                docker: None,
+                install: Some(MCPInstallInfo {
+                    source: "npm".to_string(),
+                    origin: spec.to_string(),
+                    commit: None,
+                    installed_at: Some(chrono::Utc::now().to_rfc3339()),
+                }),
+                external: None,
+            }
+        }
+        "local" => {
+            let mcps_dir = platform::get_mcp_install_dir();
+            let install_path = std::path::Path::new(&mcps_dir).join(&server_name);
+            std::fs::create_dir_all(&install_path)
+                .map_err(|e| format!("Failed to create install directory: {}", e))?;
+
+            let pm = node_pm::active();
+            info!("[MCP Install] Running {} add {} in {}", pm.name(), spec, install_path.display());
+            let mut npm_install = node_pm::command(pm.name());
+            npm_install.args(pm.add_args(spec)).current_dir(&install_path);
+
+            let output = shell::output(&mut npm_install)
+                .map_err(|e| format!("Failed to run {} install: {}", pm.name(), e))?;
+            if !output.status.success() {
+                return Err(format!("{} install failed: {}", pm.name(), String::from_utf8_lossy(&output.stderr)));
+            }
+
+            let package_dir = package_name
+                .split('/')
+                .fold(install_path.join("node_modules"), |dir, part| dir.join(part));
+            let entry_point = npm_package_entry(&package_dir)
+                .filter(|p| p.exists())
+                .ok_or(format!("Installed {} but could not find its entry script", package_name))?;
+
+            let mut args = vec![entry_point.to_string_lossy().to_string()];
+            args.extend(preset);
+            MCPConfig {
+                command: "node".to_string(),
+                args,
+                env: HashMap::new(),
+                url: String::new(),
+                enabled: true,
+                docker: None,
+                install: Some(MCPInstallInfo {
+                    source: "npm".to_string(),
+                    origin: spec.to_string(),
+                    commit: None,
+                    installed_at: Some(chrono::Utc::now().to_rfc3339()),
+                }),
+                external: None,
+            }
+        }
+        other => return Err(format!("Unknown install mode '{}', expected 'npx' or 'local'", other)),
+    };
+    Ok((server_name, entry))
 }
 
 /// Uninstall an MCP server
 #[command]
+#[specta::specta]
 pub async fn uninstall_mcp(name: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "uninstall_mcp").await;
     info!("[MCP Uninstall] Uninstalling MCP: {}", name);
 
     // Remove directory
     let mcps_dir = platform::get_mcp_install_dir();
-    let install_path = if platform::is_windows() {
-        format!("{}\\{}", mcps_dir, name)
-    } else {
-        format!("{}/{}", mcps_dir, name)
-    };
+    let install_path = platform::path_string(&platform::join_path(&mcps_dir, &[&name]));
 
     if std::path::Path::new(&install_path).exists() {
         std::fs::remove_dir_all(&install_path)
@@ -1236,8 +3428,146 @@ pub async fn uninstall_mcp(name: String) -> Result<String, String> {
     Ok(format!("Successfully uninstalled MCP: {}", name))
 }
 
+/// Update status of a git-installed MCP server
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MCPUpdateInfo {
+    pub name: String,
+    pub origin: String,
+    pub installed_commit: Option<String>,
+    pub latest_commit: Option<String>,
+    pub update_available: bool,
+    pub error: Option<String>,
+}
+
+fn mcp_install_path(name: &str) -> String {
+    let mcps_dir = platform::get_mcp_install_dir();
+    platform::path_string(&platform::join_path(&mcps_dir, &[name]))
+}
+
+/// Git-installed servers: recorded installs, plus older installs detected by their .git dir
+fn git_installed_mcps(configs: &HashMap<String, MCPConfig>) -> Vec<(String, String)> {
+    let mut servers: Vec<(String, String)> = configs
+        .iter()
+        .filter_map(|(name, config)| {
+            if let Some(install) = config.install.as_ref().filter(|i| i.source == "git") {
+                return Some((name.clone(), install.origin.clone()));
+            }
+            let path = mcp_install_path(name);
+            if std::path::Path::new(&path).join(".git").exists() {
+                let origin = shell::run_command_output("git", &["-C", &path, "remote", "get-url", "origin"]).ok()?;
+                return Some((name.clone(), origin));
+            }
+            None
+        })
+        .collect();
+    servers.sort();
+    servers
+}
+
+/// Check git-installed MCP servers for upstream changes (git fetch + compare)
+#[command]
+#[specta::specta]
+pub async fn check_mcp_updates() -> Result<Vec<MCPUpdateInfo>, String> {
+    info!("[MCP Update] Checking for MCP server updates...");
+    let configs = load_mcp_config_file()?;
+
+    let installed = git_installed_mcps(&configs);
+    let total = installed.len();
+    let updates = installed
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, origin))| {
+            events::emit(ManagerEvent::TaskProgress {
+                task: "check-mcp-updates".to_string(),
+                current: index,
+                total,
+                message: format!("Checking {}", name),
+            });
+            let path = mcp_install_path(&name);
+            let installed_commit = git_head_commit(&path);
+            let fetched = shell::run_command_output("git", &["-C", &path, "fetch", "--quiet", "origin"]);
+            let latest = fetched.and_then(|_| {
+                shell::run_command_output("git", &["-C", &path, "rev-parse", "@{u}"])
+                    .or_else(|_| shell::run_command_output("git", &["-C", &path, "rev-parse", "origin/HEAD"]))
+            });
+            match latest {
+                Ok(latest_commit) => MCPUpdateInfo {
+                    update_available: installed_commit.as_deref() != Some(latest_commit.as_str()),
+                    name,
+                    origin,
+                    installed_commit,
+                    latest_commit: Some(latest_commit),
+                    error: None,
+                },
+                Err(e) => MCPUpdateInfo {
+                    name,
+                    origin,
+                    installed_commit,
+                    latest_commit: None,
+                    update_available: false,
+                    error: Some(e),
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    info!(
+        "[MCP Update] {} git-installed servers, {} with updates",
+        updates.len(),
+        updates.iter().filter(|u| u.update_available).count()
+    );
+    Ok(updates)
+}
+
+/// Update a git-installed MCP server: pull, npm install, rebuild, then test that it starts
+#[command]
+#[specta::specta]
+pub async fn update_mcp(name: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Mcps], "update_mcp").await;
+    info!("[MCP Update] Updating MCP: {}", name);
+    let mut configs = load_mcp_config_file()?;
+    let (_, origin) = git_installed_mcps(&configs)
+        .into_iter()
+        .find(|(n, _)| *n == name)
+        .ok_or(format!("{} was not installed from git", name))?;
+    let path = mcp_install_path(&name);
+    let previous_commit = git_head_commit(&path);
+
+    shell::run_command_output("git", &["-C", &path, "pull", "--ff-only"])
+        .map_err(|e| format!("git pull failed (local changes?): {}", e))?;
+    npm_install_and_build(&path)?;
+
+    let commit = git_head_commit(&path);
+    let config = configs.get_mut(&name).ok_or(format!("MCP server not found: {}", name))?;
+    config.install = Some(MCPInstallInfo {
+        source: "git".to_string(),
+        origin,
+        commit: commit.clone(),
+        installed_at: Some(chrono::Utc::now().to_rfc3339()),
+    });
+    let test_config = resolve_mcp_client_config(config);
+    save_mcp_config_file(&configs)?;
+
+    let short = |c: &Option<String>| c.as_deref().map(|c| c.chars().take(8).collect::<String>()).unwrap_or_default();
+    info!("[MCP Update] {} updated {} -> {}", name, short(&previous_commit), short(&commit));
+
+    let test = test_mcp_server(
+        "stdio".to_string(),
+        test_config.command.clone(),
+        Some(test_config.command),
+        Some(test_config.args),
+        None,
+    )
+    .await;
+    match test {
+        Ok(msg) => Ok(format!("Updated {} to {}\n{}", name, short(&commit), msg)),
+        Err(e) => Err(format!("Updated {} to {}, but the server failed to start:\n{}", name, short(&commit), e)),
+    }
+}
+
 /// Check if mcporter is installed
 #[command]
+#[specta::specta]
 pub async fn check_mcporter_installed() -> Result<bool, String> {
     info!("[mcporter] Checking if mcporter is installed...");
     let installed = shell::command_exists("mcporter");
@@ -1245,29 +3575,21 @@ pub async fn check_mcporter_installed() -> Result<bool, String> {
     Ok(installed)
 }
 
-/// Install mcporter via npm
+/// Install mcporter with the active Node.js package manager
 #[command]
+#[specta::specta]
 pub async fn install_mcporter() -> Result<String, String> {
-    info!("[mcporter] Installing mcporter globally via npm...");
+    let pm = node_pm::active();
+    info!("[mcporter] Installing mcporter globally via {}...", pm.name());
 
-    let npm_cmd = if platform::is_windows() { "npm.cmd" } else { "npm" };
+    let install = pm.global_add("mcporter");
+    let output = shell::output(&mut node_pm::command(&install))
+        .map_err(|e| format!("Failed to run {} install: {}", pm.name(), e))?;
 
-    let mut cmd = std::process::Command::new(npm_cmd);
-    cmd.args(&["install", "-g", "mcporter"]);
-
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000);
-    }
-
-    let output = cmd.output()
-        .map_err(|e| format!("Failed to run npm install: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("npm install -g mcporter failed: {}", stderr));
-    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} failed: {}", install, stderr));
+    }
 
     info!("[mcporter] Installation successful");
     Ok("mcporter installed successfully".to_string())
@@ -1275,23 +3597,13 @@ pub async fn install_mcporter() -> Result<String, String> {
 
 /// Uninstall Mcporter
 #[command]
+#[specta::specta]
 pub async fn uninstall_mcporter() -> Result<String, String> {
-    info!("Uninstalling mcporter globally via npm");
-
-    #[cfg(target_os = "windows")]
-    let program = "cmd";
-    #[cfg(target_os = "windows")]
-    let args = ["/C", "npm uninstall -g @openclaw/mcporter"];
+    let pm = node_pm::active();
+    info!("Uninstalling mcporter globally via {}", pm.name());
 
-    #[cfg(not(target_os = "windows"))]
-    let program = "npm";
-    #[cfg(not(target_os = "windows"))]
-    let args = ["uninstall", "-g", "@openclaw/mcporter"];
-
-    let output = std::process::Command::new(program)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to execute npm uninstall: {}", e))?;
+    let output = shell::output(&mut node_pm::command(&pm.global_remove("@openclaw/mcporter")))
+        .map_err(|e| format!("Failed to execute {} uninstall: {}", pm.name(), e))?;
 
     if output.status.success() {
         info!("mcporter uninstalled successfully");
@@ -1303,10 +3615,77 @@ pub async fn uninstall_mcporter() -> Result<String, String> {
     }
 }
 
-/// Install MCP server as an OpenClaw plugin (using openclaw plugins install)
+/// Read what a plugin package declares: `npm view --json` for registry packages, package.json
+/// for local directories. Git URLs and tarballs are not fetched and come back unreviewed.
+fn fetch_plugin_review(spec: &str) -> PluginReview {
+    let spec = spec.trim();
+    let local = std::path::Path::new(spec).join("package.json");
+    if local.is_file() {
+        return match std::fs::read_to_string(&local)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        {
+            Some(manifest) => plugin_review::review(spec, &manifest),
+            None => plugin_review::unavailable(spec, "package.json could not be read"),
+        };
+    }
+    if !plugin_review::is_registry_spec(spec) {
+        return plugin_review::unavailable(spec, "Only npm packages and local directories can be reviewed");
+    }
+
+    match shell::output(&mut node_pm::command(&format!("npm view {} --json", spec))) {
+        Ok(output) if output.status.success() => {
+            match serde_json::from_slice::<Value>(&output.stdout) {
+                Ok(manifest) => plugin_review::review(spec, &manifest),
+                Err(e) => plugin_review::unavailable(spec, &format!("npm view returned invalid JSON ({})", e)),
+            }
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().find(|l| !l.trim().is_empty()).unwrap_or("npm view failed").trim().to_string();
+            plugin_review::unavailable(spec, &reason)
+        }
+        Err(e) => plugin_review::unavailable(spec, &format!("npm could not be run ({})", e)),
+    }
+}
+
+/// Refuse a plugin install whose review needs confirmation (install scripts, or metadata that
+/// could not be read) unless the user confirmed it
+fn check_plugin_review(spec: &str, confirmed: bool) -> Result<(), String> {
+    let review = fetch_plugin_review(spec);
+    for script in &review.install_scripts {
+        info!("[Plugin Review] {} declares {}: {}", spec, script.hook, script.command);
+    }
+    if review.requires_confirmation && !confirmed {
+        warn!("[Plugin Review] Install of {} refused until confirmed: {}", spec, review.warnings.join("; "));
+        return Err(format!(
+            "Review {} before installing it: {}. Confirm the review to install anyway",
+            spec,
+            review.warnings.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+/// Package metadata, dependencies and install scripts of a plugin, shown before
+/// `install_mcp_plugin` or `install_feishu_plugin` runs it
+#[command]
+#[specta::specta]
+pub async fn review_plugin_install(spec: String) -> Result<PluginReview, String> {
+    if spec.trim().is_empty() {
+        return Err("No package given".to_string());
+    }
+    info!("[Plugin Review] Reviewing {}", spec.trim());
+    Ok(fetch_plugin_review(&spec))
+}
+
+/// Install MCP server as an OpenClaw plugin (using openclaw plugins install). Packages with
+/// install scripts, or whose metadata can't be read, need `confirmed` (see `review_plugin_install`).
 #[command]
-pub async fn install_mcp_plugin(url: String) -> Result<String, String> {
+#[specta::specta]
+pub async fn install_mcp_plugin(url: String, confirmed: Option<bool>) -> Result<String, String> {
     info!("[MCP Plugin] Installing MCP plugin from: {}", url);
+    check_plugin_review(&url, confirmed.unwrap_or(false))?;
 
     let result = shell::run_openclaw(&["plugins", "install", &url])
         .map_err(|e| format!("Failed to install plugin: {}", e))?;
@@ -1317,6 +3696,7 @@ pub async fn install_mcp_plugin(url: String) -> Result<String, String> {
 
 /// Set openclaw config via CLI (openclaw config set <key> <value>)
 #[command]
+#[specta::specta]
 pub async fn openclaw_config_set(key: String, value: String) -> Result<String, String> {
     info!("[Config CLI] Setting config: {} = {}", key, value);
 
@@ -1327,8 +3707,124 @@ pub async fn openclaw_config_set(key: String, value: String) -> Result<String, S
     Ok(format!("Set {} = {}", key, value))
 }
 
+/// Cached OpenClaw config schema (manager/openclaw-schema.json), with the version it came from
+fn get_schema_cache_path() -> String {
+    platform::path_string(&platform::join_path(platform::get_manager_data_dir(), &["openclaw-schema.json"]))
+}
+
+/// How long to wait before asking openclaw for its schema again after it had none
+const SCHEMA_RETRY_SECS: u64 = 600;
+
+/// Schema used this session and when it was last looked for
+static CONFIG_SCHEMA: std::sync::Mutex<(Option<Value>, Option<std::time::Instant>)> = std::sync::Mutex::new((None, None));
+
+/// Schema printed by `openclaw config schema`, cached per OpenClaw version. Falls back to the
+/// last cached schema when openclaw is missing or too old to print one.
+fn load_config_schema() -> Option<Value> {
+    let cache_path = get_schema_cache_path();
+    let cached: Option<Value> = file::read_file(&cache_path).ok().and_then(|c| serde_json::from_str(&c).ok());
+    let version = shell::run_openclaw(&["--version"]).ok().map(|v| v.trim().to_string());
+    if let (Some(cached), Some(version)) = (&cached, &version) {
+        if cached["version"].as_str() == Some(version.as_str()) {
+            return cached.get("schema").cloned();
+        }
+    }
+    if let Some(version) = &version {
+        match shell::run_openclaw(&["config", "schema"]) {
+            Ok(output) => {
+                let schema = output
+                    .find('{')
+                    .and_then(|start| serde_json::from_str::<Value>(output[start..].trim()).ok())
+                    .filter(|schema| schema.is_object());
+                if let Some(schema) = schema {
+                    let entry = json!({ "version": version, "fetchedAt": chrono::Utc::now().to_rfc3339(), "schema": schema });
+                    if let Err(e) = file::write_file(&cache_path, &entry.to_string()) {
+                        warn!("[Config Schema] Failed to cache the schema: {}", e);
+                    }
+                    info!("[Config Schema] Loaded the config schema of OpenClaw {}", version);
+                    return Some(schema);
+                }
+                warn!("[Config Schema] openclaw config schema did not print a JSON schema");
+            }
+            Err(e) => debug!("[Config Schema] openclaw config schema failed: {}", e),
+        }
+    }
+    cached.and_then(|c| c.get("schema").cloned())
+}
+
+/// The OpenClaw config schema, if one can be had (loaded once per session). The lock is not
+/// held while openclaw runs; saves made during that first load are not checked.
+fn config_schema() -> Option<Value> {
+    {
+        let mut state = CONFIG_SCHEMA.lock().ok()?;
+        let (schema, checked_at) = &mut *state;
+        let retry = checked_at.map(|t| t.elapsed().as_secs() >= SCHEMA_RETRY_SECS).unwrap_or(true);
+        if schema.is_some() || !retry {
+            return schema.clone();
+        }
+        *checked_at = Some(std::time::Instant::now());
+    }
+    let loaded = load_config_schema();
+    if let Ok(mut state) = CONFIG_SCHEMA.lock() {
+        state.0 = loaded.clone();
+    }
+    loaded
+}
+
+/// Refuse a save that would add schema violations, so the core never gets a config it refuses
+/// to load. Violations the current config already has don't block it; without a schema
+/// (no openclaw, or a core too old to print one) nothing is checked.
+fn check_config_schema(config: &Value) -> Result<(), String> {
+    let Some(schema) = config_schema() else {
+        return Ok(());
+    };
+    let errors = config_schema::validate(&schema, config);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let current = load_openclaw_config().unwrap_or_else(|_| json!({}));
+    let existing = config_schema::validate(&schema, &current);
+    let introduced = config_schema::new_errors(&existing, errors);
+    if introduced.is_empty() {
+        warn!("[Config Schema] Saving with {} schema violation(s) already in the config", existing.len());
+        return Ok(());
+    }
+    warn!("[Config Schema] Save refused, {} schema violation(s)", introduced.len());
+    Err(format!(
+        "The configuration would not pass the OpenClaw schema, nothing was saved:\n{}",
+        config_schema::describe(&introduced)
+    ))
+}
+
+/// Result of validate_config_schema
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSchemaReport {
+    /// False when no schema could be had; nothing was checked then
+    pub schema_available: bool,
+    pub valid: bool,
+    /// Violations with the JSON pointer of each offending value
+    pub errors: Vec<SchemaError>,
+}
+
+/// Check `config` (the current config if omitted) against the OpenClaw config schema
+#[command]
+#[specta::specta]
+pub async fn validate_config_schema(config: Option<Value>) -> Result<ConfigSchemaReport, String> {
+    let Some(schema) = config_schema() else {
+        return Ok(ConfigSchemaReport { schema_available: false, valid: true, errors: Vec::new() });
+    };
+    let config = match config {
+        Some(config) => config,
+        None => load_openclaw_config()?,
+    };
+    let errors = config_schema::validate(&schema, &config);
+    Ok(ConfigSchemaReport { schema_available: true, valid: errors.is_empty(), errors })
+}
+
 /// Validate a given config JSON string by writing to a temporary file and running openclaw config validate --json
 #[command]
+#[specta::specta]
 pub async fn validate_openclaw_config(config_json: String) -> Result<String, String> {
     info!("[Config CLI] Validating config json");
     
@@ -1346,7 +3842,7 @@ pub async fn validate_openclaw_config(config_json: String) -> Result<String, Str
         "Cannot find openclaw command".to_string()
     })?;
 
-    let mut cmd = std::process::Command::new(&openclaw_path);
+    let mut cmd = shell::openclaw_command(&openclaw_path);
     cmd.args(&["config", "validate", "--json"]);
     cmd.env("OPENCLAW_CONFIG", &temp_file_str);
     cmd.env("PATH", crate::utils::shell::get_extended_path());
@@ -1357,7 +3853,7 @@ pub async fn validate_openclaw_config(config_json: String) -> Result<String, Str
         cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
 
-    let output = cmd.output().map_err(|e| {
+    let output = shell::output(&mut cmd).map_err(|e| {
         let _ = std::fs::remove_file(&temp_file);
         format!("Failed to execute config validate: {}", e)
     })?;
@@ -1376,9 +3872,26 @@ pub async fn validate_openclaw_config(config_json: String) -> Result<String, Str
 
 /// Test an MCP server connectivity
 #[command]
-pub async fn test_mcp_server(server_type: String, target: String, command: Option<String>, args: Option<Vec<String>>) -> Result<String, String> {
+#[specta::specta]
+pub async fn test_mcp_server(
+    server_type: String,
+    target: String,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    docker: Option<DockerMCPConfig>,
+) -> Result<String, String> {
     info!("[MCP Test] Testing MCP server: type={}, target={}", server_type, target);
 
+    // Docker servers are tested as stdio servers running `docker run -i`
+    let (server_type, command, args, extra_env) = if server_type == "docker" {
+        check_docker_available()?;
+        let docker = docker.unwrap_or_else(|| DockerMCPConfig { image: target.clone(), ..Default::default() });
+        let (docker_cmd, docker_args) = docker_mcp_command(&docker);
+        ("stdio".to_string(), Some(docker_cmd), Some(docker_args), docker.env)
+    } else {
+        (server_type, command, args, HashMap::new())
+    };
+
     if server_type == "url" {
         // Remote HTTP MCP: POST an MCP initialize request to the URL
         let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
@@ -1398,7 +3911,7 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
             cmd.creation_flags(0x08000000);
         }
 
-        match cmd.output() {
+        match shell::output(&mut cmd) {
             Ok(out) => {
                 let output_str = String::from_utf8_lossy(&out.stdout).to_string();
                 let lines: Vec<&str> = output_str.trim().lines().collect();
@@ -1437,6 +3950,11 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
         
         info!("[MCP Test] Spawning: {} {:?}", cmd_name, cmd_args);
 
+        // The stdio probe talks to the live process, which mock mode cannot simulate
+        if shell::is_mock() {
+            return Ok(format!("✅ Server process started (mock mode, {} not run)", cmd_name));
+        }
+
         let extended_path = shell::get_extended_path();
         
         // On Windows, use cmd /c to resolve .cmd files (npx.cmd, node.cmd, etc.)
@@ -1458,7 +3976,8 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
         cmd.stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .env("PATH", &extended_path);
+            .env("PATH", &extended_path)
+            .envs(&extra_env);
 
         #[cfg(windows)]
         {
@@ -1516,6 +4035,7 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
 
 /// Get all supported AI Providers (legacy compatibility)
 #[command]
+#[specta::specta]
 pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>, String> {
     info!("[AI Provider] Getting supported AI Provider list (legacy)...");
 
@@ -1548,6 +4068,7 @@ pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>,
 
 /// Get channel configuration - read from openclaw.json and env file
 #[command]
+#[specta::specta]
 pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, String> {
     info!("[Channel Config] Getting channel configuration list...");
 
@@ -1628,7 +4149,9 @@ pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, String> {
 
 /// Save channel configuration - save to openclaw.json
 #[command]
+#[specta::specta]
 pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_channel_config").await;
     info!(
         "[Save Channel Config] Saving channel configuration: {} ({})",
         channel.id, channel.channel_type
@@ -1662,6 +4185,15 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
     // These fields are only for testing, not saved to openclaw.json, but saved to env file
     let test_only_fields = vec!["userId", "testChatId", "testChannelId"];
 
+    // Contact names typed into allow lists are stored as their peer ids
+    let contact_book = crate::commands::contacts::load_contacts();
+    let mut channel = channel;
+    for key in ["allowFrom", "groupAllowFrom"] {
+        if let Some(list) = channel.config.get_mut(key) {
+            *list = contacts::resolve_list(&contact_book, &channel.id, list);
+        }
+    }
+
     // Update channels configuration - MERGE with existing
     if let Some(existing_channel) = config["channels"].get_mut(&channel.id).and_then(|v| v.as_object_mut()) {
         existing_channel.insert("enabled".to_string(), json!(true));
@@ -1706,9 +4238,11 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
         obj.remove("allowlist");
     }
 
+    check_config_schema(&config)?;
+
     // Save configuration
     info!("[Save Channel Config] Writing configuration file...");
-    match save_openclaw_config(&config) {
+    match save_openclaw_config(&config, "save_channel_config") {
         Ok(_) => {
             info!(
                 "[Save Channel Config] {} configuration saved successfully",
@@ -1725,27 +4259,33 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
 
 /// Clear channel configuration - delete specified channel configuration from openclaw.json
 #[command]
+#[specta::specta]
 pub async fn clear_channel_config(channel_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "clear_channel_config").await;
+    snapshots::snapshot_before("clear_channel_config")?;
     info!("[Clear Channel Config] Clearing channel configuration: {}", channel_id);
 
     let mut config = load_openclaw_config()?;
     let env_path = platform::get_env_file_path();
+    // Removed values are parked in the trash so the channel can be restored
+    let mut removed = Vec::new();
+    let key = trash::pointer_key(&channel_id);
 
     // Delete channel from channels object
-    if let Some(channels) = config.get_mut("channels").and_then(|v| v.as_object_mut()) {
-        channels.remove(&channel_id);
+    if let Some(value) = trash::take_value(&mut config, &format!("/channels/{}", key)) {
+        removed.push(value);
         info!("[Clear Channel Config] Deleted from channels: {}", channel_id);
     }
 
     // Delete from plugins.allow array
-    if let Some(allow_arr) = config.pointer_mut("/plugins/allow").and_then(|v| v.as_array_mut()) {
-        allow_arr.retain(|v| v.as_str() != Some(&channel_id));
-        info!("[Clear Channel Config] Deleted from plugins.allow: {}", channel_id);
-    }
+    removed.extend(trash::take_array_items(&mut config, "/plugins/allow", |v| {
+        v.as_str() == Some(&channel_id)
+    }));
+    info!("[Clear Channel Config] Deleted from plugins.allow: {}", channel_id);
 
     // Delete from plugins.entries
-    if let Some(entries) = config.pointer_mut("/plugins/entries").and_then(|v| v.as_object_mut()) {
-        entries.remove(&channel_id);
+    if let Some(value) = trash::take_value(&mut config, &format!("/plugins/entries/{}", key)) {
+        removed.push(value);
         info!("[Clear Channel Config] Deleted from plugins.entries: {}", channel_id);
     }
 
@@ -1755,14 +4295,29 @@ pub async fn clear_channel_config(channel_id: String) -> Result<String, String>
         format!("OPENCLAW_{}_TESTCHATID", channel_id.to_uppercase()),
         format!("OPENCLAW_{}_TESTCHANNELID", channel_id.to_uppercase()),
     ];
+    let mut removed_env = std::collections::BTreeMap::new();
     for env_key in env_prefixes {
+        if let Some(value) = file::read_env_value(&env_path, &env_key) {
+            removed_env.insert(env_key.clone(), value);
+        }
         let _ = file::remove_env_value(&env_path, &env_key);
     }
 
     // Save configuration
-    match save_openclaw_config(&config) {
+    match save_openclaw_config(&config, "clear_channel_config") {
         Ok(_) => {
             info!("[Clear Channel Config] {} configuration cleared", channel_id);
+            if let Err(e) = trash::park(trash::DeletedItem {
+                id: trash::new_item_id("channel"),
+                kind: "channel".to_string(),
+                name: channel_id.clone(),
+                deleted_at: chrono::Utc::now().to_rfc3339(),
+                values: removed,
+                env: removed_env,
+                paths: Vec::new(),
+            }) {
+                warn!("[Clear Channel Config] Cleared but not restorable: {}", e);
+            }
             Ok(format!("{} configuration cleared", channel_id))
         }
         Err(e) => {
@@ -1772,10 +4327,75 @@ pub async fn clear_channel_config(channel_id: String) -> Result<String, String>
     }
 }
 
+// ============ Access Policy Risk ============
+
+/// Risk id recorded for access that lets anyone message the bot
+const WILDCARD_ACCESS_RISK: &str = "wildcard-allowFrom";
+
+/// Whether a channel/account config accepts DMs or group messages from anyone
+pub(crate) fn has_wildcard_access(value: &Value) -> bool {
+    let wildcard_list = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().any(|v| v.as_str() == Some("*")))
+            .unwrap_or(false)
+    };
+    value.get("dmPolicy").and_then(|v| v.as_str()) == Some("open")
+        || wildcard_list("allowFrom")
+        || wildcard_list("groupAllowFrom")
+}
+
+/// Recorded acknowledgement for `key` ("<channel>/<account>") in manager.json riskAcknowledgements
+pub(crate) fn risk_acknowledgement(manager_config: &Value, key: &str) -> Option<Value> {
+    manager_config
+        .get("riskAcknowledgements")
+        .and_then(|v| v.get(key))
+        .cloned()
+}
+
+/// Gate a save that grants wildcard access. Newly introduced wildcard access needs an explicit
+/// acknowledgement, which is recorded; dropping the wildcard clears the record.
+fn check_wildcard_access(key: &str, wildcard: bool, was_wildcard: bool, acknowledged: bool) -> Result<(), String> {
+    let mut manager_config = load_manager_config().unwrap_or(json!({}));
+    let recorded = risk_acknowledgement(&manager_config, key).is_some();
+
+    if !wildcard {
+        if recorded {
+            if let Some(acks) = manager_config.get_mut("riskAcknowledgements").and_then(|v| v.as_object_mut()) {
+                acks.remove(key);
+            }
+            save_manager_config(&manager_config)?;
+        }
+        return Ok(());
+    }
+    if recorded || (was_wildcard && !acknowledged) {
+        return Ok(());
+    }
+    if !acknowledged {
+        return Err(format!(
+            "'{}' would accept messages from anyone (dmPolicy \"open\" writes allowFrom [\"*\"]). \
+             Confirm the risk to save this policy",
+            key
+        ));
+    }
+
+    warn!("[Access Policy] Wildcard access acknowledged for {}", key);
+    if manager_config.get("riskAcknowledgements").is_none() {
+        manager_config["riskAcknowledgements"] = json!({});
+    }
+    manager_config["riskAcknowledgements"][key] = json!({
+        "risk": WILDCARD_ACCESS_RISK,
+        "acknowledgedAt": chrono::Utc::now().to_rfc3339(),
+        "user": history::current_user(),
+    });
+    save_manager_config(&manager_config)
+}
+
 // ============ Telegram Multi-Account Management ============
 
 /// Telegram account info for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct TelegramAccount {
     pub id: String,
     #[serde(alias = "botToken", alias = "bot_token")]
@@ -1792,10 +4412,13 @@ pub struct TelegramAccount {
     pub primary: Option<bool>,
     #[serde(alias = "allowFrom", alias = "allow_from")]
     pub allow_from: Option<Vec<String>>,
+    /// False when the account is temporarily turned off (see set_account_enabled)
+    pub enabled: Option<bool>,
 }
 
 /// Get all Telegram bot accounts
 #[command]
+#[specta::specta]
 pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
     info!("[Telegram Accounts] Getting accounts...");
     let config = load_openclaw_config()?;
@@ -1812,22 +4435,14 @@ pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
                 dm_policy: acct_val.get("dmPolicy").and_then(|v| v.as_str()).map(|s| s.to_string()),
                 stream_mode: acct_val.get("streamMode").and_then(|v| v.as_str()).map(|s| s.to_string()),
                 exclusive_topics: {
-                    // Re-infer exclusive topics from group config
-                    // Logic: If a group has requireMention=true and specific topics have requireMention=false, those are exclusive topics.
+                    // Topics that reply without a mention inside otherwise mention-only groups
                     let mut inferred_topics = Vec::new();
                     if let Some(groups_map) = acct_val.get("groups").and_then(|g| g.as_object()) {
-                        for (_, group_val) in groups_map {
-                             // Check if group is muted (requireMention=true)
-                             if group_val.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(false) {
-                                 if let Some(topics_map) = group_val.get("topics").and_then(|t| t.as_object()) {
-                                     for (tid, tval) in topics_map {
-                                         // Check if topic is unmuted (requireMention=false)
-                                         if !tval.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true) {
-                                             inferred_topics.push(tid.clone());
-                                         }
-                                     }
-                                 }
-                             }
+                        for group_val in groups_map.values() {
+                            let (default_behavior, rules) = read_topic_rules(group_val);
+                            if default_behavior == "mention" {
+                                inferred_topics.extend(rules.into_iter().filter(|r| r.behavior == "always").map(|r| r.topic_id));
+                            }
                         }
                     }
                     if inferred_topics.is_empty() { None } else { Some(inferred_topics) }
@@ -1841,6 +4456,7 @@ pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
                         else if let Some(n) = v.as_i64() { Some(n.to_string()) }
                         else { None }
                     }).collect()),
+                enabled: acct_val.get("enabled").and_then(|v| v.as_bool()),
             });
         }
     }
@@ -1865,6 +4481,7 @@ pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
                             else if let Some(n) = v.as_i64() { Some(n.to_string()) }
                             else { None }
                         }).collect()),
+                    enabled: None,
                 });
             }
         }
@@ -1890,14 +4507,8 @@ pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
     Ok(accounts)
 }
 
-/// Save a Telegram bot account
-#[command]
-pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, String> {
-    // Normalize account ID to lowercase and replace spaces with dashes
-    let account_id = account.id.to_lowercase().replace(' ', "-");
-    info!("[Telegram Accounts] Saving account: {}", account_id);
-    let mut config = load_openclaw_config()?;
-
+/// Ensure channels.telegram.accounts exists, moving a single-bot config into accounts["default"]
+fn ensure_telegram_accounts(config: &mut Value) {
     // Ensure channels.telegram exists
     if config.get("channels").is_none() {
         config["channels"] = json!({});
@@ -1945,6 +4556,33 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
             }
         }
     }
+}
+
+/// Save a Telegram bot account. Opening DMs to everyone (dmPolicy "open" / allowFrom "*")
+/// needs `acknowledge_risk`, unless the account already had wildcard access.
+#[command]
+#[specta::specta]
+pub async fn save_telegram_account(account: TelegramAccount, acknowledge_risk: Option<bool>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "save_telegram_account").await;
+    // Normalize account ID to lowercase and replace spaces with dashes
+    let account_id = account.id.to_lowercase().replace(' ', "-");
+    info!("[Telegram Accounts] Saving account: {}", account_id);
+    let mut config = load_openclaw_config()?;
+
+    ensure_telegram_accounts(&mut config);
+
+    let wildcard = account.dm_policy.as_deref() == Some("open")
+        || account.allow_from.as_ref().map(|af| af.iter().any(|id| id == "*")).unwrap_or(false);
+    let was_wildcard = config
+        .pointer(&format!("/channels/telegram/accounts/{}", account_id))
+        .map(has_wildcard_access)
+        .unwrap_or(false);
+    check_wildcard_access(
+        &format!("telegram/{}", account_id),
+        wildcard,
+        was_wildcard,
+        acknowledge_risk.unwrap_or(false),
+    )?;
 
     // If this account is set as primary, unset primary for all others
     // (This is now handled by only storing one ID in `meta`, so no need to iterate and clear others manually)
@@ -1968,8 +4606,11 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
         acct_obj["allowFrom"] = json!(["*"]);
     } else if let Some(ref af) = account.allow_from {
         if !af.is_empty() {
-            // Convert string IDs to numbers where possible for Core compatibility
+            // Contact names become their peer ids; convert string IDs to numbers where possible
+            // for Core compatibility
+            let contact_book = crate::commands::contacts::load_contacts();
             let allow_vals: Vec<serde_json::Value> = af.iter().map(|id| {
+                let id = contacts::resolve_peer(&contact_book, "telegram", id);
                 if let Ok(n) = id.parse::<i64>() { json!(n) } else { json!(id) }
             }).collect();
             info!("[Telegram Accounts] Saving allowFrom: {:?}", allow_vals);
@@ -2136,64 +4777,42 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
     }
 
     // Handle groups configuration
-    // If exclusive_topics is set, we need to modify the group config to enforce it
-    // 1. Set group-level requireMention = true (default behavior: ignore everything)
-    // 2. Set topic-level requireMention = false for whitelisted topics (exception: auto-reply)
+    // Exclusive topics reply without a mention; the rest of each group is mention-only, and
+    // topics owned by other bot accounts stay mention-only to avoid cross-talk
     let mut groups_json = account.groups.clone();
-    
-    if let Some(exclusive_topics) = &account.exclusive_topics {
-        if !exclusive_topics.is_empty() {
-             // We also save the raw list so the UI can reload it (using a hidden field or relying on inference)
-             // However, OpenClaw core rejects unknown fields. So we must ONLY output valid config.
-             // Strategy: The UI will need to infer exclusive topics from the config structure if we can't save the field.
-             // OR: We save it as a comment? No, JSON doesn't support comments.
-             // COMPROMISE: We will NOT save "exclusiveTopics" to the file to avoid validation errors.
-             // The UI will have to populate the field by checking if a group has topics configured.
-             // For now, let's just apply the logic to the groups logic.
-
-            if let Some(groups_map) = groups_json.as_mut().and_then(|g| g.as_object_mut()) {
-                for (_, group_val) in groups_map.iter_mut() {
-                    if let Some(group_obj) = group_val.as_object_mut() {
-                        // Enforce whitelist logic:
-                        // 1. Group requires mention (mute general)
-                        group_obj.insert("requireMention".to_string(), json!(true));
-                        group_obj.insert("enabled".to_string(), json!(true));
-
-                        // 2. Allow specific topics
-                        let mut topics_map = serde_json::Map::new();
-                        for topic_id in exclusive_topics {
-                            let mut topic_config = serde_json::Map::new();
-                            topic_config.insert("requireMention".to_string(), json!(false));
-                            topics_map.insert(topic_id.clone(), json!(topic_config));
-                        }
-
-                        // 3. Explicitly block topics owned by OTHER bot accounts
-                        //    This prevents cross-talk when OpenClaw core doesn't
-                        //    fall back to group-level requireMention for unlisted topics.
-                        if let Some(all_accts) = config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()) {
-                            for (other_id, other_val) in all_accts {
-                                if other_id == &account.id { continue; }
-                                if let Some(other_groups) = other_val.get("groups").and_then(|g| g.as_object()) {
-                                    for (_, other_group) in other_groups {
-                                        if let Some(other_topics) = other_group.get("topics").and_then(|t| t.as_object()) {
-                                            for (other_tid, _) in other_topics {
-                                                if !exclusive_topics.contains(other_tid) && !topics_map.contains_key(other_tid) {
-                                                    let mut block_config = serde_json::Map::new();
-                                                    block_config.insert("requireMention".to_string(), json!(true));
-                                                    topics_map.insert(other_tid.clone(), json!(block_config));
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        group_obj.insert("topics".to_string(), json!(topics_map));
+    if let Some(exclusive_topics) = account.exclusive_topics.as_ref().filter(|t| !t.is_empty()) {
+        let mut rules: Vec<TopicRule> = exclusive_topics
+            .iter()
+            .map(|tid| TopicRule { topic_id: tid.clone(), behavior: "always".to_string(), agent_id: None })
+            .collect();
+        if let Some(all_accts) = config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()) {
+            for (other_id, other_val) in all_accts {
+                if other_id == &account_id {
+                    continue;
+                }
+                let other_topics = other_val
+                    .get("groups")
+                    .and_then(|g| g.as_object())
+                    .into_iter()
+                    .flat_map(|groups| groups.values())
+                    .filter_map(|group| group.get("topics").and_then(|t| t.as_object()))
+                    .flat_map(|topics| topics.keys());
+                for other_tid in other_topics {
+                    if !rules.iter().any(|r| &r.topic_id == other_tid) {
+                        rules.push(TopicRule { topic_id: other_tid.clone(), behavior: "mention".to_string(), agent_id: None });
                     }
                 }
             }
         }
+
+        if let Some(groups_map) = groups_json.as_mut().and_then(|g| g.as_object_mut()) {
+            for group_val in groups_map.values_mut() {
+                if group_val.is_object() {
+                    write_topic_rules(group_val, "mention", &rules);
+                    group_val["enabled"] = json!(true);
+                }
+            }
+        }
     }
 
     if let Some(g) = groups_json {
@@ -2216,6 +4835,16 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
         }
     }
 
+    // Keep a disabled account disabled unless the caller changes it
+    let enabled = account.enabled.or_else(|| {
+        config
+            .pointer(&format!("/channels/telegram/accounts/{}/enabled", account_id))
+            .and_then(|v| v.as_bool())
+    });
+    if enabled == Some(false) {
+        acct_obj["enabled"] = json!(false);
+    }
+
     config["channels"]["telegram"]["accounts"][&account_id] = acct_obj;
 
     // Ensure telegram is enabled and in plugins
@@ -2224,551 +4853,1709 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
         config["plugins"] = json!({ "allow": ["telegram"], "entries": { "telegram": { "enabled": true } } });
     }
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, "save_telegram_account")?;
     Ok(format!("Account '{}' saved", account_id))
 }
 
-/// Delete a Telegram bot account
-#[command]
-pub async fn delete_telegram_account(account_id: String) -> Result<String, String> {
-    let account_id = account_id.to_lowercase().replace(' ', "-");
-    info!("[Telegram Accounts] Deleting account: {}", account_id);
-    let mut config = load_openclaw_config()?;
-
-    if let Some(accts) = config.pointer_mut("/channels/telegram/accounts").and_then(|v| v.as_object_mut()) {
-        accts.remove(&account_id);
-    }
-
-    // Also clean up any bindings referencing this account
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        bindings.retain(|b| b.pointer("/match/accountId").and_then(|v| v.as_str()) != Some(&account_id));
-    }
+/// One row of a bulk Telegram account import
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TelegramImportRow {
+    /// 1-based row number in the pasted input
+    pub row: usize,
+    pub id: String,
+    /// "imported" | "updated" | "invalid" | "duplicate"
+    pub status: String,
+    /// Bot @username reported by getMe
+    pub bot_username: Option<String>,
+    pub error: Option<String>,
+}
 
-    save_openclaw_config(&config)?;
-    Ok(format!("Account '{}' deleted", account_id))
+/// Per-row report of import_telegram_accounts
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TelegramImportReport {
+    pub imported: usize,
+    pub rows: Vec<TelegramImportRow>,
 }
 
-// ============ Feishu Plugin Management ============
+/// Account parsed from pasted input, before validation
+struct TelegramImportEntry {
+    row: usize,
+    id: String,
+    token: String,
+    dm_policy: Option<String>,
+    group_policy: Option<String>,
+}
 
-/// Feishu plugin status
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FeishuPluginStatus {
-    pub installed: bool,
-    pub version: Option<String>,
-    pub plugin_name: Option<String>,
+/// Parse a pasted JSON array (`[{id, token|botToken, dmPolicy?, groupPolicy?}]`) or CSV
+/// (`id,token[,dmPolicy[,groupPolicy]]`, optional header, `#` comments)
+fn parse_telegram_import(list: &str) -> Result<Vec<TelegramImportEntry>, String> {
+    let text = list.trim();
+    let opt = |s: Option<&str>| s.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    if text.starts_with('[') {
+        let rows: Vec<Value> = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+        return Ok(rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let field = |keys: &[&str]| keys.iter().find_map(|k| row.get(*k).and_then(|v| v.as_str()));
+                TelegramImportEntry {
+                    row: i + 1,
+                    id: field(&["id", "accountId"]).unwrap_or("").trim().to_lowercase().replace(' ', "-"),
+                    token: field(&["token", "botToken", "bot_token"]).unwrap_or("").trim().to_string(),
+                    dm_policy: opt(field(&["dmPolicy", "dm_policy"])),
+                    group_policy: opt(field(&["groupPolicy", "group_policy"])),
+                }
+            })
+            .collect());
+    }
+
+    Ok(text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .filter(|(_, line)| !line.to_lowercase().replace(' ', "").starts_with("id,"))
+        .map(|(i, line)| {
+            let cols: Vec<&str> = line.split(',').map(|c| c.trim().trim_matches('"')).collect();
+            TelegramImportEntry {
+                row: i + 1,
+                id: cols.first().copied().unwrap_or("").to_lowercase().replace(' ', "-"),
+                token: cols.get(1).copied().unwrap_or("").to_string(),
+                dm_policy: opt(cols.get(2).copied()),
+                group_policy: opt(cols.get(3).copied()),
+            }
+        })
+        .collect())
 }
 
-/// Check if Feishu plugin is installed
+/// Import many Telegram bot accounts at once. Tokens are checked against getMe in parallel and all
+/// valid rows are written in a single config save. Rows with dmPolicy "open" need `acknowledge_risk`.
 #[command]
-pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, String> {
-    info!("[Feishu Plugin] Checking Feishu plugin installation status...");
-
-    // Execute openclaw plugins list command
-    match shell::run_openclaw(&["plugins", "list"]) {
-        Ok(output) => {
-            debug!("[Feishu Plugin] plugins list output: {}", output);
+#[specta::specta]
+pub async fn import_telegram_accounts(list: String, acknowledge_risk: Option<bool>) -> Result<TelegramImportReport, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "import_telegram_accounts").await;
+    let entries = parse_telegram_import(&list)?;
+    info!("[Telegram Import] Importing {} accounts...", entries.len());
+    if entries.is_empty() {
+        return Err("No accounts found in the input".to_string());
+    }
 
-            // Find line containing feishu (case-insensitive)
-            let lines: Vec<&str> = output.lines().collect();
-            let feishu_line = lines.iter().find(|line| {
-                line.to_lowercase().contains("feishu")
-            });
+    // Validate all tokens against getMe in parallel
+    let handles: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            let token = entry.token.clone();
+            std::thread::spawn(move || telegram_api_call(&token, "getMe", &[]))
+        })
+        .collect();
+    let checks: Vec<Result<Value, String>> = handles
+        .into_iter()
+        .map(|h| h.join().unwrap_or_else(|_| Err("Validation thread panicked".to_string())))
+        .collect();
 
-            if let Some(line) = feishu_line {
-                info!("[Feishu Plugin] Feishu plugin installed: {}", line);
+    let mut config = load_openclaw_config()?;
+    ensure_telegram_accounts(&mut config);
+    let existing_tokens: HashMap<String, String> = config
+        .pointer("/channels/telegram/accounts")
+        .and_then(|v| v.as_object())
+        .map(|accts| {
+            accts
+                .iter()
+                .filter_map(|(id, a)| Some((a.get("botToken")?.as_str()?.to_string(), id.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
 
-                // Try to parse version number (usually format is "name@version" or "name version")
-                let version = if line.contains('@') {
-                    line.split('@').last().map(|s| s.trim().to_string())
-                } else {
-                    // Try to match version number pattern (e.g. 0.1.2)
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    parts.iter()
-                        .find(|p| p.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(false))
-                        .map(|s| s.to_string())
-                };
+    let mut rows = Vec::new();
+    let mut seen_ids: Vec<String> = Vec::new();
+    let mut seen_tokens: Vec<String> = Vec::new();
+    for (entry, check) in entries.into_iter().zip(checks) {
+        let mut row = TelegramImportRow {
+            row: entry.row,
+            id: entry.id.clone(),
+            status: "invalid".to_string(),
+            bot_username: None,
+            error: None,
+        };
+        let problem = if entry.id.is_empty() || entry.token.is_empty() {
+            Some("Missing id or token".to_string())
+        } else if entry.dm_policy.as_deref().map(|p| !["pairing", "allowlist", "open", "disabled"].contains(&p)).unwrap_or(false) {
+            Some(format!("Unknown dmPolicy '{}'", entry.dm_policy.as_deref().unwrap_or("")))
+        } else if entry.group_policy.as_deref().map(|p| !["open", "allowlist", "disabled"].contains(&p)).unwrap_or(false) {
+            Some(format!("Unknown groupPolicy '{}'", entry.group_policy.as_deref().unwrap_or("")))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            row.error = Some(problem);
+            rows.push(row);
+            continue;
+        }
+        if seen_ids.contains(&entry.id) || seen_tokens.contains(&entry.token) {
+            row.status = "duplicate".to_string();
+            row.error = Some("Same id or token appears earlier in the input".to_string());
+            rows.push(row);
+            continue;
+        }
+        if let Some(other) = existing_tokens.get(&entry.token).filter(|other| **other != entry.id) {
+            row.status = "duplicate".to_string();
+            row.error = Some(format!("Token is already used by account '{}'", other));
+            rows.push(row);
+            continue;
+        }
+        let me = match check {
+            Ok(me) => me,
+            Err(e) => {
+                row.error = Some(format!("getMe failed: {}", e));
+                rows.push(row);
+                continue;
+            }
+        };
+        row.bot_username = me.get("username").and_then(|v| v.as_str()).map(|u| format!("@{}", u));
+        if entry.dm_policy.as_deref() == Some("open") {
+            let was_wildcard = config
+                .pointer(&format!("/channels/telegram/accounts/{}", entry.id))
+                .map(has_wildcard_access)
+                .unwrap_or(false);
+            let key = format!("telegram/{}", entry.id);
+            if let Err(e) = check_wildcard_access(&key, true, was_wildcard, acknowledge_risk.unwrap_or(false)) {
+                row.error = Some(e);
+                rows.push(row);
+                continue;
+            }
+        }
 
-                Ok(FeishuPluginStatus {
-                    installed: true,
-                    version,
-                    plugin_name: Some(line.trim().to_string()),
-                })
-            } else {
-                info!("[Feishu Plugin] Feishu plugin not installed");
-                Ok(FeishuPluginStatus {
-                    installed: false,
-                    version: None,
-                    plugin_name: None,
-                })
+        let exists = config.pointer(&format!("/channels/telegram/accounts/{}", entry.id)).is_some();
+        let mut acct_obj = config
+            .pointer(&format!("/channels/telegram/accounts/{}", entry.id))
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        acct_obj["botToken"] = json!(entry.token);
+        if let Some(dp) = &entry.dm_policy {
+            acct_obj["dmPolicy"] = json!(dp);
+            // dmPolicy="open" requires allowFrom to include "*"
+            if dp == "open" {
+                acct_obj["allowFrom"] = json!(["*"]);
             }
         }
-        Err(e) => {
-            warn!("[Feishu Plugin] Failed to check plugin list: {}", e);
-            // If command fails, assume plugin is not installed
-            Ok(FeishuPluginStatus {
-                installed: false,
-                version: None,
-                plugin_name: None,
-            })
+        if let Some(gp) = &entry.group_policy {
+            acct_obj["groupPolicy"] = json!(gp);
         }
-    }
-}
-
-/// Install Feishu plugin
-#[command]
-pub async fn install_feishu_plugin() -> Result<String, String> {
-    info!("[Feishu Plugin] Starting Feishu plugin installation...");
+        config["channels"]["telegram"]["accounts"][&entry.id] = acct_obj;
 
-    // First check if already installed
-    let status = check_feishu_plugin().await?;
-    if status.installed {
-        info!("[Feishu Plugin] Feishu plugin already installed, skipping");
-        return Ok(format!("Feishu plugin already installed: {}", status.plugin_name.unwrap_or_default()));
+        row.status = if exists { "updated" } else { "imported" }.to_string();
+        seen_ids.push(entry.id);
+        seen_tokens.push(entry.token);
+        rows.push(row);
     }
 
-    // Install Feishu plugin
-    // Note: Using @m1heng-clawd/feishu package name
-    info!("[Feishu Plugin] Executing openclaw plugins install @m1heng-clawd/feishu ...");
-    match shell::run_openclaw(&["plugins", "install", "@m1heng-clawd/feishu"]) {
-        Ok(output) => {
-            info!("[Feishu Plugin] Installation output: {}", output);
-
-            // Verify installation result
-            let verify_status = check_feishu_plugin().await?;
-            if verify_status.installed {
-                info!("[Feishu Plugin] Feishu plugin installed successfully");
-                Ok(format!("Feishu plugin installed successfully: {}", verify_status.plugin_name.unwrap_or_default()))
-            } else {
-                warn!("[Feishu Plugin] Installation command succeeded but plugin not found");
-                Err("Installation command succeeded but plugin not found, please check openclaw version".to_string())
-            }
-        }
-        Err(e) => {
-            error!("[Feishu Plugin] Installation failed: {}", e);
-            Err(format!("Failed to install Feishu plugin: {}\n\nPlease run manually: openclaw plugins install @m1heng-clawd/feishu", e))
+    let imported = seen_ids.len();
+    if imported > 0 {
+        // Ensure telegram is enabled and in plugins
+        config["channels"]["telegram"]["enabled"] = json!(true);
+        if config.get("plugins").is_none() {
+            config["plugins"] = json!({ "allow": ["telegram"], "entries": { "telegram": { "enabled": true } } });
         }
+        save_openclaw_config(&config, "import_telegram_accounts")?;
     }
+    info!("[Telegram Import] {} of {} accounts written", imported, rows.len());
+    Ok(TelegramImportReport { imported, rows })
 }
 
-// ============ OpenClaw Home Directory ============
-
-/// Get the OpenClaw home directory path (~/.openclaw)
+/// Turn a single channel account (channels.<channel>.accounts.<id>.enabled) or, with channel "mcp",
+/// a single MCP server on or off without deleting its config
 #[command]
-pub async fn get_openclaw_home_dir() -> Result<String, String> {
-    Ok(platform::get_config_dir())
-}
-
-// ============ Multi-Agent Routing ============
-
-/// Agent configuration for the frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AgentInfo {
-    pub id: String,
-    pub name: Option<String>,
-    pub workspace: Option<String>,
-    #[serde(alias = "agentDir", alias = "agent_dir")]
-    pub agent_dir: Option<String>,
-    pub model: Option<String>,
-    pub sandbox: Option<bool>,
-    pub heartbeat: Option<String>,
-    pub default: Option<bool>,
-    pub subagents: Option<SubagentConfig>,
-}
-// ============ New 2026.3.2 Features Configuration ============
+#[specta::specta]
+pub async fn set_account_enabled(channel: String, account_id: String, enabled: bool) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Mcps], "set_account_enabled").await;
+    info!("[Accounts] Setting {}/{} enabled: {}", channel, account_id, enabled);
+
+    if channel == "mcp" {
+        let mut configs = load_mcp_config_file()?;
+        let server = configs
+            .get_mut(&account_id)
+            .ok_or(format!("MCP server not found: {}", account_id))?;
+        server.enabled = enabled;
+        save_mcp_config_file(&configs)?;
+        return Ok(format!("MCP server '{}' {}", account_id, if enabled { "enabled" } else { "disabled" }));
+    }
+
+    let account_id = if channel == "telegram" {
+        account_id.to_lowercase().replace(' ', "-")
+    } else {
+        account_id
+    };
+    let mut config = load_openclaw_config()?;
+    let account = config
+        .pointer_mut(&format!("/channels/{}/accounts/{}", channel, account_id))
+        .and_then(|v| v.as_object_mut())
+        .ok_or(format!("Account '{}' not found in channels.{}.accounts", account_id, channel))?;
+    if enabled {
+        // Enabled is the core default, so drop the key instead of writing true
+        account.remove("enabled");
+    } else {
+        account.insert("enabled".to_string(), json!(false));
+    }
 
-/// Security profile for tools access
-#[command]
-pub async fn get_tools_profile() -> Result<String, String> {
-    info!("[Config] Getting tools profile...");
-    let config = load_openclaw_config()?;
-    let profile = config
-        .pointer("/tools/profile")
-        .and_then(|v| v.as_str())
-        .unwrap_or("messaging")
-        .to_string();
-    Ok(profile)
+    save_openclaw_config(&config, "set_account_enabled")?;
+    Ok(format!("Account '{}' {}", account_id, if enabled { "enabled" } else { "disabled" }))
 }
 
+/// Delete a Telegram bot account
 #[command]
-pub async fn save_tools_profile(profile: String) -> Result<String, String> {
-    info!("[Config] Saving tools profile: {}", profile);
+#[specta::specta]
+pub async fn delete_telegram_account(account_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "delete_telegram_account").await;
+    snapshots::snapshot_before("delete_telegram_account")?;
+    let account_id = account_id.to_lowercase().replace(' ', "-");
+    info!("[Telegram Accounts] Deleting account: {}", account_id);
     let mut config = load_openclaw_config()?;
-    if config.get("tools").is_none() {
-        config["tools"] = json!({});
-    }
-    config["tools"]["profile"] = json!(profile);
-    save_openclaw_config(&config)?;
-    Ok("Tools profile saved".to_string())
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct PdfConfig {
-    #[serde(alias = "pdfMaxPages", alias = "max_pages")]
-    pub max_pages: Option<u64>,
-    #[serde(alias = "pdfMaxBytesMb", alias = "max_bytes_mb")]
-    pub max_bytes_mb: Option<f64>,
+    let mut removed: Vec<trash::TrashedValue> = trash::take_value(
+        &mut config,
+        &format!("/channels/telegram/accounts/{}", trash::pointer_key(&account_id)),
+    )
+    .into_iter()
+    .collect();
+
+    // Also clean up any bindings referencing this account
+    removed.extend(trash::take_array_items(&mut config, "/bindings", |b| {
+        b.pointer("/match/accountId").and_then(|v| v.as_str()) == Some(&account_id)
+    }));
+
+    save_openclaw_config(&config, "delete_telegram_account")?;
+    if let Err(e) = trash::park(trash::DeletedItem {
+        id: trash::new_item_id("telegram-account"),
+        kind: "telegram-account".to_string(),
+        name: account_id.clone(),
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        values: removed,
+        env: Default::default(),
+        paths: Vec::new(),
+    }) {
+        warn!("[Telegram Accounts] Deleted but not restorable: {}", e);
+    }
+    Ok(format!("Account '{}' deleted", account_id))
 }
 
-#[command]
-pub async fn get_pdf_config() -> Result<PdfConfig, String> {
-    info!("[Config] Getting PDF config...");
-    let config = load_openclaw_config()?;
-    let max_pages = config.get("pdfMaxPages").and_then(|v| v.as_u64());
-    let max_bytes_mb = config.get("pdfMaxBytesMb").and_then(|v| v.as_f64());
-    Ok(PdfConfig { max_pages, max_bytes_mb })
+/// Result of verifying the bot's membership and rights in a Telegram group
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TelegramGroupVerification {
+    pub group_id: String,
+    /// Whether the bot is a current member of the group
+    pub in_group: bool,
+    /// getChatMember status: creator / administrator / member / restricted / left / kicked
+    pub member_status: Option<String>,
+    pub title: Option<String>,
+    /// Whether the group has topics (forum) enabled
+    pub is_forum: bool,
+    pub is_admin: bool,
+    pub can_send_messages: bool,
+    /// Bot sees every message (admin or privacy mode disabled)
+    pub can_read_all_messages: bool,
+    /// Mismatches between the configured policy and the bot's actual rights
+    pub issues: Vec<String>,
 }
 
-#[command]
-pub async fn save_pdf_config(pdf_config: PdfConfig) -> Result<String, String> {
-    info!("[Config] Saving PDF config...");
-    let mut config = load_openclaw_config()?;
-    if let Some(pages) = pdf_config.max_pages {
-        config["pdfMaxPages"] = json!(pages);
-    } else if let Some(obj) = config.as_object_mut() {
-        obj.remove("pdfMaxPages");
+/// Call a Telegram Bot API method via curl and return the `result` field
+fn telegram_api_call(bot_token: &str, method: &str, params: &[(&str, &str)]) -> Result<Value, String> {
+    let url = format!("https://api.telegram.org/bot{}/{}", bot_token, method);
+    let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args(["-s", "-G", "--max-time", "10"]);
+    for (key, value) in params {
+        cmd.arg("--data-urlencode").arg(format!("{}={}", key, value));
     }
-    if let Some(mb) = pdf_config.max_bytes_mb {
-        config["pdfMaxBytesMb"] = json!(mb);
-    } else if let Some(obj) = config.as_object_mut() {
-        obj.remove("pdfMaxBytesMb");
+    cmd.arg(&url);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
     }
-    save_openclaw_config(&config)?;
-    Ok("PDF config saved".to_string())
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct MemoryConfig {
-    pub provider: Option<String>,
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+    let body = String::from_utf8_lossy(&output.stdout).to_string();
+    let json: Value = serde_json::from_str(&body)
+        .map_err(|_| format!("Telegram API {} returned an invalid response", method))?;
+
+    if json.get("ok").and_then(|v| v.as_bool()) == Some(true) {
+        Ok(json.get("result").cloned().unwrap_or(Value::Null))
+    } else {
+        Err(json.get("description").and_then(|v| v.as_str()).unwrap_or("Unknown Telegram API error").to_string())
+    }
 }
 
+/// Verify the bot is in a Telegram group and has the rights the configured policy assumes
 #[command]
-pub async fn get_memory_config() -> Result<MemoryConfig, String> {
-    info!("[Config] Getting memory config...");
+#[specta::specta]
+pub async fn verify_telegram_group(account_id: String, group_id: String) -> Result<TelegramGroupVerification, String> {
+    let account_id = account_id.to_lowercase().replace(' ', "-");
+    info!("[Telegram Verify] Verifying group {} for account {}", group_id, account_id);
     let config = load_openclaw_config()?;
-    let provider = config
-        .pointer("/memorySearch/provider")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    Ok(MemoryConfig { provider })
-}
 
-#[command]
-pub async fn save_memory_config(memory_config: MemoryConfig) -> Result<String, String> {
-    info!("[Config] Saving memory config...");
-    let mut config = load_openclaw_config()?;
-    if let Some(provider) = memory_config.provider {
-        if config.get("memorySearch").is_none() {
-            config["memorySearch"] = json!({});
+    // Resolve the account config (multi-account first, then single-bot fallback)
+    let account_cfg = config
+        .pointer(&format!("/channels/telegram/accounts/{}", account_id))
+        .cloned()
+        .or_else(|| if account_id == "default" { config.pointer("/channels/telegram").cloned() } else { None })
+        .ok_or(format!("Telegram account '{}' not found", account_id))?;
+    let bot_token = account_cfg.get("botToken").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    if bot_token.is_empty() {
+        return Err(format!("Telegram account '{}' has no bot token", account_id));
+    }
+
+    let me = telegram_api_call(&bot_token, "getMe", &[])?;
+    let bot_id = me.get("id").and_then(|v| v.as_i64()).ok_or("getMe returned no bot id")?.to_string();
+    let privacy_disabled = me.get("can_read_all_group_messages").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let mut result = TelegramGroupVerification {
+        group_id: group_id.clone(),
+        in_group: false,
+        member_status: None,
+        title: None,
+        is_forum: false,
+        is_admin: false,
+        can_send_messages: false,
+        can_read_all_messages: false,
+        issues: Vec::new(),
+    };
+
+    let chat = match telegram_api_call(&bot_token, "getChat", &[("chat_id", &group_id)]) {
+        Ok(chat) => chat,
+        Err(e) => {
+            warn!("[Telegram Verify] getChat failed: {}", e);
+            result.issues.push(format!("Bot cannot access this group: {}", e));
+            return Ok(result);
         }
-        config["memorySearch"]["provider"] = json!(provider);
-    } else if let Some(obj) = config.as_object_mut() {
-        obj.remove("memorySearch");
-    }
-    save_openclaw_config(&config)?;
-    Ok("Memory config saved".to_string())
-}
+    };
+    result.title = chat.get("title").and_then(|v| v.as_str()).map(|s| s.to_string());
+    result.is_forum = chat.get("is_forum").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let member = telegram_api_call(&bot_token, "getChatMember", &[("chat_id", &group_id), ("user_id", &bot_id)])?;
+    let status = member.get("status").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    result.in_group = matches!(status.as_str(), "creator" | "administrator" | "member" | "restricted");
+    result.is_admin = matches!(status.as_str(), "creator" | "administrator");
+    result.can_send_messages = match status.as_str() {
+        "creator" | "administrator" | "member" => true,
+        "restricted" => member.get("can_send_messages").and_then(|v| v.as_bool()).unwrap_or(false),
+        _ => false,
+    };
+    result.can_read_all_messages = result.is_admin || privacy_disabled;
+    result.member_status = Some(status);
+
+    // Compare against what the configured policy assumes
+    if !result.in_group {
+        result.issues.push("Bot is not a member of this group".to_string());
+    } else if !result.can_send_messages {
+        result.issues.push("Bot is restricted and cannot send messages".to_string());
+    }
+
+    let group_cfg = account_cfg.pointer(&format!("/groups/{}", group_id)).cloned();
+    if let Some(group_cfg) = group_cfg {
+        let group_require_mention = group_cfg.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true);
+        let topics = group_cfg.get("topics").and_then(|v| v.as_object());
+        let any_topic_unmuted = topics
+            .map(|t| t.values().any(|tv| !tv.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true)))
+            .unwrap_or(false);
 
+        if topics.map(|t| !t.is_empty()).unwrap_or(false) && !result.is_forum {
+            result.issues.push("Topics are configured but the group does not have topics enabled".to_string());
+        }
+        if (!group_require_mention || any_topic_unmuted) && !result.can_read_all_messages {
+            result.issues.push(
+                "Policy expects the bot to read all messages, but it is not an admin and privacy mode is enabled (disable it via @BotFather or make the bot an admin)".to_string(),
+            );
+        }
+    }
 
-/// Per-agent subagent configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct SubagentConfig {
-    #[serde(alias = "allowAgents", alias = "allow_agents")]
-    pub allow_agents: Option<Vec<String>>,
+    info!("[Telegram Verify] Group {}: in_group={}, admin={}, issues={}",
+        group_id, result.in_group, result.is_admin, result.issues.len());
+    Ok(result)
 }
 
-/// Global subagent defaults
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct SubagentDefaults {
-    #[serde(alias = "maxSpawnDepth", alias = "max_spawn_depth")]
-    pub max_spawn_depth: Option<u32>,
-    #[serde(alias = "maxChildrenPerAgent", alias = "max_children_per_agent")]
-    pub max_children_per_agent: Option<u32>,
-    #[serde(alias = "maxConcurrent", alias = "max_concurrent")]
-    pub max_concurrent: Option<u32>,
-    #[serde(alias = "attachmentsEnabled", alias = "attachments_enabled")]
-    pub attachments_enabled: Option<bool>,
-    #[serde(alias = "attachmentsMaxTotalBytes", alias = "attachments_max_total_bytes")]
-    pub attachments_max_total_bytes: Option<u64>,
+// ============ Telegram Topic Routing ============
+//
+// Forum topics map to a behavior: "always" replies without a mention, "mention" needs one,
+// "blocked" ignores the topic and "agent" replies without a mention and routes the topic to a
+// specific agent through a peer binding ("<groupId>:topic:<topicId>").
+
+/// Behaviors a topic can be mapped to
+const TOPIC_BEHAVIORS: &[&str] = &["always", "mention", "blocked", "agent"];
+
+/// Behavior of a single forum topic
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TopicRule {
+    #[serde(alias = "topicId")]
+    pub topic_id: String,
+    /// "always" | "mention" | "blocked" | "agent"
+    pub behavior: String,
+    /// Target agent when behavior is "agent"
+    #[serde(alias = "agentId")]
+    pub agent_id: Option<String>,
 }
 
-/// Agent binding rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AgentBinding {
-    #[serde(alias = "agentId", alias = "agent_id")]
-    pub agent_id: String,
-    #[serde(alias = "matchRule", alias = "match_rule")]
-    pub match_rule: MatchRule,
+/// Topic mappings of one group for one bot account
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TopicRouting {
+    #[serde(alias = "accountId")]
+    pub account_id: String,
+    #[serde(alias = "groupId")]
+    pub group_id: String,
+    /// Behavior for topics without a rule: "always" | "mention"
+    #[serde(alias = "defaultBehavior")]
+    pub default_behavior: String,
+    pub topics: Vec<TopicRule>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MatchRule {
-    pub channel: Option<String>,
-    #[serde(alias = "accountId", alias = "account_id")]
-    pub account_id: Option<String>,
-    pub peer: Option<serde_json::Value>,
+/// Pointer to a group entry: per-account when accounts are configured, otherwise the single-bot groups
+fn telegram_group_pointer(config: &Value, account_id: &str, group_id: &str) -> String {
+    let escaped = group_id.replace('~', "~0").replace('/', "~1");
+    if config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()).is_some() {
+        format!("/channels/telegram/accounts/{}/groups/{}", account_id, escaped)
+    } else {
+        format!("/channels/telegram/groups/{}", escaped)
+    }
 }
 
-/// Combined agents config for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AgentsConfigResponse {
-    pub agents: Vec<AgentInfo>,
-    pub bindings: Vec<AgentBinding>,
-    pub subagent_defaults: SubagentDefaults,
+fn topic_peer_id(group_id: &str, topic_id: &str) -> String {
+    format!("{}:topic:{}", group_id, topic_id)
 }
 
-/// Get multi-agent routing configuration
-#[command]
-pub async fn get_agents_config() -> Result<AgentsConfigResponse, String> {
-    info!("[Agents] Getting agents configuration...");
-    let config = load_openclaw_config()?;
+/// Whether a binding routes a topic of `group_id` for this account
+fn is_topic_binding(binding: &Value, account_id: &str, group_id: &str) -> bool {
+    let m = match binding.get("match") {
+        Some(m) => m,
+        None => return false,
+    };
+    let account_matches = match m.get("accountId").and_then(|v| v.as_str()) {
+        Some(id) => id == account_id,
+        None => account_id == "default",
+    };
+    m.get("channel").and_then(|v| v.as_str()) == Some("telegram")
+        && account_matches
+        && m.pointer("/peer/id")
+            .and_then(|v| v.as_str())
+            .map(|id| id.starts_with(&format!("{}:topic:", group_id)))
+            .unwrap_or(false)
+}
 
-    let mut agents = Vec::new();
-    let mut bindings = Vec::new();
+/// Read the default behavior and per-topic rules of a group entry (agent routes not included).
+/// Groups require a mention unless requireMention is explicitly false.
+fn read_topic_rules(group: &Value) -> (String, Vec<TopicRule>) {
+    let require_mention = group.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true);
+    let default_behavior = if require_mention { "mention" } else { "always" };
+    let rules = group
+        .get("topics")
+        .and_then(|t| t.as_object())
+        .map(|topics| {
+            topics
+                .iter()
+                .map(|(tid, topic)| {
+                    let behavior = if topic.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+                        "blocked"
+                    } else {
+                        match topic.get("requireMention").and_then(|v| v.as_bool()) {
+                            Some(true) => "mention",
+                            Some(false) => "always",
+                            None => default_behavior,
+                        }
+                    };
+                    TopicRule { topic_id: tid.clone(), behavior: behavior.to_string(), agent_id: None }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (default_behavior.to_string(), rules)
+}
 
-    // Read agents.list — supports both array format (correct) and object format (legacy)
-    if let Some(list_arr) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
-        // Correct format: array of { id, workspace, agentDir, model, ... }
-        for agent_val in list_arr {
-            agents.push(AgentInfo {
-                id: agent_val.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
-                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                default: agent_val.get("default").and_then(|v| v.as_bool()),
-                subagents: agent_val.get("subagents").and_then(|v| {
-                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
-                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
-                    });
-                    Some(SubagentConfig { allow_agents: allow })
-                }),
-            });
-        }
-    } else if let Some(list_obj) = config.pointer("/agents/list").and_then(|v| v.as_object()) {
-        // Legacy format: object with id as keys
-        for (id, agent_val) in list_obj {
-            agents.push(AgentInfo {
-                id: id.clone(),
-                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
-                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                default: agent_val.get("default").and_then(|v| v.as_bool()),
-                subagents: agent_val.get("subagents").and_then(|v| {
-                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
-                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
-                    });
-                    Some(SubagentConfig { allow_agents: allow })
-                }),
-            });
+/// Write the default behavior and topic rules into a group entry. Every topic gets an explicit
+/// requireMention/enabled so the result does not depend on core fallbacks; other per-topic keys
+/// (systemPrompt, skills, ...) are kept.
+fn write_topic_rules(group: &mut Value, default_behavior: &str, rules: &[TopicRule]) {
+    if !group.is_object() {
+        *group = json!({});
+    }
+    group["requireMention"] = json!(default_behavior != "always");
+
+    let existing = group.get("topics").and_then(|t| t.as_object()).cloned().unwrap_or_default();
+    let mut topics = serde_json::Map::new();
+    for rule in rules {
+        let mut topic = existing.get(&rule.topic_id).and_then(|t| t.as_object()).cloned().unwrap_or_default();
+        topic.remove("enabled");
+        match rule.behavior.as_str() {
+            "blocked" => {
+                topic.remove("requireMention");
+                topic.insert("enabled".to_string(), json!(false));
+            }
+            "mention" => {
+                topic.insert("requireMention".to_string(), json!(true));
+            }
+            _ => {
+                topic.insert("requireMention".to_string(), json!(false));
+            }
         }
+        topics.insert(rule.topic_id.clone(), Value::Object(topic));
     }
 
-    // Read bindings — check top-level first (correct), then agents.bindings (legacy)
-    let bindings_arr = config.get("bindings").and_then(|v| v.as_array())
-        .or_else(|| config.pointer("/agents/bindings").and_then(|v| v.as_array()));
-    
-    if let Some(bindings_arr) = bindings_arr {
-        for binding_val in bindings_arr {
-            let empty_match = json!({});
-            let match_obj = binding_val.get("match").unwrap_or(&empty_match);
-            
-            bindings.push(AgentBinding {
-                agent_id: binding_val.get("agentId").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                match_rule: MatchRule {
-                    channel: match_obj.get("channel").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    account_id: match_obj.get("accountId").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    peer: match_obj.get("peer").cloned(),
-                }
-            });
+    if let Some(obj) = group.as_object_mut() {
+        if topics.is_empty() {
+            obj.remove("topics");
+        } else {
+            obj.insert("topics".to_string(), Value::Object(topics));
         }
     }
+}
 
-    // Read global subagent defaults from agents.defaults.subagents and tools.sessions_spawn.attachments
-    let subagent_defaults = if let Some(sub_val) = config.pointer("/agents/defaults/subagents") {
-        SubagentDefaults {
-            max_spawn_depth: sub_val.get("maxSpawnDepth").and_then(|v| v.as_u64()).map(|v| v as u32),
-            max_children_per_agent: sub_val.get("maxChildrenPerAgent").and_then(|v| v.as_u64()).map(|v| v as u32),
-            max_concurrent: sub_val.get("maxConcurrent").and_then(|v| v.as_u64()).map(|v| v as u32),
-            attachments_enabled: config.pointer("/tools/sessions_spawn/attachments/enabled").and_then(|v| v.as_bool()),
-            attachments_max_total_bytes: config.pointer("/tools/sessions_spawn/attachments/maxTotalBytes").and_then(|v| v.as_u64()),
-        }
-    } else {
-        SubagentDefaults {
-            max_spawn_depth: None,
-            max_children_per_agent: None,
-            max_concurrent: None,
-            attachments_enabled: config.pointer("/tools/sessions_spawn/attachments/enabled").and_then(|v| v.as_bool()),
-            attachments_max_total_bytes: config.pointer("/tools/sessions_spawn/attachments/maxTotalBytes").and_then(|v| v.as_u64()),
+/// Get the topic → behavior mappings of a Telegram group
+#[command]
+#[specta::specta]
+pub async fn get_topic_routing(account_id: String, group_id: String) -> Result<TopicRouting, String> {
+    info!("[Topic Routing] Getting routing for group {} (account {})", group_id, account_id);
+    let config = load_openclaw_config()?;
+
+    let group = config
+        .pointer(&telegram_group_pointer(&config, &account_id, &group_id))
+        .cloned()
+        .unwrap_or(json!({}));
+    let (default_behavior, mut topics) = read_topic_rules(&group);
+
+    let bindings = config.get("bindings").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    for binding in bindings.iter().filter(|b| is_topic_binding(b, &account_id, &group_id)) {
+        let agent_id = binding.get("agentId").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let topic_id = binding
+            .pointer("/match/peer/id")
+            .and_then(|v| v.as_str())
+            .and_then(|id| id.rsplit(":topic:").next())
+            .unwrap_or("")
+            .to_string();
+        match topics.iter_mut().find(|r| r.topic_id == topic_id) {
+            Some(rule) if rule.behavior != "blocked" => {
+                rule.behavior = "agent".to_string();
+                rule.agent_id = agent_id;
+            }
+            Some(_) => {}
+            None => topics.push(TopicRule { topic_id, behavior: "agent".to_string(), agent_id }),
         }
-    };
+    }
 
-    info!("[Agents] Found {} agents, {} bindings", agents.len(), bindings.len());
-    Ok(AgentsConfigResponse { agents, bindings, subagent_defaults })
+    Ok(TopicRouting { account_id, group_id, default_behavior, topics })
 }
 
-/// Save (add/update) an agent
+/// Save the topic → behavior mappings of a Telegram group, replacing the group's topic entries
+/// and its topic bindings
 #[command]
-pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
-    info!("[Agents] Saving agent: {}", agent.id);
-    let mut config = load_openclaw_config()?;
-
-    // Ensure agents object exists
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
+#[specta::specta]
+pub async fn save_topic_routing(routing: TopicRouting) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_topic_routing").await;
+    info!(
+        "[Topic Routing] Saving {} rule(s) for group {} (account {})",
+        routing.topics.len(),
+        routing.group_id,
+        routing.account_id
+    );
+    if routing.group_id.trim().is_empty() {
+        return Err("Group ID is required".to_string());
+    }
+    if !["always", "mention"].contains(&routing.default_behavior.as_str()) {
+        return Err(format!(
+            "Invalid default behavior '{}', expected 'always' or 'mention'",
+            routing.default_behavior
+        ));
     }
 
-    // Build agent object (array element format with "id" field)
-    let mut agent_obj = json!({ "id": agent.id });
-    if let Some(name) = &agent.name {
-        if !name.is_empty() {
-            agent_obj["name"] = json!(name);
+    let mut config = load_openclaw_config()?;
+    let agent_ids: Vec<String> = config
+        .pointer("/agents/list")
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|a| a.get("id").and_then(|v| v.as_str()).map(|s| s.to_string())).collect())
+        .unwrap_or_default();
+
+    let mut seen = std::collections::HashSet::new();
+    for rule in &routing.topics {
+        if rule.topic_id.trim().is_empty() {
+            return Err("Topic ID is required".to_string());
         }
-    }
-    if let Some(workspace) = &agent.workspace {
-        if !workspace.is_empty() {
-            agent_obj["workspace"] = json!(workspace);
+        if !seen.insert(rule.topic_id.as_str()) {
+            return Err(format!("Topic {} is listed more than once", rule.topic_id));
         }
-    }
-    if let Some(agent_dir) = &agent.agent_dir {
-        if !agent_dir.is_empty() {
-            agent_obj["agentDir"] = json!(agent_dir);
+        if !TOPIC_BEHAVIORS.contains(&rule.behavior.as_str()) {
+            return Err(format!(
+                "Invalid behavior '{}' for topic {}, expected one of: {}",
+                rule.behavior,
+                rule.topic_id,
+                TOPIC_BEHAVIORS.join(", ")
+            ));
         }
-    }
-    if let Some(model) = &agent.model {
-        if !model.is_empty() {
-            agent_obj["model"] = json!({ "primary": model });
+        if rule.behavior == "agent" {
+            match rule.agent_id.as_deref().filter(|id| !id.is_empty()) {
+                Some(id) if agent_ids.iter().any(|a| a == id) => {}
+                Some(id) => return Err(format!("Agent '{}' for topic {} does not exist", id, rule.topic_id)),
+                None => return Err(format!("Topic {} routes to an agent but no agent is selected", rule.topic_id)),
+            }
         }
     }
-    if let Some(sandbox) = agent.sandbox {
-        agent_obj["sandbox"] = json!(sandbox);
+
+    let multi_account = config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()).is_some();
+    if multi_account && config.pointer(&format!("/channels/telegram/accounts/{}", routing.account_id)).is_none() {
+        return Err(format!("Telegram account '{}' not found", routing.account_id));
     }
-    if let Some(heartbeat) = &agent.heartbeat {
-        if !heartbeat.is_empty() {
-            agent_obj["heartbeat"] = json!({ "every": heartbeat });
+
+    let pointer = telegram_group_pointer(&config, &routing.account_id, &routing.group_id);
+    let (parent, _) = pointer.rsplit_once('/').unwrap_or(("", ""));
+    if config.pointer(parent).and_then(|v| v.as_object()).is_none() {
+        let (owner, _) = parent.rsplit_once('/').unwrap_or(("", ""));
+        match config.pointer_mut(owner).and_then(|v| v.as_object_mut()) {
+            Some(obj) => {
+                obj.insert("groups".to_string(), json!({}));
+            }
+            None => return Err("Telegram channel is not configured".to_string()),
         }
     }
-    if let Some(is_default) = agent.default {
-        if is_default {
-            agent_obj["default"] = json!(true);
-        }
+    if let Some(groups) = config.pointer_mut(parent).and_then(|v| v.as_object_mut()) {
+        let group = groups.entry(routing.group_id.clone()).or_insert_with(|| json!({}));
+        write_topic_rules(group, &routing.default_behavior, &routing.topics);
     }
-    if let Some(sub) = &agent.subagents {
-        if let Some(allow) = &sub.allow_agents {
-            if !allow.is_empty() {
-                agent_obj["subagents"] = json!({ "allowAgents": allow });
+
+    // Replace this group's topic bindings
+    let routes_agents = routing.topics.iter().any(|r| r.behavior == "agent");
+    if routes_agents && !config.get("bindings").map(|v| v.is_array()).unwrap_or(false) {
+        config["bindings"] = json!([]);
+    }
+    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
+        bindings.retain(|b| !is_topic_binding(b, &routing.account_id, &routing.group_id));
+        for rule in routing.topics.iter().filter(|r| r.behavior == "agent") {
+            let mut matcher = json!({
+                "channel": "telegram",
+                "peer": { "kind": "group", "id": topic_peer_id(&routing.group_id, &rule.topic_id) }
+            });
+            if multi_account {
+                matcher["accountId"] = json!(routing.account_id);
             }
+            bindings.push(json!({ "agentId": rule.agent_id, "match": matcher }));
         }
     }
 
-    // Migrate legacy object format to array if needed
-    let mut list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
-        arr.clone()
-    } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
-        // Convert legacy object to array
-        obj.iter().map(|(id, val)| {
-            let mut entry = val.clone();
-            entry["id"] = json!(id);
-            entry
-        }).collect()
-    } else {
-        Vec::new()
-    };
+    save_openclaw_config(&config, "save_topic_routing")?;
+    Ok(format!("Saved {} topic rule(s) for group {}", routing.topics.len(), routing.group_id))
+}
 
-    // For NEW agents: use `openclaw agents add <id> --workspace <dir>` to create proper directory structure
-    // The --workspace flag is required to make the CLI non-interactive
-    let is_new_agent = !list.iter().any(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent.id));
-    let mut cli_error: Option<String> = None;
-    let is_reserved_name = agent.id.eq_ignore_ascii_case("main"); // Check if name is "main" to bypass CLI
-    
-    if is_new_agent {
-        if !is_reserved_name {
-            let openclaw_home = platform::get_config_dir();
-            let workspace_dir = if let Some(ws) = &agent.workspace {
-                ws.clone()
-            } else if agent.default == Some(true) {
-                std::path::Path::new(&openclaw_home).join("workspace").to_string_lossy().to_string()
+// ============ Feishu Plugin Management ============
+
+/// Feishu plugin status
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
+pub struct FeishuPluginStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub plugin_name: Option<String>,
+}
+
+/// Check if Feishu plugin is installed
+#[command]
+#[specta::specta]
+pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, String> {
+    info!("[Feishu Plugin] Checking Feishu plugin installation status...");
+
+    // Execute openclaw plugins list command
+    match shell::run_openclaw(&["plugins", "list"]) {
+        Ok(output) => {
+            debug!("[Feishu Plugin] plugins list output: {}", output);
+
+            // Find line containing feishu (case-insensitive)
+            let lines: Vec<&str> = output.lines().collect();
+            let feishu_line = lines.iter().find(|line| {
+                line.to_lowercase().contains("feishu")
+            });
+
+            if let Some(line) = feishu_line {
+                info!("[Feishu Plugin] Feishu plugin installed: {}", line);
+
+                // Try to parse version number (usually format is "name@version" or "name version")
+                let version = if line.contains('@') {
+                    line.split('@').last().map(|s| s.trim().to_string())
+                } else {
+                    // Try to match version number pattern (e.g. 0.1.2)
+                    let parts: Vec<&str> = line.split_whitespace().collect();
+                    parts.iter()
+                        .find(|p| p.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(false))
+                        .map(|s| s.to_string())
+                };
+
+                Ok(FeishuPluginStatus {
+                    installed: true,
+                    version,
+                    plugin_name: Some(line.trim().to_string()),
+                })
             } else {
-                std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent.id)).to_string_lossy().to_string()
-            };
-            
-            info!("[Agents] New agent '{}' — running `openclaw agents add --workspace {}`", agent.id, workspace_dir);
-            match shell::run_openclaw(&["agents", "add", &agent.id, "--workspace", &workspace_dir]) {
-                Ok(output) => {
-                    info!("[Agents] openclaw agents add succeeded: {}", output);
-                }
-                Err(e) => {
-                    // NOTE: The CLI may exit with code 1 due to TUI stdin issues in non-interactive mode,
-                    // but it still writes the agent entry to openclaw.json successfully.
-                    warn!("[Agents] openclaw agents add exited with error (may still have written config): {}", e);
-                    cli_error = Some(e);
-                }
+                info!("[Feishu Plugin] Feishu plugin not installed");
+                Ok(FeishuPluginStatus {
+                    installed: false,
+                    version: None,
+                    plugin_name: None,
+                })
             }
-            
-            // CRITICAL: Always reload config after CLI runs — it may have written the entry
-            config = load_openclaw_config()?;
-            list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
-                arr.clone()
-            } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
-                obj.iter().map(|(id, val)| {
-                    let mut entry = val.clone();
-                    entry["id"] = json!(id);
-                    entry
-                }).collect()
+        }
+        Err(e) => {
+            warn!("[Feishu Plugin] Failed to check plugin list: {}", e);
+            // If command fails, assume plugin is not installed
+            Ok(FeishuPluginStatus {
+                installed: false,
+                version: None,
+                plugin_name: None,
+            })
+        }
+    }
+}
+
+/// npm package of the Feishu channel plugin
+const FEISHU_PLUGIN_PACKAGE: &str = "@m1heng-clawd/feishu";
+
+/// Install Feishu plugin. Like `install_mcp_plugin`, a package with install scripts needs `confirmed`.
+#[command]
+#[specta::specta]
+pub async fn install_feishu_plugin(confirmed: Option<bool>) -> Result<String, String> {
+    info!("[Feishu Plugin] Starting Feishu plugin installation...");
+
+    // First check if already installed
+    let status = check_feishu_plugin().await?;
+    if status.installed {
+        info!("[Feishu Plugin] Feishu plugin already installed, skipping");
+        return Ok(format!("Feishu plugin already installed: {}", status.plugin_name.unwrap_or_default()));
+    }
+
+    check_plugin_review(FEISHU_PLUGIN_PACKAGE, confirmed.unwrap_or(false))?;
+
+    // Install Feishu plugin
+    info!("[Feishu Plugin] Executing openclaw plugins install {} ...", FEISHU_PLUGIN_PACKAGE);
+    match shell::run_openclaw(&["plugins", "install", FEISHU_PLUGIN_PACKAGE]) {
+        Ok(output) => {
+            info!("[Feishu Plugin] Installation output: {}", output);
+
+            // Verify installation result
+            let verify_status = check_feishu_plugin().await?;
+            if verify_status.installed {
+                info!("[Feishu Plugin] Feishu plugin installed successfully");
+                Ok(format!("Feishu plugin installed successfully: {}", verify_status.plugin_name.unwrap_or_default()))
             } else {
-                Vec::new()
-            };
-        } else {
-             info!("[Agents] Skipping CLI for reserved name '{}', will create manually.", agent.id);
+                warn!("[Feishu Plugin] Installation command succeeded but plugin not found");
+                Err("Installation command succeeded but plugin not found, please check openclaw version".to_string())
+            }
+        }
+        Err(e) => {
+            error!("[Feishu Plugin] Installation failed: {}", e);
+            Err(format!("Failed to install Feishu plugin: {}\n\nPlease run manually: openclaw plugins install @m1heng-clawd/feishu", e))
         }
     }
+}
 
-    // Find agent in list (handle case-insensitive match if CLI normalized the ID, e.g. AgentTest -> agenttest)
-    let match_index = list.iter().position(|a| {
-        a.get("id").and_then(|v| v.as_str()) == Some(&agent.id)
-    }).or_else(|| {
-        list.iter().position(|a| {
-             a.get("id").and_then(|v| v.as_str()).map(|s| s.to_lowercase()) == Some(agent.id.to_lowercase())
-        })
-    });
+// ============ OpenClaw Home Directory ============
 
-    // Helper closure to create agent directories
-    let ensure_directories = |agent_entry: &serde_json::Value| {
-        let openclaw_home = platform::get_config_dir();
-        
-        // 1. Agent Config Directory
-        // Use configured 'agentDir' or default to ~/.openclaw/agents/<id>/agent
-        // The CLI standard is to have the agent files inside an `agent` subdirectory
-        let agent_dir_path = if let Some(dir) = agent_entry.get("agentDir").and_then(|v| v.as_str()) {
-             std::path::PathBuf::from(dir)
-        } else {
-             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
-             std::path::Path::new(&openclaw_home).join("agents").join(id).join("agent")
-        };
-        
-        if !agent_dir_path.exists() {
-             info!("[Agents] Creating agent directory: {:?}", agent_dir_path);
-             let _ = std::fs::create_dir_all(&agent_dir_path);
+/// Get the OpenClaw home directory path (~/.openclaw)
+#[command]
+#[specta::specta]
+pub async fn get_openclaw_home_dir() -> Result<String, String> {
+    Ok(platform::get_config_dir())
+}
+
+// ============ Portable Mode ============
+
+/// Where Manager state is stored
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PortableModeStatus {
+    /// Portable mode active for this run
+    pub active: bool,
+    /// Marker file present next to the executable (applies on next start)
+    pub marker_present: bool,
+    /// Forced through OPENCLAW_MANAGER_PORTABLE
+    pub forced_by_env: bool,
+    pub exe_dir: Option<String>,
+    /// Directory currently holding manager.json and manager/
+    pub data_dir: String,
+}
+
+fn get_portable_marker_path() -> Result<std::path::PathBuf, String> {
+    let exe_dir = platform::get_exe_dir().ok_or("Cannot determine the Manager executable directory")?;
+    Ok(std::path::Path::new(&exe_dir).join(platform::PORTABLE_MARKER_FILE))
+}
+
+/// Copy manager.json and the manager/ data directory from one root to another
+fn migrate_manager_state(from_root: &str, to_root: &str) -> Result<(), String> {
+    let from = std::path::Path::new(from_root);
+    let to = std::path::Path::new(to_root);
+    std::fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+
+    let manager_json = from.join("manager.json");
+    if manager_json.exists() {
+        std::fs::copy(&manager_json, to.join("manager.json"))
+            .map_err(|e| format!("Failed to copy manager.json: {}", e))?;
+    }
+    let data_dir = from.join("manager");
+    if data_dir.is_dir() {
+        file::copy_dir_all(&data_dir, &to.join("manager"))
+            .map_err(|e| format!("Failed to copy Manager data: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Get portable mode status
+#[command]
+#[specta::specta]
+pub async fn get_portable_mode() -> Result<PortableModeStatus, String> {
+    let marker_present = get_portable_marker_path().map(|p| p.exists()).unwrap_or(false);
+    Ok(PortableModeStatus {
+        active: platform::is_portable_mode(),
+        marker_present,
+        forced_by_env: std::env::var(platform::PORTABLE_ENV).map(|v| !v.is_empty() && v != "0").unwrap_or(false),
+        exe_dir: platform::get_exe_dir(),
+        data_dir: platform::get_manager_root_dir(),
+    })
+}
+
+/// Enable or disable portable mode (takes effect after restarting the Manager).
+/// With `migrate`, current Manager state is copied to the new location.
+#[command]
+#[specta::specta]
+pub async fn set_portable_mode(enabled: bool, migrate: bool) -> Result<String, String> {
+    info!("[Portable Mode] Setting portable mode enabled={} migrate={}", enabled, migrate);
+    let marker = get_portable_marker_path()?;
+    let exe_dir = platform::get_exe_dir().ok_or("Cannot determine the Manager executable directory")?;
+    let portable_root = std::path::Path::new(&exe_dir).join("data").display().to_string();
+
+    if enabled {
+        std::fs::write(&marker, "Manager state is stored in the data directory next to this file\n")
+            .map_err(|e| format!("Failed to create portable marker (is the directory writable?): {}", e))?;
+        if migrate && !platform::is_portable_mode() {
+            migrate_manager_state(&platform::get_manager_root_dir(), &portable_root)?;
         }
-        
-        // SOUL.md
-        let soul_path = agent_dir_path.join("SOUL.md");
-        if !soul_path.exists() {
-             info!("[Agents] SOUL.md missing, creating default");
-             let name = agent_entry.get("name").and_then(|v| v.as_str()).unwrap_or("agent");
-             let default_soul = format!("You are {}, a helpful AI assistant.", name);
-             let _ = std::fs::write(soul_path, default_soul);
+    } else {
+        if marker.exists() {
+            std::fs::remove_file(&marker).map_err(|e| format!("Failed to remove portable marker: {}", e))?;
+        }
+        if migrate && platform::is_portable_mode() {
+            migrate_manager_state(&portable_root, &platform::get_config_dir())?;
         }
+    }
 
-        // models.json
-        let models_path = agent_dir_path.join("models.json");
-        if !models_path.exists() {
-             info!("[Agents] models.json missing, creating default");
-             let default_models = json!({
+    info!("[Portable Mode] Marker updated at {}", marker.display());
+    Ok("Portable mode setting saved, restart the Manager to apply".to_string())
+}
+
+// ============ Config Directory Relocation ============
+
+/// Top-level entries of the config directory that belong to the Manager, not OpenClaw
+const MANAGER_OWNED_ENTRIES: &[&str] = &["manager.json", "manager"];
+
+/// Count files and bytes below a directory (used to verify a copy)
+fn dir_summary(path: &std::path::Path, skip_top_level: &[&str]) -> std::io::Result<(u64, u64)> {
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        if skip_top_level.iter().any(|s| entry.file_name() == *s) {
+            continue;
+        }
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            let (f, b) = dir_summary(&entry.path(), &[])?;
+            files += f;
+            bytes += b;
+        } else {
+            files += 1;
+            bytes += meta.len();
+        }
+    }
+    Ok((files, bytes))
+}
+
+/// Move the OpenClaw config directory (e.g. to another drive or a synced folder).
+/// Copies the current directory, verifies the copy, then records the new location.
+/// The old directory is left in place so nothing is lost if the new one is unusable.
+#[command]
+#[specta::specta]
+pub async fn set_config_dir(path: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager, ConfigResource::Mcps], "set_config_dir").await;
+    info!("[Config Dir] Relocating config directory to {}", path);
+    let target = std::path::PathBuf::from(path.trim());
+    if !target.is_absolute() {
+        return Err("Config directory must be an absolute path".to_string());
+    }
+
+    let current = std::path::PathBuf::from(platform::get_config_dir());
+    let default_dir = platform::get_default_config_dir();
+    let target_str = target.display().to_string();
+    if target == current {
+        return Ok("Config directory unchanged".to_string());
+    }
+    if target.starts_with(&current) {
+        return Err("The new config directory cannot be inside the current one".to_string());
+    }
+    if target.join("openclaw.json").exists() && target_str != default_dir {
+        return Err(format!("{} already contains an openclaw.json, refusing to overwrite it", target_str));
+    }
+
+    if current.is_dir() {
+        std::fs::create_dir_all(&target).map_err(|e| format!("Failed to create {}: {}", target_str, e))?;
+        for entry in std::fs::read_dir(&current).map_err(|e| format!("Failed to read config directory: {}", e))? {
+            let entry = entry.map_err(|e| format!("Failed to read config directory: {}", e))?;
+            if MANAGER_OWNED_ENTRIES.iter().any(|s| entry.file_name() == *s) {
+                continue;
+            }
+            let dest = target.join(entry.file_name());
+            let copied = if entry.path().is_dir() {
+                file::copy_dir_all(&entry.path(), &dest)
+            } else {
+                std::fs::copy(entry.path(), &dest).map(|_| ())
+            };
+            copied.map_err(|e| format!("Failed to copy {}: {}", entry.path().display(), e))?;
+        }
+
+        let before = dir_summary(&current, MANAGER_OWNED_ENTRIES)
+            .map_err(|e| format!("Failed to verify copy: {}", e))?;
+        let after = dir_summary(&target, MANAGER_OWNED_ENTRIES)
+            .map_err(|e| format!("Failed to verify copy: {}", e))?;
+        if after.0 < before.0 || after.1 < before.1 {
+            return Err(format!(
+                "Copy verification failed ({} of {} files, {} of {} bytes), keeping the current directory",
+                after.0, before.0, after.1, before.1
+            ));
+        }
+        info!("[Config Dir] Copied and verified {} files ({} bytes)", before.0, before.1);
+    } else {
+        std::fs::create_dir_all(&target).map_err(|e| format!("Failed to create {}: {}", target_str, e))?;
+    }
+
+    // Moving back to ~/.openclaw clears the override
+    let new_override = if target_str == default_dir { None } else { Some(target_str.clone()) };
+    let mut manager_config = load_manager_config()?;
+    if !manager_config.is_object() {
+        manager_config = json!({});
+    }
+    match &new_override {
+        Some(dir) => manager_config[platform::CONFIG_DIR_OVERRIDE_KEY] = json!(dir),
+        None => {
+            if let Some(obj) = manager_config.as_object_mut() {
+                obj.remove(platform::CONFIG_DIR_OVERRIDE_KEY);
+            }
+        }
+    }
+    save_manager_config(&manager_config)?;
+    platform::set_config_dir_override(new_override);
+
+    info!("[Config Dir] Config directory is now {}", target_str);
+    Ok(format!(
+        "Config directory moved to {}. The old directory {} was kept, delete it once everything works. Restart the gateway to apply.",
+        target_str,
+        current.display()
+    ))
+}
+
+// ============ Multi-Agent Routing ============
+
+/// Agent configuration for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AgentInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub workspace: Option<String>,
+    #[serde(alias = "agentDir", alias = "agent_dir")]
+    pub agent_dir: Option<String>,
+    pub model: Option<String>,
+    pub sandbox: Option<bool>,
+    pub heartbeat: Option<String>,
+    pub default: Option<bool>,
+    pub subagents: Option<SubagentConfig>,
+}
+// ============ New 2026.3.2 Features Configuration ============
+
+/// Security profile for tools access
+#[command]
+#[specta::specta]
+pub async fn get_tools_profile() -> Result<String, String> {
+    info!("[Config] Getting tools profile...");
+    let config = load_openclaw_config()?;
+    let profile = config
+        .pointer("/tools/profile")
+        .and_then(|v| v.as_str())
+        .unwrap_or("messaging")
+        .to_string();
+    Ok(profile)
+}
+
+#[command]
+
+#[specta::specta]
+pub async fn save_tools_profile(profile: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_tools_profile").await;
+    info!("[Config] Saving tools profile: {}", profile);
+    let mut config = load_openclaw_config()?;
+    if config.get("tools").is_none() {
+        config["tools"] = json!({});
+    }
+    config["tools"]["profile"] = json!(profile);
+    save_openclaw_config(&config, "save_tools_profile")?;
+    Ok("Tools profile saved".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct PdfConfig {
+    #[serde(alias = "pdfMaxPages", alias = "max_pages")]
+    pub max_pages: Option<u64>,
+    #[serde(alias = "pdfMaxBytesMb", alias = "max_bytes_mb")]
+    pub max_bytes_mb: Option<f64>,
+}
+
+#[command]
+
+#[specta::specta]
+pub async fn get_pdf_config() -> Result<PdfConfig, String> {
+    info!("[Config] Getting PDF config...");
+    let config = load_openclaw_config()?;
+    let max_pages = config.get("pdfMaxPages").and_then(|v| v.as_u64());
+    let max_bytes_mb = config.get("pdfMaxBytesMb").and_then(|v| v.as_f64());
+    Ok(PdfConfig { max_pages, max_bytes_mb })
+}
+
+#[command]
+
+#[specta::specta]
+pub async fn save_pdf_config(pdf_config: PdfConfig) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_pdf_config").await;
+    info!("[Config] Saving PDF config...");
+    let mut config = load_openclaw_config()?;
+    if let Some(pages) = pdf_config.max_pages {
+        config["pdfMaxPages"] = json!(pages);
+    } else if let Some(obj) = config.as_object_mut() {
+        obj.remove("pdfMaxPages");
+    }
+    if let Some(mb) = pdf_config.max_bytes_mb {
+        config["pdfMaxBytesMb"] = json!(mb);
+    } else if let Some(obj) = config.as_object_mut() {
+        obj.remove("pdfMaxBytesMb");
+    }
+    save_openclaw_config(&config, "save_pdf_config")?;
+    Ok("PDF config saved".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct MemoryConfig {
+    pub provider: Option<String>,
+}
+
+/// Where openclaw reads the memory search settings
+const MEMORY_SEARCH_POINTER: &str = "/agents/defaults/memorySearch";
+
+/// memorySearch settings, falling back to the top-level block older Manager versions wrote
+pub(crate) fn memory_search(config: &Value) -> Option<&Value> {
+    config.pointer(MEMORY_SEARCH_POINTER).or_else(|| config.get("memorySearch"))
+}
+
+/// memorySearch settings for editing, with a top-level block moved under agents.defaults
+/// (keys already there win)
+pub(crate) fn memory_search_mut(config: &mut Value) -> &mut serde_json::Map<String, Value> {
+    let legacy = config.as_object_mut().and_then(|root| root.remove("memorySearch"));
+    let mut block = config
+        .pointer(MEMORY_SEARCH_POINTER)
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    if let Some(Value::Object(legacy)) = legacy {
+        for (key, value) in legacy {
+            block.entry(key).or_insert(value);
+        }
+    }
+    set_at_pointer(config, MEMORY_SEARCH_POINTER, Some(Value::Object(block)));
+    config
+        .pointer_mut(MEMORY_SEARCH_POINTER)
+        .and_then(|v| v.as_object_mut())
+        .expect("memorySearch was just set")
+}
+
+#[command]
+
+#[specta::specta]
+pub async fn get_memory_config() -> Result<MemoryConfig, String> {
+    info!("[Config] Getting memory config...");
+    let config = load_openclaw_config()?;
+    let memory = memory_search(&config);
+    let enabled = memory.and_then(|m| m.get("enabled")).and_then(|v| v.as_bool()) != Some(false);
+    let provider = memory
+        .and_then(|m| m.get("provider"))
+        .and_then(|v| v.as_str())
+        .filter(|_| enabled)
+        .map(|s| s.to_string());
+    Ok(MemoryConfig { provider })
+}
+
+/// Set the memory search provider; None turns memory search off and keeps the embedding
+/// model and key for when it is turned back on
+#[command]
+
+#[specta::specta]
+pub async fn save_memory_config(memory_config: MemoryConfig) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_memory_config").await;
+    info!("[Config] Saving memory config...");
+    let mut config = load_openclaw_config()?;
+    let memory = memory_search_mut(&mut config);
+    match memory_config.provider {
+        Some(provider) => {
+            memory.insert("provider".to_string(), json!(provider));
+            memory.remove("enabled");
+        }
+        None => {
+            memory.insert("enabled".to_string(), json!(false));
+        }
+    }
+    save_openclaw_config(&config, "save_memory_config")?;
+    Ok("Memory config saved".to_string())
+}
+
+// ============ Embeddings ============
+
+/// Embedding model used for memory search (agents.defaults.memorySearch in openclaw.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct EmbeddingConfig {
+    /// "openai" | "gemini" | "local"
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    /// OpenAI-compatible endpoint for self-hosted embedding servers (memorySearch.remote.baseUrl)
+    pub base_url: Option<String>,
+    /// Write-only: empty keeps the stored key
+    pub api_key: Option<String>,
+    /// Read-only: masked stored key
+    #[serde(default)]
+    pub api_key_masked: Option<String>,
+    /// Provider to use when the primary one fails ("none" disables)
+    pub fallback: Option<String>,
+}
+
+const EMBEDDING_PROVIDERS: &[&str] = &["openai", "gemini", "local"];
+
+/// Default embedding model per provider, as the core picks it
+pub(crate) fn default_embedding_model(provider: &str) -> Option<&'static str> {
+    match provider {
+        "openai" => Some("text-embedding-3-small"),
+        "gemini" => Some("gemini-embedding-001"),
+        _ => None,
+    }
+}
+
+/// Get the embeddings provider/model for memory search
+#[command]
+#[specta::specta]
+pub async fn get_embedding_config() -> Result<EmbeddingConfig, String> {
+    info!("[Config] Getting embedding config...");
+    let config = load_openclaw_config()?;
+    let memory = memory_search(&config);
+    let get = |pointer: &str| memory.and_then(|m| m.pointer(pointer)).and_then(|v| v.as_str()).map(String::from);
+    Ok(EmbeddingConfig {
+        provider: get("/provider"),
+        model: get("/model"),
+        base_url: get("/remote/baseUrl"),
+        api_key: None,
+        api_key_masked: get("/remote/apiKey").map(|k| mask_key(&k)),
+        fallback: get("/fallback"),
+    })
+}
+
+/// Save the embeddings provider/model. Other memorySearch keys are left untouched.
+#[command]
+#[specta::specta]
+pub async fn save_embedding_config(embedding_config: EmbeddingConfig) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_embedding_config").await;
+    info!("[Config] Saving embedding config: {:?} / {:?}", embedding_config.provider, embedding_config.model);
+    for provider in embedding_config.provider.iter().chain(embedding_config.fallback.iter().filter(|f| f.as_str() != "none")) {
+        if !EMBEDDING_PROVIDERS.contains(&provider.as_str()) {
+            return Err(format!("Unknown embeddings provider '{}' (use {})", provider, EMBEDDING_PROVIDERS.join(", ")));
+        }
+    }
+    if let Some(url) = embedding_config.base_url.as_deref().filter(|u| !u.is_empty()) {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!("Embeddings base URL must start with http:// or https://, got '{}'", url));
+        }
+    }
+
+    let mut config = load_openclaw_config()?;
+    let memory = memory_search_mut(&mut config);
+    let mut set = |key: &str, value: Option<String>| match value.filter(|v| !v.trim().is_empty()) {
+        Some(value) => {
+            memory.insert(key.to_string(), json!(value.trim()));
+        }
+        None => {
+            memory.remove(key);
+        }
+    };
+    set("provider", embedding_config.provider);
+    set("model", embedding_config.model);
+    set("fallback", embedding_config.fallback);
+
+    let mut remote = memory.get("remote").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+    match embedding_config.base_url.filter(|u| !u.trim().is_empty()) {
+        Some(url) => remote.insert("baseUrl".to_string(), json!(url.trim())),
+        None => remote.remove("baseUrl"),
+    };
+    if let Some(key) = embedding_config.api_key.filter(|k| !k.trim().is_empty()) {
+        remote.insert("apiKey".to_string(), json!(key.trim()));
+    }
+    if remote.is_empty() {
+        memory.remove("remote");
+    } else {
+        memory.insert("remote".to_string(), Value::Object(remote));
+    }
+
+    save_openclaw_config(&config, "save_embedding_config")?;
+    Ok("Embedding config saved".to_string())
+}
+
+// ============ Voice (TTS / STT) ============
+
+/// Speech settings: messages.tts (replies as voice notes) and tools.media.audio (transcription)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct VoiceConfig {
+    /// When replies are spoken: "off" | "always" | "inbound" | "tagged"
+    pub tts_auto: Option<String>,
+    /// "openai" | "elevenlabs" | "edge"
+    pub tts_provider: Option<String>,
+    /// Voice name (openai, edge) or voice id (elevenlabs)
+    pub tts_voice: Option<String>,
+    pub tts_model: Option<String>,
+    /// Write-only: empty keeps the stored key
+    pub tts_api_key: Option<String>,
+    /// Read-only: masked stored key of the selected provider
+    #[serde(default)]
+    pub tts_api_key_masked: Option<String>,
+    /// Transcribe inbound voice messages
+    pub stt_enabled: Option<bool>,
+    /// e.g. "openai", "groq", "deepgram"
+    pub stt_provider: Option<String>,
+    pub stt_model: Option<String>,
+}
+
+const TTS_PROVIDERS: &[&str] = &["openai", "elevenlabs", "edge"];
+const TTS_AUTO_MODES: &[&str] = &["off", "always", "inbound", "tagged"];
+
+/// Key names of the voice and model settings inside messages.tts.<provider>
+fn tts_provider_keys(provider: &str) -> (&'static str, &'static str) {
+    match provider {
+        "elevenlabs" => ("voiceId", "modelId"),
+        _ => ("voice", "model"),
+    }
+}
+
+/// Get TTS/STT settings
+#[command]
+#[specta::specta]
+pub async fn get_voice_config() -> Result<VoiceConfig, String> {
+    info!("[Voice] Getting voice config...");
+    let config = load_openclaw_config()?;
+    let get = |pointer: &str| config.pointer(pointer).and_then(|v| v.as_str()).map(String::from);
+    let provider = get("/messages/tts/provider");
+    let (voice_key, model_key) = tts_provider_keys(provider.as_deref().unwrap_or(""));
+    let section = |key: &str| provider.as_ref().and_then(|p| get(&format!("/messages/tts/{}/{}", p, key)));
+    let stt_model = config.pointer("/tools/media/audio/models/0");
+
+    Ok(VoiceConfig {
+        tts_auto: get("/messages/tts/auto"),
+        tts_voice: section(voice_key),
+        tts_model: section(model_key),
+        tts_api_key: None,
+        tts_api_key_masked: section("apiKey").map(|k| mask_key(&k)),
+        tts_provider: provider.clone(),
+        stt_enabled: config.pointer("/tools/media/audio/enabled").and_then(|v| v.as_bool()),
+        stt_provider: stt_model.and_then(|m| m.get("provider")).and_then(|v| v.as_str()).map(String::from),
+        stt_model: stt_model.and_then(|m| m.get("model")).and_then(|v| v.as_str()).map(String::from),
+    })
+}
+
+/// Save TTS/STT settings. Settings of other TTS providers and other audio models are kept.
+#[command]
+#[specta::specta]
+pub async fn save_voice_config(voice_config: VoiceConfig) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_voice_config").await;
+    info!("[Voice] Saving voice config: tts={:?}, stt={:?}", voice_config.tts_provider, voice_config.stt_provider);
+    if let Some(auto) = voice_config.tts_auto.as_deref().filter(|a| !TTS_AUTO_MODES.contains(a)) {
+        return Err(format!("Invalid TTS mode '{}' (use {})", auto, TTS_AUTO_MODES.join(", ")));
+    }
+    if let Some(provider) = voice_config.tts_provider.as_deref().filter(|p| !TTS_PROVIDERS.contains(p)) {
+        return Err(format!("Unknown TTS provider '{}' (use {})", provider, TTS_PROVIDERS.join(", ")));
+    }
+
+    let mut config = load_openclaw_config()?;
+    let trimmed = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    // messages.tts
+    let mut tts = config.pointer("/messages/tts").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+    match trimmed(voice_config.tts_auto) {
+        Some(auto) => tts.insert("auto".to_string(), json!(auto)),
+        None => tts.remove("auto"),
+    };
+    if let Some(provider) = trimmed(voice_config.tts_provider) {
+        let (voice_key, model_key) = tts_provider_keys(&provider);
+        let mut section = tts.get(&provider).and_then(|v| v.as_object()).cloned().unwrap_or_default();
+        for (key, value) in [(voice_key, trimmed(voice_config.tts_voice)), (model_key, trimmed(voice_config.tts_model))] {
+            match value {
+                Some(value) => section.insert(key.to_string(), json!(value)),
+                None => section.remove(key),
+            };
+        }
+        if let Some(key) = trimmed(voice_config.tts_api_key) {
+            section.insert("apiKey".to_string(), json!(key));
+        }
+        tts.insert(provider.clone(), Value::Object(section));
+        tts.insert("provider".to_string(), json!(provider));
+    } else {
+        tts.remove("provider");
+    }
+    if config.get("messages").map(|v| !v.is_object()).unwrap_or(true) {
+        config["messages"] = json!({});
+    }
+    if tts.is_empty() {
+        if let Some(messages) = config["messages"].as_object_mut() {
+            messages.remove("tts");
+        }
+    } else {
+        config["messages"]["tts"] = Value::Object(tts);
+    }
+
+    // tools.media.audio: the first model entry is the one the Manager edits
+    if voice_config.stt_enabled.is_some() || voice_config.stt_provider.is_some() {
+        let mut audio = config.pointer("/tools/media/audio").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+        if let Some(enabled) = voice_config.stt_enabled {
+            audio.insert("enabled".to_string(), json!(enabled));
+        }
+        if let Some(provider) = trimmed(voice_config.stt_provider) {
+            let mut models = audio.get("models").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let mut entry = json!({ "provider": provider });
+            if let Some(model) = trimmed(voice_config.stt_model) {
+                entry["model"] = json!(model);
+            }
+            if models.is_empty() {
+                models.push(entry);
+            } else {
+                models[0] = entry;
+            }
+            audio.insert("models".to_string(), Value::Array(models));
+        }
+        if config.get("tools").map(|v| !v.is_object()).unwrap_or(true) {
+            config["tools"] = json!({});
+        }
+        if config["tools"].get("media").map(|v| !v.is_object()).unwrap_or(true) {
+            config["tools"]["media"] = json!({});
+        }
+        config["tools"]["media"]["audio"] = Value::Object(audio);
+    }
+
+    save_openclaw_config(&config, "save_voice_config")?;
+    Ok("Voice config saved".to_string())
+}
+
+/// Per-agent subagent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct SubagentConfig {
+    #[serde(alias = "allowAgents", alias = "allow_agents")]
+    pub allow_agents: Option<Vec<String>>,
+}
+
+/// Global subagent defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct SubagentDefaults {
+    #[serde(alias = "maxSpawnDepth", alias = "max_spawn_depth")]
+    pub max_spawn_depth: Option<u32>,
+    #[serde(alias = "maxChildrenPerAgent", alias = "max_children_per_agent")]
+    pub max_children_per_agent: Option<u32>,
+    #[serde(alias = "maxConcurrent", alias = "max_concurrent")]
+    pub max_concurrent: Option<u32>,
+    #[serde(alias = "attachmentsEnabled", alias = "attachments_enabled")]
+    pub attachments_enabled: Option<bool>,
+    #[serde(alias = "attachmentsMaxTotalBytes", alias = "attachments_max_total_bytes")]
+    pub attachments_max_total_bytes: Option<u64>,
+}
+
+/// Agent binding rule
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AgentBinding {
+    #[serde(alias = "agentId", alias = "agent_id")]
+    pub agent_id: String,
+    #[serde(alias = "matchRule", alias = "match_rule")]
+    pub match_rule: MatchRule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MatchRule {
+    pub channel: Option<String>,
+    #[serde(alias = "accountId", alias = "account_id")]
+    pub account_id: Option<String>,
+    pub peer: Option<serde_json::Value>,
+    /// Forum topic inside a Telegram group peer, stored as peer id "<groupId>:topic:<topicId>"
+    pub topic: Option<String>,
+}
+
+/// Combined agents config for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AgentsConfigResponse {
+    pub agents: Vec<AgentInfo>,
+    pub bindings: Vec<AgentBinding>,
+    pub subagent_defaults: SubagentDefaults,
+}
+
+/// Get multi-agent routing configuration
+#[command]
+#[specta::specta]
+pub async fn get_agents_config() -> Result<AgentsConfigResponse, String> {
+    info!("[Agents] Getting agents configuration...");
+    let config = load_openclaw_config()?;
+
+    let mut agents = Vec::new();
+    let mut bindings = Vec::new();
+
+    // Read agents.list — supports both array format (correct) and object format (legacy)
+    if let Some(list_arr) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
+        // Correct format: array of { id, workspace, agentDir, model, ... }
+        for agent_val in list_arr {
+            agents.push(AgentInfo {
+                id: agent_val.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
+                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                default: agent_val.get("default").and_then(|v| v.as_bool()),
+                subagents: agent_val.get("subagents").and_then(|v| {
+                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
+                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
+                    });
+                    Some(SubagentConfig { allow_agents: allow })
+                }),
+            });
+        }
+    } else if let Some(list_obj) = config.pointer("/agents/list").and_then(|v| v.as_object()) {
+        // Legacy format: object with id as keys
+        for (id, agent_val) in list_obj {
+            agents.push(AgentInfo {
+                id: id.clone(),
+                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
+                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                default: agent_val.get("default").and_then(|v| v.as_bool()),
+                subagents: agent_val.get("subagents").and_then(|v| {
+                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
+                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
+                    });
+                    Some(SubagentConfig { allow_agents: allow })
+                }),
+            });
+        }
+    }
+
+    // Read bindings — check top-level first (correct), then agents.bindings (legacy)
+    let bindings_arr = config.get("bindings").and_then(|v| v.as_array())
+        .or_else(|| config.pointer("/agents/bindings").and_then(|v| v.as_array()));
+    
+    if let Some(bindings_arr) = bindings_arr {
+        for binding_val in bindings_arr {
+            let empty_match = json!({});
+            let match_obj = binding_val.get("match").unwrap_or(&empty_match);
+            
+            let (peer, topic) = split_topic_peer(match_obj.get("peer").cloned());
+            bindings.push(AgentBinding {
+                agent_id: binding_val.get("agentId").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                match_rule: MatchRule {
+                    channel: match_obj.get("channel").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    account_id: match_obj.get("accountId").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    peer,
+                    topic,
+                }
+            });
+        }
+    }
+
+    // Read global subagent defaults from agents.defaults.subagents and tools.sessions_spawn.attachments
+    let subagent_defaults = if let Some(sub_val) = config.pointer("/agents/defaults/subagents") {
+        SubagentDefaults {
+            max_spawn_depth: sub_val.get("maxSpawnDepth").and_then(|v| v.as_u64()).map(|v| v as u32),
+            max_children_per_agent: sub_val.get("maxChildrenPerAgent").and_then(|v| v.as_u64()).map(|v| v as u32),
+            max_concurrent: sub_val.get("maxConcurrent").and_then(|v| v.as_u64()).map(|v| v as u32),
+            attachments_enabled: config.pointer("/tools/sessions_spawn/attachments/enabled").and_then(|v| v.as_bool()),
+            attachments_max_total_bytes: config.pointer("/tools/sessions_spawn/attachments/maxTotalBytes").and_then(|v| v.as_u64()),
+        }
+    } else {
+        SubagentDefaults {
+            max_spawn_depth: None,
+            max_children_per_agent: None,
+            max_concurrent: None,
+            attachments_enabled: config.pointer("/tools/sessions_spawn/attachments/enabled").and_then(|v| v.as_bool()),
+            attachments_max_total_bytes: config.pointer("/tools/sessions_spawn/attachments/maxTotalBytes").and_then(|v| v.as_u64()),
+        }
+    };
+
+    info!("[Agents] Found {} agents, {} bindings", agents.len(), bindings.len());
+    Ok(AgentsConfigResponse { agents, bindings, subagent_defaults })
+}
+
+/// Save (add/update) an agent
+#[command]
+#[specta::specta]
+pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_agent").await;
+    snapshots::snapshot_before("save_agent")?;
+    info!("[Agents] Saving agent: {}", agent.id);
+    let mut config = load_openclaw_config()?;
+
+    // Ensure agents object exists
+    if config.get("agents").is_none() {
+        config["agents"] = json!({});
+    }
+
+    // Build agent object (array element format with "id" field)
+    let mut agent_obj = json!({ "id": agent.id });
+    if let Some(name) = &agent.name {
+        if !name.is_empty() {
+            agent_obj["name"] = json!(name);
+        }
+    }
+    if let Some(workspace) = &agent.workspace {
+        if !workspace.is_empty() {
+            agent_obj["workspace"] = json!(workspace);
+        }
+    }
+    if let Some(agent_dir) = &agent.agent_dir {
+        if !agent_dir.is_empty() {
+            agent_obj["agentDir"] = json!(agent_dir);
+        }
+    }
+    if let Some(model) = &agent.model {
+        if !model.is_empty() {
+            validate_agent_model(&config, model)?;
+            agent_obj["model"] = json!({ "primary": model });
+        }
+    }
+    if let Some(sandbox) = agent.sandbox {
+        agent_obj["sandbox"] = json!(sandbox);
+    }
+    if let Some(heartbeat) = &agent.heartbeat {
+        if !heartbeat.is_empty() {
+            agent_obj["heartbeat"] = json!({ "every": heartbeat });
+        }
+    }
+    if let Some(is_default) = agent.default {
+        if is_default {
+            agent_obj["default"] = json!(true);
+        }
+    }
+    if let Some(sub) = &agent.subagents {
+        if let Some(allow) = &sub.allow_agents {
+            if !allow.is_empty() {
+                agent_obj["subagents"] = json!({ "allowAgents": allow });
+            }
+        }
+    }
+
+    // Migrate legacy object format to array if needed
+    let mut list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
+        arr.clone()
+    } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
+        // Convert legacy object to array
+        obj.iter().map(|(id, val)| {
+            let mut entry = val.clone();
+            entry["id"] = json!(id);
+            entry
+        }).collect()
+    } else {
+        Vec::new()
+    };
+
+    // For NEW agents: use `openclaw agents add <id> --workspace <dir>` to create proper directory structure
+    // The --workspace flag is required to make the CLI non-interactive
+    let is_new_agent = !list.iter().any(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent.id));
+    let mut cli_error: Option<String> = None;
+    let is_reserved_name = agent.id.eq_ignore_ascii_case("main"); // Check if name is "main" to bypass CLI
+    
+    if is_new_agent {
+        if !is_reserved_name {
+            let openclaw_home = platform::get_config_dir();
+            let workspace_dir = if let Some(ws) = &agent.workspace {
+                ws.clone()
+            } else if agent.default == Some(true) {
+                std::path::Path::new(&openclaw_home).join("workspace").to_string_lossy().to_string()
+            } else {
+                std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent.id)).to_string_lossy().to_string()
+            };
+            
+            info!("[Agents] New agent '{}' — running `openclaw agents add --workspace {}`", agent.id, workspace_dir);
+            match shell::run_openclaw(&["agents", "add", &agent.id, "--workspace", &workspace_dir]) {
+                Ok(output) => {
+                    info!("[Agents] openclaw agents add succeeded: {}", output);
+                }
+                Err(e) => {
+                    // NOTE: The CLI may exit with code 1 due to TUI stdin issues in non-interactive mode,
+                    // but it still writes the agent entry to openclaw.json successfully.
+                    warn!("[Agents] openclaw agents add exited with error (may still have written config): {}", e);
+                    cli_error = Some(e);
+                }
+            }
+            
+            // CRITICAL: Always reload config after CLI runs — it may have written the entry
+            config = load_openclaw_config()?;
+            list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
+                arr.clone()
+            } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
+                obj.iter().map(|(id, val)| {
+                    let mut entry = val.clone();
+                    entry["id"] = json!(id);
+                    entry
+                }).collect()
+            } else {
+                Vec::new()
+            };
+        } else {
+             info!("[Agents] Skipping CLI for reserved name '{}', will create manually.", agent.id);
+        }
+    }
+
+    // Find agent in list (handle case-insensitive match if CLI normalized the ID, e.g. AgentTest -> agenttest)
+    let match_index = list.iter().position(|a| {
+        a.get("id").and_then(|v| v.as_str()) == Some(&agent.id)
+    }).or_else(|| {
+        list.iter().position(|a| {
+             a.get("id").and_then(|v| v.as_str()).map(|s| s.to_lowercase()) == Some(agent.id.to_lowercase())
+        })
+    });
+
+    // Helper closure to create agent directories
+    let ensure_directories = |agent_entry: &serde_json::Value| {
+        let openclaw_home = platform::get_config_dir();
+        
+        // 1. Agent Config Directory
+        // Use configured 'agentDir' or default to ~/.openclaw/agents/<id>/agent
+        // The CLI standard is to have the agent files inside an `agent` subdirectory
+        let agent_dir_path = if let Some(dir) = agent_entry.get("agentDir").and_then(|v| v.as_str()) {
+             std::path::PathBuf::from(dir)
+        } else {
+             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
+             std::path::Path::new(&openclaw_home).join("agents").join(id).join("agent")
+        };
+        
+        if !agent_dir_path.exists() {
+             info!("[Agents] Creating agent directory: {:?}", agent_dir_path);
+             let _ = std::fs::create_dir_all(&agent_dir_path);
+        }
+        
+        // SOUL.md
+        let soul_path = agent_dir_path.join("SOUL.md");
+        if !soul_path.exists() {
+             info!("[Agents] SOUL.md missing, creating default");
+             let name = agent_entry.get("name").and_then(|v| v.as_str()).unwrap_or("agent");
+             let default_soul = format!("You are {}, a helpful AI assistant.", name);
+             let _ = std::fs::write(soul_path, default_soul);
+        }
+
+        // models.json
+        let models_path = agent_dir_path.join("models.json");
+        if !models_path.exists() {
+             info!("[Agents] models.json missing, creating default");
+             let default_models = json!({
                 "providers": {
                     "glm": {
                         "baseUrl": "https://api.z.ai/api/anthropic",
@@ -2786,1006 +6573,3058 @@ pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
                         ]
                     }
                 }
-             });
-             // Pretty print the JSON
-             if let Ok(content) = serde_json::to_string_pretty(&default_models) {
-                 let _ = std::fs::write(models_path, content);
-             }
+             });
+             // Pretty print the JSON
+             if let Ok(content) = serde_json::to_string_pretty(&default_models) {
+                 let _ = std::fs::write(models_path, content);
+             }
+        }
+        
+        // 2. Workspace Directory
+        // Use configured 'workspace' or default to ~/.openclaw/workspace-<id>
+        let workspace_path = if let Some(ws) = agent_entry.get("workspace").and_then(|v| v.as_str()) {
+             std::path::PathBuf::from(ws)
+        } else {
+             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
+             std::path::Path::new(&openclaw_home).join(format!("workspace-{}", id))
+        };
+        
+        if !workspace_path.exists() {
+             info!("[Agents] Creating workspace directory: {:?}", workspace_path);
+             let _ = std::fs::create_dir_all(&workspace_path);
+        }
+        
+        // Return paths to update config if they were defaults
+        (agent_dir_path.to_string_lossy().to_string(), workspace_path.to_string_lossy().to_string())
+    };
+
+    // Update or add the agent
+    if let Some(idx) = match_index {
+        // Enforce "Main" agent properties: it is the fallback unless another agent was made the default
+        if agent.id.eq_ignore_ascii_case("main") && !list.iter().any(|a| a.get("default").and_then(|v| v.as_bool()) == Some(true)) {
+            list[idx]["default"] = json!(true);
+        }
+
+        let existing = &mut list[idx];
+        
+        // Merge: only overwrite fields the user explicitly set (non-empty)
+        if let Some(name) = &agent.name {
+            if !name.is_empty() {
+                existing["name"] = json!(name);
+            }
+        }
+        if let Some(model) = &agent.model {
+            if !model.is_empty() {
+                existing["model"] = json!({ "primary": model });
+            }
+        }
+        if let Some(is_default) = agent.default {
+            if is_default {
+                existing["default"] = json!(true);
+            }
+        }
+
+        if let Some(sub) = &agent.subagents {
+            if let Some(allow) = &sub.allow_agents {
+                if !allow.is_empty() {
+                    existing["subagents"] = json!({ "allowAgents": allow });
+                }
+            }
+        }
+        if let Some(sandbox) = agent.sandbox {
+            existing["sandbox"] = json!(sandbox);
+        }
+        if let Some(heartbeat) = &agent.heartbeat {
+            if !heartbeat.is_empty() {
+                existing["heartbeat"] = json!({ "every": heartbeat });
+            }
+        }
+        
+        // Repair directories for existing agent
+        let _ = ensure_directories(existing);
+        
+    } else {
+        // Not found in config (New agent, manual addition)
+        
+        // If we tried to create it via CLI and it's missing (and NOT reserved), that means CLI strictly failed.
+        if let Some(err) = cli_error {
+             if !is_reserved_name {
+                 return Err(format!("Failed to create agent via CLI: {}. Check logs or name uniqueness.", err));
+             }
+        }
+
+        // Add to list
+        let mut new_entry = agent_obj.clone();
+        
+        // Ensure directories and get default paths if we need to explicitly save them
+        let (actual_agent_dir, actual_workspace) = ensure_directories(&new_entry);
+        
+        // If user didn't specify paths, save the defaults we just used/created
+        if new_entry.get("agentDir").is_none() {
+             new_entry["agentDir"] = json!(actual_agent_dir);
+        }
+        if new_entry.get("workspace").is_none() {
+             new_entry["workspace"] = json!(actual_workspace);
+        }
+        
+        list.push(new_entry);
+    }
+
+    // Only one agent can be the fallback
+    if agent.default == Some(true) {
+        mark_fallback_agent(&mut list, &agent.id);
+    }
+    config["agents"]["list"] = json!(list);
+
+    // Auto-create binding if a Telegram bot account is available and this agent has no binding yet
+    let agent_id = agent.id.clone();
+    let available_accounts: Vec<String> = config.pointer("/channels/telegram/accounts")
+        .and_then(|v| v.as_object())
+        .map(|accts| accts.keys().cloned().collect())
+        .unwrap_or_default();
+
+    if !available_accounts.is_empty() {
+        // Check if this agent already has ANY binding
+        let has_existing_binding = config.get("bindings")
+            .and_then(|v| v.as_array())
+            .map(|bindings| bindings.iter().any(|b| {
+                b.get("agentId").and_then(|v| v.as_str()) == Some(&agent_id)
+            }))
+            .unwrap_or(false);
+
+        if !has_existing_binding {
+            // Find accounts already bound to other agents
+            let bound_accounts: Vec<String> = config.get("bindings")
+                .and_then(|v| v.as_array())
+                .map(|bindings| bindings.iter().filter_map(|b| {
+                    b.get("match").and_then(|m| m.get("accountId")).and_then(|v| v.as_str()).map(|s| s.to_string())
+                }).collect())
+                .unwrap_or_default();
+
+            // Prefer: exact match > substring match > first unbound account > first account
+            let best_account = available_accounts.iter()
+                .find(|a| **a == agent_id) // exact match
+                .or_else(|| available_accounts.iter().find(|a| a.contains(&agent_id) || agent_id.contains(a.as_str()))) // substring
+                .or_else(|| available_accounts.iter().find(|a| !bound_accounts.contains(a))) // unbound
+                .or_else(|| available_accounts.first()) // fallback
+                .cloned();
+
+            if let Some(account_id) = best_account {
+                info!("[Agents] Auto-creating binding for agent '{}' → account '{}'", agent_id, account_id);
+                if config.get("bindings").is_none() {
+                    config["bindings"] = json!([]);
+                }
+                if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
+                    bindings.push(json!({
+                        "agentId": agent_id,
+                        "match": { "channel": "telegram", "accountId": account_id }
+                    }));
+                }
+            }
+        }
+    }
+
+    check_config_schema(&config)?;
+    save_openclaw_config(&config, "save_agent")?;
+    Ok(format!("Agent '{}' saved", agent.id))
+}
+
+/// Save global subagent defaults
+#[command]
+#[specta::specta]
+pub async fn save_subagent_defaults(defaults: SubagentDefaults) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_subagent_defaults").await;
+    info!("[Agents] Saving subagent defaults");
+    let mut config = load_openclaw_config()?;
+
+    // Ensure agents.defaults exists
+    if config.get("agents").is_none() {
+        config["agents"] = json!({});
+    }
+    if config["agents"].get("defaults").is_none() {
+        config["agents"]["defaults"] = json!({});
+    }
+
+    let mut sub_obj = json!({});
+    if let Some(depth) = defaults.max_spawn_depth {
+        sub_obj["maxSpawnDepth"] = json!(depth);
+    }
+    if let Some(children) = defaults.max_children_per_agent {
+        sub_obj["maxChildrenPerAgent"] = json!(children);
+    }
+    if let Some(concurrent) = defaults.max_concurrent {
+        sub_obj["maxConcurrent"] = json!(concurrent);
+    }
+
+    config["agents"]["defaults"]["subagents"] = sub_obj;
+
+    // Subagent sessions_spawn inline file attachments
+    if defaults.attachments_enabled.is_some() || defaults.attachments_max_total_bytes.is_some() {
+        if config.get("tools").is_none() {
+            config["tools"] = json!({});
+        }
+        if config["tools"].get("sessions_spawn").is_none() {
+            config["tools"]["sessions_spawn"] = json!({});
+        }
+        if config["tools"]["sessions_spawn"].get("attachments").is_none() {
+            config["tools"]["sessions_spawn"]["attachments"] = json!({});
+        }
+
+        if let Some(enabled) = defaults.attachments_enabled {
+            config["tools"]["sessions_spawn"]["attachments"]["enabled"] = json!(enabled);
+        }
+        if let Some(max_bytes) = defaults.attachments_max_total_bytes {
+            config["tools"]["sessions_spawn"]["attachments"]["maxTotalBytes"] = json!(max_bytes);
+        }
+    }
+
+    save_openclaw_config(&config, "save_subagent_defaults")?;
+    Ok("Subagent defaults saved".to_string())
+}
+
+/// Delete an agent
+#[command]
+#[specta::specta]
+pub async fn delete_agent(agent_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "delete_agent").await;
+    snapshots::snapshot_before("delete_agent")?;
+    info!("[Agents] Deleting agent: {}", agent_id);
+    let mut config = load_openclaw_config()?;
+
+    // 1. Find the agent to get its paths (before deleting from config)
+    let mut agent_dir_to_delete: Option<String> = None;
+    let mut workspace_to_delete: Option<String> = None;
+
+    if let Some(list) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
+        if let Some(agent) = list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id)) {
+            // Get agent directory
+            if let Some(dir) = agent.get("agentDir").and_then(|v| v.as_str()) {
+                agent_dir_to_delete = Some(dir.to_string());
+            }
+            // Get workspace directory
+            if let Some(ws) = agent.get("workspace").and_then(|v| v.as_str()) {
+                workspace_to_delete = Some(ws.to_string());
+            } else {
+                // Fallback: deduce workspace path if default pattern was used
+                let openclaw_home = platform::get_config_dir();
+                let default_ws = std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_id));
+                if default_ws.exists() {
+                    workspace_to_delete = Some(default_ws.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    // 2. Move the files (if they exist) into the trash
+    // We do this BEFORE updating config, but we don't abort if it fails (just warn)
+    // because we still want to remove the broken/stale entry from config.
+    let trash_id = trash::new_item_id("agent");
+    let mut moved = Vec::new();
+
+    if let Some(agent_dir) = agent_dir_to_delete {
+        let path = std::path::Path::new(&agent_dir);
+        // Check if this is a nested 'agent' directory (standard structure: .../agents/<id>/agent)
+        // If so, we want to delete the PARENT directory (e.g. .../agents/<id>) to clean up everything including sessions.
+        let path_to_remove = if path.ends_with("agent") {
+            path.parent().unwrap_or(path)
+        } else {
+            path
+        };
+
+        if path_to_remove.exists() {
+            info!("[Agents] Removing agent directory tree: {:?}", path_to_remove);
+            match trash::move_to_trash(&trash_id, path_to_remove) {
+                Ok(path) => moved.push(path),
+                Err(e) => warn!("[Agents] Failed to remove agent directory {:?}: {}", path_to_remove, e),
+            }
+        }
+    } else {
+        // Fallback: try default location if not specified in config
+        let openclaw_home = platform::get_config_dir();
+        // Default structure is now ~/.openclaw/agents/<id> (which contains agent/, sessions/, etc.)
+        let default_agent_root = std::path::Path::new(&openclaw_home).join("agents").join(&agent_id);
+        
+        if default_agent_root.exists() {
+             info!("[Agents] Removing default agent directory tree: {:?}", default_agent_root);
+             match trash::move_to_trash(&trash_id, &default_agent_root) {
+                Ok(path) => moved.push(path),
+                Err(e) => warn!("[Agents] Failed to remove default agent directory: {}", e),
+            }
+        }
+    }
+
+    if let Some(workspace) = workspace_to_delete {
+        let path = std::path::Path::new(&workspace);
+        if path.exists() {
+            info!("[Agents] Removing workspace directory: {}", workspace);
+            match trash::move_to_trash(&trash_id, path) {
+                Ok(path) => moved.push(path),
+                Err(e) => warn!("[Agents] Failed to remove workspace directory {}: {}", workspace, e),
+            }
+        }
+    }
+
+    // 3. Remove from agents.list (array format)
+    let is_agent = |a: &Value| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id);
+    let mut removed = trash::take_array_items(&mut config, "/agents/list", is_agent);
+
+    // Remove related bindings (top-level)
+    let binds_agent = |b: &Value| b.get("agentId").and_then(|v| v.as_str()) == Some(&agent_id);
+    removed.extend(trash::take_array_items(&mut config, "/bindings", binds_agent));
+    // Also clean legacy agents.bindings
+    removed.extend(trash::take_array_items(&mut config, "/agents/bindings", binds_agent));
+
+    save_openclaw_config(&config, "delete_agent")?;
+    if let Err(e) = trash::park(trash::DeletedItem {
+        id: trash_id,
+        kind: "agent".to_string(),
+        name: agent_id.clone(),
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        values: removed,
+        env: Default::default(),
+        paths: moved,
+    }) {
+        warn!("[Agents] Deleted but not restorable: {}", e);
+    }
+    Ok(format!("Agent '{}' and its files were deleted", agent_id))
+}
+
+/// Split a Telegram topic peer ("<groupId>:topic:<topicId>") into the group peer and topic id
+fn split_topic_peer(peer: Option<Value>) -> (Option<Value>, Option<String>) {
+    let mut peer = match peer {
+        Some(p) => p,
+        None => return (None, None),
+    };
+    let split = peer
+        .get("id")
+        .and_then(|v| v.as_str())
+        .and_then(|id| id.split_once(":topic:"))
+        .map(|(group, topic)| (group.to_string(), topic.to_string()));
+    match split {
+        Some((group, topic)) => {
+            peer["id"] = json!(group);
+            (Some(peer), Some(topic))
+        }
+        None => (Some(peer), None),
+    }
+}
+
+/// Build the core `match` object for a rule, folding a topic into the peer id
+fn binding_match_json(rule: &MatchRule) -> Value {
+    let mut match_obj = json!({});
+    if let Some(ch) = rule.channel.as_ref().filter(|c| !c.is_empty()) {
+        match_obj["channel"] = json!(ch);
+    }
+    if let Some(acc) = rule.account_id.as_ref().filter(|a| !a.is_empty()) {
+        match_obj["accountId"] = json!(acc);
+    }
+    if let Some(peer) = rule.peer.as_ref().filter(|p| !p.is_null()) {
+        let mut peer = peer.clone();
+        if let (Some(topic), Some(id)) = (rule.topic.as_ref().filter(|t| !t.is_empty()), peer.get("id").and_then(|v| v.as_str())) {
+            peer["id"] = json!(topic_peer_id(id, topic));
+        }
+        match_obj["peer"] = peer;
+    }
+    match_obj
+}
+
+/// Peer kinds understood by the core router
+const BINDING_PEER_KINDS: &[&str] = &["direct", "dm", "group", "channel"];
+
+/// Check a binding before it is written: the agent exists, the peer is well formed, topics only
+/// narrow Telegram groups, and the same match is not already bound
+fn validate_agent_binding(config: &Value, binding: &AgentBinding) -> Result<(), String> {
+    let agent_exists = config
+        .pointer("/agents/list")
+        .map(|list| match list {
+            Value::Array(arr) => arr.iter().any(|a| a.get("id").and_then(|v| v.as_str()) == Some(binding.agent_id.as_str())),
+            Value::Object(obj) => obj.contains_key(&binding.agent_id),
+            _ => false,
+        })
+        .unwrap_or(false);
+    if !agent_exists && binding.agent_id != "main" {
+        return Err(format!("Agent '{}' does not exist", binding.agent_id));
+    }
+
+    let rule = &binding.match_rule;
+    let channel = rule.channel.as_deref().filter(|c| !c.is_empty());
+    if let Some(peer) = rule.peer.as_ref().filter(|p| !p.is_null()) {
+        let kind = peer.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+        if !BINDING_PEER_KINDS.contains(&kind) {
+            return Err(format!(
+                "Invalid peer kind '{}', expected one of: {}",
+                kind,
+                BINDING_PEER_KINDS.join(", ")
+            ));
+        }
+        if peer.get("id").and_then(|v| v.as_str()).map(|id| id.trim().is_empty()).unwrap_or(true) {
+            return Err("Peer binding needs a peer id".to_string());
+        }
+        if channel.is_none() {
+            return Err("Peer binding needs a channel".to_string());
+        }
+    }
+    if rule.topic.as_ref().map(|t| !t.is_empty()).unwrap_or(false) {
+        let group_peer = rule.peer.as_ref().and_then(|p| p.get("kind")).and_then(|v| v.as_str()) == Some("group");
+        if channel != Some("telegram") || !group_peer {
+            return Err("Topic bindings are only supported for Telegram group peers".to_string());
+        }
+    }
+
+    let new_match = binding_match_json(rule);
+    let existing = config
+        .get("bindings")
+        .or_else(|| config.pointer("/agents/bindings"))
+        .and_then(|v| v.as_array());
+    if let Some(other) = existing.and_then(|list| list.iter().find(|b| b.get("match").unwrap_or(&json!({})) == &new_match)) {
+        return Err(format!(
+            "This match is already bound to agent '{}'",
+            other.get("agentId").and_then(|v| v.as_str()).unwrap_or("unknown")
+        ));
+    }
+    Ok(())
+}
+
+/// Save an agent binding rule
+#[command]
+#[specta::specta]
+
+pub async fn save_agent_binding(binding: AgentBinding) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_agent_binding").await;
+    info!("[Agents] Saving binding for agent: {}", binding.agent_id);
+    let mut binding = binding;
+    // A contact name as the peer id is bound by its peer id
+    if let (Some(channel), Some(peer)) = (binding.match_rule.channel.clone(), binding.match_rule.peer.as_mut()) {
+        if let Some(id) = peer.get("id").and_then(|v| v.as_str()).map(|id| id.to_string()) {
+            peer["id"] = json!(crate::commands::contacts::resolve_peer(&channel, &id));
+        }
+    }
+    let mut config = load_openclaw_config()?;
+
+    // Ensure top-level bindings array exists
+    if config.get("bindings").is_none() {
+        config["bindings"] = json!([]);
+    }
+
+    // Migrate legacy agents.bindings to top-level if present
+    if let Some(legacy) = config.pointer("/agents/bindings").and_then(|v| v.as_array()).map(|a| a.clone()) {
+        if let Some(top) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
+            for b in legacy {
+                top.push(b);
+            }
+        }
+        // Remove legacy location
+        if let Some(agents) = config.get_mut("agents").and_then(|v| v.as_object_mut()) {
+            agents.remove("bindings");
+        }
+    }
+
+    validate_agent_binding(&config, &binding)?;
+
+    let binding_obj = json!({
+        "agentId": binding.agent_id,
+        "match": binding_match_json(&binding.match_rule)
+    });
+
+    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
+        bindings.push(binding_obj);
+    }
+
+    save_openclaw_config(&config, "save_agent_binding")?;
+    Ok(format!("Binding for agent '{}' saved", binding.agent_id))
+}
+
+/// Delete an agent binding by index
+#[command]
+#[specta::specta]
+pub async fn delete_agent_binding(index: usize) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "delete_agent_binding").await;
+    snapshots::snapshot_before("delete_agent_binding")?;
+    info!("[Agents] Deleting binding at index: {}", index);
+    let mut config = load_openclaw_config()?;
+
+    // Try top-level bindings first (correct location)
+    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
+        if index < bindings.len() {
+            bindings.remove(index);
+            save_openclaw_config(&config, "delete_agent_binding")?;
+            return Ok(format!("Binding at index {} deleted", index));
+        } else {
+            return Err(format!("Binding index {} out of range", index));
+        }
+    }
+
+    // Fallback to legacy agents.bindings
+    if let Some(bindings) = config.pointer_mut("/agents/bindings").and_then(|v| v.as_array_mut()) {
+        if index < bindings.len() {
+            bindings.remove(index);
+            save_openclaw_config(&config, "delete_agent_binding")?;
+            return Ok(format!("Binding at index {} deleted", index));
+        } else {
+            return Err(format!("Binding index {} out of range", index));
+        }
+    }
+
+    Err("No bindings found".to_string())
+}
+
+/// Agent whose bindings are parked in manager.json (pausedAgents)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PausedAgent {
+    pub agent_id: String,
+    pub paused_at: Option<String>,
+    pub bindings: Vec<Value>,
+}
+
+/// List agents paused with pause_agent
+#[command]
+#[specta::specta]
+pub async fn get_paused_agents() -> Result<Vec<PausedAgent>, String> {
+    let manager_config = load_manager_config()?;
+    let mut paused: Vec<PausedAgent> = manager_config
+        .get("pausedAgents")
+        .and_then(|v| v.as_object())
+        .map(|m| {
+            m.iter()
+                .map(|(id, entry)| PausedAgent {
+                    agent_id: id.clone(),
+                    paused_at: entry.get("pausedAt").and_then(|v| v.as_str()).map(String::from),
+                    bindings: entry.get("bindings").and_then(|v| v.as_array()).cloned().unwrap_or_default(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paused.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+    Ok(paused)
+}
+
+/// Kill switch for one agent: move its bindings out of openclaw.json into manager.json
+/// (pausedAgents) so resume_agent can put them back. Writes the live config even if a draft is open.
+#[command]
+#[specta::specta]
+pub async fn pause_agent(agent_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "pause_agent").await;
+    warn!("[Agents] Pausing agent: {}", agent_id);
+    let mut manager_config = load_manager_config()?;
+    if manager_config.pointer(&format!("/pausedAgents/{}", agent_id)).is_some() {
+        return Err(format!("Agent '{}' is already paused", agent_id));
+    }
+
+    let mut config = load_live_openclaw_config()?;
+    let mut parked = Vec::new();
+    for pointer in ["/bindings", "/agents/bindings"] {
+        if let Some(bindings) = config.pointer_mut(pointer).and_then(|v| v.as_array_mut()) {
+            bindings.retain(|b| {
+                let matches = b.get("agentId").and_then(|v| v.as_str()) == Some(agent_id.as_str());
+                if matches {
+                    parked.push(b.clone());
+                }
+                !matches
+            });
+        }
+    }
+    if parked.is_empty() {
+        return Err(format!("Agent '{}' has no bindings to pause", agent_id));
+    }
+
+    // The default agent also gets every message no binding matches
+    let is_default = config
+        .pointer("/agents/list")
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter().any(|a| {
+                a.get("id").and_then(|v| v.as_str()) == Some(agent_id.as_str())
+                    && a.get("default").and_then(|v| v.as_bool()).unwrap_or(false)
+            })
+        })
+        .unwrap_or(false);
+
+    if manager_config.get("pausedAgents").is_none() {
+        manager_config["pausedAgents"] = json!({});
+    }
+    manager_config["pausedAgents"][&agent_id] = json!({
+        "pausedAt": chrono::Utc::now().to_rfc3339(),
+        "bindings": parked,
+    });
+    save_manager_config(&manager_config)?;
+    save_live_openclaw_config(&config, "pause_agent")?;
+
+    let mut message = format!("Agent '{}' paused ({} binding(s) parked)", agent_id, parked.len());
+    if is_default {
+        message.push_str(". It is the default agent and still answers unmatched messages");
+    }
+    info!("[Agents] {}", message);
+    Ok(message)
+}
+
+/// Restore the bindings parked by pause_agent
+#[command]
+#[specta::specta]
+pub async fn resume_agent(agent_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "resume_agent").await;
+    info!("[Agents] Resuming agent: {}", agent_id);
+    let mut manager_config = load_manager_config()?;
+    let parked = manager_config
+        .pointer(&format!("/pausedAgents/{}/bindings", agent_id))
+        .and_then(|v| v.as_array())
+        .cloned()
+        .ok_or(format!("Agent '{}' is not paused", agent_id))?;
+
+    let mut config = load_live_openclaw_config()?;
+    if !config.get("bindings").map(|b| b.is_array()).unwrap_or(false) {
+        config["bindings"] = json!([]);
+    }
+    let bindings = config["bindings"].as_array_mut().unwrap();
+    let mut restored = 0;
+    for binding in parked {
+        // Skip bindings recreated by hand while the agent was paused
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+            restored += 1;
+        }
+    }
+
+    save_live_openclaw_config(&config, "resume_agent")?;
+    if let Some(paused) = manager_config.get_mut("pausedAgents").and_then(|v| v.as_object_mut()) {
+        paused.remove(&agent_id);
+    }
+    save_manager_config(&manager_config)?;
+
+    info!("[Agents] Agent '{}' resumed ({} binding(s) restored)", agent_id, restored);
+    Ok(format!("Agent '{}' resumed ({} binding(s) restored)", agent_id, restored))
+}
+
+// ============ Agent Soul / Personality ============
+
+/// Directory holding an agent's SOUL.md: its agentDir (absolute, or relative to `base`),
+/// otherwise <base>/agents/<id>
+fn agent_soul_dir(config: &Value, base: &str, agent_id: &str) -> std::path::PathBuf {
+    let agent_dir = config
+        .pointer("/agents/list")
+        .and_then(|v| v.as_array())
+        .and_then(|list| list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id)))
+        .and_then(|agent| agent.get("agentDir").and_then(|v| v.as_str()));
+    match agent_dir {
+        // Path::join keeps an absolute agentDir as is
+        Some(dir) => std::path::Path::new(base).join(dir),
+        None => platform::join_path(base, &["agents", agent_id]),
+    }
+}
+
+/// SOUL.md locations in order of likelihood - the CORRECT one first, then legacy layouts
+pub(crate) fn agent_soul_candidates(config: &Value, base: &str, agent_id: &str) -> Vec<std::path::PathBuf> {
+    let dir = agent_soul_dir(config, base, agent_id);
+    vec![
+        dir.join("SOUL.md"),                                        // 1. agents/{id}/SOUL.md (CORRECT)
+        platform::join_path(base, &["agent", agent_id, "SOUL.md"]), // 2. agent/{id}/SOUL.md (Legacy/Buggy)
+        dir.join("agent").join("SOUL.md"),                          // 3. agents/{id}/agent/SOUL.md (Legacy/Buggy)
+    ]
+}
+
+/// A saved workspace prompt file and what it now costs on every message
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PromptFileSaved {
+    pub message: String,
+    pub estimate: PromptCostEstimate,
+}
+
+/// Token / cost estimate for writing `content` over `previous` in an agent's `file`, priced with
+/// the agent's primary model and cut to bootstrapMaxChars like OpenClaw does
+fn prompt_cost_estimate(config: &Value, agent_id: &str, file: &str, previous: &str, content: &str) -> PromptCostEstimate {
+    let max_chars = config
+        .pointer("/agents/defaults/bootstrapMaxChars")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(session_replay::DEFAULT_BOOTSTRAP_MAX_CHARS);
+    let model = prompt_cost::agent_primary_model(config, agent_id);
+    let price = model.as_deref().and_then(|m| prompt_cost::model_price(config, m));
+    prompt_cost::estimate(file, previous, content, max_chars, model, price)
+}
+
+/// Workspace prompt file name, limited to the files OpenClaw injects into the system prompt
+fn bootstrap_file_name(file: &str) -> Result<&'static str, String> {
+    session_replay::BOOTSTRAP_FILES
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(file))
+        .copied()
+        .ok_or_else(|| format!("'{}' is not a workspace prompt file ({})", file, session_replay::BOOTSTRAP_FILES.join(", ")))
+}
+
+/// Current content of an agent's prompt file (SOUL.md also in its legacy locations)
+fn current_prompt_file(config: &Value, agent_id: &str, file: &str) -> String {
+    let mut paths: Vec<std::path::PathBuf> = agent_workspace_dir(config, agent_id).map(|dir| dir.join(file)).into_iter().collect();
+    if file == "SOUL.md" {
+        paths.extend(agent_soul_candidates(config, &platform::get_config_dir(), agent_id));
+    }
+    paths
+        .iter()
+        .find(|p| p.exists())
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .unwrap_or_default()
+}
+
+/// Read the personality (SOUL.md) for an agent
+#[command]
+#[specta::specta]
+pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>) -> Result<String, String> {
+    let base = workspace.unwrap_or_else(|| platform::get_config_dir());
+
+    // Resolve agent directory from config to handle case where ID != dir name
+    let config = load_openclaw_config().map_err(|e| e.to_string())?;
+    let paths = agent_soul_candidates(&config, &base, &agent_id);
+
+    for path in &paths {
+        if path.exists() {
+            info!("[Agents] Found SOUL.md at: {}", path.display());
+            return std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read SOUL.md: {}", e));
+        }
+    }
+    
+    Ok(String::new())
+}
+
+/// Save the personality (SOUL.md) for an agent, returning its token count and per-message cost
+#[command]
+#[specta::specta]
+pub async fn save_agent_system_prompt(agent_id: String, workspace: Option<String>, content: String) -> Result<PromptFileSaved, String> {
+    let base = workspace.unwrap_or_else(|| platform::get_config_dir());
+
+    // Resolve agent directory from config
+    let config = load_openclaw_config().map_err(|e| e.to_string())?;
+
+    // ONLY save to the correct canonical path
+    let path = agent_soul_dir(&config, &base, &agent_id).join("SOUL.md");
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            return Err(format!("Failed to create directory for {}: {}", path.display(), e));
+        }
+    }
+    
+    let previous = std::fs::read_to_string(&path).unwrap_or_default();
+    match std::fs::write(&path, &content) {
+        Ok(_) => {
+            info!("[Agents] Wrote SOUL.md to: {}", path.display());
+            Ok(PromptFileSaved {
+                message: format!("Personality (SOUL.md) saved for agent '{}'", agent_id),
+                estimate: prompt_cost_estimate(&config, &agent_id, "SOUL.md", &previous, &content),
+            })
+        },
+        Err(e) => Err(format!("Failed to save SOUL.md to {}: {}", path.display(), e))
+    }
+}
+
+/// Save a workspace prompt file (SOUL.md, AGENTS.md, ...) of an agent, returning its token
+/// count and the per-message cost change
+#[command]
+#[specta::specta]
+pub async fn save_agent_workspace_file(agent_id: String, file: String, content: String) -> Result<PromptFileSaved, String> {
+    let file = bootstrap_file_name(&file)?;
+    let config = load_openclaw_config()?;
+    let dir = agent_workspace_dir(&config, &agent_id)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create workspace {}: {}", dir.display(), e))?;
+    let path = dir.join(file);
+
+    let previous = std::fs::read_to_string(&path).unwrap_or_default();
+    std::fs::write(&path, &content).map_err(|e| format!("Failed to save {} to {}: {}", file, path.display(), e))?;
+    let estimate = prompt_cost_estimate(&config, &agent_id, file, &previous, &content);
+    info!(
+        "[Agents] Wrote {} to {} (~{} tokens, {:+} tokens)",
+        file,
+        path.display(),
+        estimate.tokens,
+        estimate.token_delta
+    );
+    Ok(PromptFileSaved {
+        message: format!("{} saved for agent '{}'", file, agent_id),
+        estimate,
+    })
+}
+
+/// Token count and per-message cost of a workspace prompt file if `content` were saved
+#[command]
+#[specta::specta]
+pub async fn estimate_prompt_cost(agent_id: String, file: String, content: String) -> Result<PromptCostEstimate, String> {
+    let file = bootstrap_file_name(&file)?;
+    let config = load_openclaw_config()?;
+    let previous = current_prompt_file(&config, &agent_id, file);
+    Ok(prompt_cost_estimate(&config, &agent_id, file, &previous, &content))
+}
+
+/// Find the binding that handles a message, most specific first: exact peer (including a forum
+/// topic), the parent group of a topic, then account-wide and finally channel-wide bindings.
+/// Returns the binding and which tier matched.
+fn find_routing_binding<'a>(
+    bindings: &'a [Value],
+    channel: &str,
+    account_id: &str,
+    peer_id: Option<&str>,
+    topic_id: Option<&str>,
+) -> Option<(&'a Value, &'static str)> {
+    let empty_match = json!({});
+    let scope_matches = |binding: &Value| {
+        let match_obj = binding.get("match").unwrap_or(&empty_match);
+        let account_matches = match_obj.get("accountId").and_then(|v| v.as_str()).map(|a| a == account_id || a == "*").unwrap_or(true); // None = catch-all
+        let channel_matches = match_obj.get("channel").and_then(|v| v.as_str()).map(|c| c == channel).unwrap_or(true);
+        account_matches && channel_matches
+    };
+    let binding_peer = |binding: &'a Value| binding.pointer("/match/peer/id").and_then(|v| v.as_str());
+    let has_account = |binding: &Value| binding.pointer("/match/accountId").and_then(|v| v.as_str()).map(|a| a != "*").unwrap_or(false);
+
+    let mut peer_tiers = Vec::new();
+    if let Some(peer) = peer_id.filter(|p| !p.is_empty()) {
+        if let Some(topic) = topic_id.filter(|t| !t.is_empty()) {
+            peer_tiers.push((topic_peer_id(peer, topic), "topic"));
+        }
+        peer_tiers.push((peer.to_string(), "peer"));
+    }
+    for (wanted, tier) in &peer_tiers {
+        if let Some(b) = bindings.iter().find(|b| scope_matches(b) && binding_peer(b) == Some(wanted.as_str())) {
+            return Some((b, tier));
+        }
+    }
+    let unscoped = |b: &&'a Value| scope_matches(b) && binding_peer(b).is_none();
+    bindings
+        .iter()
+        .filter(unscoped)
+        .find(|b| has_account(b))
+        .map(|b| (b, "account"))
+        .or_else(|| bindings.iter().filter(unscoped).find(|b| !has_account(b)).map(|b| (b, "channel")))
+}
+
+/// Test agent routing: given an account ID (and optionally a channel, group/channel peer and
+/// forum topic), find which agent handles it
+#[command]
+#[specta::specta]
+pub async fn test_agent_routing(
+    account_id: String,
+    channel: Option<String>,
+    peer_id: Option<String>,
+    topic_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let config = load_openclaw_config()?;
+    let channel = channel.filter(|c| !c.is_empty()).unwrap_or_else(|| "telegram".to_string());
+
+    // Walk through bindings to find a match
+    let bindings = config.get("bindings").and_then(|v| v.as_array());
+
+    if let Some(bindings) = bindings {
+        if let Some((binding, matched_by)) =
+            find_routing_binding(bindings, &channel, &account_id, peer_id.as_deref(), topic_id.as_deref())
+        {
+            let agent_id = binding.get("agentId").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+            // Find agent details
+            let agent_info = config.pointer("/agents/list")
+                .and_then(|v| v.as_array())
+                .and_then(|list| list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id)));
+
+            // Read SOUL.md preview (try all 3 locations)
+            let mut prompt_preview = String::new();
+            for path in agent_soul_candidates(&config, &platform::get_config_dir(), agent_id) {
+                if path.exists() {
+                    prompt_preview = std::fs::read_to_string(&path).unwrap_or_default();
+                    break;
+                }
+            }
+            // Cut on a char boundary, previews are often non-ASCII
+            let prompt_preview = if prompt_preview.chars().count() > 200 {
+                format!("{}...", prompt_preview.chars().take(200).collect::<String>())
+            } else {
+                prompt_preview
+            };
+
+            return Ok(json!({
+                "matched": true,
+                "agent_id": agent_id,
+                "agent_dir": agent_info.and_then(|a| a.get("agentDir").and_then(|v| v.as_str())),
+                "model": agent_info.and_then(|a| a.pointer("/model/primary").and_then(|v| v.as_str())),
+                "system_prompt_preview": prompt_preview,
+                "matched_by": matched_by,
+                "binding": binding
+            }));
+        }
+    }
+
+    let (fallback, explicit) = fallback_agent(&config);
+    Ok(json!({
+        "matched": false,
+        "agent_id": fallback,
+        "matched_by": "fallback",
+        "message": if explicit {
+            format!("No specific binding found. Messages will be handled by the fallback agent '{}'.", fallback)
+        } else {
+            format!("No specific binding found and no fallback agent is set. Messages will be handled by '{}' (first agent in the list).", fallback)
+        }
+    }))
+}
+
+/// Agent that handles messages no binding matches: the agent flagged `default`, otherwise the
+/// first agent in the list, otherwise "main". The flag tells whether it was set explicitly.
+pub(crate) fn fallback_agent(config: &Value) -> (String, bool) {
+    let ids: Vec<(String, bool)> = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|a| {
+                let id = a.get("id").and_then(|v| v.as_str())?;
+                Some((id.to_string(), a.get("default").and_then(|v| v.as_bool()) == Some(true)))
+            })
+            .collect(),
+        Some(Value::Object(obj)) => obj
+            .iter()
+            .map(|(id, a)| (id.clone(), a.get("default").and_then(|v| v.as_bool()) == Some(true)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    if let Some((id, _)) = ids.iter().find(|(_, is_default)| *is_default) {
+        return (id.clone(), true);
+    }
+    (ids.first().map(|(id, _)| id.clone()).unwrap_or_else(|| "main".to_string()), false)
+}
+
+/// Flag `agent_id` as the only default agent in an agents.list array
+fn mark_fallback_agent(list: &mut [Value], agent_id: &str) {
+    for entry in list.iter_mut() {
+        let is_target = entry.get("id").and_then(|v| v.as_str()) == Some(agent_id);
+        if let Some(obj) = entry.as_object_mut() {
+            if is_target {
+                obj.insert("default".to_string(), json!(true));
+            } else {
+                obj.remove("default");
+            }
+        }
+    }
+}
+
+/// Fallback agent info for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct FallbackAgent {
+    pub agent_id: String,
+    /// False when no agent is flagged default and the first agent is used implicitly
+    pub explicit: bool,
+}
+
+/// Get the agent that handles messages no binding matches
+#[command]
+#[specta::specta]
+pub async fn get_fallback_agent() -> Result<FallbackAgent, String> {
+    let config = load_openclaw_config()?;
+    let (agent_id, explicit) = fallback_agent(&config);
+    Ok(FallbackAgent { agent_id, explicit })
+}
+
+/// Make `agent_id` the fallback agent, clearing the default flag on every other agent
+#[command]
+#[specta::specta]
+pub async fn set_fallback_agent(agent_id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "set_fallback_agent").await;
+    info!("[Agents] Setting fallback agent: {}", agent_id);
+    let mut config = load_openclaw_config()?;
+
+    let mut list: Vec<Value> = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr.clone(),
+        Some(Value::Object(obj)) => obj
+            .iter()
+            .map(|(id, val)| {
+                let mut entry = val.clone();
+                entry["id"] = json!(id);
+                entry
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !list.iter().any(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id.as_str())) {
+        return Err(format!("Agent '{}' does not exist", agent_id));
+    }
+
+    mark_fallback_agent(&mut list, &agent_id);
+    config["agents"]["list"] = json!(list);
+    save_openclaw_config(&config, "set_fallback_agent")?;
+    Ok(format!("Agent '{}' is now the fallback agent", agent_id))
+}
+
+// ============ Heartbeat & Compaction ============
+
+/// Heartbeat configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct HeartbeatConfig {
+    pub every: Option<String>,
+    pub target: Option<String>,
+}
+
+/// Compaction configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CompactionConfig {
+    pub enabled: bool,
+    pub threshold: Option<u32>,
+    pub context_pruning: bool,
+    pub max_context_messages: Option<u32>,
+}
+
+/// Get heartbeat configuration
+#[command]
+#[specta::specta]
+pub async fn get_heartbeat_config() -> Result<HeartbeatConfig, String> {
+    info!("[Heartbeat] Getting heartbeat config...");
+    let config = load_openclaw_config()?;
+
+    let every = config.pointer("/agents/defaults/heartbeat/every")
+        .and_then(|v| v.as_str()).map(|s| s.to_string());
+    let target = config.pointer("/agents/defaults/heartbeat/target")
+        .and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    Ok(HeartbeatConfig { every, target })
+}
+
+/// Save heartbeat configuration
+#[command]
+#[specta::specta]
+pub async fn save_heartbeat_config(every: Option<String>, target: Option<String>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_heartbeat_config").await;
+    info!("[Heartbeat] Saving heartbeat config: every={:?}, target={:?}", every, target);
+    let mut config = load_openclaw_config()?;
+
+    if config.get("agents").is_none() { config["agents"] = json!({}); }
+    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+
+    if every.is_some() || target.is_some() {
+        let mut hb = json!({});
+        if let Some(e) = &every { hb["every"] = json!(e); }
+        if let Some(t) = &target { hb["target"] = json!(t); }
+        config["agents"]["defaults"]["heartbeat"] = hb;
+    } else {
+        // Remove heartbeat if both are None
+        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
+            defaults.remove("heartbeat");
         }
-        
-        // 2. Workspace Directory
-        // Use configured 'workspace' or default to ~/.openclaw/workspace-<id>
-        let workspace_path = if let Some(ws) = agent_entry.get("workspace").and_then(|v| v.as_str()) {
-             std::path::PathBuf::from(ws)
+    }
+
+    save_openclaw_config(&config, "save_heartbeat_config")?;
+    Ok("Heartbeat configuration saved".to_string())
+}
+
+/// Get compaction configuration
+#[command]
+#[specta::specta]
+pub async fn get_compaction_config() -> Result<CompactionConfig, String> {
+    info!("[Compaction] Getting compaction config...");
+    let config = load_openclaw_config()?;
+
+    let compaction_val = config.pointer("/agents/defaults/compaction");
+    let pruning_val = config.pointer("/agents/defaults/contextPruning");
+
+    let enabled = compaction_val.map(|v| {
+        // compaction can be true/false or an object with settings
+        v.as_bool().unwrap_or(true)
+    }).unwrap_or(false);
+
+    let threshold = compaction_val
+        .and_then(|v| v.get("threshold"))
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32);
+
+    let context_pruning = pruning_val.map(|v| v.as_bool().unwrap_or(false)).unwrap_or(false);
+
+    let max_context_messages = pruning_val
+        .and_then(|v| v.get("maxMessages"))
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32);
+
+    Ok(CompactionConfig { enabled, threshold, context_pruning, max_context_messages })
+}
+
+/// Save compaction configuration
+#[command]
+#[specta::specta]
+pub async fn save_compaction_config(
+    enabled: bool,
+    threshold: Option<u32>,
+    context_pruning: bool,
+    max_context_messages: Option<u32>,
+) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_compaction_config").await;
+    info!("[Compaction] Saving compaction config: enabled={}, pruning={}", enabled, context_pruning);
+    let mut config = load_openclaw_config()?;
+
+    if config.get("agents").is_none() { config["agents"] = json!({}); }
+    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+
+    if enabled {
+        let mut comp = json!({});
+        if let Some(t) = threshold { comp["threshold"] = json!(t); }
+        config["agents"]["defaults"]["compaction"] = comp;
+    } else {
+        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
+            defaults.remove("compaction");
+        }
+    }
+
+    if context_pruning {
+        let mut pruning = json!(true);
+        if let Some(max) = max_context_messages {
+            pruning = json!({ "maxMessages": max });
+        }
+        config["agents"]["defaults"]["contextPruning"] = pruning;
+    } else {
+        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
+            defaults.remove("contextPruning");
+        }
+    }
+
+    save_openclaw_config(&config, "save_compaction_config")?;
+    Ok("Compaction configuration saved".to_string())
+}
+
+// ============ Workspace & Agent Personality ============
+
+/// Workspace configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WorkspaceConfig {
+    pub workspace: Option<String>,
+    pub timezone: Option<String>,
+    pub time_format: Option<String>,
+    pub skip_bootstrap: bool,
+    pub bootstrap_max_chars: Option<u32>,
+}
+
+/// Get workspace configuration
+#[command]
+#[specta::specta]
+pub async fn get_workspace_config() -> Result<WorkspaceConfig, String> {
+    info!("[Workspace] Getting workspace config...");
+    let config = load_openclaw_config()?;
+
+    let workspace = config.pointer("/agents/defaults/workspace")
+        .and_then(|v| v.as_str()).map(|s| s.to_string());
+    let timezone = config.pointer("/manager/timezone")
+        .and_then(|v| v.as_str()).map(|s| s.to_string());
+    let time_format = config.pointer("/manager/time_format")
+        .and_then(|v| v.as_str()).map(|s| s.to_string());
+    let skip_bootstrap = config.pointer("/agents/defaults/skipBootstrap")
+        .and_then(|v| v.as_bool()).unwrap_or(false);
+    let bootstrap_max_chars = config.pointer("/agents/defaults/bootstrapMaxChars")
+        .and_then(|v| v.as_u64()).map(|v| v as u32);
+
+    Ok(WorkspaceConfig { workspace, timezone, time_format, skip_bootstrap, bootstrap_max_chars })
+}
+
+/// Save workspace configuration
+#[command]
+#[specta::specta]
+pub async fn save_workspace_config(
+    workspace: Option<String>,
+    timezone: Option<String>,
+    time_format: Option<String>,
+    skip_bootstrap: bool,
+    bootstrap_max_chars: Option<u32>,
+) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_workspace_config").await;
+    info!("[Workspace] Saving workspace config...");
+    let mut config = load_openclaw_config()?;
+
+    if config.get("agents").is_none() { config["agents"] = json!({}); }
+    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+
+    // Set or remove each field in agents.defaults
+    if let Some(defaults) = config.pointer_mut("/agents/defaults").and_then(|v| v.as_object_mut()) {
+        match &workspace {
+            Some(w) if !w.is_empty() => { defaults.insert("workspace".into(), json!(w)); }
+            _ => { defaults.remove("workspace"); }
+        }
+        if skip_bootstrap {
+            defaults.insert("skipBootstrap".into(), json!(true));
         } else {
-             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
-             std::path::Path::new(&openclaw_home).join(format!("workspace-{}", id))
-        };
-        
-        if !workspace_path.exists() {
-             info!("[Agents] Creating workspace directory: {:?}", workspace_path);
-             let _ = std::fs::create_dir_all(&workspace_path);
+            defaults.remove("skipBootstrap");
         }
-        
-        // Return paths to update config if they were defaults
-        (agent_dir_path.to_string_lossy().to_string(), workspace_path.to_string_lossy().to_string())
+        match bootstrap_max_chars {
+            Some(max) => { defaults.insert("bootstrapMaxChars".into(), json!(max)); }
+            None => { defaults.remove("bootstrapMaxChars"); }
+        }
+        // Remove timezone/timeFormat from defaults if present (migrate to manager)
+        defaults.remove("timezone");
+        defaults.remove("timeFormat");
+    }
+
+    // Set manager fields
+    if config.get("manager").is_none() { config["manager"] = json!({}); }
+    if let Some(manager) = config.get_mut("manager").and_then(|v| v.as_object_mut()) {
+        match &timezone {
+            Some(tz) if !tz.is_empty() => { manager.insert("timezone".into(), json!(tz)); }
+            _ => { manager.remove("timezone"); }
+        }
+        match &time_format {
+            Some(tf) if !tf.is_empty() => { manager.insert("time_format".into(), json!(tf)); }
+            _ => { manager.remove("time_format"); }
+        }
+    }
+
+    save_openclaw_config(&config, "save_workspace_config")?;
+    Ok("Workspace configuration saved".to_string())
+}
+
+/// Get a personality file from the workspace directory
+#[command]
+#[specta::specta]
+pub async fn get_personality_file(filename: String) -> Result<String, String> {
+    info!("[Personality] Reading file: {}", filename);
+
+    // Validate filename
+    let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
+    if !allowed.contains(&filename.as_str()) {
+        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+    }
+
+    // Get workspace path from config, fallback to ~/.openclaw
+    let config = load_openclaw_config()?;
+    let workspace = config.pointer("/agents/defaults/workspace")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let dir = if workspace.is_empty() {
+        platform::get_config_dir()
+    } else {
+        workspace.to_string()
+    };
+
+    let filepath = platform::path_string(&platform::join_path(&dir, &[&filename]));
+
+    match file::read_file(&filepath) {
+        Ok(content) => Ok(content),
+        Err(_) => Ok(String::new()), // File doesn't exist yet, return empty
+    }
+}
+
+/// Save a personality file to the workspace directory
+#[command]
+#[specta::specta]
+pub async fn save_personality_file(filename: String, content: String) -> Result<String, String> {
+    info!("[Personality] Saving file: {}", filename);
+
+    let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
+    if !allowed.contains(&filename.as_str()) {
+        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+    }
+
+    let config = load_openclaw_config()?;
+    let workspace = config.pointer("/agents/defaults/workspace")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let dir = if workspace.is_empty() {
+        platform::get_config_dir()
+    } else {
+        workspace.to_string()
     };
 
-    // Update or add the agent
-    if let Some(idx) = match_index {
-        let existing = &mut list[idx];
-        
-        // Merge: only overwrite fields the user explicitly set (non-empty)
-        if let Some(name) = &agent.name {
-            if !name.is_empty() {
-                existing["name"] = json!(name);
-            }
-        }
-        if let Some(model) = &agent.model {
-            if !model.is_empty() {
-                existing["model"] = json!({ "primary": model });
-            }
-        }
-        if let Some(is_default) = agent.default {
-            if is_default {
-                existing["default"] = json!(true);
+    let filepath = platform::path_string(&platform::join_path(&dir, &[&filename]));
+
+    file::write_file(&filepath, &content)
+        .map_err(|e| format!("Failed to save {}: {}", filename, e))?;
+
+    Ok(format!("{} saved successfully", filename))
+}
+
+/// Directories markdown previews may be read from: the OpenClaw home, the default workspace and
+/// every configured agent workspace/agentDir
+fn markdown_preview_roots(config: &Value) -> Vec<std::path::PathBuf> {
+    let mut roots = vec![std::path::PathBuf::from(platform::get_config_dir())];
+    if let Some(ws) = config.pointer("/agents/defaults/workspace").and_then(|v| v.as_str()) {
+        roots.push(std::path::PathBuf::from(ws));
+    }
+    let agents: Vec<&Value> = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr.iter().collect(),
+        Some(Value::Object(obj)) => obj.values().collect(),
+        _ => Vec::new(),
+    };
+    for agent in agents {
+        for key in ["workspace", "agentDir"] {
+            if let Some(dir) = agent.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+                roots.push(std::path::PathBuf::from(dir));
             }
         }
-        
-        // Enforce "Main" agent properties
-        if agent.id.eq_ignore_ascii_case("main") {
-            // "Main" should always be default unless user explicitly sets another default (which handles itself)
-            // But to ensure fallback behavior, we mark it.
-            existing["default"] = json!(true);
-        }
+    }
+    roots.into_iter().filter_map(|r| r.canonicalize().ok()).collect()
+}
 
-        if let Some(sub) = &agent.subagents {
-            if let Some(allow) = &sub.allow_agents {
-                if !allow.is_empty() {
-                    existing["subagents"] = json!({ "allowAgents": allow });
-                }
-            }
-        }
-        if let Some(sandbox) = agent.sandbox {
-            existing["sandbox"] = json!(sandbox);
-        }
-        if let Some(heartbeat) = &agent.heartbeat {
-            if !heartbeat.is_empty() {
-                existing["heartbeat"] = json!({ "every": heartbeat });
-            }
-        }
-        
-        // Repair directories for existing agent
-        let _ = ensure_directories(existing);
-        
+/// Render a workspace/personality markdown file to sanitized HTML for previews.
+/// Relative paths resolve against the default workspace, like get_personality_file.
+#[command]
+#[specta::specta]
+pub async fn render_markdown(path: String) -> Result<String, String> {
+    info!("[Markdown] Rendering {}", path);
+    let config = load_openclaw_config()?;
+
+    let requested = std::path::PathBuf::from(&path);
+    let full = if requested.is_absolute() {
+        requested
     } else {
-        // Not found in config (New agent, manual addition)
-        
-        // If we tried to create it via CLI and it's missing (and NOT reserved), that means CLI strictly failed.
-        if let Some(err) = cli_error {
-             if !is_reserved_name {
-                 return Err(format!("Failed to create agent via CLI: {}. Check logs or name uniqueness.", err));
-             }
-        }
+        let workspace = config.pointer("/agents/defaults/workspace").and_then(|v| v.as_str()).unwrap_or("");
+        let dir = if workspace.is_empty() { platform::get_config_dir() } else { workspace.to_string() };
+        std::path::Path::new(&dir).join(requested)
+    };
+    let is_markdown = full
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("md") || e.eq_ignore_ascii_case("markdown"))
+        .unwrap_or(false);
+    if !is_markdown {
+        return Err(format!("Not a markdown file: {}", path));
+    }
 
-        // Add to list
-        let mut new_entry = agent_obj.clone();
-        
-        // Ensure directories and get default paths if we need to explicitly save them
-        let (actual_agent_dir, actual_workspace) = ensure_directories(&new_entry);
-        
-        // If user didn't specify paths, save the defaults we just used/created
-        if new_entry.get("agentDir").is_none() {
-             new_entry["agentDir"] = json!(actual_agent_dir);
-        }
-        if new_entry.get("workspace").is_none() {
-             new_entry["workspace"] = json!(actual_workspace);
-        }
-        
-        list.push(new_entry);
+    let full = full.canonicalize().map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    if !markdown_preview_roots(&config).iter().any(|root| full.starts_with(root)) {
+        return Err(format!("{} is outside the OpenClaw home and agent workspaces", path));
     }
 
-    config["agents"]["list"] = json!(list);
+    let source = std::fs::read_to_string(&full).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut options = pulldown_cmark::Options::empty();
+    options.insert(pulldown_cmark::Options::ENABLE_TABLES);
+    options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+    options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new_ext(&source, options));
 
-    // Auto-create binding if a Telegram bot account is available and this agent has no binding yet
-    let agent_id = agent.id.clone();
-    let available_accounts: Vec<String> = config.pointer("/channels/telegram/accounts")
-        .and_then(|v| v.as_object())
-        .map(|accts| accts.keys().cloned().collect())
-        .unwrap_or_default();
+    Ok(ammonia::clean(&html))
+}
 
-    if !available_accounts.is_empty() {
-        // Check if this agent already has ANY binding
-        let has_existing_binding = config.get("bindings")
-            .and_then(|v| v.as_array())
-            .map(|bindings| bindings.iter().any(|b| {
-                b.get("agentId").and_then(|v| v.as_str()) == Some(&agent_id)
-            }))
-            .unwrap_or(false);
+// ============ Workspace Templates ============
 
-        if !has_existing_binding {
-            // Find accounts already bound to other agents
-            let bound_accounts: Vec<String> = config.get("bindings")
-                .and_then(|v| v.as_array())
-                .map(|bindings| bindings.iter().filter_map(|b| {
-                    b.get("match").and_then(|m| m.get("accountId")).and_then(|v| v.as_str()).map(|s| s.to_string())
-                }).collect())
-                .unwrap_or_default();
+/// Workspace document template shipped with the Manager
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WorkspaceTemplate {
+    pub id: String,
+    pub name: String,
+    /// Workspace file the template is written to (AGENTS.md, SOUL.md, TOOLS.md)
+    pub file: String,
+    pub description: String,
+    pub content: String,
+}
 
-            // Prefer: exact match > substring match > first unbound account > first account
-            let best_account = available_accounts.iter()
-                .find(|a| **a == agent_id) // exact match
-                .or_else(|| available_accounts.iter().find(|a| a.contains(&agent_id) || agent_id.contains(a.as_str()))) // substring
-                .or_else(|| available_accounts.iter().find(|a| !bound_accounts.contains(a))) // unbound
-                .or_else(|| available_accounts.first()) // fallback
-                .cloned();
+/// (id, name, target file, description, content)
+const WORKSPACE_TEMPLATES: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "agents-task-runner",
+        "Task runner",
+        "AGENTS.md",
+        "Works through TASKS.md one item at a time and records progress",
+        include_str!("../../templates/workspace/agents-task-runner.md"),
+    ),
+    (
+        "soul-assistant",
+        "Personal assistant",
+        "SOUL.md",
+        "Concise, calm assistant persona",
+        include_str!("../../templates/workspace/soul-assistant.md"),
+    ),
+    (
+        "soul-support",
+        "Customer support",
+        "SOUL.md",
+        "Friendly first-line support with escalation rules",
+        include_str!("../../templates/workspace/soul-support.md"),
+    ),
+    (
+        "soul-researcher",
+        "Researcher",
+        "SOUL.md",
+        "Careful researcher that cites sources",
+        include_str!("../../templates/workspace/soul-researcher.md"),
+    ),
+    (
+        "tools-examples",
+        "Tool notes",
+        "TOOLS.md",
+        "Example notes on shell, web and file conventions",
+        include_str!("../../templates/workspace/tools-examples.md"),
+    ),
+];
+
+fn workspace_template(id: &str) -> Option<WorkspaceTemplate> {
+    WORKSPACE_TEMPLATES
+        .iter()
+        .find(|(tid, ..)| *tid == id)
+        .map(|(id, name, file, description, content)| WorkspaceTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            file: file.to_string(),
+            description: description.to_string(),
+            content: content.to_string(),
+        })
+}
 
-            if let Some(account_id) = best_account {
-                info!("[Agents] Auto-creating binding for agent '{}' → account '{}'", agent_id, account_id);
-                if config.get("bindings").is_none() {
-                    config["bindings"] = json!([]);
-                }
-                if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-                    bindings.push(json!({
-                        "agentId": agent_id,
-                        "match": { "channel": "telegram", "accountId": account_id }
-                    }));
-                }
-            }
-        }
+/// Workspace directory of an agent: its configured `workspace`, otherwise the CLI default
+/// (~/.openclaw/workspace for the default agent, ~/.openclaw/workspace-<id> for others)
+pub(crate) fn agent_workspace_dir(config: &Value, agent_id: &str) -> Result<std::path::PathBuf, String> {
+    let entry = match config.pointer("/agents/list") {
+        Some(Value::Array(arr)) => arr.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id)).cloned(),
+        Some(Value::Object(obj)) => obj.get(agent_id).cloned(),
+        _ => None,
+    };
+    let entry = match entry {
+        Some(e) => e,
+        None if agent_id == "main" => json!({ "default": true }),
+        None => return Err(format!("Agent '{}' does not exist", agent_id)),
+    };
+
+    if let Some(ws) = entry.get("workspace").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+        return Ok(std::path::PathBuf::from(ws));
     }
+    let openclaw_home = platform::get_config_dir();
+    if entry.get("default").and_then(|v| v.as_bool()) == Some(true) {
+        let ws = config.pointer("/agents/defaults/workspace").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+        return Ok(ws
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::path::Path::new(&openclaw_home).join("workspace")));
+    }
+    Ok(std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_id)))
+}
 
-    save_openclaw_config(&config)?;
-    Ok(format!("Agent '{}' saved", agent.id))
+/// List the workspace document templates shipped with the Manager
+#[command]
+#[specta::specta]
+pub async fn list_workspace_templates() -> Result<Vec<WorkspaceTemplate>, String> {
+    Ok(WORKSPACE_TEMPLATES.iter().filter_map(|(id, ..)| workspace_template(id)).collect())
 }
 
-/// Save global subagent defaults
+/// Write a template into an agent's workspace. An existing non-empty file is kept and the
+/// template appended, unless `mode` is "replace".
 #[command]
-pub async fn save_subagent_defaults(defaults: SubagentDefaults) -> Result<String, String> {
-    info!("[Agents] Saving subagent defaults");
+#[specta::specta]
+pub async fn insert_workspace_template(agent_id: String, template_id: String, mode: Option<String>) -> Result<String, String> {
+    info!("[Workspace Templates] Inserting {} into agent {}", template_id, agent_id);
+    let template = workspace_template(&template_id).ok_or_else(|| format!("Unknown template '{}'", template_id))?;
+    let replace = match mode.as_deref() {
+        None | Some("append") => false,
+        Some("replace") => true,
+        Some(other) => return Err(format!("Invalid mode '{}', expected 'append' or 'replace'", other)),
+    };
+
+    let config = load_openclaw_config()?;
+    let dir = agent_workspace_dir(&config, &agent_id)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create workspace {}: {}", dir.display(), e))?;
+    let path = dir.join(&template.file);
+
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let content = if replace || existing.trim().is_empty() {
+        template.content.clone()
+    } else {
+        format!("{}\n\n{}", existing.trim_end(), template.content)
+    };
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    info!("[Workspace Templates] Wrote {} to {}", template.id, path.display());
+    Ok(format!(
+        "{} template {} {}",
+        template.name,
+        if replace || existing.trim().is_empty() { "written to" } else { "appended to" },
+        path.display()
+    ))
+}
+
+// ============ Browser Control ============
+
+/// Browser configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct BrowserConfig {
+    pub enabled: bool,
+    pub color: Option<String>,
+}
+
+/// Get browser configuration
+#[command]
+#[specta::specta]
+pub async fn get_browser_config() -> Result<BrowserConfig, String> {
+    info!("[Browser] Getting browser config...");
+    let config = load_openclaw_config()?;
+
+    // Read from meta (Manager specific)
+    let enabled = config.pointer("/meta/gui/browser/enabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true); // Default to true if not set
+
+    let color = config.pointer("/meta/gui/browser/color")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    Ok(BrowserConfig { enabled, color })
+}
+
+/// Save browser configuration
+#[command]
+#[specta::specta]
+pub async fn save_browser_config(enabled: bool, color: Option<String>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_browser_config").await;
+    info!("[Browser] Saving browser config: enabled={}, color={:?}", enabled, color);
     let mut config = load_openclaw_config()?;
 
-    // Ensure agents.defaults exists
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
-    if config["agents"].get("defaults").is_none() {
-        config["agents"]["defaults"] = json!({});
-    }
+    // Store in meta.gui.browser to avoid polluting core config
+    if config.get("meta").is_none() { config["meta"] = json!({}); }
+    if config["meta"].get("gui").is_none() { config["meta"]["gui"] = json!({}); }
+    
+    let mut browser_config = json!({
+        "enabled": enabled
+    });
 
-    let mut sub_obj = json!({});
-    if let Some(depth) = defaults.max_spawn_depth {
-        sub_obj["maxSpawnDepth"] = json!(depth);
+    if let Some(c) = color {
+        if !c.is_empty() {
+            browser_config["color"] = json!(c);
+        }
     }
-    if let Some(children) = defaults.max_children_per_agent {
-        sub_obj["maxChildrenPerAgent"] = json!(children);
+
+    config["meta"]["gui"]["browser"] = browser_config;
+
+    save_openclaw_config(&config, "save_browser_config")?;
+    Ok("Browser configuration saved".to_string())
+}
+
+// ============ Web Search ============
+
+/// Web Search configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WebConfig {
+    pub brave_api_key: Option<String>,
+}
+
+/// Get web search configuration
+#[command]
+#[specta::specta]
+pub async fn get_web_config() -> Result<WebConfig, String> {
+    info!("[Web] Getting web search config...");
+    let config = load_openclaw_config()?;
+
+    let brave_api_key = config.pointer("/web/braveApiKey")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    Ok(WebConfig { brave_api_key })
+}
+
+/// Save web search configuration
+#[command]
+#[specta::specta]
+pub async fn save_web_config(brave_api_key: Option<String>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_web_config").await;
+    info!("[Web] Saving web search config...");
+    let mut config = load_openclaw_config()?;
+
+    if config.get("web").is_none() {
+        config["web"] = json!({});
     }
-    if let Some(concurrent) = defaults.max_concurrent {
-        sub_obj["maxConcurrent"] = json!(concurrent);
+
+    match brave_api_key {
+        Some(key) if !key.is_empty() => {
+            config["web"]["braveApiKey"] = json!(key);
+        }
+        _ => {
+            if let Some(web) = config.get_mut("web").and_then(|v| v.as_object_mut()) {
+                web.remove("braveApiKey");
+            }
+        }
     }
 
-    config["agents"]["defaults"]["subagents"] = sub_obj;
 
-    // Subagent sessions_spawn inline file attachments
-    if defaults.attachments_enabled.is_some() || defaults.attachments_max_total_bytes.is_some() {
-        if config.get("tools").is_none() {
-            config["tools"] = json!({});
-        }
-        if config["tools"].get("sessions_spawn").is_none() {
-            config["tools"]["sessions_spawn"] = json!({});
-        }
-        if config["tools"]["sessions_spawn"].get("attachments").is_none() {
-            config["tools"]["sessions_spawn"]["attachments"] = json!({});
-        }
+    save_openclaw_config(&config, "save_web_config")?;
+    Ok("Web search configuration saved".to_string())
+}
+
+// ============ Gateway Configuration ============
+
+/// Gateway configuration for frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GatewayConfig {
+    pub port: u16,
+    pub log_level: String,
+}
+
+/// Get gateway configuration
+#[command]
+#[specta::specta]
+pub async fn get_gateway_config() -> Result<GatewayConfig, String> {
+    info!("[Gateway] Getting gateway config...");
+    let config = load_openclaw_config()?;
+
+    let port = config.pointer("/gateway/port")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u16)
+        .unwrap_or(3000);
 
-        if let Some(enabled) = defaults.attachments_enabled {
-            config["tools"]["sessions_spawn"]["attachments"]["enabled"] = json!(enabled);
-        }
-        if let Some(max_bytes) = defaults.attachments_max_total_bytes {
-            config["tools"]["sessions_spawn"]["attachments"]["maxTotalBytes"] = json!(max_bytes);
-        }
-    }
+    let log_level = config.pointer("/manager/log_level")
+        .and_then(|v| v.as_str())
+        .or_else(|| config.pointer("/gateway/logLevel").and_then(|v| v.as_str())) // Legacy fallback
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "info".to_string());
 
-    save_openclaw_config(&config)?;
-    Ok("Subagent defaults saved".to_string())
+    Ok(GatewayConfig { port, log_level })
 }
 
-/// Delete an agent
+/// Save gateway configuration
 #[command]
-pub async fn delete_agent(agent_id: String) -> Result<String, String> {
-    info!("[Agents] Deleting agent: {}", agent_id);
+#[specta::specta]
+pub async fn save_gateway_config(port: u16, log_level: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_gateway_config").await;
+    info!("[Gateway] Saving gateway config: port={}, level={}", port, log_level);
     let mut config = load_openclaw_config()?;
 
-    // 1. Find the agent to get its paths (before deleting from config)
-    let mut agent_dir_to_delete: Option<String> = None;
-    let mut workspace_to_delete: Option<String> = None;
-
-    if let Some(list) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
-        if let Some(agent) = list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id)) {
-            // Get agent directory
-            if let Some(dir) = agent.get("agentDir").and_then(|v| v.as_str()) {
-                agent_dir_to_delete = Some(dir.to_string());
-            }
-            // Get workspace directory
-            if let Some(ws) = agent.get("workspace").and_then(|v| v.as_str()) {
-                workspace_to_delete = Some(ws.to_string());
-            } else {
-                // Fallback: deduce workspace path if default pattern was used
-                let openclaw_home = platform::get_config_dir();
-                let default_ws = std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_id));
-                if default_ws.exists() {
-                    workspace_to_delete = Some(default_ws.to_string_lossy().to_string());
-                }
-            }
-        }
+    if config.get("gateway").is_none() {
+        config["gateway"] = json!({});
     }
 
-    // 2. Delete the files (if they exist)
-    // We do this BEFORE updating config, but we don't abort if it fails (just warn)
-    // because we still want to remove the broken/stale entry from config.
+    if let Some(gateway) = config.get_mut("gateway").and_then(|v| v.as_object_mut()) {
+        gateway.insert("port".to_string(), json!(port));
+        // Remove legacy logLevel if exists
+        gateway.remove("logLevel");
+        gateway.remove("log_level");
+    }
 
-    if let Some(agent_dir) = agent_dir_to_delete {
-        let path = std::path::Path::new(&agent_dir);
-        // Check if this is a nested 'agent' directory (standard structure: .../agents/<id>/agent)
-        // If so, we want to delete the PARENT directory (e.g. .../agents/<id>) to clean up everything including sessions.
-        let path_to_remove = if path.ends_with("agent") {
-            path.parent().unwrap_or(path)
-        } else {
-            path
-        };
+    if config.get("manager").is_none() {
+        config["manager"] = json!({});
+    }
 
-        if path_to_remove.exists() {
-            info!("[Agents] Removing agent directory tree: {:?}", path_to_remove);
-            if let Err(e) = std::fs::remove_dir_all(path_to_remove) {
-                warn!("[Agents] Failed to remove agent directory {:?}: {}", path_to_remove, e);
-            }
-        }
-    } else {
-        // Fallback: try default location if not specified in config
-        let openclaw_home = platform::get_config_dir();
-        // Default structure is now ~/.openclaw/agents/<id> (which contains agent/, sessions/, etc.)
-        let default_agent_root = std::path::Path::new(&openclaw_home).join("agents").join(&agent_id);
-        
-        if default_agent_root.exists() {
-             info!("[Agents] Removing default agent directory tree: {:?}", default_agent_root);
-             if let Err(e) = std::fs::remove_dir_all(&default_agent_root) {
-                warn!("[Agents] Failed to remove default agent directory: {}", e);
-            }
-        }
+    if let Some(manager) = config.get_mut("manager").and_then(|v| v.as_object_mut()) {
+        manager.insert("log_level".to_string(), json!(log_level));
     }
+    
+    save_openclaw_config(&config, "save_gateway_config")?;
+    Ok("Gateway configuration saved".to_string())
+}
 
-    if let Some(workspace) = workspace_to_delete {
-        let path = std::path::Path::new(&workspace);
-        if path.exists() {
-            info!("[Agents] Removing workspace directory: {}", workspace);
-            if let Err(e) = std::fs::remove_dir_all(path) {
-                warn!("[Agents] Failed to remove workspace directory {}: {}", workspace, e);
+// ============ Rate Limits ============
+//
+// The core has no per-peer messages-per-minute limit; flood control is done with the inbound
+// debounce (bursts from one peer become one turn), the per-session queue cap and the global
+// agent concurrency limit.
+
+/// Flood-control settings of one channel (messages.inbound.byChannel / messages.queue.byChannel)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+pub struct ChannelRateLimit {
+    /// Wait this long for more messages from the same peer before replying
+    pub inbound_debounce_ms: Option<u64>,
+    /// Queue mode while a run is active: "collect" | "followup" | "steer" | "interrupt"
+    pub queue_mode: Option<String>,
+}
+
+/// Rate-limit / flood-control settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+pub struct RateLimitSettings {
+    /// agents.defaults.maxConcurrent: agent runs in parallel across all sessions
+    pub max_concurrent: Option<u32>,
+    /// messages.inbound.debounceMs
+    pub inbound_debounce_ms: Option<u64>,
+    /// messages.queue.cap: queued messages per session before dropping
+    pub queue_cap: Option<u32>,
+    /// messages.queue.drop: "old" | "new" | "summarize"
+    pub queue_drop: Option<String>,
+    /// messages.queue.debounceMs
+    pub queue_debounce_ms: Option<u64>,
+    pub channels: HashMap<String, ChannelRateLimit>,
+}
+
+const QUEUE_MODES: &[&str] = &["collect", "followup", "steer", "steer-backlog", "interrupt"];
+const QUEUE_DROP_POLICIES: &[&str] = &["old", "new", "summarize"];
+
+/// Set (Some) or remove (None) the value at a JSON pointer, creating parent objects
+pub(crate) fn set_at_pointer(config: &mut Value, pointer: &str, value: Option<Value>) {
+    match value {
+        None => remove_at_pointer(config, pointer),
+        Some(value) => {
+            let mut target = config;
+            for key in pointer.trim_start_matches('/').split('/') {
+                if !target.is_object() {
+                    *target = json!({});
+                }
+                target = target
+                    .as_object_mut()
+                    .unwrap()
+                    .entry(key.replace("~1", "/").replace("~0", "~"))
+                    .or_insert(Value::Null);
             }
+            *target = value;
         }
     }
+}
 
-    // 3. Remove from agents.list (array format)
-    if let Some(list) = config.pointer_mut("/agents/list").and_then(|v| v.as_array_mut()) {
-        list.retain(|a| a.get("id").and_then(|v| v.as_str()) != Some(&agent_id));
+/// Suggested flood-control settings for a channel type. Group-heavy chat apps get a longer
+/// debounce so a burst of messages becomes a single (billed) turn.
+fn suggested_channel_rate_limit(channel_type: &str) -> ChannelRateLimit {
+    let debounce = match channel_type {
+        "telegram" | "discord" | "whatsapp" => 1500,
+        "slack" | "feishu" | "dingtalk" | "wechat" => 1000,
+        _ => 800,
+    };
+    ChannelRateLimit {
+        inbound_debounce_ms: Some(debounce),
+        queue_mode: Some("collect".to_string()),
     }
+}
 
-    // Remove related bindings (top-level)
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        bindings.retain(|b| b.get("agentId").and_then(|v| v.as_str()) != Some(&agent_id));
+/// Get rate-limit / flood-control settings
+#[command]
+#[specta::specta]
+pub async fn get_rate_limits() -> Result<RateLimitSettings, String> {
+    let config = load_openclaw_config()?;
+    let u64_at = |p: &str| config.pointer(p).and_then(|v| v.as_u64());
+    let str_at = |p: &str| config.pointer(p).and_then(|v| v.as_str()).map(String::from);
+
+    let mut channels: HashMap<String, ChannelRateLimit> = HashMap::new();
+    if let Some(by_channel) = config.pointer("/messages/inbound/byChannel").and_then(|v| v.as_object()) {
+        for (channel, ms) in by_channel {
+            channels.entry(channel.clone()).or_default().inbound_debounce_ms = ms.as_u64();
+        }
     }
-    // Also clean legacy agents.bindings
-    if let Some(bindings) = config.pointer_mut("/agents/bindings").and_then(|v| v.as_array_mut()) {
-        bindings.retain(|b| b.get("agentId").and_then(|v| v.as_str()) != Some(&agent_id));
+    if let Some(by_channel) = config.pointer("/messages/queue/byChannel").and_then(|v| v.as_object()) {
+        for (channel, mode) in by_channel {
+            channels.entry(channel.clone()).or_default().queue_mode = mode.as_str().map(String::from);
+        }
     }
 
-    save_openclaw_config(&config)?;
-    Ok(format!("Agent '{}' and its files were deleted", agent_id))
+    Ok(RateLimitSettings {
+        max_concurrent: u64_at("/agents/defaults/maxConcurrent").map(|v| v as u32),
+        inbound_debounce_ms: u64_at("/messages/inbound/debounceMs"),
+        queue_cap: u64_at("/messages/queue/cap").map(|v| v as u32),
+        queue_drop: str_at("/messages/queue/drop"),
+        queue_debounce_ms: u64_at("/messages/queue/debounceMs"),
+        channels,
+    })
 }
 
-/// Save an agent binding rule
+/// Save rate-limit / flood-control settings (unset fields fall back to the core defaults)
 #[command]
+#[specta::specta]
+pub async fn save_rate_limits(settings: RateLimitSettings) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "save_rate_limits").await;
+    info!("[Rate Limits] Saving rate limits for {} channel(s)", settings.channels.len());
+    if settings.max_concurrent == Some(0) {
+        return Err("maxConcurrent must be at least 1".to_string());
+    }
+    if let Some(drop) = settings.queue_drop.as_deref().filter(|d| !QUEUE_DROP_POLICIES.contains(d)) {
+        return Err(format!("Unknown queue drop policy '{}'", drop));
+    }
+    for (channel, limits) in &settings.channels {
+        if let Some(mode) = limits.queue_mode.as_deref().filter(|m| !QUEUE_MODES.contains(m)) {
+            return Err(format!("Unknown queue mode '{}' for {}", mode, channel));
+        }
+    }
 
-pub async fn save_agent_binding(binding: AgentBinding) -> Result<String, String> {
-    info!("[Agents] Saving binding for agent: {}", binding.agent_id);
     let mut config = load_openclaw_config()?;
+    set_at_pointer(&mut config, "/agents/defaults/maxConcurrent", settings.max_concurrent.map(|v| json!(v)));
+    set_at_pointer(&mut config, "/messages/inbound/debounceMs", settings.inbound_debounce_ms.map(|v| json!(v)));
+    set_at_pointer(&mut config, "/messages/queue/cap", settings.queue_cap.map(|v| json!(v)));
+    set_at_pointer(&mut config, "/messages/queue/drop", settings.queue_drop.map(|v| json!(v)));
+    set_at_pointer(&mut config, "/messages/queue/debounceMs", settings.queue_debounce_ms.map(|v| json!(v)));
+
+    let inbound: serde_json::Map<String, Value> = settings
+        .channels
+        .iter()
+        .filter_map(|(c, l)| Some((c.clone(), json!(l.inbound_debounce_ms?))))
+        .collect();
+    let queue: serde_json::Map<String, Value> = settings
+        .channels
+        .iter()
+        .filter_map(|(c, l)| Some((c.clone(), json!(l.queue_mode.clone()?))))
+        .collect();
+    set_at_pointer(&mut config, "/messages/inbound/byChannel", (!inbound.is_empty()).then_some(Value::Object(inbound)));
+    set_at_pointer(&mut config, "/messages/queue/byChannel", (!queue.is_empty()).then_some(Value::Object(queue)));
 
-    // Ensure top-level bindings array exists
-    if config.get("bindings").is_none() {
-        config["bindings"] = json!([]);
-    }
+    save_openclaw_config(&config, "save_rate_limits")?;
+    Ok("Rate limits saved".to_string())
+}
 
-    // Migrate legacy agents.bindings to top-level if present
-    if let Some(legacy) = config.pointer("/agents/bindings").and_then(|v| v.as_array()).map(|a| a.clone()) {
-        if let Some(top) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-            for b in legacy {
-                top.push(b);
-            }
-        }
-        // Remove legacy location
-        if let Some(agents) = config.get_mut("agents").and_then(|v| v.as_object_mut()) {
-            agents.remove("bindings");
-        }
-    }
+/// Suggested rate limits for the configured channels (or the given channel types)
+#[command]
+#[specta::specta]
+pub async fn suggest_rate_limits(channel_types: Option<Vec<String>>) -> Result<RateLimitSettings, String> {
+    let channel_types = match channel_types {
+        Some(types) => types,
+        None => load_openclaw_config()?
+            .get("channels")
+            .and_then(|c| c.as_object())
+            .map(|c| c.keys().cloned().collect())
+            .unwrap_or_default(),
+    };
+    Ok(RateLimitSettings {
+        max_concurrent: Some(4),
+        inbound_debounce_ms: Some(1000),
+        queue_cap: Some(20),
+        queue_drop: Some("summarize".to_string()),
+        queue_debounce_ms: Some(1000),
+        channels: channel_types
+            .into_iter()
+            .map(|c| {
+                let limits = suggested_channel_rate_limit(&c);
+                (c, limits)
+            })
+            .collect(),
+    })
+}
 
-    let mut match_obj = json!({});
-    if let Some(ch) = &binding.match_rule.channel {
-        if !ch.is_empty() { match_obj["channel"] = json!(ch); }
-    }
-    if let Some(acc) = &binding.match_rule.account_id {
-        if !acc.is_empty() { match_obj["accountId"] = json!(acc); }
-    }
-    if let Some(peer) = &binding.match_rule.peer {
-        match_obj["peer"] = peer.clone();
-    }
+// ============ Orphaned References ============
 
-    let binding_obj = json!({
-        "agentId": binding.agent_id,
-        "match": match_obj
-    });
+/// A reference in openclaw.json pointing at something that no longer exists
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct OrphanReference {
+    /// JSON pointer of the dangling value, also used as the fix id
+    pub id: String,
+    /// "model_provider" | "primary_model" | "agent_model" | "binding_agent" | "binding_account" | "subagent"
+    pub kind: String,
+    /// The dangling value (model ref, agent id, account id)
+    pub reference: Option<String>,
+    pub description: String,
+    /// Valid values the reference can be remapped to
+    pub candidates: Vec<String>,
+    /// Pointer removed by the "remove" action
+    #[serde(skip)]
+    remove_pointer: String,
+    /// Map key rename instead of value replacement on remap
+    #[serde(skip)]
+    remap_key: bool,
+}
 
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        bindings.push(binding_obj);
-    }
+/// Chosen fix for one orphan: action "remove" or "remap" (with target)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct OrphanFix {
+    pub id: String,
+    pub action: String,
+    pub target: Option<String>,
+}
 
-    save_openclaw_config(&config)?;
-    Ok(format!("Binding for agent '{}' saved", binding.agent_id))
+/// Agent ids from agents.list (array or legacy object form) with their JSON pointers
+fn list_agent_entries(config: &Value) -> Vec<(String, String)> {
+    if let Some(arr) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
+        arr.iter()
+            .enumerate()
+            .filter_map(|(i, a)| a.get("id").and_then(|v| v.as_str()).map(|id| (id.to_string(), format!("/agents/list/{}", i))))
+            .collect()
+    } else if let Some(obj) = config.pointer("/agents/list").and_then(|v| v.as_object()) {
+        obj.keys()
+            .map(|id| (id.clone(), format!("/agents/list/{}", config_include::escape_pointer_token(id))))
+            .collect()
+    } else {
+        Vec::new()
+    }
 }
 
-/// Delete an agent binding by index
-#[command]
-pub async fn delete_agent_binding(index: usize) -> Result<String, String> {
-    info!("[Agents] Deleting binding at index: {}", index);
-    let mut config = load_openclaw_config()?;
+fn collect_orphans(config: &Value) -> Vec<OrphanReference> {
+    let mut orphans = Vec::new();
 
-    // Try top-level bindings first (correct location)
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        if index < bindings.len() {
-            bindings.remove(index);
-            save_openclaw_config(&config)?;
-            return Ok(format!("Binding at index {} deleted", index));
-        } else {
-            return Err(format!("Binding index {} out of range", index));
+    let valid_models: Vec<String> = config
+        .pointer("/agents/defaults/models")
+        .and_then(|v| v.as_object())
+        .map(|m| m.keys().filter(|k| validate_agent_model(config, k).is_ok()).cloned().collect())
+        .unwrap_or_default();
+    let agents = list_agent_entries(config);
+    let mut agent_ids: Vec<String> = agents.iter().map(|(id, _)| id.clone()).collect();
+    if !agent_ids.iter().any(|id| id == "main") {
+        // "main" always exists implicitly
+        agent_ids.push("main".to_string());
+    }
+    let telegram_accounts: Option<Vec<String>> = config
+        .pointer("/channels/telegram/accounts")
+        .and_then(|v| v.as_object())
+        .map(|m| m.keys().cloned().collect());
+
+    // Available models whose provider/model is gone
+    if let Some(models) = config.pointer("/agents/defaults/models").and_then(|v| v.as_object()) {
+        for model_id in models.keys() {
+            if let Err(reason) = validate_agent_model(config, model_id) {
+                let pointer = format!("/agents/defaults/models/{}", config_include::escape_pointer_token(model_id));
+                orphans.push(OrphanReference {
+                    id: pointer.clone(),
+                    kind: "model_provider".to_string(),
+                    reference: Some(model_id.clone()),
+                    description: format!("Available model '{}': {}", model_id, reason),
+                    candidates: valid_models.clone(),
+                    remove_pointer: pointer,
+                    remap_key: true,
+                });
+            }
         }
     }
 
-    // Fallback to legacy agents.bindings
-    if let Some(bindings) = config.pointer_mut("/agents/bindings").and_then(|v| v.as_array_mut()) {
-        if index < bindings.len() {
-            bindings.remove(index);
-            save_openclaw_config(&config)?;
-            return Ok(format!("Binding at index {} deleted", index));
-        } else {
-            return Err(format!("Binding index {} out of range", index));
+    // Primary model cleared to null or pointing at a removed provider
+    match config.pointer("/agents/defaults/model/primary") {
+        Some(Value::Null) => orphans.push(OrphanReference {
+            id: "/agents/defaults/model/primary".to_string(),
+            kind: "primary_model".to_string(),
+            reference: None,
+            description: "Primary model is empty (its provider was deleted)".to_string(),
+            candidates: valid_models.clone(),
+            remove_pointer: "/agents/defaults/model/primary".to_string(),
+            remap_key: false,
+        }),
+        Some(Value::String(model)) => {
+            if let Err(reason) = validate_agent_model(config, model) {
+                orphans.push(OrphanReference {
+                    id: "/agents/defaults/model/primary".to_string(),
+                    kind: "primary_model".to_string(),
+                    reference: Some(model.clone()),
+                    description: format!("Primary model '{}': {}", model, reason),
+                    candidates: valid_models.clone(),
+                    remove_pointer: "/agents/defaults/model/primary".to_string(),
+                    remap_key: false,
+                });
+            }
         }
+        _ => {}
     }
 
-    Err("No bindings found".to_string())
-}
-
-// ============ Agent Soul / Personality ============
-
-/// Read the personality (SOUL.md) for an agent
-#[command]
-pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>) -> Result<String, String> {
-    let base = workspace.unwrap_or_else(|| platform::get_config_dir());
-    let sep = if cfg!(windows) { "\\" } else { "/" };
-    
-    // Resolve agent directory from config to handle case where ID != dir name
-    let config = load_openclaw_config().map_err(|e| e.to_string())?;
-    let agent_dir_rel = config.pointer("/agents/list")
-        .and_then(|v| v.as_array())
-        .and_then(|list| list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id)))
-        .and_then(|agent| agent.get("agentDir").and_then(|v| v.as_str()))
-        .map(|s| s.replace("/", sep)) //normalize separators
-        .unwrap_or_else(|| format!("agents{}{}", sep, agent_id)); // fallback
-
-    // If agentDir is already an absolute path, use it directly; otherwise join with base
-    let dir_config = if std::path::Path::new(&agent_dir_rel).is_absolute() {
-        agent_dir_rel
-    } else {
-        format!("{}{}{}", base, sep, agent_dir_rel)
-    };
-    
-    // Try locations in order of likelihood - prioritizing the CORRECT one first
-    let paths = vec![
-        format!("{}{}SOUL.md", dir_config, sep),                                // 1. agents/{id}/SOUL.md (CORRECT)
-        format!("{}{}{}{}{}{}SOUL.md", base, sep, "agent", sep, agent_id, sep), // 2. agent/{id}/SOUL.md (Legacy/Buggy)
-        format!("{}{}agent{}SOUL.md", dir_config, sep, sep),                    // 3. agents/{id}/agent/SOUL.md (Legacy/Buggy)
-    ];
+    // Per-agent models and sub-agent allow lists
+    for (agent_id, agent_pointer) in &agents {
+        let agent = match config.pointer(agent_pointer) {
+            Some(a) => a,
+            None => continue,
+        };
+        if let Some(model) = agent.pointer("/model/primary").and_then(|v| v.as_str()) {
+            if let Err(reason) = validate_agent_model(config, model) {
+                orphans.push(OrphanReference {
+                    id: format!("{}/model/primary", agent_pointer),
+                    kind: "agent_model".to_string(),
+                    reference: Some(model.to_string()),
+                    description: format!("Agent '{}' model '{}': {}", agent_id, model, reason),
+                    candidates: valid_models.clone(),
+                    remove_pointer: format!("{}/model", agent_pointer),
+                    remap_key: false,
+                });
+            }
+        }
+        if let Some(allow) = agent.pointer("/subagents/allowAgents").and_then(|v| v.as_array()) {
+            for (i, target) in allow.iter().enumerate() {
+                if let Some(target) = target.as_str() {
+                    if target != "*" && !agent_ids.iter().any(|id| id == target) {
+                        let pointer = format!("{}/subagents/allowAgents/{}", agent_pointer, i);
+                        orphans.push(OrphanReference {
+                            id: pointer.clone(),
+                            kind: "subagent".to_string(),
+                            reference: Some(target.to_string()),
+                            description: format!("Agent '{}' allows missing sub-agent '{}'", agent_id, target),
+                            candidates: agent_ids.clone(),
+                            remove_pointer: pointer,
+                            remap_key: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
 
-    for path in &paths {
-        if std::path::Path::new(path).exists() {
-            info!("[Agents] Found SOUL.md at: {}", path);
-            return std::fs::read_to_string(path)
-                .map_err(|e| format!("Failed to read SOUL.md: {}", e));
+    // Bindings to deleted agents or Telegram accounts
+    if let Some(bindings) = config.get("bindings").and_then(|v| v.as_array()) {
+        for (i, binding) in bindings.iter().enumerate() {
+            if let Some(agent_id) = binding.get("agentId").and_then(|v| v.as_str()) {
+                if !agent_ids.iter().any(|id| id == agent_id) {
+                    orphans.push(OrphanReference {
+                        id: format!("/bindings/{}/agentId", i),
+                        kind: "binding_agent".to_string(),
+                        reference: Some(agent_id.to_string()),
+                        description: format!("Binding #{} routes to missing agent '{}'", i + 1, agent_id),
+                        candidates: agent_ids.clone(),
+                        remove_pointer: format!("/bindings/{}", i),
+                        remap_key: false,
+                    });
+                }
+            }
+            let is_telegram = binding.pointer("/match/channel").and_then(|v| v.as_str()) == Some("telegram");
+            if let (true, Some(account_id), Some(accounts)) =
+                (is_telegram, binding.pointer("/match/accountId").and_then(|v| v.as_str()), &telegram_accounts)
+            {
+                if !accounts.iter().any(|a| a == account_id) {
+                    orphans.push(OrphanReference {
+                        id: format!("/bindings/{}/match/accountId", i),
+                        kind: "binding_account".to_string(),
+                        reference: Some(account_id.to_string()),
+                        description: format!("Binding #{} matches missing Telegram account '{}'", i + 1, account_id),
+                        candidates: accounts.clone(),
+                        remove_pointer: format!("/bindings/{}", i),
+                        remap_key: false,
+                    });
+                }
+            }
         }
     }
-    
-    Ok(String::new())
+
+    orphans
 }
 
-/// Save the personality (SOUL.md) for an agent
-#[command]
-pub async fn save_agent_system_prompt(agent_id: String, workspace: Option<String>, content: String) -> Result<String, String> {
-    let base = workspace.unwrap_or_else(|| platform::get_config_dir());
-    let sep = if cfg!(windows) { "\\" } else { "/" };
-    
-    // Resolve agent directory from config
-    let config = load_openclaw_config().map_err(|e| e.to_string())?;
-    let agent_dir_rel = config.pointer("/agents/list")
-        .and_then(|v| v.as_array())
-        .and_then(|list| list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id)))
-        .and_then(|agent| agent.get("agentDir").and_then(|v| v.as_str()))
-        .map(|s| s.replace("/", sep))
-        .unwrap_or_else(|| format!("agents{}{}", sep, agent_id));
-
-    // If agentDir is already an absolute path, use it directly; otherwise join with base
-    let dir_config = if std::path::Path::new(&agent_dir_rel).is_absolute() {
-        agent_dir_rel
-    } else {
-        format!("{}{}{}", base, sep, agent_dir_rel)
+/// Remove the value at a JSON pointer (object key or array element)
+pub(crate) fn remove_at_pointer(config: &mut Value, pointer: &str) {
+    let (parent, last) = match pointer.rsplit_once('/') {
+        Some(parts) => parts,
+        None => return,
     };
-    
-    // ONLY save to the correct canonical path
-    let path = format!("{}{}SOUL.md", dir_config, sep);
-
-    if let Some(parent) = std::path::Path::new(&path).parent() {
-        if let Err(e) = std::fs::create_dir_all(parent) {
-            return Err(format!("Failed to create directory for {}: {}", path, e));
+    let key = last.replace("~1", "/").replace("~0", "~");
+    match config.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.remove(&key);
         }
+        Some(Value::Array(arr)) => {
+            if let Ok(i) = key.parse::<usize>() {
+                if i < arr.len() {
+                    arr.remove(i);
+                }
+            }
+        }
+        _ => {}
     }
-    
-    match std::fs::write(&path, &content) {
-        Ok(_) => {
-            info!("[Agents] Wrote SOUL.md to: {}", path);
-            Ok(format!("Personality (SOUL.md) saved for agent '{}'", agent_id))
-        },
-        Err(e) => Err(format!("Failed to save SOUL.md to {}: {}", path, e))
-    }
 }
 
-/// Test agent routing: given an account ID, find which agent handles it
+/// Order pointers so array elements are removed from the highest index down
+fn compare_pointers_desc(a: &str, b: &str) -> std::cmp::Ordering {
+    let tokens = |p: &str| -> Vec<(Option<usize>, String)> {
+        p.split('/').map(|t| (t.parse::<usize>().ok(), t.to_string())).collect()
+    };
+    tokens(b).cmp(&tokens(a))
+}
+
+/// Find all dangling references in openclaw.json
 #[command]
-pub async fn test_agent_routing(account_id: String) -> Result<serde_json::Value, String> {
+#[specta::specta]
+pub async fn find_orphans() -> Result<Vec<OrphanReference>, String> {
+    info!("[Orphans] Scanning configuration for dangling references...");
     let config = load_openclaw_config()?;
+    let orphans = collect_orphans(&config);
+    info!("[Orphans] Found {} dangling reference(s)", orphans.len());
+    Ok(orphans)
+}
 
-    // Walk through bindings to find a match
-    let bindings = config.get("bindings").and_then(|v| v.as_array());
-
-    if let Some(bindings) = bindings {
-        let empty_match = json!({});
-        for binding in bindings {
-            let match_obj = binding.get("match").unwrap_or(&empty_match);
-            let binding_account = match_obj.get("accountId").and_then(|v| v.as_str());
-            let binding_channel = match_obj.get("channel").and_then(|v| v.as_str());
-
-            // Check if this binding matches
-            let account_matches = binding_account.map(|a| a == account_id).unwrap_or(true); // None = catch-all
-            let channel_matches = binding_channel.map(|c| c == "telegram").unwrap_or(true);
-
-            if account_matches && channel_matches {
-                let agent_id = binding.get("agentId").and_then(|v| v.as_str()).unwrap_or("unknown");
-
-                // Find agent details
-                let agent_info = config.pointer("/agents/list")
-                    .and_then(|v| v.as_array())
-                    .and_then(|list| list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id)));
-
-                // Read SOUL.md preview (try all 3 locations)
-                let base = platform::get_config_dir();
-                let sep = if cfg!(windows) { "\\" } else { "/" };
-                let agent_dir_rel = agent_info.and_then(|a| a.get("agentDir").and_then(|v| v.as_str()))
-                    .map(|s| s.replace("/", sep))
-                    .unwrap_or_else(|| format!("agents{}{}", sep, agent_id));
-                
-                let dir_config = format!("{}{}{}", base, sep, agent_dir_rel);
-                let check_paths = vec![
-                    format!("{}{}{}{}{}{}SOUL.md", base, sep, "agent", sep, agent_id, sep),
-                    format!("{}{}agent{}SOUL.md", dir_config, sep, sep),
-                    format!("{}{}SOUL.md", dir_config, sep),
-                ];
-                
-                let mut prompt_preview = String::new();
-                for path in check_paths {
-                    if std::path::Path::new(&path).exists() {
-                        prompt_preview = std::fs::read_to_string(&path).unwrap_or_default();
-                        break;
-                    }
+/// Apply the chosen fix (remove or remap) for each orphan
+#[command]
+#[specta::specta]
+pub async fn fix_orphans(fixes: Vec<OrphanFix>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "fix_orphans").await;
+    info!("[Orphans] Applying {} fix(es)...", fixes.len());
+    let mut config = load_openclaw_config()?;
+    let orphans = collect_orphans(&config);
+
+    let mut removals = Vec::new();
+    let mut applied = 0;
+    for fix in &fixes {
+        let orphan = orphans
+            .iter()
+            .find(|o| o.id == fix.id)
+            .ok_or(format!("Reference {} is no longer dangling, please rescan", fix.id))?;
+
+        match fix.action.as_str() {
+            "remove" => removals.push(orphan.remove_pointer.clone()),
+            "remap" => {
+                let target = fix.target.clone().filter(|t| !t.is_empty())
+                    .ok_or(format!("No remap target given for {}", fix.id))?;
+                if !orphan.candidates.contains(&target) {
+                    return Err(format!("'{}' is not a valid target for {}", target, fix.id));
                 }
-                let prompt_preview = if prompt_preview.len() > 200 {
-                    format!("{}...", &prompt_preview[..200])
+                if orphan.remap_key {
+                    // Rename the map key, keeping its settings
+                    let settings = config.pointer(&orphan.id).cloned().unwrap_or(json!({}));
+                    removals.push(orphan.id.clone());
+                    config["agents"]["defaults"]["models"][&target] = settings;
+                } else if let Some(slot) = config.pointer_mut(&orphan.id) {
+                    *slot = json!(target);
                 } else {
-                    prompt_preview
-                };
-
-                return Ok(json!({
-                    "matched": true,
-                    "agent_id": agent_id,
-                    "agent_dir": agent_info.and_then(|a| a.get("agentDir").and_then(|v| v.as_str())),
-                    "model": agent_info.and_then(|a| a.pointer("/model/primary").and_then(|v| v.as_str())),
-                    "system_prompt_preview": prompt_preview,
-                    "binding": binding
-                }));
+                    return Err(format!("Reference {} not found", fix.id));
+                }
             }
+            other => return Err(format!("Unknown fix action: {}", other)),
         }
+        applied += 1;
     }
 
-    Ok(json!({
-        "matched": false,
-        "agent_id": "default",
-        "message": "No specific binding found. Messages will be handled by the default agent."
-    }))
-}
-
-// ============ Heartbeat & Compaction ============
+    removals.sort_by(|a, b| compare_pointers_desc(a, b));
+    removals.dedup();
+    for pointer in &removals {
+        remove_at_pointer(&mut config, pointer);
+    }
 
-/// Heartbeat configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HeartbeatConfig {
-    pub every: Option<String>,
-    pub target: Option<String>,
+    save_openclaw_config(&config, "fix_orphans")?;
+    info!("[Orphans] {} fix(es) applied", applied);
+    Ok(format!("{} reference(s) fixed", applied))
 }
+// ============ Model Resolution ============
 
-/// Compaction configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CompactionConfig {
-    pub enabled: bool,
-    pub threshold: Option<u32>,
-    pub context_pruning: bool,
-    pub max_context_messages: Option<u32>,
+/// One step of the model resolution chain
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ModelResolutionStep {
+    /// Where the value comes from, e.g. "agents.list[sales].model.primary"
+    pub source: String,
+    pub model: Option<String>,
+    /// Whether this step decided the model
+    pub selected: bool,
+    /// Whether the provider/model it names is configured
+    pub exists: bool,
+    pub note: Option<String>,
 }
 
-/// Get heartbeat configuration
-#[command]
-pub async fn get_heartbeat_config() -> Result<HeartbeatConfig, String> {
-    info!("[Heartbeat] Getting heartbeat config...");
-    let config = load_openclaw_config()?;
+/// Explanation of which model an agent ends up using, and why
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ModelResolution {
+    pub agent_id: String,
+    pub agent_found: bool,
+    pub steps: Vec<ModelResolutionStep>,
+    pub resolved_model: Option<String>,
+    pub provider: Option<String>,
+    pub base_url: Option<String>,
+    pub api_type: Option<String>,
+    /// "config", "env:VAR_NAME" or None when no key was found
+    pub api_key_source: Option<String>,
+    pub api_key_masked: Option<String>,
+    pub issues: Vec<String>,
+}
 
-    let every = config.pointer("/agents/defaults/heartbeat/every")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-    let target = config.pointer("/agents/defaults/heartbeat/target")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
+/// Conventional env var holding the API key of a built-in provider
+fn provider_env_key(provider: &str) -> Option<&'static str> {
+    match provider {
+        "anthropic" => Some("ANTHROPIC_API_KEY"),
+        "openai" => Some("OPENAI_API_KEY"),
+        "google" => Some("GEMINI_API_KEY"),
+        "openrouter" => Some("OPENROUTER_API_KEY"),
+        "deepseek" => Some("DEEPSEEK_API_KEY"),
+        "moonshot" => Some("MOONSHOT_API_KEY"),
+        "qwen" => Some("DASHSCOPE_API_KEY"),
+        "glm" => Some("ZAI_API_KEY"),
+        "minimax" => Some("MINIMAX_API_KEY"),
+        "venice" => Some("VENICE_API_KEY"),
+        _ => None,
+    }
+}
 
-    Ok(HeartbeatConfig { every, target })
+fn mask_key(key: &str) -> String {
+    // By chars, a pasted key may contain multi-byte characters
+    let count = key.chars().count();
+    if count > 8 {
+        let head: String = key.chars().take(4).collect();
+        let tail: String = key.chars().skip(count - 4).collect();
+        format!("{}...{}", head, tail)
+    } else {
+        "****".to_string()
+    }
 }
 
-/// Save heartbeat configuration
+/// Explain which model an agent will use (agent override -> defaults.primary -> fallbacks)
+/// and which provider, base URL and key that resolves to
 #[command]
-pub async fn save_heartbeat_config(every: Option<String>, target: Option<String>) -> Result<String, String> {
-    info!("[Heartbeat] Saving heartbeat config: every={:?}, target={:?}", every, target);
-    let mut config = load_openclaw_config()?;
-
-    if config.get("agents").is_none() { config["agents"] = json!({}); }
-    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+#[specta::specta]
+pub async fn explain_model_resolution(agent_id: String) -> Result<ModelResolution, String> {
+    info!("[Model Resolution] Explaining model for agent: {}", agent_id);
+    let config = load_openclaw_config()?;
 
-    if every.is_some() || target.is_some() {
-        let mut hb = json!({});
-        if let Some(e) = &every { hb["every"] = json!(e); }
-        if let Some(t) = &target { hb["target"] = json!(t); }
-        config["agents"]["defaults"]["heartbeat"] = hb;
+    let agent = list_agent_entries(&config)
+        .into_iter()
+        .find(|(id, _)| id == &agent_id)
+        .and_then(|(_, pointer)| config.pointer(&pointer).cloned());
+    let mut issues = Vec::new();
+    if agent.is_none() && agent_id != "main" {
+        issues.push(format!("Agent '{}' is not in agents.list, defaults apply", agent_id));
+    }
+
+    // Candidates in resolution order
+    let mut candidates: Vec<(String, Option<String>)> = Vec::new();
+    let model_field = |v: &Value| -> (Option<String>, Vec<String>) {
+        match v.get("model") {
+            Some(Value::String(s)) => (Some(s.clone()), Vec::new()),
+            Some(m) => (
+                m.get("primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                m.get("fallbacks")
+                    .and_then(|v| v.as_array())
+                    .map(|a| a.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect())
+                    .unwrap_or_default(),
+            ),
+            None => (None, Vec::new()),
+        }
+    };
+    let (agent_primary, agent_fallbacks) = agent.as_ref().map(model_field).unwrap_or((None, Vec::new()));
+    let (default_primary, default_fallbacks) = config
+        .pointer("/agents/defaults")
+        .map(model_field)
+        .unwrap_or((None, Vec::new()));
+
+    candidates.push((format!("agents.list[{}].model.primary", agent_id), agent_primary));
+    candidates.push(("agents.defaults.model.primary".to_string(), default_primary));
+    // Agent fallbacks replace the default ones when set
+    let (fallback_source, fallbacks) = if !agent_fallbacks.is_empty() {
+        (format!("agents.list[{}].model.fallbacks", agent_id), agent_fallbacks)
     } else {
-        // Remove heartbeat if both are None
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("heartbeat");
+        ("agents.defaults.model.fallbacks".to_string(), default_fallbacks)
+    };
+    for (i, fb) in fallbacks.into_iter().enumerate() {
+        candidates.push((format!("{}[{}]", fallback_source, i), Some(fb)));
+    }
+
+    let mut steps = Vec::new();
+    let mut resolved_model: Option<String> = None;
+    for (source, model) in candidates {
+        let check = model.as_deref().map(|m| validate_agent_model(&config, m));
+        let exists = matches!(check, Some(Ok(())));
+        let note = match (&model, &check) {
+            (None, _) => Some("not set".to_string()),
+            (Some(_), Some(Err(reason))) => Some(reason.clone()),
+            _ => None,
+        };
+        let selected = resolved_model.is_none() && model.is_some();
+        if selected {
+            resolved_model = model.clone();
+            if !exists {
+                issues.push(format!("Selected model from {} is not usable: {}", source, note.clone().unwrap_or_default()));
+            }
+        }
+        steps.push(ModelResolutionStep { source, model, selected, exists, note });
+    }
+
+    if resolved_model.is_none() {
+        issues.push("No model configured for this agent or in defaults".to_string());
+    }
+
+    // Map the resolved model to provider settings
+    let provider = resolved_model.as_deref().and_then(|m| m.split_once('/')).map(|(p, _)| p.to_string());
+    let provider_cfg = provider.as_deref().and_then(|p| config.pointer(&format!("/models/providers/{}", p)));
+    let base_url = provider_cfg.and_then(|p| p.get("baseUrl")).and_then(|v| v.as_str()).map(|s| s.to_string());
+    let api_type = provider_cfg.and_then(|p| p.get("api")).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    let mut api_key_source = None;
+    let mut api_key_masked = None;
+    if let Some(key) = provider_cfg.and_then(|p| p.get("apiKey")).and_then(|v| v.as_str()).filter(|k| !k.is_empty()) {
+        api_key_source = Some("config".to_string());
+        api_key_masked = Some(mask_key(key));
+    } else if let Some(var) = provider.as_deref().and_then(provider_env_key) {
+        let env_value = file::read_env_value(&platform::get_env_file_path(), var)
+            .or_else(|| std::env::var(var).ok())
+            .filter(|v| !v.is_empty());
+        if let Some(value) = env_value {
+            api_key_source = Some(format!("env:{}", var));
+            api_key_masked = Some(mask_key(&value));
         }
     }
+    if provider.is_some() && api_key_source.is_none() && provider.as_deref() != Some("ollama") {
+        issues.push(format!("No API key found for provider '{}'", provider.clone().unwrap_or_default()));
+    }
+
+    Ok(ModelResolution {
+        agent_id,
+        agent_found: agent.is_some(),
+        steps,
+        resolved_model,
+        provider,
+        base_url,
+        api_type,
+        api_key_source,
+        api_key_masked,
+        issues,
+    })
+}
 
-    save_openclaw_config(&config)?;
-    Ok("Heartbeat configuration saved".to_string())
+// ============ Channel Pairing ============
+
+/// Pending pairing request (dmPolicy = "pairing")
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PairingRequest {
+    /// Stable id used by approve/reject: "<channel>:<code>"
+    pub id: String,
+    pub channel: String,
+    pub code: String,
+    /// Sender id on the channel (e.g. Telegram user id)
+    pub sender_id: Option<String>,
+    pub created_at: Option<String>,
+    pub last_seen_at: Option<String>,
+    /// Extra info from the channel (username, display name...)
+    pub meta: Option<Value>,
 }
 
-/// Get compaction configuration
-#[command]
-pub async fn get_compaction_config() -> Result<CompactionConfig, String> {
-    info!("[Compaction] Getting compaction config...");
-    let config = load_openclaw_config()?;
+/// Read all `<channel>-pairing.json` stores in the credentials directory
+pub(crate) fn read_pairing_requests() -> Vec<PairingRequest> {
+    let dir = platform::get_credentials_dir();
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
 
-    let compaction_val = config.pointer("/agents/defaults/compaction");
-    let pruning_val = config.pointer("/agents/defaults/contextPruning");
+    let mut requests = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let channel = match file_name.strip_suffix("-pairing.json") {
+            Some(c) if !c.is_empty() => c.to_string(),
+            _ => continue,
+        };
 
-    let enabled = compaction_val.map(|v| {
-        // compaction can be true/false or an object with settings
-        v.as_bool().unwrap_or(true)
-    }).unwrap_or(false);
+        let content = match file::read_file(&entry.path().to_string_lossy()) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("[Pairing] Failed to read {}: {}", file_name, e);
+                continue;
+            }
+        };
+        let store: Value = match serde_json::from_str(&content) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!("[Pairing] Failed to parse {}: {}", file_name, e);
+                continue;
+            }
+        };
 
-    let threshold = compaction_val
-        .and_then(|v| v.get("threshold"))
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+        if let Some(items) = store.get("requests").and_then(|v| v.as_array()) {
+            for item in items {
+                let code = match item.get("code").and_then(|v| v.as_str()) {
+                    Some(c) => c.to_string(),
+                    None => continue,
+                };
+                requests.push(PairingRequest {
+                    id: format!("{}:{}", channel, code),
+                    channel: channel.clone(),
+                    code,
+                    sender_id: item.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    created_at: item.get("createdAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    last_seen_at: item.get("lastSeenAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    meta: item.get("meta").cloned(),
+                });
+            }
+        }
+    }
 
-    let context_pruning = pruning_val.map(|v| v.as_bool().unwrap_or(false)).unwrap_or(false);
+    requests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    requests
+}
 
-    let max_context_messages = pruning_val
-        .and_then(|v| v.get("maxMessages"))
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+/// Split a pairing id ("<channel>:<code>") into its parts
+fn parse_pairing_id(id: &str) -> Result<(String, String), String> {
+    match id.split_once(':') {
+        Some((channel, code)) if !channel.is_empty() && !code.is_empty() => {
+            Ok((channel.to_string(), code.to_string()))
+        }
+        _ => Err(format!("Invalid pairing id: {}", id)),
+    }
+}
 
-    Ok(CompactionConfig { enabled, threshold, context_pruning, max_context_messages })
+/// List pending pairing requests across all channels
+#[command]
+#[specta::specta]
+pub async fn list_pending_pairings() -> Result<Vec<PairingRequest>, String> {
+    info!("[Pairing] Listing pending pairing requests...");
+    let requests = read_pairing_requests();
+    info!("[Pairing] Found {} pending request(s)", requests.len());
+    Ok(requests)
 }
 
-/// Save compaction configuration
+/// Approve a pairing request (delegates to `openclaw pairing approve`)
 #[command]
-pub async fn save_compaction_config(
-    enabled: bool,
-    threshold: Option<u32>,
-    context_pruning: bool,
-    max_context_messages: Option<u32>,
-) -> Result<String, String> {
-    info!("[Compaction] Saving compaction config: enabled={}, pruning={}", enabled, context_pruning);
-    let mut config = load_openclaw_config()?;
+#[specta::specta]
+pub async fn approve_pairing(id: String) -> Result<String, String> {
+    info!("[Pairing] Approving pairing request: {}", id);
+    let (channel, code) = parse_pairing_id(&id)?;
 
-    if config.get("agents").is_none() { config["agents"] = json!({}); }
-    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+    shell::run_openclaw(&["pairing", "approve", &channel, &code])
+        .map_err(|e| format!("Failed to approve pairing: {}", e))?;
 
-    if enabled {
-        let mut comp = json!({});
-        if let Some(t) = threshold { comp["threshold"] = json!(t); }
-        config["agents"]["defaults"]["compaction"] = comp;
-    } else {
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("compaction");
-        }
-    }
+    info!("[Pairing] Approved {} on {}", code, channel);
+    Ok(format!("Pairing {} approved", code))
+}
 
-    if context_pruning {
-        let mut pruning = json!(true);
-        if let Some(max) = max_context_messages {
-            pruning = json!({ "maxMessages": max });
-        }
-        config["agents"]["defaults"]["contextPruning"] = pruning;
-    } else {
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("contextPruning");
+/// Reject a pairing request by removing it from the channel's pairing store
+#[command]
+#[specta::specta]
+pub async fn reject_pairing(id: String) -> Result<String, String> {
+    info!("[Pairing] Rejecting pairing request: {}", id);
+    let (channel, code) = parse_pairing_id(&id)?;
+
+    let store_path = platform::path_string(&platform::join_path(platform::get_credentials_dir(), &[&format!("{}-pairing.json", channel)]));
+
+    let content = file::read_file(&store_path)
+        .map_err(|e| format!("Failed to read pairing store: {}", e))?;
+    let mut store: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse pairing store: {}", e))?;
+
+    let removed = match store.get_mut("requests").and_then(|v| v.as_array_mut()) {
+        Some(items) => {
+            let before = items.len();
+            items.retain(|item| item.get("code").and_then(|v| v.as_str()) != Some(code.as_str()));
+            before != items.len()
         }
-    }
+        None => false,
+    };
 
-    save_openclaw_config(&config)?;
-    Ok("Compaction configuration saved".to_string())
-}
+    if !removed {
+        return Err(format!("Pairing request {} not found", id));
+    }
 
-// ============ Workspace & Agent Personality ============
+    let content = serde_json::to_string_pretty(&store)
+        .map_err(|e| format!("Failed to serialize pairing store: {}", e))?;
+    file::write_file(&store_path, &content)
+        .map_err(|e| format!("Failed to write pairing store: {}", e))?;
 
-/// Workspace configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WorkspaceConfig {
-    pub workspace: Option<String>,
-    pub timezone: Option<String>,
-    pub time_format: Option<String>,
-    pub skip_bootstrap: bool,
-    pub bootstrap_max_chars: Option<u32>,
+    info!("[Pairing] Rejected {} on {}", code, channel);
+    Ok(format!("Pairing {} rejected", code))
 }
 
-/// Get workspace configuration
-#[command]
-pub async fn get_workspace_config() -> Result<WorkspaceConfig, String> {
-    info!("[Workspace] Getting workspace config...");
-    let config = load_openclaw_config()?;
+/// Time to wait for more events after a change: an editor save or an atomic rename shows up
+/// as several events
+const CONFIG_WATCH_DEBOUNCE_MS: u64 = 300;
+
+/// How often the watcher checks that it still watches the current config directory (it may
+/// be relocated, or not exist yet)
+const CONFIG_WATCH_RECHECK_SECS: u64 = 5;
+
+/// Watch the config directory and emit `config-changed` (scope "openclaw", "env" or "mcps",
+/// detail `{"external": true}`) when openclaw.json, env or mcps.json is changed outside the
+/// Manager (by hand, or by the openclaw CLI), so the UI can reload. The Manager's own saves
+/// already emit `config-changed` and are not reported again.
+pub fn start_config_watcher() {
+    use notify::Watcher;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+    use std::time::Duration;
+
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("[Config Watcher] File watching is unavailable: {}", e);
+                return;
+            }
+        };
+        let mut watched: Option<std::path::PathBuf> = None;
+        loop {
+            let dir = std::path::PathBuf::from(platform::get_config_dir());
+            if watched.as_ref() != Some(&dir) || !dir.is_dir() {
+                if let Some(previous) = watched.take() {
+                    let _ = watcher.unwatch(&previous);
+                }
+                if dir.is_dir() {
+                    match watcher.watch(&dir, notify::RecursiveMode::NonRecursive) {
+                        Ok(()) => {
+                            config_watch::seed(&dir);
+                            info!("[Config Watcher] Watching {}", dir.display());
+                            watched = Some(dir);
+                        }
+                        Err(e) => warn!("[Config Watcher] Failed to watch {}: {}", dir.display(), e),
+                    }
+                }
+            }
 
-    let workspace = config.pointer("/agents/defaults/workspace")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-    let timezone = config.pointer("/manager/timezone")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-    let time_format = config.pointer("/manager/time_format")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-    let skip_bootstrap = config.pointer("/agents/defaults/skipBootstrap")
-        .and_then(|v| v.as_bool()).unwrap_or(false);
-    let bootstrap_max_chars = config.pointer("/agents/defaults/bootstrapMaxChars")
-        .and_then(|v| v.as_u64()).map(|v| v as u32);
+            // Changed files by scope, collected over one burst of events
+            let mut changed: std::collections::BTreeMap<&'static str, std::path::PathBuf> = Default::default();
+            let mut collect = |event: notify::Result<notify::Event>| match event {
+                Ok(event) if !matches!(event.kind, notify::EventKind::Access(_)) => {
+                    for path in event.paths {
+                        if let Some(scope) = config_watch::scope_for(&path) {
+                            changed.insert(scope, path);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!("[Config Watcher] Watch error: {}", e),
+            };
+            match rx.recv_timeout(Duration::from_secs(CONFIG_WATCH_RECHECK_SECS)) {
+                Ok(event) => collect(event),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            while let Ok(event) = rx.recv_timeout(Duration::from_millis(CONFIG_WATCH_DEBOUNCE_MS)) {
+                collect(event);
+            }
 
-    Ok(WorkspaceConfig { workspace, timezone, time_format, skip_bootstrap, bootstrap_max_chars })
+            for (scope, path) in changed {
+                if !config_watch::changed_on_disk(&path) {
+                    continue;
+                }
+                if scope == "openclaw" {
+                    state::app_state().config_cache.invalidate();
+                }
+                info!("[Config Watcher] {} changed outside the Manager", path.display());
+                events::emit(ManagerEvent::ConfigChanged {
+                    scope: scope.to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    detail: Some(json!({ "external": true })),
+                });
+            }
+        }
+    });
 }
 
-/// Save workspace configuration
-#[command]
-pub async fn save_workspace_config(
-    workspace: Option<String>,
-    timezone: Option<String>,
-    time_format: Option<String>,
-    skip_bootstrap: bool,
-    bootstrap_max_chars: Option<u32>,
-) -> Result<String, String> {
-    info!("[Workspace] Saving workspace config...");
-    let mut config = load_openclaw_config()?;
+/// Watch pairing stores and show a desktop notification when a new request arrives
+pub fn start_pairing_watcher(app: tauri::AppHandle) {
+    use tauri_plugin_notification::NotificationExt;
 
-    if config.get("agents").is_none() { config["agents"] = json!({}); }
-    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+    std::thread::spawn(move || {
+        let mut seen: std::collections::HashSet<String> =
+            read_pairing_requests().into_iter().map(|r| r.id).collect();
 
-    // Set or remove each field in agents.defaults
-    if let Some(defaults) = config.pointer_mut("/agents/defaults").and_then(|v| v.as_object_mut()) {
-        match &workspace {
-            Some(w) if !w.is_empty() => { defaults.insert("workspace".into(), json!(w)); }
-            _ => { defaults.remove("workspace"); }
-        }
-        if skip_bootstrap {
-            defaults.insert("skipBootstrap".into(), json!(true));
-        } else {
-            defaults.remove("skipBootstrap");
-        }
-        match bootstrap_max_chars {
-            Some(max) => { defaults.insert("bootstrapMaxChars".into(), json!(max)); }
-            None => { defaults.remove("bootstrapMaxChars"); }
-        }
-        // Remove timezone/timeFormat from defaults if present (migrate to manager)
-        defaults.remove("timezone");
-        defaults.remove("timeFormat");
-    }
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(15));
 
-    // Set manager fields
-    if config.get("manager").is_none() { config["manager"] = json!({}); }
-    if let Some(manager) = config.get_mut("manager").and_then(|v| v.as_object_mut()) {
-        match &timezone {
-            Some(tz) if !tz.is_empty() => { manager.insert("timezone".into(), json!(tz)); }
-            _ => { manager.remove("timezone"); }
-        }
-        match &time_format {
-            Some(tf) if !tf.is_empty() => { manager.insert("time_format".into(), json!(tf)); }
-            _ => { manager.remove("time_format"); }
+            let requests = read_pairing_requests();
+            for request in &requests {
+                if seen.contains(&request.id) {
+                    continue;
+                }
+                info!("[Pairing] New pairing request on {}: {}", request.channel, request.code);
+                events::emit(ManagerEvent::alert(
+                    "pairing-request",
+                    AlertLevel::Info,
+                    format!("New pairing request on {}", request.channel),
+                    request,
+                ));
+                if let Err(e) = app
+                    .notification()
+                    .builder()
+                    .title("New pairing request")
+                    .body(format!("{} wants to pair via {} (code {})",
+                        request.sender_id.as_deref().unwrap_or("Someone"), request.channel, request.code))
+                    .show()
+                {
+                    debug!("[Pairing] Failed to show notification: {}", e);
+                }
+            }
+            seen = requests.into_iter().map(|r| r.id).collect();
         }
+    });
+}
+
+// ============ Configuration Draft ============
+
+/// Draft workspace status
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct DraftStatus {
+    pub active: bool,
+    /// When the draft was opened (RFC3339)
+    pub started_at: Option<String>,
+    /// Number of pending changes compared to the live config
+    pub change_count: usize,
+}
+
+/// Get the state of the configuration draft
+#[command]
+#[specta::specta]
+pub async fn get_draft_status() -> Result<DraftStatus, String> {
+    if !draft_active() {
+        return Ok(DraftStatus { active: false, started_at: None, change_count: 0 });
     }
 
-    save_openclaw_config(&config)?;
-    Ok("Workspace configuration saved".to_string())
+    let live = read_config_file(&platform::get_config_file_path())?;
+    let draft = read_config_file(&get_draft_file_path())?;
+    let manager_config = load_manager_config().unwrap_or(json!({}));
+
+    Ok(DraftStatus {
+        active: true,
+        started_at: manager_config.pointer("/draft/startedAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        change_count: json_diff::diff(&live, &draft).len(),
+    })
 }
 
-/// Get a personality file from the workspace directory
+/// Open a configuration draft: subsequent saves go to a staged copy instead of openclaw.json
 #[command]
-pub async fn get_personality_file(filename: String) -> Result<String, String> {
-    info!("[Personality] Reading file: {}", filename);
-
-    // Validate filename
-    let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
-    if !allowed.contains(&filename.as_str()) {
-        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+#[specta::specta]
+pub async fn begin_draft() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "begin_draft").await;
+    info!("[Draft] Beginning configuration draft...");
+    if draft_active() {
+        return Err("A configuration draft is already open".to_string());
     }
 
-    // Get workspace path from config, fallback to ~/.openclaw
-    let config = load_openclaw_config()?;
-    let workspace = config.pointer("/agents/defaults/workspace")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    let dir = if workspace.is_empty() {
-        platform::get_config_dir()
-    } else {
-        workspace.to_string()
-    };
+    let live = read_config_file(&platform::get_config_file_path())?;
+    let content = serde_json::to_string_pretty(&live)
+        .map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+    file::write_file(&get_draft_file_path(), &content)
+        .map_err(|e| format!("Failed to create draft: {}", e))?;
 
-    let filepath = if platform::is_windows() {
-        format!("{}\\{}", dir, filename)
-    } else {
-        format!("{}/{}", dir, filename)
-    };
+    let mut manager_config = load_manager_config().unwrap_or(json!({}));
+    manager_config["draft"] = json!({ "startedAt": chrono::Utc::now().to_rfc3339() });
+    save_manager_config(&manager_config)?;
 
-    match file::read_file(&filepath) {
-        Ok(content) => Ok(content),
-        Err(_) => Ok(String::new()), // File doesn't exist yet, return empty
-    }
+    info!("[Draft] Draft opened at {}", get_draft_file_path());
+    Ok("Draft started. Changes will be staged until committed.".to_string())
 }
 
-/// Save a personality file to the workspace directory
+/// Preview the changes staged in the draft (secrets masked)
 #[command]
-pub async fn save_personality_file(filename: String, content: String) -> Result<String, String> {
-    info!("[Personality] Saving file: {}", filename);
+#[specta::specta]
+pub async fn preview_draft_diff() -> Result<Vec<json_diff::JsonChange>, String> {
+    info!("[Draft] Building draft diff...");
+    if !draft_active() {
+        return Err("No configuration draft is open".to_string());
+    }
+
+    let live = read_config_file(&platform::get_config_file_path())?;
+    let draft = read_config_file(&get_draft_file_path())?;
+    let changes = json_diff::diff_masked(&live, &draft);
+    info!("[Draft] {} pending change(s)", changes.len());
+    Ok(changes)
+}
 
-    let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
-    if !allowed.contains(&filename.as_str()) {
-        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+/// Apply the draft to openclaw.json in one step, keeping a single backup of the previous file
+#[command]
+#[specta::specta]
+pub async fn commit_draft() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "commit_draft").await;
+    info!("[Draft] Committing configuration draft...");
+    if !draft_active() {
+        return Err("No configuration draft is open".to_string());
     }
 
-    let config = load_openclaw_config()?;
-    let workspace = config.pointer("/agents/defaults/workspace")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    let dir = if workspace.is_empty() {
-        platform::get_config_dir()
-    } else {
-        workspace.to_string()
-    };
+    let live_path = platform::get_config_file_path();
+    let draft = read_config_file(&get_draft_file_path())?;
 
-    let filepath = if platform::is_windows() {
-        format!("{}\\{}", dir, filename)
-    } else {
-        format!("{}/{}", dir, filename)
-    };
+    // One backup for the whole batch of changes
+    if file::file_exists(&live_path) {
+        let backup_path = format!("{}.pre-draft.bak", live_path);
+        std::fs::copy(&live_path, &backup_path)
+            .map_err(|e| format!("Failed to back up configuration: {}", e))?;
+        info!("[Draft] Backup written to {}", backup_path);
+    }
 
-    file::write_file(&filepath, &content)
-        .map_err(|e| format!("Failed to save {}: {}", filename, e))?;
+    let previous = read_config_file(&live_path).unwrap_or_else(|_| json!({}));
+    write_config_file(&live_path, &draft).map_err(|e| format!("Failed to apply draft: {}", e))?;
+    history::record_config_change("commit_draft", &previous, &draft);
 
-    Ok(format!("{} saved successfully", filename))
+    discard_draft_files();
+    info!("[Draft] Draft committed");
+    Ok("Draft committed".to_string())
 }
 
-// ============ Browser Control ============
+/// Throw away the draft without touching openclaw.json
+#[command]
+#[specta::specta]
+pub async fn discard_draft() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "discard_draft").await;
+    info!("[Draft] Discarding configuration draft...");
+    if !draft_active() {
+        return Err("No configuration draft is open".to_string());
+    }
+    discard_draft_files();
+    Ok("Draft discarded".to_string())
+}
 
-/// Browser configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BrowserConfig {
-    pub enabled: bool,
-    pub color: Option<String>,
+fn discard_draft_files() {
+    let _ = std::fs::remove_file(get_draft_file_path());
+    if let Ok(mut manager_config) = load_manager_config() {
+        if let Some(obj) = manager_config.as_object_mut() {
+            obj.remove("draft");
+        }
+        let _ = save_manager_config(&manager_config);
+    }
 }
 
-/// Get browser configuration
+// ============ Configuration Management ============
+
+/// List credentials that export_config would write out
 #[command]
-pub async fn get_browser_config() -> Result<BrowserConfig, String> {
-    info!("[Browser] Getting browser config...");
+#[specta::specta]
+pub async fn scan_config_secrets() -> Result<Vec<SecretFinding>, String> {
     let config = load_openclaw_config()?;
+    Ok(secrets_scan::scan(&config))
+}
 
-    // Read from meta (Manager specific)
-    let enabled = config.pointer("/meta/gui/browser/enabled")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(true); // Default to true if not set
+/// Export configuration. `secrets` is "redact" or "include"; required when the config holds credentials.
+#[command]
+#[specta::specta]
+pub async fn export_config(path: String, secrets: Option<String>) -> Result<String, String> {
+    info!("[Config] Exporting config to: {}", path);
+    let config = secrets_scan::apply_policy(&load_openclaw_config()?, secrets.as_deref())?;
 
-    let color = config.pointer("/meta/gui/browser/color")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    Ok(BrowserConfig { enabled, color })
+    file::write_file(&path, &content)
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    Ok(format!("Configuration exported to {}", path))
 }
 
-/// Save browser configuration
+/// Import configuration
 #[command]
-pub async fn save_browser_config(enabled: bool, color: Option<String>) -> Result<String, String> {
-    info!("[Browser] Saving browser config: enabled={}, color={:?}", enabled, color);
-    let mut config = load_openclaw_config()?;
+#[specta::specta]
+pub async fn import_config(path: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "import_config").await;
+    info!("[Config] Importing config from: {}", path);
 
-    // Store in meta.gui.browser to avoid polluting core config
-    if config.get("meta").is_none() { config["meta"] = json!({}); }
-    if config["meta"].get("gui").is_none() { config["meta"]["gui"] = json!({}); }
-    
-    let mut browser_config = json!({
-        "enabled": enabled
-    });
+    let content = file::read_file(&path)
+        .map_err(|e| format!("Failed to read import file: {}", e))?;
 
-    if let Some(c) = color {
-        if !c.is_empty() {
-            browser_config["color"] = json!(c);
-        }
+    let new_config: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON file: {}", e))?;
+
+    if !new_config.is_object() {
+        return Err("Imported file is not a valid configuration object".to_string());
     }
 
-    config["meta"]["gui"]["browser"] = browser_config;
+    save_openclaw_config(&new_config, "import_config")?;
 
-    save_openclaw_config(&config)?;
-    Ok("Browser configuration saved".to_string())
+    Ok("Configuration imported successfully".to_string())
 }
 
-// ============ Web Search ============
-
-/// Web Search configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WebConfig {
-    pub brave_api_key: Option<String>,
+/// Where a configuration key is stored
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ConfigKeySource {
+    /// Dotted key path (e.g. "models.providers")
+    pub path: String,
+    /// File the key is read from and saved to
+    pub file: String,
 }
 
-/// Get web search configuration
+/// List keys that come from `$include` files (everything else lives in openclaw.json)
 #[command]
-pub async fn get_web_config() -> Result<WebConfig, String> {
-    info!("[Web] Getting web search config...");
-    let config = load_openclaw_config()?;
-
-    let brave_api_key = config.pointer("/web/braveApiKey")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    Ok(WebConfig { brave_api_key })
+#[specta::specta]
+pub async fn get_config_sources() -> Result<Vec<ConfigKeySource>, String> {
+    info!("[Config] Resolving configuration sources...");
+    let (_, sources) = config_include::load(&platform::get_config_file_path())?;
+
+    let mut result = Vec::new();
+    for source in sources {
+        let prefix = source.pointer.trim_start_matches('/').replace('/', ".").replace("~1", "/").replace("~0", "~");
+        for key in source.keys {
+            result.push(ConfigKeySource {
+                path: if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) },
+                file: source.file.clone(),
+            });
+        }
+    }
+    info!("[Config] {} key(s) come from included files", result.len());
+    Ok(result)
 }
 
-/// Save web search configuration
-#[command]
-pub async fn save_web_config(brave_api_key: Option<String>) -> Result<String, String> {
-    info!("[Web] Saving web search config...");
-    let mut config = load_openclaw_config()?;
-
-    if config.get("web").is_none() {
-        config["web"] = json!({});
-    }
+/// Read another instance's config for compare_with: an http(s) URL serving an export, an
+/// exported openclaw.json, or a Manager backup file
+fn read_compare_source(source: &str, passphrase: Option<&str>) -> Result<Value, String> {
+    let content = if source.starts_with("http://") || source.starts_with("https://") {
+        let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+        cmd.args(["-s", "-f", "-L", "--max-time", "30", source]);
 
-    match brave_api_key {
-        Some(key) if !key.is_empty() => {
-            config["web"]["braveApiKey"] = json!(key);
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000);
         }
-        _ => {
-            if let Some(web) = config.get_mut("web").and_then(|v| v.as_object_mut()) {
-                web.remove("braveApiKey");
-            }
+
+        let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to download the config (curl exit code {:?})", output.status.code()));
         }
-    }
+        String::from_utf8_lossy(&output.stdout).to_string()
+    } else {
+        file::read_file(source).map_err(|e| format!("Failed to read {}: {}", source, e))?
+    };
 
+    if let Some(config) = crate::commands::backup::config_from_backup_content(&content, passphrase)? {
+        return Ok(config);
+    }
+    let config: Value = serde_json::from_str(&content).map_err(|e| format!("Not a valid config file: {}", e))?;
+    if !config.is_object() {
+        return Err("The other config is not a configuration object".to_string());
+    }
+    Ok(config)
+}
 
-    save_openclaw_config(&config)?;
-    Ok("Web search configuration saved".to_string())
+/// Compare the local openclaw.json with another machine's exported config or backup (file
+/// path or http(s) URL) and report the drift per section. `passphrase` opens encrypted backups.
+#[command]
+#[specta::specta]
+pub async fn compare_with(file_or_remote: String, passphrase: Option<String>) -> Result<config_drift::DriftReport, String> {
+    let source = file_or_remote.trim().to_string();
+    if source.is_empty() {
+        return Err("Choose a file or URL to compare with".to_string());
+    }
+    info!("[Config] Comparing config with {}", log_sanitizer::sanitize(&source));
+    let other = read_compare_source(&source, passphrase.as_deref())?;
+    let report = config_drift::compare(&source, &load_openclaw_config()?, &other);
+    info!("[Config] {} difference(s) in {} section(s)", report.total, report.sections.len());
+    Ok(report)
 }
 
-// ============ Gateway Configuration ============
+// ============ Git Sync ============
+
+/// Repository the configuration is synced with (manager.json -> gitSync)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitSyncSettings {
+    pub url: String,
+    pub branch: String,
+    /// Config file inside the repository
+    pub path: String,
+    /// Commit the local config was last pulled from or pushed as
+    pub last_synced_commit: Option<String>,
+}
 
-/// Gateway configuration for frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GatewayConfig {
-    pub port: u16,
-    pub log_level: String,
+/// Git sync state for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GitSyncStatus {
+    pub linked: bool,
+    pub settings: Option<GitSyncSettings>,
+    /// Commit of the local mirror (as of the last fetch)
+    pub head_commit: Option<String>,
+    /// Differences between openclaw.json and the config in the mirror
+    pub local_changes: usize,
 }
 
-/// Get gateway configuration
-#[command]
-pub async fn get_gateway_config() -> Result<GatewayConfig, String> {
-    info!("[Gateway] Getting gateway config...");
-    let config = load_openclaw_config()?;
+/// What applying the repository's config would change
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GitPullPreview {
+    pub commit: String,
+    pub subject: String,
+    /// Changes to openclaw.json (secrets masked)
+    pub changes: Vec<json_diff::JsonChange>,
+    pub valid: bool,
+    pub validation_error: Option<String>,
+}
 
-    let port = config.pointer("/gateway/port")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u16)
-        .unwrap_or(3000);
+/// Local mirror of the linked repository (Manager-owned, reset on every fetch)
+fn get_git_sync_dir() -> String {
+    platform::path_string(&platform::join_path(platform::get_manager_data_dir(), &["git-sync"]))
+}
 
-    let log_level = config.pointer("/manager/log_level")
-        .and_then(|v| v.as_str())
-        .or_else(|| config.pointer("/gateway/logLevel").and_then(|v| v.as_str())) // Legacy fallback
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| "info".to_string());
+fn load_git_sync_settings() -> Option<GitSyncSettings> {
+    load_manager_config()
+        .ok()
+        .and_then(|c| c.get("gitSync").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+}
 
-    Ok(GatewayConfig { port, log_level })
+fn save_git_sync_settings(settings: Option<&GitSyncSettings>) -> Result<(), String> {
+    let mut manager_config = load_manager_config().unwrap_or(json!({}));
+    match settings {
+        Some(settings) => manager_config["gitSync"] = json!(settings),
+        None => {
+            if let Some(obj) = manager_config.as_object_mut() {
+                obj.remove("gitSync");
+            }
+        }
+    }
+    save_manager_config(&manager_config)
 }
 
-/// Save gateway configuration
-#[command]
-pub async fn save_gateway_config(port: u16, log_level: String) -> Result<String, String> {
-    info!("[Gateway] Saving gateway config: port={}, level={}", port, log_level);
-    let mut config = load_openclaw_config()?;
+/// Run git without prompting; credentials come from the user's git setup (SSH agent,
+/// credential helper)
+fn run_git(args: &[&str]) -> Result<String, String> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("PATH", shell::get_extended_path());
 
-    if config.get("gateway").is_none() {
-        config["gateway"] = json!({});
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
 
-    if let Some(gateway) = config.get_mut("gateway").and_then(|v| v.as_object_mut()) {
-        gateway.insert("port".to_string(), json!(port));
-        // Remove legacy logLevel if exists
-        gateway.remove("logLevel");
-        gateway.remove("log_level");
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run git: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(format!("git {} failed: {}", args.first().unwrap_or(&""), if stderr.is_empty() { stdout } else { stderr }))
     }
+}
 
-    if config.get("manager").is_none() {
-        config["manager"] = json!({});
+/// Refuse branch names git would read as an option or that are not valid branch names
+fn check_git_branch(branch: &str) -> Result<(), String> {
+    if branch.starts_with('-') || run_git(&["check-ref-format", "--branch", branch]).is_err() {
+        return Err(format!("'{}' is not a valid branch name", branch));
     }
+    Ok(())
+}
 
-    if let Some(manager) = config.get_mut("manager").and_then(|v| v.as_object_mut()) {
-        manager.insert("log_level".to_string(), json!(log_level));
+/// Bring the mirror to the remote branch head, cloning it again if it is missing
+fn fetch_git_mirror(settings: &GitSyncSettings) -> Result<String, String> {
+    check_git_branch(&settings.branch)?;
+    let dir = get_git_sync_dir();
+    if !std::path::Path::new(&dir).join(".git").exists() {
+        let _ = std::fs::remove_dir_all(&dir);
+        run_git(&["clone", "--branch", &settings.branch, "--single-branch", "--", &settings.url, &dir])?;
+    } else {
+        run_git(&["-C", &dir, "fetch", "--", "origin", &settings.branch])?;
+        run_git(&["-C", &dir, "reset", "--hard", &format!("origin/{}", settings.branch), "--"])?;
     }
-    
-    save_openclaw_config(&config)?;
-    Ok("Gateway configuration saved".to_string())
+    run_git(&["-C", &dir, "rev-parse", "HEAD"])
 }
 
-// ============ Configuration Management ============
+/// The config file inside the mirror. Symlinks in the repository are resolved, and a path
+/// leading outside the mirror is refused.
+fn git_mirror_file(settings: &GitSyncSettings) -> Result<String, String> {
+    let dir = get_git_sync_dir();
+    let outside = || format!("{} is outside the repository", settings.path);
+    if std::path::Path::new(&settings.path).is_absolute() || settings.path.split(['/', '\\']).any(|part| part == "..") {
+        return Err(outside());
+    }
+    let root = std::fs::canonicalize(&dir).map_err(|e| format!("Git mirror is not available: {}", e))?;
+    let path = platform::join_path(&dir, &[&settings.path]);
+    // A dangling symlink has metadata but cannot be resolved, so it is refused as well
+    let existing = path.ancestors().find(|p| std::fs::symlink_metadata(p).is_ok()).ok_or_else(outside)?;
+    let resolved = std::fs::canonicalize(existing).map_err(|_| outside())?;
+    if !resolved.starts_with(&root) {
+        return Err(outside());
+    }
+    Ok(platform::path_string(&path))
+}
+
+/// Config in the mirror, with redacted values filled in from the live config
+fn read_git_mirror_config(settings: &GitSyncSettings, live: &Value) -> Result<Value, String> {
+    let content = file::read_file(&git_mirror_file(settings)?)
+        .map_err(|e| format!("{} not found in the repository: {}", settings.path, e))?;
+    let incoming: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{} in the repository is not valid JSON: {}", settings.path, e))?;
+    if !incoming.is_object() {
+        return Err(format!("{} in the repository is not a configuration object", settings.path));
+    }
+    Ok(secrets_scan::restore_redacted(&incoming, live))
+}
 
-/// Export configuration
+/// Link a git repository holding the configuration. `path` is the config file inside the
+/// repository (default openclaw.json). Nothing is applied until a previewed pull is applied.
 #[command]
-pub async fn export_config(path: String) -> Result<String, String> {
-    info!("[Config] Exporting config to: {}", path);
-    let config = load_openclaw_config()?;
-    
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+#[specta::specta]
+pub async fn link_git_repo(url: String, branch: String, path: Option<String>) -> Result<GitSyncStatus, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "link_git_repo").await;
+    if !shell::command_exists("git") {
+        return Err("Git is not installed. Install Git first (Settings > Environment > Install Git)".to_string());
+    }
+    let url = url.trim().to_string();
+    let branch = branch.trim().to_string();
+    if url.is_empty() || branch.is_empty() {
+        return Err("Repository URL and branch are required".to_string());
+    }
+    let path = path
+        .map(|p| p.trim().trim_start_matches('/').to_string())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "openclaw.json".to_string());
+    if std::path::Path::new(&path).is_absolute() || path.split(['/', '\\']).any(|part| part == "..") {
+        return Err("The config path must stay inside the repository".to_string());
+    }
+    check_git_branch(&branch)?;
+    info!("[Git Sync] Linking repository (branch {}, file {})", branch, path);
+
+    let settings = GitSyncSettings { url, branch, path, last_synced_commit: None };
+    let _ = std::fs::remove_dir_all(get_git_sync_dir());
+    let head = fetch_git_mirror(&settings)?;
+    save_git_sync_settings(Some(&settings))?;
+    info!("[Git Sync] Linked at {}", head);
+    git_sync_status(settings)
+}
 
-    file::write_file(&path, &content)
-        .map_err(|e| format!("Failed to write export file: {}", e))?;
+/// Forget the linked repository and delete the local mirror
+#[command]
+#[specta::specta]
+pub async fn unlink_git_repo() -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "unlink_git_repo").await;
+    info!("[Git Sync] Unlinking repository");
+    save_git_sync_settings(None)?;
+    let _ = std::fs::remove_dir_all(get_git_sync_dir());
+    Ok("Repository unlinked".to_string())
+}
 
-    Ok(format!("Configuration exported to {}", path))
+fn git_sync_status(settings: GitSyncSettings) -> Result<GitSyncStatus, String> {
+    let head_commit = run_git(&["-C", &get_git_sync_dir(), "rev-parse", "HEAD"]).ok();
+    let live = load_openclaw_config()?;
+    let local_changes = read_git_mirror_config(&settings, &live)
+        .map(|incoming| json_diff::diff(&incoming, &live).len())
+        .unwrap_or(0);
+    Ok(GitSyncStatus { linked: true, settings: Some(settings), head_commit, local_changes })
 }
 
-/// Import configuration
+/// Get the linked repository and how far the local config is from the mirror
 #[command]
-pub async fn import_config(path: String) -> Result<String, String> {
-    info!("[Config] Importing config from: {}", path);
+#[specta::specta]
+pub async fn get_git_sync_status() -> Result<GitSyncStatus, String> {
+    match load_git_sync_settings() {
+        Some(settings) => git_sync_status(settings),
+        None => Ok(GitSyncStatus { linked: false, settings: None, head_commit: None, local_changes: 0 }),
+    }
+}
 
-    let content = file::read_file(&path)
-        .map_err(|e| format!("Failed to read import file: {}", e))?;
+/// Fetch the repository and show what applying its config would change, with schema validation
+#[command]
+#[specta::specta]
+pub async fn preview_git_pull() -> Result<GitPullPreview, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "preview_git_pull").await;
+    let settings = load_git_sync_settings().ok_or("No git repository is linked")?;
+    info!("[Git Sync] Fetching {}...", settings.branch);
+    let commit = fetch_git_mirror(&settings)?;
+    let subject = run_git(&["-C", &get_git_sync_dir(), "log", "-1", "--format=%s"]).unwrap_or_default();
+
+    let live = load_openclaw_config()?;
+    let incoming = read_git_mirror_config(&settings, &live)?;
+    let changes = json_diff::diff_masked(&live, &incoming);
+    let content = serde_json::to_string_pretty(&incoming).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let validation_error = validate_openclaw_config(content).await.err();
+    info!("[Git Sync] {} change(s) at {}, valid: {}", changes.len(), commit, validation_error.is_none());
+    Ok(GitPullPreview { commit, subject, changes, valid: validation_error.is_none(), validation_error })
+}
 
-    let new_config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Invalid JSON file: {}", e))?;
+/// Apply the previewed commit: validate, back up, then write openclaw.json
+#[command]
+#[specta::specta]
+pub async fn apply_git_pull(commit: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "apply_git_pull").await;
+    let mut settings = load_git_sync_settings().ok_or("No git repository is linked")?;
+    let head = run_git(&["-C", &get_git_sync_dir(), "rev-parse", "HEAD"])?;
+    if head != commit {
+        return Err("The repository changed since the preview, preview the pull again".to_string());
+    }
+    info!("[Git Sync] Applying {}...", commit);
+
+    let live = load_openclaw_config()?;
+    let incoming = read_git_mirror_config(&settings, &live)?;
+    let content = serde_json::to_string_pretty(&incoming).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    validate_openclaw_config(content)
+        .await
+        .map_err(|e| format!("The repository config does not pass validation: {}", e))?;
+
+    let backup = crate::commands::backup::create_backup(None).await?;
+    save_openclaw_config(&incoming, "apply_git_pull")?;
+    settings.last_synced_commit = Some(commit.clone());
+    save_git_sync_settings(Some(&settings))?;
+    info!("[Git Sync] Applied {} (backup {})", commit, backup.id);
+    Ok(format!("Configuration from {} applied, previous config backed up as {}", &commit[..commit.len().min(8)], backup.id))
+}
 
-    if !new_config.is_object() {
-        return Err("Imported file is not a valid configuration object".to_string());
-    }
+/// Commit the local openclaw.json to the repository and push it. Refused while the repository
+/// has commits that were not pulled. `secrets` is "redact" or "include" like export_config.
+#[command]
+#[specta::specta]
+pub async fn push_git_changes(message: Option<String>, secrets: Option<String>) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "push_git_changes").await;
+    let mut settings = load_git_sync_settings().ok_or("No git repository is linked")?;
+    let dir = get_git_sync_dir();
+    let head = fetch_git_mirror(&settings)?;
+
+    let file_path = git_mirror_file(&settings)?;
+    let in_repo = file::file_exists(&file_path);
+    if in_repo && settings.last_synced_commit.as_deref() != Some(head.as_str()) {
+        return Err("The repository has changes that were not pulled yet, preview and apply them first".to_string());
+    }
+
+    let config = secrets_scan::apply_policy(&load_openclaw_config()?, secrets.as_deref())?;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    file::write_file(&file_path, &(content + "\n")).map_err(|e| format!("Failed to write {}: {}", settings.path, e))?;
+
+    run_git(&["-C", &dir, "add", "--", &settings.path])?;
+    if run_git(&["-C", &dir, "diff", "--cached", "--quiet"]).is_ok() {
+        return Ok("Nothing to push, the repository is up to date".to_string());
+    }
+
+    let message = message
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| format!("Update {} from OpenClaw Manager", settings.path));
+    // Fall back to a Manager identity when git has none configured
+    let mut commit_args = vec!["-C", dir.as_str()];
+    if run_git(&["-C", &dir, "config", "user.email"]).map(|e| e.is_empty()).unwrap_or(true) {
+        commit_args.extend(["-c", "user.name=OpenClaw Manager", "-c", "user.email=manager@openclaw.local"]);
+    }
+    commit_args.extend(["commit", "-m", message.as_str()]);
+    run_git(&commit_args)?;
+
+    let refspec = format!("HEAD:{}", settings.branch);
+    if let Err(e) = run_git(&["-C", &dir, "push", "--", "origin", &refspec]) {
+        // Drop the local commit so the mirror matches the remote again
+        let _ = run_git(&["-C", &dir, "reset", "--hard", &format!("origin/{}", settings.branch), "--"]);
+        return Err(e);
+    }
+    let pushed = run_git(&["-C", &dir, "rev-parse", "HEAD"])?;
+    settings.last_synced_commit = Some(pushed.clone());
+    save_git_sync_settings(Some(&settings))?;
+    info!("[Git Sync] Pushed {}", pushed);
+    Ok(format!("Configuration pushed as {}", &pushed[..pushed.len().min(8)]))
+}
 
-    save_openclaw_config(&new_config)?;
+// ============ Setting Locations ============
 
-    Ok("Configuration imported successfully".to_string())
+/// Map a JSON pointer or dotted config path to the Manager screen that edits it, so
+/// diagnostics and search results can deep-link into the UI
+#[command]
+#[specta::specta]
+pub async fn resolve_setting_location(pointer: String) -> Result<Option<SettingLocation>, String> {
+    Ok(setting_location::resolve(&pointer))
 }
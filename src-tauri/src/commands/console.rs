@@ -0,0 +1,309 @@
+use crate::commands::config::{load_manager_config, save_manager_config};
+use crate::commands::history;
+use crate::commands::locks::{self, ConfigResource};
+use crate::commands::process;
+use crate::events::{self, ManagerEvent};
+use crate::utils::console::{self, AuditEntry, Utf8Stream};
+use crate::utils::{file, platform, shell};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::ChildStdin;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::command;
+
+/// Oldest audit entries are dropped beyond this many
+const MAX_AUDIT_ENTRIES: usize = 5000;
+
+/// A console command that is still running
+struct Session {
+    pid: u32,
+    /// Taken (closed) by close_console_input
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+    cancelled: bool,
+}
+
+fn sessions() -> &'static Mutex<HashMap<String, Session>> {
+    static SESSIONS: OnceLock<Mutex<HashMap<String, Session>>> = OnceLock::new();
+    SESSIONS.get_or_init(Default::default)
+}
+
+fn get_audit_file_path() -> String {
+    platform::path_string(&platform::join_path(
+        platform::get_manager_data_dir(),
+        &["audit.jsonl"],
+    ))
+}
+
+fn read_audit_log() -> Vec<AuditEntry> {
+    let file = match std::fs::File::open(get_audit_file_path()) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Append to manager/audit.jsonl. Console commands are refused when this fails, so nothing
+/// runs from the console without leaving a trace.
+fn record_audit(action: &str, detail: &str, outcome: Option<String>) -> Result<(), String> {
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: action.to_string(),
+        user: history::current_user(),
+        detail: detail.to_string(),
+        outcome,
+    };
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+    let path = get_audit_file_path();
+    if let Some(parent) = std::path::Path::new(&path).parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    file::append_file(&path, &line).map_err(|e| format!("Failed to write the audit log: {}", e))?;
+
+    // Trim occasionally rather than on every write
+    let entries = read_audit_log();
+    if entries.len() > MAX_AUDIT_ENTRIES + MAX_AUDIT_ENTRIES / 10 {
+        let keep = &entries[entries.len() - MAX_AUDIT_ENTRIES..];
+        let content: Vec<String> = keep
+            .iter()
+            .filter_map(|e| serde_json::to_string(e).ok())
+            .collect();
+        let _ = file::write_file(&path, &(content.join("\n") + "\n"));
+    }
+    Ok(())
+}
+
+fn advanced_mode_enabled() -> bool {
+    load_manager_config()
+        .ok()
+        .and_then(|c| c.get(console::ADVANCED_MODE_KEY).and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// Whether advanced mode (the embedded console) is on
+#[command]
+#[specta::specta]
+pub async fn get_advanced_mode() -> Result<bool, String> {
+    Ok(advanced_mode_enabled())
+}
+
+/// Turn advanced mode on or off; the change is recorded in the audit log
+#[command]
+#[specta::specta]
+pub async fn set_advanced_mode(enabled: bool) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_advanced_mode").await;
+    record_audit("advanced-mode", if enabled { "on" } else { "off" }, None)?;
+    let mut manager_config = load_manager_config()?;
+    if !manager_config.is_object() {
+        manager_config = serde_json::json!({});
+    }
+    manager_config[console::ADVANCED_MODE_KEY] = serde_json::json!(enabled);
+    save_manager_config(&manager_config)?;
+    info!(
+        "[Console] Advanced mode {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(if enabled {
+        "Advanced mode enabled".to_string()
+    } else {
+        "Advanced mode disabled".to_string()
+    })
+}
+
+/// Console command that was started
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleSession {
+    /// Identifies the command in console-output / console-exit events
+    pub id: String,
+    /// Command line as recorded in the audit log (secrets masked)
+    pub command: String,
+    pub pid: u32,
+}
+
+/// Forward one output stream of a console command as console-output events
+fn pump_output(session: &str, stream: &str, mut reader: impl Read) {
+    let mut decoder = Utf8Stream::default();
+    let mut buf = [0u8; 4096];
+    loop {
+        let (text, done) = match reader.read(&mut buf) {
+            Ok(0) => (decoder.finish(), true),
+            Ok(n) => (decoder.push(&buf[..n]), false),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => (decoder.finish(), true),
+        };
+        if !text.is_empty() {
+            events::emit(ManagerEvent::ConsoleOutput {
+                session: session.to_string(),
+                stream: stream.to_string(),
+                text,
+            });
+        }
+        if done {
+            break;
+        }
+    }
+}
+
+/// Run an arbitrary `openclaw ...` command (advanced mode only). Output streams as
+/// console-output events and a console-exit event follows when it ends; the command line
+/// (secrets masked) and its outcome are recorded in the audit log.
+#[command]
+#[specta::specta]
+pub async fn start_console_command(args: Vec<String>) -> Result<ConsoleSession, String> {
+    if !advanced_mode_enabled() {
+        return Err("The console is only available in advanced mode".to_string());
+    }
+    let args = console::normalize_args(args)?;
+    let running = sessions().lock().map(|s| s.len()).unwrap_or(0);
+    if running >= console::MAX_SESSIONS {
+        return Err(format!(
+            "{} console commands are already running, cancel one first",
+            running
+        ));
+    }
+    let command_line = console::command_line(&args);
+    record_audit("console-run", &command_line, None)?;
+
+    let mut child = shell::run_openclaw_interactive(&args)?;
+    let pid = child.id();
+    let id = format!("console-{}", pid);
+    info!("[Console] {} started: {}", id, command_line);
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    if let Ok(mut sessions) = sessions().lock() {
+        sessions.insert(
+            id.clone(),
+            Session {
+                pid,
+                stdin: Arc::new(Mutex::new(child.stdin.take())),
+                cancelled: false,
+            },
+        );
+    }
+
+    let session = id.clone();
+    let audited = command_line.clone();
+    std::thread::spawn(move || {
+        let readers: Vec<_> = [
+            stdout.map(|out| ("stdout", Box::new(out) as Box<dyn Read + Send>)),
+            stderr.map(|err| ("stderr", Box::new(err) as Box<dyn Read + Send>)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(stream, reader)| {
+            let session = session.clone();
+            std::thread::spawn(move || pump_output(&session, stream, reader))
+        })
+        .collect();
+        let status = child.wait();
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        let cancelled = sessions()
+            .lock()
+            .ok()
+            .and_then(|mut s| s.remove(&session))
+            .map(|s| s.cancelled)
+            .unwrap_or(false);
+        let code = status.ok().and_then(|s| s.code());
+        let outcome = match (cancelled, code) {
+            (true, _) => "cancelled".to_string(),
+            (false, Some(code)) => code.to_string(),
+            (false, None) => "killed".to_string(),
+        };
+        info!("[Console] {} ended: {}", session, outcome);
+        events::emit(ManagerEvent::ConsoleExit {
+            session,
+            code,
+            cancelled,
+        });
+        if let Err(e) = record_audit("console-exit", &audited, Some(outcome)) {
+            warn!("[Console] {}", e);
+        }
+    });
+
+    Ok(ConsoleSession {
+        id,
+        command: command_line,
+        pid,
+    })
+}
+
+fn session_stdin(session_id: &str) -> Result<Arc<Mutex<Option<ChildStdin>>>, String> {
+    sessions()
+        .lock()
+        .map_err(|_| "Console state is unavailable".to_string())?
+        .get(session_id)
+        .map(|s| s.stdin.clone())
+        .ok_or_else(|| format!("Console command {} is not running", session_id))
+}
+
+/// Write to the stdin of a running console command (add "\n" to submit a line)
+#[command]
+#[specta::specta]
+pub async fn write_console_input(session_id: String, input: String) -> Result<(), String> {
+    if input.len() > console::MAX_INPUT_BYTES {
+        return Err(format!(
+            "Input is too long (at most {} bytes at once)",
+            console::MAX_INPUT_BYTES
+        ));
+    }
+    let stdin = session_stdin(&session_id)?;
+    let mut stdin = stdin
+        .lock()
+        .map_err(|_| "Console input is unavailable".to_string())?;
+    let pipe = stdin
+        .as_mut()
+        .ok_or_else(|| format!("Input of {} is closed", session_id))?;
+    pipe.write_all(input.as_bytes())
+        .and_then(|_| pipe.flush())
+        .map_err(|e| format!("Failed to write to {}: {}", session_id, e))
+}
+
+/// Close the stdin of a running console command (end of input, like Ctrl+D)
+#[command]
+#[specta::specta]
+pub async fn close_console_input(session_id: String) -> Result<(), String> {
+    let stdin = session_stdin(&session_id)?;
+    if let Ok(mut stdin) = stdin.lock() {
+        stdin.take();
+    }
+    Ok(())
+}
+
+/// Kill a running console command and everything it started
+#[command]
+#[specta::specta]
+pub async fn cancel_console_command(session_id: String) -> Result<String, String> {
+    let pid = {
+        let mut sessions = sessions()
+            .lock()
+            .map_err(|_| "Console state is unavailable".to_string())?;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Console command {} is not running", session_id))?;
+        session.cancelled = true;
+        session.pid
+    };
+    info!("[Console] Cancelling {}", session_id);
+    let processes = process::list_processes().unwrap_or_default();
+    process::kill_process_tree(&processes, pid)
+}
+
+/// Audit log entries (console commands, advanced mode changes), newest first
+#[command]
+#[specta::specta]
+pub async fn get_audit_log(limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let mut entries = read_audit_log();
+    entries.reverse();
+    entries.truncate(limit.unwrap_or(200));
+    Ok(entries)
+}
@@ -0,0 +1,161 @@
+use crate::commands::config::{load_manager_config, read_pairing_requests, save_manager_config};
+use crate::commands::locks::{self, ConfigResource};
+use crate::utils::contacts::{self, Contact};
+use crate::utils::{file, platform};
+use log::{info, warn};
+use tauri::command;
+
+/// Address book (manager.json -> contacts); empty when it cannot be read
+pub(crate) fn load_contacts() -> Vec<Contact> {
+    load_manager_config()
+        .ok()
+        .and_then(|m| m.get(contacts::SETTINGS_KEY).cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn save_contacts(list: &[Contact]) -> Result<(), String> {
+    let mut manager_config = load_manager_config()?;
+    manager_config[contacts::SETTINGS_KEY] =
+        serde_json::to_value(list).map_err(|e| format!("Failed to serialize contacts: {}", e))?;
+    save_manager_config(&manager_config)
+}
+
+/// Peer id for a contact name (or a peer id, returned as is) on `channel`
+pub(crate) fn resolve_peer(channel: &str, text: &str) -> String {
+    contacts::resolve_peer(&load_contacts(), channel, text)
+}
+
+/// Peers seen in the session stores of every agent and in pending pairing requests
+fn recent_peers() -> Vec<Contact> {
+    let agents_dir = platform::join_path(platform::get_config_dir(), &["agents"]);
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(&agents_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let store_path = entry.path().join("sessions").join("sessions.json");
+        let Ok(content) = file::read_file(&platform::path_string(&store_path)) else {
+            continue;
+        };
+        match serde_json::from_str(&content) {
+            Ok(store) => found.extend(contacts::from_session_store(&store)),
+            Err(e) => warn!("[Contacts] Failed to parse {}: {}", store_path.display(), e),
+        }
+    }
+    for request in read_pairing_requests() {
+        let Some(peer_id) = request.sender_id.filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        found.push(Contact {
+            id: contacts::contact_id(&request.channel, &peer_id),
+            name: contacts::pairing_name(request.meta.as_ref()).unwrap_or_else(|| peer_id.clone()),
+            kind: "direct".to_string(),
+            account_id: None,
+            notes: None,
+            source: "pairing".to_string(),
+            last_seen_at: request.last_seen_at.or(request.created_at),
+            channel: request.channel,
+            peer_id,
+        });
+    }
+    found
+}
+
+/// List the address book, optionally only one channel's contacts
+#[command]
+#[specta::specta]
+pub async fn list_contacts(channel: Option<String>) -> Result<Vec<Contact>, String> {
+    let mut list = load_contacts();
+    if let Some(channel) = channel.filter(|c| !c.is_empty()) {
+        list.retain(|c| c.channel.eq_ignore_ascii_case(&channel));
+    }
+    list.sort_by_key(|c| (c.channel.clone(), c.name.to_lowercase()));
+    Ok(list)
+}
+
+/// Add or update a contact (matched by channel and peer id)
+#[command]
+#[specta::specta]
+pub async fn save_contact(contact: Contact) -> Result<Contact, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_contact").await;
+    let contact = contacts::normalize(contact)?;
+    let mut list = load_contacts();
+    if let Some(other) = contacts::name_conflict(&list, &contact) {
+        return Err(format!(
+            "'{}' is already the name of {} on {}",
+            contact.name, other.peer_id, other.channel
+        ));
+    }
+    info!(
+        "[Contacts] Saving contact {} ({})",
+        contact.name, contact.id
+    );
+    match list.iter_mut().find(|c| c.id == contact.id) {
+        Some(existing) => *existing = contact.clone(),
+        None => list.push(contact.clone()),
+    }
+    save_contacts(&list)?;
+    Ok(contact)
+}
+
+/// Delete a contact by id ("<channel>:<peerId>")
+#[command]
+#[specta::specta]
+pub async fn delete_contact(id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "delete_contact").await;
+    let mut list = load_contacts();
+    let before = list.len();
+    list.retain(|c| c.id != id);
+    if list.len() == before {
+        return Err(format!("Contact {} not found", id));
+    }
+    info!("[Contacts] Deleting contact {}", id);
+    save_contacts(&list)?;
+    Ok(format!("Contact {} deleted", id))
+}
+
+/// Peers from recent gateway activity (session stores, pairing requests) that are not in the
+/// address book yet
+#[command]
+#[specta::specta]
+pub async fn suggest_contacts() -> Result<Vec<Contact>, String> {
+    let suggestions = contacts::suggestions(&load_contacts(), recent_peers());
+    info!(
+        "[Contacts] {} peer(s) from recent activity not in the address book",
+        suggestions.len()
+    );
+    Ok(suggestions)
+}
+
+/// Add suggested (or any) contacts in one go; ones already in the address book and names
+/// taken on their channel are skipped. Returns the contacts added.
+#[command]
+#[specta::specta]
+pub async fn import_contacts(contacts: Vec<Contact>) -> Result<Vec<Contact>, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "import_contacts").await;
+    let mut list = load_contacts();
+    let mut added = Vec::new();
+    for contact in contacts {
+        let contact = match contacts::normalize(contact) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("[Contacts] Skipping import: {}", e);
+                continue;
+            }
+        };
+        if list.iter().any(|c| c.id == contact.id)
+            || contacts::name_conflict(&list, &contact).is_some()
+        {
+            continue;
+        }
+        list.push(contact.clone());
+        added.push(contact);
+    }
+    if !added.is_empty() {
+        save_contacts(&list)?;
+    }
+    info!("[Contacts] Imported {} contact(s)", added.len());
+    Ok(added)
+}
@@ -0,0 +1,271 @@
+use crate::commands::config::load_manager_config;
+use crate::utils::crash_report::{self, CrashReport, RecentLines, RunMarker};
+use crate::utils::{file, log_sanitizer, platform, shell};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+/// Environment variable pointing the Manager at a crash report collector (overrides
+/// crashReports.endpoint in manager.json)
+const CRASH_ENDPOINT_ENV: &str = "OPENCLAW_MANAGER_CRASH_URL";
+
+/// Recent log lines, attached to a panic report
+static RECENT_LOG: Mutex<RecentLines> = Mutex::new(RecentLines::new(crash_report::LOG_TAIL_LINES));
+
+/// When this session started (RFC3339), recorded in its reports and run marker
+static SESSION_STARTED_AT: Mutex<Option<String>> = Mutex::new(None);
+
+fn get_reports_dir() -> PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["crash-reports"])
+}
+
+fn report_path(id: &str) -> PathBuf {
+    get_reports_dir().join(format!("{}.json", id))
+}
+
+fn get_run_marker_path() -> PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["running.json"])
+}
+
+/// Keep a (sanitized) log line for the next panic report; called by the logger
+pub fn record_log_line(line: &str) {
+    // try_lock: a panic while logging must not deadlock the panic hook
+    if let Ok(mut recent) = RECENT_LOG.try_lock() {
+        recent.push(line.to_string());
+    }
+}
+
+fn read_reports() -> Vec<CrashReport> {
+    let mut reports: Vec<CrashReport> = std::fs::read_dir(get_reports_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+        .filter_map(|e| {
+            let content = std::fs::read_to_string(e.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    reports
+}
+
+/// Write a report and prune old ones. Plain std::fs, as this also runs inside the panic hook.
+fn write_report(report: &CrashReport) -> std::io::Result<()> {
+    std::fs::create_dir_all(get_reports_dir())?;
+    let content = serde_json::to_string_pretty(report).map_err(std::io::Error::other)?;
+    std::fs::write(report_path(&report.id), content)?;
+    for id in crash_report::to_prune(&read_reports(), crash_report::MAX_REPORTS) {
+        let _ = std::fs::remove_file(report_path(&id));
+    }
+    Ok(())
+}
+
+/// Write a crash report for every panic, then let the default hook print it (in release
+/// builds the process aborts right after). Install before anything else runs.
+pub fn install_panic_hook() {
+    if let Ok(mut session) = SESSION_STARTED_AT.lock() {
+        *session = Some(chrono::Utc::now().to_rfc3339());
+    }
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let now = chrono::Local::now();
+        let report = CrashReport {
+            id: crash_report::report_id(now),
+            created_at: now.with_timezone(&chrono::Utc).to_rfc3339(),
+            manager_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: platform::get_os(),
+            arch: platform::get_arch(),
+            kind: "panic".to_string(),
+            message: log_sanitizer::sanitize(&crash_report::panic_message(panic_info.payload())),
+            location: panic_info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace: crash_report::backtrace_lines(
+                &std::backtrace::Backtrace::force_capture().to_string(),
+            ),
+            log_tail: RECENT_LOG
+                .try_lock()
+                .map(|recent| recent.snapshot())
+                .unwrap_or_default(),
+            session_started_at: SESSION_STARTED_AT.try_lock().ok().and_then(|s| s.clone()),
+            submitted_at: None,
+            dismissed: false,
+        };
+        if let Err(e) = write_report(&report) {
+            eprintln!("[Crash Reports] Failed to write crash report: {}", e);
+        }
+        default_hook(panic_info);
+    }));
+}
+
+/// Record an unclean-exit report if the previous session ended abruptly, then mark this
+/// session as running
+pub fn check_previous_run() {
+    let started_at = SESSION_STARTED_AT
+        .lock()
+        .ok()
+        .and_then(|s| s.clone())
+        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+
+    let marker_path = get_run_marker_path();
+    let previous: Option<RunMarker> = std::fs::read_to_string(&marker_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    if let Some(previous) = previous {
+        if crash_report::needs_unclean_exit_report(
+            &previous,
+            std::process::id(),
+            env!("CARGO_PKG_VERSION"),
+            &read_reports(),
+        ) {
+            let report = crash_report::unclean_exit_report(
+                &previous,
+                crash_report::report_id(chrono::Local::now()),
+                &started_at,
+                &platform::get_os(),
+                &platform::get_arch(),
+            );
+            warn!(
+                "[Crash Reports] The previous session (started {}) did not exit cleanly",
+                previous.started_at
+            );
+            if let Err(e) = write_report(&report) {
+                warn!("[Crash Reports] Failed to write crash report: {}", e);
+            }
+        }
+    }
+
+    let marker = RunMarker {
+        pid: std::process::id(),
+        started_at,
+        manager_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let written = serde_json::to_string(&marker)
+        .map_err(|e| e.to_string())
+        .and_then(|content| {
+            file::write_file(&platform::path_string(&marker_path), &content)
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = written {
+        warn!("[Crash Reports] Failed to write the run marker: {}", e);
+    }
+}
+
+/// Remove the run marker when the Manager exits normally
+pub fn mark_clean_exit() {
+    let _ = std::fs::remove_file(get_run_marker_path());
+}
+
+fn get_endpoint() -> Option<String> {
+    if let Ok(url) = std::env::var(CRASH_ENDPOINT_ENV) {
+        if !url.trim().is_empty() {
+            return Some(url.trim().to_string());
+        }
+    }
+    load_manager_config()
+        .ok()
+        .and_then(|c| {
+            c.pointer("/crashReports/endpoint")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        })
+        .filter(|s| !s.is_empty())
+}
+
+/// Crash reports offered to the user at launch
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportStatus {
+    /// Reports neither sent nor dismissed, newest first
+    pub pending: Vec<CrashReport>,
+    /// Whether reports can be sent (a collector endpoint is configured)
+    pub endpoint_configured: bool,
+    /// Where the reports are kept, to attach them to an issue by hand
+    pub reports_dir: String,
+}
+
+/// Crash reports waiting for the user to send or dismiss them
+#[command]
+#[specta::specta]
+pub async fn get_crash_report_status() -> Result<CrashReportStatus, String> {
+    Ok(CrashReportStatus {
+        pending: read_reports()
+            .into_iter()
+            .filter(|r| r.is_pending())
+            .collect(),
+        endpoint_configured: get_endpoint().is_some(),
+        reports_dir: platform::path_string(&get_reports_dir()),
+    })
+}
+
+/// Every kept crash report, newest first
+#[command]
+#[specta::specta]
+pub async fn list_crash_reports() -> Result<Vec<CrashReport>, String> {
+    Ok(read_reports())
+}
+
+fn read_report(id: &str) -> Result<CrashReport, String> {
+    if !crash_report::is_valid_id(id) {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    let content = file::read_file(&platform::path_string(&report_path(id)))
+        .map_err(|_| format!("Crash report {} not found", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Crash report {} is not valid: {}", id, e))
+}
+
+fn update_report(report: &CrashReport) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    file::write_file(&platform::path_string(&report_path(&report.id)), &content)
+        .map_err(|e| format!("Failed to write crash report: {}", e))
+}
+
+/// Send crash reports to the configured collector. Only ever called after the user agreed;
+/// nothing is sent automatically.
+#[command]
+#[specta::specta]
+pub async fn submit_crash_reports(ids: Vec<String>) -> Result<String, String> {
+    let endpoint = get_endpoint().ok_or_else(|| {
+        format!(
+            "No crash report endpoint is configured ({} or crashReports.endpoint in manager.json). The reports are kept in {}",
+            CRASH_ENDPOINT_ENV,
+            platform::path_string(&get_reports_dir())
+        )
+    })?;
+    let mut sent = 0;
+    for id in &ids {
+        let mut report = read_report(id)?;
+        let body = format!("@{}", platform::path_string(&report_path(id)));
+        let status = shell::curl_post_json(&endpoint, &body, 15)?;
+        if !status.starts_with('2') {
+            return Err(format!(
+                "Crash report endpoint returned HTTP {} ({} of {} sent)",
+                status,
+                sent,
+                ids.len()
+            ));
+        }
+        report.submitted_at = Some(chrono::Utc::now().to_rfc3339());
+        update_report(&report)?;
+        sent += 1;
+    }
+    info!("[Crash Reports] Sent {} report(s)", sent);
+    Ok(format!("Sent {} crash report(s)", sent))
+}
+
+/// Don't offer these crash reports again (they stay on disk until pruned)
+#[command]
+#[specta::specta]
+pub async fn dismiss_crash_reports(ids: Vec<String>) -> Result<String, String> {
+    for id in &ids {
+        let mut report = read_report(id)?;
+        report.dismissed = true;
+        update_report(&report)?;
+    }
+    Ok(format!("Dismissed {} crash report(s)", ids.len()))
+}
@@ -1,5 +1,10 @@
+use crate::commands::locks::{self, ConfigResource};
+use crate::events::{self, AlertLevel, ManagerEvent};
 use crate::models::{AITestResult, ChannelTestResult, DiagnosticResult, SystemInfo};
-use crate::utils::{log_sanitizer, platform, shell};
+use crate::utils::json_diff::{self, JsonChange};
+use crate::utils::task_pool;
+use crate::utils::{config_include, file, gateway_token, heartbeat, log_sanitizer, message_template, platform, shell, sync_conflict};
+use serde::{Deserialize, Serialize};
 use tauri::command;
 use log::{info, warn, debug};
 
@@ -30,7 +35,7 @@ fn strip_ansi_codes(input: &str) -> String {
 }
 
 /// Extract JSON content from mixed output
-fn extract_json_from_output(output: &str) -> Option<String> {
+pub(crate) fn extract_json_from_output(output: &str) -> Option<String> {
     // First strip ANSI color codes
     let clean_output = strip_ansi_codes(output);
 
@@ -83,6 +88,7 @@ fn extract_json_from_output(output: &str) -> Option<String> {
 
 /// Run diagnostics
 #[command]
+#[specta::specta]
 pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
     info!("[Diagnostics] Starting system diagnostics...");
     let mut results = Vec::new();
@@ -157,6 +163,43 @@ pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
         },
     });
 
+    // Check for cloud-sync conflict copies
+    let sync_report = collect_sync_conflicts();
+    if sync_report.sync_provider.is_some() || !sync_report.conflicts.is_empty() {
+        let provider = sync_report.sync_provider.clone().unwrap_or_else(|| "a sync service".to_string());
+        results.push(DiagnosticResult {
+            name: "Cloud Sync".to_string(),
+            passed: sync_report.conflicts.is_empty(),
+            message: if sync_report.conflicts.is_empty() {
+                format!("Config directory is synced by {}, no conflict copies found", provider)
+            } else {
+                format!("{} sync-conflict copies found in {}", sync_report.conflicts.len(), sync_report.config_dir)
+            },
+            suggestion: if sync_report.conflicts.is_empty() {
+                Some("Avoid running the gateway on two machines sharing this directory at the same time".to_string())
+            } else {
+                Some("Review the conflict copies and keep, replace or merge them".to_string())
+            },
+        });
+    }
+
+    // Flag channels open to everyone
+    let security_findings = collect_security_findings();
+    if !security_findings.is_empty() {
+        let unacknowledged = security_findings.iter().filter(|f| f.severity == "critical").count();
+        results.push(DiagnosticResult {
+            name: "Wildcard Access".to_string(),
+            passed: unacknowledged == 0,
+            message: format!(
+                "{} account(s) accept messages from anyone ({} not acknowledged): {}",
+                security_findings.len(),
+                unacknowledged,
+                security_findings.iter().map(|f| f.location.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+            suggestion: Some("Use an allowlist or pairing DM policy unless the bot is meant to be public".to_string()),
+        });
+    }
+
     // Run openclaw doctor
     if openclaw_installed {
         let doctor_result = shell::run_openclaw(&["doctor"]);
@@ -173,6 +216,7 @@ pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
 
 /// Test AI connection
 #[command]
+#[specta::specta]
 pub async fn test_ai_connection() -> Result<AITestResult, String> {
     info!("[AI Test] Starting AI connection test...");
 
@@ -226,6 +270,353 @@ pub async fn test_ai_connection() -> Result<AITestResult, String> {
     }
 }
 
+/// Test a Gemini (AI Studio) key with a one-line generateContent request
+#[command]
+#[specta::specta]
+pub async fn test_gemini_connection(api_key: Option<String>, model: String) -> Result<AITestResult, String> {
+    info!("[AI Test] Testing Gemini model {}...", model);
+    let key = crate::commands::config::resolve_gemini_key(api_key)?;
+    let body = serde_json::json!({
+        "contents": [{ "parts": [{ "text": "Reply with the single word OK" }] }]
+    });
+    let start = std::time::Instant::now();
+    let result = crate::commands::config::gemini_api_call(&key, &format!("models/{}:generateContent", model), Some(&body));
+    let latency = start.elapsed().as_millis() as u64;
+
+    let result = result.and_then(|json| {
+        json.pointer("/candidates/0/content/parts/0/text")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .ok_or_else(|| "Gemini returned no text (the model may not support chat)".to_string())
+    });
+    Ok(ai_test_result("google", &model, result, latency))
+}
+
+/// Test Vertex AI with the stored service account by running a completion through the core
+#[command]
+#[specta::specta]
+pub async fn test_vertex_connection(model: String) -> Result<AITestResult, String> {
+    info!("[AI Test] Testing Vertex AI model {}...", model);
+    let env_path = platform::get_env_file_path();
+    let env: Vec<(String, String)> = ["GOOGLE_APPLICATION_CREDENTIALS", "GOOGLE_CLOUD_PROJECT", "GOOGLE_CLOUD_LOCATION"]
+        .iter()
+        .filter_map(|key| crate::utils::file::read_env_value(&env_path, key).map(|v| (key.to_string(), v)))
+        .collect();
+    if !env.iter().any(|(k, _)| k == "GOOGLE_APPLICATION_CREDENTIALS") {
+        return Err("No Vertex AI service account configured, upload a key first".to_string());
+    }
+
+    let mut config = crate::commands::config::load_openclaw_config()?;
+    crate::commands::config::apply_primary_model(&mut config, &format!("google-vertex/{}", model));
+    let start = std::time::Instant::now();
+    let result = crate::commands::config::run_model_probe_with_env(&config, &env);
+    let latency = start.elapsed().as_millis() as u64;
+    Ok(ai_test_result("google-vertex", &model, result, latency))
+}
+
+/// Test configured providers in parallel (all of them, or only `providers`), at most
+/// `concurrency` at a time (default 3): a one-shot completion against each provider's first
+/// model, with `task-progress` events per provider
+#[command]
+#[specta::specta]
+pub async fn test_providers(providers: Option<Vec<String>>, concurrency: Option<usize>) -> Result<Vec<AITestResult>, String> {
+    let config = crate::commands::config::load_openclaw_config()?;
+    let configured = config
+        .pointer("/models/providers")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    if let Some(missing) = providers.iter().flatten().find(|p| !configured.contains_key(p.as_str())) {
+        return Err(format!("Provider {} is not configured", missing));
+    }
+    let items: Vec<(String, (String, Option<String>))> = configured
+        .iter()
+        .filter(|(name, _)| providers.as_ref().map(|p| p.contains(name)).unwrap_or(true))
+        .map(|(name, provider)| {
+            let model = provider.pointer("/models/0/id").and_then(|v| v.as_str()).map(String::from);
+            (name.clone(), (name.clone(), model))
+        })
+        .collect();
+    if items.is_empty() {
+        return Err("No providers configured".to_string());
+    }
+    let workers = task_pool::workers_for(concurrency, items.len());
+    info!("[AI Test] Testing {} provider(s), {} at a time", items.len(), workers);
+
+    let tests: Vec<(String, Option<String>)> = items.iter().map(|(_, test)| test.clone()).collect();
+    let results = task_pool::run(
+        items,
+        workers,
+        |(provider, model)| {
+            let model = model.ok_or_else(|| format!("Provider {} has no models", provider))?;
+            let mut probe = config.clone();
+            crate::commands::config::apply_primary_model(&mut probe, &format!("{}/{}", provider, model));
+            let probe = crate::utils::placeholders::to_native(&probe).unwrap_or(probe);
+            let start = std::time::Instant::now();
+            let result = crate::commands::config::run_model_probe(&probe);
+            let test = ai_test_result(&provider, &model, result, start.elapsed().as_millis() as u64);
+            match &test.error {
+                Some(e) if !test.success => Err(e.clone()),
+                _ => Ok(test),
+            }
+        },
+        |progress| events::emit(ManagerEvent::item_progress("test-providers", &progress)),
+    );
+    Ok(tests
+        .into_iter()
+        .zip(results)
+        .map(|((provider, model), result)| {
+            result.unwrap_or_else(|e| AITestResult {
+                success: false,
+                provider,
+                model: model.unwrap_or_default(),
+                response: None,
+                error: Some(e),
+                latency_ms: None,
+            })
+        })
+        .collect())
+}
+
+/// Result of embedding a sample string
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct EmbeddingTestResult {
+    pub success: bool,
+    pub provider: String,
+    pub model: String,
+    /// Vector length reported by the model
+    pub dimensions: Option<usize>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// POST an OpenAI-style /embeddings request and return the vector length
+fn openai_embedding_dimensions(base_url: &str, api_key: &str, model: &str, input: &str) -> Result<usize, String> {
+    let url = format!("{}/embeddings", base_url.trim_end_matches('/'));
+    let body = serde_json::json!({ "model": model, "input": input });
+    let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args(["-s", "--max-time", "30", "-X", "POST", "-H", "Content-Type: application/json"]);
+    let _headers = if api_key.is_empty() {
+        None
+    } else {
+        Some(shell::curl_secret_headers(&mut cmd, &[format!("Authorization: Bearer {}", api_key)])?)
+    };
+    cmd.args(["-d", &body.to_string(), &url]);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|_| format!("{} returned an invalid response", url))?;
+    if let Some(message) = json.pointer("/error/message").and_then(|v| v.as_str()) {
+        return Err(message.to_string());
+    }
+    json.pointer("/data/0/embedding")
+        .and_then(|v| v.as_array())
+        .map(|v| v.len())
+        .ok_or_else(|| "Response contained no embedding".to_string())
+}
+
+/// Embed a sample string with the configured memorySearch provider and report the dimensionality
+#[command]
+#[specta::specta]
+pub async fn test_embeddings(sample: Option<String>) -> Result<EmbeddingTestResult, String> {
+    let config = crate::commands::config::load_openclaw_config()?;
+    let get = |pointer: &str| config.pointer(pointer).and_then(|v| v.as_str()).map(String::from);
+    let memory = crate::commands::config::memory_search(&config);
+    let get_memory = |key: &str| memory.and_then(|m| m.pointer(key)).and_then(|v| v.as_str()).map(String::from);
+    let provider = get_memory("/provider").unwrap_or_else(|| "openai".to_string());
+    let model = get_memory("/model")
+        .or_else(|| crate::commands::config::default_embedding_model(&provider).map(String::from))
+        .unwrap_or_default();
+    let remote_key = get_memory("/remote/apiKey");
+    let sample = sample.filter(|s| !s.trim().is_empty()).unwrap_or_else(|| "OpenClaw embeddings test".to_string());
+    info!("[Embeddings Test] Embedding sample with {}/{}", provider, model);
+
+    let start = std::time::Instant::now();
+    let result = match provider.as_str() {
+        "gemini" => crate::commands::config::resolve_gemini_key(remote_key).and_then(|key| {
+            let body = serde_json::json!({ "content": { "parts": [{ "text": sample }] } });
+            crate::commands::config::gemini_api_call(&key, &format!("models/{}:embedContent", model), Some(&body))?
+                .pointer("/embedding/values")
+                .and_then(|v| v.as_array())
+                .map(|v| v.len())
+                .ok_or_else(|| "Response contained no embedding".to_string())
+        }),
+        "openai" => {
+            let base_url = get_memory("/remote/baseUrl")
+                .or_else(|| get("/models/providers/openai/baseUrl"))
+                .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+            let key = remote_key
+                .or_else(|| get("/models/providers/openai/apiKey"))
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+                .or_else(|| crate::utils::file::read_env_value(&platform::get_env_file_path(), "OPENAI_API_KEY"))
+                .unwrap_or_default();
+            openai_embedding_dimensions(&base_url, &key, &model, &sample)
+        }
+        "local" => Err("Local embeddings run inside the gateway and cannot be tested from the Manager".to_string()),
+        other => Err(format!("Unknown embeddings provider '{}'", other)),
+    };
+    let latency_ms = Some(start.elapsed().as_millis() as u64);
+
+    Ok(match result {
+        Ok(dimensions) => {
+            info!("[Embeddings Test] ✓ {} dimensions", dimensions);
+            EmbeddingTestResult { success: true, provider, model, dimensions: Some(dimensions), latency_ms, error: None }
+        }
+        Err(e) => {
+            let e = log_sanitizer::sanitize(&e);
+            warn!("[Embeddings Test] ✗ {}", e);
+            EmbeddingTestResult { success: false, provider, model, dimensions: None, latency_ms, error: Some(e) }
+        }
+    })
+}
+
+/// Result of synthesizing a test phrase
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TtsTestResult {
+    pub success: bool,
+    pub provider: String,
+    /// Temporary audio file that the UI can play
+    pub audio_path: Option<String>,
+    pub bytes: Option<u64>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// POST a JSON body via curl, writing the response body to `out_path`; returns the HTTP status
+fn curl_post_to_file(url: &str, headers: &[String], body: &serde_json::Value, out_path: &std::path::Path) -> Result<u16, String> {
+    let mut cmd = std::process::Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args(["-s", "--max-time", "60", "-X", "POST", "-H", "Content-Type: application/json"]);
+    let _headers = shell::curl_secret_headers(&mut cmd, headers)?;
+    cmd.args(["-d", &body.to_string(), "-w", "%{http_code}", "-o"]).arg(out_path).arg(url);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run curl: {}", e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("No response from {}", url))
+}
+
+/// Synthesize a short phrase with the configured TTS provider and return the audio file path
+#[command]
+#[specta::specta]
+pub async fn test_tts(text: Option<String>) -> Result<TtsTestResult, String> {
+    let voice = crate::commands::config::get_voice_config().await?;
+    let config = crate::commands::config::load_openclaw_config()?;
+    let provider = voice.tts_provider.clone().ok_or("No TTS provider configured")?;
+    let text = text.filter(|t| !t.trim().is_empty()).unwrap_or_else(|| "Hello from OpenClaw".to_string());
+    let stored_key = config
+        .pointer(&format!("/messages/tts/{}/apiKey", provider))
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let env_key = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .or_else(|| crate::utils::file::read_env_value(&platform::get_env_file_path(), var))
+            .filter(|k| !k.is_empty())
+    };
+    info!("[TTS Test] Synthesizing test phrase with {}", provider);
+
+    let out_path = std::env::temp_dir().join(format!("openclaw-tts-test-{}.mp3", chrono::Utc::now().timestamp_millis()));
+    let start = std::time::Instant::now();
+    let result = match provider.as_str() {
+        "openai" => stored_key
+            .or_else(|| config.pointer("/models/providers/openai/apiKey").and_then(|v| v.as_str()).map(String::from))
+            .or_else(|| env_key("OPENAI_API_KEY"))
+            .ok_or_else(|| "No OpenAI API key for TTS".to_string())
+            .and_then(|key| {
+                let body = serde_json::json!({
+                    "model": voice.tts_model.as_deref().unwrap_or("gpt-4o-mini-tts"),
+                    "voice": voice.tts_voice.as_deref().unwrap_or("alloy"),
+                    "input": text,
+                    "response_format": "mp3",
+                });
+                let headers = [format!("Authorization: Bearer {}", key)];
+                curl_post_to_file("https://api.openai.com/v1/audio/speech", &headers, &body, &out_path)
+            }),
+        "elevenlabs" => stored_key
+            .or_else(|| env_key("ELEVENLABS_API_KEY"))
+            .ok_or_else(|| "No ElevenLabs API key for TTS".to_string())
+            .and_then(|key| {
+                let voice_id = voice.tts_voice.clone().ok_or("Set an ElevenLabs voice id first")?;
+                let body = serde_json::json!({
+                    "text": text,
+                    "model_id": voice.tts_model.as_deref().unwrap_or("eleven_multilingual_v2"),
+                });
+                let headers = [format!("xi-api-key: {}", key)];
+                let url = format!("https://api.elevenlabs.io/v1/text-to-speech/{}", voice_id);
+                curl_post_to_file(&url, &headers, &body, &out_path)
+            }),
+        "edge" => Err("Edge TTS runs inside the gateway and cannot be tested from the Manager".to_string()),
+        other => Err(format!("Unknown TTS provider '{}'", other)),
+    };
+    let latency_ms = Some(start.elapsed().as_millis() as u64);
+
+    // Error responses are JSON bodies written to the output file
+    let result = result.and_then(|status| {
+        if (200..300).contains(&status) {
+            return Ok(());
+        }
+        let body = std::fs::read_to_string(&out_path).unwrap_or_default();
+        let _ = std::fs::remove_file(&out_path);
+        Err(format!("HTTP {}: {}", status, body.trim()))
+    });
+    Ok(match result {
+        Ok(()) => {
+            let bytes = std::fs::metadata(&out_path).map(|m| m.len()).ok();
+            info!("[TTS Test] ✓ Audio written to {}", out_path.display());
+            TtsTestResult {
+                success: true,
+                provider,
+                audio_path: Some(platform::path_string(&out_path)),
+                bytes,
+                latency_ms,
+                error: None,
+            }
+        }
+        Err(e) => {
+            let e = log_sanitizer::sanitize(&e);
+            warn!("[TTS Test] ✗ {}", e);
+            TtsTestResult { success: false, provider, audio_path: None, bytes: None, latency_ms, error: Some(e) }
+        }
+    })
+}
+
+fn ai_test_result(provider: &str, model: &str, result: Result<String, String>, latency_ms: u64) -> AITestResult {
+    match result {
+        Ok(reply) => AITestResult {
+            success: true,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            response: Some(reply),
+            error: None,
+            latency_ms: Some(latency_ms),
+        },
+        Err(e) => {
+            warn!("[AI Test] ✗ {}/{} failed: {}", provider, model, log_sanitizer::sanitize(&e));
+            AITestResult {
+                success: false,
+                provider: provider.to_string(),
+                model: model.to_string(),
+                response: None,
+                error: Some(log_sanitizer::sanitize(&e)),
+                latency_ms: Some(latency_ms),
+            }
+        }
+    }
+}
+
 /// Get channel test target
 fn get_channel_test_target(channel_type: &str) -> Option<String> {
     let env_path = platform::get_env_file_path();
@@ -283,6 +674,7 @@ fn parse_channel_status_text(output: &str, channel_type: &str) -> Option<(bool,
 
 /// Test channel connection (check status and send test message)
 #[command]
+#[specta::specta]
 pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, String> {
     info!("[Channel Test] Testing channel: {}", channel_type);
     let channel_lower = channel_type.to_lowercase();
@@ -479,18 +871,29 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
     }
 }
 
-/// Send test message to channel
+/// Send a test message, rendered from a message template (the built-in "default" if none given)
 #[command]
-pub async fn send_test_message(channel_type: String, target: String) -> Result<ChannelTestResult, String> {
-    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-    let message = format!("🤖 OpenClaw Test Message\n\n✅ Connection successful!\n⏰ {}", timestamp);
+#[specta::specta]
+pub async fn send_test_message(
+    channel_type: String,
+    target: String,
+    template_id: Option<String>,
+) -> Result<ChannelTestResult, String> {
+    let template = find_message_template(template_id.as_deref().unwrap_or(DEFAULT_TEMPLATE_ID))?;
+    let message = render_message_template(&template.body, &channel_type);
+    // The target can be a contact name from the address book
+    let target = crate::commands::contacts::resolve_peer(&channel_type, &target);
+    Ok(send_channel_message(&channel_type, &target, &message))
+}
 
+/// Send a message via `openclaw message send` and report whether it was delivered
+fn send_channel_message(channel_type: &str, target: &str, message: &str) -> ChannelTestResult {
     // Use openclaw message send command to send test message
     let send_result = shell::run_openclaw(&[
         "message", "send",
-        "--channel", &channel_type,
-        "--target", &target,
-        "--message", &message,
+        "--channel", channel_type,
+        "--target", target,
+        "--message", message,
         "--json"
     ]);
 
@@ -510,24 +913,32 @@ pub async fn send_test_message(channel_type: String, target: String) -> Result<C
                 !output.to_lowercase().contains("error") && !output.to_lowercase().contains("failed")
             };
 
-            Ok(ChannelTestResult {
+            ChannelTestResult {
                 success,
-                channel: channel_type,
+                channel: channel_type.to_string(),
                 message: if success { "Message sent".to_string() } else { "Message send failed".to_string() },
                 error: if success { None } else { Some(output) },
-            })
+            }
         }
-        Err(e) => Ok(ChannelTestResult {
+        Err(e) => ChannelTestResult {
             success: false,
-            channel: channel_type,
+            channel: channel_type.to_string(),
             message: "Send failed".to_string(),
             error: Some(e),
-        }),
+        },
     }
 }
 
+/// Whether commands are served from fixtures (`--mock` / OPENCLAW_MANAGER_MOCK)
+#[command]
+#[specta::specta]
+pub async fn get_mock_mode() -> Result<bool, String> {
+    Ok(shell::is_mock())
+}
+
 /// Get system information
 #[command]
+#[specta::specta]
 pub async fn get_system_info() -> Result<SystemInfo, String> {
     info!("[System Info] Getting system information...");
     let os = platform::get_os();
@@ -558,6 +969,8 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
         os,
         os_version,
         arch,
+        native_arch: platform::get_native_arch(),
+        translated: platform::is_translated(),
         openclaw_installed,
         openclaw_version,
         node_version,
@@ -567,6 +980,7 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
 
 /// Start channel login (e.g., WhatsApp QR code scan)
 #[command]
+#[specta::specta]
 pub async fn start_channel_login(channel_type: String) -> Result<String, String> {
     info!("[Channel Login] Starting channel login flow: {}", channel_type);
 
@@ -663,15 +1077,11 @@ read -p "Press Enter to close this window..."
                     .map_err(|e| format!("Failed to create script: {}", e))?;
 
                 // Set executable permission
-                std::process::Command::new("chmod")
-                    .args(["+x", script_path])
-                    .output()
+                shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
                     .map_err(|e| format!("Failed to set permission: {}", e))?;
 
                 // Use open command to open .command file (will automatically execute in new terminal window)
-                std::process::Command::new("open")
-                    .arg(script_path)
-                    .spawn()
+                shell::spawn(std::process::Command::new("open").arg(script_path))
                     .map_err(|e| format!("Failed to launch terminal: {}", e))?;
             }
 
@@ -696,9 +1106,7 @@ read -p "Press Enter to close..."
                 std::fs::write(script_path, &script_content)
                     .map_err(|e| format!("Failed to create script: {}", e))?;
 
-                std::process::Command::new("chmod")
-                    .args(["+x", script_path])
-                    .output()
+                shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
                     .map_err(|e| format!("Failed to set permission: {}", e))?;
 
                 // Try different terminal emulators
@@ -706,9 +1114,7 @@ read -p "Press Enter to close..."
                 let mut launched = false;
 
                 for term in terminals {
-                    let result = std::process::Command::new(term)
-                        .args(["--", script_path])
-                        .spawn();
+                    let result = shell::spawn(std::process::Command::new(term).args(["--", script_path]));
 
                     if result.is_ok() {
                         launched = true;
@@ -732,3 +1138,1232 @@ read -p "Press Enter to close..."
         _ => Err(format!("Login wizard not supported for {}", channel_type)),
     }
 }
+
+// ============ PATH Repair ============
+
+/// Marker written next to PATH changes so they can be found (and not duplicated)
+const PATH_FIX_MARKER: &str = "# Added by OpenClaw Manager";
+
+/// A proposed PATH fix with the exact change it will make
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PathFixOption {
+    /// "zprofile" | "bash_profile" | "windows_user_path"
+    pub id: String,
+    pub description: String,
+    /// File or registry location that will be modified
+    pub target: String,
+    /// Exact text that will be appended / the new value that will be written
+    pub preview: String,
+}
+
+/// Where openclaw/node were found compared to what GUI apps and login shells see
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PathDiagnosis {
+    pub openclaw_path: Option<String>,
+    pub node_path: Option<String>,
+    /// PATH of the Manager process (what GUI apps inherit)
+    pub process_path: Vec<String>,
+    /// PATH of a login shell (Unix) or the user PATH from the registry (Windows)
+    pub login_path: Option<Vec<String>>,
+    /// Directories that hold openclaw/node but are missing from `login_path`
+    pub missing_dirs: Vec<String>,
+    pub fixes: Vec<PathFixOption>,
+}
+
+fn path_separator() -> char {
+    if platform::is_windows() { ';' } else { ':' }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split(path_separator())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect()
+}
+
+fn parent_dir(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Locate the node binary the Manager would use
+fn find_node_binary() -> Option<String> {
+    if platform::is_windows() {
+        if let Ok(out) = shell::run_cmd_output("where node") {
+            if let Some(first) = out.lines().next().filter(|l| !l.trim().is_empty()) {
+                return Some(first.trim().to_string());
+            }
+        }
+        let default = "C:\\Program Files\\nodejs\\node.exe";
+        return std::path::Path::new(default).exists().then(|| default.to_string());
+    }
+
+    // Same search order as the gateway launcher
+    shell::get_extended_path()
+        .split(':')
+        .map(|dir| format!("{}/node", dir))
+        .find(|candidate| std::path::Path::new(candidate).exists())
+}
+
+/// Persisted user PATH exactly as stored, `%VAR%` references left unexpanded
+const WINDOWS_USER_PATH_READ: &str = "$ErrorActionPreference = 'Stop'; \
+    (Get-Item -Path 'HKCU:\\Environment').GetValue('Path', '', 'DoNotExpandEnvironmentNames')";
+
+/// Re-reads the raw user PATH, appends the missing entries from OPENCLAW_ADD_PATH and writes it
+/// back as REG_EXPAND_SZ, so `%VAR%` entries keep working. The throwaway variable broadcasts
+/// WM_SETTINGCHANGE so new Explorer-launched processes see the change.
+const WINDOWS_USER_PATH_APPEND: &str = r#"$ErrorActionPreference = 'Stop'
+$key = [Microsoft.Win32.Registry]::CurrentUser.OpenSubKey('Environment', $true)
+$current = $key.GetValue('Path', '', [Microsoft.Win32.RegistryValueOptions]::DoNotExpandEnvironmentNames)
+$entries = @($current -split ';' | Where-Object { $_ -ne '' })
+foreach ($dir in ($env:OPENCLAW_ADD_PATH -split ';')) {
+    if ($dir -and ($entries -notcontains $dir)) { $entries += $dir }
+}
+$key.SetValue('Path', ($entries -join ';'), [Microsoft.Win32.RegistryValueKind]::ExpandString)
+$key.Close()
+[Environment]::SetEnvironmentVariable('OPENCLAW_PATH_REFRESH', '1', 'User')
+[Environment]::SetEnvironmentVariable('OPENCLAW_PATH_REFRESH', $null, 'User')"#;
+
+/// Expand `%VAR%` references in a Windows PATH entry; unknown variables are left as-is
+fn expand_windows_vars(entry: &str) -> String {
+    let mut result = String::new();
+    let mut rest = entry;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else { break };
+        let name = &rest[start + 1..start + 1 + len];
+        result.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => result.push_str(&value),
+            _ => result.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// PATH seen by a login shell (Unix) or the raw persisted user PATH (Windows).
+/// None when it couldn't be read, which is different from an empty PATH.
+fn get_login_path() -> Option<String> {
+    if platform::is_windows() {
+        return shell::run_powershell_output(WINDOWS_USER_PATH_READ).ok();
+    }
+
+    let shell_bin = std::env::var("SHELL").unwrap_or_else(|_| {
+        if platform::is_macos() { "/bin/zsh".to_string() } else { "/bin/bash".to_string() }
+    });
+    let output = shell::output(std::process::Command::new(&shell_bin).args(["-l", "-c", "echo \"$PATH\""]))
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .last()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn build_path_fixes(missing_dirs: &[String], login_path: Option<&str>) -> Vec<PathFixOption> {
+    if missing_dirs.is_empty() {
+        return Vec::new();
+    }
+
+    if platform::is_windows() {
+        // Writing without the current value would replace the whole user PATH
+        let Some(current) = login_path else { return Vec::new() };
+        let mut entries = split_path(current);
+        entries.extend(missing_dirs.iter().cloned());
+        return vec![PathFixOption {
+            id: "windows_user_path".to_string(),
+            description: "Append the directories to your Windows user PATH".to_string(),
+            target: "HKEY_CURRENT_USER\\Environment\\Path".to_string(),
+            preview: entries.join(";"),
+        }];
+    }
+
+    let home = dirs::home_dir().map(|h| h.display().to_string()).unwrap_or_else(|| "~".to_string());
+    let snippet = format!("\n{}\nexport PATH=\"{}:$PATH\"\n", PATH_FIX_MARKER, missing_dirs.join(":"));
+    vec![
+        PathFixOption {
+            id: "zprofile".to_string(),
+            description: "Add to ~/.zprofile (zsh login shells, macOS default)".to_string(),
+            target: format!("{}/.zprofile", home),
+            preview: snippet.clone(),
+        },
+        PathFixOption {
+            id: "bash_profile".to_string(),
+            description: "Add to ~/.bash_profile (bash login shells)".to_string(),
+            target: format!("{}/.bash_profile", home),
+            preview: snippet,
+        },
+    ]
+}
+
+fn collect_path_diagnosis() -> PathDiagnosis {
+    let openclaw_path = shell::get_openclaw_path().filter(|p| p != "openclaw");
+    let node_path = find_node_binary();
+    let process_path = split_path(&std::env::var("PATH").unwrap_or_default());
+    let login_path_raw = get_login_path();
+    let login_path = login_path_raw.as_deref().map(|path| {
+        let entries = split_path(path);
+        if platform::is_windows() {
+            entries.iter().map(|e| expand_windows_vars(e)).collect()
+        } else {
+            entries
+        }
+    });
+
+    let mut missing_dirs = Vec::new();
+    for dir in [&openclaw_path, &node_path].into_iter().flatten().filter_map(|p| parent_dir(p)) {
+        let present = login_path
+            .as_ref()
+            .map(|entries| {
+                entries.iter().any(|e| {
+                    // Windows paths are case-insensitive
+                    if platform::is_windows() { e.eq_ignore_ascii_case(&dir) } else { *e == dir }
+                })
+            })
+            .unwrap_or(false);
+        if !present && !missing_dirs.contains(&dir) {
+            missing_dirs.push(dir);
+        }
+    }
+
+    PathDiagnosis {
+        fixes: build_path_fixes(&missing_dirs, login_path_raw.as_deref()),
+        openclaw_path,
+        node_path,
+        process_path,
+        login_path,
+        missing_dirs,
+    }
+}
+
+/// Show where openclaw/node were found vs. the login-shell PATH, with fix previews
+#[command]
+#[specta::specta]
+pub async fn diagnose_path() -> Result<PathDiagnosis, String> {
+    info!("[PATH] Diagnosing PATH...");
+    let diagnosis = collect_path_diagnosis();
+    info!(
+        "[PATH] openclaw={:?}, node={:?}, missing dirs={:?}",
+        diagnosis.openclaw_path, diagnosis.node_path, diagnosis.missing_dirs
+    );
+    Ok(diagnosis)
+}
+
+/// Apply one of the fixes offered by `diagnose_path`
+#[command]
+#[specta::specta]
+pub async fn fix_path(option_id: String) -> Result<String, String> {
+    info!("[PATH] Applying PATH fix: {}", option_id);
+    let diagnosis = collect_path_diagnosis();
+    let fix = diagnosis
+        .fixes
+        .into_iter()
+        .find(|f| f.id == option_id)
+        .ok_or(format!("PATH fix '{}' is not applicable", option_id))?;
+
+    if fix.id == "windows_user_path" {
+        // Only the missing dirs are passed (through an env var, so no quoting is needed); the
+        // script appends them to the value it reads at write time
+        let mut cmd = std::process::Command::new("powershell");
+        cmd.args(["-NoProfile", "-Command", WINDOWS_USER_PATH_APPEND])
+            .env("OPENCLAW_ADD_PATH", diagnosis.missing_dirs.join(";"));
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000);
+        }
+        let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run PowerShell: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to update user PATH: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        info!("[PATH] Windows user PATH updated");
+        return Ok("User PATH updated. Restart the Manager to pick it up.".to_string());
+    }
+
+    let existing = std::fs::read_to_string(&fix.target).unwrap_or_default();
+    if existing.contains(fix.preview.trim()) {
+        return Ok(format!("{} already contains this PATH entry", fix.target));
+    }
+    crate::utils::file::append_file(&fix.target, &fix.preview)
+        .map_err(|e| format!("Failed to write {}: {}", fix.target, e))?;
+
+    info!("[PATH] Appended PATH entry to {}", fix.target);
+    Ok(format!("Updated {}. Open a new terminal or log out and back in to apply.", fix.target))
+}
+
+// ============ Sync Conflicts ============
+
+/// Directories never scanned for conflict copies (large and not synced config)
+const SYNC_SCAN_SKIP_DIRS: &[&str] = &["node_modules", ".git", "mcps", "manager"];
+
+/// Maximum directory depth scanned below the config dir
+const SYNC_SCAN_MAX_DEPTH: usize = 4;
+
+/// A sync-conflict copy found in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SyncConflictFile {
+    pub path: String,
+    /// File the conflict copy belongs to
+    pub original_path: String,
+    pub original_exists: bool,
+    /// Sync service that created the copy (Dropbox, iCloud, Syncthing, ...)
+    pub provider: String,
+    pub modified_at: Option<String>,
+    pub original_modified_at: Option<String>,
+    /// Changes from the original to the conflict copy (JSON files only, secrets masked)
+    pub changes: Vec<JsonChange>,
+}
+
+/// Sync-conflict scan of the config directory
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SyncConflictReport {
+    pub config_dir: String,
+    /// Sync service the config dir appears to live in
+    pub sync_provider: Option<String>,
+    pub conflicts: Vec<SyncConflictFile>,
+}
+
+fn modified_rfc3339(path: &std::path::Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+fn read_json_file(path: &std::path::Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(content.trim_start_matches('\u{FEFF}')).ok()
+}
+
+fn scan_sync_conflicts(dir: &std::path::Path, depth: usize, found: &mut Vec<SyncConflictFile>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if depth < SYNC_SCAN_MAX_DEPTH && !SYNC_SCAN_SKIP_DIRS.contains(&name.as_str()) {
+                scan_sync_conflicts(&path, depth + 1, found);
+            }
+            continue;
+        }
+
+        let artifact = match sync_conflict::classify(&name) {
+            Some(a) => a,
+            None => continue,
+        };
+        let original = dir.join(&artifact.original_name);
+        let original_exists = original.exists();
+        if !artifact.certain && !original_exists {
+            continue;
+        }
+
+        let changes = match (read_json_file(&original), read_json_file(&path)) {
+            (Some(old), Some(new)) => json_diff::diff_masked(&old, &new),
+            _ => Vec::new(),
+        };
+        found.push(SyncConflictFile {
+            path: path.display().to_string(),
+            original_path: original.display().to_string(),
+            original_exists,
+            provider: artifact.provider.to_string(),
+            modified_at: modified_rfc3339(&path),
+            original_modified_at: modified_rfc3339(&original),
+            changes,
+        });
+    }
+}
+
+fn collect_sync_conflicts() -> SyncConflictReport {
+    let config_dir = platform::get_config_dir();
+    let dir = std::path::Path::new(&config_dir);
+    let sync_provider = sync_conflict::detect_sync_folder(dir).map(|s| s.to_string()).or_else(|| {
+        // Syncthing marks shared folders with .stfolder
+        dir.ancestors()
+            .any(|a| a.join(".stfolder").exists())
+            .then(|| "Syncthing".to_string())
+    });
+
+    let mut conflicts = Vec::new();
+    scan_sync_conflicts(dir, 0, &mut conflicts);
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    SyncConflictReport {
+        config_dir,
+        sync_provider,
+        conflicts,
+    }
+}
+
+/// Find cloud-sync conflict copies in the config directory
+#[command]
+#[specta::specta]
+pub async fn find_sync_conflicts() -> Result<SyncConflictReport, String> {
+    info!("[Sync Conflicts] Scanning config directory...");
+    let report = collect_sync_conflicts();
+    info!(
+        "[Sync Conflicts] provider={:?}, {} conflict copies",
+        report.sync_provider,
+        report.conflicts.len()
+    );
+    Ok(report)
+}
+
+/// Resolve a conflict copy. `action`:
+/// - `keep_original`: drop the conflict copy
+/// - `use_conflict`: replace the original with the conflict copy
+/// - `merge`: write `merged` (built by the frontend from the change list) to the original
+///
+/// The conflict copy and any replaced original are moved to manager/sync-conflicts, never deleted.
+#[command]
+#[specta::specta]
+pub async fn resolve_sync_conflict(
+    path: String,
+    action: String,
+    merged: Option<serde_json::Value>,
+) -> Result<String, String> {
+    info!("[Sync Conflicts] Resolving {} with action {}", path, action);
+    let report = collect_sync_conflicts();
+    let conflict = report
+        .conflicts
+        .into_iter()
+        .find(|c| c.path == path)
+        .ok_or(format!("{} is not a known sync-conflict copy", path))?;
+
+    let archive_dir = std::path::Path::new(&platform::get_manager_data_dir()).join("sync-conflicts");
+    std::fs::create_dir_all(&archive_dir).map_err(|e| format!("Failed to create archive directory: {}", e))?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let archive = |file: &str| -> Result<(), String> {
+        let name = std::path::Path::new(file).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let target = archive_dir.join(format!("{}-{}", stamp, name));
+        std::fs::copy(file, &target).map_err(|e| format!("Failed to archive {}: {}", file, e))?;
+        Ok(())
+    };
+
+    match action.as_str() {
+        "keep_original" => {}
+        "use_conflict" => {
+            if conflict.original_exists {
+                archive(&conflict.original_path)?;
+            }
+            std::fs::copy(&conflict.path, &conflict.original_path)
+                .map_err(|e| format!("Failed to replace {}: {}", conflict.original_path, e))?;
+        }
+        "merge" => {
+            let merged = merged.ok_or("Merged content is required for the merge action")?;
+            let content = serde_json::to_string_pretty(&merged)
+                .map_err(|e| format!("Failed to serialize merged content: {}", e))?;
+            if conflict.original_exists {
+                archive(&conflict.original_path)?;
+            }
+            crate::utils::file::write_file(&conflict.original_path, &content)
+                .map_err(|e| format!("Failed to write {}: {}", conflict.original_path, e))?;
+        }
+        other => return Err(format!("Unknown action: {}", other)),
+    }
+
+    archive(&conflict.path)?;
+    std::fs::remove_file(&conflict.path).map_err(|e| format!("Failed to remove {}: {}", conflict.path, e))?;
+    info!("[Sync Conflicts] Resolved {}", conflict.path);
+    Ok(format!("Resolved conflict for {}", conflict.original_path))
+}
+
+// ============ Security Audit ============
+
+/// A risky access setting found in the config
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SecurityFinding {
+    /// "critical" (unacknowledged) | "warning" (acknowledged)
+    pub severity: String,
+    /// "<channel>/<account>", "default" for single-account channels
+    pub location: String,
+    pub message: String,
+    pub acknowledged_at: Option<String>,
+    pub acknowledged_by: Option<String>,
+}
+
+fn collect_security_findings() -> Vec<SecurityFinding> {
+    let config = crate::utils::config_include::load(&platform::get_config_file_path())
+        .map(|(c, _)| c)
+        .unwrap_or_else(|_| serde_json::json!({}));
+    let manager_config = crate::commands::config::load_manager_config().unwrap_or_else(|_| serde_json::json!({}));
+
+    let mut targets: Vec<(String, &serde_json::Value)> = Vec::new();
+    if let Some(channels) = config.get("channels").and_then(|c| c.as_object()) {
+        for (channel, value) in channels {
+            targets.push((format!("{}/default", channel), value));
+            if let Some(accounts) = value.get("accounts").and_then(|a| a.as_object()) {
+                for (account, acct) in accounts {
+                    targets.push((format!("{}/{}", channel, account), acct));
+                }
+            }
+        }
+    }
+
+    let mut findings: Vec<SecurityFinding> = targets
+        .into_iter()
+        .filter(|(_, value)| crate::commands::config::has_wildcard_access(value))
+        .map(|(location, _)| {
+            let ack = crate::commands::config::risk_acknowledgement(&manager_config, &location);
+            let field = |k: &str| ack.as_ref().and_then(|a| a.get(k)).and_then(|v| v.as_str()).map(String::from);
+            SecurityFinding {
+                severity: if ack.is_some() { "warning" } else { "critical" }.to_string(),
+                message: "Accepts messages from anyone (open dmPolicy or \"*\" in allowFrom)".to_string(),
+                acknowledged_at: field("acknowledgedAt"),
+                acknowledged_by: field("user"),
+                location,
+            }
+        })
+        .collect();
+    findings.sort_by(|a, b| a.severity.cmp(&b.severity).then(a.location.cmp(&b.location)));
+    findings
+}
+
+/// Security audit of channel access policies (wildcard allowFrom / open DMs)
+#[command]
+#[specta::specta]
+pub async fn security_audit() -> Result<Vec<SecurityFinding>, String> {
+    info!("[Security Audit] Auditing channel access policies...");
+    let findings = collect_security_findings();
+    info!("[Security Audit] {} finding(s)", findings.len());
+    Ok(findings)
+}
+
+// ============ Message Templates ============
+
+/// Id of the built-in test message template
+const DEFAULT_TEMPLATE_ID: &str = "default";
+
+/// Test/canary message template (stored in manager.json -> messageTemplates)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct MessageTemplate {
+    pub id: String,
+    pub name: String,
+    /// Text with {time}, {date}, {agent}, {version}, {channel} placeholders
+    pub body: String,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn builtin_message_template() -> MessageTemplate {
+    MessageTemplate {
+        id: DEFAULT_TEMPLATE_ID.to_string(),
+        name: "Connection test".to_string(),
+        body: "🤖 OpenClaw Test Message\n\n✅ Connection successful!\n⏰ {date} {time}".to_string(),
+        builtin: true,
+    }
+}
+
+fn load_message_templates() -> Vec<MessageTemplate> {
+    let manager_config = crate::commands::config::load_manager_config().unwrap_or_else(|_| serde_json::json!({}));
+    let mut templates: Vec<MessageTemplate> = manager_config
+        .get("messageTemplates")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    // A stored "default" overrides the built-in text
+    if !templates.iter().any(|t| t.id == DEFAULT_TEMPLATE_ID) {
+        templates.insert(0, builtin_message_template());
+    }
+    templates
+}
+
+fn save_message_templates(templates: &[MessageTemplate]) -> Result<(), String> {
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config["messageTemplates"] = serde_json::to_value(templates)
+        .map_err(|e| format!("Failed to serialize templates: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)
+}
+
+fn find_message_template(id: &str) -> Result<MessageTemplate, String> {
+    load_message_templates()
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or(format!("Message template not found: {}", id))
+}
+
+/// Fill in template variables for a message sent on `channel`
+fn render_message_template(body: &str, channel: &str) -> String {
+    let now = chrono::Local::now();
+    let config = crate::utils::config_include::load(&platform::get_config_file_path())
+        .map(|(c, _)| c)
+        .unwrap_or_else(|_| serde_json::json!({}));
+    let agents = config.pointer("/agents/list").and_then(|v| v.as_array());
+    let agent = agents
+        .and_then(|list| {
+            list.iter()
+                .find(|a| a.get("default").and_then(|v| v.as_bool()).unwrap_or(false))
+                .or_else(|| list.first())
+        })
+        .and_then(|a| a.get("id").and_then(|v| v.as_str()))
+        .unwrap_or("main")
+        .to_string();
+
+    let mut vars = std::collections::HashMap::new();
+    vars.insert("time", now.format("%H:%M:%S").to_string());
+    vars.insert("date", now.format("%Y-%m-%d").to_string());
+    vars.insert("agent", agent);
+    vars.insert("channel", channel.to_string());
+    if body.contains("{version}") {
+        let version = shell::run_openclaw(&["--version"]).map(|v| v.trim().to_string());
+        vars.insert("version", version.unwrap_or_else(|_| "unknown".to_string()));
+    }
+    message_template::render(body, &vars)
+}
+
+/// List message templates (the built-in "default" is always present)
+#[command]
+#[specta::specta]
+pub async fn list_message_templates() -> Result<Vec<MessageTemplate>, String> {
+    Ok(load_message_templates())
+}
+
+/// Add or update a message template
+#[command]
+#[specta::specta]
+pub async fn save_message_template(template: MessageTemplate) -> Result<MessageTemplate, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_message_template").await;
+    let id = template.id.trim().to_lowercase().replace(' ', "-");
+    if id.is_empty() || template.body.trim().is_empty() {
+        return Err("Template needs an id and a body".to_string());
+    }
+    let unknown = message_template::unknown_variables(&template.body);
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Unknown variable(s): {}. Available: {}",
+            unknown.iter().map(|u| format!("{{{}}}", u)).collect::<Vec<_>>().join(", "),
+            message_template::TEMPLATE_VARIABLES.iter().map(|v| format!("{{{}}}", v)).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    info!("[Templates] Saving message template: {}", id);
+
+    let template = MessageTemplate { id, builtin: false, ..template };
+    let mut templates: Vec<MessageTemplate> = load_message_templates().into_iter().filter(|t| !t.builtin).collect();
+    match templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => *existing = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    save_message_templates(&templates)?;
+    Ok(template)
+}
+
+/// Delete a message template (deleting "default" restores the built-in text)
+#[command]
+#[specta::specta]
+pub async fn delete_message_template(id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "delete_message_template").await;
+    info!("[Templates] Deleting message template: {}", id);
+    let templates: Vec<MessageTemplate> = load_message_templates()
+        .into_iter()
+        .filter(|t| !t.builtin && t.id != id)
+        .collect();
+    save_message_templates(&templates)?;
+    Ok(format!("Template '{}' deleted", id))
+}
+
+/// Render a template body without sending it
+#[command]
+#[specta::specta]
+pub async fn preview_message_template(body: String, channel: Option<String>) -> Result<String, String> {
+    Ok(render_message_template(&body, channel.as_deref().unwrap_or("telegram")))
+}
+
+// ============ Canary Ping ============
+
+/// Daily canary ping settings (manager.json -> canaryPing)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct CanaryConfig {
+    pub enabled: bool,
+    pub channel: String,
+    pub target: String,
+    pub template_id: Option<String>,
+    /// Local time of day to send, "HH:MM"
+    pub time: String,
+    pub last_run_at: Option<String>,
+    pub last_result: Option<ChannelTestResult>,
+}
+
+fn load_canary_config() -> CanaryConfig {
+    crate::commands::config::load_manager_config()
+        .ok()
+        .and_then(|m| m.get("canaryPing").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn save_canary_config_file(canary: &CanaryConfig) -> Result<(), String> {
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config["canaryPing"] = serde_json::to_value(canary)
+        .map_err(|e| format!("Failed to serialize canary config: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)
+}
+
+/// Send the canary message now and record the result; the caller holds the Manager lock
+fn run_canary(canary: &mut CanaryConfig) -> ChannelTestResult {
+    let result = send_canary(canary);
+    record_canary_result(canary, &result);
+    result
+}
+
+/// Send the canary message
+fn send_canary(canary: &CanaryConfig) -> ChannelTestResult {
+    match find_message_template(canary.template_id.as_deref().unwrap_or(DEFAULT_TEMPLATE_ID)) {
+        Ok(template) => {
+            let message = render_message_template(&template.body, &canary.channel);
+            send_channel_message(&canary.channel, &canary.target, &message)
+        }
+        Err(e) => ChannelTestResult {
+            success: false,
+            channel: canary.channel.clone(),
+            message: "Template missing".to_string(),
+            error: Some(e),
+        },
+    }
+}
+
+/// Store the last run and result on the saved settings (re-read, so settings changed while
+/// the message was out are kept); the caller holds the Manager lock
+fn record_canary_result(canary: &mut CanaryConfig, result: &ChannelTestResult) {
+    *canary = CanaryConfig {
+        last_run_at: Some(chrono::Local::now().to_rfc3339()),
+        last_result: Some(result.clone()),
+        ..load_canary_config()
+    };
+    if let Err(e) = save_canary_config_file(canary) {
+        warn!("[Canary] Failed to record result: {}", e);
+    }
+}
+
+/// Whether the daily canary is due: enabled, past its time of day, and not yet run today
+fn canary_due(canary: &CanaryConfig, now: chrono::DateTime<chrono::Local>) -> bool {
+    let Ok(at) = chrono::NaiveTime::parse_from_str(&canary.time, "%H:%M") else {
+        return false;
+    };
+    let ran_today = canary
+        .last_run_at
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Local).date_naive() == now.date_naive())
+        .unwrap_or(false);
+    canary.enabled && now.time() >= at && !ran_today
+}
+
+/// Background task that sends the daily canary ping and alerts when delivery fails
+pub fn start_canary_task(app: tauri::AppHandle) {
+    use tauri_plugin_notification::NotificationExt;
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(60));
+
+        let mut canary = load_canary_config();
+        if !canary_due(&canary, chrono::Local::now()) {
+            continue;
+        }
+        info!("[Canary] Sending daily canary ping to {} {}", canary.channel, canary.target);
+        // Sent without the lock, so manager.json saves are not held up by the delivery
+        let result = send_canary(&canary);
+        {
+            let _lock = locks::lock_blocking(&[ConfigResource::Manager], "canary_task");
+            record_canary_result(&mut canary, &result);
+        }
+        if result.success {
+            info!("[Canary] Delivered");
+            continue;
+        }
+        warn!("[Canary] Delivery failed: {:?}", result.error);
+        events::emit(ManagerEvent::alert(
+            "canary-failed",
+            AlertLevel::Error,
+            format!("Daily canary ping on {} failed", canary.channel),
+            &result,
+        ));
+        if let Err(e) = app
+            .notification()
+            .builder()
+            .title("OpenClaw canary ping failed")
+            .body(format!("Could not deliver the daily test message on {}", canary.channel))
+            .show()
+        {
+            debug!("[Canary] Failed to show notification: {}", e);
+        }
+    });
+}
+
+/// Get canary ping settings and last result
+#[command]
+#[specta::specta]
+pub async fn get_canary_config() -> Result<CanaryConfig, String> {
+    Ok(load_canary_config())
+}
+
+/// Save canary ping settings (last run/result are kept)
+#[command]
+#[specta::specta]
+pub async fn save_canary_config(canary: CanaryConfig) -> Result<CanaryConfig, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_canary_config").await;
+    if canary.enabled && (canary.channel.is_empty() || canary.target.is_empty()) {
+        return Err("Canary ping needs a channel and a target".to_string());
+    }
+    if chrono::NaiveTime::parse_from_str(&canary.time, "%H:%M").is_err() {
+        return Err(format!("Invalid time '{}', expected HH:MM", canary.time));
+    }
+    if let Some(id) = &canary.template_id {
+        find_message_template(id)?;
+    }
+    info!("[Canary] Saving canary config (enabled: {}, time: {})", canary.enabled, canary.time);
+    let previous = load_canary_config();
+    let canary = CanaryConfig {
+        last_run_at: previous.last_run_at,
+        last_result: previous.last_result,
+        ..canary
+    };
+    save_canary_config_file(&canary)?;
+    Ok(canary)
+}
+
+/// Send the canary ping immediately
+#[command]
+#[specta::specta]
+pub async fn run_canary_now() -> Result<ChannelTestResult, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "run_canary_now").await;
+    let mut canary = load_canary_config();
+    if canary.channel.is_empty() || canary.target.is_empty() {
+        return Err("Canary ping is not configured".to_string());
+    }
+    Ok(run_canary(&mut canary))
+}
+
+// ============ Heartbeat Status ============
+
+/// Last and next heartbeat run of one agent
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AgentHeartbeatStatus {
+    pub agent_id: String,
+    /// Configured interval (heartbeat.every), "0m" when disabled
+    pub every: String,
+    pub enabled: bool,
+    pub last_run_at: Option<String>,
+    /// ok, sent, skipped or failed
+    pub last_result: Option<String>,
+    pub last_message: Option<String>,
+    pub next_run_at: Option<String>,
+    /// A full interval has passed since the expected run without a new one
+    pub overdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct HeartbeatStatus {
+    pub agents: Vec<AgentHeartbeatStatus>,
+    /// Where runs were found (CLI, log files)
+    pub sources: Vec<String>,
+    pub checked_at: String,
+}
+
+/// Agents that run heartbeats with their interval: those with a heartbeat block, or the
+/// default agent when no agent has one
+fn heartbeat_agents(config: &serde_json::Value) -> Vec<(String, String)> {
+    let default_every = config
+        .pointer("/agents/defaults/heartbeat/every")
+        .and_then(|v| v.as_str())
+        .unwrap_or(heartbeat::DEFAULT_EVERY)
+        .to_string();
+    let entries: Vec<(String, &serde_json::Value)> = match config.pointer("/agents/list") {
+        Some(serde_json::Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|a| Some((a.get("id")?.as_str()?.to_string(), a)))
+            .collect(),
+        Some(serde_json::Value::Object(obj)) => obj.iter().map(|(id, a)| (id.clone(), a)).collect(),
+        _ => Vec::new(),
+    };
+    let explicit: Vec<(String, String)> = entries
+        .iter()
+        .filter_map(|(id, a)| {
+            let hb = a.get("heartbeat")?;
+            let every = hb.get("every").and_then(|v| v.as_str()).unwrap_or(&default_every);
+            Some((id.clone(), every.to_string()))
+        })
+        .collect();
+    if !explicit.is_empty() {
+        return explicit;
+    }
+    let (default_agent, _) = crate::commands::config::fallback_agent(config);
+    vec![(default_agent, default_every)]
+}
+
+/// Heartbeat runs from `openclaw system heartbeat last` and the gateway logs of today and yesterday
+fn collect_heartbeat_events(sources: &mut Vec<String>) -> Vec<heartbeat::HeartbeatEvent> {
+    let mut events = Vec::new();
+    if let Ok(output) = shell::run_openclaw(&["system", "heartbeat", "last"]) {
+        let event = extract_json_from_output(&output)
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+            .and_then(|v| heartbeat::event_from_json(&v));
+        if let Some(event) = event {
+            sources.push("openclaw system heartbeat last".to_string());
+            events.push(event);
+        }
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let log_dir = crate::commands::service::get_gateway_log_dir();
+    let mut log_files: Vec<std::path::PathBuf> = [today - chrono::Duration::days(1), today]
+        .iter()
+        .map(|d| log_dir.join(format!("openclaw-{}.log", d.format("%Y-%m-%d"))))
+        .collect();
+    log_files.push(std::path::PathBuf::from(platform::get_log_file_path()));
+    for path in log_files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let before = events.len();
+        events.extend(
+            content
+                .lines()
+                .filter(|l| l.to_ascii_lowercase().contains("heartbeat"))
+                .filter_map(heartbeat::event_from_log_line),
+        );
+        if events.len() > before {
+            sources.push(platform::path_string(&path));
+        }
+    }
+    events
+}
+
+/// Last run, result and next expected run of each agent's heartbeat, from the gateway's
+/// heartbeat CLI and logs
+#[command]
+#[specta::specta]
+pub async fn get_heartbeat_status() -> Result<HeartbeatStatus, String> {
+    info!("[Heartbeat] Reading heartbeat status...");
+    let config = crate::commands::config::load_openclaw_config()?;
+    let (default_agent, _) = crate::commands::config::fallback_agent(&config);
+    let mut sources = Vec::new();
+    let events = collect_heartbeat_events(&mut sources);
+    let now = chrono::Utc::now();
+
+    let agents = heartbeat_agents(&config)
+        .into_iter()
+        .map(|(agent_id, every)| {
+            let interval = heartbeat::parse_every(&every).unwrap_or_else(chrono::Duration::zero);
+            let enabled = interval > chrono::Duration::zero();
+            let last = events
+                .iter()
+                .filter(|e| e.agent_id.as_deref().unwrap_or(&default_agent) == agent_id)
+                .max_by_key(|e| e.at);
+            let (next, overdue) = if enabled {
+                heartbeat::schedule(last.map(|e| e.at), interval, now)
+            } else {
+                (None, false)
+            };
+            if overdue {
+                warn!("[Heartbeat] Agent '{}' missed its heartbeat (every {})", agent_id, every);
+            }
+            AgentHeartbeatStatus {
+                agent_id,
+                every,
+                enabled,
+                last_run_at: last.map(|e| e.at.to_rfc3339()),
+                last_result: last.map(|e| e.result.clone()),
+                last_message: last.map(|e| log_sanitizer::sanitize(&e.message)),
+                next_run_at: next.map(|n| n.to_rfc3339()),
+                overdue,
+            }
+        })
+        .collect();
+
+    Ok(HeartbeatStatus {
+        agents,
+        sources,
+        checked_at: now.to_rfc3339(),
+    })
+}
+
+// ============ Write Access ============
+
+/// Set while the Manager runs in read-only mode, with the reason shown to the user
+static READ_ONLY: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// Result of probing one location the Manager writes to
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WriteAccessCheck {
+    pub path: String,
+    pub writable: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct WriteAccessReport {
+    /// True when any location is not writable; saves are refused until the next successful check
+    pub read_only: bool,
+    pub checks: Vec<WriteAccessCheck>,
+}
+
+/// Reason the Manager is in read-only mode, if it is
+pub(crate) fn read_only_reason() -> Option<String> {
+    READ_ONLY.lock().ok().and_then(|r| r.clone())
+}
+
+/// EPERM/EACCES or a read-only filesystem (EROFS)
+pub(crate) fn is_permission_error(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::PermissionDenied || e.raw_os_error() == Some(30)
+}
+
+/// Switch to read-only mode. Only the first switch is logged and reported to the frontend, so
+/// a denied directory produces one alert instead of a failure per command.
+pub(crate) fn enter_read_only_mode(reason: &str) {
+    let first = match READ_ONLY.lock() {
+        Ok(mut state) => {
+            let first = state.is_none();
+            *state = Some(reason.to_string());
+            first
+        }
+        Err(_) => return,
+    };
+    if !first {
+        return;
+    }
+    warn!("[Write Access] Entering read-only mode: {}", reason);
+    events::emit(ManagerEvent::Alert {
+        kind: "read-only".to_string(),
+        level: AlertLevel::Warning,
+        message: reason.to_string(),
+        data: None,
+    });
+}
+
+/// Create, write and delete a probe file in `dir`
+fn probe_dir(dir: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = std::path::Path::new(dir).join(format!(".manager-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"ok")?;
+    std::fs::remove_file(&probe)
+}
+
+/// Open an existing file for writing without changing it
+fn probe_file(path: &str) -> std::io::Result<()> {
+    if !std::path::Path::new(path).exists() {
+        return Ok(());
+    }
+    std::fs::OpenOptions::new().append(true).open(path).map(|_| ())
+}
+
+fn collect_write_access() -> WriteAccessReport {
+    let probes: Vec<(String, std::io::Result<()>)> = vec![
+        (platform::get_config_dir(), probe_dir(&platform::get_config_dir())),
+        (platform::get_config_file_path(), probe_file(&platform::get_config_file_path())),
+        (platform::get_manager_data_dir(), probe_dir(&platform::get_manager_data_dir())),
+        (platform::get_manager_config_file_path(), probe_file(&platform::get_manager_config_file_path())),
+    ];
+    let checks: Vec<WriteAccessCheck> = probes
+        .into_iter()
+        .map(|(path, result)| WriteAccessCheck {
+            path,
+            writable: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        })
+        .collect();
+    WriteAccessReport { read_only: checks.iter().any(|c| !c.writable), checks }
+}
+
+/// Enter read-only mode for a report with a denied location, leave it when everything is writable
+fn update_read_only_mode(report: &WriteAccessReport) {
+    match report.checks.iter().find(|c| !c.writable) {
+        Some(denied) => enter_read_only_mode(&format!(
+            "{} is not writable: {}",
+            denied.path,
+            denied.error.as_deref().unwrap_or("unknown error")
+        )),
+        None => {
+            if let Ok(mut state) = READ_ONLY.lock() {
+                if state.take().is_some() {
+                    info!("[Write Access] Write access restored, leaving read-only mode");
+                }
+            }
+        }
+    }
+}
+
+/// Probe write access to the OpenClaw and Manager directories
+#[command]
+#[specta::specta]
+pub async fn check_write_access() -> Result<WriteAccessReport, String> {
+    info!("[Write Access] Checking write access...");
+    let report = collect_write_access();
+    update_read_only_mode(&report);
+    Ok(report)
+}
+
+/// Run the write-access preflight once at startup
+pub fn start_write_access_preflight() {
+    std::thread::spawn(|| update_read_only_mode(&collect_write_access()));
+}
+
+// ============ Manager Self-Test ============
+
+/// One self-test step
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Result of `run_self_test`. A failure here is a Manager bug or a broken environment
+/// (temp dir, shell), not an OpenClaw problem; those show up in `run_doctor`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+    /// Informational only, a missing OpenClaw does not fail the self-test
+    pub openclaw_path: Option<String>,
+}
+
+fn self_test_config(dir: &std::path::Path) -> Result<(), String> {
+    let main = dir.join("openclaw.json");
+    let main_path = platform::path_string(&main);
+    file::write_file(&main_path, r#"{"gateway": {"port": 18789}, "agents": {"$include": "./agents.json"}}"#)
+        .map_err(|e| format!("Failed to write {}: {}", main_path, e))?;
+    let agents_path = platform::path_string(&dir.join("agents.json"));
+    file::write_file(&agents_path, r#"{"defaults": {"model": {"primary": "test/model"}}}"#)
+        .map_err(|e| format!("Failed to write {}: {}", agents_path, e))?;
+
+    let (mut config, sources) = config_include::load(&main_path)?;
+    if config.pointer("/agents/defaults/model/primary").and_then(|v| v.as_str()) != Some("test/model") {
+        return Err("Included file was not merged".to_string());
+    }
+    config["gateway"]["port"] = serde_json::json!(18790);
+    for (path, content) in config_include::split(&main_path, &config, &sources) {
+        let text = serde_json::to_string_pretty(&content).map_err(|e| e.to_string())?;
+        file::write_file(&path, &text).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    let (reloaded, _) = config_include::load(&main_path)?;
+    if reloaded != config {
+        return Err("Config read back differs from what was written".to_string());
+    }
+    let main_content = file::read_file(&main_path).map_err(|e| e.to_string())?;
+    if !main_content.contains(config_include::INCLUDE_KEY) {
+        return Err("$include directive was lost on save".to_string());
+    }
+    Ok(())
+}
+
+fn self_test_env(dir: &std::path::Path) -> Result<(), String> {
+    let env_path = platform::path_string(&dir.join("env"));
+    let write = |key: &str, value: &str| {
+        file::set_env_value(&env_path, key, value).map_err(|e| format!("Failed to write {}: {}", key, e))
+    };
+    write("SELF_TEST_A", "first")?;
+    write("SELF_TEST_B", "sk-test value with spaces")?;
+    write("SELF_TEST_A", "second")?;
+    if file::read_env_value(&env_path, "SELF_TEST_A").as_deref() != Some("second") {
+        return Err("Updated env value was not read back".to_string());
+    }
+    if file::read_env_value(&env_path, "SELF_TEST_B").as_deref() != Some("sk-test value with spaces") {
+        return Err("Env value with spaces was not read back".to_string());
+    }
+    file::remove_env_value(&env_path, "SELF_TEST_A").map_err(|e| e.to_string())?;
+    if file::read_env_value(&env_path, "SELF_TEST_A").is_some() {
+        return Err("Removed env value is still present".to_string());
+    }
+    Ok(())
+}
+
+fn self_test_shell() -> Result<(), String> {
+    if shell::get_extended_path().is_empty() {
+        return Err("Extended PATH is empty".to_string());
+    }
+    let output = if platform::is_windows() {
+        shell::run_cmd_output("echo manager-self-test")?
+    } else {
+        shell::run_bash_output("echo manager-self-test")?
+    };
+    if !output.contains("manager-self-test") {
+        return Err(format!("Unexpected shell output: {}", output));
+    }
+    Ok(())
+}
+
+fn self_test_token() -> Result<(), String> {
+    let bytes = gateway_token::token_bytes();
+    let token = gateway_token::generate(bytes)?;
+    if token.len() != bytes as usize * 2 || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Malformed token ({} chars)", token.len()));
+    }
+    if gateway_token::weakness(&token).is_some() {
+        return Err("Generated token is flagged as weak".to_string());
+    }
+    if gateway_token::generate(bytes)? == token {
+        return Err("Two generated tokens are identical".to_string());
+    }
+    Ok(())
+}
+
+fn self_test_backup(dir: &std::path::Path) -> Result<(), String> {
+    let files = std::collections::BTreeMap::from([
+        ("openclaw.json".to_string(), r#"{"gateway": {"port": 18789}}"#.to_string()),
+        ("env".to_string(), "export SELF_TEST=\"1\"".to_string()),
+    ]);
+    crate::commands::backup::roundtrip_check(dir, files)
+}
+
+fn collect_self_test() -> SelfTestReport {
+    let dir = std::env::temp_dir().join(format!("openclaw-manager-self-test-{}", std::process::id()));
+    let mut checks = Vec::new();
+    let mut run = |name: &str, check: &dyn Fn() -> Result<(), String>| {
+        let started = std::time::Instant::now();
+        let result = check();
+        if let Err(e) = &result {
+            warn!("[Self-Test] {} failed: {}", name, e);
+        }
+        checks.push(SelfTestCheck {
+            name: name.to_string(),
+            passed: result.is_ok(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.err(),
+        });
+    };
+
+    run("temp_dir", &|| std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e)));
+    run("config_read_write", &|| self_test_config(&dir));
+    run("env_parsing", &|| self_test_env(&dir));
+    run("shell", &self_test_shell);
+    run("token_generation", &self_test_token);
+    run("backup_restore", &|| self_test_backup(&dir));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    SelfTestReport {
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+        openclaw_path: shell::get_openclaw_path(),
+    }
+}
+
+/// Exercise the Manager's own critical paths against a temp directory
+#[command]
+#[specta::specta]
+pub async fn run_self_test() -> Result<SelfTestReport, String> {
+    info!("[Self-Test] Running Manager self-test...");
+    let report = collect_self_test();
+    info!("[Self-Test] Done, passed={}", report.passed);
+    Ok(report)
+}
+
+/// Run the self-test once at startup and alert when the Manager itself is broken
+pub fn start_self_test() {
+    std::thread::spawn(|| {
+        let report = collect_self_test();
+        if !report.passed {
+            let failed: Vec<&str> = report.checks.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect();
+            events::emit(ManagerEvent::alert(
+                "self-test-failed",
+                AlertLevel::Error,
+                format!("Manager self-test failed: {}", failed.join(", ")),
+                &report,
+            ));
+        }
+    });
+}
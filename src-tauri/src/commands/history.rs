@@ -0,0 +1,143 @@
+use crate::utils::json_diff::{self, JsonChange};
+use crate::utils::{file, platform};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Oldest entries are dropped beyond this many
+const MAX_HISTORY_ENTRIES: usize = 1000;
+
+/// Frontend view the user is currently on, reported by the UI on navigation
+static ACTIVE_VIEW: Mutex<Option<String>> = Mutex::new(None);
+
+/// One recorded configuration change
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryEntry {
+    pub timestamp: String,
+    /// Manager command that wrote the config (e.g. save_provider)
+    pub command: String,
+    /// Frontend view active at the time (e.g. ai, channels)
+    #[serde(default)]
+    pub view: Option<String>,
+    /// OS user running the Manager
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Top-level config sections touched (e.g. models, channels)
+    pub sections: Vec<String>,
+    /// Changes with secrets masked
+    pub changes: Vec<JsonChange>,
+}
+
+fn get_history_file_path() -> String {
+    if platform::is_windows() {
+        format!("{}\\config-history.jsonl", platform::get_manager_data_dir())
+    } else {
+        format!("{}/config-history.jsonl", platform::get_manager_data_dir())
+    }
+}
+
+pub(crate) fn current_user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|u| !u.is_empty())
+}
+
+fn read_history() -> Vec<ConfigHistoryEntry> {
+    let file = match std::fs::File::open(get_history_file_path()) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Record a config write. Called by the save path with the config before and after.
+pub(crate) fn record_config_change(command: &str, old: &Value, new: &Value) {
+    let changes = json_diff::diff_masked(old, new);
+    if changes.is_empty() {
+        return;
+    }
+    let mut sections: Vec<String> = changes
+        .iter()
+        .map(|c| c.path.split('.').next().unwrap_or("").to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    sections.sort();
+    sections.dedup();
+
+    let entry = ConfigHistoryEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: command.to_string(),
+        view: ACTIVE_VIEW.lock().ok().and_then(|v| v.clone()),
+        user: current_user(),
+        sections,
+        changes,
+    };
+    let line = match serde_json::to_string(&entry) {
+        Ok(line) => line,
+        Err(e) => {
+            warn!("[History] Failed to serialize entry: {}", e);
+            return;
+        }
+    };
+
+    let path = get_history_file_path();
+    if let Some(parent) = std::path::Path::new(&path).parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = file::append_file(&path, &line) {
+        warn!("[History] Failed to record change: {}", e);
+        return;
+    }
+    debug!("[History] Recorded {} change(s) by {}", entry.changes.len(), command);
+
+    // Trim occasionally rather than on every write
+    let history = read_history();
+    if history.len() > MAX_HISTORY_ENTRIES + MAX_HISTORY_ENTRIES / 10 {
+        let keep = &history[history.len() - MAX_HISTORY_ENTRIES..];
+        let content: Vec<String> = keep.iter().filter_map(|e| serde_json::to_string(e).ok()).collect();
+        let _ = file::write_file(&path, &(content.join("\n") + "\n"));
+    }
+}
+
+/// Report the frontend view the user is on, used to attribute later changes
+#[command]
+#[specta::specta]
+pub async fn set_active_view(view: Option<String>) -> Result<(), String> {
+    if let Ok(mut active) = ACTIVE_VIEW.lock() {
+        *active = view.filter(|v| !v.is_empty());
+    }
+    Ok(())
+}
+
+/// Get config change history, newest first.
+/// `section` filters to entries touching that top-level section or dotted path prefix.
+#[command]
+#[specta::specta]
+pub async fn get_config_history(section: Option<String>, limit: Option<usize>) -> Result<Vec<ConfigHistoryEntry>, String> {
+    info!("[History] Getting config history (section={:?})", section);
+    let mut entries: Vec<ConfigHistoryEntry> = read_history();
+    entries.reverse();
+
+    if let Some(section) = section.filter(|s| !s.is_empty()) {
+        let prefix = format!("{}.", section);
+        entries = entries
+            .into_iter()
+            .filter_map(|mut entry| {
+                entry.changes.retain(|c| c.path == section || c.path.starts_with(&prefix));
+                (!entry.changes.is_empty()).then_some(entry)
+            })
+            .collect();
+    }
+
+    entries.truncate(limit.unwrap_or(200));
+    Ok(entries)
+}
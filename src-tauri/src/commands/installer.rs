@@ -1,10 +1,18 @@
+use crate::commands::config::{load_manager_config, save_manager_config};
+use crate::commands::locks::{self, ConfigResource};
+use crate::commands::tasks::{self, Task};
+use crate::commands::telemetry;
+use crate::events::{self, AlertLevel, ManagerEvent};
+use crate::utils::node_pm::{self, NodePackageManager};
+use crate::utils::pkgmgr::{self, Package, PackageStep};
+use crate::utils::wsl::{self, WslDistro, WslSettings};
 use crate::utils::{log_sanitizer, platform, shell};
 use serde::{Deserialize, Serialize};
 use tauri::command;
 use log::{info, warn, error, debug};
 
 /// Environment check result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct EnvironmentStatus {
     /// Whether Node.js is installed
     pub node_installed: bool,
@@ -28,10 +36,14 @@ pub struct EnvironmentStatus {
     pub ready: bool,
     /// Operating system
     pub os: String,
+    /// WSL distros on Windows (empty elsewhere or without WSL)
+    pub wsl_distros: Vec<WslDistro>,
+    /// Distro openclaw and node run in when WSL mode is enabled
+    pub wsl_distro: Option<String>,
 }
 
 /// Installation progress
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct InstallProgress {
     pub step: String,
     pub progress: u8,
@@ -40,21 +52,33 @@ pub struct InstallProgress {
 }
 
 /// Installation result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct InstallResult {
     pub success: bool,
     pub message: String,
     pub error: Option<String>,
+    /// Per-step output when installed through a package manager
+    #[serde(default)]
+    pub steps: Vec<PackageStep>,
 }
 
 /// Check environment status
 #[command]
+#[specta::specta]
 pub async fn check_environment() -> Result<EnvironmentStatus, String> {
     info!("[Environment Check] Starting system environment check...");
 
     let os = platform::get_os();
     info!("[Environment Check] Operating system: {}", os);
 
+    // Check WSL (Windows only); in WSL mode the checks below run inside the distro
+    let wsl_distros = wsl::detect_distros();
+    let wsl_distro = wsl::active().map(|t| t.distro);
+    if !wsl_distros.is_empty() {
+        info!("[Environment Check] WSL distros: {:?}, WSL mode: {:?}",
+            wsl_distros.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(), wsl_distro);
+    }
+
     // Check Node.js
     info!("[Environment Check] Checking Node.js...");
     let node_version = get_node_version();
@@ -107,12 +131,20 @@ pub async fn check_environment() -> Result<EnvironmentStatus, String> {
         config_dir_exists,
         ready,
         os,
+        wsl_distros,
+        wsl_distro,
     })
 }
 
 /// Get Node.js version
 /// Detects multiple possible installation paths, since GUI apps don't inherit user shell PATH
 fn get_node_version() -> Option<String> {
+    if let Some(target) = wsl::active() {
+        return wsl::run(&target.distro, "node --version")
+            .ok()
+            .filter(|v| v.starts_with('v'));
+    }
+
     if platform::is_windows() {
         // Windows: First try direct call (if PATH is updated)
         if let Ok(v) = shell::run_cmd_output("node --version") {
@@ -182,36 +214,36 @@ fn get_unix_node_paths() -> Vec<String> {
 
     // nvm (check common versions)
     if let Some(home) = dirs::home_dir() {
-        let home_str = home.display().to_string();
+        let home_path = |parts: &[&str]| platform::path_string(&platform::join_path(&home, parts));
 
         // nvm default versions
-        paths.push(format!("{}/.nvm/versions/node/v22.0.0/bin/node", home_str));
-        paths.push(format!("{}/.nvm/versions/node/v22.1.0/bin/node", home_str));
-        paths.push(format!("{}/.nvm/versions/node/v22.2.0/bin/node", home_str));
-        paths.push(format!("{}/.nvm/versions/node/v22.11.0/bin/node", home_str));
-        paths.push(format!("{}/.nvm/versions/node/v22.12.0/bin/node", home_str));
-        paths.push(format!("{}/.nvm/versions/node/v23.0.0/bin/node", home_str));
+        paths.push(home_path(&[".nvm", "versions", "node", "v22.0.0", "bin", "node"]));
+        paths.push(home_path(&[".nvm", "versions", "node", "v22.1.0", "bin", "node"]));
+        paths.push(home_path(&[".nvm", "versions", "node", "v22.2.0", "bin", "node"]));
+        paths.push(home_path(&[".nvm", "versions", "node", "v22.11.0", "bin", "node"]));
+        paths.push(home_path(&[".nvm", "versions", "node", "v22.12.0", "bin", "node"]));
+        paths.push(home_path(&[".nvm", "versions", "node", "v23.0.0", "bin", "node"]));
 
         // Try nvm alias default (read nvm's default alias)
-        let nvm_default = format!("{}/.nvm/alias/default", home_str);
+        let nvm_default = home_path(&[".nvm", "alias", "default"]);
         if let Ok(version) = std::fs::read_to_string(&nvm_default) {
             let version = version.trim();
             if !version.is_empty() {
-                paths.insert(0, format!("{}/.nvm/versions/node/v{}/bin/node", home_str, version));
+                paths.insert(0, home_path(&[".nvm", "versions", "node", &format!("v{}", version), "bin", "node"]));
             }
         }
 
         // fnm
-        paths.push(format!("{}/.fnm/aliases/default/bin/node", home_str));
+        paths.push(home_path(&[".fnm", "aliases", "default", "bin", "node"]));
 
         // volta
-        paths.push(format!("{}/.volta/bin/node", home_str));
+        paths.push(home_path(&[".volta", "bin", "node"]));
 
         // asdf
-        paths.push(format!("{}/.asdf/shims/node", home_str));
+        paths.push(home_path(&[".asdf", "shims", "node"]));
 
         // mise (formerly rtx)
-        paths.push(format!("{}/.local/share/mise/shims/node", home_str));
+        paths.push(home_path(&[".local", "share", "mise", "shims", "node"]));
     }
 
     paths
@@ -230,23 +262,23 @@ fn get_windows_node_paths() -> Vec<String> {
 
     // 3. Various installations in user directory
     if let Some(home) = dirs::home_dir() {
-        let home_str = home.display().to_string();
+        let home_path = |parts: &[&str]| platform::path_string(&platform::join_path(&home, parts));
 
         // nvm for Windows user installation
-        paths.push(format!("{}\\AppData\\Roaming\\nvm\\current\\node.exe", home_str));
+        paths.push(home_path(&["AppData", "Roaming", "nvm", "current", "node.exe"]));
 
         // fnm (Fast Node Manager) for Windows
-        paths.push(format!("{}\\AppData\\Roaming\\fnm\\aliases\\default\\node.exe", home_str));
-        paths.push(format!("{}\\AppData\\Local\\fnm\\aliases\\default\\node.exe", home_str));
-        paths.push(format!("{}\\.fnm\\aliases\\default\\node.exe", home_str));
+        paths.push(home_path(&["AppData", "Roaming", "fnm", "aliases", "default", "node.exe"]));
+        paths.push(home_path(&["AppData", "Local", "fnm", "aliases", "default", "node.exe"]));
+        paths.push(home_path(&[".fnm", "aliases", "default", "node.exe"]));
 
         // volta
-        paths.push(format!("{}\\AppData\\Local\\Volta\\bin\\node.exe", home_str));
+        paths.push(home_path(&["AppData", "Local", "Volta", "bin", "node.exe"]));
         // volta invokes via shim, just check bin directory
 
         // scoop installation
-        paths.push(format!("{}\\scoop\\apps\\nodejs\\current\\node.exe", home_str));
-        paths.push(format!("{}\\scoop\\apps\\nodejs-lts\\current\\node.exe", home_str));
+        paths.push(home_path(&["scoop", "apps", "nodejs", "current", "node.exe"]));
+        paths.push(home_path(&["scoop", "apps", "nodejs-lts", "current", "node.exe"]));
 
         // chocolatey installation
         paths.push("C:\\ProgramData\\chocolatey\\lib\\nodejs\\tools\\node.exe".to_string());
@@ -254,28 +286,28 @@ fn get_windows_node_paths() -> Vec<String> {
 
     // 4. Installation paths from registry (obtained indirectly via environment variables)
     if let Ok(program_files) = std::env::var("ProgramFiles") {
-        paths.push(format!("{}\\nodejs\\node.exe", program_files));
+        paths.push(platform::path_string(&platform::join_path(&program_files, &["nodejs", "node.exe"])));
     }
     if let Ok(program_files_x86) = std::env::var("ProgramFiles(x86)") {
-        paths.push(format!("{}\\nodejs\\node.exe", program_files_x86));
+        paths.push(platform::path_string(&platform::join_path(&program_files_x86, &["nodejs", "node.exe"])));
     }
 
     // 5. nvm-windows symlink path (NVM_SYMLINK environment variable)
     if let Ok(nvm_symlink) = std::env::var("NVM_SYMLINK") {
-        paths.insert(0, format!("{}\\node.exe", nvm_symlink));
+        paths.insert(0, platform::path_string(&platform::join_path(&nvm_symlink, &["node.exe"])));
     }
 
     // 6. Current version under nvm-windows NVM_HOME path
     if let Ok(nvm_home) = std::env::var("NVM_HOME") {
         // Try to read the currently activated version
-        let settings_path = format!("{}\\settings.txt", nvm_home);
+        let settings_path = platform::path_string(&platform::join_path(&nvm_home, &["settings.txt"]));
         if let Ok(content) = std::fs::read_to_string(&settings_path) {
             for line in content.lines() {
                 if line.starts_with("current:") {
                     if let Some(version) = line.strip_prefix("current:") {
                         let version = version.trim();
                         if !version.is_empty() {
-                            paths.insert(0, format!("{}\\v{}\\node.exe", nvm_home, version));
+                            paths.insert(0, platform::path_string(&platform::join_path(&nvm_home, &[&format!("v{}", version), "node.exe"])));
                         }
                     }
                 }
@@ -361,6 +393,7 @@ fn check_gateway_installed() -> bool {
 
 /// Install gateway service (opens elevated terminal)
 #[command]
+#[specta::specta]
 pub async fn install_gateway_service() -> Result<String, String> {
     info!("[Gateway Install] Starting gateway service installation...");
     let os = platform::get_os();
@@ -449,14 +482,10 @@ read -p "Press Enter to close this window..."
     std::fs::write(script_path, script_content)
         .map_err(|e| format!("Failed to create script: {}", e))?;
 
-    std::process::Command::new("chmod")
-        .args(["+x", script_path])
-        .output()
+    shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
         .map_err(|e| format!("Failed to set permissions: {}", e))?;
 
-    std::process::Command::new("open")
-        .arg(script_path)
-        .spawn()
+    shell::spawn(std::process::Command::new("open").arg(script_path))
         .map_err(|e| format!("Failed to launch terminal: {}", e))?;
 
     info!("[Gateway Install] Terminal launched successfully on macOS");
@@ -494,17 +523,13 @@ read -p "Press Enter to close this window..."
     std::fs::write(script_path, script_content)
         .map_err(|e| format!("Failed to create script: {}", e))?;
 
-    std::process::Command::new("chmod")
-        .args(["+x", script_path])
-        .output()
+    shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
         .map_err(|e| format!("Failed to set permissions: {}", e))?;
 
     // Try different terminal emulators
     let terminals = ["gnome-terminal", "xfce4-terminal", "konsole", "xterm"];
     for term in terminals {
-        if std::process::Command::new(term)
-            .args(["--", script_path])
-            .spawn()
+        if shell::spawn(std::process::Command::new(term).args(["--", script_path]))
             .is_ok()
         {
             info!("[Gateway Install] Terminal '{}' launched successfully on Linux", term);
@@ -518,23 +543,25 @@ read -p "Press Enter to close this window..."
 
 /// Install Node.js
 #[command]
+#[specta::specta]
 pub async fn install_nodejs() -> Result<InstallResult, String> {
     info!("[Install Node.js] Starting Node.js installation...");
     let os = platform::get_os();
     info!("[Install Node.js] Detected operating system: {}", os);
+    let task = Task::begin("install-nodejs");
 
     let result = match os.as_str() {
         "windows" => {
             info!("[Install Node.js] Using Windows installation method...");
-            install_nodejs_windows().await
+            install_nodejs_windows(&task).await
         },
         "macos" => {
             info!("[Install Node.js] Using macOS installation method (Homebrew)...");
-            install_nodejs_macos().await
+            install_nodejs_macos(&task).await
         },
         "linux" => {
             info!("[Install Node.js] Using Linux installation method...");
-            install_nodejs_linux().await
+            install_nodejs_linux(&task).await
         },
         _ => {
             error!("[Install Node.js] Unsupported operating system: {}", os);
@@ -542,6 +569,7 @@ pub async fn install_nodejs() -> Result<InstallResult, String> {
                 success: false,
                 message: "Unsupported operating system".to_string(),
                 error: Some(format!("Unsupported operating system: {}", os)),
+                steps: Vec::new(),
             })
         },
     };
@@ -551,35 +579,68 @@ pub async fn install_nodejs() -> Result<InstallResult, String> {
         Ok(r) => warn!("[Install Node.js] Installation failed: {}", r.message),
         Err(e) => error!("[Install Node.js] Installation error: {}", e),
     }
+    task.finish(&result);
 
     result
 }
 
+/// Build an InstallResult from package manager steps
+fn install_result_from_steps(package: Package, steps: Vec<PackageStep>) -> InstallResult {
+    if pkgmgr::all_succeeded(&steps) {
+        InstallResult {
+            success: true,
+            message: format!(
+                "{} installed successfully! {}",
+                package.display_name(),
+                pkgmgr::version(package).unwrap_or_default()
+            ),
+            error: None,
+            steps,
+        }
+    } else {
+        let error = steps.iter().find(|s| !s.success).map(|s| format!("{}: {}", s.name, s.output));
+        InstallResult {
+            success: false,
+            message: format!("{} installation failed", package.display_name()),
+            error,
+            steps,
+        }
+    }
+}
+
 /// Install Node.js on Windows
-async fn install_nodejs_windows() -> Result<InstallResult, String> {
-    // Use winget to install Node.js (built-in on Windows 10/11)
-    let script = r#"
-$ErrorActionPreference = 'Stop'
+async fn install_nodejs_windows(task: &Task) -> Result<InstallResult, String> {
+    if get_node_version().is_some() {
+        return Ok(InstallResult {
+            success: true,
+            message: "Node.js is already installed".to_string(),
+            error: None,
+            steps: Vec::new(),
+        });
+    }
 
-# Check if already installed
-$nodeVersion = node --version 2>$null
-if ($nodeVersion) {
-    Write-Host "Node.js is already installed: $nodeVersion"
-    exit 0
-}
+    // Prefer winget (built-in on Windows 10/11), then Scoop
+    if let Some(pm) = pkgmgr::preferred_for(Package::Node) {
+        let steps = pkgmgr::install_with_progress(pm, Package::Node, &mut |_, _, name| task.step(name))?;
+        let mut result = install_result_from_steps(Package::Node, steps);
+        if result.success {
+            result.message = "Node.js installed successfully! Please restart the application for environment variables to take effect.".to_string();
+        }
+        return Ok(result);
+    }
 
-# Prefer winget
-$hasWinget = Get-Command winget -ErrorAction SilentlyContinue
-if ($hasWinget) {
-    Write-Host "Installing Node.js using winget..."
-    winget install --id OpenJS.NodeJS.LTS --accept-source-agreements --accept-package-agreements
-    if ($LASTEXITCODE -eq 0) {
-        Write-Host "Node.js installed successfully!"
-        exit 0
+    // Fallback: Use fnm (Fast Node Manager), asking for the native build (fnm itself may be x64)
+    if let Err(e) = pkgmgr::ensure_node_build() {
+        return Ok(InstallResult {
+            success: false,
+            message: "Node.js installation failed".to_string(),
+            error: Some(e),
+            steps: Vec::new(),
+        });
     }
-}
+    let script = r#"
+$ErrorActionPreference = 'Stop'
 
-# Fallback: Use fnm (Fast Node Manager)
 Write-Host "Attempting to install Node.js using fnm..."
 $fnmInstallScript = "irm https://fnm.vercel.app/install.ps1 | iex"
 Invoke-Expression $fnmInstallScript
@@ -589,7 +650,7 @@ $env:FNM_DIR = "$env:USERPROFILE\.fnm"
 $env:Path = "$env:FNM_DIR;$env:Path"
 
 # Install Node.js 22
-fnm install 22
+fnm install 22 --arch {arch}
 fnm default 22
 fnm use 22
 
@@ -602,9 +663,11 @@ if ($nodeVersion) {
     Write-Host "Node.js installation failed"
     exit 1
 }
-"#;
+"#
+    .replace("{arch}", &platform::get_native_arch());
 
-    match shell::run_powershell_output(script) {
+    task.step("fnm install");
+    match task.run(&mut shell::powershell_command(&script)) {
         Ok(output) => {
             // Verify installation
             if get_node_version().is_some() {
@@ -612,12 +675,14 @@ if ($nodeVersion) {
                     success: true,
                     message: "Node.js installed successfully! Please restart the application for environment variables to take effect.".to_string(),
                     error: None,
+                    steps: Vec::new(),
                 })
             } else {
                 Ok(InstallResult {
                     success: false,
                     message: "Application restart required after installation".to_string(),
                     error: Some(output),
+                    steps: Vec::new(),
                 })
             }
         }
@@ -625,121 +690,100 @@ if ($nodeVersion) {
             success: false,
             message: "Node.js installation failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
+/// Install Homebrew if it is missing (macOS)
+fn ensure_homebrew() -> Result<(), String> {
+    if pkgmgr::PackageManager::Homebrew.is_available() {
+        return Ok(());
+    }
+    info!("[Install Node.js] Installing Homebrew...");
+    shell::run_bash_output(r#"NONINTERACTIVE=1 /bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)""#)
+        .map(|_| ())
+        .map_err(|e| format!("Homebrew installation failed: {}", e))
+}
+
 /// Install Node.js on macOS
-async fn install_nodejs_macos() -> Result<InstallResult, String> {
+async fn install_nodejs_macos(task: &Task) -> Result<InstallResult, String> {
     // Install using Homebrew
-    let script = r#"
-# Check Homebrew
-if ! command -v brew &> /dev/null; then
-    echo "Installing Homebrew..."
-    /bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)"
-
-    # Configure PATH
-    if [[ -f /opt/homebrew/bin/brew ]]; then
-        eval "$(/opt/homebrew/bin/brew shellenv)"
-    elif [[ -f /usr/local/bin/brew ]]; then
-        eval "$(/usr/local/bin/brew shellenv)"
-    fi
-fi
-
-echo "Installing Node.js 22..."
-brew install node@22
-brew link --overwrite node@22
-
-# Verify installation
-node --version
-"#;
-
-    match shell::run_bash_output(script) {
-        Ok(output) => Ok(InstallResult {
-            success: true,
-            message: format!("Node.js installed successfully! {}", output),
-            error: None,
-        }),
-        Err(e) => Ok(InstallResult {
+    task.step("homebrew");
+    if let Err(e) = ensure_homebrew() {
+        return Ok(InstallResult {
             success: false,
             message: "Node.js installation failed".to_string(),
             error: Some(e),
-        }),
+            steps: Vec::new(),
+        });
     }
+
+    let steps = pkgmgr::install_with_progress(pkgmgr::PackageManager::Homebrew, Package::Node, &mut |_, _, name| task.step(name))?;
+    Ok(install_result_from_steps(Package::Node, steps))
 }
 
 /// Install Node.js on Linux
-async fn install_nodejs_linux() -> Result<InstallResult, String> {
-    // Install using NodeSource repository
-    let script = r#"
-# Detect package manager
-if command -v apt-get &> /dev/null; then
-    echo "Detected apt, using NodeSource repository..."
-    curl -fsSL https://deb.nodesource.com/setup_22.x | sudo -E bash -
-    sudo apt-get install -y nodejs
-elif command -v dnf &> /dev/null; then
-    echo "Detected dnf, using NodeSource repository..."
-    curl -fsSL https://rpm.nodesource.com/setup_22.x | sudo bash -
-    sudo dnf install -y nodejs
-elif command -v yum &> /dev/null; then
-    echo "Detected yum, using NodeSource repository..."
-    curl -fsSL https://rpm.nodesource.com/setup_22.x | sudo bash -
-    sudo yum install -y nodejs
-elif command -v pacman &> /dev/null; then
-    echo "Detected pacman..."
-    sudo pacman -S nodejs npm --noconfirm
-else
-    echo "Unable to detect a supported package manager"
-    exit 1
-fi
-
-# Verify installation
-node --version
-"#;
-
-    match shell::run_bash_output(script) {
-        Ok(output) => Ok(InstallResult {
-            success: true,
-            message: format!("Node.js installed successfully! {}", output),
-            error: None,
-        }),
+async fn install_nodejs_linux(task: &Task) -> Result<InstallResult, String> {
+    // apt/dnf/yum go through the NodeSource repository, pacman uses the distro package
+    let installed = pkgmgr::preferred_for(Package::Node)
+        .ok_or(format!("No supported package manager found to install {}", Package::Node.display_name()))
+        .and_then(|pm| pkgmgr::install_with_progress(pm, Package::Node, &mut |_, _, name| task.step(name)));
+    match installed {
+        Ok(steps) => Ok(install_result_from_steps(Package::Node, steps)),
         Err(e) => Ok(InstallResult {
             success: false,
             message: "Node.js installation failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
 /// Install OpenClaw
 #[command]
+#[specta::specta]
 pub async fn install_openclaw() -> Result<InstallResult, String> {
     info!("[Install OpenClaw] Starting OpenClaw installation...");
+    telemetry::record_feature("install_openclaw");
     let os = platform::get_os();
     info!("[Install OpenClaw] Detected operating system: {}", os);
+    let task = Task::begin("install-openclaw");
+    let pm = node_pm::active();
+    info!("[Install OpenClaw] Using package manager: {}", pm.name());
 
     let result = match os.as_str() {
-        "windows" => {
+        "windows" if wsl::active().is_none() => {
             info!("[Install OpenClaw] Using Windows installation method...");
-            install_openclaw_windows().await
+            install_openclaw_windows(&task, pm).await
         },
         _ => {
-            info!("[Install OpenClaw] Using Unix installation method (npm)...");
-            install_openclaw_unix().await
+            info!("[Install OpenClaw] Using Unix installation method ({})...", pm.name());
+            install_openclaw_unix(&task, pm).await
         },
     };
+    task.finish(&result);
+    if matches!(&result, Ok(r) if r.success) {
+        record_global_bin(pm).await;
+    }
 
     match &result {
         Ok(r) if r.success => info!("[Install OpenClaw] Installation successful"),
-        Ok(r) => warn!("[Install OpenClaw] Installation failed: {}", r.message),
-        Err(e) => error!("[Install OpenClaw] Installation error: {}", e),
+        Ok(r) => {
+            warn!("[Install OpenClaw] Installation failed: {}", r.message);
+            telemetry::record_error("install_openclaw", r.error.as_deref().unwrap_or(&r.message));
+        }
+        Err(e) => {
+            error!("[Install OpenClaw] Installation error: {}", e);
+            telemetry::record_error("install_openclaw", e);
+        }
     }
 
     result
 }
 
 /// Install OpenClaw on Windows
-async fn install_openclaw_windows() -> Result<InstallResult, String> {
+async fn install_openclaw_windows(task: &Task, pm: NodePackageManager) -> Result<InstallResult, String> {
     let script = r#"
 $ErrorActionPreference = 'Stop'
 
@@ -750,8 +794,8 @@ if (-not $nodeVersion) {
     exit 1
 }
 
-Write-Host "Installing OpenClaw using npm..."
-npm install -g openclaw@latest --unsafe-perm
+Write-Host "Installing OpenClaw using {pm}..."
+{install}
 
 # Verify installation
 $openclawVersion = openclaw --version 2>$null
@@ -762,21 +806,26 @@ if ($openclawVersion) {
     Write-Host "OpenClaw installation failed"
     exit 1
 }
-"#;
+"#
+    .replace("{pm}", pm.name())
+    .replace("{install}", &openclaw_install_command(pm));
 
-    match shell::run_powershell_output(script) {
+    task.step(&pm.global_add("openclaw"));
+    match task.run(&mut shell::powershell_command(&script)) {
         Ok(output) => {
             if get_openclaw_version().is_some() {
                 Ok(InstallResult {
                     success: true,
                     message: "OpenClaw installed successfully!".to_string(),
                     error: None,
+                    steps: Vec::new(),
                 })
             } else {
                 Ok(InstallResult {
                     success: false,
                     message: "Application restart required after installation".to_string(),
                     error: Some(output),
+                    steps: Vec::new(),
                 })
             }
         }
@@ -784,12 +833,13 @@ if ($openclawVersion) {
             success: false,
             message: "OpenClaw installation failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
 /// Install OpenClaw on Unix systems
-async fn install_openclaw_unix() -> Result<InstallResult, String> {
+async fn install_openclaw_unix(task: &Task, pm: NodePackageManager) -> Result<InstallResult, String> {
     let script = r#"
 # Check Node.js
 if ! command -v node &> /dev/null; then
@@ -797,29 +847,66 @@ if ! command -v node &> /dev/null; then
     exit 1
 fi
 
-echo "Installing OpenClaw using npm..."
-npm install -g openclaw@latest --unsafe-perm
+echo "Installing OpenClaw using {pm}..."
+{install}
 
 # Verify installation
 openclaw --version
-"#;
+"#
+    .replace("{pm}", pm.name())
+    .replace("{install}", &openclaw_install_command(pm));
 
-    match shell::run_bash_output(script) {
+    task.step(&pm.global_add("openclaw"));
+    match task.run(&mut shell::bash_command(&tasks::with_exit_marker(&script))) {
         Ok(output) => Ok(InstallResult {
             success: true,
             message: format!("OpenClaw installed successfully! {}", output),
             error: None,
+            steps: Vec::new(),
         }),
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw installation failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
+/// Global install command line for the latest OpenClaw
+fn openclaw_install_command(pm: NodePackageManager) -> String {
+    match pm {
+        NodePackageManager::Npm => format!("{} --unsafe-perm", pm.global_add("openclaw@latest")),
+        _ => pm.global_add("openclaw@latest"),
+    }
+}
+
+/// Remember where `pm` links global binaries so openclaw is found even when that directory
+/// is not on the PATH the app was started with
+async fn record_global_bin(pm: NodePackageManager) {
+    // Inside WSL openclaw is looked up through the distro's own PATH
+    if wsl::active().is_some() {
+        return;
+    }
+    let Some(dir) = node_pm::resolve_global_bin(pm) else {
+        warn!("[Install OpenClaw] Could not resolve the {} global bin directory", pm.name());
+        return;
+    };
+    info!("[Install OpenClaw] {} global bin directory: {}", pm.name(), dir);
+    let _lock = locks::lock(&[ConfigResource::Manager], "record_global_bin").await;
+    let mut manager_config = load_manager_config().unwrap_or_default();
+    if !manager_config.is_object() {
+        manager_config = serde_json::json!({});
+    }
+    manager_config[node_pm::GLOBAL_BIN_KEY] = serde_json::json!(dir);
+    if let Err(e) = save_manager_config(&manager_config) {
+        warn!("[Install OpenClaw] Failed to save global bin directory: {}", e);
+    }
+}
+
 /// Initialize OpenClaw configuration
 #[command]
+#[specta::specta]
 pub async fn init_openclaw_config() -> Result<InstallResult, String> {
     info!("[Init Config] Starting OpenClaw configuration initialization...");
 
@@ -834,13 +921,14 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
             success: false,
             message: "Failed to create config directory".to_string(),
             error: Some(e.to_string()),
+            steps: Vec::new(),
         });
     }
 
     // Create subdirectories
     let subdirs = ["agents/main/sessions", "agents/main/agent", "credentials"];
     for subdir in subdirs {
-        let path = format!("{}/{}", config_dir, subdir);
+        let path = platform::join_path(&config_dir, &subdir.split('/').collect::<Vec<_>>());
         info!("[Init Config] Creating subdirectory: {}", subdir);
         if let Err(e) = std::fs::create_dir_all(&path) {
             error!("[Init Config] Failed to create directory: {} - {}", subdir, e);
@@ -848,6 +936,7 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
                 success: false,
                 message: format!("Failed to create directory: {}", subdir),
                 error: Some(e.to_string()),
+                steps: Vec::new(),
             });
         }
     }
@@ -885,6 +974,7 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
                 success: true,
                 message: "Configuration initialized successfully!".to_string(),
                 error: None,
+                steps: Vec::new(),
             })
         },
         Err(e) => {
@@ -893,6 +983,7 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
                 success: false,
                 message: "Configuration initialization failed".to_string(),
                 error: Some(e),
+                steps: Vec::new(),
             })
         },
     }
@@ -900,6 +991,7 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
 
 /// Open terminal to execute installation script (for scenarios requiring administrator privileges)
 #[command]
+#[specta::specta]
 pub async fn open_install_terminal(install_type: String) -> Result<String, String> {
     match install_type.as_str() {
         "nodejs" => open_nodejs_install_terminal().await,
@@ -953,10 +1045,8 @@ if ! command -v brew &> /dev/null; then
     echo "Installing Homebrew..."
     /bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)"
 
-    if [[ -f /opt/homebrew/bin/brew ]]; then
-        eval "$(/opt/homebrew/bin/brew shellenv)"
-    elif [[ -f /usr/local/bin/brew ]]; then
-        eval "$(/usr/local/bin/brew shellenv)"
+    if [[ -f {brew_prefix}/bin/brew ]]; then
+        eval "$({brew_prefix}/bin/brew shellenv)"
     fi
 fi
 
@@ -969,20 +1059,17 @@ echo "Installation complete!"
 node --version
 echo ""
 read -p "Press Enter to close this window..."
-"#;
+"#
+        .replace("{brew_prefix}", platform::homebrew_prefix());
 
         let script_path = "/tmp/openclaw_install_nodejs.command";
-        std::fs::write(script_path, script_content)
+        std::fs::write(script_path, &script_content)
             .map_err(|e| format!("Failed to create script: {}", e))?;
 
-        std::process::Command::new("chmod")
-            .args(["+x", script_path])
-            .output()
+        shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
             .map_err(|e| format!("Failed to set permissions: {}", e))?;
 
-        std::process::Command::new("open")
-            .arg(script_path)
-            .spawn()
+        shell::spawn(std::process::Command::new("open").arg(script_path))
             .map_err(|e| format!("Failed to launch terminal: {}", e))?;
 
         Ok("Installation terminal opened".to_string())
@@ -993,6 +1080,7 @@ read -p "Press Enter to close this window..."
 
 /// Open terminal to install OpenClaw
 async fn open_openclaw_install_terminal() -> Result<String, String> {
+    let install = node_pm::active().global_add("openclaw@latest");
     if platform::is_windows() {
         let script = r#"
 Start-Process powershell -ArgumentList '-NoExit', '-Command', '
@@ -1002,7 +1090,7 @@ Write-Host "========================================" -ForegroundColor Cyan
 Write-Host ""
 
 Write-Host "Installing OpenClaw..." -ForegroundColor Yellow
-npm install -g openclaw@latest
+{install}
 
 Write-Host ""
 Write-Host "Initializing configuration..."
@@ -1014,8 +1102,9 @@ openclaw --version
 Write-Host ""
 Read-Host "Press Enter to close this window"
 '
-"#;
-        shell::run_powershell_output(script)?;
+"#
+        .replace("{install}", &install);
+        shell::run_powershell_output(&script)?;
         Ok("Installation terminal opened".to_string())
     } else if platform::is_macos() {
         let script_content = r#"#!/bin/bash
@@ -1026,7 +1115,7 @@ echo "========================================"
 echo ""
 
 echo "Installing OpenClaw..."
-npm install -g openclaw@latest
+{install}
 
 echo ""
 echo "Initializing configuration..."
@@ -1041,20 +1130,17 @@ echo "Installation complete!"
 openclaw --version
 echo ""
 read -p "Press Enter to close this window..."
-"#;
+"#
+        .replace("{install}", &install);
 
         let script_path = "/tmp/openclaw_install_openclaw.command";
-        std::fs::write(script_path, script_content)
+        std::fs::write(script_path, &script_content)
             .map_err(|e| format!("Failed to create script: {}", e))?;
 
-        std::process::Command::new("chmod")
-            .args(["+x", script_path])
-            .output()
+        shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
             .map_err(|e| format!("Failed to set permissions: {}", e))?;
 
-        std::process::Command::new("open")
-            .arg(script_path)
-            .spawn()
+        shell::spawn(std::process::Command::new("open").arg(script_path))
             .map_err(|e| format!("Failed to launch terminal: {}", e))?;
 
         Ok("Installation terminal opened".to_string())
@@ -1068,7 +1154,7 @@ echo "========================================"
 echo ""
 
 echo "Installing OpenClaw..."
-npm install -g openclaw@latest
+{install}
 
 echo ""
 echo "Initializing configuration..."
@@ -1083,35 +1169,33 @@ echo "Installation complete!"
 openclaw --version
 echo ""
 read -p "Press Enter to close..."
-"#;
+"#
+        .replace("{install}", &install);
 
         let script_path = "/tmp/openclaw_install_openclaw.sh";
-        std::fs::write(script_path, script_content)
+        std::fs::write(script_path, &script_content)
             .map_err(|e| format!("Failed to create script: {}", e))?;
 
-        std::process::Command::new("chmod")
-            .args(["+x", script_path])
-            .output()
+        shell::output(std::process::Command::new("chmod").args(["+x", script_path]))
             .map_err(|e| format!("Failed to set permissions: {}", e))?;
 
         // Try different terminals
         let terminals = ["gnome-terminal", "xfce4-terminal", "konsole", "xterm"];
         for term in terminals {
-            if std::process::Command::new(term)
-                .args(["--", script_path])
-                .spawn()
+            if shell::spawn(std::process::Command::new(term).args(["--", script_path]))
                 .is_ok()
             {
                 return Ok("Installation terminal opened".to_string());
             }
         }
 
-        Err("Unable to launch terminal, please run manually: npm install -g openclaw".to_string())
+        Err(format!("Unable to launch terminal, please run manually: {}", install))
     }
 }
 
 /// Uninstall OpenClaw
 #[command]
+#[specta::specta]
 pub async fn uninstall_openclaw() -> Result<InstallResult, String> {
     info!("[Uninstall OpenClaw] Starting OpenClaw uninstallation...");
     let os = platform::get_os();
@@ -1122,18 +1206,19 @@ pub async fn uninstall_openclaw() -> Result<InstallResult, String> {
     let _ = shell::run_openclaw(&["gateway", "stop"]);
     std::thread::sleep(std::time::Duration::from_millis(500));
 
+    let pm = node_pm::active();
     let result = match os.as_str() {
-        "windows" => {
+        "windows" if wsl::active().is_none() => {
             info!("[Uninstall OpenClaw] Using Windows uninstallation method...");
-            uninstall_openclaw_windows().await
+            uninstall_openclaw_windows(pm).await
         },
         _ => {
-            info!("[Uninstall OpenClaw] Using Unix uninstallation method (npm)...");
-            uninstall_openclaw_unix().await
+            info!("[Uninstall OpenClaw] Using Unix uninstallation method ({})...", pm.name());
+            uninstall_openclaw_unix(pm).await
         },
     };
 
-    // After npm uninstall, delete the .openclaw config directory
+    // After the package manager uninstall, delete the .openclaw config directory
     if let Some(home) = dirs::home_dir() {
         let openclaw_dir = home.join(".openclaw");
         if openclaw_dir.exists() {
@@ -1159,13 +1244,14 @@ pub async fn uninstall_openclaw() -> Result<InstallResult, String> {
 }
 
 /// Uninstall OpenClaw on Windows
-async fn uninstall_openclaw_windows() -> Result<InstallResult, String> {
-    // Use cmd.exe to execute npm uninstall to avoid PowerShell execution policy issues
-    info!("[Uninstall OpenClaw] Executing npm uninstall -g openclaw...");
+async fn uninstall_openclaw_windows(pm: NodePackageManager) -> Result<InstallResult, String> {
+    // Use cmd.exe to execute the uninstall to avoid PowerShell execution policy issues
+    let uninstall = pm.global_remove("openclaw");
+    info!("[Uninstall OpenClaw] Executing {}...", uninstall);
 
-    match shell::run_cmd_output("npm uninstall -g openclaw") {
+    match shell::run_cmd_output(&uninstall) {
         Ok(output) => {
-            info!("[Uninstall OpenClaw] npm output: {}", output);
+            info!("[Uninstall OpenClaw] {} output: {}", pm.name(), output);
 
             // Verify uninstallation was successful
             std::thread::sleep(std::time::Duration::from_millis(500));
@@ -1174,31 +1260,34 @@ async fn uninstall_openclaw_windows() -> Result<InstallResult, String> {
                     success: true,
                     message: "OpenClaw has been successfully uninstalled!".to_string(),
                     error: None,
+                    steps: Vec::new(),
                 })
             } else {
                 Ok(InstallResult {
                     success: false,
                     message: "Uninstall command executed but OpenClaw still exists, please try manual uninstallation".to_string(),
                     error: Some(output),
+                    steps: Vec::new(),
                 })
             }
         }
         Err(e) => {
-            warn!("[Uninstall OpenClaw] npm uninstall failed: {}", e);
+            warn!("[Uninstall OpenClaw] {} failed: {}", uninstall, e);
             Ok(InstallResult {
                 success: false,
                 message: "OpenClaw uninstallation failed".to_string(),
                 error: Some(e),
+                steps: Vec::new(),
             })
         }
     }
 }
 
 /// Uninstall OpenClaw on Unix systems
-async fn uninstall_openclaw_unix() -> Result<InstallResult, String> {
+async fn uninstall_openclaw_unix(pm: NodePackageManager) -> Result<InstallResult, String> {
     let script = r#"
 echo "Uninstalling OpenClaw..."
-npm uninstall -g openclaw
+{uninstall}
 
 # Verify uninstallation
 if command -v openclaw &> /dev/null; then
@@ -1208,24 +1297,27 @@ else
     echo "OpenClaw has been successfully uninstalled"
     exit 0
 fi
-"#;
+"#
+    .replace("{uninstall}", &pm.global_remove("openclaw"));
 
-    match shell::run_bash_output(script) {
+    match shell::run_bash_output(&script) {
         Ok(output) => Ok(InstallResult {
             success: true,
             message: format!("OpenClaw has been successfully uninstalled! {}", output),
             error: None,
+            steps: Vec::new(),
         }),
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw uninstallation failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
 /// Version update information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct UpdateInfo {
     /// Whether an update is available
     pub update_available: bool,
@@ -1239,6 +1331,7 @@ pub struct UpdateInfo {
 
 /// Check for OpenClaw updates
 #[command]
+#[specta::specta]
 pub async fn check_openclaw_update() -> Result<UpdateInfo, String> {
     info!("[Version Check] Starting OpenClaw update check...");
 
@@ -1286,11 +1379,12 @@ pub async fn check_openclaw_update() -> Result<UpdateInfo, String> {
 
 /// Get the latest version from npm registry
 fn get_latest_openclaw_version() -> Option<String> {
-    // Use npm view to get the latest version
-    let result = if platform::is_windows() {
-        shell::run_cmd_output("npm view openclaw version")
+    // Ask the registry through the active package manager
+    let view = node_pm::active().view_version("openclaw");
+    let result = if platform::is_windows() && wsl::active().is_none() {
+        shell::run_cmd_output(&view)
     } else {
-        shell::run_bash_output("npm view openclaw version 2>/dev/null")
+        shell::run_bash_output(&format!("{} 2>/dev/null", view))
     };
 
     match result {
@@ -1344,25 +1438,33 @@ fn compare_versions(current: &str, latest: &str) -> bool {
 
 /// Update OpenClaw
 #[command]
+#[specta::specta]
 pub async fn update_openclaw() -> Result<InstallResult, String> {
     info!("[Update OpenClaw] Starting OpenClaw update...");
     let os = platform::get_os();
+    let task = Task::begin("update-openclaw");
 
     // Stop service first
     info!("[Update OpenClaw] Attempting to stop service...");
+    task.step("stop gateway");
     let _ = shell::run_openclaw(&["gateway", "stop"]);
     std::thread::sleep(std::time::Duration::from_millis(500));
 
+    let pm = node_pm::active();
     let result = match os.as_str() {
-        "windows" => {
+        "windows" if wsl::active().is_none() => {
             info!("[Update OpenClaw] Using Windows update method...");
-            update_openclaw_windows().await
+            update_openclaw_windows(&task, pm).await
         },
         _ => {
-            info!("[Update OpenClaw] Using Unix update method (npm)...");
-            update_openclaw_unix().await
+            info!("[Update OpenClaw] Using Unix update method ({})...", pm.name());
+            update_openclaw_unix(&task, pm).await
         },
     };
+    task.finish(&result);
+    if matches!(&result, Ok(r) if r.success) {
+        record_global_bin(pm).await;
+    }
 
     match &result {
         Ok(r) if r.success => info!("[Update OpenClaw] Update successful"),
@@ -1374,12 +1476,14 @@ pub async fn update_openclaw() -> Result<InstallResult, String> {
 }
 
 /// Update OpenClaw on Windows
-async fn update_openclaw_windows() -> Result<InstallResult, String> {
-    info!("[Update OpenClaw] Executing npm install -g openclaw@latest...");
+async fn update_openclaw_windows(task: &Task, pm: NodePackageManager) -> Result<InstallResult, String> {
+    let update = pm.global_add("openclaw@latest");
+    info!("[Update OpenClaw] Executing {}...", update);
 
-    match shell::run_cmd_output("npm install -g openclaw@latest") {
+    task.step(&pm.global_add("openclaw"));
+    match task.run(&mut shell::cmd_command(&update)) {
         Ok(output) => {
-            info!("[Update OpenClaw] npm output: {}", output);
+            info!("[Update OpenClaw] {} output: {}", pm.name(), output);
 
             // Get new version
             let new_version = get_openclaw_version();
@@ -1388,40 +1492,325 @@ async fn update_openclaw_windows() -> Result<InstallResult, String> {
                 success: true,
                 message: format!("OpenClaw has been updated to {}", new_version.unwrap_or("latest version".to_string())),
                 error: None,
+                steps: Vec::new(),
             })
         }
         Err(e) => {
-            warn!("[Update OpenClaw] npm install failed: {}", e);
+            warn!("[Update OpenClaw] {} failed: {}", update, e);
             Ok(InstallResult {
                 success: false,
                 message: "OpenClaw update failed".to_string(),
                 error: Some(e),
+                steps: Vec::new(),
             })
         }
     }
 }
 
 /// Update OpenClaw on Unix systems
-async fn update_openclaw_unix() -> Result<InstallResult, String> {
+async fn update_openclaw_unix(task: &Task, pm: NodePackageManager) -> Result<InstallResult, String> {
     let script = r#"
 echo "Updating OpenClaw..."
-npm install -g openclaw@latest
+{update}
 
 # Verify update
 openclaw --version
-"#;
+"#
+    .replace("{update}", &pm.global_add("openclaw@latest"));
 
-    match shell::run_bash_output(script) {
+    task.step(&pm.global_add("openclaw"));
+    match task.run(&mut shell::bash_command(&tasks::with_exit_marker(&script))) {
         Ok(output) => Ok(InstallResult {
             success: true,
             message: format!("OpenClaw has been updated! {}", output),
             error: None,
+            steps: Vec::new(),
         }),
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw update failed".to_string(),
             error: Some(e),
+            steps: Vec::new(),
         }),
     }
 }
 
+
+// ============ Environment Watcher ============
+
+/// Poll interval for detecting Node.js / OpenClaw installs made outside the Manager
+const ENVIRONMENT_POLL_SECS: u64 = 15;
+
+/// Payload of the `environment-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct EnvironmentChange {
+    pub node_version: Option<String>,
+    pub openclaw_version: Option<String>,
+    pub git_version: Option<String>,
+}
+
+fn snapshot_environment() -> EnvironmentChange {
+    EnvironmentChange {
+        node_version: get_node_version(),
+        openclaw_version: get_openclaw_version(),
+        git_version: get_git_version(),
+    }
+}
+
+/// Emit `environment-changed` when node/openclaw/git appear, disappear or change version
+pub fn start_environment_watcher() {
+    std::thread::spawn(move || {
+        let mut last = snapshot_environment();
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(ENVIRONMENT_POLL_SECS));
+
+            let current = snapshot_environment();
+            if current != last {
+                info!(
+                    "[Environment Watcher] Environment changed: node {:?} -> {:?}, openclaw {:?} -> {:?}",
+                    last.node_version, current.node_version, last.openclaw_version, current.openclaw_version
+                );
+                events::emit(ManagerEvent::alert(
+                    "environment-changed",
+                    AlertLevel::Info,
+                    "Node.js or OpenClaw installation changed",
+                    &current,
+                ));
+                last = current;
+            }
+        }
+    });
+}
+
+/// Package managers detected on this system (most preferred first)
+#[command]
+#[specta::specta]
+pub async fn get_package_managers() -> Result<Vec<pkgmgr::PackageManager>, String> {
+    let managers = pkgmgr::detect();
+    info!("[PkgMgr] Detected package managers: {:?}", managers);
+    Ok(managers)
+}
+
+/// Node.js package managers usable for global installs and which one is in use
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct NodePackageManagerStatus {
+    /// Preferred manager, None for automatic selection
+    pub preference: Option<NodePackageManager>,
+    /// Managers found on this system
+    pub available: Vec<NodePackageManager>,
+    /// Manager used for the next install (None when none is available)
+    pub active: Option<NodePackageManager>,
+    /// Global bin directory resolved after the last install or update
+    pub global_bin: Option<String>,
+}
+
+/// Detected npm / pnpm / yarn and the configured preference
+#[command]
+#[specta::specta]
+pub async fn get_node_package_manager() -> Result<NodePackageManagerStatus, String> {
+    let available = node_pm::detect();
+    let preference = node_pm::preference();
+    Ok(NodePackageManagerStatus {
+        preference,
+        active: node_pm::choose(preference, &available),
+        available,
+        global_bin: node_pm::global_bin(),
+    })
+}
+
+/// Set the package manager for OpenClaw, clawhub and MCP installs ("auto", "npm", "pnpm" or "yarn").
+/// A preferred manager that is not installed falls back to the automatic choice.
+#[command]
+#[specta::specta]
+pub async fn set_node_package_manager(preference: String) -> Result<NodePackageManagerStatus, String> {
+    let parsed = match preference.trim() {
+        "" | "auto" => None,
+        other => Some(NodePackageManager::parse(other).ok_or(format!(
+            "Unknown package manager '{}', expected auto, npm, pnpm or yarn",
+            other
+        ))?),
+    };
+    info!("[Node PM] Preferred package manager: {}", parsed.map(|pm| pm.name()).unwrap_or("auto"));
+
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_node_package_manager").await;
+    let mut manager_config = load_manager_config()?;
+    if !manager_config.is_object() {
+        manager_config = serde_json::json!({});
+    }
+    match parsed {
+        Some(pm) => manager_config[node_pm::PREFERENCE_KEY] = serde_json::json!(pm.name()),
+        None => {
+            if let Some(obj) = manager_config.as_object_mut() {
+                obj.remove(node_pm::PREFERENCE_KEY);
+            }
+        }
+    }
+    save_manager_config(&manager_config)?;
+    node_pm::set_preference(parsed);
+
+    get_node_package_manager().await
+}
+
+/// WSL execution mode settings
+#[command]
+#[specta::specta]
+pub async fn get_wsl_mode() -> Result<WslSettings, String> {
+    Ok(wsl::settings())
+}
+
+/// Run openclaw and node inside a WSL distro (`distro` defaults to WSL's default distro).
+/// The distro's ~/.openclaw becomes the config directory; Windows paths handed to openclaw
+/// are translated to /mnt/<drive> paths.
+#[command]
+#[specta::specta]
+pub async fn set_wsl_mode(enabled: bool, distro: Option<String>) -> Result<WslSettings, String> {
+    let settings = if enabled {
+        if !platform::is_windows() {
+            return Err("WSL mode is only available on Windows".to_string());
+        }
+        let distros = wsl::detect_distros();
+        let chosen = match distro.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(name) => distros
+                .iter()
+                .find(|d| d.name.eq_ignore_ascii_case(name))
+                .ok_or(format!("WSL distro '{}' is not installed", name))?,
+            None => distros
+                .iter()
+                .find(|d| d.default)
+                .or(distros.first())
+                .ok_or("No WSL distro is installed (run: wsl --install)")?,
+        };
+        let home = wsl::run(&chosen.name, "echo \"$HOME\"")
+            .ok()
+            .filter(|h| h.starts_with('/'))
+            .ok_or(format!("Could not resolve the home directory in {}", chosen.name))?;
+        info!("[WSL] Enabling WSL mode: distro={}, home={}", chosen.name, home);
+        WslSettings {
+            enabled: true,
+            distro: Some(chosen.name.clone()),
+            home: Some(home),
+        }
+    } else {
+        info!("[WSL] Disabling WSL mode");
+        WslSettings {
+            enabled: false,
+            ..wsl::settings()
+        }
+    };
+
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_wsl_mode").await;
+    let mut manager_config = load_manager_config()?;
+    if !manager_config.is_object() {
+        manager_config = serde_json::json!({});
+    }
+    manager_config[wsl::SETTINGS_KEY] = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    save_manager_config(&manager_config)?;
+    wsl::set_settings(settings.clone());
+    Ok(settings)
+}
+
+/// Upgrade Node.js, Git or cloudflared through the system package manager
+#[command]
+#[specta::specta]
+pub async fn upgrade_package(package: Package) -> Result<InstallResult, String> {
+    info!("[PkgMgr] Upgrading {}...", package.display_name());
+    let steps = pkgmgr::upgrade(package)?;
+    let success = pkgmgr::all_succeeded(&steps);
+    Ok(InstallResult {
+        success,
+        message: if success {
+            format!("{} upgraded: {}", package.display_name(), pkgmgr::version(package).unwrap_or_default())
+        } else {
+            format!("{} upgrade failed", package.display_name())
+        },
+        error: steps.iter().find(|s| !s.success).map(|s| s.output.clone()),
+        steps,
+    })
+}
+
+/// Install Git through the system package manager (winget/Scoop/Homebrew/apt/dnf/yum/pacman)
+/// Emits `install-progress` events for each step.
+#[command]
+#[specta::specta]
+pub async fn install_git() -> Result<InstallResult, String> {
+
+    info!("[Install Git] Starting Git installation...");
+    telemetry::record_feature("install_git");
+
+    let task = Task::begin("install-git");
+    let result = install_git_steps(&task);
+    task.finish(&result);
+    let result = result?;
+
+    match &result {
+        r if r.success => info!("[Install Git] Installation successful"),
+        r => {
+            warn!("[Install Git] Installation failed: {}", r.message);
+            telemetry::record_error("install_git", r.error.as_deref().unwrap_or(&r.message));
+        }
+    }
+
+    Ok(result)
+}
+
+/// install_git without the logging, recorded as `task`
+fn install_git_steps(task: &Task) -> Result<InstallResult, String> {
+    if let Some(version) = get_git_version() {
+        info!("[Install Git] Git is already installed: {}", version);
+        return Ok(InstallResult {
+            success: true,
+            message: format!("Git is already installed: {}", version),
+            error: None,
+            steps: Vec::new(),
+        });
+    }
+
+    if platform::is_macos() {
+        task.step("homebrew");
+        if let Err(e) = ensure_homebrew() {
+            return Ok(InstallResult {
+                success: false,
+                message: "Git installation failed".to_string(),
+                error: Some(e),
+                steps: Vec::new(),
+            });
+        }
+    }
+
+    let pm = match pkgmgr::preferred_for(Package::Git) {
+        Some(pm) => pm,
+        None => {
+            error!("[Install Git] No supported package manager found");
+            return Ok(InstallResult {
+                success: false,
+                message: "No supported package manager found".to_string(),
+                error: Some("Please install Git manually from https://git-scm.com/downloads".to_string()),
+                steps: Vec::new(),
+            });
+        }
+    };
+    info!("[Install Git] Using package manager: {:?}", pm);
+
+    let steps = pkgmgr::install_with_progress(pm, Package::Git, &mut |index, total, name| {
+        task.step(name);
+        events::emit(ManagerEvent::InstallProgress(InstallProgress {
+            step: name.to_string(),
+            progress: ((index * 100) / total.max(1)) as u8,
+            message: format!("{} ({}/{})", name, index + 1, total),
+            error: None,
+        }));
+    })?;
+
+    for line in steps.iter().flat_map(|step| step.output.lines()) {
+        events::emit(ManagerEvent::LogLine { source: "install-git".to_string(), line: line.to_string() });
+    }
+    let result = install_result_from_steps(Package::Git, steps);
+    events::emit(ManagerEvent::InstallProgress(InstallProgress {
+        step: "done".to_string(),
+        progress: 100,
+        message: result.message.clone(),
+        error: result.error.clone(),
+    }));
+
+    Ok(result)
+}
@@ -0,0 +1,136 @@
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::command;
+
+/// Config files whose read-modify-write cycles are serialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigResource {
+    /// openclaw.json (or the open draft)
+    OpenClaw,
+    /// manager.json
+    Manager,
+    /// mcps.json
+    Mcps,
+}
+
+#[derive(Default)]
+struct ResourceLock {
+    mutex: tokio::sync::Mutex<()>,
+    /// Commands waiting for or holding the lock
+    queued: AtomicUsize,
+    holder: std::sync::Mutex<Option<String>>,
+}
+
+/// One async mutex per config file, part of the managed AppState so every mutating command
+/// shares the same locks; a command takes the locks for every file it writes before loading.
+#[derive(Default)]
+pub struct ConfigLocks {
+    openclaw: ResourceLock,
+    manager: ResourceLock,
+    mcps: ResourceLock,
+}
+
+impl ConfigLocks {
+    fn get(&self, resource: ConfigResource) -> &ResourceLock {
+        match resource {
+            ConfigResource::OpenClaw => &self.openclaw,
+            ConfigResource::Manager => &self.manager,
+            ConfigResource::Mcps => &self.mcps,
+        }
+    }
+}
+
+fn config_locks() -> &'static ConfigLocks {
+    &crate::state::app_state().locks
+}
+
+/// Held locks; released (and the queue depth decremented) on drop
+pub struct ConfigLockGuard {
+    held: Vec<(&'static ResourceLock, tokio::sync::MutexGuard<'static, ()>)>,
+}
+
+impl Drop for ConfigLockGuard {
+    fn drop(&mut self) {
+        for (lock, _) in &self.held {
+            if let Ok(mut holder) = lock.holder.lock() {
+                *holder = None;
+            }
+            lock.queued.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Wait for the locks of every file `command` is about to modify. Locks are always taken in the
+/// same order, so commands touching several files cannot deadlock.
+pub async fn lock(resources: &[ConfigResource], command: &str) -> ConfigLockGuard {
+    let mut resources = resources.to_vec();
+    resources.sort();
+    resources.dedup();
+
+    let locks = config_locks();
+    let mut guard = ConfigLockGuard { held: Vec::new() };
+    for resource in resources {
+        let lock = locks.get(resource);
+        let waiting = lock.queued.fetch_add(1, Ordering::SeqCst);
+        if waiting > 0 {
+            debug!("[Config Lock] {} waiting for {:?} ({} ahead)", command, resource, waiting);
+        }
+        let held = lock.mutex.lock().await;
+        if let Ok(mut holder) = lock.holder.lock() {
+            *holder = Some(command.to_string());
+        }
+        guard.held.push((lock, held));
+    }
+    guard
+}
+
+/// `lock` for background threads (std threads outside the async runtime)
+pub fn lock_blocking(resources: &[ConfigResource], command: &str) -> ConfigLockGuard {
+    tauri::async_runtime::block_on(lock(resources, command))
+}
+
+/// Warn when a config file is written while no command holds its lock: that write can
+/// interleave with another command's read-modify-write and lose one of the changes
+pub(crate) fn warn_if_unlocked(resource: ConfigResource, writer: &str) {
+    let held = config_locks()
+        .get(resource)
+        .holder
+        .lock()
+        .map(|h| h.is_some())
+        .unwrap_or(true);
+    if !held {
+        warn!("[Config Lock] {:?} written by {} without holding its lock", resource, writer);
+    }
+}
+
+/// Lock state of one config file, for debugging
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ConfigLockStatus {
+    pub resource: ConfigResource,
+    /// Command currently holding the lock
+    pub held_by: Option<String>,
+    /// Commands waiting behind the holder
+    pub queue_depth: usize,
+}
+
+/// Get holder and queue depth of each config file lock
+#[command]
+#[specta::specta]
+pub async fn get_config_lock_status() -> Result<Vec<ConfigLockStatus>, String> {
+    let locks = config_locks();
+    Ok([ConfigResource::OpenClaw, ConfigResource::Manager, ConfigResource::Mcps]
+        .into_iter()
+        .map(|resource| {
+            let lock = locks.get(resource);
+            let held_by = lock.holder.lock().ok().and_then(|h| h.clone());
+            let queued = lock.queued.load(Ordering::SeqCst);
+            ConfigLockStatus {
+                resource,
+                queue_depth: queued.saturating_sub(held_by.is_some() as usize),
+                held_by,
+            }
+        })
+        .collect())
+}
@@ -1,6 +1,21 @@
+pub mod api;
+pub mod attachments;
+pub mod backup;
+pub mod chat;
 pub mod config;
+pub mod console;
+pub mod contacts;
+pub mod crash_reports;
 pub mod diagnostics;
+pub mod history;
 pub mod installer;
+pub mod locks;
+pub mod placeholders;
 pub mod process;
 pub mod service;
+pub mod sessions;
 pub mod skills;
+pub mod snapshots;
+pub mod tasks;
+pub mod telemetry;
+pub mod trash;
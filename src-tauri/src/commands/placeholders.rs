@@ -0,0 +1,364 @@
+use crate::commands::config::{
+    load_live_openclaw_config, load_manager_config, load_openclaw_config,
+    save_live_openclaw_config, save_manager_config,
+};
+use crate::commands::locks::{self, ConfigResource};
+use crate::utils::placeholders::{self, Placeholder, PlaceholderKind};
+use crate::utils::{file, keychain, platform};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::command;
+
+/// How one placeholder in openclaw.json resolves
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PlaceholderStatus {
+    /// JSON pointer of the value holding it
+    pub pointer: String,
+    /// As written, e.g. `${secret:telegram_token}`
+    pub placeholder: String,
+    pub kind: PlaceholderKind,
+    pub name: String,
+    /// Env variable the gateway reads the value from
+    pub env_var: String,
+    /// "resolved", "missing" or "invalid" (name OpenClaw cannot substitute)
+    pub status: String,
+    /// "environment", "env file", "secret store" or "keychain"
+    pub source: Option<String>,
+}
+
+/// Placeholders in openclaw.json and whether the gateway can start with them
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PlaceholderReport {
+    pub placeholders: Vec<PlaceholderStatus>,
+    pub unresolved: usize,
+}
+
+/// A stored secret (the value never leaves the backend)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ConfigSecretInfo {
+    pub name: String,
+    pub env_var: String,
+    /// "file" (manager/secrets.json) or "keychain" (OS credential store)
+    pub storage: String,
+    /// Config values referencing it
+    pub used_at: Vec<String>,
+}
+
+fn get_secrets_file_path() -> String {
+    if platform::is_windows() {
+        format!("{}\\secrets.json", platform::get_manager_data_dir())
+    } else {
+        format!("{}/secrets.json", platform::get_manager_data_dir())
+    }
+}
+
+/// Secrets kept in manager/secrets.json (readable by the user only)
+fn load_file_secrets() -> BTreeMap<String, String> {
+    file::read_file(&get_secrets_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Values for `${secret:name}` placeholders: secrets.json plus the keychain entries
+fn load_secrets() -> BTreeMap<String, String> {
+    let mut secrets = load_file_secrets();
+    secrets.extend(keychain::values());
+    secrets
+}
+
+fn save_secrets(secrets: &BTreeMap<String, String>) -> Result<(), String> {
+    let path = get_secrets_file_path();
+    let content = serde_json::to_string_pretty(secrets)
+        .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+    file::write_file(&path, &content).map_err(|e| format!("Failed to write secrets: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// Current value of an env placeholder and where it came from: the env file, then the
+/// Manager's own environment
+fn env_value(name: &str) -> Option<(String, &'static str)> {
+    if let Some(value) =
+        file::read_env_value(&platform::get_env_file_path(), name).filter(|v| !v.is_empty())
+    {
+        return Some((value, "env file"));
+    }
+    std::env::var(name)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(|v| (v, "environment"))
+}
+
+/// Value behind a placeholder. `secrets` comes from `load_secrets`, so keychain entries win
+/// over the secret store; env variables come from `env_value`.
+fn lookup(p: &Placeholder, secrets: &BTreeMap<String, String>) -> Option<String> {
+    match p.kind {
+        PlaceholderKind::Secret => secrets.get(&p.name).cloned(),
+        PlaceholderKind::Env => env_value(&p.name).map(|(value, _)| value),
+    }
+}
+
+/// Env variables for the values behind `config`'s placeholders, for an openclaw process run
+/// on a config that isn't the live one (e.g. a model probe)
+pub(crate) fn config_env(config: &serde_json::Value) -> Vec<(String, String)> {
+    let found = placeholders::find(config);
+    let secrets = if found.iter().any(|p| p.kind == PlaceholderKind::Secret) {
+        load_secrets()
+    } else {
+        BTreeMap::new()
+    };
+    placeholders::resolve_env(config, |p| lookup(p, &secrets))
+}
+
+/// A config value with its placeholders replaced by what they resolve to (the value itself
+/// when it has none); None when one of them doesn't resolve
+pub(crate) fn resolve_value(text: &str) -> Option<String> {
+    let secrets = if placeholders::find(&serde_json::Value::from(text))
+        .iter()
+        .any(|p| p.kind == PlaceholderKind::Secret)
+    {
+        load_secrets()
+    } else {
+        BTreeMap::new()
+    };
+    placeholders::substitute(text, |p| lookup(p, &secrets))
+}
+
+fn collect_report(config: &serde_json::Value) -> PlaceholderReport {
+    let secrets = load_secrets();
+    let placeholders: Vec<PlaceholderStatus> = placeholders::find(config)
+        .into_iter()
+        .map(|p| {
+            let source = match p.kind {
+                _ if !p.is_valid() => None,
+                PlaceholderKind::Secret if keychain::names().contains(&p.name) => {
+                    secrets.get(&p.name).map(|_| "keychain")
+                }
+                PlaceholderKind::Secret => secrets.get(&p.name).map(|_| "secret store"),
+                PlaceholderKind::Env => env_value(&p.name).map(|(_, source)| source),
+            };
+            let status = match source {
+                Some(_) => "resolved",
+                None if !p.is_valid() => "invalid",
+                None => "missing",
+            };
+            PlaceholderStatus {
+                env_var: p.env_var(),
+                pointer: p.pointer,
+                placeholder: p.text,
+                kind: p.kind,
+                name: p.name,
+                status: status.to_string(),
+                source: source.map(String::from),
+            }
+        })
+        .collect();
+    PlaceholderReport {
+        unresolved: placeholders.iter().filter(|p| p.status != "resolved").count(),
+        placeholders,
+    }
+}
+
+/// Placeholders that would stop the gateway from loading its (live) config
+pub(crate) fn unresolved_placeholders() -> Vec<PlaceholderStatus> {
+    let config = match load_live_openclaw_config() {
+        Ok(config) => config,
+        Err(_) => return Vec::new(),
+    };
+    collect_report(&config)
+        .placeholders
+        .into_iter()
+        .filter(|p| p.status != "resolved")
+        .collect()
+}
+
+/// Env variables for the values behind the live config's placeholders, passed to every
+/// openclaw process (see shell::set_openclaw_env). Env file values are included because
+/// openclaw commands other than the gateway do not read the env file.
+pub(crate) fn placeholder_env() -> Vec<(String, String)> {
+    let config = match load_live_openclaw_config() {
+        Ok(config) => config,
+        Err(_) => return Vec::new(),
+    };
+    let env = config_env(&config);
+    if !env.is_empty() {
+        debug!("[Placeholders] Passing {} placeholder value(s)", env.len());
+    }
+    env
+}
+
+/// Rewrite `${env:VAR}` / `${secret:name}` in the live openclaw.json (the one the gateway
+/// reads, even while a draft is open) to the `${VAR}` form OpenClaw substitutes.
+/// Called with the OpenClaw config lock held, right before the gateway starts.
+pub(crate) fn normalize_placeholders() -> Result<(), String> {
+    let config = load_live_openclaw_config()?;
+    if let Some(native) = placeholders::to_native(&config) {
+        info!("[Placeholders] Rewriting Manager placeholders to env references");
+        save_live_openclaw_config(&native, "normalize_placeholders")?;
+    }
+    Ok(())
+}
+
+/// List the placeholders in openclaw.json and whether each one resolves
+#[command]
+#[specta::specta]
+pub async fn validate_config_placeholders() -> Result<PlaceholderReport, String> {
+    info!("[Placeholders] Validating config placeholders...");
+    let report = collect_report(&load_openclaw_config()?);
+    info!(
+        "[Placeholders] {} placeholder(s), {} unresolved",
+        report.placeholders.len(),
+        report.unresolved
+    );
+    Ok(report)
+}
+
+/// List stored secrets by name with the config values using them
+#[command]
+#[specta::specta]
+pub async fn list_config_secrets() -> Result<Vec<ConfigSecretInfo>, String> {
+    let config = load_openclaw_config().unwrap_or_default();
+    let found = placeholders::find(&config);
+    let in_keychain = keychain::names();
+    let mut names: Vec<String> = load_file_secrets().into_keys().collect();
+    names.extend(in_keychain.iter().cloned());
+    names.sort();
+    names.dedup();
+    Ok(names
+        .iter()
+        .map(|name| ConfigSecretInfo {
+            name: name.clone(),
+            env_var: placeholders::secret_env_var(name),
+            storage: if in_keychain.contains(name) { "keychain" } else { "file" }.to_string(),
+            used_at: found
+                .iter()
+                .filter(|p| p.kind == PlaceholderKind::Secret && p.name == *name)
+                .map(|p| p.pointer.clone())
+                .collect(),
+        })
+        .collect())
+}
+
+fn save_keychain_names(names: Vec<String>) -> Result<(), String> {
+    let mut manager_config = load_manager_config()?;
+    manager_config[keychain::SETTINGS_KEY] = serde_json::json!(names);
+    save_manager_config(&manager_config)?;
+    keychain::set_names(names);
+    Ok(())
+}
+
+/// Store a secret in secrets.json or the keychain, removing it from the other place.
+/// The caller holds the Manager config lock.
+pub(crate) fn store_secret(name: &str, value: &str, in_keychain: bool) -> Result<(), String> {
+    if !placeholders::is_valid_secret_name(name) {
+        return Err("Secret names may only contain letters, digits and underscores".to_string());
+    }
+    if value.is_empty() {
+        return Err("Secret value is empty".to_string());
+    }
+    let mut names = keychain::names();
+    let mut file_secrets = load_file_secrets();
+    if in_keychain {
+        info!("[Placeholders] Storing secret '{}' in the keychain", name);
+        keychain::set(name, value)?;
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+            save_keychain_names(names)?;
+        }
+        if file_secrets.remove(name).is_some() {
+            save_secrets(&file_secrets)?;
+        }
+    } else {
+        info!("[Placeholders] Storing secret '{}'", name);
+        file_secrets.insert(name.to_string(), value.to_string());
+        save_secrets(&file_secrets)?;
+        if names.iter().any(|n| n == name) {
+            names.retain(|n| n != name);
+            save_keychain_names(names)?;
+            if let Err(e) = keychain::delete(name) {
+                warn!("[Placeholders] {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Store the value for `${secret:name}`, in the OS keychain when `keychain` is set
+#[command]
+#[specta::specta]
+pub async fn set_config_secret(
+    name: String,
+    value: String,
+    keychain: Option<bool>,
+) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_config_secret").await;
+    let name = name.trim().to_lowercase();
+    store_secret(&name, &value, keychain.unwrap_or(false))?;
+    Ok(format!(
+        "Secret '{}' saved, use ${{secret:{}}} in the config",
+        name, name
+    ))
+}
+
+/// Delete a stored secret; placeholders using it become unresolved
+#[command]
+#[specta::specta]
+pub async fn delete_config_secret(name: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "delete_config_secret").await;
+    let mut secrets = load_file_secrets();
+    let mut names = keychain::names();
+    let in_file = secrets.remove(&name).is_some();
+    let in_keychain = names.iter().any(|n| *n == name);
+    if !in_file && !in_keychain {
+        return Err(format!("Secret '{}' not found", name));
+    }
+    if in_file {
+        save_secrets(&secrets)?;
+    }
+    if in_keychain {
+        keychain::delete(&name)?;
+        names.retain(|n| *n != name);
+        save_keychain_names(names)?;
+    }
+    let used = unresolved_placeholders()
+        .iter()
+        .filter(|p| p.kind == PlaceholderKind::Secret && p.name == name)
+        .count();
+    if used > 0 {
+        warn!(
+            "[Placeholders] Deleted secret '{}' is still used {} time(s)",
+            name, used
+        );
+    }
+    Ok(format!("Secret '{}' deleted", name))
+}
+
+/// Whether the OS keychain can hold secrets on this machine
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct KeychainStatus {
+    pub available: bool,
+    pub error: Option<String>,
+    /// Secrets currently stored there
+    pub secrets: usize,
+}
+
+/// Check the OS keychain by storing, reading and deleting a probe entry
+#[command]
+#[specta::specta]
+pub async fn get_keychain_status() -> Result<KeychainStatus, String> {
+    let probe = keychain::probe();
+    if let Err(e) = &probe {
+        warn!("[Placeholders] Keychain unavailable: {}", e);
+    }
+    Ok(KeychainStatus {
+        available: probe.is_ok(),
+        error: probe.err(),
+        secrets: keychain::names().len(),
+    })
+}
@@ -1,9 +1,13 @@
-use crate::utils::shell;
+use crate::commands::service;
+use crate::utils::process_tree::{self, ProcessInfo, ProcessNode};
+use crate::utils::executor::Executor;
+use crate::utils::{platform, shell};
 use tauri::command;
-use log::{info, debug};
+use log::{info, debug, warn};
 
 /// Check if OpenClaw is installed
 #[command]
+#[specta::specta]
 pub async fn check_openclaw_installed() -> Result<bool, String> {
     info!("[Process Check] Checking if OpenClaw is installed...");
     // Use get_openclaw_path to check, because command_exists may be unreliable on Windows
@@ -14,6 +18,7 @@ pub async fn check_openclaw_installed() -> Result<bool, String> {
 
 /// Get OpenClaw version
 #[command]
+#[specta::specta]
 pub async fn get_openclaw_version() -> Result<Option<String>, String> {
     info!("[Process Check] Getting OpenClaw version...");
     // Use run_openclaw to get the version
@@ -32,6 +37,7 @@ pub async fn get_openclaw_version() -> Result<Option<String>, String> {
 
 /// Check if port is in use (by attempting to connect to openclaw gateway)
 #[command]
+#[specta::specta]
 pub async fn check_port_in_use(port: u16) -> Result<bool, String> {
     info!("[Process Check] Checking if port {} is in use...", port);
 
@@ -64,7 +70,7 @@ pub async fn check_port_in_use(port: u16) -> Result<bool, String> {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, specta::Type)]
 pub struct SecureVersionInfo {
     pub current_version: String,
     pub is_secure: bool,
@@ -72,6 +78,7 @@ pub struct SecureVersionInfo {
 
 /// Check if current OpenClaw version is secure (>= 2026.1.29)
 #[command]
+#[specta::specta]
 pub async fn check_secure_version() -> Result<SecureVersionInfo, String> {
     info!("[Process Check] Checking OpenClaw version security...");
     match shell::run_openclaw(&["--version"]) {
@@ -96,6 +103,7 @@ pub async fn check_secure_version() -> Result<SecureVersionInfo, String> {
 
 /// Get Node.js version
 #[command]
+#[specta::specta]
 pub async fn get_node_version() -> Result<Option<String>, String> {
     info!("[Process Check] Getting Node.js version...");
     if !shell::command_exists("node") {
@@ -103,20 +111,28 @@ pub async fn get_node_version() -> Result<Option<String>, String> {
         return Ok(None);
     }
 
-    match shell::run_command_output("node", &["--version"]) {
-        Ok(version) => {
-            info!("[Process Check] Node.js version: {}", version);
-            Ok(Some(version))
-        },
+    let version = node_version(shell::executor());
+    if let Some(version) = &version {
+        info!("[Process Check] Node.js version: {}", version);
+    }
+    Ok(version)
+}
+
+/// `node --version` through `executor`, None if node cannot be run
+pub(crate) fn node_version(executor: &dyn Executor) -> Option<String> {
+    match shell::run_command_output_with(executor, "node", &["--version"]) {
+        Ok(version) if !version.is_empty() => Some(version),
+        Ok(_) => None,
         Err(e) => {
             debug!("[Process Check] Failed to get Node.js version: {}", e);
-            Ok(None)
+            None
         },
     }
 }
 
 /// Check if Ollama is installed
 #[command]
+#[specta::specta]
 pub async fn check_ollama_installed() -> Result<bool, String> {
     info!("[Ollama Check] Checking if Ollama is installed...");
     let installed = shell::command_exists("ollama");
@@ -126,9 +142,15 @@ pub async fn check_ollama_installed() -> Result<bool, String> {
 
 /// Get installed Ollama models
 #[command]
+#[specta::specta]
 pub async fn get_ollama_models() -> Result<Vec<String>, String> {
     info!("[Ollama Check] Getting installed Ollama models...");
-    match shell::run_command_output("ollama", &["list"]) {
+    ollama_models(shell::executor())
+}
+
+/// Model names from `ollama list`, run through `executor`
+pub(crate) fn ollama_models(executor: &dyn Executor) -> Result<Vec<String>, String> {
+    match shell::run_command_output_with(executor, "ollama", &["list"]) {
         Ok(output) => {
             let mut models = Vec::new();
             // Output format: NAME               ID           SIZE   MODIFIED   
@@ -153,6 +175,7 @@ pub async fn get_ollama_models() -> Result<Vec<String>, String> {
 
 /// Install / pull an Ollama model
 #[command]
+#[specta::specta]
 pub async fn install_ollama_model(model_name: String) -> Result<String, String> {
     info!("[Ollama Check] Installing Ollama model: {}", model_name);
     // Use `ollama pull` instead of `ollama run` so it doesn't stay interactive.
@@ -167,3 +190,76 @@ pub async fn install_ollama_model(model_name: String) -> Result<String, String>
         },
     }
 }
+
+/// Win32_Process rows as `pid,ppid,workingSetBytes,name`
+const WIN32_PROCESS_SCRIPT: &str = r#"Get-CimInstance Win32_Process | ForEach-Object { "{0},{1},{2},{3}" -f $_.ProcessId, $_.ParentProcessId, $_.WorkingSetSize, $_.Name }"#;
+
+/// Snapshot of the system process table
+pub(crate) fn list_processes() -> Result<Vec<ProcessInfo>, String> {
+    list_processes_with(shell::executor())
+}
+
+/// Process table read through `executor`
+pub(crate) fn list_processes_with(executor: &dyn Executor) -> Result<Vec<ProcessInfo>, String> {
+    if platform::is_windows() {
+        let args = ["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", WIN32_PROCESS_SCRIPT];
+        shell::run_command_output_with(executor, "powershell", &args).map(|out| process_tree::parse_win32_process(&out))
+    } else {
+        shell::run_command_output_with(executor, "ps", &["-axo", "pid=,ppid=,pcpu=,rss=,comm="])
+            .map(|out| process_tree::parse_ps(&out))
+    }
+}
+
+/// Gateway process with the node children it spawned (plugins, subagents). None if the gateway is not running.
+#[command]
+#[specta::specta]
+pub async fn get_gateway_process_tree() -> Result<Option<ProcessNode>, String> {
+    let pid = match service::gateway_pid() {
+        Some(pid) => pid,
+        None => return Ok(None),
+    };
+    let processes = list_processes().map_err(|e| format!("Failed to list processes: {}", e))?;
+    let tree = process_tree::build_tree(&processes, pid);
+    debug!("[Process Tree] Gateway PID {}, {} descendants", pid, process_tree::descendants(&processes, pid).len());
+    Ok(tree)
+}
+
+/// Kill one child of the gateway (with its own children). Refuses PIDs outside the gateway tree.
+#[command]
+#[specta::specta]
+pub async fn kill_gateway_child(pid: u32) -> Result<String, String> {
+    let gateway = service::gateway_pid().ok_or("Gateway is not running")?;
+    if pid == gateway {
+        return Err("Use Stop to end the gateway itself".to_string());
+    }
+    let processes = list_processes().map_err(|e| format!("Failed to list processes: {}", e))?;
+    if !process_tree::descendants(&processes, gateway).contains(&pid) {
+        return Err(format!("Process {} is not a child of the gateway", pid));
+    }
+
+    info!("[Process Tree] Killing gateway child {}", pid);
+    kill_process_tree(&processes, pid)
+}
+
+/// Kill a process with its children
+pub(crate) fn kill_process_tree(processes: &[ProcessInfo], pid: u32) -> Result<String, String> {
+    let result = if platform::is_windows() {
+        shell::run_command_output("taskkill", &["/F", "/T", "/PID", &pid.to_string()])
+    } else {
+        // Children first so they are not re-parented to init
+        let mut pids = process_tree::descendants(processes, pid);
+        pids.reverse();
+        pids.push(pid);
+        let pids = pids.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        let mut args = vec!["-9"];
+        args.extend(pids.iter().map(|p| p.as_str()));
+        shell::run_command_output("kill", &args)
+    };
+    match result {
+        Ok(_) => Ok(format!("Process {} killed", pid)),
+        Err(e) => {
+            warn!("[Process Tree] Failed to kill {}: {}", pid, e);
+            Err(format!("Failed to kill process {}: {}", pid, e))
+        }
+    }
+}
@@ -1,15 +1,32 @@
+use crate::commands::{config, placeholders, telemetry};
+use crate::commands::locks::{self, ConfigResource};
+use crate::events::{self, AlertLevel, ManagerEvent};
 use crate::models::ServiceStatus;
-use crate::utils::shell;
+use crate::utils::executor::Executor;
+use crate::utils::process_tree::ProcessNode;
+use crate::utils::resource_alerts::{self, ResourceAlert, ResourceAlertConfig, ResourceMonitor, ResourceSample};
+use crate::utils::resource_floor::{self, ResourceFloorConfig};
+use crate::utils::process_limits::{self, ProcessLimits};
+use crate::utils::network_usage::{self, NetworkDay, NetworkMeter};
+use crate::utils::{platform, process_tree, shell};
+use serde::{Deserialize, Serialize};
 use tauri::command;
 use std::process::Command;
 use log::{info, warn, debug, error};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // Track if service stop was intentional (manual stop) vs unexpected (crash/restart command)
 static INTENTIONAL_STOP: AtomicBool = AtomicBool::new(false);
 
+// Supervisor restart timestamps, used for crash-loop detection
+static RESTART_TIMES: Mutex<Vec<Instant>> = Mutex::new(Vec::new());
+
+// App handle for user notifications from background threads
+static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
@@ -21,12 +38,20 @@ const SERVICE_PORT: u16 = 18789;
 
 /// Check if a service is listening on the port, return PID
 /// Simple and direct: port in use = service running
+/// PID listening on the gateway port, if the gateway is up
+pub(crate) fn gateway_pid() -> Option<u32> {
+    check_port_listening(SERVICE_PORT)
+}
+
 fn check_port_listening(port: u16) -> Option<u32> {
+    port_listener_pid(shell::executor(), port)
+}
+
+/// PID listening on `port`, looked up with lsof (netstat on Windows) through `executor`
+pub(crate) fn port_listener_pid(executor: &dyn Executor, port: u16) -> Option<u32> {
     #[cfg(unix)]
     {
-        let output = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output()
+        let output = executor.output(Command::new("lsof").args(["-ti", &format!(":{}", port)]))
             .ok()?;
         
         if output.status.success() {
@@ -45,7 +70,7 @@ fn check_port_listening(port: u16) -> Option<u32> {
         cmd.args(["-ano"]);
         cmd.creation_flags(CREATE_NO_WINDOW);
         
-        let output = cmd.output().ok()?;
+        let output = executor.output(&mut cmd).ok()?;
         
         if output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -69,9 +94,7 @@ fn find_all_port_pids(port: u16) -> Vec<u32> {
 
     #[cfg(unix)]
     {
-        if let Ok(output) = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output()
+        if let Ok(output) = shell::output(Command::new("lsof").args(["-ti", &format!(":{}", port)]))
         {
             if output.status.success() {
                 for line in String::from_utf8_lossy(&output.stdout).lines() {
@@ -91,7 +114,7 @@ fn find_all_port_pids(port: u16) -> Vec<u32> {
         cmd.args(["-ano"]);
         cmd.creation_flags(CREATE_NO_WINDOW);
 
-        if let Ok(output) = cmd.output() {
+        if let Ok(output) = shell::output(&mut cmd) {
             if output.status.success() {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 for line in stdout.lines() {
@@ -116,6 +139,7 @@ fn find_all_port_pids(port: u16) -> Vec<u32> {
 /// Uses openclaw gateway health to verify the gateway is actually responding,
 /// not just that the port is busy (which could be svchost.exe or another process).
 #[command]
+#[specta::specta]
 pub async fn get_service_status() -> Result<ServiceStatus, String> {
     // Primary check: use gateway health RPC to verify the gateway is actually running
     let health_ok = match shell::run_openclaw(&["gateway", "health", "--timeout", "3000"]) {
@@ -128,25 +152,35 @@ pub async fn get_service_status() -> Result<ServiceStatus, String> {
     // Gateway is running only if health check passes AND port is occupied
     let running = health_ok && pid.is_some();
     
+    let pid = if running { pid } else { None };
     Ok(ServiceStatus {
         running,
-        pid: if running { pid } else { None },
+        pid,
         port: SERVICE_PORT,
         uptime_seconds: None,
         memory_mb: None,
         cpu_percent: None,
+        origin: pid.map(|pid| gateway_origin(pid).to_string()),
     })
 }
 
-/// Start service
+/// Start service. Blocking readiness problems abort the start unless `force` is set.
 #[command]
-pub async fn start_service() -> Result<String, String> {
+#[specta::specta]
+pub async fn start_service(force: Option<bool>) -> Result<String, String> {
     info!("[Service] Starting service...");
+    telemetry::record_feature("start_service");
 
     // Check if already running via health check
     let health_ok = shell::run_openclaw(&["gateway", "health", "--timeout", "2000"]).is_ok();
     if health_ok {
         info!("[Service] Service is already running (health check passed)");
+        if let Some(pid) = gateway_pid().filter(|pid| gateway_origin(*pid) == ORIGIN_EXTERNAL) {
+            return Err(format!(
+                "Gateway is already running outside the Manager (PID {}), adopt it to stop or restart it from here",
+                pid
+            ));
+        }
         return Err("Service is already running".to_string());
     }
 
@@ -158,6 +192,8 @@ pub async fn start_service() -> Result<String, String> {
     }
     info!("[Service] openclaw path: {:?}", openclaw_path);
 
+    check_start_readiness(force.unwrap_or(false))?;
+
     // Clear any processes squatting on the port (e.g. svchost.exe)
     let squatter_pids = find_all_port_pids(SERVICE_PORT);
     if !squatter_pids.is_empty() {
@@ -168,11 +204,11 @@ pub async fn start_service() -> Result<String, String> {
                 let mut cmd = Command::new("taskkill");
                 cmd.args(["/F", "/PID", &pid.to_string()]);
                 cmd.creation_flags(CREATE_NO_WINDOW);
-                let _ = cmd.output();
+                let _ = shell::output(&mut cmd);
             }
             #[cfg(unix)]
             {
-                let _ = Command::new("kill").args(["-9", &pid.to_string()]).output();
+                let _ = shell::output(Command::new("kill").args(["-9", &pid.to_string()]));
             }
         }
         // Wait for port to free up
@@ -180,6 +216,8 @@ pub async fn start_service() -> Result<String, String> {
     }
 
     // Start gateway in background
+    prepare_gateway_config().await?;
+    record_restart_diff("start");
     info!("[Service] Starting gateway in background...");
     shell::spawn_openclaw_gateway()
         .map_err(|e| format!("Failed to start service: {}", e))?;
@@ -207,12 +245,14 @@ pub async fn start_service() -> Result<String, String> {
 
     if health_ok {
         info!("[Service] Gateway is healthy!");
+        save_last_good_config();
     } else {
         warn!("[Service] Gateway health check failed, port is active but gateway may still be initializing");
     }
 
-    // Reset stop flag
+    // Reset stop flag; a manual start also re-arms the crash-loop breaker
     INTENTIONAL_STOP.store(false, Ordering::Relaxed);
+    reset_crash_loop_state();
 
     // Spawn supervisor thread
     thread::spawn(|| {
@@ -233,6 +273,13 @@ pub async fn start_service() -> Result<String, String> {
                 // Double check flag just in case
                 if INTENTIONAL_STOP.load(Ordering::Relaxed) { break; }
 
+                // Stop restarting if the gateway keeps crashing
+                if record_restart_and_check_loop() {
+                    trip_crash_loop_breaker();
+                    break;
+                }
+
+                record_restart_diff("supervisor");
                 if let Err(e) = shell::spawn_openclaw_gateway() {
                     error!("[Service Supervisor] Failed to restart service: {}", e);
                 } else {
@@ -244,19 +291,54 @@ pub async fn start_service() -> Result<String, String> {
         }
     });
 
-    if let Some(pid) = check_port_listening(SERVICE_PORT) {
+    let pid = check_port_listening(SERVICE_PORT);
+    if let Some(pid) = pid {
+        record_gateway_owner(pid, ORIGIN_MANAGER).await;
+    }
+    emit_service_status(true, pid);
+    if let Some(pid) = pid {
         Ok(format!("Service started, PID: {}", pid))
     } else {
         Ok("Service started (pid unknown)".to_string())
     }
 }
 
-/// Stop service
+/// Rewrite config placeholders to env references and export their values for the gateway
+async fn prepare_gateway_config() -> Result<(), String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw], "start_service").await;
+    config::ensure_gateway_auth()?;
+    placeholders::normalize_placeholders()
+}
+
+/// Push the gateway status to the frontend after a start, stop or restart
+fn emit_service_status(running: bool, pid: Option<u32>) {
+    let origin = pid.map(|pid| gateway_origin(pid).to_string());
+    events::emit(ManagerEvent::ServiceStatus(ServiceStatus { running, pid, origin, ..ServiceStatus::default() }));
+}
+
 /// Stop service
 #[command]
+#[specta::specta]
 pub async fn stop_service() -> Result<String, String> {
     info!("[Service] Stopping service...");
 
+    // Name where an adopted gateway came from, it was not started by the Manager
+    let stopped = match gateway_pid() {
+        Some(pid) if gateway_origin(pid) == ORIGIN_ADOPTED => {
+            info!("[Service] Stopping adopted gateway (PID {})", pid);
+            format!("Service stopped (adopted gateway, PID {})", pid)
+        }
+        _ => "Service stopped".to_string(),
+    };
+    let result = stop_gateway_process(&stopped).await;
+    if result.is_ok() {
+        clear_gateway_owner().await;
+    }
+    result
+}
+
+async fn stop_gateway_process(stopped: &str) -> Result<String, String> {
+
     // Set flag so supervisor knows this is intentional
     INTENTIONAL_STOP.store(true, Ordering::Relaxed);
 
@@ -269,7 +351,8 @@ pub async fn stop_service() -> Result<String, String> {
         let status = get_service_status().await?;
         if !status.running {
             info!("[Service] Successfully stopped (graceful)");
-            return Ok("Service stopped".to_string());
+            emit_service_status(false, None);
+            return Ok(stopped.to_string());
         }
     }
 
@@ -281,7 +364,8 @@ pub async fn stop_service() -> Result<String, String> {
     let status = get_service_status().await?;
     if !status.running {
         info!("[Service] Successfully stopped (CLI force)");
-        return Ok("Service stopped".to_string());
+        emit_service_status(false, None);
+        return Ok(stopped.to_string());
     }
 
     // 3. Last resort: Kill process by PID
@@ -293,7 +377,7 @@ pub async fn stop_service() -> Result<String, String> {
             let mut cmd = Command::new("taskkill");
             cmd.args(["/F", "/PID", &pid.to_string()]);
             cmd.creation_flags(CREATE_NO_WINDOW);
-            if let Ok(output) = cmd.output() {
+            if let Ok(output) = shell::output(&mut cmd) {
                 if !output.status.success() {
                      let stderr = String::from_utf8_lossy(&output.stderr);
                      warn!("[Service] Failed to taskkill PID {}: {}", pid, stderr);
@@ -303,7 +387,7 @@ pub async fn stop_service() -> Result<String, String> {
 
         #[cfg(unix)]
         {
-            let _ = Command::new("kill").args(["-9", &pid.to_string()]).output();
+            let _ = shell::output(Command::new("kill").args(["-9", &pid.to_string()]));
         }
         
         std::thread::sleep(std::time::Duration::from_millis(1000));
@@ -311,17 +395,21 @@ pub async fn stop_service() -> Result<String, String> {
         let final_status = get_service_status().await?;
         if !final_status.running {
              info!("[Service] Successfully killed process");
-             return Ok("Service stopped (killed)".to_string());
+             emit_service_status(false, None);
+             return Ok(format!("{} (killed)", stopped));
         }
     }
 
     Err("Failed to stop service after all attempts".to_string())
 }
 
-/// Restart service
+/// Restart service. Readiness is checked before stopping, so a blocked restart leaves the gateway running.
 #[command]
-pub async fn restart_service() -> Result<String, String> {
+#[specta::specta]
+pub async fn restart_service(force: Option<bool>) -> Result<String, String> {
     info!("[Service] Restarting service...");
+    telemetry::record_feature("restart_service");
+    check_start_readiness(force.unwrap_or(false))?;
 
     // Step 1: Stop the service if it's running
     match stop_service().await {
@@ -344,17 +432,19 @@ pub async fn restart_service() -> Result<String, String> {
                 let mut cmd = Command::new("taskkill");
                 cmd.args(["/F", "/PID", &pid.to_string()]);
                 cmd.creation_flags(CREATE_NO_WINDOW);
-                let _ = cmd.output();
+                let _ = shell::output(&mut cmd);
             }
             #[cfg(unix)]
             {
-                let _ = Command::new("kill").args(["-9", &pid.to_string()]).output();
+                let _ = shell::output(Command::new("kill").args(["-9", &pid.to_string()]));
             }
         }
         std::thread::sleep(std::time::Duration::from_millis(1500));
     }
 
     // Step 3: Start the gateway
+    prepare_gateway_config().await?;
+    record_restart_diff("restart");
     info!("[Service] Starting gateway in background...");
     shell::spawn_openclaw_gateway()
         .map_err(|e| format!("Failed to start service: {}", e))?;
@@ -367,7 +457,13 @@ pub async fn restart_service() -> Result<String, String> {
             info!("[Service] Port {} is now active ({}s)", SERVICE_PORT, i);
             // Give gateway a moment to fully initialize
             std::thread::sleep(std::time::Duration::from_secs(2));
-            if let Some(pid) = check_port_listening(SERVICE_PORT) {
+            let pid = check_port_listening(SERVICE_PORT);
+            if let Some(pid) = pid {
+                // From here on the gateway is the Manager's own, even if it was adopted before
+                record_gateway_owner(pid, ORIGIN_MANAGER).await;
+            }
+            emit_service_status(true, pid);
+            if let Some(pid) = pid {
                 info!("[Service] Successfully restarted, PID: {}", pid);
                 return Ok(format!("Service restarted, PID: {}", pid));
             }
@@ -381,6 +477,7 @@ pub async fn restart_service() -> Result<String, String> {
 
 /// Get logs
 #[command]
+#[specta::specta]
 pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, String> {
     let n = lines.unwrap_or(100);
 
@@ -394,6 +491,7 @@ pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, String> {
 
 /// Kill ALL processes using port 18789
 #[command]
+#[specta::specta]
 pub async fn kill_all_port_processes() -> Result<String, String> {
     info!("[Service] Kill All: Finding all processes on port {}...", SERVICE_PORT);
 
@@ -418,7 +516,7 @@ pub async fn kill_all_port_processes() -> Result<String, String> {
             cmd.args(["/F", "/PID", &pid.to_string()]);
             cmd.creation_flags(CREATE_NO_WINDOW);
 
-            match cmd.output() {
+            match shell::output(&mut cmd) {
                 Ok(output) if output.status.success() => {
                     info!("[Service] Kill All: Successfully killed PID {}", pid);
                     killed += 1;
@@ -437,7 +535,7 @@ pub async fn kill_all_port_processes() -> Result<String, String> {
 
         #[cfg(unix)]
         {
-            match Command::new("kill").args(["-9", &pid.to_string()]).output() {
+            match shell::output(Command::new("kill").args(["-9", &pid.to_string()])) {
                 Ok(output) if output.status.success() => {
                     info!("[Service] Kill All: Successfully killed PID {}", pid);
                     killed += 1;
@@ -464,3 +562,1805 @@ pub async fn kill_all_port_processes() -> Result<String, String> {
     info!("[Service] Kill All: {}", msg);
     Ok(msg)
 }
+
+// ============ Gateway HTTP Probe ============
+
+/// What the gateway's HTTP listener answered
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GatewayProbe {
+    pub port: u16,
+    /// An HTTP server answered on the port
+    pub reachable: bool,
+    pub http_status: Option<u16>,
+    /// Whether the token was accepted (None if the answer says neither)
+    pub token_accepted: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// GET the gateway root on `port` with `token` as bearer token, through `executor`
+pub(crate) fn probe_gateway_http(executor: &dyn Executor, port: u16, token: &str) -> GatewayProbe {
+    let url = format!("http://127.0.0.1:{}/", port);
+    let mut cmd = Command::new(if cfg!(windows) { "curl.exe" } else { "curl" });
+    cmd.args(["-s", "-o", shell::null_device(), "-w", "%{http_code}", "--max-time", "3"]);
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let started = Instant::now();
+    let mut probe = GatewayProbe {
+        port,
+        reachable: false,
+        http_status: None,
+        token_accepted: None,
+        latency_ms: None,
+        error: None,
+    };
+    let _headers = match shell::curl_secret_headers(&mut cmd, &[format!("Authorization: Bearer {}", token)]) {
+        Ok(file) => file,
+        Err(e) => {
+            probe.error = Some(e);
+            return probe;
+        }
+    };
+    cmd.arg(&url);
+    match executor.output(&mut cmd) {
+        Ok(output) => {
+            // curl prints 000 when nothing answered
+            match String::from_utf8_lossy(&output.stdout).trim().parse::<u16>() {
+                Ok(status) if status > 0 => {
+                    probe.reachable = true;
+                    probe.http_status = Some(status);
+                    probe.latency_ms = Some(started.elapsed().as_millis() as u64);
+                    probe.token_accepted = match status {
+                        200..=399 => Some(true),
+                        401 | 403 => Some(false),
+                        _ => None,
+                    };
+                }
+                _ => probe.error = Some(format!("Nothing is answering on port {}", port)),
+            }
+        }
+        Err(e) => probe.error = Some(format!("Failed to run curl: {}", e)),
+    }
+    probe
+}
+
+/// Probe the default gateway over HTTP with the configured token
+#[command]
+#[specta::specta]
+pub async fn probe_gateway() -> Result<GatewayProbe, String> {
+    let config = crate::commands::config::load_openclaw_config()?;
+    let token = config
+        .pointer("/gateway/auth/token")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let port = config
+        .pointer("/gateway/port")
+        .and_then(|v| v.as_u64())
+        .map(|p| p as u16)
+        .unwrap_or(SERVICE_PORT);
+    let probe = probe_gateway_http(shell::executor(), port, &token);
+    debug!("[Service] HTTP probe on port {}: {:?}", port, probe.http_status);
+    Ok(probe)
+}
+
+// ============ Log Rotation ============
+
+/// Default rotation limits when none are configured
+const DEFAULT_LOG_MAX_SIZE_MB: u64 = 20;
+const DEFAULT_LOG_MAX_FILES: u32 = 5;
+
+/// Log rotation settings (stored in manager.json -> logRotation)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct LogRotationSettings {
+    pub max_size_mb: u64,
+    pub max_files: u32,
+}
+
+/// Size info for one log location
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct LogLocationStats {
+    pub label: String,
+    pub path: String,
+    pub file_count: u32,
+    pub total_bytes: u64,
+    /// Whether the Manager rotates files in this location
+    pub rotated: bool,
+}
+
+/// Log storage overview
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct LogStorageStats {
+    pub settings: LogRotationSettings,
+    pub locations: Vec<LogLocationStats>,
+    pub total_bytes: u64,
+}
+
+fn load_log_rotation_settings() -> LogRotationSettings {
+    let manager_config = crate::commands::config::load_manager_config().unwrap_or_default();
+    LogRotationSettings {
+        max_size_mb: manager_config
+            .pointer("/logRotation/maxSizeMb")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_LOG_MAX_SIZE_MB),
+        max_files: manager_config
+            .pointer("/logRotation/maxFiles")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .unwrap_or(DEFAULT_LOG_MAX_FILES),
+    }
+}
+
+/// Directory where the core writes its daily gateway logs (openclaw-YYYY-MM-DD.log)
+pub(crate) fn get_gateway_log_dir() -> std::path::PathBuf {
+    if crate::utils::platform::is_windows() {
+        std::env::temp_dir().join("openclaw")
+    } else {
+        std::path::PathBuf::from("/tmp/openclaw")
+    }
+}
+
+/// Rotate a single file: file -> file.1 -> file.2 ... keeping at most `max_files` rotated copies
+fn rotate_file(path: &std::path::Path, max_size_bytes: u64, max_files: u32) {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size <= max_size_bytes {
+        return;
+    }
+
+    let path_str = path.to_string_lossy().to_string();
+    let _ = std::fs::remove_file(format!("{}.{}", path_str, max_files));
+    for i in (1..max_files).rev() {
+        let from = format!("{}.{}", path_str, i);
+        if std::path::Path::new(&from).exists() {
+            let _ = std::fs::rename(&from, format!("{}.{}", path_str, i + 1));
+        }
+    }
+    if max_files > 0 {
+        // Copy + truncate so the gateway can keep its open handle
+        if let Err(e) = std::fs::copy(path, format!("{}.1", path_str)) {
+            warn!("[Log Rotation] Failed to rotate {}: {}", path_str, e);
+            return;
+        }
+    }
+    match std::fs::OpenOptions::new().write(true).truncate(true).open(path) {
+        Ok(_) => info!("[Log Rotation] Rotated {} ({} bytes)", path_str, size),
+        Err(e) => warn!("[Log Rotation] Failed to truncate {}: {}", path_str, e),
+    }
+}
+
+/// Apply rotation limits to all log locations managed by the Manager
+pub fn rotate_logs() {
+    let settings = load_log_rotation_settings();
+    let max_size_bytes = settings.max_size_mb * 1024 * 1024;
+
+    // Manager-captured gateway log
+    let manager_log = crate::utils::platform::get_log_file_path();
+    rotate_file(std::path::Path::new(&manager_log), max_size_bytes, settings.max_files);
+
+    // Core daily logs: cap each file's size and keep only the newest `max_files` days
+    let log_dir = get_gateway_log_dir();
+    if let Ok(entries) = std::fs::read_dir(&log_dir) {
+        let mut daily: Vec<std::path::PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                name.starts_with("openclaw-") && name.ends_with(".log")
+            })
+            .collect();
+        // File names embed the date, so lexical order is chronological
+        daily.sort();
+
+        let keep = settings.max_files.max(1) as usize;
+        if daily.len() > keep {
+            for old in daily.drain(..daily.len() - keep) {
+                match std::fs::remove_file(&old) {
+                    Ok(_) => info!("[Log Rotation] Removed old log {}", old.display()),
+                    Err(e) => warn!("[Log Rotation] Failed to remove {}: {}", old.display(), e),
+                }
+            }
+        }
+        if let Some(current) = daily.last() {
+            if std::fs::metadata(current).map(|m| m.len()).unwrap_or(0) > max_size_bytes {
+                // Rotated copies of the current day are not kept; the day file is just capped
+                rotate_file(current, max_size_bytes, 0);
+            }
+        }
+    }
+}
+
+/// Start a background thread that applies log rotation every hour
+pub fn start_log_rotation_task() {
+    thread::spawn(|| loop {
+        rotate_logs();
+        thread::sleep(Duration::from_secs(60 * 60));
+    });
+}
+
+/// Configure log rotation limits and apply them immediately
+#[command]
+#[specta::specta]
+pub async fn configure_log_rotation(max_size_mb: u64, max_files: u32) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "configure_log_rotation").await;
+    info!("[Log Rotation] Configuring: max_size_mb={}, max_files={}", max_size_mb, max_files);
+    if max_size_mb == 0 {
+        return Err("Maximum log size must be at least 1 MB".to_string());
+    }
+
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config["logRotation"] = serde_json::json!({
+        "maxSizeMb": max_size_mb,
+        "maxFiles": max_files,
+    });
+    crate::commands::config::save_manager_config(&manager_config)?;
+
+    rotate_logs();
+    Ok(format!("Log rotation set to {} MB x {} files", max_size_mb, max_files))
+}
+
+/// Sum file count and size of a directory (recursively)
+pub(crate) fn dir_stats(path: &std::path::Path) -> (u32, u64) {
+    let mut count = 0u32;
+    let mut bytes = 0u64;
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() {
+                let (c, b) = dir_stats(&p);
+                count += c;
+                bytes += b;
+            } else if let Ok(meta) = entry.metadata() {
+                count += 1;
+                bytes += meta.len();
+            }
+        }
+    }
+    (count, bytes)
+}
+
+/// Get disk usage of gateway and session logs
+#[command]
+#[specta::specta]
+pub async fn get_log_storage_stats() -> Result<LogStorageStats, String> {
+    info!("[Log Rotation] Collecting log storage stats...");
+    let mut locations = Vec::new();
+
+    let manager_log = crate::utils::platform::get_log_file_path();
+    let mut manager_count = 0u32;
+    let mut manager_bytes = 0u64;
+    for candidate in std::iter::once(manager_log.clone())
+        .chain((1..=load_log_rotation_settings().max_files).map(|i| format!("{}.{}", manager_log, i)))
+    {
+        if let Ok(meta) = std::fs::metadata(&candidate) {
+            manager_count += 1;
+            manager_bytes += meta.len();
+        }
+    }
+    locations.push(LogLocationStats {
+        label: "Manager gateway log".to_string(),
+        path: manager_log,
+        file_count: manager_count,
+        total_bytes: manager_bytes,
+        rotated: true,
+    });
+
+    let log_dir = get_gateway_log_dir();
+    let (count, bytes) = dir_stats(&log_dir);
+    locations.push(LogLocationStats {
+        label: "Gateway daily logs".to_string(),
+        path: log_dir.to_string_lossy().to_string(),
+        file_count: count,
+        total_bytes: bytes,
+        rotated: true,
+    });
+
+    // Session transcripts are history, not logs: reported but never rotated
+    let agents_dir = std::path::Path::new(&crate::utils::platform::get_config_dir()).join("agents");
+    let (count, bytes) = dir_stats(&agents_dir);
+    locations.push(LogLocationStats {
+        label: "Agent sessions".to_string(),
+        path: agents_dir.to_string_lossy().to_string(),
+        file_count: count,
+        total_bytes: bytes,
+        rotated: false,
+    });
+
+    let total_bytes = locations.iter().map(|l| l.total_bytes).sum();
+    Ok(LogStorageStats {
+        settings: load_log_rotation_settings(),
+        locations,
+        total_bytes,
+    })
+}
+
+// ============ Resource Alerts ============
+
+/// Seconds between gateway resource samples
+const RESOURCE_POLL_SECS: u64 = 30;
+
+static RESOURCE_MONITOR: Mutex<Option<(u32, ResourceMonitor)>> = Mutex::new(None);
+
+/// Diagnostics captured before a restart triggered from a resource alert
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ResourceDiagnostics {
+    /// File the capture was written to
+    pub path: String,
+    pub captured_at: String,
+    pub pid: Option<u32>,
+    /// Recent samples, oldest first
+    pub samples: Vec<ResourceSample>,
+    pub process_tree: Option<ProcessNode>,
+    /// Last gateway log lines (secrets redacted)
+    pub log_excerpt: Vec<String>,
+    pub restarted: bool,
+    pub restart_message: String,
+}
+
+fn load_resource_alert_config() -> ResourceAlertConfig {
+    crate::commands::config::load_manager_config()
+        .ok()
+        .and_then(|m| m.get("resourceAlerts").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn get_diagnostics_dir() -> std::path::PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["diagnostics"])
+}
+
+/// CPU time the process has used so far, in seconds
+fn process_cpu_seconds(pid: u32) -> Option<f64> {
+    if platform::is_windows() {
+        let script = format!("(Get-Process -Id {}).CPU", pid);
+        shell::run_command_output("powershell", &["-NoProfile", "-NonInteractive", "-Command", &script])
+            .ok()
+            .and_then(|out| out.trim().parse().ok())
+    } else {
+        shell::run_command_output("ps", &["-o", "time=", "-p", &pid.to_string()])
+            .ok()
+            .and_then(|out| resource_alerts::parse_cpu_time(&out))
+    }
+}
+
+/// The gateway and the processes it started
+fn gateway_process_ids(pid: u32) -> Vec<u32> {
+    let processes = crate::commands::process::list_processes().unwrap_or_default();
+    let mut pids = process_tree::descendants(&processes, pid);
+    pids.push(pid);
+    pids
+}
+
+/// Memory of the gateway with its children, in MB
+fn gateway_memory_mb(pid: u32) -> Option<f64> {
+    let processes = crate::commands::process::list_processes().ok()?;
+    let mut pids = process_tree::descendants(&processes, pid);
+    pids.push(pid);
+    Some(
+        processes
+            .iter()
+            .filter(|p| pids.contains(&p.pid))
+            .filter_map(|p| p.memory_mb)
+            .sum(),
+    )
+}
+
+/// Memory of the gateway with its children and CPU time of the gateway process
+fn sample_gateway(pid: u32) -> Option<ResourceSample> {
+    Some(ResourceSample {
+        at: chrono::Utc::now().timestamp().max(0) as u64,
+        memory_mb: gateway_memory_mb(pid)?,
+        cpu_seconds: process_cpu_seconds(pid)?,
+        cpu_percent: None,
+    })
+}
+
+fn notify_resource_alert(alert: &ResourceAlert) {
+    warn!("[Resources] {}", alert.message);
+    events::emit(ManagerEvent::alert("gateway-resources", AlertLevel::Warning, alert.message.clone(), alert));
+    if let Some(app) = APP_HANDLE.get() {
+        use tauri_plugin_notification::NotificationExt;
+
+        let body = format!("{}. Capture diagnostics and restart from the dashboard.", alert.message);
+        if let Err(e) = app.notification().builder().title("OpenClaw gateway resource alert").body(body).show() {
+            debug!("[Resources] Failed to show notification: {}", e);
+        }
+    }
+}
+
+/// Background task sampling gateway memory and CPU against the configured thresholds
+pub fn start_resource_monitor() {
+    thread::spawn(|| loop {
+        thread::sleep(Duration::from_secs(RESOURCE_POLL_SECS));
+
+        let config = load_resource_alert_config();
+        let pid = match gateway_pid() {
+            Some(pid) if config.enabled => pid,
+            _ => {
+                *RESOURCE_MONITOR.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                continue;
+            }
+        };
+        let Some(sample) = sample_gateway(pid) else {
+            continue;
+        };
+        let alerts = {
+            let mut state = RESOURCE_MONITOR.lock().unwrap_or_else(|e| e.into_inner());
+            if state.as_ref().map(|(p, _)| *p) != Some(pid) {
+                *state = Some((pid, ResourceMonitor::default()));
+            }
+            match state.as_mut() {
+                Some((_, monitor)) => monitor.observe(&config, sample),
+                None => Vec::new(),
+            }
+        };
+        for alert in &alerts {
+            notify_resource_alert(alert);
+        }
+    });
+}
+
+/// Get the gateway memory / CPU alert thresholds
+#[command]
+#[specta::specta]
+pub async fn get_resource_alert_config() -> Result<ResourceAlertConfig, String> {
+    Ok(load_resource_alert_config())
+}
+
+/// Save the gateway memory / CPU alert thresholds
+#[command]
+#[specta::specta]
+pub async fn save_resource_alert_config(config: ResourceAlertConfig) -> Result<ResourceAlertConfig, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_resource_alert_config").await;
+    config.validate()?;
+    info!(
+        "[Resources] Saving alert thresholds (enabled: {}, memory: {} MB, cpu: {}% for {} min)",
+        config.enabled, config.memory_mb, config.cpu_percent, config.cpu_minutes
+    );
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config["resourceAlerts"] = serde_json::to_value(&config)
+        .map_err(|e| format!("Failed to serialize resource alerts: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)?;
+    Ok(config)
+}
+
+// ============ Resource Floor ============
+
+fn load_resource_floor_config() -> ResourceFloorConfig {
+    crate::commands::config::load_manager_config()
+        .ok()
+        .and_then(|m| m.get(resource_floor::SETTINGS_KEY).cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Free memory and disk checks against the configured floors. With `reclaim`, the running
+/// gateway's memory counts as available because a restart frees it first.
+fn resource_floor_checks(reclaim: bool) -> Vec<ReadinessCheck> {
+    let config = load_resource_floor_config();
+    if !config.enabled {
+        return Vec::new();
+    }
+    let resources = resource_floor::probe();
+    let reclaimable = if reclaim { gateway_pid().and_then(gateway_memory_mb).unwrap_or(0.0) } else { 0.0 };
+    let violations = resource_floor::evaluate(&config, &resources, reclaimable);
+    let status = if config.block { "error" } else { "warning" };
+
+    let measured = [
+        ("memory", resources.available_memory_mb.map(|mb| format!("{:.0} MB of memory available", mb + reclaimable))),
+        ("disk", resources.available_disk_mb.map(|mb| format!("{:.0} MB free on the config drive", mb))),
+    ];
+    measured
+        .into_iter()
+        .filter_map(|(kind, ok_message)| {
+            let ok_message = ok_message?;
+            Some(match violations.iter().find(|v| v.kind == kind) {
+                Some(v) => readiness_check("system", kind, status, v.message.clone()),
+                None => readiness_check("system", kind, "ok", ok_message),
+            })
+        })
+        .collect()
+}
+
+/// Get the free memory / disk floors checked before the gateway starts
+#[command]
+#[specta::specta]
+pub async fn get_resource_floor_config() -> Result<ResourceFloorConfig, String> {
+    Ok(load_resource_floor_config())
+}
+
+/// Save the free memory / disk floors checked before the gateway starts
+#[command]
+#[specta::specta]
+pub async fn save_resource_floor_config(config: ResourceFloorConfig) -> Result<ResourceFloorConfig, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_resource_floor_config").await;
+    config.validate()?;
+    info!(
+        "[Resources] Saving start floors (enabled: {}, memory: {} MB, disk: {} MB, block: {})",
+        config.enabled, config.memory_mb, config.disk_mb, config.block
+    );
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config[resource_floor::SETTINGS_KEY] = serde_json::to_value(&config)
+        .map_err(|e| format!("Failed to serialize resource floors: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)?;
+    Ok(config)
+}
+
+// ============ Network Usage ============
+
+/// Seconds between gateway network samples
+const NETWORK_POLL_SECS: u64 = 30;
+
+static NETWORK_METER: Mutex<Option<NetworkMeter>> = Mutex::new(None);
+
+/// Daily gateway traffic over a period
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct NetworkStats {
+    /// How traffic is measured ("ss" or "nettop"), None where the OS has no per-process counters
+    pub method: Option<String>,
+    /// Days with traffic, oldest first
+    pub days: Vec<NetworkDay>,
+    pub total_sent: u64,
+    pub total_received: u64,
+}
+
+fn get_network_usage_file() -> std::path::PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["network-usage.json"])
+}
+
+fn load_network_days() -> Vec<NetworkDay> {
+    std::fs::read_to_string(get_network_usage_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_network_days(days: &[NetworkDay]) {
+    let path = get_network_usage_file();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(days) {
+        if let Err(e) = std::fs::write(&path, content) {
+            debug!("[Network] Failed to write network usage: {}", e);
+        }
+    }
+}
+
+/// Background task adding the gateway's network traffic to today's totals
+pub fn start_network_meter() {
+    if network_usage::method(std::env::consts::OS).is_none() {
+        info!("[Network] No per-process network counters on this OS, gateway traffic is not metered");
+        return;
+    }
+    thread::spawn(|| loop {
+        thread::sleep(Duration::from_secs(NETWORK_POLL_SECS));
+
+        let counters = match gateway_pid() {
+            Some(pid) => match network_usage::sample(&gateway_process_ids(pid)) {
+                Some(counters) => counters,
+                None => continue,
+            },
+            None => Vec::new(),
+        };
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut meter = NETWORK_METER.lock().unwrap_or_else(|e| e.into_inner());
+        let meter = meter.get_or_insert_with(|| NetworkMeter::with_days(load_network_days()));
+        if meter.observe(&today, &counters) {
+            save_network_days(&meter.days);
+        }
+    });
+}
+
+/// Bytes the gateway sent and received per day over the last `days` days (default 30, at most
+/// the 90 kept). With "ss" the totals are a lower bound: sockets are sampled periodically, so
+/// traffic of connections opened and closed between two samples is not counted.
+#[command]
+#[specta::specta]
+pub async fn get_network_stats(days: Option<u32>) -> Result<NetworkStats, String> {
+    let days = days.unwrap_or(30).clamp(1, network_usage::HISTORY_DAYS as u32);
+    let since = (chrono::Local::now() - chrono::Duration::days(days as i64 - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let all = match NETWORK_METER.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(meter) => meter.days.clone(),
+        None => load_network_days(),
+    };
+    let days: Vec<NetworkDay> = all.into_iter().filter(|d| d.date >= since).collect();
+    Ok(NetworkStats {
+        method: network_usage::method(std::env::consts::OS).map(String::from),
+        total_sent: days.iter().map(|d| d.bytes_sent).sum(),
+        total_received: days.iter().map(|d| d.bytes_received).sum(),
+        days,
+    })
+}
+
+// ============ Gateway Limits ============
+
+/// Gateway priority / affinity / memory and CPU limits and how they apply on this machine
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct GatewayLimitsStatus {
+    pub limits: ProcessLimits,
+    /// Limits that cannot be applied here, with the reason
+    pub unsupported: Vec<String>,
+    pub cpu_count: usize,
+}
+
+fn gateway_limits_status() -> GatewayLimitsStatus {
+    let mut unsupported = process_limits::current_plan().unsupported;
+    if crate::utils::wsl::active().is_some() {
+        unsupported.push("Limits are not applied in WSL mode".to_string());
+    }
+    GatewayLimitsStatus {
+        limits: process_limits::limits(),
+        unsupported,
+        cpu_count: process_limits::cpu_count(),
+    }
+}
+
+/// Get the limits the gateway is started with
+#[command]
+#[specta::specta]
+pub async fn get_gateway_limits() -> Result<GatewayLimitsStatus, String> {
+    Ok(gateway_limits_status())
+}
+
+/// Save the limits the gateway is started with; they apply from the next start or restart
+#[command]
+#[specta::specta]
+pub async fn save_gateway_limits(limits: ProcessLimits) -> Result<GatewayLimitsStatus, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_gateway_limits").await;
+    limits.validate(process_limits::cpu_count())?;
+    info!(
+        "[Limits] Saving gateway limits (priority: {:?}, low I/O: {}, CPUs: {:?}, memory: {:?} MB, CPU quota: {:?}%)",
+        limits.priority, limits.low_io, limits.cpu_affinity, limits.memory_limit_mb, limits.cpu_quota_percent
+    );
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config[process_limits::SETTINGS_KEY] = serde_json::to_value(&limits)
+        .map_err(|e| format!("Failed to serialize gateway limits: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)?;
+    process_limits::set_limits(limits);
+    Ok(gateway_limits_status())
+}
+
+/// Save recent resource samples, the process tree and the gateway log tail to
+/// manager/diagnostics, then restart the gateway
+#[command]
+#[specta::specta]
+pub async fn capture_diagnostics_and_restart() -> Result<ResourceDiagnostics, String> {
+    info!("[Resources] Capturing diagnostics before restart...");
+    let pid = gateway_pid();
+    let samples: Vec<ResourceSample> = RESOURCE_MONITOR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .filter(|(p, _)| Some(*p) == pid)
+        .map(|(_, monitor)| monitor.history.iter().cloned().collect())
+        .unwrap_or_default();
+    let process_tree = pid.and_then(|pid| {
+        crate::commands::process::list_processes()
+            .ok()
+            .and_then(|processes| process_tree::build_tree(&processes, pid))
+    });
+    let log_excerpt: Vec<String> = shell::run_openclaw(&["logs", "--limit", "200"])
+        .map(|out| out.lines().map(crate::utils::log_sanitizer::sanitize).collect())
+        .unwrap_or_default();
+
+    let now = chrono::Utc::now();
+    let dir = get_diagnostics_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = platform::path_string(&dir.join(format!("gateway-{}.json", now.format("%Y%m%d-%H%M%S"))));
+    let mut diagnostics = ResourceDiagnostics {
+        path: path.clone(),
+        captured_at: now.to_rfc3339(),
+        pid,
+        samples,
+        process_tree,
+        log_excerpt,
+        restarted: false,
+        restart_message: String::new(),
+    };
+    let content = serde_json::to_string_pretty(&diagnostics)
+        .map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+    crate::utils::file::write_file(&path, &content)
+        .map_err(|e| format!("Failed to write diagnostics: {}", e))?;
+    info!("[Resources] Diagnostics written to {}", path);
+
+    let restart = restart_service(None).await;
+    *RESOURCE_MONITOR.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    diagnostics.restarted = restart.is_ok();
+    diagnostics.restart_message = restart.unwrap_or_else(|e| format!("Restart failed: {}", e));
+    Ok(diagnostics)
+}
+
+// ============ Crash-Loop Detection ============
+
+/// Default: more than 3 restarts within 5 minutes is a crash loop
+const CRASH_LOOP_MAX_RESTARTS: u64 = 3;
+const CRASH_LOOP_WINDOW_MINUTES: u64 = 5;
+
+/// Details captured when the crash-loop breaker trips
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CrashLoopReport {
+    /// When the breaker tripped (RFC3339)
+    pub detected_at: String,
+    pub restart_count: u32,
+    pub window_minutes: u64,
+    /// Last gateway log lines before giving up (secrets redacted)
+    pub log_excerpt: Vec<String>,
+    /// Config sections changed since the last healthy start (likely culprits)
+    pub suspect_sections: Vec<String>,
+    /// Individual changed config paths
+    pub changed_paths: Vec<String>,
+}
+
+/// Remember the app handle so background threads can notify the user
+pub fn set_app_handle(app: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// App handle for emitting events from code that has none passed in
+pub(crate) fn app_handle() -> Option<&'static tauri::AppHandle> {
+    APP_HANDLE.get()
+}
+
+fn get_crash_loop_report_path() -> String {
+    if crate::utils::platform::is_windows() {
+        format!("{}\\crash-loop.json", crate::utils::platform::get_manager_data_dir())
+    } else {
+        format!("{}/crash-loop.json", crate::utils::platform::get_manager_data_dir())
+    }
+}
+
+fn get_last_good_config_path() -> String {
+    if crate::utils::platform::is_windows() {
+        format!("{}\\last-good-config.json", crate::utils::platform::get_manager_data_dir())
+    } else {
+        format!("{}/last-good-config.json", crate::utils::platform::get_manager_data_dir())
+    }
+}
+
+/// Read crash-loop thresholds from manager.json (crashLoop.maxRestarts / windowMinutes)
+fn crash_loop_limits() -> (u64, u64) {
+    let manager_config = crate::commands::config::load_manager_config().unwrap_or_default();
+    (
+        manager_config.pointer("/crashLoop/maxRestarts").and_then(|v| v.as_u64()).unwrap_or(CRASH_LOOP_MAX_RESTARTS),
+        manager_config.pointer("/crashLoop/windowMinutes").and_then(|v| v.as_u64()).unwrap_or(CRASH_LOOP_WINDOW_MINUTES),
+    )
+}
+
+/// Record a supervisor restart; returns true when the restart budget is exhausted
+fn record_restart_and_check_loop() -> bool {
+    let (max_restarts, window_minutes) = crash_loop_limits();
+    let window = Duration::from_secs(window_minutes * 60);
+    let mut times = RESTART_TIMES.lock().unwrap_or_else(|e| e.into_inner());
+    times.retain(|t| t.elapsed() < window);
+    times.push(Instant::now());
+    times.len() as u64 > max_restarts
+}
+
+fn reset_crash_loop_state() {
+    RESTART_TIMES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    let _ = std::fs::remove_file(get_crash_loop_report_path());
+}
+
+/// Keep a copy of the config the gateway last started healthy with
+fn save_last_good_config() {
+    match crate::utils::config_include::load(&crate::utils::platform::get_config_file_path()) {
+        Ok((config, _)) => {
+            if let Ok(content) = serde_json::to_string_pretty(&config) {
+                if let Err(e) = crate::utils::file::write_file(&get_last_good_config_path(), &content) {
+                    debug!("[Crash Loop] Failed to save last good config: {}", e);
+                }
+            }
+        }
+        Err(e) => debug!("[Crash Loop] Failed to read config: {}", e),
+    }
+}
+
+/// Stop auto-restarting, capture diagnostics and notify the user
+fn trip_crash_loop_breaker() {
+    let (_, window_minutes) = crash_loop_limits();
+    let restart_count = RESTART_TIMES.lock().map(|t| t.len() as u32).unwrap_or(0);
+    error!("[Crash Loop] Gateway restarted {} times in {} minutes, auto-restart disabled",
+        restart_count, window_minutes);
+
+    let log_excerpt: Vec<String> = shell::run_openclaw(&["logs", "--limit", "40"])
+        .map(|out| out.lines().map(crate::utils::log_sanitizer::sanitize).collect())
+        .unwrap_or_default();
+
+    // Compare the current config with the last one the gateway was healthy with
+    let mut changed_paths = Vec::new();
+    let mut suspect_sections: Vec<String> = Vec::new();
+    let last_good = crate::utils::file::read_file(&get_last_good_config_path())
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+    let current = crate::utils::config_include::load(&crate::utils::platform::get_config_file_path())
+        .map(|(c, _)| c)
+        .ok();
+    if let (Some(last_good), Some(current)) = (last_good, current) {
+        for change in crate::utils::json_diff::diff(&last_good, &current) {
+            let section = change.path.split('.').take(2).collect::<Vec<_>>().join(".");
+            if !suspect_sections.contains(&section) {
+                suspect_sections.push(section);
+            }
+            changed_paths.push(change.path);
+        }
+    }
+
+    let report = CrashLoopReport {
+        detected_at: chrono::Utc::now().to_rfc3339(),
+        restart_count,
+        window_minutes,
+        log_excerpt,
+        suspect_sections,
+        changed_paths,
+    };
+    if let Ok(content) = serde_json::to_string_pretty(&report) {
+        let _ = crate::utils::file::write_file(&get_crash_loop_report_path(), &content);
+    }
+
+    events::emit(ManagerEvent::alert(
+        "crash-loop",
+        AlertLevel::Error,
+        "Gateway keeps crashing, auto-restart stopped",
+        &report,
+    ));
+    if let Some(app) = APP_HANDLE.get() {
+        use tauri_plugin_notification::NotificationExt;
+
+        let body = match report.suspect_sections.first() {
+            Some(section) => format!("Auto-restart stopped. Recent change in '{}' is the likely cause.", section),
+            None => "Auto-restart stopped. Check the gateway logs for details.".to_string(),
+        };
+        if let Err(e) = app.notification().builder().title("OpenClaw gateway keeps crashing").body(body).show() {
+            debug!("[Crash Loop] Failed to show notification: {}", e);
+        }
+    }
+}
+
+/// Get the crash-loop report, if the breaker has tripped
+#[command]
+#[specta::specta]
+pub async fn get_crash_loop_status() -> Result<Option<CrashLoopReport>, String> {
+    let content = match crate::utils::file::read_file(&get_crash_loop_report_path()) {
+        Ok(c) => c,
+        Err(_) => return Ok(None),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse crash-loop report: {}", e))
+}
+
+/// Clear the crash-loop state. The supervisor exits when the breaker trips, so a tripped
+/// breaker also starts the gateway again (with a new supervisor) unless it is already up.
+#[command]
+#[specta::specta]
+pub async fn reset_crash_loop() -> Result<String, String> {
+    info!("[Crash Loop] Resetting crash-loop breaker");
+    let tripped = std::path::Path::new(&get_crash_loop_report_path()).exists();
+    reset_crash_loop_state();
+    if !tripped {
+        return Ok("Crash-loop breaker reset".to_string());
+    }
+    if shell::run_openclaw(&["gateway", "health", "--timeout", "2000"]).is_ok() {
+        return Ok(
+            "Crash-loop breaker reset. The gateway is running without auto-restart, restart the service to turn it back on"
+                .to_string(),
+        );
+    }
+    info!("[Crash Loop] Starting the gateway after the reset");
+    start_service(None)
+        .await
+        .map(|started| format!("Crash-loop breaker reset. {}", started))
+        .map_err(|e| format!("Crash-loop breaker reset, but the gateway could not be started: {}", e))
+}
+
+// ============ Restart Diffs ============
+
+/// Oldest restart diffs are dropped beyond this many
+const MAX_RESTART_DIFFS: usize = 200;
+
+/// How the effective config differed from the previous gateway start
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartDiff {
+    pub timestamp: String,
+    /// "start", "restart" or "supervisor" (automatic restart after a failed health check)
+    pub trigger: String,
+    /// When the config compared against was snapshotted; None for the first recorded start
+    pub previous_at: Option<String>,
+    /// Top-level config sections that differ
+    pub sections: Vec<String>,
+    /// Changes with secrets masked
+    pub changes: Vec<crate::utils::json_diff::JsonChange>,
+}
+
+/// Effective config of the last gateway start ({ takenAt, config })
+fn get_start_config_path() -> String {
+    platform::path_string(&platform::join_path(platform::get_manager_data_dir(), &["gateway-start-config.json"]))
+}
+
+fn get_restart_diffs_path() -> String {
+    platform::path_string(&platform::join_path(platform::get_manager_data_dir(), &["restart-diffs.jsonl"]))
+}
+
+fn read_restart_diffs() -> Vec<RestartDiff> {
+    crate::utils::file::read_file(&get_restart_diffs_path())
+        .map(|content| content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Snapshot the effective config the gateway is about to start with and record how it
+/// differs from the one of the previous start
+fn record_restart_diff(trigger: &str) {
+    let config = match crate::utils::config_include::load(&platform::get_config_file_path()) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            debug!("[Restart Diff] Failed to read config: {}", e);
+            return;
+        }
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    let previous = crate::utils::file::read_file(&get_start_config_path())
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+    let changes = previous
+        .as_ref()
+        .and_then(|p| p.get("config"))
+        .map(|old| crate::utils::json_diff::diff_masked(old, &config))
+        .unwrap_or_default();
+    let mut sections: Vec<String> = changes
+        .iter()
+        .filter_map(|c| c.path.split('.').next().filter(|s| !s.is_empty()).map(String::from))
+        .collect();
+    sections.sort();
+    sections.dedup();
+    let entry = RestartDiff {
+        timestamp: now.clone(),
+        trigger: trigger.to_string(),
+        previous_at: previous
+            .as_ref()
+            .and_then(|p| p.get("takenAt"))
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        sections,
+        changes,
+    };
+    info!("[Restart Diff] Gateway {} with {} config change(s) since the last start", trigger, entry.changes.len());
+
+    let mut entries = read_restart_diffs();
+    entries.push(entry);
+    let skip = entries.len().saturating_sub(MAX_RESTART_DIFFS);
+    let lines: Vec<String> = entries[skip..].iter().filter_map(|e| serde_json::to_string(e).ok()).collect();
+    if let Err(e) = crate::utils::file::write_file_atomic(&get_restart_diffs_path(), &(lines.join("\n") + "\n"), false) {
+        warn!("[Restart Diff] Failed to record restart diff: {}", e);
+    }
+    let snapshot = serde_json::json!({ "takenAt": now, "config": config });
+    if let Ok(content) = serde_json::to_string_pretty(&snapshot) {
+        if let Err(e) = crate::utils::file::write_file_atomic(&get_start_config_path(), &content, false) {
+            warn!("[Restart Diff] Failed to snapshot the start config: {}", e);
+        }
+    }
+}
+
+/// Config differences recorded at each gateway start, newest first
+#[command]
+#[specta::specta]
+pub async fn get_restart_diffs(limit: Option<usize>) -> Result<Vec<RestartDiff>, String> {
+    let mut entries = read_restart_diffs();
+    entries.reverse();
+    entries.truncate(limit.unwrap_or(50));
+    Ok(entries)
+}
+
+// ============ Start Readiness ============
+
+/// One pre-start check for an enabled channel
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ReadinessCheck {
+    pub channel: String,
+    /// "token" | "session" | "plugin" | "placeholder" | "memory" | "disk"
+    pub check: String,
+    /// "ok" | "warning" | "error" (errors block the start)
+    pub status: String,
+    pub message: String,
+}
+
+/// Pre-start checklist for all enabled channels
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct StartReadiness {
+    /// False when any check is an error
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+/// Channels that come from a separately installed plugin rather than the core
+const EXTERNAL_PLUGIN_CHANNELS: &[&str] = &["feishu", "wechat", "dingtalk"];
+
+fn readiness_check(channel: &str, check: &str, status: &str, message: impl Into<String>) -> ReadinessCheck {
+    ReadinessCheck {
+        channel: channel.to_string(),
+        check: check.to_string(),
+        status: status.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Non-empty string at `key` in the channel config or any of its accounts
+fn channel_has_value(channel: &serde_json::Value, key: &str) -> bool {
+    let has = |v: &serde_json::Value| v.get(key).and_then(|v| v.as_str()).map(|s| !s.trim().is_empty()).unwrap_or(false);
+    has(channel)
+        || channel
+            .get("accounts")
+            .and_then(|a| a.as_object())
+            .map(|accounts| accounts.values().any(has))
+            .unwrap_or(false)
+}
+
+/// Value from the process env or the Manager's env file
+fn env_value_present(key: &str) -> bool {
+    std::env::var(key).map(|v| !v.is_empty()).unwrap_or(false)
+        || crate::utils::file::read_env_value(&crate::utils::platform::get_env_file_path(), key)
+            .map(|v| !v.is_empty())
+            .unwrap_or(false)
+}
+
+/// WhatsApp keeps its login state in credentials/whatsapp/<account>/creds.json
+fn whatsapp_session_exists() -> bool {
+    let dir = std::path::Path::new(&crate::utils::platform::get_credentials_dir()).join("whatsapp");
+    dir.join("creds.json").exists()
+        || std::fs::read_dir(&dir)
+            .map(|entries| entries.flatten().any(|e| e.path().join("creds.json").exists()))
+            .unwrap_or(false)
+}
+
+fn collect_start_readiness() -> StartReadiness {
+    let config = crate::utils::config_include::load(&crate::utils::platform::get_config_file_path())
+        .map(|(c, _)| c)
+        .unwrap_or_else(|_| serde_json::json!({}));
+    let channels = config.get("channels").and_then(|c| c.as_object()).cloned().unwrap_or_default();
+    let plugins_allow: Vec<String> = config
+        .pointer("/plugins/allow")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let mut plugin_list: Option<String> = None;
+
+    let mut checks = Vec::new();
+    let mut ids: Vec<&String> = channels.keys().collect();
+    ids.sort();
+    for id in ids {
+        let channel = &channels[id];
+        // WhatsApp has no 'enabled' key, presence means enabled
+        if channel.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+            continue;
+        }
+
+        // Credentials
+        let token_check = match id.as_str() {
+            "telegram" => Some(
+                channel_has_value(channel, "botToken")
+                    || channel_has_value(channel, "tokenFile")
+                    || env_value_present("TELEGRAM_BOT_TOKEN"),
+            ),
+            "discord" => Some(channel_has_value(channel, "token") || env_value_present("DISCORD_BOT_TOKEN")),
+            "slack" => Some(
+                (channel_has_value(channel, "botToken") || env_value_present("SLACK_BOT_TOKEN"))
+                    && (channel_has_value(channel, "appToken") || env_value_present("SLACK_APP_TOKEN")),
+            ),
+            "feishu" => Some(channel_has_value(channel, "appId") && channel_has_value(channel, "appSecret")),
+            _ => None,
+        };
+        match token_check {
+            Some(true) => checks.push(readiness_check(id, "token", "ok", "Credentials configured")),
+            Some(false) => checks.push(readiness_check(id, "token", "error", "Channel is enabled but has no token configured")),
+            None => {}
+        }
+
+        // Login session
+        if id == "whatsapp" {
+            if whatsapp_session_exists() {
+                checks.push(readiness_check(id, "session", "ok", "Session files found"));
+            } else {
+                checks.push(readiness_check(
+                    id,
+                    "session",
+                    "warning",
+                    "Not linked yet: run the WhatsApp login (QR code) before starting, or the channel stays offline",
+                ));
+            }
+        }
+
+        // Plugin
+        let entry_disabled = config
+            .pointer(&format!("/plugins/entries/{}/enabled", id))
+            .and_then(|v| v.as_bool())
+            == Some(false);
+        if entry_disabled {
+            checks.push(readiness_check(id, "plugin", "error", format!("Plugin '{}' is disabled in plugins.entries", id)));
+        } else if !plugins_allow.is_empty() && !plugins_allow.contains(id) {
+            checks.push(readiness_check(id, "plugin", "error", format!("Plugin '{}' is not in plugins.allow", id)));
+        } else if EXTERNAL_PLUGIN_CHANNELS.contains(&id.as_str()) {
+            let list = plugin_list
+                .get_or_insert_with(|| shell::run_openclaw(&["plugins", "list"]).unwrap_or_default().to_lowercase());
+            if list.contains(id.as_str()) {
+                checks.push(readiness_check(id, "plugin", "ok", "Plugin installed"));
+            } else {
+                checks.push(readiness_check(id, "plugin", "error", format!("Plugin for '{}' is not installed", id)));
+            }
+        } else {
+            checks.push(readiness_check(id, "plugin", "ok", "Plugin enabled"));
+        }
+    }
+
+    // ${env:VAR} / ${secret:name} values the gateway could not load
+    for p in placeholders::unresolved_placeholders() {
+        let message = match p.status.as_str() {
+            "invalid" => format!("{} at {} is not a valid name", p.placeholder, p.pointer),
+            _ => format!("{} at {} has no value", p.placeholder, p.pointer),
+        };
+        checks.push(readiness_check("config", "placeholder", "error", message));
+    }
+
+    // Free memory / disk below the configured floors
+    checks.extend(resource_floor_checks(true));
+
+    StartReadiness {
+        ready: !checks.iter().any(|c| c.status == "error"),
+        checks,
+    }
+}
+
+/// Run the readiness checklist before spawning the gateway. Warnings are logged, errors block unless forced.
+pub(crate) fn check_start_readiness(force: bool) -> Result<(), String> {
+    let readiness = collect_start_readiness();
+    for check in readiness.checks.iter().filter(|c| c.status != "ok") {
+        warn!("[Service] Readiness {} {}/{}: {}", check.status, check.channel, check.check, check.message);
+    }
+    if readiness.ready || force {
+        return Ok(());
+    }
+    let problems: Vec<String> = readiness
+        .checks
+        .iter()
+        .filter(|c| c.status == "error")
+        .map(|c| format!("{}: {}", c.channel, c.message))
+        .collect();
+    Err(format!("Gateway start blocked by readiness checks:\n{}", problems.join("\n")))
+}
+
+/// Memory / disk floors before starting an additional gateway (the built-in one keeps running)
+fn check_instance_resource_floor(force: bool) -> Result<(), String> {
+    let problems: Vec<ReadinessCheck> = resource_floor_checks(false).into_iter().filter(|c| c.status != "ok").collect();
+    for check in &problems {
+        warn!("[Gateways] Resource floor {} {}: {}", check.status, check.check, check.message);
+    }
+    let blocking: Vec<String> = problems.into_iter().filter(|c| c.status == "error").map(|c| c.message).collect();
+    if blocking.is_empty() || force {
+        return Ok(());
+    }
+    Err(format!("Gateway start blocked by resource floors:\n{}", blocking.join("\n")))
+}
+
+/// Pre-start checklist per enabled channel (token present, session files exist, plugin installed)
+/// plus free memory / disk against the configured floors
+#[command]
+#[specta::specta]
+pub async fn get_start_readiness() -> Result<StartReadiness, String> {
+    info!("[Service] Checking start readiness...");
+    let readiness = collect_start_readiness();
+    info!(
+        "[Service] Readiness: {} checks, ready: {}",
+        readiness.checks.len(),
+        readiness.ready
+    );
+    Ok(readiness)
+}
+
+// ============ Channel Pause ============
+
+/// Channels without an `enabled` key in the core schema; they are parked in manager.json instead
+const PARKED_CHANNELS: &[&str] = &["whatsapp"];
+
+/// Current channel pause state
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ChannelPauseStatus {
+    pub paused: bool,
+    pub paused_at: Option<String>,
+    pub channels: Vec<String>,
+}
+
+fn channel_pause_status(manager_config: &serde_json::Value) -> ChannelPauseStatus {
+    let pause = manager_config.get("channelPause");
+    let mut channels: Vec<String> = ["previous", "parked"]
+        .iter()
+        .filter_map(|k| pause.and_then(|p| p.get(*k)).and_then(|v| v.as_object()))
+        .flat_map(|m| m.keys().cloned())
+        .collect();
+    channels.sort();
+    ChannelPauseStatus {
+        paused: pause.is_some(),
+        paused_at: pause.and_then(|p| p.get("pausedAt")).and_then(|v| v.as_str()).map(String::from),
+        channels,
+    }
+}
+
+/// Make a channel config change take effect: the gateway hot-reloads channel changes unless
+/// gateway.reload.mode is "off", in which case a running gateway is restarted
+async fn apply_channel_change(config: &serde_json::Value) {
+    let reload_off = config.pointer("/gateway/reload/mode").and_then(|v| v.as_str()) == Some("off");
+    if !reload_off {
+        return;
+    }
+    if shell::run_openclaw(&["gateway", "health", "--timeout", "2000"]).is_ok() {
+        info!("[Channel Pause] Config reload is off, restarting gateway...");
+        if let Err(e) = restart_service(Some(true)).await {
+            error!("[Channel Pause] Gateway restart failed: {}", e);
+        }
+    }
+}
+
+/// Get whether channels are paused
+#[command]
+#[specta::specta]
+pub async fn get_channel_pause_status() -> Result<ChannelPauseStatus, String> {
+    let manager_config = crate::commands::config::load_manager_config()?;
+    Ok(channel_pause_status(&manager_config))
+}
+
+/// Emergency stop: disable message intake on every channel. Previous state is kept in
+/// manager.json (channelPause) so resume_all_channels can restore it exactly.
+#[command]
+#[specta::specta]
+pub async fn pause_all_channels() -> Result<ChannelPauseStatus, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "pause_all_channels").await;
+    use crate::commands::config::{load_live_openclaw_config, load_manager_config, save_live_openclaw_config, save_manager_config};
+    warn!("[Channel Pause] Pausing all channels");
+
+    let mut manager_config = load_manager_config()?;
+    if manager_config.get("channelPause").is_some() {
+        return Err("Channels are already paused".to_string());
+    }
+    let mut config = load_live_openclaw_config()?;
+    let channels = match config.get_mut("channels").and_then(|c| c.as_object_mut()) {
+        Some(channels) => channels,
+        None => return Err("No channels are configured".to_string()),
+    };
+
+    let mut previous = serde_json::Map::new();
+    let mut parked = serde_json::Map::new();
+    for id in channels.keys().cloned().collect::<Vec<_>>() {
+        if PARKED_CHANNELS.contains(&id.as_str()) {
+            if let Some(value) = channels.remove(&id) {
+                parked.insert(id, value);
+            }
+        } else if let Some(channel) = channels.get_mut(&id).and_then(|c| c.as_object_mut()) {
+            previous.insert(id, channel.get("enabled").cloned().unwrap_or(serde_json::Value::Null));
+            channel.insert("enabled".to_string(), serde_json::json!(false));
+        }
+    }
+
+    // Record the pause first so a failed config write can still be resumed
+    manager_config["channelPause"] = serde_json::json!({
+        "pausedAt": chrono::Utc::now().to_rfc3339(),
+        "previous": previous,
+        "parked": parked,
+    });
+    save_manager_config(&manager_config)?;
+    save_live_openclaw_config(&config, "pause_all_channels")?;
+    // A restart (reload mode "off") takes these locks again
+    drop(_lock);
+    apply_channel_change(&config).await;
+
+    let status = channel_pause_status(&manager_config);
+    warn!("[Channel Pause] Paused {} channel(s)", status.channels.len());
+    Ok(status)
+}
+
+/// Undo pause_all_channels, restoring each channel's previous enabled state
+#[command]
+#[specta::specta]
+pub async fn resume_all_channels() -> Result<ChannelPauseStatus, String> {
+    let _lock = locks::lock(&[ConfigResource::OpenClaw, ConfigResource::Manager], "resume_all_channels").await;
+    use crate::commands::config::{load_live_openclaw_config, load_manager_config, save_live_openclaw_config, save_manager_config};
+    info!("[Channel Pause] Resuming all channels");
+
+    let mut manager_config = load_manager_config()?;
+    let pause = manager_config
+        .get("channelPause")
+        .cloned()
+        .ok_or("Channels are not paused")?;
+    let mut config = load_live_openclaw_config()?;
+    if !config.get("channels").map(|c| c.is_object()).unwrap_or(false) {
+        config["channels"] = serde_json::json!({});
+    }
+    let channels = config["channels"].as_object_mut().unwrap();
+
+    if let Some(previous) = pause.get("previous").and_then(|v| v.as_object()) {
+        for (id, enabled) in previous {
+            if let Some(channel) = channels.get_mut(id).and_then(|c| c.as_object_mut()) {
+                match enabled {
+                    serde_json::Value::Null => channel.remove("enabled"),
+                    value => channel.insert("enabled".to_string(), value.clone()),
+                };
+            }
+        }
+    }
+    if let Some(parked) = pause.get("parked").and_then(|v| v.as_object()) {
+        for (id, value) in parked {
+            // A channel set up again while paused wins over the parked copy
+            if !channels.contains_key(id) {
+                channels.insert(id.clone(), value.clone());
+            }
+        }
+    }
+
+    save_live_openclaw_config(&config, "resume_all_channels")?;
+    if let Some(obj) = manager_config.as_object_mut() {
+        obj.remove("channelPause");
+    }
+    save_manager_config(&manager_config)?;
+    // A restart (reload mode "off") takes these locks again
+    drop(_lock);
+    apply_channel_change(&config).await;
+
+    info!("[Channel Pause] Channels resumed");
+    Ok(channel_pause_status(&manager_config))
+}
+
+// ============ External Gateway Adoption ============
+
+/// Gateway spawned by this Manager
+const ORIGIN_MANAGER: &str = "manager";
+/// Gateway started elsewhere that the user handed over to the Manager
+const ORIGIN_ADOPTED: &str = "adopted";
+/// Gateway started elsewhere (e.g. `openclaw gateway` in a terminal), not adopted
+const ORIGIN_EXTERNAL: &str = "external";
+
+/// A gateway process as identified from the process table
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ExternalGateway {
+    pub pid: u32,
+    /// "manager" | "adopted" | "external"
+    pub origin: String,
+    /// Full command line, if the OS reports it
+    pub command: Option<String>,
+    /// State directory the gateway reads its config from
+    pub config_dir: Option<String>,
+    /// Whether that is the directory the Manager edits (None when unknown)
+    pub config_dir_matches: Option<bool>,
+    /// When the Manager started or adopted it (RFC3339)
+    pub since: Option<String>,
+}
+
+/// Owner record in manager.json (gatewayOwner), survives Manager restarts
+fn gateway_owner() -> Option<serde_json::Value> {
+    crate::commands::config::load_manager_config()
+        .ok()
+        .and_then(|config| config.get("gatewayOwner").cloned())
+}
+
+/// Who started the gateway listening as `pid`. A recorded owner only counts while the PID
+/// matches, so a gateway restarted from a terminal shows up as external again.
+fn gateway_origin(pid: u32) -> &'static str {
+    let owner = gateway_owner();
+    let owned = owner.as_ref().and_then(|o| o.get("pid")).and_then(|v| v.as_u64()) == Some(pid as u64);
+    match owner.as_ref().and_then(|o| o.get("origin")).and_then(|v| v.as_str()) {
+        Some(ORIGIN_MANAGER) if owned => ORIGIN_MANAGER,
+        Some(ORIGIN_ADOPTED) if owned => ORIGIN_ADOPTED,
+        _ => ORIGIN_EXTERNAL,
+    }
+}
+
+async fn record_gateway_owner(pid: u32, origin: &str) {
+    let _lock = locks::lock(&[ConfigResource::Manager], "record_gateway_owner").await;
+    let mut manager_config = crate::commands::config::load_manager_config().unwrap_or_else(|_| serde_json::json!({}));
+    manager_config["gatewayOwner"] = serde_json::json!({
+        "pid": pid,
+        "origin": origin,
+        "since": chrono::Utc::now().to_rfc3339(),
+    });
+    if let Err(e) = crate::commands::config::save_manager_config(&manager_config) {
+        warn!("[Service] Failed to record gateway owner: {}", e);
+    }
+}
+
+async fn clear_gateway_owner() {
+    let _lock = locks::lock(&[ConfigResource::Manager], "clear_gateway_owner").await;
+    let mut manager_config = match crate::commands::config::load_manager_config() {
+        Ok(config) => config,
+        Err(_) => return,
+    };
+    if let Some(obj) = manager_config.as_object_mut() {
+        if obj.remove("gatewayOwner").is_some() {
+            if let Err(e) = crate::commands::config::save_manager_config(&manager_config) {
+                warn!("[Service] Failed to clear gateway owner: {}", e);
+            }
+        }
+    }
+}
+
+/// Command line of a running process
+fn process_command_line(pid: u32) -> Option<String> {
+    let output = if platform::is_windows() {
+        shell::run_powershell_output(&format!(
+            "(Get-CimInstance Win32_Process -Filter \"ProcessId={}\").CommandLine",
+            pid
+        ))
+    } else {
+        shell::run_command_output("ps", &["-o", "args=", "-p", &pid.to_string()])
+    };
+    output.ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// OPENCLAW_STATE_DIR of another process; only Linux exposes it (/proc/<pid>/environ)
+fn process_state_dir_env(pid: u32) -> Option<String> {
+    let environ = std::fs::read(format!("/proc/{}/environ", pid)).ok()?;
+    process_tree::environ_value(&environ, "OPENCLAW_STATE_DIR")
+}
+
+fn normalize_dir(dir: &str) -> String {
+    let path = std::fs::canonicalize(dir).unwrap_or_else(|_| std::path::PathBuf::from(dir));
+    platform::path_string(&path).trim_end_matches(['/', '\\']).to_string()
+}
+
+/// Identify the gateway listening on the service port: PID, command line and config directory
+fn inspect_gateway() -> Option<ExternalGateway> {
+    let pid = gateway_pid()?;
+    let command = process_command_line(pid);
+    let config_dir = match (&command, dirs::home_dir()) {
+        (Some(command), Some(home)) => Some(process_tree::gateway_state_dir(
+            command,
+            process_state_dir_env(pid).as_deref(),
+            &platform::path_string(&home),
+        )),
+        _ => None,
+    };
+    // Outside Linux the environment is unknown, so this is a best guess from the CLI flags
+    let config_dir_matches = config_dir
+        .as_ref()
+        .map(|dir| normalize_dir(dir) == normalize_dir(&platform::get_config_dir()));
+    let since = gateway_owner()
+        .filter(|o| o.get("pid").and_then(|v| v.as_u64()) == Some(pid as u64))
+        .and_then(|o| o.get("since").and_then(|v| v.as_str()).map(String::from));
+
+    Some(ExternalGateway {
+        pid,
+        origin: gateway_origin(pid).to_string(),
+        command,
+        config_dir,
+        config_dir_matches,
+        since,
+    })
+}
+
+/// Describe the running gateway and who started it. None if no gateway is listening.
+#[command]
+#[specta::specta]
+pub async fn detect_external_gateway() -> Result<Option<ExternalGateway>, String> {
+    let gateway = inspect_gateway();
+    if let Some(gateway) = &gateway {
+        info!(
+            "[Service] Gateway PID {} ({}), config dir: {:?}",
+            gateway.pid, gateway.origin, gateway.config_dir
+        );
+    }
+    Ok(gateway)
+}
+
+/// Take over a gateway started outside the Manager so stop/restart act on it and the status
+/// attributes it. Refused when it runs on a different config directory than the Manager edits.
+#[command]
+#[specta::specta]
+pub async fn adopt_gateway() -> Result<ExternalGateway, String> {
+    if shell::run_openclaw(&["gateway", "health", "--timeout", "2000"]).is_err() {
+        return Err("No healthy gateway is running".to_string());
+    }
+    let gateway = inspect_gateway().ok_or("No gateway is listening on port 18789")?;
+    if gateway.origin != ORIGIN_EXTERNAL {
+        return Err(format!("Gateway (PID {}) is already managed", gateway.pid));
+    }
+    if gateway.config_dir_matches == Some(false) {
+        return Err(format!(
+            "Gateway (PID {}) uses {}, but the Manager edits {}. Stop it and start it from the Manager instead.",
+            gateway.pid,
+            gateway.config_dir.as_deref().unwrap_or("another directory"),
+            platform::get_config_dir()
+        ));
+    }
+
+    info!("[Service] Adopting external gateway PID {} ({:?})", gateway.pid, gateway.command);
+    record_gateway_owner(gateway.pid, ORIGIN_ADOPTED).await;
+    emit_service_status(true, Some(gateway.pid));
+    inspect_gateway().ok_or_else(|| "Gateway exited during adoption".to_string())
+}
+
+// ============ Gateway Instances ============
+
+/// Id of the built-in gateway (port 18789, the Manager's config directory)
+const DEFAULT_GATEWAY_ID: &str = "default";
+
+/// A gateway with its own port and state directory (manager.json -> gateways)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GatewayInstance {
+    pub id: String,
+    pub name: String,
+    pub port: u16,
+    /// State directory; defaults to ~/.openclaw-<profile>
+    pub config_dir: Option<String>,
+    /// CLI profile passed as `--profile`
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn builtin_gateway_instance() -> GatewayInstance {
+    GatewayInstance {
+        id: DEFAULT_GATEWAY_ID.to_string(),
+        name: "Default".to_string(),
+        port: SERVICE_PORT,
+        config_dir: Some(platform::get_config_dir()),
+        profile: None,
+        builtin: true,
+    }
+}
+
+fn load_gateway_instances() -> Vec<GatewayInstance> {
+    let manager_config = crate::commands::config::load_manager_config().unwrap_or_else(|_| serde_json::json!({}));
+    let mut instances: Vec<GatewayInstance> = manager_config
+        .get("gateways")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    instances.retain(|g| g.id != DEFAULT_GATEWAY_ID);
+    instances.insert(0, builtin_gateway_instance());
+    instances
+}
+
+fn save_gateway_instances(instances: &[GatewayInstance]) -> Result<(), String> {
+    let instances: Vec<&GatewayInstance> = instances.iter().filter(|g| !g.builtin).collect();
+    let mut manager_config = crate::commands::config::load_manager_config()?;
+    manager_config["gateways"] = serde_json::to_value(instances)
+        .map_err(|e| format!("Failed to serialize gateways: {}", e))?;
+    crate::commands::config::save_manager_config(&manager_config)
+}
+
+fn find_gateway_instance(id: &str) -> Result<GatewayInstance, String> {
+    load_gateway_instances()
+        .into_iter()
+        .find(|g| g.id == id)
+        .ok_or(format!("Gateway not found: {}", id))
+}
+
+/// State directory an instance runs on
+fn instance_state_dir(instance: &GatewayInstance) -> String {
+    if let Some(dir) = instance.config_dir.as_deref().filter(|d| !d.trim().is_empty()) {
+        return dir.to_string();
+    }
+    let home = dirs::home_dir().map(|h| platform::path_string(&h)).unwrap_or_else(|| "~".to_string());
+    let profile = instance.profile.as_deref().map(|p| format!("--profile {}", p)).unwrap_or_default();
+    process_tree::gateway_state_dir(&profile, None, &home)
+}
+
+/// Environment that points the CLI at the instance's state directory, port and token
+fn instance_env(instance: &GatewayInstance) -> Vec<(&'static str, String)> {
+    let state_dir = instance_state_dir(instance);
+    let config_path = platform::path_string(&std::path::Path::new(&state_dir).join("openclaw.json"));
+    let mut env = vec![
+        ("OPENCLAW_STATE_DIR", state_dir),
+        ("OPENCLAW_GATEWAY_PORT", instance.port.to_string()),
+    ];
+    let token = crate::utils::config_include::load(&config_path)
+        .ok()
+        .and_then(|(c, _)| c.pointer("/gateway/auth/token").and_then(|v| v.as_str()).map(String::from));
+    if let Some(token) = token {
+        env.push(("OPENCLAW_GATEWAY_TOKEN", token));
+    }
+    env.push(("OPENCLAW_CONFIG_PATH", config_path));
+    env
+}
+
+fn instance_global_args(instance: &GatewayInstance) -> Vec<&str> {
+    match instance.profile.as_deref().filter(|p| !p.is_empty()) {
+        Some(profile) => vec!["--profile", profile],
+        None => Vec::new(),
+    }
+}
+
+fn run_instance_openclaw(instance: &GatewayInstance, args: &[&str]) -> Result<String, String> {
+    let mut full_args = instance_global_args(instance);
+    full_args.extend_from_slice(args);
+    shell::run_openclaw_with_env(&full_args, &instance_env(instance))
+}
+
+fn instance_status(instance: &GatewayInstance) -> ServiceStatus {
+    let health_ok = run_instance_openclaw(instance, &["gateway", "health", "--timeout", "3000"]).is_ok();
+    let pid = check_port_listening(instance.port).filter(|_| health_ok);
+    ServiceStatus {
+        running: pid.is_some(),
+        pid,
+        port: instance.port,
+        origin: None,
+        ..ServiceStatus::default()
+    }
+}
+
+/// End the openclaw gateway processes listening on `port`. Anything else holding the port is
+/// left alone (and reported by the caller when the port stays busy).
+fn kill_port_gateways(port: u16) {
+    for pid in find_all_port_pids(port) {
+        if !process_command_line(pid).map(|c| process_tree::is_gateway_command(&c)).unwrap_or(false) {
+            warn!("[Gateways] PID {} on port {} is not an openclaw gateway, not killing it", pid, port);
+            continue;
+        }
+        #[cfg(windows)]
+        {
+            let mut cmd = Command::new("taskkill");
+            cmd.args(["/F", "/T", "/PID", &pid.to_string()]);
+            cmd.creation_flags(CREATE_NO_WINDOW);
+            let _ = shell::output(&mut cmd);
+        }
+        #[cfg(unix)]
+        {
+            let _ = shell::output(Command::new("kill").args(["-9", &pid.to_string()]));
+        }
+    }
+}
+
+/// All gateways: the built-in one first, then user-defined instances
+#[command]
+#[specta::specta]
+pub async fn list_gateway_instances() -> Result<Vec<GatewayInstance>, String> {
+    Ok(load_gateway_instances())
+}
+
+/// Add or update a gateway instance. Ports and state directories must be unique.
+#[command]
+#[specta::specta]
+pub async fn save_gateway_instance(instance: GatewayInstance) -> Result<GatewayInstance, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "save_gateway_instance").await;
+    let id = instance.id.trim().to_lowercase().replace(' ', "-");
+    if id.is_empty() || id == DEFAULT_GATEWAY_ID {
+        return Err(format!("Gateway id must be set and not '{}'", DEFAULT_GATEWAY_ID));
+    }
+    if instance.port < 1024 {
+        return Err("Gateway port must be 1024 or higher".to_string());
+    }
+    let instance = GatewayInstance {
+        id,
+        config_dir: instance.config_dir.filter(|d| !d.trim().is_empty()),
+        profile: instance.profile.filter(|p| !p.trim().is_empty()),
+        builtin: false,
+        ..instance
+    };
+    if instance.config_dir.is_none() && instance.profile.is_none() {
+        return Err("Set a config directory or a profile, or the gateway would share the default state".to_string());
+    }
+
+    let state_dir = normalize_dir(&instance_state_dir(&instance));
+    let mut instances = load_gateway_instances();
+    for other in instances.iter().filter(|g| g.id != instance.id) {
+        if other.port == instance.port {
+            return Err(format!("Port {} is already used by gateway '{}'", instance.port, other.name));
+        }
+        if normalize_dir(&instance_state_dir(other)) == state_dir {
+            return Err(format!("Config directory {} is already used by gateway '{}'", state_dir, other.name));
+        }
+    }
+    info!("[Gateways] Saving gateway '{}' on port {} ({})", instance.id, instance.port, state_dir);
+
+    match instances.iter_mut().find(|g| g.id == instance.id) {
+        Some(existing) => *existing = instance.clone(),
+        None => instances.push(instance.clone()),
+    }
+    save_gateway_instances(&instances)?;
+    Ok(instance)
+}
+
+/// Remove a gateway instance (it must be stopped first; its state directory is kept)
+#[command]
+#[specta::specta]
+pub async fn delete_gateway_instance(id: String) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "delete_gateway_instance").await;
+    let instance = find_gateway_instance(&id)?;
+    if instance.builtin {
+        return Err("The default gateway cannot be removed".to_string());
+    }
+    if check_port_listening(instance.port).is_some() {
+        return Err(format!("Stop gateway '{}' before removing it", instance.name));
+    }
+    info!("[Gateways] Deleting gateway '{}'", id);
+    let instances: Vec<GatewayInstance> = load_gateway_instances().into_iter().filter(|g| g.id != id).collect();
+    save_gateway_instances(&instances)?;
+    Ok(format!("Gateway '{}' removed", instance.name))
+}
+
+/// Status of one gateway
+#[command]
+#[specta::specta]
+pub async fn get_gateway_instance_status(id: String) -> Result<ServiceStatus, String> {
+    let instance = find_gateway_instance(&id)?;
+    if instance.builtin {
+        return get_service_status().await;
+    }
+    Ok(instance_status(&instance))
+}
+
+/// Start one gateway. Additional instances are not supervised: a crash is not auto-restarted.
+#[command]
+#[specta::specta]
+pub async fn start_gateway_instance(id: String, force: Option<bool>) -> Result<String, String> {
+    let instance = find_gateway_instance(&id)?;
+    if instance.builtin {
+        return start_service(force).await;
+    }
+    info!("[Gateways] Starting gateway '{}' on port {}...", instance.id, instance.port);
+    if instance_status(&instance).running {
+        return Err(format!("Gateway '{}' is already running", instance.name));
+    }
+    if let Some(pid) = check_port_listening(instance.port) {
+        return Err(format!("Port {} is in use by another process (PID {})", instance.port, pid));
+    }
+    check_instance_resource_floor(force.unwrap_or(false))?;
+    {
+        let _lock = locks::lock(&[ConfigResource::OpenClaw], "start_gateway_instance").await;
+        config::ensure_gateway_auth()?;
+    }
+
+    let global_args = instance_global_args(&instance);
+    shell::spawn_openclaw_gateway_on(instance.port, &global_args, &instance_env(&instance))
+        .map_err(|e| format!("Failed to start gateway '{}': {}", instance.name, e))?;
+
+    for i in 1..=15 {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        if let Some(pid) = check_port_listening(instance.port) {
+            info!("[Gateways] Gateway '{}' listening on port {} ({}s)", instance.id, instance.port, i);
+            return Ok(format!("Gateway '{}' started, PID: {}", instance.name, pid));
+        }
+    }
+    Err(format!("Gateway '{}' start timeout: port {} not listening after 15s", instance.name, instance.port))
+}
+
+/// Stop one gateway
+#[command]
+#[specta::specta]
+pub async fn stop_gateway_instance(id: String) -> Result<String, String> {
+    let instance = find_gateway_instance(&id)?;
+    if instance.builtin {
+        return stop_service().await;
+    }
+    info!("[Gateways] Stopping gateway '{}'...", instance.id);
+    let _ = run_instance_openclaw(&instance, &["gateway", "stop"]);
+    for _ in 0..5 {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        if check_port_listening(instance.port).is_none() {
+            return Ok(format!("Gateway '{}' stopped", instance.name));
+        }
+    }
+
+    info!("[Gateways] Graceful stop failed, killing the gateway on port {}", instance.port);
+    kill_port_gateways(instance.port);
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+    match check_port_listening(instance.port) {
+        None => Ok(format!("Gateway '{}' stopped (killed)", instance.name)),
+        Some(pid) => Err(format!(
+            "Failed to stop gateway '{}': port {} is still held by PID {}",
+            instance.name, instance.port, pid
+        )),
+    }
+}
+
+/// Recent log lines of one gateway
+#[command]
+#[specta::specta]
+pub async fn get_gateway_instance_logs(id: String, lines: Option<u32>) -> Result<Vec<String>, String> {
+    let instance = find_gateway_instance(&id)?;
+    if instance.builtin {
+        return get_logs(lines).await;
+    }
+    let n = lines.unwrap_or(100).to_string();
+    run_instance_openclaw(&instance, &["logs", "--limit", &n])
+        .map(|output| output.lines().map(|s| s.to_string()).collect())
+        .map_err(|e| format!("Failed to read logs: {}", e))
+}
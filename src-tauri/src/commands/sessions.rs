@@ -0,0 +1,193 @@
+use crate::commands::config::{agent_soul_candidates, agent_workspace_dir, load_openclaw_config};
+use crate::utils::session_replay::{self, ReplayMessage, ReplayTurn, BOOTSTRAP_FILES};
+use crate::utils::{file, platform};
+use chrono::{DateTime, Duration, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::command;
+
+/// A workspace file that is part of the prompt stack
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptFile {
+    pub name: String,
+    pub path: String,
+    /// Injected into the system prompt; memory notes are only read through tools
+    pub included: bool,
+    /// Content as injected (after the bootstrapMaxChars cut)
+    pub content: String,
+    pub truncated: bool,
+    pub modified_at: Option<String>,
+    /// Modified after the session started, so the model may have seen another version
+    pub changed_since_session: bool,
+}
+
+/// The prompt stack of every model call in a session
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReplay {
+    pub agent_id: String,
+    pub session_id: String,
+    pub started_at: Option<String>,
+    pub files: Vec<PromptFile>,
+    pub messages: Vec<ReplayMessage>,
+    pub turns: Vec<ReplayTurn>,
+    /// What the replay could not reconstruct
+    pub notes: Vec<String>,
+    /// Where the export was written
+    pub export_path: String,
+}
+
+fn validate_id(kind: &str, id: &str) -> Result<(), String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid {} '{}'", kind, id));
+    }
+    Ok(())
+}
+
+fn modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+fn prompt_file(
+    name: &str,
+    path: &Path,
+    included: bool,
+    max_chars: usize,
+    started: Option<DateTime<Utc>>,
+) -> Option<PromptFile> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let (content, truncated) = if included {
+        session_replay::truncate_bootstrap(&content, max_chars)
+    } else {
+        (content, false)
+    };
+    let modified = modified_at(path);
+    Some(PromptFile {
+        name: name.to_string(),
+        path: platform::path_string(path),
+        included,
+        content,
+        truncated,
+        modified_at: modified.map(|m| m.to_rfc3339()),
+        changed_since_session: matches!((modified, started), (Some(m), Some(s)) if m > s),
+    })
+}
+
+/// Reconstruct the prompt stack (workspace files, memory, conversation window) of each model
+/// call in a session and write it to manager/replays/<agent>-<session>.json
+#[command]
+#[specta::specta]
+pub async fn export_replay(agent_id: String, session_id: String) -> Result<SessionReplay, String> {
+    validate_id("agent id", &agent_id)?;
+    let session_id = session_id.trim_end_matches(".jsonl").to_string();
+    validate_id("session id", &session_id)?;
+    info!("[Sessions] Exporting replay of {}/{}", agent_id, session_id);
+
+    let config = load_openclaw_config()?;
+    let openclaw_home = platform::get_config_dir();
+    let transcript_path = platform::join_path(
+        &openclaw_home,
+        &[
+            "agents",
+            &agent_id,
+            "sessions",
+            &format!("{}.jsonl", session_id),
+        ],
+    );
+    let content = file::read_file(&platform::path_string(&transcript_path)).map_err(|e| {
+        format!(
+            "Failed to read session {}: {}",
+            platform::path_string(&transcript_path),
+            e
+        )
+    })?;
+    let transcript = session_replay::parse(&content);
+    let started = transcript
+        .started_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc));
+
+    let mut notes = vec![
+        "OpenClaw does not store the system prompt in the transcript; it is rebuilt from the current workspace files".to_string(),
+        "Tool schemas, the skills list and runtime sections of the system prompt are not included".to_string(),
+    ];
+    if transcript.skipped_lines > 0 {
+        notes.push(format!(
+            "{} transcript line(s) could not be parsed",
+            transcript.skipped_lines
+        ));
+    }
+
+    let max_chars = config
+        .pointer("/agents/defaults/bootstrapMaxChars")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(session_replay::DEFAULT_BOOTSTRAP_MAX_CHARS);
+    let workspace = agent_workspace_dir(&config, &agent_id)?;
+    let mut files = Vec::new();
+    for name in BOOTSTRAP_FILES {
+        let mut path = workspace.join(name);
+        if !path.exists() && *name == "SOUL.md" {
+            // Older Manager versions kept the personality next to the agent's state
+            if let Some(legacy) = agent_soul_candidates(&config, &openclaw_home, &agent_id)
+                .into_iter()
+                .find(|p| p.exists())
+            {
+                path = legacy;
+            }
+        }
+        if !path.exists() && *name == "MEMORY.md" {
+            path = workspace.join("memory.md");
+        }
+        files.extend(prompt_file(name, &path, true, max_chars, started));
+    }
+
+    // Daily notes the agent reads at session start (the session's day and the day before)
+    if let Some(day) = started.map(|s| s.date_naive()) {
+        for date in [day - Duration::days(1), day] {
+            let name = format!("memory/{}.md", date.format("%Y-%m-%d"));
+            let path = platform::join_path(
+                &workspace,
+                &["memory", &format!("{}.md", date.format("%Y-%m-%d"))],
+            );
+            files.extend(prompt_file(&name, &path, false, max_chars, started));
+        }
+    }
+    for changed in files.iter().filter(|f| f.changed_since_session) {
+        notes.push(format!(
+            "{} changed after the session started",
+            changed.name
+        ));
+    }
+
+    let replay_dir = platform::join_path(platform::get_manager_data_dir(), &["replays"]);
+    std::fs::create_dir_all(&replay_dir)
+        .map_err(|e| format!("Failed to create replay directory: {}", e))?;
+    let export_path =
+        platform::path_string(&replay_dir.join(format!("{}-{}.json", agent_id, session_id)));
+
+    let replay = SessionReplay {
+        agent_id,
+        session_id,
+        started_at: transcript.started_at,
+        files,
+        messages: transcript.messages,
+        turns: transcript.turns,
+        notes,
+        export_path: export_path.clone(),
+    };
+    let json = serde_json::to_string_pretty(&replay).map_err(|e| e.to_string())?;
+    file::write_file(&export_path, &json).map_err(|e| format!("Failed to write replay: {}", e))?;
+    info!(
+        "[Sessions] Replay with {} turn(s) written to {}",
+        replay.turns.len(),
+        export_path
+    );
+    Ok(replay)
+}
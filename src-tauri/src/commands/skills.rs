@@ -1,3 +1,6 @@
+use crate::events::{self, ManagerEvent};
+use crate::utils::task_pool::{self, BulkItemResult};
+use crate::utils::{node_pm, platform, shell};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::process::Command;
@@ -6,7 +9,7 @@ use std::os::windows::process::CommandExt;
 use tauri::command;
 use log::{info, error, debug};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct Skill {
     pub id: String,
     pub name: String,
@@ -14,22 +17,23 @@ pub struct Skill {
     pub path: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, specta::Type)]
 struct SkillFrontmatter {
     name: String,
     description: Option<String>,
 }
 
 #[command]
+
+#[specta::specta]
 pub async fn get_skills() -> Result<Vec<Skill>, String> {
     info!("Executing get_skills command");
     let mut skills = Vec::new();
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let skills_dir = home_dir.join(".openclaw").join("skills");
+    let skills_dir = skills_dir()?;
     info!("Using skills directory: {:?}", skills_dir);
 
     if !skills_dir.exists() {
-        info!("Skills directory does not exist, looking in ~/.openclaw/skills");
+        info!("Skills directory {:?} does not exist", skills_dir);
         return Ok(skills);
     }
 
@@ -82,6 +86,8 @@ fn create_command(program: &str) -> Command {
 }
 
 #[command]
+
+#[specta::specta]
 pub async fn check_clawhub_installed() -> Result<bool, String> {
     info!("Checking if clawhub is installed");
     
@@ -96,60 +102,39 @@ pub async fn check_clawhub_installed() -> Result<bool, String> {
     #[cfg(not(target_os = "windows"))]
     let args = ["--version"];
 
-    if let Ok(output) = create_command(program).args(args).output() {
+    if let Ok(output) = shell::output(create_command(program).args(args)) {
         if output.status.success() {
             debug!("clawhub command found locally");
             return Ok(true);
         }
     }
 
-    // Method 2: Check via npm list -g (more robust if PATH isn't updated)
-    info!("Direct command failed, checking via npm list -g");
-    #[cfg(target_os = "windows")]
-    let program = "cmd";
-    #[cfg(target_os = "windows")]
-    let args = ["/C", "npm list -g clawhub --depth=0"];
-
-    #[cfg(not(target_os = "windows"))]
-    let program = "npm";
-    #[cfg(not(target_os = "windows"))]
-    let args = ["list", "-g", "clawhub", "--depth=0"];
-
-    let output = create_command(program)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to execute npm list: {}", e))?;
+    // Method 2: Check the package manager's global list (more robust if PATH isn't updated)
+    let pm = node_pm::active();
+    info!("Direct command failed, checking via {} global list", pm.name());
+    let output = shell::output(&mut node_pm::command(&pm.global_list("clawhub")))
+        .map_err(|e| format!("Failed to execute {} list: {}", pm.name(), e))?;
 
-    // npm list returns 0 if found (or empty), 1 if empty/error depending on version
-    // We check if stdout contains "clawhub@"
+    // The list exits 0 or 1 depending on version, so check the printed packages instead
     let stdout = String::from_utf8_lossy(&output.stdout);
-    if stdout.contains("clawhub@") {
-        debug!("clawhub found in npm global list");
+    if node_pm::list_mentions(&stdout, "clawhub") {
+        debug!("clawhub found in {} global list", pm.name());
         Ok(true)
     } else {
-        debug!("clawhub not found in npm global list");
+        debug!("clawhub not found in {} global list", pm.name());
         Ok(false)
     }
 }
 
 #[command]
-pub async fn install_clawhub() -> Result<String, String> {
-    info!("Installing clawhub globally via npm");
-
-    #[cfg(target_os = "windows")]
-    let program = "cmd";
-    #[cfg(target_os = "windows")]
-    let args = ["/C", "npm install -g clawhub"];
 
-    #[cfg(not(target_os = "windows"))]
-    let program = "npm";
-    #[cfg(not(target_os = "windows"))]
-    let args = ["install", "-g", "clawhub"];
+#[specta::specta]
+pub async fn install_clawhub() -> Result<String, String> {
+    let pm = node_pm::active();
+    info!("Installing clawhub globally via {}", pm.name());
 
-    let output = create_command(program)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to execute npm install: {}", e))?;
+    let output = shell::output(&mut node_pm::command(&pm.global_add("clawhub")))
+        .map_err(|e| format!("Failed to execute {} install: {}", pm.name(), e))?;
 
     if output.status.success() {
         info!("clawhub installed successfully");
@@ -162,33 +147,62 @@ pub async fn install_clawhub() -> Result<String, String> {
 }
 
 #[command]
+
+#[specta::specta]
 pub async fn install_skill(skill_name: String) -> Result<String, String> {
+    crate::commands::telemetry::record_feature("install_skill");
+    run_skill_install(&skill_name)
+}
+
+/// Install several skills one after another (see `SKILL_INSTALL`), with `task-progress` events
+/// per skill
+#[command]
+#[specta::specta]
+pub async fn install_skills(skill_names: Vec<String>) -> Result<Vec<BulkItemResult>, String> {
+    crate::commands::telemetry::record_feature("install_skills");
+    let mut seen = std::collections::HashSet::new();
+    let names: Vec<String> = skill_names
+        .iter()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty() && seen.insert(n.clone()))
+        .collect();
+    if names.is_empty() {
+        return Err("No skills to install".to_string());
+    }
+    info!("Installing {} skill(s)", names.len());
+
+    let items: Vec<(String, String)> = names.iter().map(|n| (n.clone(), n.clone())).collect();
+    let results = task_pool::run(
+        items,
+        1,
+        |name| run_skill_install(&name),
+        |progress| events::emit(ManagerEvent::item_progress("install-skills", &progress)),
+    );
+    Ok(names.iter().zip(results).map(|(name, result)| BulkItemResult::new(name, result)).collect())
+}
+
+/// clawhub installs share the config dir (skills dir, lock file) and the npx cache, so only one
+/// runs at a time
+static SKILL_INSTALL: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Run `clawhub install <skill_name>` in the OpenClaw config dir
+fn run_skill_install(skill_name: &str) -> Result<String, String> {
+    let _install = SKILL_INSTALL.lock().unwrap_or_else(|e| e.into_inner());
     info!("Installing skill: {}", skill_name);
+
+    let openclaw_dir = std::path::PathBuf::from(platform::get_config_dir());
     
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let openclaw_dir = home_dir.join(".openclaw");
-    
-    // Ensure .openclaw directory exists
+    // Ensure the config directory exists
     if !openclaw_dir.exists() {
         fs::create_dir_all(&openclaw_dir)
-            .map_err(|e| format!("Failed to create .openclaw directory: {}", e))?;
+            .map_err(|e| format!("Failed to create {}: {}", openclaw_dir.display(), e))?;
     }
 
-    // Run 'npx clawhub install <skill_name>' in ~/.openclaw
-    #[cfg(target_os = "windows")]
-    let program = "cmd";
-    #[cfg(target_os = "windows")]
-    let args = ["/C", "npx", "clawhub", "install", &skill_name];
-
-    #[cfg(not(target_os = "windows"))]
-    let program = "npx";
-    #[cfg(not(target_os = "windows"))]
-    let args = ["clawhub", "install", &skill_name];
+    // Run 'npx clawhub install <skill_name>' (or 'pnpm dlx ...') in the config dir
+    let mut cmd = node_pm::command(node_pm::active().exec_prefix());
+    cmd.args(["clawhub", "install", skill_name]);
 
-    let output = create_command(program)
-        .args(args)
-        .current_dir(&openclaw_dir)
-        .output()
+    let output = shell::output(cmd.current_dir(&openclaw_dir))
         .map_err(|e| format!("Failed to execute clawhub install: {}", e))?;
 
     if output.status.success() {
@@ -203,11 +217,12 @@ pub async fn install_skill(skill_name: String) -> Result<String, String> {
 }
 
 #[command]
+
+#[specta::specta]
 pub async fn uninstall_skill(skill_id: String) -> Result<String, String> {
     info!("Uninstalling skill: {}", skill_id);
     
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let skill_path = home_dir.join(".openclaw").join("skills").join(&skill_id);
+    let skill_path = skills_dir()?.join(&skill_id);
     
     if !skill_path.exists() {
         return Err(format!("Skill directory not found: {:?}", skill_path));
@@ -222,23 +237,14 @@ pub async fn uninstall_skill(skill_id: String) -> Result<String, String> {
 }
 
 #[command]
-pub async fn uninstall_clawhub() -> Result<String, String> {
-    info!("Uninstalling clawhub globally via npm");
-
-    #[cfg(target_os = "windows")]
-    let program = "cmd";
-    #[cfg(target_os = "windows")]
-    let args = ["/C", "npm uninstall -g clawhub"];
 
-    #[cfg(not(target_os = "windows"))]
-    let program = "npm";
-    #[cfg(not(target_os = "windows"))]
-    let args = ["uninstall", "-g", "clawhub"];
+#[specta::specta]
+pub async fn uninstall_clawhub() -> Result<String, String> {
+    let pm = node_pm::active();
+    info!("Uninstalling clawhub globally via {}", pm.name());
 
-    let output = create_command(program)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to execute npm uninstall: {}", e))?;
+    let output = shell::output(&mut node_pm::command(&pm.global_remove("clawhub")))
+        .map_err(|e| format!("Failed to execute {} uninstall: {}", pm.name(), e))?;
 
     if output.status.success() {
         info!("clawhub uninstalled successfully");
@@ -249,3 +255,400 @@ pub async fn uninstall_clawhub() -> Result<String, String> {
         Err(format!("Failed to uninstall clawhub: {}", error_msg))
     }
 }
+
+// ============ Skill Dry Run ============
+
+/// Tools that reach the network directly; denied during a dry run
+const DRY_RUN_NETWORK_TOOLS: &[&str] = &["web_fetch", "web_search", "browser"];
+
+/// A tool call the agent attempted while following the skill
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AttemptedToolCall {
+    pub tool: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Result of running a skill in a throwaway workspace
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SkillDryRunResult {
+    pub skill: String,
+    pub tool_calls: Vec<AttemptedToolCall>,
+    pub reply: Option<String>,
+    /// Whether command execution ran without network access (Docker sandbox)
+    pub network_isolated: bool,
+    /// Notes about the isolation that could be applied on this machine
+    pub notes: Vec<String>,
+    pub success: bool,
+    pub output: String,
+}
+
+/// Locate an installed skill directory by directory name or frontmatter name
+fn find_skill_dir(name: &str) -> Result<std::path::PathBuf, String> {
+    let skills_dir = skills_dir()?;
+    let by_id = skills_dir.join(name);
+    if by_id.join("SKILL.md").exists() {
+        return Ok(by_id);
+    }
+    let entries = fs::read_dir(&skills_dir).map_err(|e| format!("Failed to read skills directory: {}", e))?;
+    for entry in entries.flatten() {
+        let content = fs::read_to_string(entry.path().join("SKILL.md")).unwrap_or_default();
+        let declared = content
+            .lines()
+            .find_map(|l| l.strip_prefix("name:"))
+            .map(|n| n.trim().trim_matches('"').to_string());
+        if declared.as_deref() == Some(name) {
+            return Ok(entry.path());
+        }
+    }
+    Err(format!("Skill not found: {}", name))
+}
+
+fn copy_dir(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != "node_modules" && entry.file_name() != ".git" {
+                copy_dir(&entry.path(), &target)?;
+            }
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Collect tool calls from session transcripts (content items of type toolCall / tool_use)
+fn collect_tool_calls(value: &serde_json::Value, calls: &mut Vec<AttemptedToolCall>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let kind = map.get("type").and_then(|v| v.as_str()).unwrap_or("");
+            if matches!(kind, "toolCall" | "tool_use" | "tool_call") {
+                if let Some(tool) = map.get("name").and_then(|v| v.as_str()) {
+                    calls.push(AttemptedToolCall {
+                        tool: tool.to_string(),
+                        arguments: map
+                            .get("arguments")
+                            .or_else(|| map.get("input"))
+                            .cloned()
+                            .unwrap_or(serde_json::Value::Null),
+                    });
+                    return;
+                }
+            }
+            for v in map.values() {
+                collect_tool_calls(v, calls);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_tool_calls(v, calls)),
+        _ => {}
+    }
+}
+
+fn read_session_tool_calls(state_dir: &std::path::Path) -> Vec<AttemptedToolCall> {
+    let mut calls = Vec::new();
+    let sessions_dir = state_dir.join("agents").join("main").join("sessions");
+    for entry in fs::read_dir(&sessions_dir).into_iter().flatten().flatten() {
+        if entry.path().extension().map(|e| e == "jsonl").unwrap_or(false) {
+            for line in fs::read_to_string(entry.path()).unwrap_or_default().lines() {
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(line) {
+                    collect_tool_calls(&value, &mut calls);
+                }
+            }
+        }
+    }
+    calls
+}
+
+/// Build the throwaway config: models/auth from the live config, workspace in the temp dir,
+/// network tools denied, no channels, and the Docker sandbox (network "none") when available
+fn build_dry_run_config(workspace: &std::path::Path, use_docker: bool) -> serde_json::Value {
+    let live = crate::commands::config::load_live_openclaw_config().unwrap_or_else(|_| serde_json::json!({}));
+
+    let mut config = serde_json::json!({
+        "agents": {
+            "defaults": {
+                "workspace": workspace.to_string_lossy(),
+            }
+        },
+        "tools": { "deny": DRY_RUN_NETWORK_TOOLS },
+    });
+    for key in ["models", "auth", "env"] {
+        if let Some(v) = live.get(key) {
+            config[key] = v.clone();
+        }
+    }
+    if let Some(model) = live.pointer("/agents/defaults/model") {
+        config["agents"]["defaults"]["model"] = model.clone();
+    }
+    if use_docker {
+        config["agents"]["defaults"]["sandbox"] = serde_json::json!({
+            "mode": "all",
+            "workspaceAccess": "rw",
+            "docker": { "network": "none" }
+        });
+    }
+    // OpenClaw only substitutes the native `${VAR}` form of placeholders
+    crate::utils::placeholders::to_native(&config).unwrap_or(config)
+}
+
+/// Run a skill once against `sample_input` in an isolated temp workspace and report the tool
+/// calls the agent attempted. Channels are not configured, network tools are denied, and
+/// commands run in a Docker sandbox without network when Docker is available.
+#[command]
+#[specta::specta]
+pub async fn dry_run_skill(name: String, sample_input: String) -> Result<SkillDryRunResult, String> {
+    info!("[Skill Dry Run] Dry-running skill: {}", name);
+    let skill_dir = find_skill_dir(&name)?;
+    let openclaw_path = crate::utils::shell::get_openclaw_path().ok_or("Cannot find openclaw command")?;
+
+    // The config copy holds API keys: owner-only, in the Manager's own directory, removed on drop
+    let data_dir = crate::utils::platform::get_manager_data_dir();
+    fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create the Manager data directory: {}", e))?;
+    let temp_root = tempfile::Builder::new()
+        .prefix("skill-dry-run-")
+        .tempdir_in(&data_dir)
+        .map_err(|e| format!("Failed to create dry-run directory: {}", e))?;
+    let state_dir = temp_root.path().join("state");
+    let workspace = temp_root.path().join("workspace");
+    let skill_name = skill_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+    copy_dir(&skill_dir, &workspace.join("skills").join(&skill_name))
+        .map_err(|e| format!("Failed to prepare dry-run workspace: {}", e))?;
+
+    // The sandbox needs a running daemon, not just the CLI
+    let docker = crate::commands::config::check_docker_available();
+    let use_docker = docker.is_ok();
+    let mut notes = vec![format!("Network tools denied: {}", DRY_RUN_NETWORK_TOOLS.join(", "))];
+    match docker {
+        Ok(()) => notes.push("Commands run in a Docker sandbox with network disabled".to_string()),
+        Err(e) => notes.push(format!("{}. Shell commands run on the host and may reach the network", e.split(". ").next().unwrap_or_default())),
+    }
+
+    let config_path = state_dir.join("openclaw.json");
+    let config = build_dry_run_config(&workspace, use_docker);
+    crate::utils::file::write_file(
+        &config_path.to_string_lossy(),
+        &serde_json::to_string_pretty(&config).unwrap_or_default(),
+    )
+    .map_err(|e| format!("Failed to write dry-run config: {}", e))?;
+
+    let message = format!("Use the {} skill for this request:\n\n{}", skill_name, sample_input);
+    let mut cmd = shell::openclaw_command(&openclaw_path);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    cmd.args(["agent", "--local", "--agent", "main", "--message", &message, "--json", "--timeout", "120"])
+        .current_dir(&workspace)
+        .env("OPENCLAW_STATE_DIR", &state_dir)
+        .env("OPENCLAW_CONFIG_PATH", &config_path)
+        .env("PATH", crate::utils::shell::get_extended_path())
+        // Values behind the copied keys' placeholders, which a command built here doesn't inherit
+        .envs(crate::utils::keychain::gateway_env())
+        .envs(crate::commands::placeholders::config_env(&config));
+    let output = shell::output(&mut cmd).map_err(|e| format!("Failed to run openclaw agent: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let reply = serde_json::from_str::<serde_json::Value>(stdout.trim())
+        .ok()
+        .and_then(|v| {
+            v.pointer("/result/payloads/0/text")
+                .or_else(|| v.pointer("/payloads/0/text"))
+                .or_else(|| v.get("text"))
+                .and_then(|t| t.as_str())
+                .map(|s| s.to_string())
+        });
+    let tool_calls = read_session_tool_calls(&state_dir);
+    drop(temp_root);
+
+    info!("[Skill Dry Run] {} attempted {} tool calls", skill_name, tool_calls.len());
+    Ok(SkillDryRunResult {
+        skill: skill_name,
+        tool_calls,
+        reply,
+        network_isolated: use_docker,
+        notes,
+        success: output.status.success(),
+        output: crate::utils::log_sanitizer::sanitize(if stderr.trim().is_empty() { &stdout } else { &stderr }),
+    })
+}
+
+// ============ Local Skill Development ============
+
+/// Poll interval for watched local skills
+const SKILL_WATCH_INTERVAL_SECS: u64 = 2;
+
+/// Stop flag of a running watcher
+type StopFlag = std::sync::Arc<std::sync::atomic::AtomicBool>;
+
+/// Stop flags of running local skill watchers, by skill name
+static SKILL_WATCHERS: std::sync::Mutex<Option<std::collections::HashMap<String, StopFlag>>> = std::sync::Mutex::new(None);
+
+/// Event payload emitted when a watched skill changes
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct LocalSkillChange {
+    pub name: String,
+    pub path: String,
+    /// SKILL.md still parses after the change
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// `skills` in the OpenClaw config dir (follows a relocated or WSL config)
+fn skills_dir() -> Result<std::path::PathBuf, String> {
+    Ok(std::path::PathBuf::from(platform::get_config_dir()).join("skills"))
+}
+
+/// Parse SKILL.md frontmatter, returning the declared skill name
+fn validate_skill_md(dir: &std::path::Path) -> Result<String, String> {
+    let content = fs::read_to_string(dir.join("SKILL.md")).map_err(|_| "SKILL.md not found".to_string())?;
+    let rest = content.strip_prefix("---").ok_or("SKILL.md has no frontmatter")?;
+    let end = rest.find("---").ok_or("SKILL.md frontmatter is not closed")?;
+    serde_yaml::from_str::<SkillFrontmatter>(&rest[..end])
+        .map(|f| f.name)
+        .map_err(|e| format!("Invalid SKILL.md frontmatter: {}", e))
+}
+
+fn create_dir_link(target: &std::path::Path, link: &std::path::Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link).map_err(|e| format!("Failed to create symlink: {}", e))
+    }
+    #[cfg(windows)]
+    {
+        // Junctions need no admin rights or developer mode, unlike directory symlinks
+        let output = shell::output(create_command("cmd").args(["/C", "mklink", "/J"]).arg(link).arg(target))
+            .map_err(|e| format!("Failed to run mklink: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to create junction: {}", String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+}
+
+/// Latest modification time of any file below `dir` (ignores node_modules/.git)
+fn latest_mtime(dir: &std::path::Path) -> Option<std::time::SystemTime> {
+    let mut latest = fs::metadata(dir).and_then(|m| m.modified()).ok();
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let name = entry.file_name();
+        if name == "node_modules" || name == ".git" {
+            continue;
+        }
+        let modified = if entry.path().is_dir() {
+            latest_mtime(&entry.path())
+        } else {
+            entry.metadata().and_then(|m| m.modified()).ok()
+        };
+        latest = latest.max(modified);
+    }
+    latest
+}
+
+/// Register a skill from a local development directory (like `npm link`):
+/// skills/<name> in the config dir becomes a link to `path`, so edits are live without publishing.
+#[command]
+#[specta::specta]
+pub async fn link_local_skill(path: String) -> Result<Skill, String> {
+    info!("[Local Skill] Linking local skill from {}", path);
+    let source = std::path::PathBuf::from(path.trim());
+    let source = source.canonicalize().map_err(|e| format!("Invalid skill directory: {}", e))?;
+    let declared_name = validate_skill_md(&source)?;
+
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid skill directory")?;
+    let skills_dir = skills_dir()?;
+    fs::create_dir_all(&skills_dir).map_err(|e| format!("Failed to create skills directory: {}", e))?;
+
+    let link = skills_dir.join(&name);
+    if let Ok(meta) = fs::symlink_metadata(&link) {
+        let existing_target = fs::read_link(&link).ok();
+        if meta.file_type().is_symlink() || existing_target.is_some() {
+            // Re-linking replaces an old link, never a real installed skill
+            fs::remove_file(&link)
+                .or_else(|_| fs::remove_dir(&link))
+                .map_err(|e| format!("Failed to replace existing link: {}", e))?;
+        } else {
+            return Err(format!("A skill named '{}' is already installed (not a link)", name));
+        }
+    }
+    create_dir_link(&source, &link)?;
+
+    info!("[Local Skill] Linked {} -> {}", link.display(), source.display());
+    Ok(Skill {
+        id: name,
+        name: declared_name,
+        description: None,
+        path: link.to_string_lossy().to_string(),
+    })
+}
+
+/// Watch a linked local skill and emit `skill://changed` whenever its files change,
+/// with the SKILL.md validation result so authors see mistakes immediately
+#[command]
+#[specta::specta]
+pub async fn watch_local_skill(name: String) -> Result<String, String> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let link = skills_dir()?.join(&name);
+    if !link.join("SKILL.md").exists() {
+        return Err(format!("Skill not found: {}", name));
+    }
+    let source = fs::canonicalize(&link).unwrap_or_else(|_| link.clone());
+
+    let stop = std::sync::Arc::new(AtomicBool::new(false));
+    {
+        let mut watchers = SKILL_WATCHERS.lock().map_err(|_| "Skill watcher state poisoned")?;
+        let watchers = watchers.get_or_insert_with(Default::default);
+        if let Some(previous) = watchers.insert(name.clone(), stop.clone()) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    info!("[Local Skill] Watching {} ({})", name, source.display());
+    let message = format!("Watching {} for changes", source.display());
+    std::thread::spawn(move || {
+        let mut last = latest_mtime(&source);
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(std::time::Duration::from_secs(SKILL_WATCH_INTERVAL_SECS));
+            let current = latest_mtime(&source);
+            if current == last {
+                continue;
+            }
+            last = current;
+
+            let validation = validate_skill_md(&source);
+            info!("[Local Skill] {} changed (valid={})", name, validation.is_ok());
+            let change = LocalSkillChange {
+                name: name.clone(),
+                path: source.to_string_lossy().to_string(),
+                valid: validation.is_ok(),
+                error: validation.err(),
+            };
+            events::emit(ManagerEvent::ConfigChanged {
+                scope: "skill".to_string(),
+                path: change.path.clone(),
+                detail: serde_json::to_value(&change).ok(),
+            });
+        }
+        debug!("[Local Skill] Stopped watching {}", name);
+    });
+
+    Ok(message)
+}
+
+/// Stop watching a local skill
+#[command]
+#[specta::specta]
+pub async fn unwatch_local_skill(name: String) -> Result<String, String> {
+    let mut watchers = SKILL_WATCHERS.lock().map_err(|_| "Skill watcher state poisoned")?;
+    match watchers.as_mut().and_then(|w| w.remove(&name)) {
+        Some(stop) => {
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            Ok(format!("Stopped watching {}", name))
+        }
+        None => Err(format!("{} is not being watched", name)),
+    }
+}
@@ -0,0 +1,202 @@
+use crate::commands::config::{
+    load_live_openclaw_config, load_manager_config, load_mcp_config_file,
+    save_live_openclaw_config, save_manager_config, save_mcp_config_file,
+};
+use crate::commands::locks::{self, ConfigResource};
+use crate::commands::telemetry;
+use crate::models::MCPConfig;
+use crate::utils::snapshots::{self, ConfigSnapshot, SnapshotDiff, SnapshotInfo};
+use crate::utils::{file, platform};
+use log::{info, warn};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tauri::command;
+
+fn get_snapshots_dir() -> PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["snapshots"])
+}
+
+fn snapshot_path(id: &str) -> PathBuf {
+    get_snapshots_dir().join(format!("{}.json", id))
+}
+
+/// Current content of the snapshotted files; missing files are left out
+fn current_files() -> Result<BTreeMap<String, Value>, String> {
+    let mut files = BTreeMap::new();
+    if file::file_exists(&platform::get_config_file_path()) {
+        files.insert("openclaw.json".to_string(), load_live_openclaw_config()?);
+    }
+    if file::file_exists(&platform::get_mcp_config_file_path()) {
+        let mcps = serde_json::to_value(load_mcp_config_file()?)
+            .map_err(|e| format!("Failed to serialize MCP config: {}", e))?;
+        files.insert("mcps.json".to_string(), mcps);
+    }
+    if file::file_exists(&platform::get_manager_config_file_path()) {
+        files.insert("manager.json".to_string(), load_manager_config()?);
+    }
+    Ok(files)
+}
+
+fn read_snapshot(id: &str) -> Result<ConfigSnapshot, String> {
+    if !snapshots::is_valid_id(id) {
+        return Err(format!("Invalid snapshot id: {}", id));
+    }
+    let content = file::read_file(&platform::path_string(&snapshot_path(id)))
+        .map_err(|_| format!("Snapshot {} not found", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Snapshot {} is not valid: {}", id, e))
+}
+
+/// Every readable snapshot, newest first
+fn read_snapshots() -> Vec<ConfigSnapshot> {
+    let mut list: Vec<ConfigSnapshot> = std::fs::read_dir(get_snapshots_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+        .filter_map(|e| {
+            let content = std::fs::read_to_string(e.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    list
+}
+
+/// Write a snapshot of the current files and prune old automatic ones. An automatic snapshot
+/// is skipped when nothing changed since the latest snapshot.
+pub(crate) fn take_snapshot(reason: &str, automatic: bool) -> Result<SnapshotInfo, String> {
+    let files = current_files()?;
+    let existing = read_snapshots();
+    if automatic && snapshots::unchanged(existing.first(), &files) {
+        return Ok(SnapshotInfo::from(&existing[0]));
+    }
+
+    let snapshot = ConfigSnapshot {
+        id: snapshots::snapshot_id(chrono::Local::now()),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        reason: reason.to_string(),
+        automatic,
+        files,
+    };
+    let content = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    file::write_file(
+        &platform::path_string(&snapshot_path(&snapshot.id)),
+        &content,
+    )
+    .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    info!("[Snapshots] {} ({})", snapshot.id, snapshot.reason);
+
+    let mut infos: Vec<SnapshotInfo> = existing.iter().map(SnapshotInfo::from).collect();
+    infos.push(SnapshotInfo::from(&snapshot));
+    for id in snapshots::to_prune(&infos, snapshots::MAX_AUTO_SNAPSHOTS) {
+        if let Err(e) = std::fs::remove_file(snapshot_path(&id)) {
+            warn!("[Snapshots] Failed to prune {}: {}", id, e);
+        }
+    }
+    Ok(SnapshotInfo::from(&snapshot))
+}
+
+/// Snapshot taken by a destructive command before it writes; the command is not run when
+/// the snapshot fails, so it can always be undone
+pub(crate) fn snapshot_before(command: &str) -> Result<(), String> {
+    take_snapshot(&format!("Before {}", command), true)
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "Could not snapshot the configuration before {}: {}",
+                command, e
+            )
+        })
+}
+
+/// Snapshot openclaw.json, mcps.json and manager.json now
+#[command]
+#[specta::specta]
+pub async fn create_config_snapshot(label: Option<String>) -> Result<SnapshotInfo, String> {
+    telemetry::record_feature("create_config_snapshot");
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| "Manual snapshot".to_string());
+    take_snapshot(&label, false)
+}
+
+/// List snapshots, newest first
+#[command]
+#[specta::specta]
+pub async fn list_config_snapshots() -> Result<Vec<SnapshotInfo>, String> {
+    Ok(read_snapshots().iter().map(SnapshotInfo::from).collect())
+}
+
+/// What changed in openclaw.json since a snapshot (shown before rolling back to it)
+#[command]
+#[specta::specta]
+pub async fn diff_config(snapshot_id: String) -> Result<SnapshotDiff, String> {
+    let snapshot = read_snapshot(&snapshot_id)?;
+    let live = if file::file_exists(&platform::get_config_file_path()) {
+        load_live_openclaw_config()?
+    } else {
+        serde_json::json!({})
+    };
+    snapshots::diff_with_live(&snapshot, &live)
+}
+
+/// Roll the config files back to a snapshot, all of them or only `files` (e.g. ["openclaw.json"]).
+/// The current state is snapshotted first, so a restore can be undone too.
+#[command]
+#[specta::specta]
+pub async fn restore_config_snapshot(
+    id: String,
+    files: Option<Vec<String>>,
+) -> Result<String, String> {
+    let _lock = locks::lock(
+        &[
+            ConfigResource::OpenClaw,
+            ConfigResource::Manager,
+            ConfigResource::Mcps,
+        ],
+        "restore_config_snapshot",
+    )
+    .await;
+    let snapshot = read_snapshot(&id)?;
+    let selected: Vec<(&String, &Value)> = snapshot
+        .files
+        .iter()
+        .filter(|(name, _)| files.as_ref().map(|f| f.contains(name)).unwrap_or(true))
+        .collect();
+    if selected.is_empty() {
+        return Err(format!("Snapshot {} holds none of the requested files", id));
+    }
+    // Parse everything before writing anything
+    let mcps: Option<HashMap<String, MCPConfig>> = match snapshot.files.get("mcps.json") {
+        Some(value) if selected.iter().any(|(name, _)| *name == "mcps.json") => Some(
+            serde_json::from_value(value.clone())
+                .map_err(|e| format!("mcps.json in the snapshot is not valid: {}", e))?,
+        ),
+        _ => None,
+    };
+
+    snapshot_before(&format!("restoring {}", id))?;
+    info!("[Snapshots] Restoring {} ({} file(s))", id, selected.len());
+    for (name, value) in &selected {
+        match name.as_str() {
+            "openclaw.json" => save_live_openclaw_config(value, "restore_config_snapshot")?,
+            "manager.json" => save_manager_config(value)?,
+            "mcps.json" => {
+                if let Some(mcps) = &mcps {
+                    save_mcp_config_file(mcps)?;
+                }
+            }
+            other => warn!("[Snapshots] Skipping unknown file {}", other),
+        }
+    }
+
+    let names: Vec<&str> = selected.iter().map(|(name, _)| name.as_str()).collect();
+    let mut message = format!("Restored {} from {}", names.join(", "), id);
+    if names.contains(&"manager.json") {
+        message.push_str(". Restart the Manager to apply all restored Manager settings");
+    }
+    Ok(message)
+}
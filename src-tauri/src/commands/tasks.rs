@@ -0,0 +1,426 @@
+use crate::commands::installer::{self, InstallResult};
+use crate::commands::process;
+use crate::events::{self, AlertLevel, ManagerEvent};
+use crate::utils::process_tree::ProcessInfo;
+use crate::utils::{file, platform, shell};
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Last line a logged bash script writes, followed by its exit code
+pub const EXIT_MARKER: &str = "[openclaw-manager] exit";
+
+/// Finished tasks kept in tasks.json
+const KEEP_FINISHED: usize = 20;
+
+/// Lines of output shown for an interrupted task
+const LOG_TAIL_LINES: usize = 20;
+
+/// Serializes read-modify-write cycles on tasks.json (written from blocking install code)
+static TASKS_LOCK: Mutex<()> = Mutex::new(());
+
+/// A long operation (install, update) as persisted in manager/tasks.json
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRecord {
+    pub id: String,
+    /// install-nodejs, install-openclaw, update-openclaw or install-git
+    pub kind: String,
+    pub step: String,
+    /// Child process running the current step
+    pub pid: Option<u32>,
+    /// Name the OS reported for `pid` when it started; a live process under another name
+    /// (after a reboot or PID reuse) is not the task's
+    #[serde(default)]
+    pub process_name: Option<String>,
+    /// Output of the task's child processes
+    pub log_file: String,
+    /// Manager process that started the task
+    pub manager_pid: u32,
+    pub started_at: String,
+    pub updated_at: String,
+    /// running, succeeded, failed or interrupted (the Manager exited while it was running)
+    pub status: String,
+    pub message: Option<String>,
+}
+
+/// A task the Manager was closed during, with what is known about how it ended
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct InterruptedTask {
+    pub task: TaskRecord,
+    /// The child process is still alive (it outlived the Manager)
+    pub process_running: bool,
+    /// succeeded, failed or unknown
+    pub outcome: String,
+    pub log_tail: Vec<String>,
+}
+
+fn get_tasks_file_path() -> String {
+    if platform::is_windows() {
+        format!("{}\\tasks.json", platform::get_manager_data_dir())
+    } else {
+        format!("{}/tasks.json", platform::get_manager_data_dir())
+    }
+}
+
+fn get_task_log_path(id: &str) -> String {
+    platform::path_string(&platform::join_path(
+        platform::get_manager_data_dir(),
+        &["tasks", &format!("{}.log", id)],
+    ))
+}
+
+fn load_tasks() -> Vec<TaskRecord> {
+    file::read_file(&get_tasks_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_tasks(tasks: &[TaskRecord]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(tasks)
+        .map_err(|e| format!("Failed to serialize tasks: {}", e))?;
+    file::write_file(&get_tasks_file_path(), &content)
+        .map_err(|e| format!("Failed to write tasks: {}", e))
+}
+
+/// Load, change and save tasks.json
+fn update_tasks<T>(f: impl FnOnce(&mut Vec<TaskRecord>) -> T) -> T {
+    let _guard = TASKS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut tasks = load_tasks();
+    let result = f(&mut tasks);
+    if let Err(e) = save_tasks(&tasks) {
+        warn!("[Tasks] {}", e);
+    }
+    result
+}
+
+/// Drop the oldest finished tasks (and their logs) beyond KEEP_FINISHED
+pub(crate) fn prune_finished(tasks: &mut Vec<TaskRecord>) -> Vec<TaskRecord> {
+    let finished = tasks
+        .iter()
+        .filter(|t| t.status == "succeeded" || t.status == "failed")
+        .count();
+    let mut excess = finished.saturating_sub(KEEP_FINISHED);
+    let mut removed = Vec::new();
+    tasks.retain(|t| {
+        if excess > 0 && (t.status == "succeeded" || t.status == "failed") {
+            excess -= 1;
+            removed.push(t.clone());
+            return false;
+        }
+        true
+    });
+    removed
+}
+
+/// Exit code recorded by a logged bash script, if it got to the end
+pub(crate) fn exit_code_from_log(log: &str) -> Option<i32> {
+    log.lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix(EXIT_MARKER))
+        .and_then(|code| code.trim().parse().ok())
+}
+
+/// The task's child process, if it is still running. A PID alone is not enough: it only counts
+/// while the process has the name recorded at spawn, and never without one.
+pub(crate) fn task_process<'a>(
+    task: &TaskRecord,
+    processes: &'a [ProcessInfo],
+) -> Option<&'a ProcessInfo> {
+    let pid = task.pid?;
+    let name = task.process_name.as_deref()?;
+    processes.iter().find(|p| p.pid == pid && p.name == name)
+}
+
+/// Mark tasks still running under another (exited) Manager process as interrupted
+pub(crate) fn mark_interrupted(tasks: &mut [TaskRecord], manager_pid: u32) -> usize {
+    let mut count = 0;
+    for task in tasks
+        .iter_mut()
+        .filter(|t| t.status == "running" && t.manager_pid != manager_pid)
+    {
+        task.status = "interrupted".to_string();
+        count += 1;
+    }
+    count
+}
+
+/// Script that records its exit code in the log when it ends (see exit_code_from_log)
+pub(crate) fn with_exit_marker(script: &str) -> String {
+    format!("trap 'echo \"{} $?\"' EXIT\n{}", EXIT_MARKER, script)
+}
+
+/// Handle to the persisted record of a running long operation
+pub(crate) struct Task {
+    id: String,
+    log_file: String,
+}
+
+impl Task {
+    /// Record the start of a task of `kind`
+    pub(crate) fn begin(kind: &str) -> Task {
+        let now = Utc::now().to_rfc3339();
+        let id = format!("{}-{}", kind, Utc::now().format("%Y%m%d%H%M%S%3f"));
+        let log_file = get_task_log_path(&id);
+        let record = TaskRecord {
+            id: id.clone(),
+            kind: kind.to_string(),
+            step: "starting".to_string(),
+            pid: None,
+            process_name: None,
+            log_file: log_file.clone(),
+            manager_pid: std::process::id(),
+            started_at: now.clone(),
+            updated_at: now,
+            status: "running".to_string(),
+            message: None,
+        };
+        let removed = update_tasks(|tasks| {
+            tasks.push(record);
+            prune_finished(tasks)
+        });
+        for task in removed {
+            let _ = std::fs::remove_file(&task.log_file);
+        }
+        Task { id, log_file }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TaskRecord)) {
+        update_tasks(|tasks| {
+            if let Some(task) = tasks.iter_mut().find(|t| t.id == self.id) {
+                f(task);
+                task.updated_at = Utc::now().to_rfc3339();
+            }
+        })
+    }
+
+    /// Record the step the task is at
+    pub(crate) fn step(&self, step: &str) {
+        self.append_log(&format!("== {}", step));
+        self.update(|task| task.step = step.to_string());
+    }
+
+    fn append_log(&self, line: &str) {
+        use std::io::Write;
+        if let Some(parent) = std::path::Path::new(&self.log_file).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(mut log) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)
+        {
+            let _ = writeln!(log, "{}", line);
+        }
+    }
+
+    /// Run a prepared command with its output going to the task log, recording its PID while
+    /// it runs. Returns the output like `shell::run_*_output`.
+    pub(crate) fn run(&self, cmd: &mut Command) -> Result<String, String> {
+        if shell::is_mock() {
+            let output = shell::output(cmd).map_err(|e| e.to_string())?;
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            self.append_log(&stdout);
+            return if output.status.success() {
+                Ok(stdout)
+            } else {
+                Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+            };
+        }
+
+        self.append_log("");
+        let start = std::fs::metadata(&self.log_file)
+            .map(|m| m.len())
+            .unwrap_or(0) as usize;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)
+            .map_err(|e| format!("Failed to open task log: {}", e))?;
+        let stderr = log
+            .try_clone()
+            .map_err(|e| format!("Failed to open task log: {}", e))?;
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(log))
+            .stderr(Stdio::from(stderr))
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let pid = child.id();
+        let process_name = process::list_processes()
+            .ok()
+            .and_then(|list| list.into_iter().find(|p| p.pid == pid))
+            .map(|p| p.name);
+        self.update(|task| {
+            task.pid = Some(pid);
+            task.process_name = process_name;
+        });
+        let status = child.wait().map_err(|e| e.to_string());
+        self.update(|task| {
+            task.pid = None;
+            task.process_name = None;
+        });
+
+        let content = file::read_file(&self.log_file).unwrap_or_default();
+        let output: String = content
+            .get(start..)
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.starts_with(EXIT_MARKER))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        match status? {
+            s if s.success() => Ok(output),
+            s if output.is_empty() => Err(format!("Command failed with exit code: {:?}", s.code())),
+            _ => Err(output),
+        }
+    }
+
+    /// Record how the task ended
+    pub(crate) fn finish(&self, result: &Result<InstallResult, String>) {
+        let (status, message) = match result {
+            Ok(r) if r.success => ("succeeded", r.message.clone()),
+            Ok(r) => (
+                "failed",
+                r.error.clone().unwrap_or_else(|| r.message.clone()),
+            ),
+            Err(e) => ("failed", e.clone()),
+        };
+        self.append_log(&format!("== {}: {}", status, message));
+        self.update(|task| {
+            task.status = status.to_string();
+            task.message = Some(message);
+            task.pid = None;
+            task.process_name = None;
+        });
+    }
+}
+
+/// Flag tasks left running by a previous Manager process. Called once at startup.
+pub fn detect_interrupted_tasks() {
+    let count = update_tasks(|tasks| mark_interrupted(tasks, std::process::id()));
+    if count > 0 {
+        warn!(
+            "[Tasks] {} operation(s) were interrupted when the Manager last exited",
+            count
+        );
+        events::emit(ManagerEvent::alert(
+            "task-interrupted",
+            AlertLevel::Warning,
+            format!(
+                "{} operation(s) did not finish before the Manager was closed",
+                count
+            ),
+            &count,
+        ));
+    }
+}
+
+fn describe(task: TaskRecord, processes: &[ProcessInfo]) -> InterruptedTask {
+    let log = file::read_file(&task.log_file).unwrap_or_default();
+    let process_running = task_process(&task, processes).is_some();
+    let outcome = match exit_code_from_log(&log) {
+        _ if process_running => "unknown",
+        Some(0) => "succeeded",
+        Some(_) => "failed",
+        None => "unknown",
+    };
+    let lines: Vec<&str> = log.lines().filter(|line| !line.trim().is_empty()).collect();
+    let log_tail = lines[lines.len().saturating_sub(LOG_TAIL_LINES)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    InterruptedTask {
+        task,
+        process_running,
+        outcome: outcome.to_string(),
+        log_tail,
+    }
+}
+
+/// List operations the Manager was closed during, with their outcome and last output
+#[command]
+#[specta::specta]
+pub async fn list_interrupted_tasks() -> Result<Vec<InterruptedTask>, String> {
+    let interrupted: Vec<TaskRecord> = load_tasks()
+        .into_iter()
+        .filter(|t| t.status == "interrupted")
+        .collect();
+    if interrupted.is_empty() {
+        return Ok(Vec::new());
+    }
+    let processes = process::list_processes().unwrap_or_default();
+    Ok(interrupted
+        .into_iter()
+        .map(|task| describe(task, &processes))
+        .collect())
+}
+
+fn take_interrupted(id: &str) -> Result<TaskRecord, String> {
+    update_tasks(|tasks| {
+        let index = tasks
+            .iter()
+            .position(|t| t.id == id && t.status == "interrupted")
+            .ok_or_else(|| format!("Interrupted task {} not found", id))?;
+        Ok(tasks.remove(index))
+    })
+}
+
+/// Run an interrupted operation again from the start (installers skip what is already done)
+#[command]
+#[specta::specta]
+pub async fn resume_task(id: String) -> Result<InstallResult, String> {
+    let task = load_tasks()
+        .into_iter()
+        .find(|t| t.id == id && t.status == "interrupted")
+        .ok_or_else(|| format!("Interrupted task {} not found", id))?;
+    let processes = process::list_processes().unwrap_or_default();
+    if task_process(&task, &processes).is_some() {
+        return Err(
+            "The interrupted operation is still running; wait for it or stop it first".to_string(),
+        );
+    }
+    take_interrupted(&id)?;
+    let _ = std::fs::remove_file(&task.log_file);
+    info!("[Tasks] Resuming {} ({})", task.kind, task.id);
+    match task.kind.as_str() {
+        "install-nodejs" => installer::install_nodejs().await,
+        "install-openclaw" => installer::install_openclaw().await,
+        "update-openclaw" => installer::update_openclaw().await,
+        "install-git" => installer::install_git().await,
+        other => Err(format!("Unknown task kind '{}'", other)),
+    }
+}
+
+/// Forget an interrupted operation. A child process still running is refused unless `kill`
+/// is set, in which case it is stopped with its children.
+#[command]
+#[specta::specta]
+pub async fn dismiss_task(id: String, kill: bool) -> Result<String, String> {
+    let task = load_tasks()
+        .into_iter()
+        .find(|t| t.id == id && t.status == "interrupted")
+        .ok_or_else(|| format!("Interrupted task {} not found", id))?;
+    let processes = process::list_processes().unwrap_or_default();
+    if let Some(running) = task_process(&task, &processes) {
+        if !kill {
+            return Err(format!(
+                "Process {} of this operation is still running",
+                running.pid
+            ));
+        }
+        info!("[Tasks] Stopping process {} of {}", running.pid, task.id);
+        process::kill_process_tree(&processes, running.pid)?;
+    }
+    take_interrupted(&id)?;
+    let _ = std::fs::remove_file(&task.log_file);
+    info!("[Tasks] Dismissed {}", task.id);
+    Ok(format!("{} dismissed", task.kind))
+}
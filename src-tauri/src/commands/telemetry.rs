@@ -0,0 +1,273 @@
+use crate::commands::config::{load_manager_config, save_manager_config};
+use crate::commands::locks::{self, ConfigResource};
+use crate::utils::{file, platform, shell};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tauri::command;
+
+/// Environment variable used to point the Manager at a telemetry collector.
+/// Nothing is ever sent unless telemetry is enabled AND an endpoint is configured.
+const TELEMETRY_ENDPOINT_ENV: &str = "OPENCLAW_MANAGER_TELEMETRY_URL";
+
+/// Minimum interval between two automatic submissions (24h)
+const SUBMIT_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// Telemetry status for frontend (first-run prompt uses `decided`)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TelemetryStatus {
+    /// Whether the user opted in
+    pub enabled: bool,
+    /// Whether the user has answered the opt-in prompt at least once
+    pub decided: bool,
+    /// Whether a collector endpoint is configured
+    pub endpoint_configured: bool,
+    /// Last successful submission (RFC3339)
+    pub last_sent_at: Option<String>,
+}
+
+/// Locally accumulated, anonymous usage counters (~/.openclaw/manager/telemetry.json)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+struct TelemetryStats {
+    #[serde(default)]
+    period_start: Option<String>,
+    #[serde(default)]
+    features: HashMap<String, u64>,
+    #[serde(default)]
+    errors: HashMap<String, u64>,
+}
+
+fn get_stats_file_path() -> String {
+    if platform::is_windows() {
+        format!("{}\\telemetry.json", platform::get_manager_data_dir())
+    } else {
+        format!("{}/telemetry.json", platform::get_manager_data_dir())
+    }
+}
+
+fn load_stats() -> TelemetryStats {
+    file::read_file(&get_stats_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_stats(stats: &TelemetryStats) {
+    if let Ok(content) = serde_json::to_string_pretty(stats) {
+        if let Err(e) = file::write_file(&get_stats_file_path(), &content) {
+            debug!("[Telemetry] Failed to write stats file: {}", e);
+        }
+    }
+}
+
+fn telemetry_enabled() -> bool {
+    load_manager_config()
+        .ok()
+        .and_then(|c| c.pointer("/telemetry/enabled").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+fn get_endpoint() -> Option<String> {
+    if let Ok(url) = std::env::var(TELEMETRY_ENDPOINT_ENV) {
+        if !url.trim().is_empty() {
+            return Some(url.trim().to_string());
+        }
+    }
+    load_manager_config()
+        .ok()
+        .and_then(|c| c.pointer("/telemetry/endpoint").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+/// Generate an anonymous install ID (random, not derived from any machine property)
+fn generate_install_id() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // RandomState is seeded from the OS random source on creation
+    let mut id = String::new();
+    for _ in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u128);
+        id.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    id
+}
+
+/// Map a free-form error message to a coarse category (never sends the message itself)
+fn classify_error(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("permission") || lower.contains("access is denied") || lower.contains("eperm") || lower.contains("eacces") {
+        "permission"
+    } else if lower.contains("timeout") || lower.contains("timed out") {
+        "timeout"
+    } else if lower.contains("cannot find") || lower.contains("not found") || lower.contains("not installed") {
+        "not_found"
+    } else if lower.contains("parse") || lower.contains("invalid json") || lower.contains("serialize") {
+        "parse"
+    } else if lower.contains("network") || lower.contains("connect") || lower.contains("dns") || lower.contains("http") {
+        "network"
+    } else {
+        "other"
+    }
+}
+
+/// Record usage of a Manager feature (no-op unless the user opted in)
+pub fn record_feature(feature: &str) {
+    if !telemetry_enabled() {
+        return;
+    }
+    let mut stats = load_stats();
+    if stats.period_start.is_none() {
+        stats.period_start = Some(chrono::Utc::now().to_rfc3339());
+    }
+    *stats.features.entry(feature.to_string()).or_insert(0) += 1;
+    save_stats(&stats);
+}
+
+/// Record an error category for a feature (no-op unless the user opted in)
+/// Only `<feature>:<category>` is stored, the error text is discarded.
+pub fn record_error(feature: &str, message: &str) {
+    if !telemetry_enabled() {
+        return;
+    }
+    let mut stats = load_stats();
+    if stats.period_start.is_none() {
+        stats.period_start = Some(chrono::Utc::now().to_rfc3339());
+    }
+    let key = format!("{}:{}", feature, classify_error(message));
+    *stats.errors.entry(key).or_insert(0) += 1;
+    save_stats(&stats);
+}
+
+/// Build the exact payload that would be submitted
+fn build_payload(manager_config: &Value) -> Value {
+    let stats = load_stats();
+    json!({
+        "schema": 1,
+        "installId": manager_config.pointer("/telemetry/installId").and_then(|v| v.as_str()).unwrap_or(""),
+        "managerVersion": env!("CARGO_PKG_VERSION"),
+        "os": platform::get_os(),
+        "arch": platform::get_arch(),
+        "periodStart": stats.period_start,
+        "periodEnd": chrono::Utc::now().to_rfc3339(),
+        "features": stats.features,
+        "errors": stats.errors,
+    })
+}
+
+/// Get telemetry status
+#[command]
+#[specta::specta]
+pub async fn get_telemetry_status() -> Result<TelemetryStatus, String> {
+    info!("[Telemetry] Getting telemetry status...");
+    let manager_config = load_manager_config()?;
+
+    Ok(TelemetryStatus {
+        enabled: manager_config.pointer("/telemetry/enabled").and_then(|v| v.as_bool()).unwrap_or(false),
+        decided: manager_config.pointer("/telemetry/decidedAt").is_some(),
+        endpoint_configured: get_endpoint().is_some(),
+        last_sent_at: manager_config.pointer("/telemetry/lastSentAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+    })
+}
+
+/// Enable or disable anonymous usage statistics (explicit opt-in)
+#[command]
+#[specta::specta]
+pub async fn set_telemetry(enabled: bool) -> Result<String, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "set_telemetry").await;
+    info!("[Telemetry] Setting telemetry enabled={}", enabled);
+    let mut manager_config = load_manager_config()?;
+
+    if manager_config.get("telemetry").is_none() {
+        manager_config["telemetry"] = json!({});
+    }
+    manager_config["telemetry"]["enabled"] = json!(enabled);
+    manager_config["telemetry"]["decidedAt"] = json!(chrono::Utc::now().to_rfc3339());
+
+    if enabled {
+        if manager_config.pointer("/telemetry/installId").and_then(|v| v.as_str()).is_none() {
+            manager_config["telemetry"]["installId"] = json!(generate_install_id());
+        }
+    } else {
+        // Opting out forgets the install ID and any counters collected so far
+        if let Some(t) = manager_config.get_mut("telemetry").and_then(|v| v.as_object_mut()) {
+            t.remove("installId");
+            t.remove("lastSentAt");
+        }
+        let _ = std::fs::remove_file(get_stats_file_path());
+    }
+
+    save_manager_config(&manager_config)?;
+    Ok(if enabled {
+        "Anonymous usage statistics enabled".to_string()
+    } else {
+        "Anonymous usage statistics disabled".to_string()
+    })
+}
+
+/// Preview exactly what would be sent on the next submission
+#[command]
+#[specta::specta]
+pub async fn preview_telemetry() -> Result<Value, String> {
+    info!("[Telemetry] Building telemetry preview...");
+    let manager_config = load_manager_config()?;
+    Ok(build_payload(&manager_config))
+}
+
+/// Submit collected statistics to the configured endpoint and reset counters
+fn submit_telemetry() -> Result<bool, String> {
+    if !telemetry_enabled() {
+        return Ok(false);
+    }
+    let endpoint = match get_endpoint() {
+        Some(url) => url,
+        None => {
+            debug!("[Telemetry] No endpoint configured, keeping stats locally");
+            return Ok(false);
+        }
+    };
+
+    let payload = build_payload(&load_manager_config()?);
+    let body = serde_json::to_string(&payload)
+        .map_err(|e| format!("Failed to serialize telemetry payload: {}", e))?;
+
+    let status = shell::curl_post_json(&endpoint, &body, 10)?;
+    if !status.starts_with('2') {
+        return Err(format!("Telemetry endpoint returned HTTP {}", status));
+    }
+
+    // Runs on a background thread: re-read under the lock so concurrent saves are kept
+    let _lock = locks::lock_blocking(&[ConfigResource::Manager], "submit_telemetry");
+    let mut manager_config = load_manager_config()?;
+    manager_config["telemetry"]["lastSentAt"] = json!(chrono::Utc::now().to_rfc3339());
+    save_manager_config(&manager_config)?;
+    let _ = std::fs::remove_file(get_stats_file_path());
+    Ok(true)
+}
+
+/// Submit statistics in the background if enabled and the last submission is older than 24h
+pub fn submit_in_background() {
+    std::thread::spawn(|| {
+        if !telemetry_enabled() {
+            return;
+        }
+        let last_sent = load_manager_config()
+            .ok()
+            .and_then(|c| c.pointer("/telemetry/lastSentAt").and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok());
+        if let Some(last) = last_sent {
+            let elapsed = chrono::Utc::now().signed_duration_since(last).num_seconds();
+            if elapsed < SUBMIT_INTERVAL_SECS {
+                debug!("[Telemetry] Last submission {}s ago, skipping", elapsed);
+                return;
+            }
+        }
+        match submit_telemetry() {
+            Ok(true) => info!("[Telemetry] Usage statistics submitted"),
+            Ok(false) => {}
+            Err(e) => warn!("[Telemetry] Submission failed: {}", e),
+        }
+    });
+}
@@ -0,0 +1,336 @@
+use crate::commands::config::{
+    load_openclaw_config, remove_at_pointer, save_openclaw_config, set_at_pointer,
+};
+use crate::commands::locks::{self, ConfigResource};
+use crate::utils::{file, platform};
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::command;
+
+/// Days a deleted channel, agent or account can be restored
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// A config value removed by a deletion
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedValue {
+    /// JSON pointer the value was removed from
+    pub pointer: String,
+    pub value: Value,
+    /// The value was an element of the array at `pointer` (appended again on restore)
+    #[serde(default)]
+    pub array_item: bool,
+}
+
+/// A file or directory moved into the trash
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct TrashedPath {
+    pub original: String,
+    pub stored: String,
+}
+
+/// Everything one deletion removed (manager/trash.json)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedItem {
+    pub id: String,
+    /// channel, agent or telegram-account
+    pub kind: String,
+    pub name: String,
+    /// RFC3339
+    pub deleted_at: String,
+    pub values: Vec<TrashedValue>,
+    /// Env file entries removed with the item
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub paths: Vec<TrashedPath>,
+}
+
+/// Deleted item as shown in the UI (values are left out, they may hold tokens)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct DeletedItemSummary {
+    pub id: String,
+    pub kind: String,
+    pub name: String,
+    pub deleted_at: String,
+    pub expires_at: String,
+    /// Config locations that will be restored
+    pub pointers: Vec<String>,
+    pub env_keys: Vec<String>,
+    pub files: Vec<String>,
+}
+
+/// Escape a key for use as a JSON pointer segment
+pub(crate) fn pointer_key(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Remove the value at `pointer`, keeping it for the trash
+pub(crate) fn take_value(config: &mut Value, pointer: &str) -> Option<TrashedValue> {
+    let value = config.pointer(pointer)?.clone();
+    remove_at_pointer(config, pointer);
+    Some(TrashedValue {
+        pointer: pointer.to_string(),
+        value,
+        array_item: false,
+    })
+}
+
+/// Remove the elements of the array at `pointer` that match, keeping them for the trash
+pub(crate) fn take_array_items(
+    config: &mut Value,
+    pointer: &str,
+    matches: impl Fn(&Value) -> bool,
+) -> Vec<TrashedValue> {
+    let arr = match config.pointer_mut(pointer).and_then(|v| v.as_array_mut()) {
+        Some(arr) => arr,
+        None => return Vec::new(),
+    };
+    let (taken, kept): (Vec<Value>, Vec<Value>) = arr.drain(..).partition(|v| matches(v));
+    *arr = kept;
+    taken
+        .into_iter()
+        .map(|value| TrashedValue {
+            pointer: pointer.to_string(),
+            value,
+            array_item: true,
+        })
+        .collect()
+}
+
+/// Put trashed values back. Fails without changing anything if a value was re-created since.
+pub(crate) fn restore_values(config: &mut Value, values: &[TrashedValue]) -> Result<(), String> {
+    for trashed in values {
+        let taken = match config.pointer(&trashed.pointer) {
+            None => false,
+            Some(_) if !trashed.array_item => true,
+            // An element with the same id (agents.list) was added since
+            Some(current) => trashed.value.get("id").is_some_and(|id| {
+                current.as_array().is_some_and(|arr| {
+                    arr.iter()
+                        .any(|v| v.get("id") == Some(id) && *v != trashed.value)
+                })
+            }),
+        };
+        if taken {
+            return Err(format!(
+                "{} exists again; delete it first to restore the old one",
+                trashed.pointer
+            ));
+        }
+    }
+    for trashed in values {
+        if !trashed.array_item {
+            set_at_pointer(config, &trashed.pointer, Some(trashed.value.clone()));
+            continue;
+        }
+        if !config
+            .pointer(&trashed.pointer)
+            .map(|v| v.is_array())
+            .unwrap_or(false)
+        {
+            set_at_pointer(config, &trashed.pointer, Some(json!([])));
+        }
+        if let Some(arr) = config
+            .pointer_mut(&trashed.pointer)
+            .and_then(|v| v.as_array_mut())
+        {
+            if !arr.contains(&trashed.value) {
+                arr.push(trashed.value.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether an item deleted at `deleted_at` is past the retention period
+pub(crate) fn is_expired(deleted_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(deleted_at)
+        .map(|t| now - t.with_timezone(&Utc) > Duration::days(TRASH_RETENTION_DAYS))
+        .unwrap_or(true)
+}
+
+fn summarize(item: &DeletedItem) -> DeletedItemSummary {
+    let expires_at = DateTime::parse_from_rfc3339(&item.deleted_at)
+        .map(|t| (t + Duration::days(TRASH_RETENTION_DAYS)).to_rfc3339())
+        .unwrap_or_default();
+    let mut pointers: Vec<String> = item.values.iter().map(|v| v.pointer.clone()).collect();
+    pointers.dedup();
+    DeletedItemSummary {
+        id: item.id.clone(),
+        kind: item.kind.clone(),
+        name: item.name.clone(),
+        deleted_at: item.deleted_at.clone(),
+        expires_at,
+        pointers,
+        env_keys: item.env.keys().cloned().collect(),
+        files: item.paths.iter().map(|p| p.original.clone()).collect(),
+    }
+}
+
+fn get_trash_file_path() -> String {
+    if platform::is_windows() {
+        format!("{}\\trash.json", platform::get_manager_data_dir())
+    } else {
+        format!("{}/trash.json", platform::get_manager_data_dir())
+    }
+}
+
+/// Directory holding the files of one deleted item
+fn get_trash_dir(id: &str) -> std::path::PathBuf {
+    platform::join_path(platform::get_manager_data_dir(), &["trash", id])
+}
+
+fn load_trash() -> Vec<DeletedItem> {
+    file::read_file(&get_trash_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_trash(items: &[DeletedItem]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(items)
+        .map_err(|e| format!("Failed to serialize trash: {}", e))?;
+    file::write_file(&get_trash_file_path(), &content)
+        .map_err(|e| format!("Failed to write trash: {}", e))
+}
+
+/// Drop items past the retention period along with their files
+fn purge_expired(items: &mut Vec<DeletedItem>) -> bool {
+    let now = Utc::now();
+    let before = items.len();
+    items.retain(|item| {
+        if !is_expired(&item.deleted_at, now) {
+            return true;
+        }
+        info!(
+            "[Trash] Purging {} '{}' deleted at {}",
+            item.kind, item.name, item.deleted_at
+        );
+        let _ = std::fs::remove_dir_all(get_trash_dir(&item.id));
+        false
+    });
+    items.len() != before
+}
+
+/// Id for a new trash item; also names its file directory
+pub(crate) fn new_item_id(kind: &str) -> String {
+    format!("{}-{}", kind, Utc::now().format("%Y%m%d%H%M%S%3f"))
+}
+
+/// Move a file or directory into the trash of item `id`
+pub(crate) fn move_to_trash(id: &str, path: &Path) -> Result<TrashedPath, String> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "item".to_string());
+    let dir = get_trash_dir(id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let mut stored = dir.join(&name);
+    let mut n = 1;
+    while stored.exists() {
+        n += 1;
+        stored = dir.join(format!("{}-{}", name, n));
+    }
+    move_path(path, &stored)?;
+    Ok(TrashedPath {
+        original: path.to_string_lossy().to_string(),
+        stored: stored.to_string_lossy().to_string(),
+    })
+}
+
+/// Rename, or copy and delete when source and target are on different file systems
+fn move_path(from: &Path, to: &Path) -> Result<(), String> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let copied = if from.is_dir() {
+        file::copy_dir_all(from, to)
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    };
+    copied.map_err(|e| format!("Failed to move {:?}: {}", from, e))?;
+    let removed = if from.is_dir() {
+        std::fs::remove_dir_all(from)
+    } else {
+        std::fs::remove_file(from)
+    };
+    removed.map_err(|e| format!("Failed to remove {:?}: {}", from, e))
+}
+
+/// Record a deletion so it can be restored for TRASH_RETENTION_DAYS
+pub(crate) fn park(item: DeletedItem) -> Result<(), String> {
+    let mut items = load_trash();
+    purge_expired(&mut items);
+    info!(
+        "[Trash] Parked {} '{}' as {}",
+        item.kind, item.name, item.id
+    );
+    items.push(item);
+    save_trash(&items)
+}
+
+/// List deleted channels, agents and accounts that can still be restored
+#[command]
+#[specta::specta]
+pub async fn list_deleted_items() -> Result<Vec<DeletedItemSummary>, String> {
+    let _lock = locks::lock(&[ConfigResource::Manager], "list_deleted_items").await;
+    let mut items = load_trash();
+    if purge_expired(&mut items) {
+        save_trash(&items)?;
+    }
+    Ok(items.iter().rev().map(summarize).collect())
+}
+
+/// Restore a deleted channel, agent or account, including its env entries and files
+#[command]
+#[specta::specta]
+pub async fn restore_deleted_item(id: String) -> Result<String, String> {
+    let _lock = locks::lock(
+        &[ConfigResource::OpenClaw, ConfigResource::Manager],
+        "restore_deleted_item",
+    )
+    .await;
+    let mut items = load_trash();
+    let index = items
+        .iter()
+        .position(|item| item.id == id)
+        .ok_or_else(|| format!("Deleted item {} not found (it may have expired)", id))?;
+    let item = items[index].clone();
+    info!("[Trash] Restoring {} '{}'", item.kind, item.name);
+
+    for path in &item.paths {
+        if Path::new(&path.original).exists() {
+            return Err(format!(
+                "{} exists again; move it away to restore the old one",
+                path.original
+            ));
+        }
+    }
+    let mut config = load_openclaw_config()?;
+    restore_values(&mut config, &item.values)?;
+
+    for path in &item.paths {
+        if let Some(parent) = Path::new(&path.original).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        move_path(Path::new(&path.stored), Path::new(&path.original))?;
+    }
+    let env_path = platform::get_env_file_path();
+    for (key, value) in &item.env {
+        if let Err(e) = file::set_env_value(&env_path, key, value) {
+            warn!("[Trash] Failed to restore {}: {}", key, e);
+        }
+    }
+    save_openclaw_config(&config, "restore_deleted_item")?;
+
+    items.remove(index);
+    save_trash(&items)?;
+    let _ = std::fs::remove_dir_all(get_trash_dir(&item.id));
+    Ok(format!("{} '{}' restored", item.kind, item.name))
+}
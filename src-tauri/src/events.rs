@@ -0,0 +1,95 @@
+use crate::commands::installer::InstallProgress;
+use crate::models::ServiceStatus;
+use crate::utils::task_pool::{ItemProgress, ItemState};
+use log::debug;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The one channel the frontend subscribes to for backend events
+pub const EVENT_CHANNEL: &str = "manager://events";
+
+/// Bumped whenever an event payload changes incompatibly
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Severity of an `alert` event
+#[derive(Debug, Clone, Copy, Serialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Everything the backend pushes to the frontend, tagged by `type`
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(tag = "type", content = "payload", rename_all = "kebab-case")]
+pub enum ManagerEvent {
+    /// Gateway status after a start, stop or restart
+    ServiceStatus(ServiceStatus),
+    /// Step of a Node.js / Git / OpenClaw installation
+    InstallProgress(InstallProgress),
+    /// Output line of a long-running operation, `source` names the operation
+    LogLine { source: String, line: String },
+    /// A config file or a watched skill changed on disk
+    ConfigChanged { scope: String, path: String, detail: Option<Value> },
+    /// Progress of a multi-item task (`current` of `total` done)
+    TaskProgress { task: String, current: usize, total: usize, message: String },
+    /// Output of a console command as it arrives; `stream` is stdout or stderr
+    ConsoleOutput { session: String, stream: String, text: String },
+    /// A console command ended; `code` is None when it was killed
+    ConsoleExit { session: String, code: Option<i32>, cancelled: bool },
+    /// Something the user should know about; `kind` identifies it, `data` carries the details
+    Alert { kind: String, level: AlertLevel, message: String, data: Option<Value> },
+}
+
+/// What goes over the wire: schema version and timestamp around the event
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EventEnvelope {
+    pub schema_version: u32,
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub event: ManagerEvent,
+}
+
+impl ManagerEvent {
+    /// Alert with a serializable details payload
+    pub fn alert<T: Serialize>(kind: &str, level: AlertLevel, message: impl Into<String>, data: &T) -> Self {
+        ManagerEvent::Alert {
+            kind: kind.to_string(),
+            level,
+            message: message.into(),
+            data: serde_json::to_value(data).ok(),
+        }
+    }
+
+    /// Progress of one item of a `task_pool` run
+    pub fn item_progress(task: &str, progress: &ItemProgress) -> Self {
+        let message = match (progress.state, progress.error) {
+            (ItemState::Started, _) => format!("Started {}", progress.label),
+            (ItemState::Succeeded, _) => format!("Finished {}", progress.label),
+            (ItemState::Failed, error) => format!("{} failed: {}", progress.label, error.unwrap_or("unknown error")),
+        };
+        ManagerEvent::TaskProgress {
+            task: task.to_string(),
+            current: progress.done,
+            total: progress.total,
+            message,
+        }
+    }
+}
+
+/// Emit an event on `manager://events`. A no-op before the app is set up.
+pub fn emit(event: ManagerEvent) {
+    use tauri::Emitter;
+    if let Some(app) = crate::commands::service::app_handle() {
+        let envelope = EventEnvelope {
+            schema_version: EVENT_SCHEMA_VERSION,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event,
+        };
+        if let Err(e) = app.emit(EVENT_CHANNEL, envelope) {
+            debug!("[Events] Failed to emit event: {:?}", e);
+        }
+    }
+}
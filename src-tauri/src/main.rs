@@ -4,31 +4,86 @@
     windows_subsystem = "windows"
 )]
 
+mod bindings;
 mod commands;
+mod events;
 mod models;
+mod state;
 mod utils;
 
-use commands::{config, diagnostics, installer, process, service, skills};
+use commands::{attachments, config, crash_reports, diagnostics, installer, service, telemetry};
 use utils::log_sanitizer;
 use std::io::Write;
 
 fn main() {
+    // Write a crash report (with a backtrace) for any panic, including ones during startup
+    crash_reports::install_panic_hook();
+
     // Initialize logging - show info level logs by default
     env_logger::Builder::from_env(
         env_logger::Env::default().default_filter_or("info")
     )
     .format(|buf, record| {
         let sanitized = log_sanitizer::sanitize(&record.args().to_string());
-        writeln!(buf, "{} [{}] {}", record.level(), record.target(), sanitized)
+        let line = format!("{} [{}] {}", record.level(), record.target(), sanitized);
+        // The last lines go into a crash report
+        crash_reports::record_log_line(&line);
+        writeln!(buf, "{}", line)
     })
     .init();
     
     log::info!("🦞 OpenClaw Manager started");
 
+    // Spawned openclaw processes must follow a relocated config directory
+    utils::platform::apply_config_dir_env();
+    // Values behind ${env:...} / ${secret:...} config placeholders, passed to openclaw
+    utils::shell::set_openclaw_env(commands::placeholders::placeholder_env);
+
+    let builder = bindings::builder();
+    // Keep src/lib/bindings.ts in step with the command signatures during development
+    #[cfg(debug_assertions)]
+    if let Err(e) = bindings::export(&builder, bindings::BINDINGS_PATH) {
+        log::warn!("{}", e);
+    }
+
     tauri::Builder::default()
+        // Config file locks and the parsed config cache
+        .manage(state::app_state())
         .setup(|app| {
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build())?;
+            // Submit anonymous usage stats (only if the user opted in)
+            telemetry::submit_in_background();
+            // Let the service supervisor notify about crash loops
+            service::set_app_handle(app.handle().clone());
+            // Report a previous session that ended without exiting (offered on the UI's next start)
+            crash_reports::check_previous_run();
+            // Detect a read-only ~/.openclaw once instead of failing every save
+            diagnostics::start_write_access_preflight();
+            // Check the Manager's own config, env, shell and backup paths
+            diagnostics::start_self_test();
+            // Flag installs and updates the last Manager session exited during
+            commands::tasks::detect_interrupted_tasks();
+            // Detect Node.js / OpenClaw installed from another terminal
+            installer::start_environment_watcher();
+            // Alert when gateway memory or CPU crosses the configured thresholds
+            service::start_resource_monitor();
+            // Count the gateway's daily network traffic
+            service::start_network_meter();
+            // Keep gateway logs within the configured rotation limits
+            service::start_log_rotation_task();
+            // Delete received attachments past the retention limits
+            attachments::start_attachment_cleanup_task();
+            // Tell the UI when openclaw.json, env or mcps.json are edited outside the Manager
+            config::start_config_watcher();
+            // Notify about new channel pairing requests
+            config::start_pairing_watcher(app.handle().clone());
+            // Rotate the gateway token on its schedule (if enabled)
+            config::start_token_rotation_task();
+            // Remind about provider API keys close to their expiration date
+            config::start_key_expiry_watcher(app.handle().clone());
+            // Daily canary ping (if enabled)
+            diagnostics::start_canary_task(app.handle().clone());
             Ok(())
         })
         .plugin(tauri_plugin_shell::init())
@@ -36,127 +91,13 @@ fn main() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_notification::init())
-        .invoke_handler(tauri::generate_handler![
-            // Service management
-            service::get_service_status,
-            service::start_service,
-            service::stop_service,
-            service::restart_service,
-            service::get_logs,
-            service::kill_all_port_processes,
-            // Process management
-            process::check_openclaw_installed,
-            process::get_openclaw_version,
-            process::check_secure_version,
-            process::check_port_in_use,
-            process::check_ollama_installed,
-            process::get_ollama_models,
-            process::install_ollama_model,
-            // Configuration management
-            config::get_config,
-            config::save_config,
-            config::get_tools_profile,
-            config::save_tools_profile,
-            config::get_pdf_config,
-            config::save_pdf_config,
-            config::get_memory_config,
-            config::save_memory_config,
-            config::get_env_value,
-            config::save_env_value,
-            config::get_ai_providers,
-            config::get_channels_config,
-            config::save_channel_config,
-            config::clear_channel_config,
-            // Gateway Token
-            config::get_or_create_gateway_token,
-            config::get_dashboard_url,
-            config::repair_device_token,
-            // AI configuration management
-            config::get_official_providers,
-            config::get_ai_config,
-            config::save_provider,
-            config::delete_provider,
-            config::set_primary_model,
-            config::add_available_model,
-            config::remove_available_model,
-            // Feishu plugin management
-            config::check_feishu_plugin,
-            config::install_feishu_plugin,
-            // MCP management
-            config::get_mcp_config,
-            config::save_mcp_config,
-            config::install_mcp_from_git,
-            config::uninstall_mcp,
-            config::check_mcporter_installed,
-            config::install_mcporter,
-            config::uninstall_mcporter,
-            config::install_mcp_plugin,
-            config::openclaw_config_set,
-            config::validate_openclaw_config,
-            config::test_mcp_server,
-            // Diagnostic tests
-            diagnostics::run_doctor,
-            diagnostics::test_ai_connection,
-            diagnostics::test_channel,
-            diagnostics::get_system_info,
-            diagnostics::start_channel_login,
-            // Installer
-            installer::check_environment,
-            installer::install_nodejs,
-            installer::install_openclaw,
-            installer::init_openclaw_config,
-            installer::open_install_terminal,
-            installer::uninstall_openclaw,
-            installer::install_gateway_service,
-            // Version update
-            installer::check_openclaw_update,
-            installer::update_openclaw,
-            // Skills management
-            skills::get_skills,
-            skills::check_clawhub_installed,
-            skills::install_clawhub,
-            skills::install_skill,
-            skills::uninstall_skill,
-            skills::uninstall_skill,
-            skills::uninstall_clawhub,
-            // Multi-Agent Routing
-            config::get_openclaw_home_dir,
-            config::get_agents_config,
-            config::save_agent,
-            config::save_subagent_defaults,
-            config::delete_agent,
-            config::save_agent_binding,
-            config::delete_agent_binding,
-            config::get_agent_system_prompt,
-            config::save_agent_system_prompt,
-            config::test_agent_routing,
-            // Telegram Multi-Account
-            config::get_telegram_accounts,
-            config::save_telegram_account,
-            config::delete_telegram_account,
-            // Heartbeat & Compaction
-            config::get_heartbeat_config,
-            config::save_heartbeat_config,
-            config::get_compaction_config,
-            config::save_compaction_config,
-            // Workspace & Personality
-            config::get_workspace_config,
-            config::save_workspace_config,
-            config::get_personality_file,
-            config::save_personality_file,
-            // Browser Control
-            config::get_browser_config,
-            config::save_browser_config,
-            // Web Search
-            config::get_web_config,
-            config::save_web_config,
-            // Gateway Configuration
-            config::get_gateway_config,
-            config::save_gateway_config,
-            // Configuration Management
-            config::export_config,
-            config::import_config,
-        ])
-        .run(tauri::generate_context!())
-        .expect("Error occurred while running Tauri application");
+        .invoke_handler(builder.invoke_handler())
+        .build(tauri::generate_context!())
+        .expect("Error occurred while running Tauri application")
+        .run(|_, event| {
+            // Without this, the next launch reports the session as crashed
+            if let tauri::RunEvent::Exit = event {
+                crash_reports::mark_clean_exit();
+            }
+        });
 }
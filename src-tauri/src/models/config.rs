@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// OpenClaw complete configuration - corresponds to openclaw.json structure
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct OpenClawConfig {
     /// Agent configuration
     #[serde(default)]
@@ -28,7 +28,7 @@ pub struct OpenClawConfig {
 }
 
 /// Agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct AgentsConfig {
     /// Default configuration
     #[serde(default)]
@@ -36,7 +36,7 @@ pub struct AgentsConfig {
 }
 
 /// Agent default configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct AgentDefaults {
     /// Model configuration
     #[serde(default)]
@@ -62,7 +62,7 @@ pub struct AgentDefaults {
 }
 
 /// Agent model configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct AgentModelConfig {
     /// Primary model (format: provider/model-id)
     #[serde(default)]
@@ -70,7 +70,7 @@ pub struct AgentModelConfig {
 }
 
 /// Model configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct ModelsConfig {
     /// Provider configuration mapping
     #[serde(default)]
@@ -78,7 +78,7 @@ pub struct ModelsConfig {
 }
 
 /// Provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ProviderConfig {
     /// API URL
     #[serde(rename = "baseUrl")]
@@ -89,10 +89,16 @@ pub struct ProviderConfig {
     /// Model list
     #[serde(default)]
     pub models: Vec<ModelConfig>,
+    /// Extra headers sent with every request (X-API-Version, Azure api-key, ...)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Whether the key is sent as `Authorization: Bearer` (false when a header carries it)
+    #[serde(rename = "authHeader", default, skip_serializing_if = "Option::is_none")]
+    pub auth_header: Option<bool>,
 }
 
 /// Model configuration details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ModelConfig {
     /// Model ID
     pub id: String,
@@ -116,10 +122,20 @@ pub struct ModelConfig {
     /// Cost configuration
     #[serde(default)]
     pub cost: Option<ModelCostConfig>,
+    /// Extra headers for this model only, on top of the provider's
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+/// Azure OpenAI connection settings for save_provider
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AzureProviderOptions {
+    /// Resource name (my-resource) or endpoint URL; model ids are deployment names
+    pub resource: String,
 }
 
 /// Model cost configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct ModelCostConfig {
     #[serde(default)]
     pub input: f64,
@@ -132,7 +148,7 @@ pub struct ModelCostConfig {
 }
 
 /// Gateway configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct GatewayConfig {
     /// Mode: local or cloud
     #[serde(default)]
@@ -143,7 +159,7 @@ pub struct GatewayConfig {
 }
 
 /// Gateway authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct GatewayAuthConfig {
     #[serde(default)]
     pub mode: Option<String>,
@@ -152,7 +168,7 @@ pub struct GatewayAuthConfig {
 }
 
 /// Plugin configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct PluginsConfig {
     #[serde(default)]
     pub allow: Vec<String>,
@@ -163,7 +179,7 @@ pub struct PluginsConfig {
 }
 
 /// MCP configuration (supports both stdio and HTTP modes)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct MCPConfig {
     /// Command to run (for stdio servers)
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -180,14 +196,56 @@ pub struct MCPConfig {
     /// Whether enabled
     #[serde(default = "default_mcp_enabled")]
     pub enabled: bool,
+    /// Docker image to run instead of a local command (for docker servers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker: Option<DockerMCPConfig>,
+    /// Where the server was installed from (Manager bookkeeping, not synced to clients)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install: Option<MCPInstallInfo>,
+    /// Set when the server was imported from another tool's config (e.g. "mcporter")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external: Option<String>,
+}
+
+/// Install source of an MCP server, used for update checks
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct MCPInstallInfo {
+    /// "git" or "npm"
+    pub source: String,
+    /// Git origin URL or npm package spec
+    pub origin: String,
+    /// Installed git commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
 }
 
 fn default_mcp_enabled() -> bool {
     true
 }
 
+/// MCP server shipped as a Docker image, run with `docker run -i --rm`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
+pub struct DockerMCPConfig {
+    /// Image reference, e.g. mcp/github:latest
+    pub image: String,
+    /// Volume mounts in `host:container[:ro]` form
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<String>,
+    /// Environment passed into the container (values stay out of the command line)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Extra arguments passed to the image entrypoint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Docker network, e.g. "none" to cut the server off from the network
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
 /// Metadata configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, specta::Type)]
 pub struct MetaConfig {
     #[serde(rename = "lastTouchedAt", default)]
     pub last_touched_at: Option<String>,
@@ -198,7 +256,7 @@ pub struct MetaConfig {
 // ============ Data structures for frontend display ============
 
 /// Official Provider preset (for frontend display)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct OfficialProvider {
     /// Provider ID (used in configuration)
     pub id: String,
@@ -221,7 +279,7 @@ pub struct OfficialProvider {
 }
 
 /// Recommended model
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct SuggestedModel {
     /// Model ID
     pub id: String,
@@ -238,7 +296,7 @@ pub struct SuggestedModel {
 }
 
 /// Configured Provider (read from configuration file)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ConfiguredProvider {
     /// Provider name (key in configuration)
     pub name: String,
@@ -248,12 +306,16 @@ pub struct ConfiguredProvider {
     pub api_key_masked: Option<String>,
     /// Whether API Key exists
     pub has_api_key: bool,
+    /// Names of custom request headers (values are not sent to the UI)
+    pub header_names: Vec<String>,
+    /// Whether the key is sent as a bearer token (None: core default)
+    pub auth_header: Option<bool>,
     /// Configured model list
     pub models: Vec<ConfiguredModel>,
 }
 
 /// Configured model
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ConfiguredModel {
     /// Full model ID (provider/model-id)
     pub full_id: String,
@@ -272,7 +334,7 @@ pub struct ConfiguredModel {
 }
 
 /// AI configuration overview (returned to frontend)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct AIConfigOverview {
     /// Primary model
     pub primary_model: Option<String>,
@@ -285,7 +347,7 @@ pub struct AIConfigOverview {
 // ============ Legacy data structures for compatibility ============
 
 /// AI Provider option (for frontend display) - legacy compatibility
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct AIProviderOption {
     /// Provider ID
     pub id: String,
@@ -302,7 +364,7 @@ pub struct AIProviderOption {
 }
 
 /// AI model option - legacy compatibility
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct AIModelOption {
     /// Model ID
     pub id: String,
@@ -315,7 +377,7 @@ pub struct AIModelOption {
 }
 
 /// Channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ChannelConfig {
     /// Channel ID
     pub id: String,
@@ -328,7 +390,7 @@ pub struct ChannelConfig {
 }
 
 /// Environment variable configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct EnvConfig {
     pub key: String,
     pub value: String,
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Service running status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ServiceStatus {
     /// Whether it is running
     pub running: bool,
@@ -15,6 +15,8 @@ pub struct ServiceStatus {
     pub memory_mb: Option<f64>,
     /// CPU usage percentage
     pub cpu_percent: Option<f64>,
+    /// Who started the gateway: "manager", "adopted" or "external" (None when stopped)
+    pub origin: Option<String>,
 }
 
 impl Default for ServiceStatus {
@@ -26,19 +28,24 @@ impl Default for ServiceStatus {
             uptime_seconds: None,
             memory_mb: None,
             cpu_percent: None,
+            origin: None,
         }
     }
 }
 
 /// System information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct SystemInfo {
     /// Operating system type
     pub os: String,
     /// Operating system version
     pub os_version: String,
-    /// System architecture
+    /// System architecture (of the Manager build)
     pub arch: String,
+    /// Native CPU architecture in Node.js naming (arm64 on Apple Silicon even under Rosetta)
+    pub native_arch: String,
+    /// Whether the Manager runs emulated (Rosetta 2, x64 on Windows on ARM)
+    pub translated: bool,
     /// Whether OpenClaw is installed
     pub openclaw_installed: bool,
     /// OpenClaw version
@@ -50,7 +57,7 @@ pub struct SystemInfo {
 }
 
 /// Diagnostic result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct DiagnosticResult {
     /// Check item name
     pub name: String,
@@ -63,7 +70,7 @@ pub struct DiagnosticResult {
 }
 
 /// AI connection test result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct AITestResult {
     /// Whether successful
     pub success: bool,
@@ -80,7 +87,7 @@ pub struct AITestResult {
 }
 
 /// Channel test result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ChannelTestResult {
     /// Whether successful
     pub success: bool,
@@ -0,0 +1,152 @@
+use crate::commands::locks::ConfigLocks;
+use crate::utils::executor::{self, Executor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::SystemTime;
+
+/// State shared by all commands, registered with `Builder::manage`
+pub struct AppState {
+    /// Serializes read-modify-write cycles per config file
+    pub locks: ConfigLocks,
+    /// Parsed openclaw.json (and draft) kept between commands
+    pub config_cache: ConfigCache,
+    /// Runs external commands: the system, or fixtures in mock mode
+    pub executor: Box<dyn Executor>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        AppState {
+            locks: ConfigLocks::default(),
+            config_cache: ConfigCache::default(),
+            executor: executor::default_executor(),
+        }
+    }
+}
+
+/// The one AppState: managed by the app and used by helpers that get no `tauri::State`
+/// parameter, before setup and in tests alike
+static APP_STATE: OnceLock<AppState> = OnceLock::new();
+
+pub fn app_state() -> &'static AppState {
+    APP_STATE.get_or_init(AppState::default)
+}
+
+/// Size and modification time of a file when it was read
+#[derive(Debug, Clone, PartialEq)]
+struct FileStamp {
+    path: String,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &str) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        FileStamp {
+            path: path.to_string(),
+            modified: meta.as_ref().and_then(|m| m.modified().ok()),
+            len: meta.map(|m| m.len()).unwrap_or(0),
+        }
+    }
+}
+
+/// Stamps of a config file (and the includes it had last time) taken before it is read
+pub struct PendingRead {
+    path: String,
+    stamps: Vec<FileStamp>,
+}
+
+struct CachedConfig {
+    /// Main file and every `$include`d file the value was built from
+    files: Vec<FileStamp>,
+    value: Value,
+}
+
+/// Parsed config files keyed by path. An entry is served while none of its files changed on
+/// disk (one stat per file instead of read + parse); Manager writes invalidate explicitly so
+/// changes within the filesystem's timestamp granularity are never missed.
+#[derive(Default)]
+pub struct ConfigCache {
+    entries: RwLock<HashMap<String, CachedConfig>>,
+    /// Bumped on every invalidation and reload
+    version: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Cache counters for debugging
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ConfigCacheStatus {
+    pub version: u64,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl ConfigCache {
+    /// Cached value for `path` if none of its files changed since it was read
+    pub fn get(&self, path: &str) -> Option<Value> {
+        let entries = self.entries.read().ok()?;
+        let fresh = entries
+            .get(path)
+            .filter(|cached| cached.files.iter().all(|stamp| FileStamp::of(&stamp.path) == *stamp))
+            .map(|cached| cached.value.clone());
+        let counter = if fresh.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        fresh
+    }
+
+    /// Stamp `path` and its last known includes before reading them, so a write landing
+    /// during the read makes the entry stale instead of caching the old content as current
+    pub fn stamp(&self, path: &str) -> PendingRead {
+        let known: Vec<String> = self
+            .entries
+            .read()
+            .ok()
+            .and_then(|entries| entries.get(path).map(|c| c.files.iter().skip(1).map(|s| s.path.clone()).collect()))
+            .unwrap_or_default();
+        let mut stamps = vec![FileStamp::of(path)];
+        stamps.extend(known.iter().map(|f| FileStamp::of(f)));
+        PendingRead { path: path.to_string(), stamps }
+    }
+
+    /// Remember the value parsed after `read` and the files it came from. Includes that were
+    /// not stamped before the read are only cached from the next read on.
+    pub fn insert(&self, read: PendingRead, value: &Value, files: &[String]) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+        let stamped: Vec<&str> = read.stamps.iter().skip(1).map(|s| s.path.as_str()).collect();
+        if let Ok(mut entries) = self.entries.write() {
+            if stamped == files {
+                entries.insert(read.path, CachedConfig { files: read.stamps, value: value.clone() });
+            } else {
+                // Keep the include list so the next read stamps it up front
+                let mut stamps = read.stamps;
+                stamps.truncate(1);
+                stamps.extend(files.iter().map(|f| FileStamp { path: f.clone(), modified: None, len: 0 }));
+                entries.insert(read.path, CachedConfig { files: stamps, value: value.clone() });
+            }
+        }
+    }
+
+    /// Drop every entry (a write may touch any `$include`d file)
+    pub fn invalidate(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+
+    pub fn status(&self) -> ConfigCacheStatus {
+        let entries = self.entries.read().map(|e| e.len()).unwrap_or(0);
+        ConfigCacheStatus {
+            version: self.version.load(Ordering::SeqCst),
+            entries,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use crate::bindings::COMMAND_NAMES;
+    use crate::commands::api::{
+        build_manifest, schema_version, CommandAlias, API_VERSION, DEPRECATED_ALIASES,
+        SCHEMA_VERSIONS,
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_command_names_unique() {
+        let mut seen = HashSet::new();
+        for name in COMMAND_NAMES {
+            assert!(seen.insert(name), "{} is registered twice", name);
+        }
+    }
+
+    #[test]
+    fn test_registry_entries_exist() {
+        for (name, _) in SCHEMA_VERSIONS {
+            assert!(COMMAND_NAMES.contains(name), "{} is not registered", name);
+        }
+        for alias in DEPRECATED_ALIASES {
+            assert!(
+                COMMAND_NAMES.contains(&alias.name),
+                "{} is not registered",
+                alias.name
+            );
+            assert!(
+                COMMAND_NAMES.contains(&alias.replaced_by),
+                "{} is not registered",
+                alias.replaced_by
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_version_default() {
+        assert_eq!(schema_version("get_service_status"), 1);
+        assert_eq!(schema_version("start_service"), 2);
+    }
+
+    #[test]
+    fn test_manifest_marks_aliases() {
+        let aliases = [CommandAlias {
+            name: "begin_service",
+            replaced_by: "start_service",
+            deprecated_in: "0.0.16",
+        }];
+        let manifest = build_manifest(&["start_service", "begin_service"], &aliases);
+        assert_eq!(manifest.api_version, API_VERSION);
+        assert_eq!(manifest.commands.len(), 2);
+
+        let current = &manifest.commands[0];
+        assert!(!current.deprecated);
+        assert_eq!(current.replaced_by, None);
+
+        let alias = &manifest.commands[1];
+        assert!(alias.deprecated);
+        assert_eq!(alias.replaced_by.as_deref(), Some("start_service"));
+        assert_eq!(alias.schema_version, 2);
+        assert_eq!(alias.deprecated_in.as_deref(), Some("0.0.16"));
+    }
+}
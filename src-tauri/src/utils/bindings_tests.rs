@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::bindings;
+
+    #[test]
+    fn test_bindings_export() {
+        let path =
+            std::env::temp_dir().join(format!("openclaw-bindings-{}.ts", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        bindings::export(&bindings::builder(), &path_str).unwrap();
+
+        let generated = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(generated.contains("getServiceStatus"));
+        assert!(generated.contains("probeGateway"));
+        assert!(generated.contains("export type ServiceStatus"));
+        assert!(generated.contains("export type EventEnvelope"));
+    }
+
+    /// `npm run bindings`: regenerate src/lib/bindings.ts without starting the app
+    #[test]
+    #[ignore]
+    fn export_bindings() {
+        bindings::export(&bindings::builder(), bindings::BINDINGS_PATH).unwrap();
+    }
+}
@@ -0,0 +1,204 @@
+//! Messages sent as a bot from the Manager: the gateway's `message` tool is invoked over HTTP
+//! (`POST /tools/invoke`) so the gateway delivers through the channel account it already
+//! runs, and the returned message id confirms the delivery.
+
+use crate::utils::contacts::Contact;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Largest attachment sent, in MB (channels cap uploads well below this)
+pub const MAX_ATTACHMENT_MB: u64 = 50;
+
+/// Where a message goes: a peer of a channel, as one of its bot accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageTarget {
+    pub channel: String,
+    /// Bot account to send as; None for the channel's default account
+    pub account_id: Option<String>,
+    /// Peer id (or a contact name, resolved before sending)
+    pub target: String,
+    /// Group or contact name, for display
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Outcome of sending to one target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryResult {
+    pub target: MessageTarget,
+    /// The gateway accepted every part of the message
+    pub delivered: bool,
+    /// Ids the channel gave the sent messages: the delivery confirmation
+    pub message_ids: Vec<String>,
+    /// "gateway" (HTTP tools API) or "cli" (`openclaw message send`, for gateways without it)
+    pub via: String,
+    pub error: Option<String>,
+}
+
+/// Text and attachments split into the messages sent: the text goes with the first
+/// attachment, further attachments follow one per message
+pub fn message_parts<'a>(
+    text: &'a str,
+    attachments: &'a [String],
+) -> Vec<(&'a str, Option<&'a str>)> {
+    if attachments.is_empty() {
+        return vec![(text, None)];
+    }
+    attachments
+        .iter()
+        .enumerate()
+        .map(|(i, media)| (if i == 0 { text } else { "" }, Some(media.as_str())))
+        .collect()
+}
+
+/// Body of a `POST /tools/invoke` calling the message tool's send action
+pub fn invoke_body(target: &MessageTarget, text: &str, media: Option<&str>) -> Value {
+    let mut args = json!({
+        "action": "send",
+        "channel": target.channel,
+        "target": target.target,
+        "message": text,
+    });
+    if let Some(account) = target.account_id.as_deref().filter(|a| !a.is_empty()) {
+        args["accountId"] = json!(account);
+    }
+    if let Some(media) = media {
+        args["media"] = json!(media);
+    }
+    json!({ "tool": "message", "action": "send", "args": args })
+}
+
+/// First `messageId` anywhere in a tool result
+pub fn find_message_id(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(map) => map
+            .get("messageId")
+            .and_then(|id| match id {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .or_else(|| map.values().find_map(find_message_id)),
+        Value::Array(items) => items.iter().find_map(find_message_id),
+        // Tool results wrap their details as JSON text content
+        Value::String(text) if text.contains("messageId") => serde_json::from_str::<Value>(text)
+            .ok()
+            .as_ref()
+            .and_then(find_message_id),
+        _ => None,
+    }
+}
+
+/// How a `/tools/invoke` answer turned out
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvokeOutcome {
+    /// Sent; the message id when the channel reported one
+    Sent(Option<String>),
+    /// The gateway has no tools API or does not expose the message tool over it
+    Unavailable,
+    Failed(String),
+}
+
+/// Interpret the HTTP status and body of a `/tools/invoke` call
+pub fn parse_invoke_response(status: u16, body: &str) -> InvokeOutcome {
+    let json: Option<Value> = serde_json::from_str(body.trim()).ok();
+    let error_text = || {
+        json.as_ref()
+            .and_then(|j| {
+                j.pointer("/error/message")
+                    .or_else(|| j.get("error"))
+                    .and_then(|e| e.as_str())
+            })
+            .map(String::from)
+            .unwrap_or_else(|| body.trim().chars().take(200).collect())
+    };
+    match status {
+        200..=299 => {
+            let Some(json) = json.as_ref() else {
+                return InvokeOutcome::Failed("Gateway returned an unreadable answer".to_string());
+            };
+            if json.get("ok").and_then(|v| v.as_bool()) == Some(false) {
+                return InvokeOutcome::Failed(error_text());
+            }
+            InvokeOutcome::Sent(find_message_id(json.get("result").unwrap_or(json)))
+        }
+        401 | 403 => InvokeOutcome::Failed("Gateway refused the token".to_string()),
+        404 | 405 => InvokeOutcome::Unavailable,
+        0 => InvokeOutcome::Failed("Gateway is not running".to_string()),
+        _ => InvokeOutcome::Failed(format!("Gateway answered {}: {}", status, error_text())),
+    }
+}
+
+/// Groups configured per channel account (`groups`, and Slack's `channels`), plus group and
+/// channel contacts, as broadcast targets. Wildcard entries are skipped.
+pub fn group_targets(config: &Value, contacts: &[Contact]) -> Vec<MessageTarget> {
+    let mut targets: Vec<MessageTarget> = Vec::new();
+    let label_of = |channel: &str, peer: &str, entry: &Value| {
+        entry
+            .get("name")
+            .or_else(|| entry.get("title"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or_else(|| {
+                contacts
+                    .iter()
+                    .find(|c| c.channel == channel && c.peer_id == peer)
+                    .map(|c| c.name.clone())
+            })
+    };
+
+    if let Some(channels) = config.get("channels").and_then(|v| v.as_object()) {
+        for (channel, channel_cfg) in channels {
+            if channel_cfg.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+                continue;
+            }
+            let mut scopes: Vec<(Option<String>, &Value)> = vec![(None, channel_cfg)];
+            if let Some(accounts) = channel_cfg.get("accounts").and_then(|v| v.as_object()) {
+                scopes.extend(accounts.iter().map(|(id, cfg)| (Some(id.clone()), cfg)));
+            }
+            for (account_id, scope) in scopes {
+                for key in ["groups", "channels"] {
+                    let Some(groups) = scope.get(key).and_then(|v| v.as_object()) else {
+                        continue;
+                    };
+                    for (peer, entry) in groups {
+                        if peer == "*"
+                            || entry.get("enabled").and_then(|v| v.as_bool()) == Some(false)
+                        {
+                            continue;
+                        }
+                        let known = targets.iter().any(|t| {
+                            t.channel == *channel && t.account_id == account_id && t.target == *peer
+                        });
+                        if !known {
+                            targets.push(MessageTarget {
+                                channel: channel.clone(),
+                                account_id: account_id.clone(),
+                                target: peer.clone(),
+                                label: label_of(channel, peer, entry),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // Contacts only add groups no bot account is configured for
+    for contact in contacts.iter().filter(|c| c.kind != "direct") {
+        if targets
+            .iter()
+            .any(|t| t.channel == contact.channel && t.target == contact.peer_id)
+        {
+            continue;
+        }
+        targets.push(MessageTarget {
+            channel: contact.channel.clone(),
+            account_id: contact.account_id.clone(),
+            target: contact.peer_id.clone(),
+            label: Some(contact.name.clone()),
+        });
+    }
+    targets
+}
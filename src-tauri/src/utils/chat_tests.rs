@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use super::super::chat::{
+        find_message_id, group_targets, invoke_body, message_parts, parse_invoke_response,
+        InvokeOutcome, MessageTarget,
+    };
+    use super::super::contacts::Contact;
+    use serde_json::json;
+
+    fn target(account: Option<&str>) -> MessageTarget {
+        MessageTarget {
+            channel: "telegram".to_string(),
+            account_id: account.map(String::from),
+            target: "-100123".to_string(),
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_message_parts() {
+        assert_eq!(message_parts("hi", &[]), vec![("hi", None)]);
+        let files = vec!["/tmp/a.png".to_string(), "/tmp/b.pdf".to_string()];
+        assert_eq!(
+            message_parts("hi", &files),
+            vec![("hi", Some("/tmp/a.png")), ("", Some("/tmp/b.pdf"))]
+        );
+    }
+
+    #[test]
+    fn test_invoke_body() {
+        assert_eq!(
+            invoke_body(&target(Some("bot2")), "Hello", Some("/tmp/a.png")),
+            json!({
+                "tool": "message",
+                "action": "send",
+                "args": {
+                    "action": "send",
+                    "channel": "telegram",
+                    "target": "-100123",
+                    "message": "Hello",
+                    "accountId": "bot2",
+                    "media": "/tmp/a.png"
+                }
+            })
+        );
+        let body = invoke_body(&target(None), "Hello", None);
+        assert!(body["args"].get("accountId").is_none());
+        assert!(body["args"].get("media").is_none());
+    }
+
+    #[test]
+    fn test_find_message_id() {
+        assert_eq!(
+            find_message_id(&json!({"details": {"messageId": 4711}})),
+            Some("4711".to_string())
+        );
+        let wrapped =
+            json!({"content": [{"type": "text", "text": "{\"ok\":true,\"messageId\":\"m-1\"}"}]});
+        assert_eq!(find_message_id(&wrapped), Some("m-1".to_string()));
+        assert_eq!(find_message_id(&json!({"ok": true})), None);
+    }
+
+    #[test]
+    fn test_parse_invoke_response() {
+        assert_eq!(
+            parse_invoke_response(
+                200,
+                r#"{"ok":true,"result":{"details":{"messageId":"99"}}}"#
+            ),
+            InvokeOutcome::Sent(Some("99".to_string()))
+        );
+        assert_eq!(
+            parse_invoke_response(200, r#"{"ok":true,"result":{}}"#),
+            InvokeOutcome::Sent(None)
+        );
+        assert_eq!(
+            parse_invoke_response(200, r#"{"ok":false,"error":{"message":"chat not found"}}"#),
+            InvokeOutcome::Failed("chat not found".to_string())
+        );
+        assert_eq!(
+            parse_invoke_response(404, "Not Found"),
+            InvokeOutcome::Unavailable
+        );
+        assert!(matches!(
+            parse_invoke_response(401, ""),
+            InvokeOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            parse_invoke_response(0, ""),
+            InvokeOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            parse_invoke_response(200, "<html>"),
+            InvokeOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_group_targets() {
+        let config = json!({
+            "channels": {
+                "telegram": {
+                    "groups": {"*": {"requireMention": true}, "-1001": {}},
+                    "accounts": {
+                        "bot2": {"groups": {"-1002": {"name": "Announcements"}, "-1003": {"enabled": false}}}
+                    }
+                },
+                "slack": {"channels": {"C0123": {}}},
+                "discord": {"enabled": false, "groups": {"g1": {}}}
+            }
+        });
+        let contacts = vec![
+            Contact {
+                id: "telegram:-1001".to_string(),
+                name: "Family".to_string(),
+                channel: "telegram".to_string(),
+                peer_id: "-1001".to_string(),
+                kind: "group".to_string(),
+                account_id: None,
+                notes: None,
+                source: "manual".to_string(),
+                last_seen_at: None,
+            },
+            Contact {
+                id: "telegram:-1009".to_string(),
+                name: "Club".to_string(),
+                channel: "telegram".to_string(),
+                peer_id: "-1009".to_string(),
+                kind: "group".to_string(),
+                account_id: Some("bot2".to_string()),
+                notes: None,
+                source: "manual".to_string(),
+                last_seen_at: None,
+            },
+        ];
+        let targets = group_targets(&config, &contacts);
+        let summary: Vec<(String, Option<String>, String, Option<String>)> = targets
+            .into_iter()
+            .map(|t| (t.channel, t.account_id, t.target, t.label))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("slack".into(), None, "C0123".into(), None),
+                (
+                    "telegram".into(),
+                    None,
+                    "-1001".into(),
+                    Some("Family".into())
+                ),
+                (
+                    "telegram".into(),
+                    Some("bot2".into()),
+                    "-1002".into(),
+                    Some("Announcements".into())
+                ),
+                (
+                    "telegram".into(),
+                    Some("bot2".into()),
+                    "-1009".into(),
+                    Some("Club".into())
+                ),
+            ]
+        );
+    }
+}
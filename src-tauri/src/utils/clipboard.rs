@@ -0,0 +1,84 @@
+use crate::utils::{platform, shell};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+/// Program name and arguments
+type ClipboardTool = (&'static str, Vec<&'static str>);
+
+/// Pick the clipboard write/read programs available on this system
+fn clipboard_programs() -> Option<(ClipboardTool, ClipboardTool)> {
+    if platform::is_macos() {
+        Some((("pbcopy", vec![]), ("pbpaste", vec![])))
+    } else if platform::is_windows() {
+        Some((
+            ("powershell", vec!["-NoProfile", "-Command", "[Console]::In.ReadToEnd() | Set-Clipboard"]),
+            ("powershell", vec!["-NoProfile", "-Command", "Get-Clipboard -Raw"]),
+        ))
+    } else if std::env::var("WAYLAND_DISPLAY").is_ok() && shell::command_exists("wl-copy") {
+        Some((("wl-copy", vec![]), ("wl-paste", vec!["--no-newline"])))
+    } else if shell::command_exists("xclip") {
+        Some((
+            ("xclip", vec!["-selection", "clipboard"]),
+            ("xclip", vec!["-selection", "clipboard", "-o"]),
+        ))
+    } else if shell::command_exists("xsel") {
+        Some((("xsel", vec!["--clipboard", "--input"]), ("xsel", vec!["--clipboard", "--output"])))
+    } else {
+        None
+    }
+}
+
+fn new_command(program: &str, args: &[&str]) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000);
+    cmd
+}
+
+/// Place text on the system clipboard (text is passed via stdin, never on the command line)
+pub fn set_text(text: &str) -> Result<(), String> {
+    let ((program, args), _) = clipboard_programs()
+        .ok_or("No clipboard tool found (install wl-clipboard, xclip or xsel)")?;
+
+    let mut child = new_command(program, &args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+    }
+
+    let status = child.wait().map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
+
+/// Read the current clipboard text, if any
+pub fn get_text() -> Option<String> {
+    let (_, (program, args)) = clipboard_programs()?;
+    let output = new_command(program, &args).output().ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim_end_matches(['\r', '\n']).to_string())
+    } else {
+        None
+    }
+}
+
+/// Clear the clipboard only if it still holds `expected` (the user may have copied something else)
+pub fn clear_if_unchanged(expected: &str) {
+    if get_text().as_deref() == Some(expected) {
+        let _ = set_text("");
+    }
+}
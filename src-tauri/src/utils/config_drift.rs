@@ -0,0 +1,122 @@
+//! Drift between the local openclaw.json and another machine's config, grouped by section.
+
+use crate::utils::{json_diff, secrets_scan};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// Sections that record when and by which version the config was last touched; they
+/// always differ between machines and are left out of the report
+pub const IGNORED_SECTIONS: &[&str] = &["meta", "wizard"];
+
+/// Drift within one top-level section of the config
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SectionDrift {
+    /// Top-level key, e.g. "channels"
+    pub section: String,
+    /// Only on the other machine
+    pub added: usize,
+    /// Only on this machine
+    pub removed: usize,
+    pub changed: usize,
+    /// Key-level differences; old_value is local, new_value is the other machine (secrets masked)
+    pub changes: Vec<json_diff::JsonChange>,
+}
+
+/// Structured comparison of the local config with another instance's
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct DriftReport {
+    /// File or URL compared with
+    pub source: String,
+    pub identical: bool,
+    pub total: usize,
+    pub sections: Vec<SectionDrift>,
+    /// Values the other config had redacted; they are compared as equal
+    pub redacted: usize,
+}
+
+/// Arrays of objects with unique string ids (agents.list, bindings with ids) become objects
+/// keyed by id, so drift is reported per element instead of for the whole array
+fn key_arrays_by_id(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), key_arrays_by_id(v)))
+                .collect(),
+        ),
+        Value::Array(items) => {
+            let ids: Vec<&str> = items
+                .iter()
+                .filter_map(|v| v.get("id").and_then(|id| id.as_str()))
+                .collect();
+            let unique: HashSet<&str> = ids.iter().copied().collect();
+            if !items.is_empty() && ids.len() == items.len() && unique.len() == ids.len() {
+                let keyed: Map<String, Value> = items
+                    .iter()
+                    .zip(ids)
+                    .map(|(item, id)| (format!("[{}]", id), key_arrays_by_id(item)))
+                    .collect();
+                Value::Object(keyed)
+            } else {
+                Value::Array(items.iter().map(key_arrays_by_id).collect())
+            }
+        }
+        other => other.clone(),
+    }
+}
+
+/// Count REDACTED markers in an exported config
+fn count_redacted(value: &Value) -> usize {
+    match value {
+        Value::String(s) => (s == secrets_scan::REDACTED) as usize,
+        Value::Array(items) => items.iter().map(count_redacted).sum(),
+        Value::Object(map) => map.values().map(count_redacted).sum(),
+        _ => 0,
+    }
+}
+
+/// Compare `local` with `other` (an export from another machine). Redacted values in the
+/// export are taken to match the local value since they cannot be compared.
+pub fn compare(source: &str, local: &Value, other: &Value) -> DriftReport {
+    let redacted = count_redacted(other);
+    let other = secrets_scan::restore_redacted(other, local);
+    let changes = json_diff::diff_masked(&key_arrays_by_id(local), &key_arrays_by_id(&other));
+
+    let mut by_section: BTreeMap<String, SectionDrift> = BTreeMap::new();
+    for change in changes {
+        let section = change
+            .path
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if IGNORED_SECTIONS.contains(&section.as_str()) {
+            continue;
+        }
+        let drift = by_section
+            .entry(section.clone())
+            .or_insert_with(|| SectionDrift {
+                section,
+                added: 0,
+                removed: 0,
+                changed: 0,
+                changes: Vec::new(),
+            });
+        match change.kind.as_str() {
+            "added" => drift.added += 1,
+            "removed" => drift.removed += 1,
+            _ => drift.changed += 1,
+        }
+        drift.changes.push(change);
+    }
+
+    let sections: Vec<SectionDrift> = by_section.into_values().collect();
+    let total = sections.iter().map(|s| s.changes.len()).sum();
+    DriftReport {
+        source: source.to_string(),
+        identical: total == 0,
+        total,
+        sections,
+        redacted,
+    }
+}
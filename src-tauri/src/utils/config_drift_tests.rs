@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_drift::compare;
+    use super::super::secrets_scan::REDACTED;
+    use serde_json::json;
+
+    #[test]
+    fn test_identical_configs_have_no_drift() {
+        let config = json!({"gateway": {"port": 18789}, "agents": {"list": [{"id": "main"}]}});
+        let report = compare("other.json", &config, &config);
+        assert!(report.identical);
+        assert_eq!(report.total, 0);
+        assert!(report.sections.is_empty());
+    }
+
+    #[test]
+    fn test_drift_is_grouped_by_section() {
+        let local = json!({
+            "gateway": {"port": 18789, "bind": "loopback"},
+            "channels": {"telegram": {"dmPolicy": "pairing"}},
+        });
+        let other = json!({
+            "gateway": {"port": 3000},
+            "channels": {"telegram": {"dmPolicy": "pairing"}, "discord": {"enabled": true}},
+        });
+        let report = compare("other.json", &local, &other);
+        assert_eq!(report.total, 3);
+        let sections: Vec<&str> = report.sections.iter().map(|s| s.section.as_str()).collect();
+        assert_eq!(sections, vec!["channels", "gateway"]);
+
+        let gateway = &report.sections[1];
+        assert_eq!((gateway.added, gateway.removed, gateway.changed), (0, 1, 1));
+        let port = gateway
+            .changes
+            .iter()
+            .find(|c| c.path == "gateway.port")
+            .unwrap();
+        assert_eq!(port.old_value, Some(json!(18789)));
+        assert_eq!(port.new_value, Some(json!(3000)));
+        assert_eq!(report.sections[0].changes[0].path, "channels.discord");
+    }
+
+    #[test]
+    fn test_agents_are_compared_by_id() {
+        let local = json!({"agents": {"list": [{"id": "main", "model": "a"}, {"id": "work", "model": "b"}]}});
+        let other = json!({"agents": {"list": [{"id": "work", "model": "c"}, {"id": "main", "model": "a"}]}});
+        let report = compare("other.json", &local, &other);
+        assert_eq!(report.total, 1);
+        assert_eq!(
+            report.sections[0].changes[0].path,
+            "agents.list.[work].model"
+        );
+    }
+
+    #[test]
+    fn test_redacted_values_and_meta_are_not_drift() {
+        let local = json!({
+            "channels": {"telegram": {"botToken": "123456789:AAHsecretvalue"}},
+            "meta": {"lastTouchedAt": "2026-01-01T00:00:00Z"},
+        });
+        let other = json!({
+            "channels": {"telegram": {"botToken": REDACTED}},
+            "meta": {"lastTouchedAt": "2026-02-01T00:00:00Z"},
+        });
+        let report = compare("other.json", &local, &other);
+        assert!(report.identical);
+        assert_eq!(report.redacted, 1);
+    }
+
+    #[test]
+    fn test_secret_drift_is_masked() {
+        let local = json!({"models": {"providers": {"openai": {"apiKey": "sk-local"}}}});
+        let other = json!({"models": {"providers": {"openai": {"apiKey": "sk-other"}}}});
+        let report = compare("other.json", &local, &other);
+        assert_eq!(report.total, 1);
+        let rendered = serde_json::to_string(&report).unwrap();
+        assert!(!rendered.contains("sk-local"));
+        assert!(!rendered.contains("sk-other"));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::utils::file;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Directive key used by OpenClaw core to pull another file into an object
+pub const INCLUDE_KEY: &str = "$include";
+
+/// Maximum include nesting (guards against include cycles)
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A file pulled in through an `$include` directive
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct IncludeSource {
+    /// JSON pointer of the object holding the directive ("" = root)
+    pub pointer: String,
+    /// Resolved absolute path of the included file
+    pub file: String,
+    /// Keys of that object owned by the included file (siblings in the parent win)
+    pub keys: Vec<String>,
+    /// Original directive value, restored on save
+    pub directive: Value,
+    /// File that contains the directive (the main file or another include)
+    pub declared_in: String,
+}
+
+/// Parse a JSON config file, stripping a UTF-8 BOM. Missing file = empty object.
+fn read_json(path: &Path) -> Result<Value, String> {
+    let path_str = path.to_string_lossy().to_string();
+    if !file::file_exists(&path_str) {
+        return Ok(Value::Object(Map::new()));
+    }
+    let content = file::read_file(&path_str)
+        .map_err(|e| format!("Failed to read configuration file {}: {}", path_str, e))?;
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+    serde_json::from_str(content).map_err(|e| format!("Failed to parse configuration file {}: {}", path_str, e))
+}
+
+/// Recursively merge `overlay` on top of `base` (objects merge, everything else is replaced)
+fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (k, v) in overlay_map {
+                match base_map.get_mut(&k) {
+                    Some(existing) => deep_merge(existing, v),
+                    None => {
+                        base_map.insert(k, v);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Escape a key for use as a JSON pointer token (RFC 6901)
+pub fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn resolve(
+    value: Value,
+    current_file: &str,
+    base_dir: &Path,
+    pointer: &str,
+    depth: usize,
+    sources: &mut Vec<IncludeSource>,
+) -> Result<Value, String> {
+    let mut map = match value {
+        Value::Object(map) => map,
+        other => return Ok(other),
+    };
+
+    // Resolve nested objects first so their own includes are relative to this file
+    let mut resolved = Map::new();
+    let directive = map.remove(INCLUDE_KEY);
+    for (k, v) in map {
+        let child_pointer = format!("{}/{}", pointer, escape_pointer_token(&k));
+        resolved.insert(k, resolve(v, current_file, base_dir, &child_pointer, depth, sources)?);
+    }
+
+    let directive = match directive {
+        Some(d) => d,
+        None => return Ok(Value::Object(resolved)),
+    };
+
+    if depth >= MAX_INCLUDE_DEPTH {
+        return Err(format!("Config includes nested too deeply at '{}'", pointer));
+    }
+
+    let files: Vec<String> = match &directive {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect(),
+        _ => return Err(format!("Invalid {} directive at '{}'", INCLUDE_KEY, pointer)),
+    };
+
+    let mut merged = Value::Object(Map::new());
+    for include in files {
+        let include_path: PathBuf = if Path::new(&include).is_absolute() {
+            PathBuf::from(&include)
+        } else {
+            base_dir.join(&include)
+        };
+        let include_dir = include_path.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| base_dir.to_path_buf());
+        let included = read_json(&include_path)?;
+        let include_file = include_path.to_string_lossy().to_string();
+        let included = resolve(included, &include_file, &include_dir, pointer, depth + 1, sources)?;
+
+        let keys = included
+            .as_object()
+            .map(|m| m.keys().filter(|k| !resolved.contains_key(*k)).cloned().collect())
+            .unwrap_or_default();
+        sources.push(IncludeSource {
+            pointer: pointer.to_string(),
+            file: include_file,
+            keys,
+            directive: directive.clone(),
+            declared_in: current_file.to_string(),
+        });
+        deep_merge(&mut merged, included);
+    }
+
+    // Keys written directly next to the directive override included ones
+    deep_merge(&mut merged, Value::Object(resolved));
+    Ok(merged)
+}
+
+/// Load a config file and resolve all `$include` directives.
+/// Returns the merged config and where each included key came from.
+pub fn load(path: &str) -> Result<(Value, Vec<IncludeSource>), String> {
+    let main_path = Path::new(path);
+    let base_dir = main_path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+    let raw = read_json(main_path)?;
+    let mut sources = Vec::new();
+    let merged = resolve(raw, path, &base_dir, "", 0, &mut sources)?;
+    Ok((merged, sources))
+}
+
+/// Split a merged config back into per-file documents according to `sources`.
+/// Returns `(file path, content)` pairs; the main file is always the last entry.
+pub fn split(main_path: &str, config: &Value, sources: &[IncludeSource]) -> Vec<(String, Value)> {
+    let mut remaining = config.clone();
+    let mut outputs: Vec<(String, Value)> = Vec::new();
+    // Directives declared inside an include file, restored once that file's output exists
+    let mut pending: Vec<&IncludeSource> = Vec::new();
+
+    // Deepest pointers first; at equal depth nested includes come first (load order)
+    let mut ordered: Vec<&IncludeSource> = sources.iter().collect();
+    ordered.sort_by_key(|s| std::cmp::Reverse(s.pointer.matches('/').count()));
+
+    for source in ordered {
+        let mut owned = Map::new();
+        if let Some(node) = remaining.pointer_mut(&source.pointer).and_then(|v| v.as_object_mut()) {
+            for key in &source.keys {
+                if let Some(v) = node.remove(key) {
+                    owned.insert(key.clone(), v);
+                }
+            }
+            if source.declared_in == main_path {
+                node.insert(INCLUDE_KEY.to_string(), source.directive.clone());
+            }
+        }
+        if source.declared_in != main_path {
+            pending.push(source);
+        }
+
+        let mut output = Value::Object(owned);
+        pending.retain(|nested| {
+            if nested.declared_in != source.file {
+                return true;
+            }
+            let relative = nested.pointer.strip_prefix(&source.pointer).unwrap_or("");
+            if let Some(obj) = output.pointer_mut(relative).and_then(|v| v.as_object_mut()) {
+                obj.insert(INCLUDE_KEY.to_string(), nested.directive.clone());
+            }
+            false
+        });
+        outputs.push((source.file.clone(), output));
+    }
+
+    outputs.push((main_path.to_string(), remaining));
+    outputs
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_include::{load, split};
+    use serde_json::json;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("openclaw-include-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_include_is_merged_and_routed_back() {
+        let dir = temp_dir("route");
+        let main = dir.join("openclaw.json");
+        fs::write(&main, r#"{"gateway": {"port": 18789}, "models": {"$include": "models.json", "mode": "merge"}}"#).unwrap();
+        fs::write(dir.join("models.json"), r#"{"providers": {"a": {"baseUrl": "x"}}}"#).unwrap();
+
+        let (config, sources) = load(main.to_str().unwrap()).unwrap();
+        assert_eq!(config.pointer("/models/providers/a/baseUrl"), Some(&json!("x")));
+        assert_eq!(config.pointer("/models/mode"), Some(&json!("merge")));
+        assert!(config.pointer("/models/$include").is_none());
+
+        let mut updated = config.clone();
+        updated["models"]["providers"]["b"] = json!({"baseUrl": "y"});
+        updated["gateway"]["port"] = json!(18790);
+
+        let outputs = split(main.to_str().unwrap(), &updated, &sources);
+        let (models_path, models) = &outputs[0];
+        assert!(models_path.ends_with("models.json"));
+        assert_eq!(models.pointer("/providers/b/baseUrl"), Some(&json!("y")));
+
+        let (_, main_doc) = outputs.last().unwrap();
+        assert_eq!(main_doc.pointer("/models/$include"), Some(&json!("models.json")));
+        assert!(main_doc.pointer("/models/providers").is_none());
+        assert_eq!(main_doc.pointer("/gateway/port"), Some(&json!(18790)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_config_without_includes_is_single_file() {
+        let dir = temp_dir("plain");
+        let main = dir.join("openclaw.json");
+        fs::write(&main, r#"{"a": 1}"#).unwrap();
+
+        let (config, sources) = load(main.to_str().unwrap()).unwrap();
+        assert!(sources.is_empty());
+        let outputs = split(main.to_str().unwrap(), &config, &sources);
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].1, json!({"a": 1}));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,297 @@
+//! Check openclaw.json against the JSON Schema printed by `openclaw config schema` before the
+//! Manager writes it. Only the keywords the core's schema uses are checked (types, enums,
+//! required and unknown keys, ranges, patterns, unions and local `$ref`s); anything else is
+//! ignored, so the check can be more lenient than the core but never stricter.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Nesting depth at which validation stops (guards against `$ref` cycles)
+const MAX_DEPTH: usize = 64;
+
+/// Errors listed in a save error; the rest are counted
+const MAX_LISTED_ERRORS: usize = 10;
+
+/// One schema violation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaError {
+    /// JSON pointer of the offending value ("" for the root)
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Every violation of `schema` in `value`
+pub fn validate(schema: &Value, value: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    check(schema, schema, value, "", 0, &mut errors);
+    errors
+}
+
+/// Violations in `after` that `before` (the config as it was) did not have: a save is only
+/// refused for problems it introduces
+pub fn new_errors(before: &[SchemaError], after: Vec<SchemaError>) -> Vec<SchemaError> {
+    after.into_iter().filter(|e| !before.contains(e)).collect()
+}
+
+/// Errors as "pointer: message" lines for a command error
+pub fn describe(errors: &[SchemaError]) -> String {
+    let mut lines: Vec<String> = errors
+        .iter()
+        .take(MAX_LISTED_ERRORS)
+        .map(|e| {
+            let pointer = if e.pointer.is_empty() {
+                "/"
+            } else {
+                &e.pointer
+            };
+            format!("{}: {}", pointer, e.message)
+        })
+        .collect();
+    if errors.len() > MAX_LISTED_ERRORS {
+        lines.push(format!("... and {} more", errors.len() - MAX_LISTED_ERRORS));
+    }
+    lines.join("\n")
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false)
+            }
+            _ => false,
+        },
+        other => type_name(value) == other,
+    }
+}
+
+fn check(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    pointer: &str,
+    depth: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let mut error = |message: String| {
+        errors.push(SchemaError {
+            pointer: pointer.to_string(),
+            message,
+        })
+    };
+    let schema = match schema {
+        Value::Bool(false) => return error("is not allowed here".to_string()),
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|r| root.pointer(r))
+    {
+        check(root, target, value, pointer, depth + 1, errors);
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(list) => list.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(t, value)) {
+            return error(format!(
+                "expected {}, found {}",
+                types.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            let list: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            error(format!("must be one of {}", list.join(", ")));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            error(format!("must be {}", expected));
+        }
+    }
+
+    match value {
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64()) {
+                if len < min {
+                    error(format!("must be at least {} characters", min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+                if len > max {
+                    error(format!("must be at most {} characters", max));
+                }
+            }
+            // Patterns are ECMAScript regexes; ones the regex crate can't compile are skipped
+            if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+                if let Ok(re) = regex::Regex::new(pattern) {
+                    if !re.is_match(s) {
+                        error(format!("must match the pattern {}", pattern));
+                    }
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            let bound = |key: &str| schema.get(key).and_then(|v| v.as_f64());
+            if let Some(min) = bound("minimum").filter(|min| n < *min) {
+                error(format!("must be at least {}", min));
+            }
+            if let Some(max) = bound("maximum").filter(|max| n > *max) {
+                error(format!("must be at most {}", max));
+            }
+            if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+                error(format!("must be greater than {}", min));
+            }
+            if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+                error(format!("must be less than {}", max));
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(|v| v.as_u64()) {
+                if (items.len() as u64) < min {
+                    error(format!("must have at least {} items", min));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(|v| v.as_u64()) {
+                if items.len() as u64 > max {
+                    error(format!("must have at most {} items", max));
+                }
+            }
+            match schema.get("items") {
+                Some(Value::Array(tuple)) => {
+                    for (i, (item, item_schema)) in items.iter().zip(tuple).enumerate() {
+                        let item_pointer = format!("{}/{}", pointer, i);
+                        check(root, item_schema, item, &item_pointer, depth + 1, errors);
+                    }
+                }
+                Some(item_schema) => {
+                    for (i, item) in items.iter().enumerate() {
+                        let item_pointer = format!("{}/{}", pointer, i);
+                        check(root, item_schema, item, &item_pointer, depth + 1, errors);
+                    }
+                }
+                None => {}
+            }
+        }
+        Value::Object(object) => {
+            for key in schema
+                .get("required")
+                .and_then(|r| r.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|k| k.as_str())
+            {
+                if !object.contains_key(key) {
+                    error(format!("missing required key \"{}\"", key));
+                }
+            }
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let patterns: Vec<(Option<regex::Regex>, &Value)> = schema
+                .get("patternProperties")
+                .and_then(|p| p.as_object())
+                .into_iter()
+                .flatten()
+                .map(|(pattern, s)| (regex::Regex::new(pattern).ok(), s))
+                .collect();
+            // A pattern the regex crate can't compile (e.g. lookarounds) may match any key
+            let unreadable_pattern = patterns.iter().any(|(re, _)| re.is_none());
+            for (key, item) in object {
+                let item_pointer = format!("{}/{}", pointer, escape(key));
+                let mut known = unreadable_pattern;
+                if let Some(property) = properties.and_then(|p| p.get(key)) {
+                    known = true;
+                    check(root, property, item, &item_pointer, depth + 1, errors);
+                }
+                for (re, pattern_schema) in &patterns {
+                    if re.as_ref().is_some_and(|re| re.is_match(key)) {
+                        known = true;
+                        check(root, pattern_schema, item, &item_pointer, depth + 1, errors);
+                    }
+                }
+                if known {
+                    continue;
+                }
+                match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => errors.push(SchemaError {
+                        pointer: item_pointer,
+                        message: format!("unknown key \"{}\"", key),
+                    }),
+                    Some(additional @ Value::Object(_)) => {
+                        check(root, additional, item, &item_pointer, depth + 1, errors)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    for sub in schema
+        .get("allOf")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+    {
+        check(root, sub, value, pointer, depth + 1, errors);
+    }
+    // oneOf is checked like anyOf: a value matching several branches is accepted
+    for key in ["anyOf", "oneOf"] {
+        let Some(branches) = schema.get(key).and_then(|b| b.as_array()) else {
+            continue;
+        };
+        let mut closest: Option<Vec<SchemaError>> = None;
+        for branch in branches {
+            let mut branch_errors = Vec::new();
+            check(root, branch, value, pointer, depth + 1, &mut branch_errors);
+            if branch_errors.is_empty() {
+                closest = None;
+                break;
+            }
+            if closest
+                .as_ref()
+                .map(|c| branch_errors.len() < c.len())
+                .unwrap_or(true)
+            {
+                closest = Some(branch_errors);
+            }
+        }
+        // Report the branch that came closest, it is usually the intended one
+        if let Some(closest) = closest {
+            errors.extend(closest);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_schema::{describe, new_errors, validate, SchemaError};
+    use serde_json::json;
+
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "gateway": {
+                    "type": "object",
+                    "properties": {
+                        "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                        "bind": { "enum": ["loopback", "lan"] }
+                    }
+                },
+                "channels": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/channel" }
+                },
+                "agents": {
+                    "type": "object",
+                    "properties": {
+                        "list": { "type": "array", "items": { "type": "object", "required": ["id"] } }
+                    }
+                },
+                "meta": {}
+            },
+            "definitions": {
+                "channel": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": { "type": "boolean" },
+                        "dmPolicy": { "anyOf": [{ "const": "open" }, { "const": "pairing" }] },
+                        "botToken": { "type": "string", "minLength": 1 }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Pointers in sorted order (object key order depends on serde_json features)
+    fn pointers(errors: &[SchemaError]) -> Vec<&str> {
+        let mut pointers: Vec<&str> = errors.iter().map(|e| e.pointer.as_str()).collect();
+        pointers.sort();
+        pointers
+    }
+
+    fn message<'a>(errors: &'a [SchemaError], pointer: &str) -> &'a str {
+        errors
+            .iter()
+            .find(|e| e.pointer == pointer)
+            .map(|e| e.message.as_str())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let config = json!({
+            "gateway": { "port": 18789, "bind": "loopback" },
+            "channels": { "telegram": { "enabled": true, "dmPolicy": "pairing", "botToken": "x" } },
+            "agents": { "list": [{ "id": "main" }] },
+            "meta": { "lastTouchedAt": "2026-01-01T00:00:00Z" }
+        });
+        assert!(validate(&schema(), &config).is_empty());
+    }
+
+    #[test]
+    fn test_errors_carry_json_pointers() {
+        let config = json!({
+            "gateway": { "port": "18789", "bind": "public" },
+            "channels": { "tele/gram": { "enabled": "yes", "dmPolicy": "everyone", "botToken": "" } },
+            "agents": { "list": [{ "name": "no id" }] },
+            "allowlist": []
+        });
+        let errors = validate(&schema(), &config);
+        assert_eq!(
+            pointers(&errors),
+            vec![
+                "/agents/list/0",
+                "/allowlist",
+                "/channels/tele~1gram/botToken",
+                "/channels/tele~1gram/dmPolicy",
+                "/channels/tele~1gram/enabled",
+                "/gateway/bind",
+                "/gateway/port",
+            ]
+        );
+        assert_eq!(
+            message(&errors, "/agents/list/0"),
+            "missing required key \"id\""
+        );
+        assert_eq!(message(&errors, "/allowlist"), "unknown key \"allowlist\"");
+        assert_eq!(
+            message(&errors, "/gateway/port"),
+            "expected integer, found string"
+        );
+        assert_eq!(
+            message(&errors, "/channels/tele~1gram/dmPolicy"),
+            "must be \"open\""
+        );
+    }
+
+    #[test]
+    fn test_numeric_ranges() {
+        let errors = validate(&schema(), &json!({ "gateway": { "port": 70000 } }));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "must be at most 65535");
+        // 8080.0 is still an integer
+        assert!(validate(&schema(), &json!({ "gateway": { "port": 8080.0 } })).is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_pattern_accepts_every_key() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "patternProperties": {
+                "^(?!internal)[a-z]+$": { "type": "string" },
+                "^x-[a-z]+$": { "type": "string" }
+            }
+        });
+        // The lookahead doesn't compile, so no key can be called unknown; readable patterns still apply
+        let errors = validate(&schema, &json!({ "name": "a", "x-tag": 1, "Other": true }));
+        assert_eq!(pointers(&errors), vec!["/x-tag"]);
+    }
+
+    #[test]
+    fn test_only_new_errors_block_a_save() {
+        let before = validate(&schema(), &json!({ "allowlist": [] }));
+        let after = validate(
+            &schema(),
+            &json!({ "allowlist": [], "gateway": { "bind": "public" } }),
+        );
+        let introduced = new_errors(&before, after);
+        assert_eq!(pointers(&introduced), vec!["/gateway/bind"]);
+        assert!(describe(&introduced).starts_with("/gateway/bind: must be one of"));
+    }
+
+    #[test]
+    fn test_describe_caps_the_list() {
+        let errors: Vec<SchemaError> = (0..12)
+            .map(|i| SchemaError {
+                pointer: format!("/k{}", i),
+                message: "unknown key".to_string(),
+            })
+            .collect();
+        let text = describe(&errors);
+        assert_eq!(text.lines().count(), 11);
+        assert!(text.ends_with("... and 2 more"));
+    }
+}
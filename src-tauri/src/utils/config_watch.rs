@@ -0,0 +1,86 @@
+//! Files of the config directory reported when they change on disk, and the content last seen
+//! in each, so the Manager's own saves are not reported as external edits.
+
+use crate::utils::platform;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Watched file name -> scope of the `config-changed` event
+pub const WATCHED_FILES: &[(&str, &str)] = &[
+    ("openclaw.json", "openclaw"),
+    ("env", "env"),
+    ("mcps.json", "mcps"),
+];
+
+/// Scope of a watched file (temp files of atomic writes and `.bak` copies are not watched)
+pub fn scope_for(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_str()?;
+    WATCHED_FILES
+        .iter()
+        .find(|(file, _)| *file == name)
+        .map(|(_, scope)| *scope)
+}
+
+/// Hash of a file's content; None for a missing file
+pub fn content_hash(content: Option<&[u8]>) -> Option<u64> {
+    content.map(|bytes| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    })
+}
+
+/// Content hash last seen per scope
+#[derive(Debug, Default)]
+pub struct KnownContent {
+    hashes: HashMap<&'static str, Option<u64>>,
+}
+
+impl KnownContent {
+    /// Remember `hash` for `scope`; true when it differs from what was known (an unknown
+    /// scope counts as changed)
+    pub fn record(&mut self, scope: &'static str, hash: Option<u64>) -> bool {
+        self.hashes.insert(scope, hash) != Some(hash)
+    }
+}
+
+fn known() -> &'static Mutex<KnownContent> {
+    static KNOWN: OnceLock<Mutex<KnownContent>> = OnceLock::new();
+    KNOWN.get_or_init(Default::default)
+}
+
+/// Note content the Manager wrote to `path`, so the watcher skips the change
+pub fn remember_write(path: &str, content: &[u8]) {
+    let path = Path::new(path);
+    let Some(scope) = scope_for(path) else {
+        return;
+    };
+    if path.parent() != Some(Path::new(&platform::get_config_dir())) {
+        return;
+    }
+    if let Ok(mut known) = known().lock() {
+        known.record(scope, content_hash(Some(content)));
+    }
+}
+
+/// Read `path` and remember its content; true when it differs from the content last seen
+/// (written by the Manager or read by the watcher)
+pub fn changed_on_disk(path: &Path) -> bool {
+    let Some(scope) = scope_for(path) else {
+        return false;
+    };
+    let hash = content_hash(std::fs::read(path).ok().as_deref());
+    known()
+        .lock()
+        .map(|mut known| known.record(scope, hash))
+        .unwrap_or(true)
+}
+
+/// Remember the current content of every watched file in `dir` (when watching starts)
+pub fn seed(dir: &Path) {
+    for (file, _) in WATCHED_FILES {
+        changed_on_disk(&dir.join(file));
+    }
+}
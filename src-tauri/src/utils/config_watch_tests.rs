@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_watch::{content_hash, scope_for, KnownContent};
+    use std::path::Path;
+
+    #[test]
+    fn test_scope_for() {
+        assert_eq!(
+            scope_for(Path::new("/home/me/.openclaw/openclaw.json")),
+            Some("openclaw")
+        );
+        assert_eq!(scope_for(Path::new("/home/me/.openclaw/env")), Some("env"));
+        assert_eq!(
+            scope_for(Path::new("/home/me/.openclaw/mcps.json")),
+            Some("mcps")
+        );
+        assert_eq!(
+            scope_for(Path::new("/home/me/.openclaw/openclaw.json.bak")),
+            None
+        );
+        assert_eq!(
+            scope_for(Path::new("/home/me/.openclaw/openclaw.json.4242.tmp")),
+            None
+        );
+        assert_eq!(scope_for(Path::new("/home/me/.openclaw/agents")), None);
+    }
+
+    #[test]
+    fn test_known_content_reports_only_changes() {
+        let mut known = KnownContent::default();
+        let first = content_hash(Some(br#"{"a": 1}"#));
+        // Nothing known yet
+        assert!(known.record("openclaw", first));
+        // Same content again, e.g. the Manager's own write or a touch
+        assert!(!known.record("openclaw", first));
+        assert!(known.record("openclaw", content_hash(Some(br#"{"a": 2}"#))));
+        // Deleted, then deleted again
+        assert!(known.record("openclaw", content_hash(None)));
+        assert!(!known.record("openclaw", None));
+        // Scopes are tracked separately
+        assert!(known.record("env", first));
+    }
+}
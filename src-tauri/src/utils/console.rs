@@ -0,0 +1,145 @@
+//! Embedded console for arbitrary `openclaw` commands: argument checks, the audit log entry
+//! every run leaves (with secrets masked) and decoding of the output as it streams in.
+
+use crate::utils::json_diff;
+use serde::{Deserialize, Serialize};
+
+/// manager.json key of the advanced mode switch the console is gated behind
+pub const ADVANCED_MODE_KEY: &str = "advancedMode";
+
+/// Console commands that may run at the same time
+pub const MAX_SESSIONS: usize = 4;
+
+/// Largest stdin write accepted at once
+pub const MAX_INPUT_BYTES: usize = 64 * 1024;
+
+const MASK: &str = "***";
+
+/// One line of manager/audit.jsonl
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// console-run, console-exit or advanced-mode
+    pub action: String,
+    /// OS user running the Manager
+    pub user: Option<String>,
+    /// Command line (secrets masked) or setting value
+    pub detail: String,
+    /// Exit code, "cancelled" or "killed" for console-exit
+    pub outcome: Option<String>,
+}
+
+/// Arguments as typed in the console: trimmed, empty ones dropped and a leading `openclaw`
+/// removed (the binary is always openclaw, there is no shell in between)
+pub fn normalize_args(args: Vec<String>) -> Result<Vec<String>, String> {
+    let mut args: Vec<String> = args
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if args.first().map(|a| a == "openclaw").unwrap_or(false) {
+        args.remove(0);
+    }
+    if args.is_empty() {
+        return Err("Enter an openclaw command, e.g. status".to_string());
+    }
+    if args.iter().any(|a| a.contains('\0')) {
+        return Err("Arguments cannot contain NUL characters".to_string());
+    }
+    Ok(args)
+}
+
+/// `--api-key`, `--gateway-token`, ... (dashes are dropped so kebab-case names match too)
+fn is_secret_flag(flag: &str) -> bool {
+    flag.starts_with("--") && json_diff::is_secret_key(&flag.replace('-', ""))
+}
+
+/// Arguments with secret values masked: values of secret-looking flags (`--token x`,
+/// `--api-key=x`) and the value of `config set <path> <value>` for a secret path
+pub fn redact_args(args: &[String]) -> Vec<String> {
+    let secret_config_set = args.len() >= 4
+        && args[0] == "config"
+        && args[1] == "set"
+        && args[2]
+            .rsplit('.')
+            .next()
+            .map(json_diff::is_secret_key)
+            .unwrap_or(false);
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut mask_next = false;
+    for (i, arg) in args.iter().enumerate() {
+        if mask_next || (secret_config_set && i == 3) {
+            mask_next = false;
+            redacted.push(MASK.to_string());
+            continue;
+        }
+        match arg.split_once('=') {
+            Some((flag, _)) if is_secret_flag(flag) => redacted.push(format!("{}={}", flag, MASK)),
+            _ => {
+                mask_next = is_secret_flag(arg);
+                redacted.push(arg.clone());
+            }
+        }
+    }
+    redacted
+}
+
+/// `openclaw ...` as it would be typed, secrets masked, for the audit log
+pub fn command_line(args: &[String]) -> String {
+    let mut line = String::from("openclaw");
+    for arg in redact_args(args) {
+        line.push(' ');
+        if arg.contains(char::is_whitespace) || arg.contains('"') {
+            line.push_str(&format!("\"{}\"", arg.replace('"', "\\\"")));
+        } else {
+            line.push_str(&arg);
+        }
+    }
+    line
+}
+
+/// Decodes output read in arbitrary chunks; a multi-byte character split between two reads
+/// is held back until the rest of it arrives
+#[derive(Debug, Default)]
+pub struct Utf8Stream {
+    pending: Vec<u8>,
+}
+
+impl Utf8Stream {
+    /// Text decoded so far from `bytes` and what was held back; invalid bytes become U+FFFD
+    pub fn push(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut text = String::new();
+        loop {
+            match std::str::from_utf8(&self.pending) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    self.pending.clear();
+                    return text;
+                }
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    text.push_str(&String::from_utf8_lossy(&self.pending[..valid]));
+                    match e.error_len() {
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            self.pending.drain(..valid + len);
+                        }
+                        None => {
+                            self.pending.drain(..valid);
+                            return text;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whatever is still held back once the stream ended
+    pub fn finish(&mut self) -> String {
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending.clear();
+        text
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use super::super::console::{command_line, normalize_args, redact_args, Utf8Stream};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_args_strips_binary_and_blanks() {
+        assert_eq!(
+            normalize_args(args(&["openclaw", " status ", "", "--deep"])).unwrap(),
+            args(&["status", "--deep"])
+        );
+        assert!(normalize_args(args(&["openclaw"])).is_err());
+        assert!(normalize_args(args(&["  "])).is_err());
+        assert!(normalize_args(args(&["status", "a\0b"])).is_err());
+    }
+
+    #[test]
+    fn test_redact_args_masks_secret_flags() {
+        assert_eq!(
+            redact_args(&args(&["gateway", "--token", "abc", "--port", "18789"])),
+            args(&["gateway", "--token", "***", "--port", "18789"])
+        );
+        assert_eq!(
+            redact_args(&args(&["onboard", "--api-key=sk-123", "--mode=local"])),
+            args(&["onboard", "--api-key=***", "--mode=local"])
+        );
+    }
+
+    #[test]
+    fn test_redact_args_masks_secret_config_values() {
+        assert_eq!(
+            redact_args(&args(&[
+                "config",
+                "set",
+                "models.providers.x.apiKey",
+                "sk-123"
+            ])),
+            args(&["config", "set", "models.providers.x.apiKey", "***"])
+        );
+        assert_eq!(
+            redact_args(&args(&["config", "set", "gateway.port", "18790"])),
+            args(&["config", "set", "gateway.port", "18790"])
+        );
+    }
+
+    #[test]
+    fn test_command_line_quotes_and_masks() {
+        assert_eq!(
+            command_line(&args(&["message", "send", "hello world", "--token", "t"])),
+            "openclaw message send \"hello world\" --token ***"
+        );
+    }
+
+    #[test]
+    fn test_utf8_stream_holds_back_split_characters() {
+        let bytes = "héllo ✓".as_bytes();
+        let mut stream = Utf8Stream::default();
+        let mut text = String::new();
+        for chunk in bytes.chunks(2) {
+            text.push_str(&stream.push(chunk));
+        }
+        text.push_str(&stream.finish());
+        assert_eq!(text, "héllo ✓");
+    }
+
+    #[test]
+    fn test_utf8_stream_replaces_invalid_bytes() {
+        let mut stream = Utf8Stream::default();
+        assert_eq!(stream.push(b"a\xffb"), "a\u{FFFD}b");
+        assert_eq!(stream.push(b"\xe2\x9c"), "");
+        assert_eq!(stream.finish(), "\u{FFFD}");
+    }
+}
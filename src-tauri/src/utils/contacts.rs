@@ -0,0 +1,264 @@
+//! Address book mapping friendly names to channel peer ids (Telegram user ids, Slack channels,
+//! phone numbers...). Names can be used wherever a peer id is typed: test message targets,
+//! allowFrom lists and binding peers. Suggestions come from the gateway's session stores and
+//! pending pairing requests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// manager.json key holding the address book
+pub const SETTINGS_KEY: &str = "contacts";
+
+/// Peer kinds a contact can have (the binding peer kinds, with "dm" folded into "direct")
+pub const CONTACT_KINDS: &[&str] = &["direct", "group", "channel"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    /// "<channel>:<peerId>"
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub channel: String,
+    pub peer_id: String,
+    /// "direct", "group" or "channel"
+    pub kind: String,
+    #[serde(default)]
+    pub account_id: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// "manual", "session" or "pairing"
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// Last gateway activity seen from this peer (RFC3339)
+    #[serde(default)]
+    pub last_seen_at: Option<String>,
+}
+
+fn default_source() -> String {
+    "manual".to_string()
+}
+
+pub fn contact_id(channel: &str, peer_id: &str) -> String {
+    format!("{}:{}", channel, peer_id)
+}
+
+/// Trimmed and checked copy of a contact, with its id derived from channel and peer
+pub fn normalize(contact: Contact) -> Result<Contact, String> {
+    let name = contact.name.trim().to_string();
+    let channel = contact.channel.trim().to_lowercase();
+    let peer_id = contact.peer_id.trim().to_string();
+    let kind = match contact.kind.trim() {
+        "dm" | "" => "direct".to_string(),
+        kind => kind.to_string(),
+    };
+    if name.is_empty() {
+        return Err("Contact needs a name".to_string());
+    }
+    if channel.is_empty() {
+        return Err("Contact needs a channel".to_string());
+    }
+    if peer_id.is_empty() || peer_id.contains(char::is_whitespace) {
+        return Err(format!("Invalid peer id '{}'", peer_id));
+    }
+    if !CONTACT_KINDS.contains(&kind.as_str()) {
+        return Err(format!(
+            "Invalid contact kind '{}', expected one of: {}",
+            kind,
+            CONTACT_KINDS.join(", ")
+        ));
+    }
+    let trimmed = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    Ok(Contact {
+        id: contact_id(&channel, &peer_id),
+        name,
+        channel,
+        peer_id,
+        kind,
+        account_id: trimmed(contact.account_id),
+        notes: trimmed(contact.notes),
+        source: contact.source,
+        last_seen_at: contact.last_seen_at,
+    })
+}
+
+/// Another contact on the same channel already using `contact`'s name
+pub fn name_conflict<'a>(contacts: &'a [Contact], contact: &Contact) -> Option<&'a Contact> {
+    contacts.iter().find(|c| {
+        c.id != contact.id
+            && c.channel == contact.channel
+            && c.name.eq_ignore_ascii_case(&contact.name)
+    })
+}
+
+/// Peer id for `text` on `channel`: the contact's peer when `text` is a contact name there,
+/// otherwise `text` itself (it already is a peer id)
+pub fn resolve_peer(contacts: &[Contact], channel: &str, text: &str) -> String {
+    let text = text.trim();
+    let on_channel = |c: &&Contact| c.channel.eq_ignore_ascii_case(channel);
+    if contacts
+        .iter()
+        .filter(on_channel)
+        .any(|c| c.peer_id == text)
+    {
+        return text.to_string();
+    }
+    contacts
+        .iter()
+        .filter(on_channel)
+        .find(|c| c.name.eq_ignore_ascii_case(text))
+        .map(|c| c.peer_id.clone())
+        .unwrap_or_else(|| text.to_string())
+}
+
+/// `resolve_peer` over the strings of an allowFrom-style list; numbers and "*" are kept as is.
+/// Telegram ids resolved from a name are written as numbers, like the core schema expects.
+pub fn resolve_list(contacts: &[Contact], channel: &str, values: &Value) -> Value {
+    match values {
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item.as_str() {
+                    Some(text) if text != "*" => {
+                        let peer = resolve_peer(contacts, channel, text);
+                        match peer.parse::<i64>() {
+                            Ok(n) if channel == "telegram" && peer != text => Value::from(n),
+                            _ => Value::String(peer),
+                        }
+                    }
+                    _ => item.clone(),
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Channel, peer kind and peer id of a per-peer session key
+/// ("agent:<agentId>:<channel>:<kind>:<peerId>", optionally followed by ":topic:<id>" or
+/// ":thread:<id>"); None for main and other shared sessions
+pub fn parse_session_key(key: &str) -> Option<(String, String, String)> {
+    let mut parts = key.splitn(5, ':');
+    if parts.next()? != "agent" {
+        return None;
+    }
+    let _agent = parts.next()?;
+    let channel = parts.next()?;
+    let kind = match parts.next()? {
+        "dm" | "direct" => "direct",
+        "group" => "group",
+        "channel" => "channel",
+        _ => return None,
+    };
+    let rest = parts.next()?;
+    let peer = [":topic:", ":thread:"]
+        .iter()
+        .filter_map(|marker| rest.find(marker))
+        .min()
+        .map(|end| &rest[..end])
+        .unwrap_or(rest);
+    if channel.is_empty() || peer.is_empty() {
+        return None;
+    }
+    Some((channel.to_string(), kind.to_string(), peer.to_string()))
+}
+
+fn millis_to_rfc3339(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|d| d.to_rfc3339())
+}
+
+/// Contacts for the peers of an agent's session store (sessions.json: session key -> entry)
+pub fn from_session_store(store: &Value) -> Vec<Contact> {
+    let Some(entries) = store.as_object() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(key, entry)| {
+            let (channel, kind, peer_id) = parse_session_key(key)?;
+            let name = ["/displayName", "/origin/label", "/subject"]
+                .iter()
+                .find_map(|p| entry.pointer(p).and_then(|v| v.as_str()))
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(&peer_id)
+                .to_string();
+            Some(Contact {
+                id: contact_id(&channel, &peer_id),
+                name,
+                account_id: entry
+                    .pointer("/deliveryContext/accountId")
+                    .or_else(|| entry.get("lastAccountId"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                channel,
+                peer_id,
+                kind,
+                notes: None,
+                source: "session".to_string(),
+                last_seen_at: millis_to_rfc3339(entry.get("updatedAt")),
+            })
+        })
+        .collect()
+}
+
+/// Display name from a pairing request's channel metadata
+pub fn pairing_name(meta: Option<&Value>) -> Option<String> {
+    let meta = meta?;
+    let text = |key: &str| {
+        meta.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let full = [text("firstName"), text("lastName")]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    text("name")
+        .or_else(|| text("displayName"))
+        .map(String::from)
+        .or_else(|| (!full.is_empty()).then_some(full))
+        .or_else(|| text("username").map(|u| format!("@{}", u.trim_start_matches('@'))))
+}
+
+/// Suggestions not yet in the address book, one per peer (the most recent sighting, keeping a
+/// real name over a bare id), most recently seen first
+pub fn suggestions(book: &[Contact], candidates: Vec<Contact>) -> Vec<Contact> {
+    let mut by_id: HashMap<String, Contact> = HashMap::new();
+    for candidate in candidates {
+        if book.iter().any(|c| c.id == candidate.id) {
+            continue;
+        }
+        match by_id.get_mut(&candidate.id) {
+            Some(existing) => {
+                let named = |c: &Contact| c.name != c.peer_id;
+                let newer = candidate.last_seen_at > existing.last_seen_at;
+                let name = if named(existing) && (!named(&candidate) || !newer) {
+                    existing.name.clone()
+                } else {
+                    candidate.name.clone()
+                };
+                if newer {
+                    *existing = candidate;
+                }
+                existing.name = name;
+            }
+            None => {
+                by_id.insert(candidate.id.clone(), candidate);
+            }
+        }
+    }
+    let mut list: Vec<Contact> = by_id.into_values().collect();
+    list.sort_by(|a, b| {
+        b.last_seen_at
+            .cmp(&a.last_seen_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    list
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contacts::{
+        from_session_store, normalize, pairing_name, parse_session_key, resolve_list, resolve_peer,
+        suggestions, Contact,
+    };
+    use serde_json::json;
+
+    fn contact(channel: &str, peer_id: &str, name: &str) -> Contact {
+        Contact {
+            id: format!("{}:{}", channel, peer_id),
+            name: name.to_string(),
+            channel: channel.to_string(),
+            peer_id: peer_id.to_string(),
+            kind: "direct".to_string(),
+            account_id: None,
+            notes: None,
+            source: "manual".to_string(),
+            last_seen_at: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut raw = contact(" Telegram ", " 12345 ", " Alice ");
+        raw.kind = "dm".to_string();
+        raw.notes = Some("  ".to_string());
+        let c = normalize(raw).unwrap();
+        assert_eq!(c.id, "telegram:12345");
+        assert_eq!(c.name, "Alice");
+        assert_eq!(c.kind, "direct");
+        assert_eq!(c.notes, None);
+
+        assert!(normalize(contact("telegram", "12 345", "Alice")).is_err());
+        assert!(normalize(contact("telegram", "12345", " ")).is_err());
+        let mut bad_kind = contact("telegram", "12345", "Alice");
+        bad_kind.kind = "topic".to_string();
+        assert!(normalize(bad_kind).is_err());
+    }
+
+    #[test]
+    fn test_resolve_peer_and_list() {
+        let book = vec![
+            contact("telegram", "12345", "Alice"),
+            contact("slack", "C0123", "ops"),
+            // A name that is another contact's peer id stays that peer
+            contact("telegram", "67890", "12345"),
+        ];
+        assert_eq!(resolve_peer(&book, "telegram", " alice "), "12345");
+        assert_eq!(resolve_peer(&book, "telegram", "12345"), "12345");
+        assert_eq!(resolve_peer(&book, "telegram", "ops"), "ops");
+        assert_eq!(resolve_peer(&book, "slack", "Ops"), "C0123");
+
+        assert_eq!(
+            resolve_list(&book, "telegram", &json!(["Alice", 999, "*", "@bob"])),
+            json!([12345, 999, "*", "@bob"])
+        );
+        assert_eq!(
+            resolve_list(&book, "slack", &json!(["ops"])),
+            json!(["C0123"])
+        );
+        assert_eq!(resolve_list(&book, "slack", &json!("ops")), json!("ops"));
+    }
+
+    #[test]
+    fn test_parse_session_key() {
+        assert_eq!(
+            parse_session_key("agent:main:telegram:dm:12345"),
+            Some(("telegram".into(), "direct".into(), "12345".into()))
+        );
+        assert_eq!(
+            parse_session_key("agent:ops:telegram:group:-100123:topic:7"),
+            Some(("telegram".into(), "group".into(), "-100123".into()))
+        );
+        assert_eq!(
+            parse_session_key("agent:main:whatsapp:direct:+15551234567"),
+            Some(("whatsapp".into(), "direct".into(), "+15551234567".into()))
+        );
+        assert_eq!(parse_session_key("agent:main:main"), None);
+        assert_eq!(parse_session_key("agent:main:cron:job:nightly"), None);
+        assert_eq!(parse_session_key("global"), None);
+    }
+
+    #[test]
+    fn test_from_session_store() {
+        let store = json!({
+            "agent:main:main": {"sessionId": "a"},
+            "agent:main:telegram:direct:12345": {
+                "displayName": "Alice Smith",
+                "updatedAt": 1_791_000_000_000i64,
+                "deliveryContext": {"channel": "telegram", "to": "12345", "accountId": "bot2"}
+            },
+            "agent:main:slack:channel:C0123": {"origin": {"label": "#ops"}}
+        });
+        let mut found = from_session_store(&store);
+        found.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].id, "slack:C0123");
+        assert_eq!(found[0].name, "#ops");
+        assert_eq!(found[0].kind, "channel");
+        assert_eq!(found[1].name, "Alice Smith");
+        assert_eq!(found[1].account_id.as_deref(), Some("bot2"));
+        assert!(found[1].last_seen_at.is_some());
+        assert_eq!(found[1].source, "session");
+    }
+
+    #[test]
+    fn test_pairing_name() {
+        assert_eq!(
+            pairing_name(Some(
+                &json!({"firstName": "Ann", "lastName": "Lee", "username": "ann"})
+            )),
+            Some("Ann Lee".to_string())
+        );
+        assert_eq!(
+            pairing_name(Some(&json!({"username": "ann"}))),
+            Some("@ann".to_string())
+        );
+        assert_eq!(pairing_name(Some(&json!({}))), None);
+        assert_eq!(pairing_name(None), None);
+    }
+
+    #[test]
+    fn test_suggestions_skip_known_and_merge_sightings() {
+        let book = vec![contact("telegram", "12345", "Alice")];
+        let seen = |peer: &str, name: &str, at: &str| Contact {
+            source: "session".to_string(),
+            last_seen_at: Some(at.to_string()),
+            ..contact("telegram", peer, name)
+        };
+        let list = suggestions(
+            &book,
+            vec![
+                seen("12345", "Alice", "2026-10-10T00:00:00Z"),
+                seen("222", "Bob", "2026-10-01T00:00:00Z"),
+                // A newer sighting without a name keeps the name and takes the time
+                seen("222", "222", "2026-10-12T00:00:00Z"),
+                seen("333", "333", "2026-10-05T00:00:00Z"),
+            ],
+        );
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].peer_id, "222");
+        assert_eq!(list[0].name, "Bob");
+        assert_eq!(
+            list[0].last_seen_at.as_deref(),
+            Some("2026-10-12T00:00:00Z")
+        );
+        assert_eq!(list[1].peer_id, "333");
+    }
+}
@@ -0,0 +1,185 @@
+//! Crash reports of the Manager itself: what a panic leaves behind (message, location,
+//! backtrace, recent log lines), and a report for a session that ended without a clean exit
+//! and without a panic (killed, or a native crash). Reports are only sent when the user agrees.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Reports kept; older ones are pruned
+pub const MAX_REPORTS: usize = 20;
+
+/// Backtrace lines kept in a report
+pub const MAX_BACKTRACE_LINES: usize = 256;
+
+/// Recent log lines kept in memory for the next report
+pub const LOG_TAIL_LINES: usize = 100;
+
+/// One report (manager/crash-reports/<id>.json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    /// RFC3339
+    pub created_at: String,
+    pub manager_version: String,
+    pub os: String,
+    pub arch: String,
+    /// "panic", or "unclean-exit" when the previous session ended without exiting and
+    /// without a panic being caught
+    pub kind: String,
+    pub message: String,
+    /// file:line:column of the panic
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: Vec<String>,
+    /// Last Manager log lines before the panic (already sanitized by the logger)
+    pub log_tail: Vec<String>,
+    /// When the session that crashed started
+    pub session_started_at: Option<String>,
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+    /// The user chose not to send it
+    #[serde(default)]
+    pub dismissed: bool,
+}
+
+impl CrashReport {
+    /// Neither sent nor dismissed yet (offered on the next launch)
+    pub fn is_pending(&self) -> bool {
+        self.submitted_at.is_none() && !self.dismissed
+    }
+}
+
+/// Marker written while the Manager runs (manager/running.json) and removed on a clean exit;
+/// finding it at startup means the previous session ended abruptly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunMarker {
+    pub pid: u32,
+    pub started_at: String,
+    pub manager_version: String,
+}
+
+/// Id for a report written at `now`; also its file name
+pub fn report_id(now: DateTime<Local>) -> String {
+    format!("crash-{}", now.format("%Y%m%d-%H%M%S%3f"))
+}
+
+/// Ids come back from the UI and name a file, so only generated ones are accepted
+pub fn is_valid_id(id: &str) -> bool {
+    id.strip_prefix("crash-")
+        .map(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        .unwrap_or(false)
+}
+
+/// Text of a panic payload (`panic!` with a literal or with format arguments)
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// Lines of a captured backtrace, capped at MAX_BACKTRACE_LINES
+pub fn backtrace_lines(backtrace: &str) -> Vec<String> {
+    let mut lines: Vec<String> = backtrace
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    if lines.len() > MAX_BACKTRACE_LINES {
+        let dropped = lines.len() - MAX_BACKTRACE_LINES;
+        lines.truncate(MAX_BACKTRACE_LINES);
+        lines.push(format!("... {} more lines", dropped));
+    }
+    lines
+}
+
+/// Whether the session of `marker` needs an unclean-exit report: it is not this process, it
+/// ran this version (a self-update relaunches without the exit event) and no report covers
+/// it yet (a panic report already explains the exit)
+pub fn needs_unclean_exit_report(
+    marker: &RunMarker,
+    current_pid: u32,
+    current_version: &str,
+    reports: &[CrashReport],
+) -> bool {
+    marker.pid != current_pid
+        && marker.manager_version == current_version
+        && !reports
+            .iter()
+            .any(|r| r.session_started_at.as_deref() == Some(marker.started_at.as_str()))
+}
+
+/// Report for a session that ended without a clean exit or a caught panic
+pub fn unclean_exit_report(
+    marker: &RunMarker,
+    id: String,
+    now: &str,
+    os: &str,
+    arch: &str,
+) -> CrashReport {
+    CrashReport {
+        id,
+        created_at: now.to_string(),
+        manager_version: marker.manager_version.clone(),
+        os: os.to_string(),
+        arch: arch.to_string(),
+        kind: "unclean-exit".to_string(),
+        message: "The Manager closed without exiting normally and no panic was caught (it was \
+                  killed, the system shut down, or it crashed in native code)"
+            .to_string(),
+        location: None,
+        thread: None,
+        backtrace: Vec::new(),
+        log_tail: Vec::new(),
+        session_started_at: Some(marker.started_at.clone()),
+        submitted_at: None,
+        dismissed: false,
+    }
+}
+
+/// Ids of the reports beyond the `keep` newest
+pub fn to_prune(reports: &[CrashReport], keep: usize) -> Vec<String> {
+    let mut sorted: Vec<&CrashReport> = reports.iter().collect();
+    sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    sorted
+        .into_iter()
+        .skip(keep)
+        .map(|r| r.id.clone())
+        .collect()
+}
+
+/// The last `capacity` lines pushed
+#[derive(Debug)]
+pub struct RecentLines {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl RecentLines {
+    pub const fn new(capacity: usize) -> Self {
+        RecentLines {
+            lines: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, line: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.lines.len() >= self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Lines oldest first
+    pub fn snapshot(&self) -> Vec<String> {
+        self.lines.iter().cloned().collect()
+    }
+}
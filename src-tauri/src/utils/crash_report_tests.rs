@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use super::super::crash_report::{
+        backtrace_lines, is_valid_id, needs_unclean_exit_report, panic_message, report_id,
+        to_prune, unclean_exit_report, CrashReport, RecentLines, RunMarker, MAX_BACKTRACE_LINES,
+    };
+    use chrono::{Local, TimeZone};
+
+    fn marker(pid: u32, started_at: &str) -> RunMarker {
+        RunMarker {
+            pid,
+            started_at: started_at.to_string(),
+            manager_version: "1.2.3".to_string(),
+        }
+    }
+
+    fn report(id: &str, created_at: &str) -> CrashReport {
+        unclean_exit_report(
+            &marker(1, "2026-01-01T00:00:00Z"),
+            id.to_string(),
+            created_at,
+            "linux",
+            "x86_64",
+        )
+    }
+
+    #[test]
+    fn test_report_id_round_trips() {
+        let now = Local.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+        let id = report_id(now);
+        assert!(id.starts_with("crash-20260304-050607"));
+        assert!(is_valid_id(&id));
+        assert!(!is_valid_id("crash-"));
+        assert!(!is_valid_id("crash-../../etc"));
+        assert!(!is_valid_id("snapshot-20260304"));
+    }
+
+    #[test]
+    fn test_panic_message_reads_str_and_string_payloads() {
+        let literal: Box<dyn std::any::Any + Send> = Box::new("boom");
+        assert_eq!(panic_message(literal.as_ref()), "boom");
+        let formatted: Box<dyn std::any::Any + Send> = Box::new(format!("bad {}", 42));
+        assert_eq!(panic_message(formatted.as_ref()), "bad 42");
+        let other: Box<dyn std::any::Any + Send> = Box::new(7u8);
+        assert_eq!(panic_message(other.as_ref()), "Box<dyn Any>");
+    }
+
+    #[test]
+    fn test_backtrace_lines_skip_blanks_and_cap() {
+        assert_eq!(
+            backtrace_lines("   0: main\n\n             at src/main.rs:1:1  \n"),
+            vec!["   0: main", "             at src/main.rs:1:1"]
+        );
+        let long = (0..MAX_BACKTRACE_LINES + 5)
+            .map(|i| format!("{}: frame", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = backtrace_lines(&long);
+        assert_eq!(lines.len(), MAX_BACKTRACE_LINES + 1);
+        assert_eq!(lines.last().unwrap(), "... 5 more lines");
+    }
+
+    #[test]
+    fn test_unclean_exit_only_without_a_report_for_the_session() {
+        let previous = marker(100, "2026-02-01T10:00:00Z");
+        assert!(needs_unclean_exit_report(&previous, 200, "1.2.3", &[]));
+        assert!(!needs_unclean_exit_report(&previous, 100, "1.2.3", &[]));
+        // Written at the start of the session `previous` describes, for the one before it
+        let earlier = report("crash-1", "2026-02-01T10:00:00Z");
+        assert!(needs_unclean_exit_report(
+            &previous,
+            200,
+            "1.2.3",
+            &[earlier]
+        ));
+        let panic = CrashReport {
+            kind: "panic".to_string(),
+            session_started_at: Some("2026-02-01T10:00:00Z".to_string()),
+            ..report("crash-2", "2026-02-01T11:00:00Z")
+        };
+        assert!(!needs_unclean_exit_report(
+            &previous,
+            200,
+            "1.2.3",
+            &[panic]
+        ));
+    }
+
+    #[test]
+    fn test_no_unclean_exit_report_after_an_update() {
+        let previous = marker(100, "2026-02-01T10:00:00Z");
+        assert!(!needs_unclean_exit_report(&previous, 200, "1.3.0", &[]));
+    }
+
+    #[test]
+    fn test_unclean_exit_report_is_pending() {
+        let report = report("crash-1", "2026-02-01T11:00:00Z");
+        assert_eq!(report.kind, "unclean-exit");
+        assert_eq!(report.manager_version, "1.2.3");
+        assert!(report.is_pending());
+        let dismissed = CrashReport {
+            dismissed: true,
+            ..report
+        };
+        assert!(!dismissed.is_pending());
+    }
+
+    #[test]
+    fn test_to_prune_keeps_newest() {
+        let list = vec![
+            report("crash-1", "2026-01-01T00:00:00Z"),
+            report("crash-3", "2026-01-03T00:00:00Z"),
+            report("crash-2", "2026-01-02T00:00:00Z"),
+        ];
+        assert_eq!(to_prune(&list, 2), vec!["crash-1".to_string()]);
+        assert!(to_prune(&list, 3).is_empty());
+    }
+
+    #[test]
+    fn test_recent_lines_keeps_the_last_ones() {
+        let mut recent = RecentLines::new(2);
+        for line in ["a", "b", "c"] {
+            recent.push(line.to_string());
+        }
+        assert_eq!(recent.snapshot(), vec!["b", "c"]);
+        let mut none = RecentLines::new(0);
+        none.push("a".to_string());
+        assert!(none.snapshot().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Argon2id parameters (OWASP minimum: 19 MiB, 2 passes, 1 lane)
+const KDF_MEMORY_KIB: u32 = 19 * 1024;
+const KDF_ITERATIONS: u32 = 2;
+const KDF_PARALLELISM: u32 = 1;
+
+/// Upper bounds for parameters read from a blob, so an imported file can't make decryption
+/// allocate gigabytes or run for minutes (room left to raise the defaults above)
+const KDF_MAX_MEMORY_KIB: u32 = 256 * 1024;
+const KDF_MAX_ITERATIONS: u32 = 10;
+const KDF_MAX_PARALLELISM: u32 = 8;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Passphrase-encrypted data: AES-256-GCM with an Argon2id-derived key.
+/// Binary fields are base64; KDF parameters are stored so they can be raised later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedBlob {
+    pub kdf: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Fill a buffer from the OS random source
+pub fn random_bytes(len: usize) -> Result<Vec<u8>, String> {
+    let mut buf = vec![0u8; len];
+    getrandom::getrandom(&mut buf).map_err(|e| format!("Failed to get random bytes: {}", e))?;
+    Ok(buf)
+}
+
+/// Lowercase hex SHA-256 digest
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Vec<u8>, String> {
+    let params = Params::new(memory_kib, iterations, parallelism, Some(KEY_LEN))
+        .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+    let mut key = vec![0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypt data with a passphrase
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<EncryptedBlob, String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".to_string());
+    }
+    let salt = random_bytes(SALT_LEN)?;
+    let nonce = random_bytes(NONCE_LEN)?;
+    let key = derive_key(passphrase, &salt, KDF_MEMORY_KIB, KDF_ITERATIONS, KDF_PARALLELISM)?;
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| "Invalid encryption key length".to_string())?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    Ok(EncryptedBlob {
+        kdf: "argon2id".to_string(),
+        memory_kib: KDF_MEMORY_KIB,
+        iterations: KDF_ITERATIONS,
+        parallelism: KDF_PARALLELISM,
+        salt: BASE64.encode(salt),
+        cipher: "aes-256-gcm".to_string(),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+/// Decrypt data; fails on a wrong passphrase or any modification (GCM authentication)
+pub fn decrypt(blob: &EncryptedBlob, passphrase: &str) -> Result<Vec<u8>, String> {
+    if blob.kdf != "argon2id" || blob.cipher != "aes-256-gcm" {
+        return Err(format!("Unsupported encryption scheme: {}/{}", blob.kdf, blob.cipher));
+    }
+    let salt = BASE64.decode(&blob.salt).map_err(|e| format!("Invalid salt: {}", e))?;
+    let nonce = BASE64.decode(&blob.nonce).map_err(|e| format!("Invalid nonce: {}", e))?;
+    let ciphertext = BASE64.decode(&blob.ciphertext).map_err(|e| format!("Invalid ciphertext: {}", e))?;
+    if nonce.len() != NONCE_LEN {
+        return Err("Invalid nonce length".to_string());
+    }
+    if blob.memory_kib > KDF_MAX_MEMORY_KIB || blob.iterations > KDF_MAX_ITERATIONS || blob.parallelism > KDF_MAX_PARALLELISM {
+        return Err(format!(
+            "Key derivation parameters too high ({} KiB, {} passes, {} lanes)",
+            blob.memory_kib, blob.iterations, blob.parallelism
+        ));
+    }
+
+    let key = derive_key(passphrase, &salt, blob.memory_kib, blob.iterations, blob.parallelism)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| "Invalid encryption key length".to_string())?;
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Wrong passphrase or corrupted data".to_string())
+}
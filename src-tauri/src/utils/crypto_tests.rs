@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use super::super::crypto::{decrypt, encrypt, sha256_hex};
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let blob = encrypt(b"{\"apiKey\":\"sk-secret\"}", "correct horse").unwrap();
+        assert!(!blob.ciphertext.contains("sk-secret"));
+        assert_eq!(decrypt(&blob, "correct horse").unwrap(), b"{\"apiKey\":\"sk-secret\"}");
+    }
+
+    #[test]
+    fn test_wrong_passphrase_and_tampering_fail() {
+        let blob = encrypt(b"payload", "pass-1").unwrap();
+        assert!(decrypt(&blob, "pass-2").is_err());
+
+        let mut tampered = blob.clone();
+        tampered.ciphertext = encrypt(b"other", "pass-1").unwrap().ciphertext;
+        assert!(decrypt(&tampered, "pass-1").is_err());
+    }
+
+    #[test]
+    fn test_oversized_kdf_parameters_rejected() {
+        let blob = encrypt(b"payload", "pass-1").unwrap();
+        let mut heavy = blob.clone();
+        heavy.memory_kib = u32::MAX;
+        let error = decrypt(&heavy, "pass-1").unwrap_err();
+        assert!(error.starts_with("Key derivation parameters too high"));
+        let mut slow = blob;
+        slow.iterations = 1_000_000;
+        assert!(decrypt(&slow, "pass-1").is_err());
+    }
+
+    #[test]
+    fn test_empty_passphrase_rejected() {
+        assert!(encrypt(b"payload", "").is_err());
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
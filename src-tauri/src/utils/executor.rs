@@ -0,0 +1,274 @@
+use log::{debug, info};
+use serde::Deserialize;
+use std::io;
+use std::process::{Command, ExitStatus, Output};
+use std::sync::Mutex;
+
+/// Environment variable that turns on mock mode (any non-empty value except "0")
+pub const MOCK_ENV: &str = "OPENCLAW_MANAGER_MOCK";
+
+/// Command line flag that turns on mock mode
+pub const MOCK_FLAG: &str = "--mock";
+
+/// PID the simulated gateway reports
+const MOCK_GATEWAY_PID: u32 = 43210;
+
+/// Port the simulated gateway listens on
+const MOCK_GATEWAY_PORT: &str = "18789";
+
+/// Runs the external processes the Manager starts (openclaw, node, npm, curl, ...)
+pub trait Executor: Send + Sync {
+    /// Run a command to completion and capture its output
+    fn output(&self, cmd: &mut Command) -> io::Result<Output>;
+    /// Start a command in the background and return its PID
+    fn spawn(&self, cmd: &mut Command) -> io::Result<u32>;
+    /// Whether commands are served from fixtures instead of the system
+    fn is_mock(&self) -> bool {
+        false
+    }
+}
+
+/// Executes commands on the system
+pub struct RealExecutor;
+
+impl Executor for RealExecutor {
+    fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+        cmd.output()
+    }
+
+    fn spawn(&self, cmd: &mut Command) -> io::Result<u32> {
+        cmd.spawn().map(|child| child.id())
+    }
+}
+
+/// Canned result for a command. `args` must be a prefix of the command's arguments and
+/// `contains` a substring of them (scripts, URLs); `gateway` restricts it to a simulated state.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fixture {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub contains: Option<String>,
+    /// "running" or "stopped"
+    pub gateway: Option<String>,
+    #[serde(default)]
+    pub stdout: String,
+    #[serde(default)]
+    pub stderr: String,
+    #[serde(default)]
+    pub exit_code: i32,
+}
+
+/// Serves commands from fixtures and simulates a gateway that can be started and stopped.
+/// Nothing is executed; commands without a fixture succeed with empty output.
+pub struct MockExecutor {
+    fixtures: Vec<Fixture>,
+    gateway_pid: Mutex<Option<u32>>,
+}
+
+/// Built-in fixtures (src-tauri/fixtures/mock.json)
+const DEFAULT_FIXTURES: &str = include_str!("../../fixtures/mock.json");
+
+impl Default for MockExecutor {
+    fn default() -> Self {
+        let fixtures = serde_json::from_str(DEFAULT_FIXTURES).unwrap_or_else(|e| {
+            log::warn!("[Mock] Built-in fixtures are invalid: {}", e);
+            Vec::new()
+        });
+        MockExecutor::new(fixtures, true)
+    }
+}
+
+impl MockExecutor {
+    pub fn new(fixtures: Vec<Fixture>, gateway_running: bool) -> Self {
+        MockExecutor {
+            fixtures,
+            gateway_pid: Mutex::new(gateway_running.then_some(MOCK_GATEWAY_PID)),
+        }
+    }
+
+    fn gateway(&self) -> Option<u32> {
+        self.gateway_pid.lock().ok().and_then(|pid| *pid)
+    }
+
+    fn set_gateway(&self, pid: Option<u32>) {
+        if let Ok(mut current) = self.gateway_pid.lock() {
+            *current = pid;
+        }
+    }
+
+    /// Answer a command from the simulated gateway state or the fixtures
+    pub fn respond(&self, program: &str, args: &[String]) -> (i32, String, String) {
+        let gateway = self.gateway();
+        let pid = gateway.map(|p| p.to_string()).unwrap_or_default();
+        let joined = args.join(" ");
+
+        // Port and process lookups that decide whether the gateway is running
+        match program {
+            "lsof" if joined.contains(MOCK_GATEWAY_PORT) => {
+                return match gateway {
+                    Some(_) => (0, format!("{}\n", pid), String::new()),
+                    None => (1, String::new(), String::new()),
+                };
+            }
+            "netstat" => {
+                let line = gateway
+                    .map(|_| {
+                        format!(
+                            "  TCP    127.0.0.1:{}    0.0.0.0:0    LISTENING    {}\n",
+                            MOCK_GATEWAY_PORT, pid
+                        )
+                    })
+                    .unwrap_or_default();
+                return (0, line, String::new());
+            }
+            "ps" if gateway.is_some() && args.iter().any(|a| *a == pid) => {
+                return (0, "openclaw-gateway\n".to_string(), String::new());
+            }
+            "kill" | "taskkill" if gateway.is_some() && args.iter().any(|a| *a == pid) => {
+                self.set_gateway(None);
+                return (0, String::new(), String::new());
+            }
+            // curl exit code 7: could not connect
+            "curl"
+                if gateway.is_none()
+                    && joined.contains(&format!("127.0.0.1:{}", MOCK_GATEWAY_PORT)) =>
+            {
+                return (7, String::new(), String::new());
+            }
+            "openclaw" if joined.starts_with("gateway stop") => {
+                self.set_gateway(None);
+                return (0, "Gateway stopped\n".to_string(), String::new());
+            }
+            _ => {}
+        }
+
+        let state = if gateway.is_some() {
+            "running"
+        } else {
+            "stopped"
+        };
+        let fixture = self.fixtures.iter().find(|f| {
+            f.program == program
+                && args.len() >= f.args.len()
+                && f.args.iter().zip(args).all(|(a, b)| a == b)
+                && f.contains
+                    .as_deref()
+                    .map(|c| joined.contains(c))
+                    .unwrap_or(true)
+                && f.gateway.as_deref().map(|g| g == state).unwrap_or(true)
+        });
+        match fixture {
+            Some(f) => (f.exit_code, f.stdout.clone(), f.stderr.clone()),
+            None => {
+                debug!("[Mock] No fixture for {} {}", program, joined);
+                (0, String::new(), String::new())
+            }
+        }
+    }
+}
+
+/// Executable name without directory and .exe/.cmd extension
+fn program_name(cmd: &Command) -> String {
+    let program = cmd.get_program().to_string_lossy().to_lowercase();
+    let name = program
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(&program)
+        .to_string();
+    name.trim_end_matches(".exe")
+        .trim_end_matches(".cmd")
+        .to_string()
+}
+
+fn command_args(cmd: &Command) -> Vec<String> {
+    cmd.get_args()
+        .map(|a| a.to_string_lossy().to_string())
+        .collect()
+}
+
+#[cfg(unix)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw(code << 8)
+}
+
+#[cfg(windows)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(code as u32)
+}
+
+impl Executor for MockExecutor {
+    fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+        let program = program_name(cmd);
+        let args = command_args(cmd);
+        let (code, mut stdout, stderr) = self.respond(&program, &args);
+
+        // curl: honor -o (write the body to the file) and -w %{http_code}
+        if program == "curl" {
+            let status = match code {
+                0 => "200",
+                7 => "000",
+                _ => "500",
+            };
+            if let Some(path) = args
+                .iter()
+                .position(|a| a == "-o")
+                .and_then(|i| args.get(i + 1))
+            {
+                if path != "/dev/null" && path != "NUL" {
+                    std::fs::write(path, &stdout)?;
+                }
+                stdout = String::new();
+            }
+            if args.iter().any(|a| a.contains("%{http_code}")) {
+                if !stdout.is_empty() {
+                    stdout.push('\n');
+                }
+                stdout.push_str(status);
+            }
+        }
+
+        Ok(Output {
+            status: exit_status(code),
+            stdout: stdout.into_bytes(),
+            stderr: stderr.into_bytes(),
+        })
+    }
+
+    fn spawn(&self, cmd: &mut Command) -> io::Result<u32> {
+        let program = program_name(cmd);
+        let args = command_args(cmd);
+        if program == "openclaw" && args.windows(2).any(|w| w[0] == "gateway" && w[1] == "run") {
+            info!("[Mock] Simulated gateway started");
+            self.set_gateway(Some(MOCK_GATEWAY_PID));
+            return Ok(MOCK_GATEWAY_PID);
+        }
+        debug!("[Mock] Not starting {} {}", program, args.join(" "));
+        Ok(0)
+    }
+
+    fn is_mock(&self) -> bool {
+        true
+    }
+}
+
+/// Executor for this run: fixtures in mock mode, the system otherwise
+pub fn default_executor() -> Box<dyn Executor> {
+    if mock_requested() {
+        info!("[Mock] Mock mode: commands are served from fixtures");
+        Box::new(MockExecutor::default())
+    } else {
+        Box::new(RealExecutor)
+    }
+}
+
+/// Whether this run should use the MockExecutor (`--mock` or OPENCLAW_MANAGER_MOCK)
+pub fn mock_requested() -> bool {
+    let env_set = std::env::var(MOCK_ENV)
+        .map(|v| !v.is_empty() && v != "0")
+        .unwrap_or(false);
+    env_set || std::env::args().any(|a| a == MOCK_FLAG)
+}
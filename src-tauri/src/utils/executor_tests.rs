@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use super::super::executor::{Executor, Fixture, MockExecutor};
+    use crate::commands::{process, service};
+    use std::process::Command;
+
+    fn fixture(program: &str, args: &[&str], gateway: Option<&str>, stdout: &str) -> Fixture {
+        Fixture {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            contains: None,
+            gateway: gateway.map(String::from),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        }
+    }
+
+    fn stdout(executor: &MockExecutor, cmd: &mut Command) -> (bool, String) {
+        let output = executor.output(cmd).unwrap();
+        (output.status.success(), String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    #[test]
+    fn test_mock_gateway_lifecycle() {
+        let executor = MockExecutor::new(Vec::new(), false);
+        let (found, _) = stdout(&executor, Command::new("lsof").args(["-ti", ":18789"]));
+        assert!(!found);
+
+        let pid = executor
+            .spawn(Command::new("/usr/local/bin/openclaw").args(["gateway", "run", "--port", "18789"]))
+            .unwrap();
+        let (found, out) = stdout(&executor, Command::new("lsof").args(["-ti", ":18789"]));
+        assert!(found);
+        assert_eq!(out.trim(), pid.to_string());
+
+        let _ = executor.output(Command::new("kill").args(["-9", &pid.to_string()]));
+        let (found, _) = stdout(&executor, Command::new("lsof").args(["-ti", ":18789"]));
+        assert!(!found);
+    }
+
+    #[test]
+    fn test_mock_fixture_matching() {
+        let executor = MockExecutor::new(
+            vec![
+                fixture("openclaw", &["gateway", "health"], Some("running"), "healthy"),
+                fixture("openclaw", &["gateway", "health"], Some("stopped"), "down"),
+                fixture("node", &["--version"], None, "v22.12.0"),
+            ],
+            true,
+        );
+        let (_, out) = stdout(&executor, Command::new("openclaw.cmd").args(["gateway", "health", "--timeout", "2000"]));
+        assert_eq!(out, "healthy");
+        let (_, out) = stdout(&executor, Command::new("node").arg("--version"));
+        assert_eq!(out, "v22.12.0");
+        // Unknown commands succeed without output
+        let (ok, out) = stdout(&executor, Command::new("npm").args(["install", "-g", "openclaw"]));
+        assert!(ok && out.is_empty());
+
+        let _ = executor.output(Command::new("openclaw").args(["gateway", "stop"]));
+        let (_, out) = stdout(&executor, Command::new("openclaw").args(["gateway", "health"]));
+        assert_eq!(out, "down");
+    }
+
+    #[test]
+    fn test_mock_curl_status_code() {
+        let mut models = fixture("curl", &[], None, "{\"data\":[]}");
+        models.contains = Some("/models".to_string());
+        let executor = MockExecutor::new(vec![models], false);
+        let (_, out) = stdout(
+            &executor,
+            Command::new("curl.exe").args(["-s", "-w", "\n%{http_code}", "https://api.example.com/v1/models"]),
+        );
+        assert_eq!(out, "{\"data\":[]}\n200");
+        let (_, out) = stdout(&executor, Command::new("curl").args(["-s", "https://api.example.com/v1/models"]));
+        assert_eq!(out, "{\"data\":[]}");
+    }
+
+    #[test]
+    fn test_port_listener_pid_follows_gateway() {
+        let executor = MockExecutor::new(Vec::new(), true);
+        let pid = service::port_listener_pid(&executor, 18789);
+        assert!(pid.is_some());
+        assert_eq!(service::port_listener_pid(&executor, 18790), None);
+
+        let _ = executor.output(Command::new("openclaw").args(["gateway", "stop"]));
+        assert_eq!(service::port_listener_pid(&executor, 18789), None);
+    }
+
+    #[test]
+    fn test_ollama_models_from_cli_output() {
+        let listing = "NAME              ID              SIZE      MODIFIED\n\
+                       llama3.2:latest   a80c4f17acd5    2.0 GB    3 days ago\n\
+                       qwen3.5:9b        abcd1234ef56    5.5 GB    1 week ago\n";
+        let executor = MockExecutor::new(vec![fixture("ollama", &["list"], None, listing)], false);
+        assert_eq!(
+            process::ollama_models(&executor).unwrap(),
+            vec!["llama3.2:latest".to_string(), "qwen3.5:9b".to_string()]
+        );
+
+        let mut missing = fixture("ollama", &["list"], None, "");
+        missing.stderr = "ollama: command not found".to_string();
+        missing.exit_code = 127;
+        let executor = MockExecutor::new(vec![missing], false);
+        assert!(process::ollama_models(&executor).is_err());
+    }
+
+    #[test]
+    fn test_node_version() {
+        let executor = MockExecutor::new(vec![fixture("node", &["--version"], None, "v22.12.0\n")], false);
+        assert_eq!(process::node_version(&executor).as_deref(), Some("v22.12.0"));
+        let executor = MockExecutor::new(Vec::new(), false);
+        assert_eq!(process::node_version(&executor), None);
+    }
+}
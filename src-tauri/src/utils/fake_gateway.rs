@@ -0,0 +1,144 @@
+//! Test-only stand-in for the OpenClaw gateway: an HTTP listener that checks the bearer
+//! token like the real one, and a WebSocket endpoint answering `{"type":"req"}` frames.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct GatewayState {
+    token: String,
+    requests: Arc<AtomicUsize>,
+}
+
+/// A running fake gateway on an ephemeral 127.0.0.1 port; stopped on drop
+pub struct FakeGateway {
+    pub port: u16,
+    pub token: String,
+    requests: Arc<AtomicUsize>,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl FakeGateway {
+    pub fn start(token: &str) -> FakeGateway {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("fake gateway runtime");
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind fake gateway");
+        listener
+            .set_nonblocking(true)
+            .expect("nonblocking listener");
+        let port = listener.local_addr().expect("local addr").port();
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let state = GatewayState {
+            token: token.to_string(),
+            requests: requests.clone(),
+        };
+        let app = Router::new().route("/", get(root)).with_state(state);
+        let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+        runtime.spawn(async move {
+            let listener = tokio::net::TcpListener::from_std(listener).expect("tokio listener");
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = stopped.await;
+                })
+                .await;
+        });
+
+        FakeGateway {
+            port,
+            token: token.to_string(),
+            requests,
+            shutdown: Some(shutdown),
+            runtime: Some(runtime),
+        }
+    }
+
+    /// Number of HTTP requests and WebSocket frames handled so far
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /// Stop listening; the port is free afterwards
+    pub fn stop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_timeout(std::time::Duration::from_secs(2));
+        }
+    }
+}
+
+impl Drop for FakeGateway {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Bearer header or `?token=` (the dashboard URL form)
+fn authorized(state: &GatewayState, headers: &HeaderMap, query: &HashMap<String, String>) -> bool {
+    let bearer = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    bearer == Some(state.token.as_str()) || query.get("token") == Some(&state.token)
+}
+
+async fn root(
+    State(state): State<GatewayState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    ws: Option<WebSocketUpgrade>,
+) -> Response {
+    state.requests.fetch_add(1, Ordering::SeqCst);
+    if !authorized(&state, &headers, &query) {
+        return (StatusCode::UNAUTHORIZED, "unauthorized").into_response();
+    }
+    match ws {
+        Some(ws) => ws.on_upgrade(move |socket| serve_socket(socket, state)),
+        None => Html("<!doctype html><title>OpenClaw Control</title>").into_response(),
+    }
+}
+
+/// Answer every `{"type":"req","id","method"}` frame with an ok response
+async fn serve_socket(mut socket: WebSocket, state: GatewayState) {
+    while let Some(Ok(message)) = socket.recv().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        state.requests.fetch_add(1, Ordering::SeqCst);
+        let request: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+        if request.get("type").and_then(|v| v.as_str()) != Some("req") {
+            continue;
+        }
+        let payload = match request.get("method").and_then(|v| v.as_str()) {
+            Some("health") => json!({ "ok": true, "channels": {} }),
+            _ => json!({}),
+        };
+        let response = json!({
+            "type": "res",
+            "id": request.get("id").cloned().unwrap_or(Value::Null),
+            "ok": true,
+            "payload": payload,
+        });
+        if socket
+            .send(Message::Text(response.to_string()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
@@ -1,19 +1,79 @@
+use crate::utils::{config_watch, platform};
 use std::fs;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
 /// 读取文件内容
 pub fn read_file(path: &str) -> io::Result<String> {
-    fs::read_to_string(path)
+    fs::read_to_string(platform::long_path(path))
 }
 
 /// 写入文件内容
 pub fn write_file(path: &str, content: &str) -> io::Result<()> {
+    let target = platform::long_path(path);
     // 确保父目录存在
-    if let Some(parent) = Path::new(path).parent() {
+    if let Some(parent) = target.parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::write(path, content)
+    fs::write(target, content)?;
+    config_watch::remember_write(path, content.as_bytes());
+    Ok(())
+}
+
+/// Copy of the previous content kept by `write_file_atomic`
+pub fn backup_path(path: &str) -> String {
+    format!("{}.bak", path)
+}
+
+/// Write a file so readers only ever see the old or the new content: the content goes to a
+/// temp file next to the target, is flushed to disk and renamed over it. With `keep_backup`
+/// the previous content is copied to `<path>.bak` first.
+pub fn write_file_atomic(path: &str, content: &str, keep_backup: bool) -> io::Result<()> {
+    use std::io::Write;
+
+    let target = platform::long_path(path);
+    let parent = target.parent().map(Path::to_path_buf);
+    if let Some(parent) = &parent {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = platform::long_path(format!("{}.{}.tmp", path, std::process::id()));
+    let previous = fs::metadata(&target).ok();
+    let written = (|| -> io::Result<()> {
+        // A leftover temp file would keep its old mode
+        let _ = fs::remove_file(&temp);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        // The content (tokens, keys) is never readable by others, not even before the rename
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(previous.as_ref().map(|m| m.permissions().mode() & 0o777).unwrap_or(0o600));
+        }
+        let mut file = options.open(&temp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        // Keep the permissions of the file being replaced (configs may be owner-only)
+        if let Some(meta) = &previous {
+            fs::set_permissions(&temp, meta.permissions())?;
+        }
+        if keep_backup && target.is_file() {
+            fs::copy(&target, platform::long_path(backup_path(path)))?;
+        }
+        fs::rename(&temp, &target)
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+        return written;
+    }
+    config_watch::remember_write(path, content.as_bytes());
+    // Flush the directory entry too, so the rename survives a power loss
+    #[cfg(unix)]
+    {
+        if let Some(dir) = parent.and_then(|p| fs::File::open(p).ok()) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
 }
 
 /// 追加文件内容
@@ -24,11 +84,27 @@ pub fn append_file(path: &str, content: &str) -> io::Result<()> {
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(path)?;
+        .open(platform::long_path(path))?;
     
     writeln!(file, "{}", content)
 }
 
+/// Recursively copy a directory, overwriting existing files
+pub fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
+    let (src, dst) = (platform::long_path(src), platform::long_path(dst));
+    fs::create_dir_all(&dst)?;
+    for entry in fs::read_dir(&src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
 /// 检查文件是否存在
 pub fn file_exists(path: &str) -> bool {
     Path::new(path).exists()
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use super::super::file::{backup_path, write_file_atomic};
+    use std::fs;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("openclaw-file-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_file_atomic_keeps_backup() {
+        let dir = temp_dir("atomic");
+        let path = dir.join("nested").join("openclaw.json");
+        let path = path.to_str().unwrap();
+
+        write_file_atomic(path, r#"{"a": 1}"#, true).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), r#"{"a": 1}"#);
+        // Nothing to back up on the first write
+        assert!(!std::path::Path::new(&backup_path(path)).exists());
+
+        write_file_atomic(path, r#"{"a": 2}"#, true).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), r#"{"a": 2}"#);
+        assert_eq!(
+            fs::read_to_string(backup_path(path)).unwrap(),
+            r#"{"a": 1}"#
+        );
+
+        write_file_atomic(path, r#"{"a": 3}"#, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(backup_path(path)).unwrap(),
+            r#"{"a": 1}"#
+        );
+
+        // No temp files are left behind
+        let leftovers: Vec<_> = fs::read_dir(dir.join("nested"))
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("perms");
+        let path = dir.join("manager.json");
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_file_atomic(path.to_str().unwrap(), r#"{"b": true}"#, false).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
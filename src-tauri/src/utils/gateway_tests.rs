@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use super::super::executor::RealExecutor;
+    use super::super::fake_gateway::FakeGateway;
+    use crate::commands::{process, service};
+
+    const TOKEN: &str = "test-gateway-token";
+
+    fn curl_available() -> bool {
+        std::process::Command::new("curl")
+            .arg("--version")
+            .output()
+            .is_ok()
+    }
+
+    #[test]
+    fn test_probe_accepts_correct_token() {
+        if !curl_available() {
+            return;
+        }
+        let gateway = FakeGateway::start(TOKEN);
+        let probe = service::probe_gateway_http(&RealExecutor, gateway.port, TOKEN);
+        assert!(probe.reachable);
+        assert_eq!(probe.http_status, Some(200));
+        assert_eq!(probe.token_accepted, Some(true));
+        assert_eq!(gateway.requests(), 1);
+    }
+
+    #[test]
+    fn test_probe_rejects_wrong_token() {
+        if !curl_available() {
+            return;
+        }
+        let gateway = FakeGateway::start(TOKEN);
+        let probe = service::probe_gateway_http(&RealExecutor, gateway.port, "wrong-token");
+        assert!(probe.reachable);
+        assert_eq!(probe.http_status, Some(401));
+        assert_eq!(probe.token_accepted, Some(false));
+    }
+
+    #[test]
+    fn test_probe_closed_port() {
+        if !curl_available() {
+            return;
+        }
+        let mut gateway = FakeGateway::start(TOKEN);
+        let port = gateway.port;
+        gateway.stop();
+        let probe = service::probe_gateway_http(&RealExecutor, port, TOKEN);
+        assert!(!probe.reachable);
+        assert_eq!(probe.token_accepted, None);
+        assert!(probe.error.is_some());
+    }
+
+    #[test]
+    fn test_port_in_use_follows_gateway() {
+        let mut gateway = FakeGateway::start(TOKEN);
+        let port = gateway.port;
+        let in_use = tauri::async_runtime::block_on(process::check_port_in_use(port));
+        assert_eq!(in_use, Ok(true));
+
+        gateway.stop();
+        let in_use = tauri::async_runtime::block_on(process::check_port_in_use(port));
+        assert_eq!(in_use, Ok(false));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_port_listener_pid_is_this_process() {
+        if std::process::Command::new("lsof")
+            .arg("-v")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let gateway = FakeGateway::start(TOKEN);
+        let pid = service::port_listener_pid(&RealExecutor, gateway.port);
+        assert_eq!(pid, Some(std::process::id()));
+    }
+}
@@ -0,0 +1,98 @@
+//! Shared gateway token (gateway.auth.token) generation. Tokens are hex-encoded bytes from the
+//! OS random source; tokens written by older Manager versions were derived from the clock and
+//! are detected so they can be replaced.
+
+use crate::utils::{crypto, platform, shell};
+use std::sync::{OnceLock, RwLock};
+
+/// Random bytes in a new token (hex-encoded, so twice as many characters)
+pub const DEFAULT_BYTES: u32 = 32;
+pub const MIN_BYTES: u32 = 16;
+pub const MAX_BYTES: u32 = 64;
+
+/// Multiplier of the old timestamp-based generator
+const LEGACY_MULTIPLIER: u64 = 0x5DEECE66D;
+
+/// New token of `bytes` random bytes
+pub fn generate(bytes: u32) -> Result<String, String> {
+    validate_bytes(bytes)?;
+    Ok(crypto::random_bytes(bytes as usize)?
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+pub fn validate_bytes(bytes: u32) -> Result<(), String> {
+    if !(MIN_BYTES..=MAX_BYTES).contains(&bytes) {
+        return Err(format!(
+            "Token length must be between {} and {} bytes",
+            MIN_BYTES, MAX_BYTES
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `token` was made by the old generator: three 64-bit hex words where the first is
+/// the clock XOR the multiplier, the second the first times the multiplier and the third the clock
+pub fn is_legacy(token: &str) -> bool {
+    if token.len() != 48 || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    let word = |i: usize| u64::from_str_radix(&token[i * 16..(i + 1) * 16], 16).unwrap_or(0);
+    let (random_part, product, timestamp) = (word(0), word(1), word(2));
+    random_part == timestamp ^ LEGACY_MULTIPLIER
+        && product == random_part.wrapping_mul(LEGACY_MULTIPLIER)
+}
+
+/// Why `token` should be replaced, None when it is fine. Tokens a user typed in themselves
+/// are left alone unless they are the well-known fallback.
+pub fn weakness(token: &str) -> Option<&'static str> {
+    if token == shell::DEFAULT_GATEWAY_TOKEN {
+        Some("the built-in fallback token")
+    } else if is_legacy(token) {
+        Some("a timestamp-derived legacy token")
+    } else {
+        None
+    }
+}
+
+/// Token length setting (manager.json -> tokenRotation.tokenBytes), loaded on first use
+static TOKEN_BYTES: OnceLock<RwLock<u32>> = OnceLock::new();
+
+fn token_bytes_lock() -> &'static RwLock<u32> {
+    TOKEN_BYTES.get_or_init(|| {
+        let value = std::fs::read_to_string(platform::get_manager_config_file_path())
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}'))
+                    .ok()
+            })
+            .and_then(|config| {
+                config
+                    .pointer("/tokenRotation/tokenBytes")
+                    .and_then(|v| v.as_u64())
+            })
+            .map(|bytes| bytes.clamp(MIN_BYTES as u64, MAX_BYTES as u64) as u32)
+            .unwrap_or(DEFAULT_BYTES);
+        RwLock::new(value)
+    })
+}
+
+pub fn token_bytes() -> u32 {
+    token_bytes_lock()
+        .read()
+        .map(|b| *b)
+        .unwrap_or(DEFAULT_BYTES)
+}
+
+/// Update the in-memory length (manager.json is written by the caller)
+pub fn set_token_bytes(bytes: u32) {
+    if let Ok(mut value) = token_bytes_lock().write() {
+        *value = bytes;
+    }
+}
+
+/// New token of the configured length
+pub fn generate_configured() -> Result<String, String> {
+    generate(token_bytes())
+}
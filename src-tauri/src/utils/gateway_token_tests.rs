@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use super::super::gateway_token::{generate, is_legacy, weakness, MAX_BYTES, MIN_BYTES};
+    use super::super::shell::DEFAULT_GATEWAY_TOKEN;
+
+    /// Token as the old timestamp-based generator made it
+    fn legacy_token(timestamp: u64) -> String {
+        let random_part = timestamp ^ 0x5DEECE66D;
+        format!(
+            "{:016x}{:016x}{:016x}",
+            random_part,
+            random_part.wrapping_mul(0x5DEECE66D),
+            timestamp
+        )
+    }
+
+    #[test]
+    fn test_generate_uses_requested_length() {
+        let token = generate(32).unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(generate(32).unwrap(), token);
+        assert_eq!(generate(MIN_BYTES).unwrap().len(), 32);
+        assert!(generate(MIN_BYTES - 1).is_err());
+        assert!(generate(MAX_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_legacy_tokens_are_detected() {
+        let legacy = legacy_token(1_791_000_000_123_456_789);
+        assert!(is_legacy(&legacy));
+        assert!(weakness(&legacy).is_some());
+        assert!(weakness(DEFAULT_GATEWAY_TOKEN).is_some());
+
+        // 24 random bytes look the same but don't satisfy the relation
+        let random = generate(24).unwrap();
+        assert!(!is_legacy(&random));
+        assert!(weakness(&random).is_none());
+        assert!(weakness("my-own-token").is_none());
+        assert!(!is_legacy(&legacy[..47]));
+    }
+}
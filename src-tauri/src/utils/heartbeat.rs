@@ -0,0 +1,184 @@
+//! Heartbeat runs as reported by the gateway (`openclaw system heartbeat last` and its logs).
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+/// Interval OpenClaw uses when heartbeat.every is not set
+pub const DEFAULT_EVERY: &str = "30m";
+
+/// One heartbeat run
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeartbeatEvent {
+    pub at: DateTime<Utc>,
+    /// None when the source does not say which agent ran (the default agent)
+    pub agent_id: Option<String>,
+    /// ok, sent, skipped or failed
+    pub result: String,
+    pub message: String,
+}
+
+/// Parse a heartbeat interval: "30m", "1h", "1h30m", "45s", "2d"; a bare number is minutes.
+/// Zero means heartbeats are disabled.
+pub fn parse_every(text: &str) -> Option<Duration> {
+    let text = text.trim().to_ascii_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return Some(Duration::minutes(minutes));
+    }
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut chars = text.chars().peekable();
+    let mut seen_unit = false;
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        total = total
+            + match c {
+                'm' if chars.peek() == Some(&'s') => {
+                    chars.next();
+                    Duration::milliseconds(value)
+                }
+                's' => Duration::seconds(value),
+                'm' => Duration::minutes(value),
+                'h' => Duration::hours(value),
+                'd' => Duration::days(value),
+                _ => return None,
+            };
+        seen_unit = true;
+    }
+    (seen_unit && number.is_empty()).then_some(total)
+}
+
+/// Result of a heartbeat log message, None when the message is not about a run
+pub fn classify(message: &str) -> Option<&'static str> {
+    let lower = message.to_ascii_lowercase();
+    if !lower.contains("heartbeat") {
+        return None;
+    }
+    let words: Vec<&str> = lower.split(|c: char| !c.is_ascii_alphanumeric()).collect();
+    let has = |candidates: &[&str]| words.iter().any(|w| candidates.contains(w));
+    if has(&["failed", "error", "errored", "timeout"]) {
+        Some("failed")
+    } else if has(&["skipped", "skip"]) {
+        Some("skipped")
+    } else if has(&["sent", "delivered"]) {
+        Some("sent")
+    } else if has(&["ok", "ran", "completed", "triggered", "done"]) {
+        Some("ok")
+    } else {
+        None
+    }
+}
+
+fn parse_time(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        Value::Number(n) => DateTime::from_timestamp_millis(n.as_i64()?),
+        _ => None,
+    }
+}
+
+/// Agent id mentioned in a plain-text message (`agent=ops`, `agent: ops`, `agentId":"ops"`)
+fn agent_in_text(text: &str) -> Option<String> {
+    for key in ["agentId\":\"", "agentid=", "agent=", "agent: "] {
+        if let Some(start) = text.to_ascii_lowercase().find(&key.to_ascii_lowercase()) {
+            let rest = &text[start + key.len()..];
+            let id: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            if !id.is_empty() {
+                return Some(id);
+            }
+        }
+    }
+    None
+}
+
+/// Event printed by `openclaw system heartbeat last`
+pub fn event_from_json(value: &Value) -> Option<HeartbeatEvent> {
+    let at = ["ts", "at", "timestamp"]
+        .iter()
+        .find_map(|k| value.get(*k).and_then(parse_time))?;
+    let status = value
+        .get("status")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let result = match status {
+        "failed" | "error" => "failed",
+        "skipped" => "skipped",
+        "sent" => "sent",
+        _ => "ok",
+    };
+    let message = ["reason", "preview", "error"]
+        .iter()
+        .find_map(|k| value.get(*k).and_then(|v| v.as_str()))
+        .map(String::from)
+        .unwrap_or_else(|| status.to_string());
+    Some(HeartbeatEvent {
+        at,
+        agent_id: value
+            .get("agentId")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        result: result.to_string(),
+        message,
+    })
+}
+
+/// Heartbeat run in a gateway log line: a JSON line of the daily log
+/// (`{"0": ..., "1": "heartbeat sent", "time": ...}`) or a plain line starting with a timestamp
+pub fn event_from_log_line(line: &str) -> Option<HeartbeatEvent> {
+    let line = line.trim();
+    let (at, message) = match serde_json::from_str::<Value>(line) {
+        Ok(Value::Object(entry)) => {
+            let at = entry
+                .get("time")
+                .or_else(|| entry.get("_meta").and_then(|m| m.get("date")))
+                .and_then(parse_time)?;
+            let message = entry
+                .iter()
+                .filter(|(k, _)| k.parse::<u32>().is_ok())
+                .filter_map(|(_, v)| v.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (at, message)
+        }
+        _ => {
+            let (first, rest) = line.split_once(char::is_whitespace)?;
+            let stamp = first.trim_matches(|c| c == '[' || c == ']');
+            let at = DateTime::parse_from_rfc3339(stamp)
+                .ok()?
+                .with_timezone(&Utc);
+            (at, rest.trim().to_string())
+        }
+    };
+    let result = classify(&message)?;
+    Some(HeartbeatEvent {
+        at,
+        agent_id: agent_in_text(&message),
+        result: result.to_string(),
+        message,
+    })
+}
+
+/// When the next heartbeat is expected, and whether it is late: a full interval past the
+/// expected time without a run
+pub fn schedule(
+    last: Option<DateTime<Utc>>,
+    every: Duration,
+    now: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, bool) {
+    match last {
+        Some(last) => {
+            let next = last + every;
+            (Some(next), now > next + every)
+        }
+        None => (None, false),
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use super::super::heartbeat::{
+        classify, event_from_json, event_from_log_line, parse_every, schedule,
+    };
+    use chrono::{DateTime, Duration, Utc};
+    use serde_json::json;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_every() {
+        assert_eq!(parse_every("30m"), Some(Duration::minutes(30)));
+        assert_eq!(parse_every("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_every("45"), Some(Duration::minutes(45)));
+        assert_eq!(parse_every("500ms"), Some(Duration::milliseconds(500)));
+        assert_eq!(parse_every("0m"), Some(Duration::zero()));
+        assert_eq!(parse_every("soon"), None);
+        assert_eq!(parse_every("30"), Some(Duration::minutes(30)));
+        assert_eq!(parse_every("1h30"), None);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("heartbeat sent to telegram"), Some("sent"));
+        assert_eq!(classify("heartbeat skipped: quiet hours"), Some("skipped"));
+        assert_eq!(classify("Heartbeat failed: model timeout"), Some("failed"));
+        assert_eq!(classify("heartbeat: ok-empty"), Some("ok"));
+        assert_eq!(classify("heartbeat runner started"), None);
+        assert_eq!(classify("message sent"), None);
+    }
+
+    #[test]
+    fn test_event_from_cli_json() {
+        let event = event_from_json(&json!({
+            "ts": 1790000000000i64,
+            "status": "skipped",
+            "reason": "quiet-hours"
+        }))
+        .unwrap();
+        assert_eq!(event.result, "skipped");
+        assert_eq!(event.message, "quiet-hours");
+        assert_eq!(event.agent_id, None);
+        assert!(event_from_json(&json!({ "status": "sent" })).is_none());
+    }
+
+    #[test]
+    fn test_event_from_log_lines() {
+        let line = r#"{"0":"{\"subsystem\":\"gateway/heartbeat\"}","1":"heartbeat sent agent=ops","time":"2026-10-01T10:00:00.000Z"}"#;
+        let event = event_from_log_line(line).unwrap();
+        assert_eq!(event.result, "sent");
+        assert_eq!(event.agent_id.as_deref(), Some("ops"));
+        assert_eq!(event.at, at("2026-10-01T10:00:00Z"));
+
+        let event =
+            event_from_log_line("2026-10-01T11:00:00+02:00 [heartbeat] failed: 401").unwrap();
+        assert_eq!(event.result, "failed");
+        assert_eq!(event.at, at("2026-10-01T09:00:00Z"));
+
+        assert!(event_from_log_line("2026-10-01T11:00:00Z gateway listening").is_none());
+        assert!(event_from_log_line("heartbeat sent").is_none());
+    }
+
+    #[test]
+    fn test_schedule_flags_a_missed_interval() {
+        let last = at("2026-10-01T10:00:00Z");
+        let every = Duration::minutes(30);
+        let (next, overdue) = schedule(Some(last), every, at("2026-10-01T10:45:00Z"));
+        assert_eq!(next, Some(at("2026-10-01T10:30:00Z")));
+        assert!(!overdue);
+        let (_, overdue) = schedule(Some(last), every, at("2026-10-01T11:01:00Z"));
+        assert!(overdue);
+        assert_eq!(schedule(None, every, last), (None, false));
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// A single difference between two JSON documents
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct JsonChange {
+    /// Dotted path to the changed value (e.g. "channels.telegram.dmPolicy")
+    pub path: String,
+    /// JSON pointer to the changed value (e.g. "/channels/telegram/dmPolicy"); unlike the
+    /// dotted path it stays unambiguous for keys containing dots, such as model ids
+    pub pointer: String,
+    /// "added" | "removed" | "changed"
+    pub kind: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+/// Whether a key holds a secret whose value must not be shown in a diff
+pub fn is_secret_key(key: &str) -> bool {
+    let lower = key.to_lowercase();
+    ["apikey", "api_key", "token", "secret", "password", "privatekey", "private_key"]
+        .iter()
+        .any(|s| lower.contains(s))
+}
+
+/// Compute the list of changes from `old` to `new` (objects are walked recursively,
+/// arrays and scalars are compared as a whole)
+pub fn diff(old: &Value, new: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    diff_at("", "", old, new, &mut changes);
+    changes
+}
+
+/// Same as `diff`, but values under secret-looking keys are replaced by a mask
+pub fn diff_masked(old: &Value, new: &Value) -> Vec<JsonChange> {
+    diff(old, new)
+        .into_iter()
+        .map(|mut change| {
+            let last_key = change.path.rsplit('.').next().unwrap_or("");
+            if is_secret_key(last_key) {
+                change.old_value = change.old_value.map(|_| Value::String("***".to_string()));
+                change.new_value = change.new_value.map(|_| Value::String("***".to_string()));
+            } else {
+                change.old_value = change.old_value.map(|v| mask_secrets(&v));
+                change.new_value = change.new_value.map(|v| mask_secrets(&v));
+            }
+            change
+        })
+        .collect()
+}
+
+/// Replace every secret-looking field in a JSON value with a mask
+pub fn mask_secrets(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    if is_secret_key(k) && !v.is_object() && !v.is_array() {
+                        (k.clone(), Value::String("***".to_string()))
+                    } else {
+                        (k.clone(), mask_secrets(v))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(mask_secrets).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Dotted paths of every object key in a document, array elements included by index
+/// (e.g. "agents.list.0.id")
+pub fn key_paths(value: &Value) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    collect_key_paths("", value, &mut paths);
+    paths
+}
+
+/// How many of the keys in `old` are gone in `new`, and how many keys `old` has
+pub fn removed_keys(old: &Value, new: &Value) -> (usize, usize) {
+    let old_paths = key_paths(old);
+    let new_paths = key_paths(new);
+    (old_paths.difference(&new_paths).count(), old_paths.len())
+}
+
+fn collect_key_paths(path: &str, value: &Value, paths: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = join_path(path, key);
+                collect_key_paths(&child_path, child, paths);
+                paths.insert(child_path);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_key_paths(&join_path(path, &i.to_string()), item, paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// `pointer` extended by `key` (RFC 6901 escaping)
+fn join_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+fn diff_at(path: &str, pointer: &str, old: &Value, new: &Value, changes: &mut Vec<JsonChange>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_val) in old_map {
+                let child = join_path(path, key);
+                let child_pointer = join_pointer(pointer, key);
+                match new_map.get(key) {
+                    Some(new_val) => diff_at(&child, &child_pointer, old_val, new_val, changes),
+                    None => changes.push(JsonChange {
+                        path: child,
+                        pointer: child_pointer,
+                        kind: "removed".to_string(),
+                        old_value: Some(old_val.clone()),
+                        new_value: None,
+                    }),
+                }
+            }
+            for (key, new_val) in new_map {
+                if !old_map.contains_key(key) {
+                    changes.push(JsonChange {
+                        path: join_path(path, key),
+                        pointer: join_pointer(pointer, key),
+                        kind: "added".to_string(),
+                        old_value: None,
+                        new_value: Some(new_val.clone()),
+                    });
+                }
+            }
+        }
+        _ => {
+            if old != new {
+                changes.push(JsonChange {
+                    path: path.to_string(),
+                    pointer: pointer.to_string(),
+                    kind: "changed".to_string(),
+                    old_value: Some(old.clone()),
+                    new_value: Some(new.clone()),
+                });
+            }
+        }
+    }
+}
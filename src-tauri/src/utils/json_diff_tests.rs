@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_diff::{diff, diff_masked, key_paths, removed_keys};
+    use serde_json::json;
+
+    #[test]
+    fn test_identical_documents_have_no_changes() {
+        let doc = json!({"a": 1, "b": {"c": [1, 2]}});
+        assert!(diff(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn test_added_removed_changed() {
+        let old = json!({"keep": 1, "gone": true, "nested": {"x": "a"}});
+        let new = json!({"keep": 1, "nested": {"x": "b"}, "fresh": 2});
+        let changes = diff(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.iter().any(|c| c.path == "gone" && c.kind == "removed"));
+        assert!(changes.iter().any(|c| c.path == "nested.x" && c.kind == "changed"));
+        assert!(changes.iter().any(|c| c.path == "fresh" && c.kind == "added"));
+    }
+
+    #[test]
+    fn test_pointers_escape_keys() {
+        let old = json!({"models": {"gpt-4.1": 1, "a/b": {"~x": 1}}});
+        let new = json!({"models": {"gpt-4.1": 2, "a/b": {"~x": 2}}});
+        let mut pointers: Vec<String> = diff(&old, &new).into_iter().map(|c| c.pointer).collect();
+        pointers.sort();
+        assert_eq!(pointers, vec!["/models/a~1b/~0x", "/models/gpt-4.1"]);
+    }
+
+    #[test]
+    fn test_masked_diff_hides_secrets() {
+        let old = json!({"channels": {"telegram": {"botToken": "111:old"}}});
+        let new = json!({"channels": {"telegram": {"botToken": "222:new"}}, "models": {"providers": {"x": {"apiKey": "sk-abc"}}}});
+        let rendered = serde_json::to_string(&diff_masked(&old, &new)).unwrap();
+        assert!(!rendered.contains("111:old"));
+        assert!(!rendered.contains("222:new"));
+        assert!(!rendered.contains("sk-abc"));
+    }
+
+    #[test]
+    fn test_key_paths_walk_objects_and_arrays() {
+        let doc = json!({"agents": {"list": [{"id": "main"}, {"id": "ops"}]}, "port": 1});
+        let paths: Vec<String> = key_paths(&doc).into_iter().collect();
+        assert_eq!(paths, vec!["agents", "agents.list", "agents.list.0.id", "agents.list.1.id", "port"]);
+    }
+
+    #[test]
+    fn test_removed_keys_counts_nested_keys() {
+        let old = json!({"gateway": {"port": 1, "auth": {"token": "x"}}, "tools": {"profile": "full"}});
+        let new = json!({"tools": {"profile": "coding"}, "extra": true});
+        assert_eq!(removed_keys(&old, &new), (4, 6));
+        assert_eq!(removed_keys(&old, &old), (0, 6));
+    }
+}
@@ -0,0 +1,139 @@
+//! Expiration dates of provider API keys (manager metadata) and when to remind about them.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Days before the expiration date a reminder starts when none is set
+pub const DEFAULT_REMIND_DAYS: u32 = 7;
+
+/// Key management pages of the official providers, matched by provider id or API host
+const KEY_PAGES: &[(&str, &str, &str)] = &[
+    (
+        "anthropic",
+        "api.anthropic.com",
+        "https://console.anthropic.com/settings/keys",
+    ),
+    (
+        "openai",
+        "api.openai.com",
+        "https://platform.openai.com/api-keys",
+    ),
+    (
+        "moonshot",
+        "api.moonshot",
+        "https://platform.moonshot.ai/console/api-keys",
+    ),
+    (
+        "qwen",
+        "dashscope",
+        "https://bailian.console.aliyun.com/?apiKey=1",
+    ),
+    (
+        "deepseek",
+        "api.deepseek.com",
+        "https://platform.deepseek.com/api_keys",
+    ),
+    (
+        "glm",
+        "bigmodel.cn",
+        "https://open.bigmodel.cn/usercenter/apikeys",
+    ),
+    (
+        "minimax",
+        "minimax",
+        "https://platform.minimax.io/user-center/basic-information/interface-key",
+    ),
+    ("venice", "venice.ai", "https://venice.ai/settings/api"),
+    (
+        "openrouter",
+        "openrouter.ai",
+        "https://openrouter.ai/settings/keys",
+    ),
+    (
+        "google-vertex",
+        "aiplatform.googleapis.com",
+        "https://console.cloud.google.com/iam-admin/serviceaccounts",
+    ),
+    (
+        "google",
+        "generativelanguage.googleapis.com",
+        "https://aistudio.google.com/app/apikey",
+    ),
+];
+
+/// Expiration or planned rotation date of one provider's key (manager.json -> providerKeyExpiry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyExpiry {
+    /// YYYY-MM-DD
+    pub expires_on: String,
+    #[serde(default = "default_remind_days")]
+    pub remind_days_before: u32,
+    /// Overrides the known key management page
+    #[serde(default)]
+    pub manage_url: Option<String>,
+    /// Day of the last reminder, so each day reminds once
+    #[serde(default)]
+    pub last_reminded_on: Option<String>,
+}
+
+fn default_remind_days() -> u32 {
+    DEFAULT_REMIND_DAYS
+}
+
+pub fn parse_day(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", text))
+}
+
+/// Key management page for a provider: the entry's own URL, else the official provider with
+/// the same id (or id prefix, e.g. "anthropic-work"), else the one whose API host matches
+pub fn key_management_url(
+    provider: &str,
+    base_url: Option<&str>,
+    expiry: Option<&KeyExpiry>,
+) -> Option<String> {
+    if let Some(url) = expiry
+        .and_then(|e| e.manage_url.clone())
+        .filter(|u| !u.is_empty())
+    {
+        return Some(url);
+    }
+    let provider = provider.to_ascii_lowercase();
+    KEY_PAGES
+        .iter()
+        .find(|(id, _, _)| provider == *id || provider.starts_with(&format!("{}-", id)))
+        .or_else(|| {
+            let base_url = base_url?.to_ascii_lowercase();
+            KEY_PAGES
+                .iter()
+                .find(|(_, host, _)| base_url.contains(host))
+        })
+        .map(|(_, _, url)| url.to_string())
+}
+
+/// Days until the key expires (negative once expired)
+pub fn days_left(expiry: &KeyExpiry, today: NaiveDate) -> Option<i64> {
+    let expires = parse_day(&expiry.expires_on).ok()?;
+    Some((expires - today).num_days())
+}
+
+/// Whether to remind today: inside the reminder window (or expired) and not reminded yet today
+pub fn reminder_due(expiry: &KeyExpiry, today: NaiveDate) -> bool {
+    let Some(left) = days_left(expiry, today) else {
+        return false;
+    };
+    let today_text = today.format("%Y-%m-%d").to_string();
+    left <= expiry.remind_days_before as i64
+        && expiry.last_reminded_on.as_deref() != Some(today_text.as_str())
+}
+
+/// Reminder text for a key with `left` days remaining
+pub fn reminder_message(provider: &str, left: i64) -> String {
+    match left {
+        l if l < 0 => format!("The {} API key expired {} day(s) ago", provider, -l),
+        0 => format!("The {} API key expires today", provider),
+        1 => format!("The {} API key expires tomorrow", provider),
+        l => format!("The {} API key expires in {} days", provider, l),
+    }
+}
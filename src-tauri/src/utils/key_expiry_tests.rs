@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use super::super::key_expiry::{
+        days_left, key_management_url, reminder_due, reminder_message, KeyExpiry,
+    };
+    use chrono::NaiveDate;
+
+    fn expiry(expires_on: &str, last_reminded_on: Option<&str>) -> KeyExpiry {
+        KeyExpiry {
+            expires_on: expires_on.to_string(),
+            remind_days_before: 7,
+            manage_url: None,
+            last_reminded_on: last_reminded_on.map(String::from),
+        }
+    }
+
+    fn day(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_reminder_window() {
+        let key = expiry("2026-10-20", None);
+        assert_eq!(days_left(&key, day("2026-10-10")), Some(10));
+        assert!(!reminder_due(&key, day("2026-10-12")));
+        assert!(reminder_due(&key, day("2026-10-13")));
+        // Expired keys keep reminding
+        assert!(reminder_due(&key, day("2026-10-25")));
+    }
+
+    #[test]
+    fn test_reminds_once_a_day() {
+        let key = expiry("2026-10-20", Some("2026-10-15"));
+        assert!(!reminder_due(&key, day("2026-10-15")));
+        assert!(reminder_due(&key, day("2026-10-16")));
+    }
+
+    #[test]
+    fn test_key_management_url() {
+        assert_eq!(
+            key_management_url("anthropic", None, None).as_deref(),
+            Some("https://console.anthropic.com/settings/keys")
+        );
+        assert_eq!(
+            key_management_url("anthropic-work", None, None).as_deref(),
+            Some("https://console.anthropic.com/settings/keys")
+        );
+        assert_eq!(
+            key_management_url("my-proxy", Some("https://openrouter.ai/api/v1"), None).as_deref(),
+            Some("https://openrouter.ai/settings/keys")
+        );
+        assert_eq!(
+            key_management_url("google-vertex", None, None).as_deref(),
+            Some("https://console.cloud.google.com/iam-admin/serviceaccounts")
+        );
+        assert_eq!(
+            key_management_url("local", Some("http://localhost:8080"), None),
+            None
+        );
+
+        let mut custom = expiry("2026-10-20", None);
+        custom.manage_url = Some("https://keys.example.com".to_string());
+        assert_eq!(
+            key_management_url("anthropic", None, Some(&custom)).as_deref(),
+            Some("https://keys.example.com")
+        );
+    }
+
+    #[test]
+    fn test_reminder_message() {
+        assert_eq!(
+            reminder_message("openai", 3),
+            "The openai API key expires in 3 days"
+        );
+        assert_eq!(
+            reminder_message("openai", 0),
+            "The openai API key expires today"
+        );
+        assert_eq!(
+            reminder_message("openai", -2),
+            "The openai API key expired 2 day(s) ago"
+        );
+    }
+}
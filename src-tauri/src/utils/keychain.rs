@@ -0,0 +1,115 @@
+//! `${secret:name}` values kept in the OS credential store (macOS Keychain, Windows
+//! Credential Manager, Secret Service on Linux) instead of manager/secrets.json. manager.json
+//! only records which secret names live there; openclaw.json carries the placeholder.
+
+use crate::utils::{placeholders, platform};
+use log::warn;
+use std::sync::{OnceLock, RwLock};
+
+/// manager.json key listing the secrets stored in the keychain
+pub const SETTINGS_KEY: &str = "keychainSecrets";
+
+/// Service name the entries are filed under
+const SERVICE: &str = "openclaw-manager";
+
+fn entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, name).map_err(|e| format!("Keychain is not available: {}", e))
+}
+
+/// Store a secret value
+pub fn set(name: &str, value: &str) -> Result<(), String> {
+    entry(name)?
+        .set_password(value)
+        .map_err(|e| format!("Failed to store '{}' in the keychain: {}", name, e))
+}
+
+/// Value of a secret, None when the keychain has no entry for it
+pub fn get(name: &str) -> Result<Option<String>, String> {
+    match entry(name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!(
+            "Failed to read '{}' from the keychain: {}",
+            name, e
+        )),
+    }
+}
+
+/// Remove a secret; a missing entry is not an error
+pub fn delete(name: &str) -> Result<(), String> {
+    match entry(name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!(
+            "Failed to delete '{}' from the keychain: {}",
+            name, e
+        )),
+    }
+}
+
+/// Whether the credential store works here (a headless Linux box may have no Secret Service)
+pub fn probe() -> Result<(), String> {
+    let name = "manager-probe";
+    set(name, "probe")?;
+    let read = get(name);
+    delete(name)?;
+    match read? {
+        Some(value) if value == "probe" => Ok(()),
+        _ => Err("Keychain did not return the stored value".to_string()),
+    }
+}
+
+/// Names of the secrets stored in the keychain, loaded from manager.json on first use
+static NAMES: OnceLock<RwLock<Vec<String>>> = OnceLock::new();
+
+fn names_lock() -> &'static RwLock<Vec<String>> {
+    NAMES.get_or_init(|| {
+        let value = std::fs::read_to_string(platform::get_manager_config_file_path())
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}'))
+                    .ok()
+            })
+            .and_then(|config| config.get(SETTINGS_KEY).cloned())
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        RwLock::new(value)
+    })
+}
+
+pub fn names() -> Vec<String> {
+    names_lock().read().map(|n| n.clone()).unwrap_or_default()
+}
+
+/// Update the in-memory list (manager.json is written by the caller)
+pub fn set_names(names: Vec<String>) {
+    if let Ok(mut value) = names_lock().write() {
+        *value = names;
+    }
+}
+
+/// Values of the keychain secrets, by secret name; unreadable ones are logged and left out
+pub fn values() -> Vec<(String, String)> {
+    names()
+        .into_iter()
+        .filter_map(|name| match get(&name) {
+            Ok(Some(value)) => Some((name, value)),
+            Ok(None) => {
+                warn!("[Keychain] Secret '{}' has no keychain entry", name);
+                None
+            }
+            Err(e) => {
+                warn!("[Keychain] {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Env variables the gateway reads the keychain secrets from (the native form of
+/// `${secret:name}` references)
+pub fn gateway_env() -> Vec<(String, String)> {
+    values()
+        .into_iter()
+        .map(|(name, value)| (placeholders::secret_env_var(&name), value))
+        .collect()
+}
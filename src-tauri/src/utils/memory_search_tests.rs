@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::config::{memory_search, memory_search_mut};
+    use serde_json::json;
+
+    #[test]
+    fn test_top_level_block_moves_under_agent_defaults() {
+        let mut config = json!({
+            "memorySearch": { "provider": "openai", "model": "old", "remote": { "apiKey": "sk-1" } },
+            "agents": { "defaults": { "memorySearch": { "model": "text-embedding-3-small" } } }
+        });
+        assert_eq!(
+            memory_search(&config).unwrap()["model"],
+            "text-embedding-3-small"
+        );
+
+        memory_search_mut(&mut config).insert("enabled".to_string(), json!(false));
+        assert!(config.get("memorySearch").is_none());
+        assert_eq!(
+            config["agents"]["defaults"]["memorySearch"],
+            json!({
+                "enabled": false,
+                "provider": "openai",
+                "model": "text-embedding-3-small",
+                "remote": { "apiKey": "sk-1" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_block_is_read_until_moved() {
+        let config = json!({ "memorySearch": { "provider": "gemini" } });
+        assert_eq!(memory_search(&config).unwrap()["provider"], "gemini");
+        assert!(memory_search(&json!({})).is_none());
+    }
+}
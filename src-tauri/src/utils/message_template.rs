@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+/// Variables available to message templates
+pub const TEMPLATE_VARIABLES: &[&str] = &["time", "date", "agent", "version", "channel"];
+
+/// Replace `{name}` placeholders with values from `vars`. Unknown placeholders are left as-is
+/// so a typo shows up in the sent message instead of silently disappearing; `{{` and `}}`
+/// produce literal braces.
+pub fn render(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if let Some(end) = tail.find('}').filter(|&end| tail.starts_with('{') && !tail[1..end].contains('{')) {
+            let name = &tail[1..end];
+            match vars.get(name) {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&tail[..=end]),
+            }
+            rest = &tail[end + 1..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Placeholders used in `template` that are not known template variables
+pub fn unknown_variables(template: &str) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("{{") {
+            rest = after;
+            continue;
+        }
+        match tail.find('}') {
+            Some(end) => {
+                let name = &tail[1..end];
+                if !TEMPLATE_VARIABLES.contains(&name) && !unknown.iter().any(|u| u == name) {
+                    unknown.push(name.to_string());
+                }
+                rest = &tail[end + 1..];
+            }
+            None => break,
+        }
+    }
+    unknown
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use super::super::message_template::{render, unknown_variables};
+    use std::collections::HashMap;
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([("time", "09:00".to_string()), ("agent", "main".to_string())])
+    }
+
+    #[test]
+    fn test_render_replaces_known_variables() {
+        assert_eq!(render("Ping from {agent} at {time}", &vars()), "Ping from main at 09:00");
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_and_escaped_braces() {
+        assert_eq!(render("{agnet} {{literal}} {time}", &vars()), "{agnet} {literal} 09:00");
+        assert_eq!(render("unclosed {time", &vars()), "unclosed {time");
+        assert_eq!(render("{a {time}", &vars()), "{a 09:00");
+    }
+
+    #[test]
+    fn test_unknown_variables() {
+        assert_eq!(unknown_variables("{time} {agnet} {{x}} {agnet}"), vec!["agnet".to_string()]);
+        assert!(unknown_variables("{time} {date} {agent} {version} {channel}").is_empty());
+    }
+}
@@ -1,7 +1,132 @@
+pub mod chat;
+pub mod clipboard;
+pub mod config_drift;
+pub mod config_include;
+pub mod config_schema;
+pub mod config_watch;
+pub mod console;
+pub mod contacts;
+pub mod crash_report;
+pub mod crypto;
+pub mod executor;
+#[cfg(test)]
+pub mod fake_gateway;
 pub mod file;
+pub mod gateway_token;
+pub mod gemini;
+pub mod heartbeat;
+pub mod json_diff;
+pub mod key_expiry;
+pub mod keychain;
 pub mod log_sanitizer;
+pub mod message_template;
+pub mod network_usage;
+pub mod node_pm;
+pub mod pkgmgr;
+pub mod placeholders;
 pub mod platform;
+pub mod plugin_review;
+pub mod process_limits;
+pub mod process_tree;
+pub mod prompt_cost;
+pub mod resource_alerts;
+pub mod resource_floor;
+pub mod retention;
+pub mod provider_headers;
+pub mod secrets_scan;
+pub mod session_replay;
+pub mod setting_location;
 pub mod shell;
+pub mod snapshots;
+pub mod sync_conflict;
+pub mod task_pool;
+pub mod wsl;
 
 #[cfg(test)]
 mod log_sanitizer_tests;
+#[cfg(test)]
+mod json_diff_tests;
+#[cfg(test)]
+mod config_include_tests;
+#[cfg(test)]
+mod sync_conflict_tests;
+#[cfg(test)]
+mod crypto_tests;
+#[cfg(test)]
+mod secrets_scan_tests;
+#[cfg(test)]
+mod message_template_tests;
+#[cfg(test)]
+mod setting_location_tests;
+#[cfg(test)]
+mod platform_tests;
+#[cfg(test)]
+mod process_tree_tests;
+#[cfg(test)]
+mod provider_headers_tests;
+#[cfg(test)]
+mod gemini_tests;
+#[cfg(test)]
+mod executor_tests;
+#[cfg(test)]
+mod retention_tests;
+#[cfg(test)]
+mod gateway_tests;
+#[cfg(test)]
+mod bindings_tests;
+#[cfg(test)]
+mod api_tests;
+#[cfg(test)]
+mod trash_tests;
+#[cfg(test)]
+mod placeholders_tests;
+#[cfg(test)]
+mod config_drift_tests;
+#[cfg(test)]
+mod tasks_tests;
+#[cfg(test)]
+mod resource_alerts_tests;
+#[cfg(test)]
+mod session_replay_tests;
+#[cfg(test)]
+mod heartbeat_tests;
+#[cfg(test)]
+mod token_rotation_tests;
+#[cfg(test)]
+mod key_expiry_tests;
+#[cfg(test)]
+mod node_pm_tests;
+#[cfg(test)]
+mod wsl_tests;
+#[cfg(test)]
+mod resource_floor_tests;
+#[cfg(test)]
+mod process_limits_tests;
+#[cfg(test)]
+mod network_usage_tests;
+#[cfg(test)]
+mod prompt_cost_tests;
+#[cfg(test)]
+mod gateway_token_tests;
+#[cfg(test)]
+mod contacts_tests;
+#[cfg(test)]
+mod chat_tests;
+#[cfg(test)]
+mod snapshots_tests;
+#[cfg(test)]
+mod file_tests;
+#[cfg(test)]
+mod plugin_review_tests;
+#[cfg(test)]
+mod config_watch_tests;
+#[cfg(test)]
+mod task_pool_tests;
+#[cfg(test)]
+mod config_schema_tests;
+#[cfg(test)]
+mod console_tests;
+#[cfg(test)]
+mod crash_report_tests;
+#[cfg(test)]
+mod memory_search_tests;
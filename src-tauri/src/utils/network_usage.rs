@@ -0,0 +1,208 @@
+//! Bytes the gateway sends and receives over the network, totalled per day. Linux reads the
+//! per-socket counters of `ss -tinp` (loopback peers such as the Manager itself are skipped),
+//! macOS the per-process counters of `nettop`. Windows has no per-process byte counters
+//! without ETW, so traffic is not metered there.
+
+use crate::utils::shell;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Days of totals kept
+pub const HISTORY_DAYS: usize = 90;
+
+/// Traffic of one local calendar day
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct NetworkDay {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Cumulative counters of one socket or process
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counter {
+    pub key: String,
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Counters seen in the previous sample and the daily totals built from their growth
+#[derive(Debug, Default)]
+pub struct NetworkMeter {
+    last: HashMap<String, (u64, u64)>,
+    primed: bool,
+    /// Oldest first, at most `HISTORY_DAYS`
+    pub days: Vec<NetworkDay>,
+}
+
+impl NetworkMeter {
+    pub fn with_days(days: Vec<NetworkDay>) -> Self {
+        NetworkMeter {
+            days,
+            ..NetworkMeter::default()
+        }
+    }
+
+    /// Add the traffic since the previous sample to `date`, returning whether a total changed.
+    /// The first sample only sets the baseline (its counters include traffic from before the
+    /// Manager started); sockets or processes that appear later count in full.
+    pub fn observe(&mut self, date: &str, counters: &[Counter]) -> bool {
+        let mut sent = 0;
+        let mut received = 0;
+        if self.primed {
+            for c in counters {
+                let (last_sent, last_received) = self.last.get(&c.key).copied().unwrap_or((0, 0));
+                // Counters going backwards belong to a new socket or process with the same key
+                sent += if c.sent >= last_sent {
+                    c.sent - last_sent
+                } else {
+                    c.sent
+                };
+                received += if c.received >= last_received {
+                    c.received - last_received
+                } else {
+                    c.received
+                };
+            }
+        }
+        self.primed = true;
+        self.last = counters
+            .iter()
+            .map(|c| (c.key.clone(), (c.sent, c.received)))
+            .collect();
+
+        if sent == 0 && received == 0 {
+            return false;
+        }
+        match self.days.last_mut().filter(|d| d.date == date) {
+            Some(day) => {
+                day.bytes_sent += sent;
+                day.bytes_received += received;
+            }
+            None => self.days.push(NetworkDay {
+                date: date.to_string(),
+                bytes_sent: sent,
+                bytes_received: received,
+            }),
+        }
+        if self.days.len() > HISTORY_DAYS {
+            let excess = self.days.len() - HISTORY_DAYS;
+            self.days.drain(..excess);
+        }
+        true
+    }
+}
+
+/// Whether an `address:port` (or `[address]:port`) is a loopback address
+pub fn is_loopback(address: &str) -> bool {
+    let host = address
+        .rsplit_once(':')
+        .map(|(host, _)| host)
+        .unwrap_or(address)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    host.starts_with("127.") || host == "::1" || host.starts_with("::ffff:127.")
+}
+
+fn field_value(line: &str, name: &str) -> Option<u64> {
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix(name)?.strip_prefix(':'))
+        .and_then(|v| v.parse().ok())
+}
+
+/// Counters of the TCP sockets owned by `pids` with a non-loopback peer, from `ss -tinpHn`.
+/// Each socket is one line with the addresses and owners, followed by an indented line of
+/// tcp_info fields (bytes_sent needs Linux 4.19, bytes_acked is used before that).
+pub fn parse_ss(text: &str, pids: &[u32]) -> Vec<Counter> {
+    let mut counters = Vec::new();
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            current = None;
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let (Some(local), Some(peer)) = (parts.get(3), parts.get(4)) else {
+                continue;
+            };
+            let owned = line.match_indices("pid=").any(|(i, _)| {
+                let digits: String = line[i + 4..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                digits.parse::<u32>().is_ok_and(|pid| pids.contains(&pid))
+            });
+            if owned && !is_loopback(peer) {
+                current = Some(format!("{}>{}", local, peer));
+            }
+        } else if let Some(key) = current.take() {
+            let sent = field_value(line, "bytes_sent").or_else(|| field_value(line, "bytes_acked"));
+            counters.push(Counter {
+                key,
+                sent: sent.unwrap_or(0),
+                received: field_value(line, "bytes_received").unwrap_or(0),
+            });
+        }
+    }
+    counters
+}
+
+/// Counters of `pids` from `nettop -P -L 1 -x -J bytes_in,bytes_out`: a CSV header, then one
+/// row per process named `<name>.<pid>`
+pub fn parse_nettop(text: &str, pids: &[u32]) -> Vec<Counter> {
+    let mut lines = text.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let (Some(in_col), Some(out_col)) = (
+        columns.iter().position(|c| *c == "bytes_in"),
+        columns.iter().position(|c| *c == "bytes_out"),
+    ) else {
+        return Vec::new();
+    };
+    lines
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            // The time column (12:00:00.123456) also has a dot
+            let pid = cells
+                .iter()
+                .filter(|cell| !cell.contains(':'))
+                .find_map(|cell| {
+                    let (_, pid) = cell.rsplit_once('.')?;
+                    pid.parse::<u32>().ok()
+                })?;
+            if !pids.contains(&pid) {
+                return None;
+            }
+            Some(Counter {
+                key: format!("pid:{}", pid),
+                sent: cells.get(out_col)?.parse().ok()?,
+                received: cells.get(in_col)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// How traffic is measured on `os`, None where per-process accounting is unavailable
+pub fn method(os: &str) -> Option<&'static str> {
+    match os {
+        "linux" => Some("ss"),
+        "macos" => Some("nettop"),
+        _ => None,
+    }
+}
+
+/// Current counters of `pids`, None when the platform tool is missing or failed
+pub fn sample(pids: &[u32]) -> Option<Vec<Counter>> {
+    match method(std::env::consts::OS)? {
+        "ss" => shell::run_command_output("ss", &["-tinpHn"])
+            .ok()
+            .map(|out| parse_ss(&out, pids)),
+        _ => shell::run_command_output(
+            "nettop",
+            &["-P", "-L", "1", "-x", "-J", "bytes_in,bytes_out"],
+        )
+        .ok()
+        .map(|out| parse_nettop(&out, pids)),
+    }
+}
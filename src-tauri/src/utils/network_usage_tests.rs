@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use super::super::network_usage::{
+        is_loopback, parse_nettop, parse_ss, Counter, NetworkDay, NetworkMeter, HISTORY_DAYS,
+    };
+
+    fn counter(key: &str, sent: u64, received: u64) -> Counter {
+        Counter {
+            key: key.to_string(),
+            sent,
+            received,
+        }
+    }
+
+    #[test]
+    fn test_meter_counts_growth_after_the_baseline() {
+        let mut meter = NetworkMeter::default();
+        // The first sample is the baseline
+        assert!(!meter.observe("2026-10-16", &[counter("a", 5000, 9000)]));
+        assert!(meter.days.is_empty());
+
+        assert!(meter.observe(
+            "2026-10-16",
+            &[counter("a", 5600, 9100), counter("b", 200, 300)]
+        ));
+        assert_eq!(
+            meter.days,
+            vec![NetworkDay {
+                date: "2026-10-16".to_string(),
+                bytes_sent: 800,
+                bytes_received: 400,
+            }]
+        );
+
+        // A counter going backwards is a new socket with the same key
+        meter.observe("2026-10-17", &[counter("a", 50, 70)]);
+        assert_eq!(meter.days.len(), 2);
+        assert_eq!(meter.days[1].bytes_sent, 50);
+        assert_eq!(meter.days[1].bytes_received, 70);
+
+        assert!(!meter.observe("2026-10-17", &[counter("a", 50, 70)]));
+    }
+
+    #[test]
+    fn test_meter_keeps_history_days() {
+        let days = (0..HISTORY_DAYS)
+            .map(|i| NetworkDay {
+                date: format!("day-{:03}", i),
+                bytes_sent: 1,
+                bytes_received: 1,
+            })
+            .collect();
+        let mut meter = NetworkMeter::with_days(days);
+        meter.observe("new", &[]);
+        meter.observe("new", &[counter("a", 10, 10)]);
+        assert_eq!(meter.days.len(), HISTORY_DAYS);
+        assert_eq!(meter.days[0].date, "day-001");
+        assert_eq!(meter.days.last().map(|d| d.date.as_str()), Some("new"));
+    }
+
+    #[test]
+    fn test_is_loopback() {
+        assert!(is_loopback("127.0.0.1:18789"));
+        assert!(is_loopback("[::1]:18789"));
+        assert!(is_loopback("[::ffff:127.0.0.1]:443"));
+        assert!(!is_loopback("104.18.3.1:443"));
+        assert!(!is_loopback("[2606:4700::6812:301]:443"));
+    }
+
+    #[test]
+    fn test_parse_ss() {
+        let text = "ESTAB 0 0 192.168.1.5:51234 104.18.3.1:443 users:((\"node\",pid=4242,fd=23))\n\
+                    \t cubic wscale:7,7 bytes_sent:1520 bytes_acked:1521 bytes_received:8831 segs_out:12\n\
+                    ESTAB 0 0 127.0.0.1:18789 127.0.0.1:40024 users:((\"node\",pid=4242,fd=30))\n\
+                    \t cubic bytes_sent:99 bytes_received:99\n\
+                    ESTAB 0 0 192.168.1.5:40000 140.82.112.3:443 users:((\"git\",pid=77,fd=3))\n\
+                    \t cubic bytes_sent:5 bytes_received:5\n\
+                    ESTAB 0 0 [2001:db8::2]:50000 [2606:4700::1]:443 users:((\"node\",pid=4250,fd=9))\n\
+                    \t cubic bytes_acked:40 bytes_received:60\n";
+        let counters = parse_ss(text, &[4242, 4250]);
+        assert_eq!(
+            counters,
+            vec![
+                counter("192.168.1.5:51234>104.18.3.1:443", 1520, 8831),
+                counter("[2001:db8::2]:50000>[2606:4700::1]:443", 40, 60),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_nettop() {
+        let text = "time,,bytes_in,bytes_out,\n\
+                    12:00:00.000000,node.4242,123456,7890,\n\
+                    12:00:00.000000,Safari.311,1,1,\n";
+        assert_eq!(
+            parse_nettop(text, &[4242]),
+            vec![counter("pid:4242", 7890, 123456)]
+        );
+        assert!(parse_nettop("garbage", &[4242]).is_empty());
+    }
+}
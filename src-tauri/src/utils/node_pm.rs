@@ -0,0 +1,241 @@
+//! Node.js package manager used for global installs (OpenClaw, clawhub) and MCP packages:
+//! npm, pnpm or Yarn 1, chosen by the user's preference (manager.json) or by availability.
+
+use crate::utils::{platform, shell, wsl};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+/// manager.json key holding the preferred package manager ("npm", "pnpm", "yarn"; absent = auto)
+pub const PREFERENCE_KEY: &str = "nodePackageManager";
+
+/// manager.json key holding the global bin directory resolved after the last install
+pub const GLOBAL_BIN_KEY: &str = "nodeGlobalBin";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum NodePackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+/// Order used when no preference is set (or the preferred one is missing)
+pub const ALL: [NodePackageManager; 3] = [
+    NodePackageManager::Npm,
+    NodePackageManager::Pnpm,
+    NodePackageManager::Yarn,
+];
+
+impl NodePackageManager {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodePackageManager::Npm => "npm",
+            NodePackageManager::Pnpm => "pnpm",
+            NodePackageManager::Yarn => "yarn",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<NodePackageManager> {
+        ALL.into_iter()
+            .find(|pm| pm.name().eq_ignore_ascii_case(text.trim()))
+    }
+
+    pub fn is_available(&self) -> bool {
+        shell::command_exists(self.name())
+    }
+
+    /// Command line installing (or updating) a package globally
+    pub fn global_add(&self, package: &str) -> String {
+        match self {
+            NodePackageManager::Npm => format!("npm install -g {}", package),
+            NodePackageManager::Pnpm => format!("pnpm add -g {}", package),
+            NodePackageManager::Yarn => format!("yarn global add {}", package),
+        }
+    }
+
+    pub fn global_remove(&self, package: &str) -> String {
+        match self {
+            NodePackageManager::Npm => format!("npm uninstall -g {}", package),
+            NodePackageManager::Pnpm => format!("pnpm remove -g {}", package),
+            NodePackageManager::Yarn => format!("yarn global remove {}", package),
+        }
+    }
+
+    /// Command line listing global packages (see `list_mentions`)
+    pub fn global_list(&self, package: &str) -> String {
+        match self {
+            NodePackageManager::Npm => format!("npm list -g {} --depth=0", package),
+            NodePackageManager::Pnpm => format!("pnpm list -g {} --depth=0", package),
+            NodePackageManager::Yarn => "yarn global list".to_string(),
+        }
+    }
+
+    /// Command line printing the latest published version of a package
+    pub fn view_version(&self, package: &str) -> String {
+        match self {
+            NodePackageManager::Npm => format!("npm view {} version", package),
+            NodePackageManager::Pnpm => format!("pnpm view {} version", package),
+            NodePackageManager::Yarn => format!("yarn info {} version --silent", package),
+        }
+    }
+
+    /// Command line printing the directory global binaries are linked into
+    pub fn global_bin_command(&self) -> &'static str {
+        match self {
+            NodePackageManager::Npm => "npm prefix -g",
+            NodePackageManager::Pnpm => "pnpm bin -g",
+            NodePackageManager::Yarn => "yarn global bin",
+        }
+    }
+
+    /// Arguments adding one package to the current directory
+    pub fn add_args<'a>(&self, package: &'a str) -> Vec<&'a str> {
+        match self {
+            NodePackageManager::Npm => vec!["install", "--no-audit", "--no-fund", package],
+            NodePackageManager::Pnpm | NodePackageManager::Yarn => vec!["add", package],
+        }
+    }
+
+    /// Command line prefix running a package without installing it. Yarn 1 (the only Yarn
+    /// with global installs) has no dlx, so it uses npx, which ships with Node.js.
+    pub fn exec_prefix(&self) -> &'static str {
+        match self {
+            NodePackageManager::Pnpm => "pnpm dlx",
+            NodePackageManager::Npm | NodePackageManager::Yarn => "npx -y",
+        }
+    }
+}
+
+/// The preferred manager when it is available, else the first available one in `ALL` order
+pub fn choose(
+    preference: Option<NodePackageManager>,
+    available: &[NodePackageManager],
+) -> Option<NodePackageManager> {
+    preference
+        .filter(|pm| available.contains(pm))
+        .or_else(|| ALL.into_iter().find(|pm| available.contains(pm)))
+}
+
+/// Global bin directory from the output of `global_bin_command` (npm prints the prefix, whose
+/// bin directory is `<prefix>/bin` on Unix and the prefix itself on Windows)
+pub fn parse_global_bin(pm: NodePackageManager, output: &str, windows: bool) -> Option<String> {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && (l.starts_with('/') || l.contains(":\\")))?;
+    let dir = line.trim_end_matches(['/', '\\']);
+    match pm {
+        NodePackageManager::Npm if !windows => Some(format!("{}/bin", dir)),
+        _ => Some(dir.to_string()),
+    }
+}
+
+/// Whether `global_list` output shows `package` installed: npm and yarn print
+/// `<package>@<version>`, pnpm prints `<package> <version>`
+pub fn list_mentions(output: &str, package: &str) -> bool {
+    output.lines().any(|line| {
+        let line = line.trim().trim_start_matches(['├', '└', '─', '│', ' ']);
+        let line = line.trim_start_matches("info ").trim_start_matches('"');
+        line.starts_with(&format!("{}@", package)) || line.starts_with(&format!("{} ", package))
+    })
+}
+
+/// Process for a command line of this module (`global_add`, `exec_prefix`, ...), run
+/// through the .cmd shim on Windows and with the extended PATH elsewhere
+pub fn command(line: &str) -> Command {
+    let mut parts = line.split_whitespace();
+    let name = parts.next().unwrap_or_default();
+    let mut cmd = if platform::is_windows() {
+        Command::new(format!("{}.cmd", name))
+    } else {
+        let mut cmd = Command::new(name);
+        cmd.env("PATH", shell::get_extended_path());
+        cmd
+    };
+    cmd.args(parts);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    cmd
+}
+
+/// Managers found on this machine, in `ALL` order
+pub fn detect() -> Vec<NodePackageManager> {
+    ALL.into_iter().filter(|pm| pm.is_available()).collect()
+}
+
+struct Settings {
+    preference: Option<NodePackageManager>,
+    global_bin: Option<String>,
+}
+
+/// Preference and resolved global bin, loaded from manager.json on first use
+static SETTINGS: OnceLock<RwLock<Settings>> = OnceLock::new();
+
+fn settings() -> &'static RwLock<Settings> {
+    SETTINGS.get_or_init(|| {
+        let config = std::fs::read_to_string(platform::get_manager_config_file_path())
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}'))
+                    .ok()
+            })
+            .unwrap_or_default();
+        let text = |key: &str| {
+            config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        RwLock::new(Settings {
+            preference: text(PREFERENCE_KEY).and_then(|s| NodePackageManager::parse(&s)),
+            global_bin: text(GLOBAL_BIN_KEY),
+        })
+    })
+}
+
+/// Preferred manager, None for automatic selection
+pub fn preference() -> Option<NodePackageManager> {
+    settings().read().ok().and_then(|s| s.preference)
+}
+
+/// Update the in-memory preference (manager.json is written by the caller)
+pub fn set_preference(preference: Option<NodePackageManager>) {
+    if let Ok(mut s) = settings().write() {
+        s.preference = preference;
+    }
+}
+
+/// Manager to use now; npm when none is detected so error messages name the usual tool
+pub fn active() -> NodePackageManager {
+    // In WSL mode the distro's managers cannot be detected from Windows
+    if wsl::active().is_some() {
+        return preference().unwrap_or(NodePackageManager::Npm);
+    }
+    choose(preference(), &detect()).unwrap_or(NodePackageManager::Npm)
+}
+
+/// Global bin directory resolved after the last install or update
+pub fn global_bin() -> Option<String> {
+    settings().read().ok().and_then(|s| s.global_bin.clone())
+}
+
+/// Ask `pm` where it links global binaries and remember the answer for PATH and openclaw
+/// lookup (manager.json is written by the caller)
+pub fn resolve_global_bin(pm: NodePackageManager) -> Option<String> {
+    let output = if platform::is_windows() {
+        shell::run_cmd_output(pm.global_bin_command())
+    } else {
+        shell::run_bash_output(&format!("{} 2>/dev/null", pm.global_bin_command()))
+    }
+    .ok()?;
+    let dir = parse_global_bin(pm, &output, platform::is_windows())?;
+    if let Ok(mut s) = settings().write() {
+        s.global_bin = Some(dir.clone());
+    }
+    Some(dir)
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use super::super::node_pm::{choose, list_mentions, parse_global_bin, NodePackageManager};
+
+    #[test]
+    fn test_choose_prefers_the_available_preference() {
+        use NodePackageManager::*;
+        assert_eq!(choose(Some(Pnpm), &[Npm, Pnpm]), Some(Pnpm));
+        assert_eq!(choose(Some(Yarn), &[Npm, Pnpm]), Some(Npm));
+        assert_eq!(choose(None, &[Pnpm, Yarn]), Some(Pnpm));
+        assert_eq!(choose(Some(Npm), &[]), None);
+    }
+
+    #[test]
+    fn test_global_commands() {
+        assert_eq!(
+            NodePackageManager::Pnpm.global_add("openclaw@latest"),
+            "pnpm add -g openclaw@latest"
+        );
+        assert_eq!(
+            NodePackageManager::Yarn.global_remove("openclaw"),
+            "yarn global remove openclaw"
+        );
+        assert_eq!(NodePackageManager::Yarn.exec_prefix(), "npx -y");
+        assert_eq!(
+            NodePackageManager::parse(" PNPM "),
+            Some(NodePackageManager::Pnpm)
+        );
+        assert_eq!(NodePackageManager::parse("bun"), None);
+    }
+
+    #[test]
+    fn test_parse_global_bin() {
+        assert_eq!(
+            parse_global_bin(NodePackageManager::Npm, "/usr/local\n", false).as_deref(),
+            Some("/usr/local/bin")
+        );
+        assert_eq!(
+            parse_global_bin(
+                NodePackageManager::Npm,
+                "C:\\Users\\me\\AppData\\Roaming\\npm\r\n",
+                true
+            )
+            .as_deref(),
+            Some("C:\\Users\\me\\AppData\\Roaming\\npm")
+        );
+        assert_eq!(
+            parse_global_bin(
+                NodePackageManager::Pnpm,
+                " WARN  deprecated\n/home/me/.local/share/pnpm/\n",
+                false
+            )
+            .as_deref(),
+            Some("/home/me/.local/share/pnpm")
+        );
+        assert_eq!(
+            parse_global_bin(NodePackageManager::Yarn, "command not found", false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_mentions_each_managers_format() {
+        assert!(list_mentions(
+            "/usr/local/lib\n└── clawhub@0.3.1\n",
+            "clawhub"
+        ));
+        assert!(list_mentions(
+            "Legend: production dependency\n\ndependencies:\nclawhub 0.3.1\n",
+            "clawhub"
+        ));
+        assert!(list_mentions(
+            "info \"clawhub@0.3.1\" has binaries:\n   - clawhub\n",
+            "clawhub"
+        ));
+        assert!(!list_mentions("└── clawhub-tools@1.0.0\n", "clawhub"));
+        assert!(!list_mentions("/usr/local/lib\n└── (empty)\n", "clawhub"));
+    }
+}
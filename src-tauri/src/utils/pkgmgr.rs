@@ -0,0 +1,315 @@
+use crate::utils::{platform, shell};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+/// System package managers the Manager knows how to drive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Homebrew,
+    Winget,
+    Scoop,
+    Apt,
+    Dnf,
+    Yum,
+    Pacman,
+}
+
+/// Dependencies that can be installed through a package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum Package {
+    Node,
+    Git,
+    Cloudflared,
+}
+
+/// Result of one step of an install/upgrade
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PackageStep {
+    pub name: String,
+    pub command: String,
+    pub success: bool,
+    pub output: String,
+}
+
+impl PackageManager {
+    /// Executable used to detect the package manager
+    pub fn binary(&self) -> &'static str {
+        match self {
+            PackageManager::Homebrew => "brew",
+            PackageManager::Winget => "winget",
+            PackageManager::Scoop => "scoop",
+            PackageManager::Apt => "apt-get",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Yum => "yum",
+            PackageManager::Pacman => "pacman",
+        }
+    }
+
+    /// Candidates for the current OS, in order of preference
+    fn candidates() -> Vec<PackageManager> {
+        if platform::is_windows() {
+            vec![PackageManager::Winget, PackageManager::Scoop]
+        } else if platform::is_macos() {
+            vec![PackageManager::Homebrew]
+        } else {
+            vec![
+                PackageManager::Apt,
+                PackageManager::Dnf,
+                PackageManager::Yum,
+                PackageManager::Pacman,
+                PackageManager::Homebrew,
+            ]
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        shell::command_exists(self.binary())
+            || (*self == PackageManager::Homebrew
+                && std::path::Path::new(&format!("{}/bin/brew", platform::homebrew_prefix())).exists())
+    }
+
+    /// Package name in this manager's repository
+    fn package_name(&self, package: Package) -> Option<&'static str> {
+        match (self, package) {
+            (PackageManager::Homebrew, Package::Node) => Some("node@22"),
+            (PackageManager::Homebrew, Package::Git) => Some("git"),
+            (PackageManager::Homebrew, Package::Cloudflared) => Some("cloudflared"),
+            (PackageManager::Winget, Package::Node) => Some("OpenJS.NodeJS.LTS"),
+            (PackageManager::Winget, Package::Git) => Some("Git.Git"),
+            (PackageManager::Winget, Package::Cloudflared) => Some("Cloudflare.cloudflared"),
+            (PackageManager::Scoop, Package::Node) => Some("nodejs-lts"),
+            (PackageManager::Scoop, Package::Git) => Some("git"),
+            (PackageManager::Scoop, Package::Cloudflared) => Some("cloudflared"),
+            (PackageManager::Apt | PackageManager::Dnf | PackageManager::Yum, Package::Node) => Some("nodejs"),
+            (PackageManager::Pacman, Package::Node) => Some("nodejs npm"),
+            (_, Package::Git) => Some("git"),
+            // cloudflared is not in the default Linux distro repositories
+            (_, Package::Cloudflared) => None,
+        }
+    }
+
+    /// Preparation steps needed before installing (e.g. NodeSource repository for Node 22).
+    /// NodeSource only publishes x64 and arm64 packages; other architectures use the distro's nodejs.
+    fn setup_commands(&self, package: Package) -> Vec<(String, String)> {
+        if !matches!(platform::get_native_arch().as_str(), "x64" | "arm64") {
+            return Vec::new();
+        }
+        match (self, package) {
+            (PackageManager::Apt, Package::Node) => vec![(
+                "Add NodeSource repository".to_string(),
+                "curl -fsSL https://deb.nodesource.com/setup_22.x | sudo -E bash -".to_string(),
+            )],
+            (PackageManager::Dnf | PackageManager::Yum, Package::Node) => vec![(
+                "Add NodeSource repository".to_string(),
+                "curl -fsSL https://rpm.nodesource.com/setup_22.x | sudo bash -".to_string(),
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    fn install_command(&self, name: &str) -> String {
+        match self {
+            PackageManager::Homebrew => format!("{} install {}", brew_command(), name),
+            PackageManager::Winget => format!(
+                "winget install --id {} -e --accept-source-agreements --accept-package-agreements{}",
+                name,
+                winget_arch_flag()
+            ),
+            PackageManager::Scoop => format!("scoop install {}{}", name, scoop_arch_flag()),
+            PackageManager::Apt => format!("sudo apt-get install -y {}", name),
+            PackageManager::Dnf => format!("sudo dnf install -y {}", name),
+            PackageManager::Yum => format!("sudo yum install -y {}", name),
+            PackageManager::Pacman => format!("sudo pacman -S --noconfirm {}", name),
+        }
+    }
+
+    fn upgrade_command(&self, name: &str) -> String {
+        match self {
+            PackageManager::Homebrew => format!("{} upgrade {}", brew_command(), name),
+            PackageManager::Winget => format!(
+                "winget upgrade --id {} -e --accept-source-agreements --accept-package-agreements{}",
+                name,
+                winget_arch_flag()
+            ),
+            PackageManager::Scoop => format!("scoop update {}", name),
+            PackageManager::Apt => format!("sudo apt-get install -y --only-upgrade {}", name),
+            PackageManager::Dnf => format!("sudo dnf upgrade -y {}", name),
+            PackageManager::Yum => format!("sudo yum update -y {}", name),
+            PackageManager::Pacman => format!("sudo pacman -S --noconfirm {}", name),
+        }
+    }
+
+    /// Post-install steps (e.g. keg-only node@22 must be linked)
+    fn post_install_commands(&self, package: Package) -> Vec<(String, String)> {
+        match (self, package) {
+            (PackageManager::Homebrew, Package::Node) => vec![(
+                "Link node@22".to_string(),
+                format!("{} link --overwrite node@22", brew_command()),
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Package {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Package::Node => "Node.js",
+            Package::Git => "Git",
+            Package::Cloudflared => "cloudflared",
+        }
+    }
+
+    fn version_command(&self) -> &'static str {
+        match self {
+            Package::Node => "node --version",
+            Package::Git => "git --version",
+            Package::Cloudflared => "cloudflared --version",
+        }
+    }
+}
+
+/// brew for the native architecture: `<prefix>/bin/brew`, run with `arch -arm64` when the
+/// Manager itself runs under Rosetta (Apple Silicon Homebrew refuses translated processes)
+fn brew_command() -> String {
+    let brew = format!("{}/bin/brew", platform::homebrew_prefix());
+    if !std::path::Path::new(&brew).exists() {
+        return "brew".to_string();
+    }
+    if platform::is_macos() && platform::is_translated() {
+        format!("arch -arm64 {}", brew)
+    } else {
+        brew
+    }
+}
+
+/// winget picks the installer of its own process; ask for native ARM64 / x86 builds explicitly
+fn winget_arch_flag() -> String {
+    match platform::get_native_arch().as_str() {
+        arch @ ("arm64" | "x86") => format!(" --architecture {}", arch),
+        _ => String::new(),
+    }
+}
+
+fn scoop_arch_flag() -> String {
+    match platform::get_native_arch().as_str() {
+        "arm64" => " --arch arm64".to_string(),
+        "x86" => " --arch 32bit".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// All package managers available on this system, most preferred first
+pub fn detect() -> Vec<PackageManager> {
+    PackageManager::candidates()
+        .into_iter()
+        .filter(|pm| pm.is_available())
+        .collect()
+}
+
+/// First available package manager that can provide `package`
+pub fn preferred_for(package: Package) -> Option<PackageManager> {
+    detect().into_iter().find(|pm| pm.package_name(package).is_some())
+}
+
+/// Installed version of a package, if any (raw first line of `--version`)
+pub fn version(package: Package) -> Option<String> {
+    shell::run_script_output(package.version_command())
+        .ok()
+        .and_then(|out| out.lines().next().map(|l| l.trim().to_string()))
+        .filter(|v| !v.is_empty())
+}
+
+fn run_step(name: &str, command: &str) -> PackageStep {
+    info!("[PkgMgr] {}: {}", name, command);
+    let result = shell::run_script_output(command);
+    if let Err(e) = &result {
+        warn!("[PkgMgr] {} failed: {}", name, e);
+    }
+    PackageStep {
+        name: name.to_string(),
+        command: command.to_string(),
+        success: result.is_ok(),
+        output: result.unwrap_or_else(|e| e),
+    }
+}
+
+fn run_steps(steps: Vec<(String, String)>, on_step: &mut dyn FnMut(usize, usize, &str)) -> Vec<PackageStep> {
+    let total = steps.len();
+    let mut results = Vec::new();
+    for (i, (name, command)) in steps.into_iter().enumerate() {
+        on_step(i, total, &name);
+        let step = run_step(&name, &command);
+        let failed = !step.success;
+        results.push(step);
+        if failed {
+            break;
+        }
+    }
+    results
+}
+
+/// Install a package with the given package manager, one structured result per step
+pub fn install_with(pm: PackageManager, package: Package) -> Result<Vec<PackageStep>, String> {
+    install_with_progress(pm, package, &mut |_, _, _| {})
+}
+
+/// Same as `install_with`, calling `on_step(index, total, name)` before each step runs
+pub fn install_with_progress(
+    pm: PackageManager,
+    package: Package,
+    on_step: &mut dyn FnMut(usize, usize, &str),
+) -> Result<Vec<PackageStep>, String> {
+    let name = pm
+        .package_name(package)
+        .ok_or(format!("{} is not available via {:?}", package.display_name(), pm))?;
+    if package == Package::Node {
+        ensure_node_build()?;
+    }
+
+    let mut steps = pm.setup_commands(package);
+    steps.push((format!("Install {}", package.display_name()), pm.install_command(name)));
+    steps.extend(pm.post_install_commands(package));
+    Ok(run_steps(steps, on_step))
+}
+
+/// Install a package with the preferred package manager
+pub fn install(package: Package) -> Result<Vec<PackageStep>, String> {
+    let pm = preferred_for(package)
+        .ok_or(format!("No supported package manager found to install {}", package.display_name()))?;
+    install_with(pm, package)
+}
+
+/// Upgrade a package with the preferred package manager
+pub fn upgrade(package: Package) -> Result<Vec<PackageStep>, String> {
+    let pm = preferred_for(package)
+        .ok_or(format!("No supported package manager found to upgrade {}", package.display_name()))?;
+    let name = pm
+        .package_name(package)
+        .ok_or(format!("{} is not available via {:?}", package.display_name(), pm))?;
+    Ok(run_steps(
+        vec![(format!("Upgrade {}", package.display_name()), pm.upgrade_command(name))],
+        &mut |_, _, _| {},
+    ))
+}
+
+/// Error for machines without an official Node.js 22 build (e.g. 32-bit x86 Linux)
+pub fn ensure_node_build() -> Result<(), String> {
+    let arch = platform::get_native_arch();
+    if platform::node_build_available(&platform::get_os(), &arch) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Node.js 22 has no official build for {} on {}; install a community build or use a supported machine",
+            platform::get_os(),
+            arch
+        ))
+    }
+}
+
+/// Whether every step succeeded
+pub fn all_succeeded(steps: &[PackageStep]) -> bool {
+    !steps.is_empty() && steps.iter().all(|s| s.success)
+}
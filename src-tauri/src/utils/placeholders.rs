@@ -0,0 +1,227 @@
+//! `${env:VAR}` and `${secret:name}` placeholders in openclaw.json string values.
+//! OpenClaw itself only substitutes `${VAR}` (upper-case env names), so the Manager rewrites
+//! its placeholders to that form and passes the resolved values in the env of openclaw processes.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Env variable prefix a `${secret:name}` placeholder is exported under
+pub const SECRET_ENV_PREFIX: &str = "OPENCLAW_SECRET_";
+
+/// `${env:VAR}`, `${secret:name}` or native `${VAR}`; `$${...}` is OpenClaw's escape for a literal
+fn placeholder_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(\$?)\$\{(?:(env|secret):([A-Za-z0-9_.\-]*)|([A-Z_][A-Z0-9_]*))\}").unwrap()
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderKind {
+    Env,
+    Secret,
+}
+
+/// A placeholder found in a config string
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// JSON pointer of the string holding it
+    pub pointer: String,
+    /// As written, e.g. `${secret:telegram_token}`
+    pub text: String,
+    pub kind: PlaceholderKind,
+    pub name: String,
+}
+
+impl Placeholder {
+    /// Env variable the value is read from (env) or exported under (secret)
+    pub fn env_var(&self) -> String {
+        match self.kind {
+            PlaceholderKind::Env => self.name.clone(),
+            PlaceholderKind::Secret => secret_env_var(&self.name),
+        }
+    }
+
+    /// Whether OpenClaw can substitute the native form of this placeholder
+    pub fn is_valid(&self) -> bool {
+        match self.kind {
+            PlaceholderKind::Env => is_valid_env_name(&self.name),
+            PlaceholderKind::Secret => is_valid_secret_name(&self.name),
+        }
+    }
+}
+
+/// Env variable a secret is exported under
+pub fn secret_env_var(name: &str) -> String {
+    format!("{}{}", SECRET_ENV_PREFIX, name.to_uppercase())
+}
+
+/// Secret names are lower-case letters, digits and underscores so the env name maps back
+pub fn is_valid_secret_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Secret holding a provider's API key when it is kept out of openclaw.json
+/// ("anthropic-work" -> "provider_anthropic_work_api_key")
+pub fn provider_key_secret_name(provider: &str) -> String {
+    let slug: String = provider
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_lowercase() || c.is_ascii_digit() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64 - "provider__api_key".len())
+        .collect();
+    format!("provider_{}_api_key", slug)
+}
+
+/// OpenClaw only substitutes upper-case env names
+pub fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The placeholder a match stands for; None for `$${...}` escapes
+fn placeholder_at(caps: &regex::Captures, pointer: &str) -> Option<Placeholder> {
+    if !caps[1].is_empty() {
+        return None;
+    }
+    let (kind, name) = match (caps.get(2), caps.get(3), caps.get(4)) {
+        (Some(kind), Some(name), _) if kind.as_str() == "secret" => {
+            (PlaceholderKind::Secret, name.as_str().to_string())
+        }
+        (Some(_), Some(name), _) => (PlaceholderKind::Env, name.as_str().to_string()),
+        // Native references to exported secrets are still secrets
+        (_, _, Some(var)) => match var.as_str().strip_prefix(SECRET_ENV_PREFIX) {
+            Some(secret) => (PlaceholderKind::Secret, secret.to_lowercase()),
+            None => (PlaceholderKind::Env, var.as_str().to_string()),
+        },
+        _ => return None,
+    };
+    Some(Placeholder {
+        pointer: pointer.to_string(),
+        text: caps[0].to_string(),
+        kind,
+        name,
+    })
+}
+
+fn placeholders_in(text: &str, pointer: &str, found: &mut Vec<Placeholder>) {
+    found.extend(
+        placeholder_re()
+            .captures_iter(text)
+            .filter_map(|caps| placeholder_at(&caps, pointer)),
+    );
+}
+
+fn walk(value: &Value, pointer: &str, found: &mut Vec<Placeholder>) {
+    match value {
+        Value::String(s) => placeholders_in(s, pointer, found),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                walk(item, &format!("{}/{}", pointer, i), found);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                walk(item, &format!("{}/{}", pointer, key), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// All placeholders in the config's string values, in document order
+pub fn find(config: &Value) -> Vec<Placeholder> {
+    let mut found = Vec::new();
+    walk(config, "", &mut found);
+    found
+}
+
+fn rewrite(value: &mut Value, changed: &mut bool) {
+    match value {
+        Value::String(s) => {
+            let native = placeholder_re()
+                .replace_all(s, |caps: &regex::Captures| {
+                    let name = caps.get(3).map(|m| m.as_str()).unwrap_or_default();
+                    match caps.get(2).map(|m| m.as_str()) {
+                        Some("env") if caps[1].is_empty() && is_valid_env_name(name) => {
+                            format!("${{{}}}", name)
+                        }
+                        Some("secret") if caps[1].is_empty() && is_valid_secret_name(name) => {
+                            format!("${{{}}}", secret_env_var(name))
+                        }
+                        _ => caps[0].to_string(),
+                    }
+                })
+                .into_owned();
+            if native != *s {
+                *s = native;
+                *changed = true;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rewrite(item, changed)),
+        Value::Object(map) => map.values_mut().for_each(|item| rewrite(item, changed)),
+        _ => {}
+    }
+}
+
+/// The config with `${env:VAR}` / `${secret:name}` rewritten to the `${VAR}` form OpenClaw
+/// substitutes; None when there is nothing to rewrite. Invalid names are left as written.
+pub fn to_native(config: &Value) -> Option<Value> {
+    let mut native = config.clone();
+    let mut changed = false;
+    rewrite(&mut native, &mut changed);
+    changed.then_some(native)
+}
+
+/// `text` with each placeholder replaced by its value from `lookup` and `$${...}` escapes
+/// unescaped; None when a placeholder is invalid or doesn't resolve
+pub fn substitute(text: &str, lookup: impl Fn(&Placeholder) -> Option<String>) -> Option<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in placeholder_re().captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        result.push_str(&text[last..whole.start()]);
+        match placeholder_at(&caps, "") {
+            Some(p) if p.is_valid() => result.push_str(&lookup(&p)?),
+            Some(_) => return None,
+            None => result.push_str(&whole.as_str()[1..]),
+        }
+        last = whole.end();
+    }
+    result.push_str(&text[last..]);
+    Some(result)
+}
+
+/// Env variables for the placeholders in `config` that `lookup` resolves, under the names
+/// the native `${VAR}` form reads them from
+pub fn resolve_env(
+    config: &Value,
+    lookup: impl Fn(&Placeholder) -> Option<String>,
+) -> Vec<(String, String)> {
+    let mut env: Vec<(String, String)> = Vec::new();
+    for p in find(config).iter().filter(|p| p.is_valid()) {
+        let var = p.env_var();
+        if env.iter().any(|(name, _)| *name == var) {
+            continue;
+        }
+        if let Some(value) = lookup(p) {
+            env.push((var, value));
+        }
+    }
+    env
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use super::super::placeholders::{
+        find, is_valid_secret_name, provider_key_secret_name, resolve_env, secret_env_var,
+        substitute, to_native, Placeholder, PlaceholderKind,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_find_placeholders() {
+        let config = json!({
+            "channels": { "telegram": { "botToken": "${secret:telegram_token}" } },
+            "models": { "providers": { "openai": { "apiKey": "${env:OPENAI_API_KEY}" } } },
+            "gateway": { "auth": { "token": "${OPENCLAW_SECRET_GATEWAY}" } },
+            "tools": { "web": { "url": "https://x/?k=${BRAVE_KEY}&v=$${LITERAL}" } },
+            "plain": "no placeholders here"
+        });
+        let found = find(&config);
+        assert_eq!(found.len(), 4);
+
+        let token = found
+            .iter()
+            .find(|p| p.pointer == "/channels/telegram/botToken")
+            .unwrap();
+        assert_eq!(token.kind, PlaceholderKind::Secret);
+        assert_eq!(token.name, "telegram_token");
+        assert_eq!(token.env_var(), "OPENCLAW_SECRET_TELEGRAM_TOKEN");
+
+        let native_secret = found
+            .iter()
+            .find(|p| p.pointer == "/gateway/auth/token")
+            .unwrap();
+        assert_eq!(native_secret.kind, PlaceholderKind::Secret);
+        assert_eq!(native_secret.name, "gateway");
+
+        let embedded = found
+            .iter()
+            .find(|p| p.pointer == "/tools/web/url")
+            .unwrap();
+        assert_eq!(embedded.kind, PlaceholderKind::Env);
+        assert_eq!(embedded.name, "BRAVE_KEY");
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let found = find(&json!({ "a": "${env:lower_case}", "b": "${secret:Bad-Name}" }));
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|p| !p.is_valid()));
+        assert!(is_valid_secret_name("telegram_token_2"));
+        assert!(!is_valid_secret_name(""));
+    }
+
+    #[test]
+    fn test_to_native() {
+        let config = json!({
+            "token": "${secret:telegram_token}",
+            "list": ["Bearer ${env:API_KEY}", "${env:lower}"],
+            "native": "${ALREADY}"
+        });
+        let native = to_native(&config).unwrap();
+        assert_eq!(native["token"], "${OPENCLAW_SECRET_TELEGRAM_TOKEN}");
+        assert_eq!(native["list"][0], "Bearer ${API_KEY}");
+        // Invalid names stay as written so validation still reports them
+        assert_eq!(native["list"][1], "${env:lower}");
+        assert_eq!(native["native"], "${ALREADY}");
+
+        // Rewriting is idempotent: the native form resolves to the same placeholders
+        assert_eq!(to_native(&native), None);
+        let names: Vec<_> = find(&native).into_iter().map(|p| p.env_var()).collect();
+        assert!(names.contains(&secret_env_var("telegram_token")));
+    }
+
+    #[test]
+    fn test_provider_key_secret_name() {
+        assert_eq!(
+            provider_key_secret_name("anthropic-work"),
+            "provider_anthropic_work_api_key"
+        );
+        assert_eq!(
+            provider_key_secret_name("glm.cn"),
+            "provider_glm_cn_api_key"
+        );
+        let long = provider_key_secret_name(&"x".repeat(100));
+        assert!(is_valid_secret_name(&long));
+        assert_eq!(long.len(), 64);
+    }
+
+    fn lookup(p: &Placeholder) -> Option<String> {
+        match (p.kind, p.name.as_str()) {
+            (PlaceholderKind::Secret, "openai_key") => Some("sk-secret".to_string()),
+            (PlaceholderKind::Env, "API_HOST") => Some("api.example.com".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_substitute_for_clipboard() {
+        // Both the Manager form and the native form written by normalize_placeholders
+        assert_eq!(
+            substitute("${secret:openai_key}", lookup).as_deref(),
+            Some("sk-secret")
+        );
+        assert_eq!(
+            substitute("${OPENCLAW_SECRET_OPENAI_KEY}", lookup).as_deref(),
+            Some("sk-secret")
+        );
+        assert_eq!(
+            substitute("https://${env:API_HOST}/v1?x=$${LITERAL}", lookup).as_deref(),
+            Some("https://api.example.com/v1?x=${LITERAL}")
+        );
+        assert_eq!(
+            substitute("plain-key", lookup).as_deref(),
+            Some("plain-key")
+        );
+        // Never fall back to copying the reference itself
+        assert_eq!(substitute("${secret:unknown}", lookup), None);
+        assert_eq!(substitute("${env:lower}", lookup), None);
+    }
+
+    #[test]
+    fn test_resolve_env_for_probe() {
+        let probe = to_native(&json!({
+            "models": { "providers": { "openai": {
+                "apiKey": "${secret:openai_key}",
+                "baseUrl": "https://${env:API_HOST}/v1",
+                "headers": { "X-Key": "${OPENCLAW_SECRET_OPENAI_KEY}", "X-Other": "${MISSING}" }
+            } } }
+        }))
+        .unwrap();
+        assert_eq!(
+            resolve_env(&probe, lookup),
+            vec![
+                (secret_env_var("openai_key"), "sk-secret".to_string()),
+                ("API_HOST".to_string(), "api.example.com".to_string()),
+            ]
+        );
+    }
+}
@@ -1,4 +1,65 @@
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Marker file next to the executable that switches the Manager to portable mode
+pub const PORTABLE_MARKER_FILE: &str = "openclaw-manager.portable";
+
+/// Environment variable that forces portable mode (any non-empty value except "0")
+pub const PORTABLE_ENV: &str = "OPENCLAW_MANAGER_PORTABLE";
+
+/// Portable data directory, resolved once per run
+static PORTABLE_DATA_DIR: OnceLock<Option<String>> = OnceLock::new();
+
+/// manager.json key holding a relocated OpenClaw config directory
+pub const CONFIG_DIR_OVERRIDE_KEY: &str = "configDir";
+
+/// Relocated config directory, loaded from manager.json on first use
+static CONFIG_DIR_OVERRIDE: OnceLock<RwLock<Option<String>>> = OnceLock::new();
+
+/// Paths at least this long get the Windows extended-length prefix (MAX_PATH is 260 including
+/// the file name, directories are limited to 248)
+const LONG_PATH_THRESHOLD: usize = 240;
+
+/// Join path segments onto `base` using the platform separator
+pub fn join_path<P: AsRef<Path>>(base: P, parts: &[&str]) -> PathBuf {
+    let mut path = base.as_ref().to_path_buf();
+    for part in parts {
+        path.push(part);
+    }
+    path
+}
+
+/// String form of a path for config values and the frontend (exact for any Unicode path)
+pub fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Extended-length form of a long absolute Windows path (`\\?\C:\...`, `\\?\UNC\server\...`)
+/// so file APIs accept it beyond MAX_PATH. Short, relative or already-prefixed paths are unchanged.
+pub fn extended_length_path(path: &str) -> String {
+    if path.chars().count() < LONG_PATH_THRESHOLD || path.starts_with(r"\\?\") {
+        return path.to_string();
+    }
+    let normalized = path.replace('/', "\\");
+    if let Some(unc) = normalized.strip_prefix(r"\\") {
+        return format!(r"\\?\UNC\{}", unc);
+    }
+    let bytes = normalized.as_bytes();
+    if bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return format!(r"\\?\{}", normalized);
+    }
+    path.to_string()
+}
+
+/// Path to hand to file APIs: extended-length on Windows, unchanged elsewhere
+pub fn long_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    if is_windows() {
+        PathBuf::from(extended_length_path(&path_string(path.as_ref())))
+    } else {
+        path.as_ref().to_path_buf()
+    }
+}
 
 /// 获取操作系统类型
 pub fn get_os() -> String {
@@ -10,50 +71,212 @@ pub fn get_arch() -> String {
     env::consts::ARCH.to_string()
 }
 
-/// 获取配置目录路径
+/// Native CPU architecture, resolved once per run
+static NATIVE_ARCH: OnceLock<String> = OnceLock::new();
+
+/// Architecture name as used by Node.js release builds (x64, arm64, armv7l, x86, ppc64le,
+/// s390x) for the spellings reported by Rust, uname, Windows and sysctl
+pub fn normalize_arch(raw: &str) -> Option<&'static str> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => Some("x64"),
+        "aarch64" | "arm64" | "armv8" | "arm64e" => Some("arm64"),
+        "arm" | "armv7" | "armv7l" | "armhf" => Some("armv7l"),
+        "x86" | "i386" | "i686" => Some("x86"),
+        "ppc64le" | "powerpc64le" => Some("ppc64le"),
+        "s390x" => Some("s390x"),
+        _ => None,
+    }
+}
+
+/// Native architecture of a Windows machine from PROCESSOR_ARCHITECTURE,
+/// PROCESSOR_ARCHITEW6432 (set for 32-bit processes on 64-bit Windows) and
+/// PROCESSOR_IDENTIFIER (x64 processes emulated on ARM64 still see AMD64 as the architecture)
+pub fn windows_native_arch(
+    architecture: Option<&str>,
+    architew6432: Option<&str>,
+    identifier: Option<&str>,
+) -> Option<&'static str> {
+    if identifier.is_some_and(|id| id.trim_start().to_ascii_uppercase().starts_with("ARM")) {
+        return Some("arm64");
+    }
+    architew6432.or(architecture).and_then(normalize_arch)
+}
+
+/// Native CPU architecture in Node.js naming. Sees through Rosetta 2 (an x64 build on an
+/// Apple Silicon Mac) and x64 emulation on Windows on ARM, so installers pick native builds.
+pub fn get_native_arch() -> String {
+    NATIVE_ARCH
+        .get_or_init(|| {
+            let build = normalize_arch(env::consts::ARCH).unwrap_or(env::consts::ARCH);
+            let native = if is_windows() {
+                windows_native_arch(
+                    env::var("PROCESSOR_ARCHITECTURE").ok().as_deref(),
+                    env::var("PROCESSOR_ARCHITEW6432").ok().as_deref(),
+                    env::var("PROCESSOR_IDENTIFIER").ok().as_deref(),
+                )
+            } else if is_macos() && build == "x64" {
+                let translated = std::process::Command::new("sysctl")
+                    .args(["-n", "sysctl.proc_translated"])
+                    .output()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "1")
+                    .unwrap_or(false);
+                translated.then_some("arm64")
+            } else {
+                None
+            };
+            native.unwrap_or(build).to_string()
+        })
+        .clone()
+}
+
+/// Whether the Manager itself runs emulated (its build differs from the native architecture)
+pub fn is_translated() -> bool {
+    normalize_arch(env::consts::ARCH).is_some_and(|build| build != get_native_arch())
+}
+
+/// Homebrew prefix for an OS and architecture: /opt/homebrew on Apple Silicon, /usr/local on
+/// Intel Macs, /home/linuxbrew/.linuxbrew on Linux
+pub fn homebrew_prefix_for(os: &str, arch: &str) -> &'static str {
+    match (os, arch) {
+        ("macos", "arm64") => "/opt/homebrew",
+        ("macos", _) => "/usr/local",
+        _ => "/home/linuxbrew/.linuxbrew",
+    }
+}
+
+/// Homebrew prefix of this machine
+pub fn homebrew_prefix() -> &'static str {
+    homebrew_prefix_for(env::consts::OS, &get_native_arch())
+}
+
+/// Whether Node.js 22 publishes an official build for an OS and architecture
+pub fn node_build_available(os: &str, arch: &str) -> bool {
+    match os {
+        "macos" => matches!(arch, "x64" | "arm64"),
+        "windows" => matches!(arch, "x64" | "arm64" | "x86"),
+        "linux" => matches!(arch, "x64" | "arm64" | "armv7l" | "ppc64le" | "s390x"),
+        _ => false,
+    }
+}
+
+/// 获取配置目录路径 (honors a relocation set via `set_config_dir_override`, then the
+/// distro's ~/.openclaw in WSL mode)
 pub fn get_config_dir() -> String {
-    if let Some(home) = dirs::home_dir() {
-        if is_windows() {
-            format!("{}\\.openclaw", home.display())
-        } else {
-            format!("{}/.openclaw", home.display())
-        }
-    } else {
-        String::from("~/.openclaw")
+    get_config_dir_override()
+        .or_else(crate::utils::wsl::config_dir)
+        .unwrap_or_else(get_default_config_dir)
+}
+
+fn config_dir_override_lock() -> &'static RwLock<Option<String>> {
+    CONFIG_DIR_OVERRIDE.get_or_init(|| {
+        let value = std::fs::read_to_string(get_manager_config_file_path())
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}')).ok())
+            .and_then(|config| config.get(CONFIG_DIR_OVERRIDE_KEY).and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .filter(|s| !s.is_empty());
+        RwLock::new(value)
+    })
+}
+
+/// Relocated config directory, if the user moved ~/.openclaw elsewhere
+pub fn get_config_dir_override() -> Option<String> {
+    config_dir_override_lock().read().ok().and_then(|v| v.clone())
+}
+
+/// Update the in-memory override (manager.json is written by the caller)
+pub fn set_config_dir_override(dir: Option<String>) {
+    if let Ok(mut value) = config_dir_override_lock().write() {
+        *value = dir.clone();
+    }
+    if dir.is_none() {
+        env::remove_var("OPENCLAW_STATE_DIR");
+        env::remove_var("OPENCLAW_CONFIG_PATH");
+    }
+    apply_config_dir_env();
+}
+
+/// Export a relocated directory to this process's environment so that every
+/// spawned openclaw process (CLI and gateway) resolves the same state directory
+pub fn apply_config_dir_env() {
+    if let Some(dir) = get_config_dir_override() {
+        let config_path = std::path::Path::new(&dir).join("openclaw.json");
+        env::set_var("OPENCLAW_STATE_DIR", &dir);
+        env::set_var("OPENCLAW_CONFIG_PATH", config_path);
+    }
+}
+
+/// Default config directory (~/.openclaw), ignoring any relocation
+pub fn get_default_config_dir() -> String {
+    match dirs::home_dir() {
+        Some(home) => path_string(&home.join(".openclaw")),
+        None => String::from("~/.openclaw"),
     }
 }
 
 /// 获取环境变量文件路径
 pub fn get_env_file_path() -> String {
-    if is_windows() {
-        format!("{}\\env", get_config_dir())
-    } else {
-        format!("{}/env", get_config_dir())
-    }
+    path_string(&join_path(get_config_dir(), &["env"]))
 }
 
 /// 获取 openclaw.json 配置文件路径
 pub fn get_config_file_path() -> String {
-    if is_windows() {
-        format!("{}\\openclaw.json", get_config_dir())
-    } else {
-        format!("{}/openclaw.json", get_config_dir())
-    }
+    path_string(&join_path(get_config_dir(), &["openclaw.json"]))
+}
+
+/// Directory containing the Manager executable
+pub fn get_exe_dir() -> Option<String> {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(path_string))
+}
+
+/// Portable data directory (`<exe dir>/data`) if portable mode is active.
+/// Decided once at first use; toggling the marker takes effect after a restart.
+pub fn get_portable_data_dir() -> Option<String> {
+    PORTABLE_DATA_DIR
+        .get_or_init(|| {
+            let exe_dir = get_exe_dir()?;
+            let forced = env::var(PORTABLE_ENV).map(|v| !v.is_empty() && v != "0").unwrap_or(false);
+            let marker = std::path::Path::new(&exe_dir).join(PORTABLE_MARKER_FILE);
+            if !forced && !marker.exists() {
+                return None;
+            }
+            Some(path_string(&join_path(&exe_dir, &["data"])))
+        })
+        .clone()
+}
+
+/// Whether Manager state lives next to the executable instead of the home directory
+pub fn is_portable_mode() -> bool {
+    get_portable_data_dir().is_some()
+}
+
+/// Root of Manager-owned state: `<exe dir>/data` in portable mode, ~/.openclaw otherwise.
+/// Never follows a config directory relocation (manager.json records the relocation).
+/// OpenClaw's own files (openclaw.json, env, agents) always stay in `get_config_dir()`.
+pub fn get_manager_root_dir() -> String {
+    get_portable_data_dir().unwrap_or_else(get_default_config_dir)
 }
 
 /// Get manager configuration file path (manager.json)
 pub fn get_manager_config_file_path() -> String {
-    if is_windows() {
-        format!("{}\\manager.json", get_config_dir())
-    } else {
-        format!("{}/manager.json", get_config_dir())
-    }
+    path_string(&join_path(get_manager_root_dir(), &["manager.json"]))
+}
+
+/// Get Manager data directory (~/.openclaw/manager) for Manager-owned state files
+pub fn get_manager_data_dir() -> String {
+    path_string(&join_path(get_manager_root_dir(), &["manager"]))
+}
+
+/// Get credentials directory (~/.openclaw/credentials), holds channel pairing stores
+pub fn get_credentials_dir() -> String {
+    path_string(&join_path(get_config_dir(), &["credentials"]))
 }
 
 /// 获取日志文件路径
 pub fn get_log_file_path() -> String {
     if is_windows() {
-        format!("{}\\openclaw-gateway.log", get_config_dir())
+        path_string(&join_path(get_config_dir(), &["openclaw-gateway.log"]))
     } else {
         String::from("/tmp/openclaw-gateway.log")
     }
@@ -61,32 +284,33 @@ pub fn get_log_file_path() -> String {
 
 /// Get MCP servers install directory
 pub fn get_mcp_install_dir() -> String {
-    if is_windows() {
-        format!("{}\\mcps", get_config_dir())
-    } else {
-        format!("{}/mcps", get_config_dir())
-    }
+    path_string(&join_path(get_config_dir(), &["mcps"]))
 }
 
 /// Get MCP configuration file path (separate from openclaw.json)
 pub fn get_mcp_config_file_path() -> String {
-    if is_windows() {
-        format!("{}\\mcps.json", get_config_dir())
-    } else {
-        format!("{}/mcps.json", get_config_dir())
-    }
+    path_string(&join_path(get_config_dir(), &["mcps.json"]))
 }
 
 /// Get mcporter configuration file path (~/.mcporter/mcporter.json)
 pub fn get_mcporter_config_file_path() -> String {
-    if let Some(home) = dirs::home_dir() {
-        if is_windows() {
-            format!("{}\\.mcporter\\mcporter.json", home.display())
-        } else {
-            format!("{}/.mcporter/mcporter.json", home.display())
-        }
-    } else {
-        String::from("~/.mcporter/mcporter.json")
+    match dirs::home_dir() {
+        Some(home) => path_string(&join_path(home, &[".mcporter", "mcporter.json"])),
+        None => String::from("~/.mcporter/mcporter.json"),
+    }
+}
+
+/// Get Claude Desktop configuration file path (<config dir>/Claude/claude_desktop_config.json)
+pub fn get_claude_desktop_config_file_path() -> String {
+    let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("~/.config"));
+    path_string(&join_path(config_dir, &["Claude", "claude_desktop_config.json"]))
+}
+
+/// Get Cursor global MCP configuration file path (~/.cursor/mcp.json)
+pub fn get_cursor_mcp_config_file_path() -> String {
+    match dirs::home_dir() {
+        Some(home) => path_string(&join_path(home, &[".cursor", "mcp.json"])),
+        None => String::from("~/.cursor/mcp.json"),
     }
 }
 
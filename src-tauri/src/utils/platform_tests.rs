@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use super::super::platform::{
+        extended_length_path, homebrew_prefix_for, join_path, node_build_available, normalize_arch,
+        path_string, windows_native_arch,
+    };
+
+    #[test]
+    fn test_join_path_keeps_non_ascii_segments() {
+        let path = join_path("/home/张三", &[".openclaw", "agents", "助手", "SOUL.md"]);
+        assert_eq!(path_string(&path), "/home/张三/.openclaw/agents/助手/SOUL.md");
+        assert_eq!(path.file_name().and_then(|n| n.to_str()), Some("SOUL.md"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_default_config_dir_with_non_ascii_home() {
+        let previous = std::env::var_os("HOME");
+        std::env::set_var("HOME", "/tmp/用户/Jürgen Müller");
+        let dir = super::super::platform::get_default_config_dir();
+        match previous {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+        assert_eq!(dir, "/tmp/用户/Jürgen Müller/.openclaw");
+    }
+
+    #[test]
+    fn test_extended_length_path() {
+        let long_dir = "目录".repeat(130);
+        let drive = format!("C:\\Users\\张三\\{}\\openclaw.json", long_dir);
+        assert_eq!(extended_length_path(&drive), format!("\\\\?\\{}", drive));
+
+        let unc = format!("\\\\server\\share\\{}", long_dir);
+        assert_eq!(extended_length_path(&unc), format!("\\\\?\\UNC\\server\\share\\{}", long_dir));
+
+        // Short, relative and already-prefixed paths are left alone
+        assert_eq!(extended_length_path("C:\\Users\\张三\\.openclaw"), "C:\\Users\\张三\\.openclaw");
+        assert_eq!(extended_length_path(&long_dir), long_dir);
+        let prefixed = format!("\\\\?\\{}", drive);
+        assert_eq!(extended_length_path(&prefixed), prefixed);
+    }
+
+    #[test]
+    fn test_normalize_arch() {
+        assert_eq!(normalize_arch("x86_64"), Some("x64"));
+        assert_eq!(normalize_arch("AMD64"), Some("x64"));
+        assert_eq!(normalize_arch("aarch64"), Some("arm64"));
+        assert_eq!(normalize_arch("arm64\n"), Some("arm64"));
+        assert_eq!(normalize_arch("armv7l"), Some("armv7l"));
+        assert_eq!(normalize_arch("i686"), Some("x86"));
+        assert_eq!(normalize_arch("riscv64"), None);
+    }
+
+    #[test]
+    fn test_windows_native_arch() {
+        assert_eq!(
+            windows_native_arch(Some("AMD64"), None, Some("Intel64 Family 6")),
+            Some("x64")
+        );
+        // 32-bit process on 64-bit Windows
+        assert_eq!(
+            windows_native_arch(Some("x86"), Some("AMD64"), None),
+            Some("x64")
+        );
+        // x64 process emulated on Windows on ARM
+        assert_eq!(
+            windows_native_arch(Some("AMD64"), None, Some("ARMv8 (64-bit) Family 8")),
+            Some("arm64")
+        );
+        assert_eq!(
+            windows_native_arch(Some("ARM64"), None, None),
+            Some("arm64")
+        );
+        assert_eq!(windows_native_arch(None, None, None), None);
+    }
+
+    #[test]
+    fn test_homebrew_prefix_and_node_builds() {
+        assert_eq!(homebrew_prefix_for("macos", "arm64"), "/opt/homebrew");
+        assert_eq!(homebrew_prefix_for("macos", "x64"), "/usr/local");
+        assert_eq!(
+            homebrew_prefix_for("linux", "arm64"),
+            "/home/linuxbrew/.linuxbrew"
+        );
+
+        assert!(node_build_available("windows", "arm64"));
+        assert!(node_build_available("linux", "armv7l"));
+        assert!(!node_build_available("macos", "x86"));
+        assert!(!node_build_available("linux", "riscv64"));
+    }
+}
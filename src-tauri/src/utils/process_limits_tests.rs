@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use super::super::process_limits::{
+        affinity_mask, cpu_rate, plan, priority_class, ProcessLimits, ProcessPriority,
+    };
+
+    fn limited() -> ProcessLimits {
+        ProcessLimits {
+            priority: ProcessPriority::BelowNormal,
+            low_io: true,
+            cpu_affinity: vec![0, 2],
+            memory_limit_mb: Some(2048),
+            cpu_quota_percent: Some(150),
+        }
+    }
+
+    #[test]
+    fn test_linux_plan_chains_systemd_run_taskset_nice_and_ionice() {
+        let plan = plan(&limited(), "linux", &|_| true);
+        assert_eq!(
+            plan.prefix.join(" "),
+            "systemd-run --user --scope --quiet -p MemoryMax=2048M -p CPUQuota=150% -- \
+             taskset -c 0,2 nice -n 10 ionice -c 2 -n 7"
+        );
+        assert!(plan.unsupported.is_empty());
+    }
+
+    #[test]
+    fn test_linux_plan_reports_missing_helpers() {
+        let plan = plan(&limited(), "linux", &|program| program == "taskset");
+        assert_eq!(plan.prefix.join(" "), "taskset -c 0,2 nice -n 10");
+        assert_eq!(plan.unsupported.len(), 2);
+        assert!(plan.unsupported[0].contains("systemd-run"));
+        assert!(plan.unsupported[1].contains("ionice"));
+    }
+
+    #[test]
+    fn test_macos_and_windows_plans() {
+        let mac = plan(&limited(), "macos", &|_| true);
+        assert_eq!(mac.prefix.join(" "), "nice -n 10 taskpolicy -d throttle");
+        assert_eq!(mac.unsupported.len(), 2);
+
+        let windows = plan(&limited(), "windows", &|_| true);
+        assert!(windows.prefix.is_empty() && windows.unsupported.is_empty());
+
+        assert!(plan(&ProcessLimits::default(), "linux", &|_| true)
+            .prefix
+            .is_empty());
+    }
+
+    #[test]
+    fn test_windows_job_values() {
+        assert_eq!(priority_class(ProcessPriority::Normal), 0);
+        assert_eq!(priority_class(ProcessPriority::Idle), 0x40);
+        assert_eq!(affinity_mask(&[0, 2, 70]), 0b101);
+        // 150% of one core on 8 CPUs is 18.75% of the machine
+        assert_eq!(cpu_rate(150, 8), 1875);
+        assert_eq!(cpu_rate(800, 4), 10_000);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(limited().validate(4).is_ok());
+        assert!(limited().validate(2).is_err());
+        let small = ProcessLimits {
+            memory_limit_mb: Some(100),
+            ..ProcessLimits::default()
+        };
+        assert!(small.validate(4).is_err());
+        let quota = ProcessLimits {
+            cpu_quota_percent: Some(500),
+            ..ProcessLimits::default()
+        };
+        assert!(quota.validate(4).is_err());
+        assert!(quota.validate(8).is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::utils::platform;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// One row of the system process table
+#[derive(Debug, Clone, PartialEq, Serialize, specta::Type)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub name: String,
+    /// Not available on Windows without sampling
+    pub cpu_percent: Option<f64>,
+    pub memory_mb: Option<f64>,
+}
+
+/// A process and everything it spawned
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct ProcessNode {
+    #[serde(flatten)]
+    pub info: ProcessInfo,
+    /// Children can be killed individually; the root goes through stop_service
+    pub killable: bool,
+    pub children: Vec<ProcessNode>,
+}
+
+/// Parse `ps -axo pid=,ppid=,pcpu=,rss=,comm=` output (rss in KB, command may contain spaces)
+pub fn parse_ps(output: &str) -> Vec<ProcessInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            let cpu = fields.next()?.parse::<f64>().ok();
+            let rss_kb = fields.next()?.parse::<f64>().ok();
+            let name = fields.collect::<Vec<_>>().join(" ");
+            Some(ProcessInfo {
+                pid,
+                ppid,
+                name,
+                cpu_percent: cpu,
+                memory_mb: rss_kb.map(|kb| kb / 1024.0),
+            })
+        })
+        .collect()
+}
+
+/// Parse `pid,ppid,workingSetBytes,name` lines produced from Win32_Process
+pub fn parse_win32_process(output: &str) -> Vec<ProcessInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(4, ',');
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            let bytes = fields.next()?.parse::<f64>().ok();
+            let name = fields.next()?.to_string();
+            Some(ProcessInfo {
+                pid,
+                ppid,
+                name,
+                cpu_percent: None,
+                memory_mb: bytes.map(|b| b / (1024.0 * 1024.0)),
+            })
+        })
+        .collect()
+}
+
+/// Tree rooted at `root_pid`, or None if it is not in the table
+pub fn build_tree(processes: &[ProcessInfo], root_pid: u32) -> Option<ProcessNode> {
+    let root = processes.iter().find(|p| p.pid == root_pid)?;
+    let mut visited = HashSet::new();
+    Some(build_node(processes, root, false, &mut visited))
+}
+
+fn build_node(processes: &[ProcessInfo], info: &ProcessInfo, killable: bool, visited: &mut HashSet<u32>) -> ProcessNode {
+    visited.insert(info.pid);
+    let children = processes
+        .iter()
+        .filter(|p| p.ppid == info.pid && p.pid != info.pid)
+        .filter_map(|child| {
+            // PIDs get reused, a stale parent link must not loop forever
+            if visited.contains(&child.pid) {
+                return None;
+            }
+            Some(build_node(processes, child, true, visited))
+        })
+        .collect();
+    ProcessNode { info: info.clone(), killable, children }
+}
+
+/// PIDs of all descendants of `root_pid` (not including the root)
+pub fn descendants(processes: &[ProcessInfo], root_pid: u32) -> Vec<u32> {
+    fn collect(node: &ProcessNode, out: &mut Vec<u32>) {
+        for child in &node.children {
+            out.push(child.info.pid);
+            collect(child, out);
+        }
+    }
+    let mut pids = Vec::new();
+    if let Some(tree) = build_tree(processes, root_pid) {
+        collect(&tree, &mut pids);
+    }
+    pids
+}
+
+/// State directory a gateway was started with. `OPENCLAW_STATE_DIR` wins, then the CLI's
+/// `--dev` / `--profile <name>` flags (~/.openclaw-dev, ~/.openclaw-<name>), else ~/.openclaw.
+pub fn gateway_state_dir(command_line: &str, state_dir_env: Option<&str>, home: &str) -> String {
+    if let Some(dir) = state_dir_env.filter(|d| !d.is_empty()) {
+        return dir.to_string();
+    }
+    let home = Path::new(home);
+    let mut args = command_line.split_whitespace();
+    while let Some(arg) = args.next() {
+        let profile = match arg {
+            "--dev" => Some("dev".to_string()),
+            "--profile" => args.next().map(String::from),
+            _ => arg.strip_prefix("--profile=").map(String::from),
+        };
+        if let Some(profile) = profile.filter(|p| !p.is_empty()) {
+            return platform::path_string(&home.join(format!(".openclaw-{}", profile)));
+        }
+    }
+    platform::path_string(&home.join(".openclaw"))
+}
+
+/// Whether a command line runs an openclaw gateway (`openclaw [flags] gateway ...`, directly
+/// or through node), so a stop never kills an unrelated process that took its port
+pub fn is_gateway_command(command_line: &str) -> bool {
+    let mut args = command_line.split_whitespace();
+    args.any(|arg| arg.to_lowercase().contains("openclaw")) && args.any(|arg| arg == "gateway")
+}
+
+/// Value of `key` in a NUL-separated environment block (/proc/<pid>/environ)
+pub fn environ_value(environ: &[u8], key: &str) -> Option<String> {
+    let prefix = format!("{}=", key);
+    environ
+        .split(|b| *b == 0)
+        .map(String::from_utf8_lossy)
+        .find_map(|entry| entry.strip_prefix(&prefix).map(String::from))
+}
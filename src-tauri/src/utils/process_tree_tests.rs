@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use super::super::process_tree::{
+        build_tree, descendants, environ_value, gateway_state_dir, is_gateway_command, parse_ps,
+        parse_win32_process,
+    };
+
+    const PS: &str = "    1     0   0.0  1024 launchd
+  500     1   2.5 51200 node
+  510   500  10.0 20480 node
+  511   510   0.0  2048 /Applications/Some App/helper
+  600     1   0.0  1024 bash";
+
+    #[test]
+    fn test_parse_ps_rows() {
+        let rows = parse_ps(PS);
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[1].pid, 500);
+        assert_eq!(rows[1].memory_mb, Some(50.0));
+        assert_eq!(rows[3].name, "/Applications/Some App/helper");
+
+        let rows = parse_win32_process("4000,4,104857600,node.exe\r\nbad line\r\n");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].memory_mb, Some(100.0));
+        assert_eq!(rows[0].cpu_percent, None);
+    }
+
+    #[test]
+    fn test_tree_and_descendants() {
+        let rows = parse_ps(PS);
+        let tree = build_tree(&rows, 500).unwrap();
+        assert!(!tree.killable);
+        assert_eq!(tree.children.len(), 1);
+        assert!(tree.children[0].killable);
+        assert_eq!(tree.children[0].children[0].info.pid, 511);
+        assert_eq!(descendants(&rows, 500), vec![510, 511]);
+        assert!(build_tree(&rows, 42).is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_gateway_state_dir() {
+        assert_eq!(gateway_state_dir("node openclaw gateway run", None, "/home/u"), "/home/u/.openclaw");
+        assert_eq!(gateway_state_dir("openclaw --dev gateway", None, "/home/u"), "/home/u/.openclaw-dev");
+        assert_eq!(gateway_state_dir("openclaw --profile work gateway", None, "/home/u"), "/home/u/.openclaw-work");
+        assert_eq!(gateway_state_dir("openclaw --profile=work gateway", Some("/data/oc"), "/home/u"), "/data/oc");
+
+        let environ = b"PATH=/usr/bin\0OPENCLAW_STATE_DIR=/data/oc\0";
+        assert_eq!(environ_value(environ, "OPENCLAW_STATE_DIR").as_deref(), Some("/data/oc"));
+        assert_eq!(environ_value(environ, "HOME"), None);
+    }
+
+    #[test]
+    fn test_is_gateway_command() {
+        assert!(is_gateway_command("node /usr/lib/node_modules/openclaw/openclaw.mjs gateway run --port 18790"));
+        assert!(is_gateway_command("openclaw --profile work gateway run"));
+        assert!(is_gateway_command("\"C:\\npm\\openclaw.cmd\" gateway run --port 18790"));
+        assert!(!is_gateway_command("python3 -m http.server 18790"));
+        assert!(!is_gateway_command("openclaw status"));
+        // "gateway" before the openclaw binary is an argument of something else
+        assert!(!is_gateway_command("my-gateway gateway /opt/openclaw"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Token count and per-message input cost of workspace files (SOUL.md, AGENTS.md, ...) that
+//! OpenClaw injects into every system prompt, priced with the agent's primary model.
+
+use crate::utils::session_replay;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Input prices of a model, in USD per million tokens (models.providers.*.models[].cost)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub cache_read: f64,
+}
+
+/// Estimate for one workspace file, as injected into the system prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct PromptCostEstimate {
+    pub file: String,
+    pub tokens: u64,
+    pub previous_tokens: u64,
+    /// tokens - previous_tokens
+    pub token_delta: i64,
+    /// Content past bootstrapMaxChars is cut by OpenClaw and not counted
+    pub truncated: bool,
+    /// Primary model of the agent ("provider/model")
+    pub model: Option<String>,
+    /// USD per million input tokens; None when the model has no price configured
+    pub input_price: Option<f64>,
+    /// Input cost this file adds to every message, in USD
+    pub cost_per_message: Option<f64>,
+    pub cost_delta_per_message: Option<f64>,
+    /// Same with a prompt cache hit, when the model has a cache read price
+    pub cached_cost_per_message: Option<f64>,
+}
+
+/// Rough token count: ~4 characters per token for Latin text, one per CJK character and two
+/// characters per token for other scripts
+pub fn estimate_tokens(text: &str) -> u64 {
+    let quarters: u64 = text
+        .chars()
+        .map(|c| match c as u32 {
+            0..=0x7F => 1,
+            0x3000..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF => 4,
+            _ => 2,
+        })
+        .sum();
+    quarters.div_ceil(4)
+}
+
+/// Primary model of an agent (its `model`, a string or `{ primary }`), else the default one
+pub fn agent_primary_model(config: &Value, agent_id: &str) -> Option<String> {
+    let entry = match config.pointer("/agents/list") {
+        Some(Value::Array(list)) => list
+            .iter()
+            .find(|a| a.get("id").and_then(|v| v.as_str()) == Some(agent_id)),
+        Some(Value::Object(map)) => map.get(agent_id),
+        _ => None,
+    };
+    entry
+        .and_then(|a| a.get("model"))
+        .and_then(|m| {
+            m.as_str()
+                .or_else(|| m.get("primary").and_then(|v| v.as_str()))
+        })
+        .or_else(|| {
+            config
+                .pointer("/agents/defaults/model/primary")
+                .and_then(|v| v.as_str())
+        })
+        .filter(|m| !m.is_empty())
+        .map(String::from)
+}
+
+/// Price of a "provider/model" reference; None when it is not configured or zero
+pub fn model_price(config: &Value, model: &str) -> Option<ModelPrice> {
+    let (provider, id) = model.split_once('/')?;
+    let cost = config
+        .pointer(&format!("/models/providers/{}/models", provider))?
+        .as_array()?
+        .iter()
+        .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(id))?
+        .get("cost")?;
+    let price = |key: &str| cost.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let input = price("input");
+    (input > 0.0).then(|| ModelPrice {
+        input,
+        cache_read: price("cacheRead"),
+    })
+}
+
+/// Estimate for saving `content` over `previous` (both cut to `max_chars` like OpenClaw does)
+pub fn estimate(
+    file: &str,
+    previous: &str,
+    content: &str,
+    max_chars: usize,
+    model: Option<String>,
+    price: Option<ModelPrice>,
+) -> PromptCostEstimate {
+    let (injected, truncated) = session_replay::truncate_bootstrap(content, max_chars);
+    let tokens = estimate_tokens(&injected);
+    let previous_tokens =
+        estimate_tokens(&session_replay::truncate_bootstrap(previous, max_chars).0);
+    let cost = |tokens: u64, per_million: f64| tokens as f64 * per_million / 1_000_000.0;
+    PromptCostEstimate {
+        file: file.to_string(),
+        tokens,
+        previous_tokens,
+        token_delta: tokens as i64 - previous_tokens as i64,
+        truncated,
+        model,
+        input_price: price.map(|p| p.input),
+        cost_per_message: price.map(|p| cost(tokens, p.input)),
+        cost_delta_per_message: price
+            .map(|p| cost(tokens, p.input) - cost(previous_tokens, p.input)),
+        cached_cost_per_message: price
+            .filter(|p| p.cache_read > 0.0)
+            .map(|p| cost(tokens, p.cache_read)),
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use super::super::prompt_cost::{
+        agent_primary_model, estimate, estimate_tokens, model_price, ModelPrice,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        // CJK characters count as one token each
+        assert_eq!(estimate_tokens("你好世界"), 4);
+        // Other non-ASCII text counts two characters per token
+        assert_eq!(estimate_tokens("مرحبا"), 3);
+    }
+
+    #[test]
+    fn test_agent_primary_model_and_price() {
+        let config = json!({
+            "agents": {
+                "defaults": { "model": { "primary": "anthropic/claude-sonnet-4-5" } },
+                "list": [
+                    { "id": "main" },
+                    { "id": "cheap", "model": "openrouter/google/gemini-flash" },
+                    { "id": "writer", "model": { "primary": "openai/gpt-5" } }
+                ]
+            },
+            "models": { "providers": {
+                "anthropic": { "models": [
+                    { "id": "claude-sonnet-4-5", "cost": { "input": 3.0, "output": 15.0, "cacheRead": 0.3 } }
+                ] },
+                "openrouter": { "models": [
+                    { "id": "google/gemini-flash", "cost": { "input": 0.3 } }
+                ] },
+                "openai": { "models": [
+                    { "id": "gpt-5", "cost": { "input": 0, "output": 0 } }
+                ] }
+            } }
+        });
+        assert_eq!(
+            agent_primary_model(&config, "main").as_deref(),
+            Some("anthropic/claude-sonnet-4-5")
+        );
+        assert_eq!(
+            agent_primary_model(&config, "cheap").as_deref(),
+            Some("openrouter/google/gemini-flash")
+        );
+        assert_eq!(
+            agent_primary_model(&config, "writer").as_deref(),
+            Some("openai/gpt-5")
+        );
+
+        assert_eq!(
+            model_price(&config, "anthropic/claude-sonnet-4-5"),
+            Some(ModelPrice {
+                input: 3.0,
+                cache_read: 0.3
+            })
+        );
+        assert_eq!(
+            model_price(&config, "openrouter/google/gemini-flash").map(|p| p.input),
+            Some(0.3)
+        );
+        // A zero price means no price was configured
+        assert_eq!(model_price(&config, "openai/gpt-5"), None);
+        assert_eq!(model_price(&config, "anthropic/unknown"), None);
+    }
+
+    #[test]
+    fn test_estimate_cost_delta() {
+        let price = Some(ModelPrice {
+            input: 3.0,
+            cache_read: 0.3,
+        });
+        let previous = "a".repeat(4_000);
+        let content = "a".repeat(40_000);
+        let saved = estimate(
+            "SOUL.md",
+            &previous,
+            &content,
+            100_000,
+            Some("anthropic/claude-sonnet-4-5".to_string()),
+            price,
+        );
+        assert_eq!(saved.tokens, 10_000);
+        assert_eq!(saved.previous_tokens, 1_000);
+        assert_eq!(saved.token_delta, 9_000);
+        assert!(!saved.truncated);
+        assert!((saved.cost_per_message.unwrap() - 0.03).abs() < 1e-9);
+        assert!((saved.cost_delta_per_message.unwrap() - 0.027).abs() < 1e-9);
+        assert!((saved.cached_cost_per_message.unwrap() - 0.003).abs() < 1e-9);
+
+        // Content past the limit is cut and not counted, and unpriced models have no cost
+        let cut = estimate("AGENTS.md", "", &content, 20_000, None, None);
+        assert!(cut.truncated);
+        assert!(cut.tokens < 6_000);
+        assert_eq!(cut.cost_per_message, None);
+        assert_eq!(cut.token_delta, cut.tokens as i64);
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+/// Header Azure OpenAI expects the key in (instead of `Authorization: Bearer`)
+pub const AZURE_API_KEY_HEADER: &str = "api-key";
+
+/// Headers the core sets itself; overriding them breaks requests
+const RESERVED_HEADERS: &[&str] = &["content-type", "content-length", "host"];
+
+/// Check a custom header name and value before they end up in every provider request
+pub fn validate_header(name: &str, value: &str) -> Result<(), String> {
+    // RFC 9110 token characters
+    let valid_name = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+    if !valid_name {
+        return Err(format!("Invalid header name '{}'", name));
+    }
+    if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+        return Err(format!("Header '{}' is set by OpenClaw and cannot be overridden", name));
+    }
+    if value.contains(['\r', '\n']) {
+        return Err(format!("Value of header '{}' must be a single line", name));
+    }
+    Ok(())
+}
+
+/// Apply edited headers onto the stored ones. An empty value keeps the stored value (the UI
+/// never receives header values, which often carry keys); headers missing from `incoming` are removed.
+pub fn merge_headers(existing: &HashMap<String, String>, incoming: &HashMap<String, String>) -> HashMap<String, String> {
+    incoming
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.trim();
+            if !value.is_empty() {
+                return Some((name.to_string(), value.clone()));
+            }
+            existing
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| (name.to_string(), v.clone()))
+        })
+        .collect()
+}
+
+/// OpenAI-compatible base URL of an Azure OpenAI resource (v1 API, model ids are deployment
+/// names). Accepts a resource name or its endpoint URL.
+pub fn azure_base_url(resource: &str) -> Result<String, String> {
+    let resource = resource.trim().trim_end_matches('/');
+    if resource.is_empty() {
+        return Err("Azure resource name or endpoint is required".to_string());
+    }
+    if resource.starts_with("https://") {
+        let base = resource.split("/openai").next().unwrap_or(resource);
+        return Ok(format!("{}/openai/v1", base));
+    }
+    if !resource.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid Azure resource name '{}'", resource));
+    }
+    Ok(format!("https://{}.openai.azure.com/openai/v1", resource))
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use super::super::provider_headers::{azure_base_url, merge_headers, validate_header};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_validate_header() {
+        assert!(validate_header("X-API-Version", "2024-10-01").is_ok());
+        assert!(validate_header("api-key", "secret").is_ok());
+        assert!(validate_header("Bad Name", "x").is_err());
+        assert!(validate_header("Content-Type", "text/plain").is_err());
+        assert!(validate_header("X-Evil", "a\r\nHost: other").is_err());
+    }
+
+    #[test]
+    fn test_merge_headers_keeps_blank_values() {
+        let existing = HashMap::from([
+            ("api-key".to_string(), "secret".to_string()),
+            ("X-Old".to_string(), "1".to_string()),
+        ]);
+        let incoming = HashMap::from([
+            ("API-Key".to_string(), String::new()),
+            ("X-New".to_string(), "2".to_string()),
+            ("X-Unknown".to_string(), String::new()),
+        ]);
+        let merged = merge_headers(&existing, &incoming);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["API-Key"], "secret");
+        assert_eq!(merged["X-New"], "2");
+    }
+
+    #[test]
+    fn test_azure_base_url() {
+        assert_eq!(azure_base_url("my-res").unwrap(), "https://my-res.openai.azure.com/openai/v1");
+        assert_eq!(
+            azure_base_url("https://my-res.openai.azure.com/openai/deployments/gpt4o/").unwrap(),
+            "https://my-res.openai.azure.com/openai/v1"
+        );
+        assert!(azure_base_url("").is_err());
+        assert!(azure_base_url("my res").is_err());
+    }
+}
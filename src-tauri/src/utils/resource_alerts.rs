@@ -0,0 +1,168 @@
+//! Gateway memory / CPU thresholds and the sampling state that decides when to alert.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Samples kept for the diagnostics capture (one per poll)
+pub const HISTORY_LEN: usize = 60;
+
+/// Alert thresholds (manager.json -> resourceAlerts)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceAlertConfig {
+    pub enabled: bool,
+    /// Resident memory of the gateway and its children, in MB
+    pub memory_mb: f64,
+    /// CPU usage of the gateway process, in percent of one core
+    pub cpu_percent: f64,
+    /// How long CPU usage has to stay above `cpu_percent`
+    pub cpu_minutes: u64,
+}
+
+impl Default for ResourceAlertConfig {
+    fn default() -> Self {
+        ResourceAlertConfig {
+            enabled: true,
+            memory_mb: 1536.0,
+            cpu_percent: 90.0,
+            cpu_minutes: 5,
+        }
+    }
+}
+
+impl ResourceAlertConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.memory_mb < 64.0 {
+            return Err("Memory threshold must be at least 64 MB".to_string());
+        }
+        if self.cpu_percent <= 0.0 {
+            return Err("CPU threshold must be above 0%".to_string());
+        }
+        if self.cpu_minutes == 0 {
+            return Err("CPU duration must be at least 1 minute".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Gateway resource usage at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ResourceSample {
+    /// Unix timestamp in seconds
+    pub at: u64,
+    pub memory_mb: f64,
+    /// CPU time the gateway process has used since it started
+    pub cpu_seconds: f64,
+    /// Usage since the previous sample; None for the first one
+    pub cpu_percent: Option<f64>,
+}
+
+/// A threshold that was crossed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ResourceAlert {
+    /// "memory" or "cpu"
+    pub kind: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub message: String,
+}
+
+/// Sampling state of one gateway process. Each threshold alerts once and re-arms when usage
+/// drops back below it.
+#[derive(Debug, Default)]
+pub struct ResourceMonitor {
+    pub history: VecDeque<ResourceSample>,
+    cpu_high_since: Option<u64>,
+    memory_alerted: bool,
+    cpu_alerted: bool,
+}
+
+impl ResourceMonitor {
+    /// Forget the state (the gateway stopped or was replaced)
+    pub fn reset(&mut self) {
+        *self = ResourceMonitor::default();
+    }
+
+    /// Record a sample (`cpu_percent` is filled in) and return the alerts it triggers
+    pub fn observe(
+        &mut self,
+        config: &ResourceAlertConfig,
+        mut sample: ResourceSample,
+    ) -> Vec<ResourceAlert> {
+        // CPU time going backwards means a new process got the PID
+        if self
+            .history
+            .back()
+            .is_some_and(|last| sample.cpu_seconds < last.cpu_seconds || sample.at <= last.at)
+        {
+            self.reset();
+        }
+        sample.cpu_percent = self.history.back().map(|last| {
+            (sample.cpu_seconds - last.cpu_seconds) / (sample.at - last.at) as f64 * 100.0
+        });
+
+        let mut alerts = Vec::new();
+        if sample.memory_mb > config.memory_mb {
+            if !self.memory_alerted {
+                self.memory_alerted = true;
+                alerts.push(ResourceAlert {
+                    kind: "memory".to_string(),
+                    value: sample.memory_mb,
+                    threshold: config.memory_mb,
+                    message: format!(
+                        "Gateway memory is {:.0} MB (limit {:.0} MB)",
+                        sample.memory_mb, config.memory_mb
+                    ),
+                });
+            }
+        } else {
+            self.memory_alerted = false;
+        }
+
+        match sample.cpu_percent {
+            Some(cpu) if cpu > config.cpu_percent => {
+                let since = *self
+                    .cpu_high_since
+                    .get_or_insert(self.history.back().map(|last| last.at).unwrap_or(sample.at));
+                if !self.cpu_alerted && sample.at - since >= config.cpu_minutes * 60 {
+                    self.cpu_alerted = true;
+                    alerts.push(ResourceAlert {
+                        kind: "cpu".to_string(),
+                        value: cpu,
+                        threshold: config.cpu_percent,
+                        message: format!(
+                            "Gateway CPU has been above {:.0}% for {} minutes",
+                            config.cpu_percent,
+                            (sample.at - since) / 60
+                        ),
+                    });
+                }
+            }
+            Some(_) => {
+                self.cpu_high_since = None;
+                self.cpu_alerted = false;
+            }
+            None => {}
+        }
+
+        self.history.push_back(sample);
+        while self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+        }
+        alerts
+    }
+}
+
+/// Parse the CPU time `ps -o time=` prints: `[[dd-]hh:]mm:ss` (Linux) or `m:ss.cc` (macOS)
+pub fn parse_cpu_time(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (days, clock) = match text.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, text),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86400.0 + seconds)
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use super::super::resource_alerts::{
+        parse_cpu_time, ResourceAlertConfig, ResourceMonitor, ResourceSample,
+    };
+
+    fn sample(at: u64, memory_mb: f64, cpu_seconds: f64) -> ResourceSample {
+        ResourceSample {
+            at,
+            memory_mb,
+            cpu_seconds,
+            cpu_percent: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_cpu_time() {
+        assert_eq!(parse_cpu_time("00:01:23"), Some(83.0));
+        assert_eq!(parse_cpu_time("1-02:00:00\n"), Some(93600.0));
+        assert_eq!(parse_cpu_time("  0:01.50"), Some(1.5));
+        assert_eq!(parse_cpu_time(""), None);
+        assert_eq!(parse_cpu_time("n/a"), None);
+    }
+
+    #[test]
+    fn test_memory_alerts_once_and_rearms() {
+        let config = ResourceAlertConfig::default();
+        let mut monitor = ResourceMonitor::default();
+        assert!(monitor.observe(&config, sample(0, 800.0, 0.0)).is_empty());
+
+        let alerts = monitor.observe(&config, sample(30, 1600.0, 1.0));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, "memory");
+        assert!(monitor.observe(&config, sample(60, 1700.0, 2.0)).is_empty());
+
+        assert!(monitor.observe(&config, sample(90, 900.0, 3.0)).is_empty());
+        assert_eq!(monitor.observe(&config, sample(120, 1600.0, 4.0)).len(), 1);
+    }
+
+    #[test]
+    fn test_cpu_alerts_after_sustained_load() {
+        let config = ResourceAlertConfig {
+            cpu_minutes: 2,
+            ..ResourceAlertConfig::default()
+        };
+        let mut monitor = ResourceMonitor::default();
+        monitor.observe(&config, sample(0, 100.0, 0.0));
+        // 100% of a core from t=0
+        assert!(monitor.observe(&config, sample(60, 100.0, 60.0)).is_empty());
+        let alerts = monitor.observe(&config, sample(120, 100.0, 120.0));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, "cpu");
+        assert!(monitor
+            .observe(&config, sample(180, 100.0, 180.0))
+            .is_empty());
+        assert_eq!(monitor.history.back().unwrap().cpu_percent, Some(100.0));
+    }
+
+    #[test]
+    fn test_cpu_dip_resets_the_timer() {
+        let config = ResourceAlertConfig {
+            cpu_minutes: 2,
+            ..ResourceAlertConfig::default()
+        };
+        let mut monitor = ResourceMonitor::default();
+        monitor.observe(&config, sample(0, 100.0, 0.0));
+        monitor.observe(&config, sample(60, 100.0, 60.0));
+        // 10% for a minute
+        monitor.observe(&config, sample(120, 100.0, 66.0));
+        assert!(monitor
+            .observe(&config, sample(180, 100.0, 126.0))
+            .is_empty());
+        assert_eq!(monitor.observe(&config, sample(240, 100.0, 186.0)).len(), 1);
+    }
+
+    #[test]
+    fn test_new_process_starts_over() {
+        let config = ResourceAlertConfig::default();
+        let mut monitor = ResourceMonitor::default();
+        monitor.observe(&config, sample(0, 100.0, 500.0));
+        monitor.observe(&config, sample(30, 100.0, 510.0));
+        monitor.observe(&config, sample(60, 100.0, 2.0));
+        assert_eq!(monitor.history.len(), 1);
+        assert_eq!(monitor.history[0].cpu_percent, None);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ResourceAlertConfig::default().validate().is_ok());
+        let config = ResourceAlertConfig {
+            cpu_minutes: 0,
+            ..ResourceAlertConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Free memory / disk floors checked before the gateway starts, so a start on a machine that is
+//! already short on RAM warns (or is refused) instead of pushing the whole desktop into swap.
+
+use crate::utils::{platform, shell};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// manager.json key holding the floors
+pub const SETTINGS_KEY: &str = "resourceFloor";
+
+/// Start-time floors (manager.json -> resourceFloor)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceFloorConfig {
+    pub enabled: bool,
+    /// Memory that has to be available before the gateway starts, in MB
+    pub memory_mb: f64,
+    /// Free space required on the drive holding the config directory, in MB
+    pub disk_mb: f64,
+    /// Refuse to start below a floor unless the start is forced; otherwise only warn
+    pub block: bool,
+}
+
+impl Default for ResourceFloorConfig {
+    fn default() -> Self {
+        ResourceFloorConfig {
+            enabled: true,
+            memory_mb: 1024.0,
+            disk_mb: 1024.0,
+            block: true,
+        }
+    }
+}
+
+impl ResourceFloorConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.memory_mb.is_finite() || self.memory_mb < 0.0 {
+            return Err("Memory floor must be 0 MB or more".to_string());
+        }
+        if !self.disk_mb.is_finite() || self.disk_mb < 0.0 {
+            return Err("Disk floor must be 0 MB or more".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Free memory and disk space; None where the platform query failed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct SystemResources {
+    pub available_memory_mb: Option<f64>,
+    pub total_memory_mb: Option<f64>,
+    pub available_disk_mb: Option<f64>,
+}
+
+/// A floor the machine is below
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct FloorViolation {
+    /// "memory" or "disk"
+    pub kind: String,
+    pub value: f64,
+    pub floor: f64,
+    pub message: String,
+}
+
+/// Floors `resources` is below. `reclaimable_memory_mb` is memory a restart frees first (the
+/// running gateway's own). Unknown values are not checked.
+pub fn evaluate(
+    config: &ResourceFloorConfig,
+    resources: &SystemResources,
+    reclaimable_memory_mb: f64,
+) -> Vec<FloorViolation> {
+    let mut violations = Vec::new();
+    if !config.enabled {
+        return violations;
+    }
+    if let Some(available) = resources.available_memory_mb {
+        let available = available + reclaimable_memory_mb;
+        if available < config.memory_mb {
+            violations.push(FloorViolation {
+                kind: "memory".to_string(),
+                value: available,
+                floor: config.memory_mb,
+                message: format!(
+                    "Only {:.0} MB of memory available (floor {:.0} MB), the gateway and its agents may push the system into swap",
+                    available, config.memory_mb
+                ),
+            });
+        }
+    }
+    if let Some(available) = resources.available_disk_mb {
+        if available < config.disk_mb {
+            violations.push(FloorViolation {
+                kind: "disk".to_string(),
+                value: available,
+                floor: config.disk_mb,
+                message: format!(
+                    "Only {:.0} MB free on the config drive (floor {:.0} MB), sessions and logs may fail to write",
+                    available, config.disk_mb
+                ),
+            });
+        }
+    }
+    violations
+}
+
+fn kb_value(line: &str) -> Option<f64> {
+    line.split_whitespace().nth(1)?.parse::<f64>().ok()
+}
+
+/// Available and total memory in MB from Linux /proc/meminfo (MemAvailable, or free + buffers +
+/// cache on kernels older than 3.14)
+pub fn parse_meminfo(text: &str) -> Option<(f64, f64)> {
+    let field = |name: &str| {
+        text.lines()
+            .find(|l| l.starts_with(&format!("{}:", name)))
+            .and_then(kb_value)
+    };
+    let total = field("MemTotal")?;
+    let available = field("MemAvailable").or_else(|| {
+        Some(field("MemFree")? + field("Buffers").unwrap_or(0.0) + field("Cached").unwrap_or(0.0))
+    })?;
+    Some((available / 1024.0, total / 1024.0))
+}
+
+/// Available memory in MB from macOS `vm_stat` (free, inactive and speculative pages)
+pub fn parse_vm_stat(text: &str) -> Option<f64> {
+    let page_size = text
+        .split("page size of ")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|n| n.parse::<f64>().ok())?;
+    let pages = |name: &str| {
+        text.lines()
+            .find(|l| l.starts_with(&format!("Pages {}:", name)))
+            .and_then(|l| l.rsplit(':').next())
+            .and_then(|n| n.trim().trim_end_matches('.').parse::<f64>().ok())
+    };
+    let free = pages("free")?;
+    let reusable = pages("inactive").unwrap_or(0.0) + pages("speculative").unwrap_or(0.0);
+    Some((free + reusable) * page_size / 1024.0 / 1024.0)
+}
+
+/// Available and total memory in MB from "FreePhysicalMemory TotalVisibleMemorySize" (KB)
+pub fn parse_windows_memory(text: &str) -> Option<(f64, f64)> {
+    let mut values = text.split_whitespace().map(|v| v.parse::<f64>().ok());
+    let free = values.next()??;
+    let total = values.next()??;
+    Some((free / 1024.0, total / 1024.0))
+}
+
+/// Available space in MB from `df -Pk <dir>`
+pub fn parse_df(text: &str) -> Option<f64> {
+    let line = text.lines().nth(1)?;
+    line.split_whitespace()
+        .nth(3)?
+        .parse::<f64>()
+        .ok()
+        .map(|kb| kb / 1024.0)
+}
+
+fn memory() -> (Option<f64>, Option<f64>) {
+    if platform::is_windows() {
+        let script = "$os = Get-CimInstance Win32_OperatingSystem; \"$($os.FreePhysicalMemory) $($os.TotalVisibleMemorySize)\"";
+        let parsed = shell::run_command_output(
+            "powershell",
+            &["-NoProfile", "-NonInteractive", "-Command", script],
+        )
+        .ok()
+        .and_then(|out| parse_windows_memory(&out));
+        (parsed.map(|p| p.0), parsed.map(|p| p.1))
+    } else if platform::is_macos() {
+        let available = shell::run_command_output("vm_stat", &[])
+            .ok()
+            .and_then(|out| parse_vm_stat(&out));
+        let total = shell::run_command_output("sysctl", &["-n", "hw.memsize"])
+            .ok()
+            .and_then(|out| out.trim().parse::<f64>().ok())
+            .map(|bytes| bytes / 1024.0 / 1024.0);
+        (available, total)
+    } else {
+        let parsed = std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|text| parse_meminfo(&text));
+        (parsed.map(|p| p.0), parsed.map(|p| p.1))
+    }
+}
+
+/// Free space on the drive holding `dir` (or its closest existing parent)
+fn disk(dir: &str) -> Option<f64> {
+    let dir = Path::new(dir).ancestors().find(|p| p.exists())?;
+    let dir = dir.to_string_lossy();
+    if platform::is_windows() {
+        let script = format!(
+            "([System.IO.DriveInfo]::new('{}')).AvailableFreeSpace",
+            dir.replace('\'', "''")
+        );
+        shell::run_command_output(
+            "powershell",
+            &["-NoProfile", "-NonInteractive", "-Command", &script],
+        )
+        .ok()
+        .and_then(|out| out.trim().parse::<f64>().ok())
+        .map(|bytes| bytes / 1024.0 / 1024.0)
+    } else {
+        shell::run_command_output("df", &["-Pk", &dir])
+            .ok()
+            .and_then(|out| parse_df(&out))
+    }
+}
+
+/// Free memory now and free space on the drive holding the config directory
+pub fn probe() -> SystemResources {
+    let (available_memory_mb, total_memory_mb) = memory();
+    SystemResources {
+        available_memory_mb,
+        total_memory_mb,
+        available_disk_mb: disk(&platform::get_config_dir()),
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use super::super::resource_floor::{
+        evaluate, parse_df, parse_meminfo, parse_vm_stat, parse_windows_memory,
+        ResourceFloorConfig, SystemResources,
+    };
+
+    fn resources(memory: Option<f64>, disk: Option<f64>) -> SystemResources {
+        SystemResources {
+            available_memory_mb: memory,
+            total_memory_mb: Some(8192.0),
+            available_disk_mb: disk,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_reports_floors_below() {
+        let config = ResourceFloorConfig::default();
+        let violations = evaluate(&config, &resources(Some(600.0), Some(300.0)), 0.0);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].kind, "memory");
+        assert_eq!(violations[0].value, 600.0);
+        assert!(violations[0].message.contains("600 MB"));
+        assert_eq!(violations[1].kind, "disk");
+
+        assert!(evaluate(&config, &resources(Some(4096.0), Some(20_000.0)), 0.0).is_empty());
+        // Unknown values are not checked
+        assert!(evaluate(&config, &resources(None, None), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_counts_reclaimable_memory_and_disabled_config() {
+        let config = ResourceFloorConfig::default();
+        // A restart frees the running gateway's memory first
+        assert!(evaluate(&config, &resources(Some(700.0), None), 500.0).is_empty());
+
+        let disabled = ResourceFloorConfig {
+            enabled: false,
+            ..ResourceFloorConfig::default()
+        };
+        assert!(evaluate(&disabled, &resources(Some(10.0), Some(10.0)), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ResourceFloorConfig::default().validate().is_ok());
+        let negative = ResourceFloorConfig {
+            memory_mb: -1.0,
+            ..ResourceFloorConfig::default()
+        };
+        assert!(negative.validate().is_err());
+        let nan = ResourceFloorConfig {
+            disk_mb: f64::NAN,
+            ..ResourceFloorConfig::default()
+        };
+        assert!(nan.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let text = "MemTotal:        8388608 kB\nMemFree:          204800 kB\nMemAvailable:    1048576 kB\nBuffers:           10240 kB\n";
+        assert_eq!(parse_meminfo(text), Some((1024.0, 8192.0)));
+        // Kernels without MemAvailable
+        let old =
+            "MemTotal: 2097152 kB\nMemFree: 512000 kB\nBuffers: 12288 kB\nCached: 307200 kB\n";
+        assert_eq!(parse_meminfo(old), Some((812.0, 2048.0)));
+        assert_eq!(parse_meminfo("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_vm_stat() {
+        let text = "Mach Virtual Memory Statistics: (page size of 16384 bytes)\n\
+                    Pages free:                               12800.\n\
+                    Pages active:                            400000.\n\
+                    Pages inactive:                           38400.\n\
+                    Pages speculative:                        12800.\n";
+        assert_eq!(parse_vm_stat(text), Some(1000.0));
+        assert_eq!(parse_vm_stat("Pages free: 10."), None);
+    }
+
+    #[test]
+    fn test_parse_windows_memory_and_df() {
+        assert_eq!(
+            parse_windows_memory("2097152 16777216\r\n"),
+            Some((2048.0, 16384.0))
+        );
+        assert_eq!(parse_windows_memory(""), None);
+
+        let df = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                  /dev/disk3s5    494384795 400000000  51200000      89% /System/Volumes/Data\n";
+        assert_eq!(parse_df(df), Some(50_000.0));
+        assert_eq!(parse_df("df: /nope: No such file or directory\n"), None);
+    }
+}
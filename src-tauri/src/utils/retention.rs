@@ -0,0 +1,63 @@
+/// A file considered for retention cleanup
+#[derive(Debug, Clone)]
+pub struct RetainedFile {
+    pub path: String,
+    pub bytes: u64,
+    /// Seconds since the file was last modified
+    pub age_secs: u64,
+    /// Per-directory age limit overriding the global one
+    pub max_age_days: Option<u32>,
+}
+
+/// Why a file was selected for removal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    Expired,
+    OverQuota,
+}
+
+/// Age limit of a directory shared by channels with `channel_limits`: the longest one, where
+/// a channel without a limit follows `global` and no limit at all keeps files forever
+pub fn shared_age_limit(channel_limits: &[Option<u32>], global: Option<u32>) -> Option<u32> {
+    if channel_limits.is_empty() {
+        return global;
+    }
+    channel_limits
+        .iter()
+        .map(|limit| limit.or(global))
+        .try_fold(0, |longest, limit| limit.map(|days| longest.max(days)))
+}
+
+/// Pick files to delete: everything older than its age limit, then the oldest remaining
+/// files until the total fits in `max_total_bytes`. Returns (index, reason) pairs.
+pub fn select_for_removal(
+    files: &[RetainedFile],
+    max_age_days: Option<u32>,
+    max_total_bytes: Option<u64>,
+) -> Vec<(usize, RemovalReason)> {
+    let mut selected = Vec::new();
+    let mut kept: Vec<usize> = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        let limit = file.max_age_days.or(max_age_days);
+        match limit {
+            Some(days) if file.age_secs >= days as u64 * 86_400 => {
+                selected.push((i, RemovalReason::Expired))
+            }
+            _ => kept.push(i),
+        }
+    }
+
+    if let Some(max_bytes) = max_total_bytes {
+        let mut total: u64 = kept.iter().map(|&i| files[i].bytes).sum();
+        // Oldest first
+        kept.sort_by(|&a, &b| files[b].age_secs.cmp(&files[a].age_secs));
+        for i in kept {
+            if total <= max_bytes {
+                break;
+            }
+            total -= files[i].bytes;
+            selected.push((i, RemovalReason::OverQuota));
+        }
+    }
+    selected
+}
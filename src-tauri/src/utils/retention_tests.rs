@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use super::super::retention::{
+        select_for_removal, shared_age_limit, RemovalReason, RetainedFile,
+    };
+
+    fn file(path: &str, bytes: u64, age_days: u64, max_age_days: Option<u32>) -> RetainedFile {
+        RetainedFile {
+            path: path.to_string(),
+            bytes,
+            age_secs: age_days * 86_400,
+            max_age_days,
+        }
+    }
+
+    #[test]
+    fn test_select_expired() {
+        let files = vec![
+            file("old.jpg", 10, 40, None),
+            file("new.jpg", 10, 2, None),
+            file("short.pdf", 10, 5, Some(3)),
+        ];
+        let selected = select_for_removal(&files, Some(30), None);
+        assert_eq!(selected, vec![(0, RemovalReason::Expired), (2, RemovalReason::Expired)]);
+        assert!(select_for_removal(&files, None, None).contains(&(2, RemovalReason::Expired)));
+        assert_eq!(select_for_removal(&files, None, None).len(), 1);
+    }
+
+    #[test]
+    fn test_select_over_quota_removes_oldest_first() {
+        let files = vec![
+            file("a", 100, 1, None),
+            file("b", 100, 9, None),
+            file("c", 100, 5, None),
+        ];
+        let selected = select_for_removal(&files, None, Some(150));
+        assert_eq!(selected, vec![(1, RemovalReason::OverQuota), (2, RemovalReason::OverQuota)]);
+        assert!(select_for_removal(&files, None, Some(300)).is_empty());
+    }
+
+    #[test]
+    fn test_expired_files_count_against_quota_first() {
+        let files = vec![file("a", 100, 50, None), file("b", 100, 1, None)];
+        let selected = select_for_removal(&files, Some(30), Some(100));
+        assert_eq!(selected, vec![(0, RemovalReason::Expired)]);
+    }
+
+    #[test]
+    fn test_shared_age_limit_keeps_the_longest() {
+        assert_eq!(shared_age_limit(&[Some(3), Some(10)], Some(30)), Some(10));
+        // A channel without its own limit follows the global one
+        assert_eq!(shared_age_limit(&[Some(3), None], Some(30)), Some(30));
+        // ...and keeps files forever when there is none
+        assert_eq!(shared_age_limit(&[Some(3), None], None), None);
+        assert_eq!(shared_age_limit(&[], Some(7)), Some(7));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::utils::json_diff;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Replacement written in place of a redacted secret
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value that looks like a credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct SecretFinding {
+    /// Dotted path to the value (e.g. "models.providers.openai.apiKey")
+    pub path: String,
+    /// What triggered the finding (e.g. "OpenAI API key", "high-entropy string")
+    pub kind: String,
+    /// First/last characters only, safe to show in the UI
+    pub preview: String,
+}
+
+fn known_prefixes() -> &'static Vec<(Regex, &'static str)> {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        vec![
+            (Regex::new(r"sk-ant-[a-zA-Z0-9\-_]{20,}").unwrap(), "Anthropic API key"),
+            (Regex::new(r"sk-(proj-)?[a-zA-Z0-9\-_]{20,}").unwrap(), "OpenAI API key"),
+            (Regex::new(r"AIza[0-9A-Za-z\-_]{35}").unwrap(), "Google API key"),
+            (Regex::new(r"hf_[a-zA-Z0-9]{30,}").unwrap(), "Hugging Face token"),
+            (Regex::new(r"gh[pousr]_[A-Za-z0-9]{36,}").unwrap(), "GitHub token"),
+            (Regex::new(r"github_pat_[A-Za-z0-9_]{22,}").unwrap(), "GitHub token"),
+            (Regex::new(r"xox[baprs]-[0-9a-zA-Z\-]{10,}").unwrap(), "Slack token"),
+            (Regex::new(r"AKIA[0-9A-Z]{16}").unwrap(), "AWS access key"),
+            (Regex::new(r"\b[0-9]{8,10}:[a-zA-Z0-9_-]{35}\b").unwrap(), "Telegram bot token"),
+            (Regex::new(r"-----BEGIN [A-Z ]*PRIVATE KEY-----").unwrap(), "private key"),
+            (Regex::new(r"(?i)bearer\s+[a-zA-Z0-9\-_=\.]{20,}").unwrap(), "bearer token"),
+        ]
+    })
+}
+
+/// Shannon entropy in bits per character
+pub fn shannon_entropy(s: &str) -> f64 {
+    let len = s.chars().count() as f64;
+    if len == 0.0 {
+        return 0.0;
+    }
+    let mut counts = std::collections::HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Env references and obvious placeholders are not secrets
+fn is_placeholder(s: &str) -> bool {
+    let t = s.trim();
+    t.is_empty()
+        || t == REDACTED
+        || t.starts_with("${")
+        || t.starts_with("env:")
+        || t.chars().all(|c| c == '*' || c == 'x' || c == 'X')
+        || t.eq_ignore_ascii_case("changeme")
+        || (t.starts_with('<') && t.ends_with('>'))
+}
+
+/// Long random-looking token: no spaces, not a path/URL, mixed character classes, high entropy
+fn looks_random(s: &str) -> bool {
+    if s.len() < 24 || s.contains(char::is_whitespace) || s.contains("://") || s.starts_with(['/', '~', '.']) {
+        return false;
+    }
+    if !s.chars().all(|c| c.is_ascii_alphanumeric() || "+/=_-.".contains(c)) {
+        return false;
+    }
+    let classes = [
+        s.chars().any(|c| c.is_ascii_lowercase()),
+        s.chars().any(|c| c.is_ascii_uppercase()),
+        s.chars().any(|c| c.is_ascii_digit()),
+    ];
+    classes.iter().filter(|&&b| b).count() >= 2 && shannon_entropy(s) >= 4.0
+}
+
+/// Mask a secret for display: keep at most 4 leading and 2 trailing characters
+pub fn preview(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 10 {
+        return "***".to_string();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 2..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// Classify a single string value found under `key`
+pub fn classify(key: &str, value: &str) -> Option<String> {
+    if is_placeholder(value) {
+        return None;
+    }
+    if let Some((_, kind)) = known_prefixes().iter().find(|(re, _)| re.is_match(value)) {
+        return Some(kind.to_string());
+    }
+    if json_diff::is_secret_key(key) && value.trim().len() >= 8 {
+        return Some(format!("value of '{}'", key));
+    }
+    if looks_random(value) {
+        return Some("high-entropy string".to_string());
+    }
+    None
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn scan_at(path: &str, key: &str, value: &Value, findings: &mut Vec<SecretFinding>) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                scan_at(&join_path(path, k), k, v, findings);
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                scan_at(&join_path(path, &i.to_string()), key, v, findings);
+            }
+        }
+        Value::String(s) => {
+            if let Some(kind) = classify(key, s) {
+                findings.push(SecretFinding {
+                    path: path.to_string(),
+                    kind,
+                    preview: preview(s),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Find every string value in `value` that looks like an API key, token or other credential
+pub fn scan(value: &Value) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    scan_at("", "", value, &mut findings);
+    findings
+}
+
+fn redact_at(key: &str, value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), redact_at(k, v))).collect()),
+        Value::Array(items) => Value::Array(items.iter().map(|v| redact_at(key, v)).collect()),
+        Value::String(s) if classify(key, s).is_some() => Value::String(REDACTED.to_string()),
+        other => other.clone(),
+    }
+}
+
+/// Copy of `value` with every finding replaced by `REDACTED`
+pub fn redact(value: &Value) -> Value {
+    redact_at("", value)
+}
+
+/// Copy of `incoming` with `REDACTED` strings replaced by the string at the same place in `local`,
+/// so a redacted export can be applied without wiping the keys already configured
+pub fn restore_redacted(incoming: &Value, local: &Value) -> Value {
+    match (incoming, local) {
+        (Value::String(s), Value::String(_)) if s == REDACTED => local.clone(),
+        (Value::Object(map), Value::Object(local_map)) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let restored = match local_map.get(k) {
+                        Some(l) => restore_redacted(v, l),
+                        None => v.clone(),
+                    };
+                    (k.clone(), restored)
+                })
+                .collect(),
+        ),
+        (Value::Array(items), Value::Array(local_items)) => Value::Array(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| match local_items.get(i) {
+                    Some(l) => restore_redacted(v, l),
+                    None => v.clone(),
+                })
+                .collect(),
+        ),
+        _ => incoming.clone(),
+    }
+}
+
+/// Gate for export paths. `policy` is the user's choice: "redact" strips secrets, "include" keeps
+/// them. Without a choice, an export containing secrets is refused so the UI can ask first.
+pub fn apply_policy(value: &Value, policy: Option<&str>) -> Result<Value, String> {
+    let findings = scan(value);
+    if findings.is_empty() {
+        return Ok(value.clone());
+    }
+    match policy {
+        Some("redact") => Ok(redact(value)),
+        Some("include") => Ok(value.clone()),
+        Some(other) => Err(format!("Unknown secrets policy '{}', expected 'redact' or 'include'", other)),
+        None => {
+            let paths: Vec<&str> = findings.iter().take(5).map(|f| f.path.as_str()).collect();
+            Err(format!(
+                "Export contains {} possible secret(s) ({}{}). Choose to redact them or confirm including them",
+                findings.len(),
+                paths.join(", "),
+                if findings.len() > paths.len() { ", ..." } else { "" }
+            ))
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Rebuild what the model saw at each turn of an OpenClaw session transcript
+//! (`agents/<id>/sessions/<session>.jsonl`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Workspace files OpenClaw injects into the system prompt, in prompt order
+pub const BOOTSTRAP_FILES: &[&str] = &[
+    "AGENTS.md",
+    "SOUL.md",
+    "TOOLS.md",
+    "IDENTITY.md",
+    "USER.md",
+    "HEARTBEAT.md",
+    "BOOTSTRAP.md",
+    "MEMORY.md",
+];
+
+/// Per-file limit OpenClaw applies when agents.defaults.bootstrapMaxChars is not set
+pub const DEFAULT_BOOTSTRAP_MAX_CHARS: usize = 20_000;
+
+/// One message of the transcript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayMessage {
+    pub index: usize,
+    pub id: Option<String>,
+    pub timestamp: Option<String>,
+    /// user, assistant, toolResult, or compaction (summary that replaced older messages)
+    pub role: String,
+    pub text: String,
+    /// Tools the assistant called in this message
+    pub tool_calls: Vec<String>,
+    pub model: Option<String>,
+}
+
+/// One model call: the messages that were in its context and the message it produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayTurn {
+    pub turn: usize,
+    pub timestamp: Option<String>,
+    pub model: Option<String>,
+    /// Indexes into the message list, in the order they were sent
+    pub window: Vec<usize>,
+    pub response: usize,
+    pub usage: Option<Value>,
+}
+
+/// Parsed transcript
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    pub started_at: Option<String>,
+    pub messages: Vec<ReplayMessage>,
+    pub turns: Vec<ReplayTurn>,
+    /// Lines that were not valid JSON
+    pub skipped_lines: usize,
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Text of a message's content (a string or a list of blocks) and the tools it called
+fn content_text(content: &Value) -> (String, Vec<String>) {
+    let blocks = match content {
+        Value::String(s) => return (s.clone(), Vec::new()),
+        Value::Array(blocks) => blocks,
+        _ => return (String::new(), Vec::new()),
+    };
+    let mut parts = Vec::new();
+    let mut tools = Vec::new();
+    for block in blocks {
+        match block
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+        {
+            "text" => parts.extend(str_field(block, "text")),
+            "toolCall" | "tool_use" => {
+                let name = str_field(block, "name").unwrap_or_else(|| "tool".to_string());
+                let args = block
+                    .get("arguments")
+                    .or_else(|| block.get("input"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                parts.push(format!("[tool call {}({})]", name, args));
+                tools.push(name);
+            }
+            "image" => parts.push("[image]".to_string()),
+            // Reasoning is not part of what the next call sends as context
+            "thinking" | "redacted_thinking" => {}
+            _ => parts.extend(str_field(block, "text")),
+        }
+    }
+    (parts.join("\n"), tools)
+}
+
+fn model_of(message: &Value) -> Option<String> {
+    let model = str_field(message, "model")?;
+    Some(match str_field(message, "provider") {
+        Some(provider) if !model.contains('/') => format!("{}/{}", provider, model),
+        _ => model,
+    })
+}
+
+/// Parse a session transcript. Each assistant message is one model call whose context is
+/// every message before it, or after a compaction the summary plus the messages it kept.
+pub fn parse(content: &str) -> Transcript {
+    let mut transcript = Transcript::default();
+    let mut context: Vec<usize> = Vec::new();
+    let mut current_model: Option<String> = None;
+
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let entry: Value = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(_) => {
+                transcript.skipped_lines += 1;
+                continue;
+            }
+        };
+        let kind = entry.get("type").and_then(|t| t.as_str());
+        match kind {
+            Some("session") => transcript.started_at = str_field(&entry, "timestamp"),
+            Some("model_change") => {
+                current_model = match (str_field(&entry, "provider"), str_field(&entry, "modelId"))
+                {
+                    (Some(provider), Some(model)) => Some(format!("{}/{}", provider, model)),
+                    (None, Some(model)) => Some(model),
+                    _ => current_model,
+                }
+            }
+            Some("compaction") => {
+                let index = transcript.messages.len();
+                transcript.messages.push(ReplayMessage {
+                    index,
+                    id: str_field(&entry, "id"),
+                    timestamp: str_field(&entry, "timestamp"),
+                    role: "compaction".to_string(),
+                    text: str_field(&entry, "summary").unwrap_or_default(),
+                    tool_calls: Vec::new(),
+                    model: None,
+                });
+                let first_kept = str_field(&entry, "firstKeptEntryId");
+                let kept_from = context
+                    .iter()
+                    .position(|&i| first_kept.is_some() && transcript.messages[i].id == first_kept)
+                    .unwrap_or(context.len());
+                let mut window = vec![index];
+                window.extend_from_slice(&context[kept_from..]);
+                context = window;
+            }
+            // `{"type":"message","message":{...}}`, or a bare message in older transcripts
+            Some("message") | None => {
+                let message = entry.get("message").unwrap_or(&entry);
+                let Some(role) = str_field(message, "role") else {
+                    continue;
+                };
+                let (text, tool_calls) =
+                    content_text(message.get("content").unwrap_or(&Value::Null));
+                let index = transcript.messages.len();
+                let model = model_of(message).or_else(|| current_model.clone());
+                let timestamp = str_field(&entry, "timestamp").or_else(|| {
+                    message.get("timestamp").and_then(|t| match t {
+                        Value::String(s) => Some(s.clone()),
+                        Value::Number(n) => Some(n.to_string()),
+                        _ => None,
+                    })
+                });
+                if role == "assistant" {
+                    transcript.turns.push(ReplayTurn {
+                        turn: transcript.turns.len() + 1,
+                        timestamp: timestamp.clone(),
+                        model: model.clone(),
+                        window: context.clone(),
+                        response: index,
+                        usage: message.get("usage").cloned(),
+                    });
+                }
+                transcript.messages.push(ReplayMessage {
+                    index,
+                    id: str_field(&entry, "id"),
+                    timestamp,
+                    role: role.clone(),
+                    text,
+                    tool_calls,
+                    model: if role == "assistant" { model } else { None },
+                });
+                context.push(index);
+            }
+            _ => {}
+        }
+    }
+    transcript
+}
+
+/// Cut a bootstrap file the way OpenClaw does when it exceeds `max_chars`: keep the head
+/// and the tail with a marker in between. Returns the text and whether it was cut.
+pub fn truncate_bootstrap(content: &str, max_chars: usize) -> (String, bool) {
+    let chars: Vec<char> = content.chars().collect();
+    if chars.len() <= max_chars {
+        return (content.to_string(), false);
+    }
+    let head = max_chars * 7 / 10;
+    let tail = max_chars * 2 / 10;
+    let text = format!(
+        "{}\n\n[... truncated, read the file for the full content ...]\n\n{}",
+        chars[..head].iter().collect::<String>(),
+        chars[chars.len() - tail..].iter().collect::<String>()
+    );
+    (text, true)
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use super::super::session_replay::{parse, truncate_bootstrap};
+
+    const TRANSCRIPT: &str = r#"{"type":"session","id":"s1","timestamp":"2026-10-01T10:00:00.000Z"}
+{"type":"model_change","id":"e0","provider":"anthropic","modelId":"claude-sonnet-4-5"}
+{"type":"message","id":"e1","timestamp":"2026-10-01T10:00:01.000Z","message":{"role":"user","content":[{"type":"text","text":"What is in notes.md?"}]}}
+{"type":"message","id":"e2","timestamp":"2026-10-01T10:00:02.000Z","message":{"role":"assistant","content":[{"type":"thinking","thinking":"read it"},{"type":"toolCall","name":"read","arguments":{"path":"notes.md"}}],"usage":{"input":120,"output":8}}}
+{"type":"message","id":"e3","message":{"role":"toolResult","content":[{"type":"text","text":"buy milk"}]}}
+not json
+{"type":"message","id":"e4","message":{"role":"assistant","provider":"openai","model":"gpt-5","content":[{"type":"text","text":"It says: buy milk"}]}}
+"#;
+
+    #[test]
+    fn test_each_assistant_message_is_a_turn_with_the_preceding_window() {
+        let transcript = parse(TRANSCRIPT);
+        assert_eq!(
+            transcript.started_at.as_deref(),
+            Some("2026-10-01T10:00:00.000Z")
+        );
+        assert_eq!(transcript.skipped_lines, 1);
+        assert_eq!(transcript.messages.len(), 4);
+        assert_eq!(transcript.turns.len(), 2);
+
+        let first = &transcript.turns[0];
+        assert_eq!(first.window, vec![0]);
+        assert_eq!(first.response, 1);
+        assert_eq!(first.model.as_deref(), Some("anthropic/claude-sonnet-4-5"));
+        assert!(first.usage.is_some());
+        assert_eq!(transcript.messages[1].tool_calls, vec!["read".to_string()]);
+        assert!(!transcript.messages[1].text.contains("read it"));
+
+        let second = &transcript.turns[1];
+        assert_eq!(second.window, vec![0, 1, 2]);
+        assert_eq!(second.model.as_deref(), Some("openai/gpt-5"));
+        assert_eq!(
+            transcript.messages[second.response].text,
+            "It says: buy milk"
+        );
+    }
+
+    #[test]
+    fn test_compaction_replaces_older_messages_with_the_summary() {
+        let content = r#"{"type":"message","id":"a","message":{"role":"user","content":"one"}}
+{"type":"message","id":"b","message":{"role":"assistant","content":"two"}}
+{"type":"message","id":"c","message":{"role":"user","content":"three"}}
+{"type":"compaction","id":"k","summary":"User counted to two","firstKeptEntryId":"c"}
+{"type":"message","id":"d","message":{"role":"assistant","content":"four"}}
+"#;
+        let transcript = parse(content);
+        assert_eq!(transcript.messages[3].role, "compaction");
+        let last = transcript.turns.last().unwrap();
+        assert_eq!(last.window, vec![3, 2]);
+    }
+
+    #[test]
+    fn test_bare_messages_of_older_transcripts() {
+        let transcript = parse("{\"role\":\"user\",\"content\":\"hi\"}\n{\"role\":\"assistant\",\"content\":\"hello\"}\n");
+        assert_eq!(transcript.turns.len(), 1);
+        assert_eq!(transcript.turns[0].window, vec![0]);
+    }
+
+    #[test]
+    fn test_truncate_bootstrap_keeps_head_and_tail() {
+        assert_eq!(
+            truncate_bootstrap("short", 100),
+            ("short".to_string(), false)
+        );
+        let long = format!("{}{}", "a".repeat(80), "z".repeat(40));
+        let (text, truncated) = truncate_bootstrap(&long, 100);
+        assert!(truncated);
+        assert!(text.starts_with(&"a".repeat(70)));
+        assert!(text.ends_with(&"z".repeat(20)));
+    }
+}
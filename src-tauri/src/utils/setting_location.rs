@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// Manager UI location of a config setting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct SettingLocation {
+    /// Frontend page (PageType: ai, channels, agents, settings, ...)
+    pub page: String,
+    /// Section within the page, if it has several
+    pub section: Option<String>,
+    /// Specific item to select (provider name, channel account, agent index, ...)
+    pub item: Option<String>,
+    /// Human-readable name of the location
+    pub label: String,
+}
+
+/// Pointer patterns → (page, section, label). `*` matches one segment and its value becomes the
+/// item. More specific patterns come first; the first match wins.
+const LOCATIONS: &[(&str, &str, Option<&str>, &str)] = &[
+    ("/models/providers/*", "ai", Some("providers"), "AI Config › Providers"),
+    ("/models", "ai", Some("providers"), "AI Config › Providers"),
+    ("/agents/defaults/model", "ai", Some("primary-model"), "AI Config › Primary model"),
+    ("/agents/defaults/models", "ai", Some("primary-model"), "AI Config › Available models"),
+    ("/agents/defaults/compaction", "settings", Some("compaction"), "Settings › Compaction & Memory"),
+    ("/agents/defaults/memorySearch", "settings", Some("compaction"), "Settings › Compaction & Memory"),
+    ("/agents/defaults/contextPruning", "settings", Some("compaction"), "Settings › Compaction & Memory"),
+    ("/agents/defaults/heartbeat", "settings", Some("compaction"), "Settings › Compaction & Memory"),
+    ("/agents/defaults/subagents", "settings", Some("subagents"), "Settings › Subagent Defaults"),
+    ("/agents/defaults", "settings", Some("workspace"), "Settings › Workspace"),
+    ("/agents/list/*", "agents", Some("agents"), "Agents"),
+    ("/agents", "agents", Some("agents"), "Agents"),
+    ("/bindings/*", "agents", Some("routing"), "Agents › Routing rules"),
+    ("/bindings", "agents", Some("routing"), "Agents › Routing rules"),
+    ("/channels/telegram/accounts/*", "channels", Some("telegram"), "Channels › Telegram"),
+    ("/channels/*", "channels", None, "Channels"),
+    ("/channels", "channels", None, "Channels"),
+    ("/plugins/entries/*", "channels", Some("plugins"), "Channels › Plugins"),
+    ("/plugins", "channels", Some("plugins"), "Channels › Plugins"),
+    ("/messages", "channels", Some("rate-limits"), "Channels › Rate limits"),
+    ("/gateway", "settings", Some("gateway"), "Settings › Gateway Settings"),
+    ("/browser", "settings", Some("browser"), "Settings › Browser Control"),
+    ("/tools/web", "settings", Some("web-search"), "Settings › Web Search"),
+    ("/tools", "settings", Some("tools"), "Settings › Tools & Security"),
+    ("/skills", "skills", None, "Skills"),
+    ("/mcp", "mcp", None, "MCPs"),
+    ("/logging", "logs", None, "Logs"),
+    ("/env", "ai", Some("environment"), "AI Config › Environment"),
+];
+
+/// Split a JSON pointer ("/a/b") or dotted path ("a.b") into unescaped segments
+fn segments(path: &str) -> Vec<String> {
+    if let Some(pointer) = path.strip_prefix('/') {
+        pointer.split('/').map(|s| s.replace("~1", "/").replace("~0", "~")).collect()
+    } else {
+        path.split('.').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect()
+    }
+}
+
+/// Resolve a JSON pointer or dotted config path to the screen that edits it
+pub fn resolve(path: &str) -> Option<SettingLocation> {
+    let segs = segments(path);
+    for (pattern, page, section, label) in LOCATIONS {
+        let pat: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+        if segs.len() < pat.len() {
+            continue;
+        }
+        let mut item = None;
+        let matched = pat.iter().zip(&segs).all(|(p, s)| {
+            if *p == "*" {
+                item = Some(s.clone());
+                true
+            } else {
+                p == s
+            }
+        });
+        if !matched {
+            continue;
+        }
+        // "/channels/*" picks the channel as the section rather than the item
+        let (section, item, label) = match (section, *pattern) {
+            (None, "/channels/*") => {
+                let channel = item.unwrap_or_default();
+                let label = format!("{} › {}", label, channel);
+                (Some(channel), None, label)
+            }
+            _ => (section.map(|s| s.to_string()), item, label.to_string()),
+        };
+        return Some(SettingLocation { page: page.to_string(), section, item, label });
+    }
+    None
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use super::super::setting_location::resolve;
+
+    #[test]
+    fn test_resolve_most_specific_pattern() {
+        let loc = resolve("/models/providers/openai/apiKey").unwrap();
+        assert_eq!((loc.page.as_str(), loc.section.as_deref(), loc.item.as_deref()), ("ai", Some("providers"), Some("openai")));
+
+        let loc = resolve("/agents/defaults/compaction/mode").unwrap();
+        assert_eq!((loc.page.as_str(), loc.section.as_deref()), ("settings", Some("compaction")));
+
+        let loc = resolve("/channels/telegram/accounts/bot-a/groups").unwrap();
+        assert_eq!((loc.section.as_deref(), loc.item.as_deref()), (Some("telegram"), Some("bot-a")));
+    }
+
+    #[test]
+    fn test_resolve_dotted_paths_and_channel_sections() {
+        let loc = resolve("channels.discord.token").unwrap();
+        assert_eq!((loc.page.as_str(), loc.section.as_deref(), loc.item), ("channels", Some("discord"), None));
+        assert_eq!(resolve("gateway.auth.token").unwrap().section.as_deref(), Some("gateway"));
+        assert!(resolve("/wizard/lastRunAt").is_none());
+    }
+}
@@ -1,8 +1,14 @@
-use std::process::{Command, Output, Stdio};
+use std::process::{Child, Command, Output, Stdio};
 use std::io;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use crate::utils::platform;
 use crate::utils::file;
+use crate::utils::keychain;
+use crate::utils::executor::Executor;
+use crate::utils::node_pm;
+use crate::utils::process_limits;
+use crate::utils::wsl;
 use log::{info, debug, warn};
 
 #[cfg(windows)]
@@ -12,14 +18,95 @@ use std::os::windows::process::CommandExt;
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// Extra environment for every openclaw process, registered at startup
+static OPENCLAW_ENV: OnceLock<fn() -> Vec<(String, String)>> = OnceLock::new();
+
+/// Register where openclaw processes get the values behind config placeholders from. They
+/// are passed per process rather than exported, as changing the Manager's own environment
+/// while other threads run is unsound.
+pub fn set_openclaw_env(provider: fn() -> Vec<(String, String)>) {
+    let _ = OPENCLAW_ENV.set(provider);
+}
+
+fn openclaw_env() -> Vec<(String, String)> {
+    OPENCLAW_ENV.get().map(|provider| provider()).unwrap_or_default()
+}
+
+/// The executor registered in the app state (fixtures in mock mode, the system otherwise)
+pub fn executor() -> &'static dyn Executor {
+    crate::state::app_state().executor.as_ref()
+}
+
+/// Whether commands are simulated (mock mode)
+pub fn is_mock() -> bool {
+    executor().is_mock()
+}
+
+/// Run a prepared command through the active executor and capture its output
+pub fn output(cmd: &mut Command) -> io::Result<Output> {
+    wsl::forward_env(cmd);
+    executor().output(cmd)
+}
+
+/// Start a prepared command in the background through the active executor, returning its PID
+pub fn spawn(cmd: &mut Command) -> io::Result<u32> {
+    wsl::forward_env(cmd);
+    executor().spawn(cmd)
+}
+
+/// Add secret headers (`Name: value`) to a curl command through an owner-only file
+/// (`-H @file`) instead of its arguments, which other local users can read in the process list.
+/// The file is removed when the returned handle is dropped, so keep it until curl has run.
+pub fn curl_secret_headers(cmd: &mut Command, headers: &[String]) -> Result<tempfile::NamedTempFile, String> {
+    use std::io::Write;
+    let data_dir = platform::get_manager_data_dir();
+    std::fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create the Manager data directory: {}", e))?;
+    let mut file = tempfile::Builder::new()
+        .prefix("curl-headers-")
+        .tempfile_in(&data_dir)
+        .map_err(|e| format!("Failed to create the curl header file: {}", e))?;
+    file.write_all(headers.join("\n").as_bytes())
+        .and_then(|_| file.flush())
+        .map_err(|e| format!("Failed to write the curl header file: {}", e))?;
+    cmd.arg("-H").arg(format!("@{}", file.path().display()));
+    Ok(file)
+}
+
+/// Where curl writes a response body nobody reads (`-o`); curl.exe has no /dev/null
+pub fn null_device() -> &'static str {
+    if platform::is_windows() { "NUL" } else { "/dev/null" }
+}
+
+/// POST JSON to `url` with curl and return the HTTP status ("000" when nothing answered).
+/// `data` is passed to `--data-binary`: the body itself, or `@path` to send a file.
+pub fn curl_post_json(url: &str, data: &str, max_time_secs: u32) -> Result<String, String> {
+    let curl = if platform::is_windows() { "curl.exe" } else { "curl" };
+    let max_time = max_time_secs.to_string();
+    let output = run_command(curl, &[
+        "-s", "-o", null_device(), "-w", "%{http_code}",
+        "-X", "POST",
+        "-H", "Content-Type: application/json",
+        "--data-binary", data,
+        "--max-time", &max_time,
+        url,
+    ]).map_err(|e| format!("Failed to run curl: {}", e))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// Get extended PATH environment variable
 /// GUI applications may not inherit user shell's PATH on startup, need to manually add common paths
 pub fn get_extended_path() -> String {
     let mut paths = Vec::new();
     
     // Add common executable paths
-    paths.push("/opt/homebrew/bin".to_string());  // Homebrew on Apple Silicon
-    paths.push("/usr/local/bin".to_string());      // Homebrew on Intel / regular installation
+    // Homebrew of the native architecture first (Apple Silicon, Intel or Linuxbrew)
+    let brew_bin = format!("{}/bin", platform::homebrew_prefix());
+    paths.push(brew_bin.clone());
+    for dir in ["/opt/homebrew/bin", "/usr/local/bin"] {
+        if dir != brew_bin {
+            paths.push(dir.to_string());
+        }
+    }
     paths.push("/usr/bin".to_string());
     paths.push("/bin".to_string());
     
@@ -55,6 +142,14 @@ pub fn get_extended_path() -> String {
         // mise
         paths.push(format!("{}/.local/share/mise/shims", home_str));
     }
+
+    // pnpm / yarn global bin directories
+    if let Ok(pnpm_home) = std::env::var("PNPM_HOME") {
+        paths.push(pnpm_home);
+    }
+    if let Some(global_bin) = node_pm::global_bin() {
+        paths.insert(0, global_bin);
+    }
     
     // Get current PATH and merge
     let current_path = std::env::var("PATH").unwrap_or_default();
@@ -67,6 +162,11 @@ pub fn get_extended_path() -> String {
 
 /// Execute shell command (with extended PATH)
 pub fn run_command(cmd: &str, args: &[&str]) -> io::Result<Output> {
+    run_command_with(executor(), cmd, args)
+}
+
+/// Execute shell command through a specific executor (with extended PATH)
+pub fn run_command_with(executor: &dyn Executor, cmd: &str, args: &[&str]) -> io::Result<Output> {
     let mut command = Command::new(cmd);
     command.args(args);
     
@@ -80,12 +180,17 @@ pub fn run_command(cmd: &str, args: &[&str]) -> io::Result<Output> {
     #[cfg(windows)]
     command.creation_flags(CREATE_NO_WINDOW);
     
-    command.output()
+    executor.output(&mut command)
 }
 
 /// Execute shell command and get output string
 pub fn run_command_output(cmd: &str, args: &[&str]) -> Result<String, String> {
-    match run_command(cmd, args) {
+    run_command_output_with(executor(), cmd, args)
+}
+
+/// Execute shell command through a specific executor and get output string
+pub fn run_command_output_with(executor: &dyn Executor, cmd: &str, args: &[&str]) -> Result<String, String> {
+    match run_command_with(executor, cmd, args) {
         Ok(output) => {
             if output.status.success() {
                 Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -97,10 +202,17 @@ pub fn run_command_output(cmd: &str, args: &[&str]) -> Result<String, String> {
     }
 }
 
-/// Execute bash command (with extended PATH)
-pub fn run_bash(script: &str) -> io::Result<Output> {
-    let mut command = Command::new("bash");
-    command.arg("-c").arg(script);
+/// bash command for a script (with extended PATH), not yet started.
+/// In WSL mode the script runs inside the selected distro.
+pub fn bash_command(script: &str) -> Command {
+    let mut command = match wsl::active() {
+        Some(target) => wsl::script_command(&target.distro, script),
+        None => {
+            let mut command = Command::new("bash");
+            command.arg("-c").arg(script);
+            command
+        }
+    };
     
     // Use extended PATH on non-Windows systems
     #[cfg(not(windows))]
@@ -112,7 +224,12 @@ pub fn run_bash(script: &str) -> io::Result<Output> {
     #[cfg(windows)]
     command.creation_flags(CREATE_NO_WINDOW);
     
-    command.output()
+    command
+}
+
+/// Execute bash command (with extended PATH)
+pub fn run_bash(script: &str) -> io::Result<Output> {
+    output(&mut bash_command(script))
 }
 
 /// Execute bash command and get output
@@ -134,15 +251,20 @@ pub fn run_bash_output(script: &str) -> Result<String, String> {
     }
 }
 
-/// Execute cmd.exe command (Windows) - avoid PowerShell execution policy issues
-pub fn run_cmd(script: &str) -> io::Result<Output> {
+/// cmd.exe command for a script (Windows), not yet started
+pub fn cmd_command(script: &str) -> Command {
     let mut cmd = Command::new("cmd");
     cmd.args(["/c", script]);
     
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
     
-    cmd.output()
+    cmd
+}
+
+/// Execute cmd.exe command (Windows) - avoid PowerShell execution policy issues
+pub fn run_cmd(script: &str) -> io::Result<Output> {
+    output(&mut cmd_command(script))
 }
 
 /// Execute cmd.exe command and get output (Windows)
@@ -169,9 +291,8 @@ pub fn run_cmd_output(script: &str) -> Result<String, String> {
     }
 }
 
-/// Execute PowerShell command (Windows) - use only when PowerShell-specific features are needed
-/// Note: PowerShell execution policy on some Windows systems may prohibit running scripts
-pub fn run_powershell(script: &str) -> io::Result<Output> {
+/// PowerShell command for a script (Windows), not yet started
+pub fn powershell_command(script: &str) -> Command {
     let mut cmd = Command::new("powershell");
     // Use -ExecutionPolicy Bypass to bypass execution policy restrictions
     cmd.args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script]);
@@ -179,7 +300,13 @@ pub fn run_powershell(script: &str) -> io::Result<Output> {
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
     
-    cmd.output()
+    cmd
+}
+
+/// Execute PowerShell command (Windows) - use only when PowerShell-specific features are needed
+/// Note: PowerShell execution policy on some Windows systems may prohibit running scripts
+pub fn run_powershell(script: &str) -> io::Result<Output> {
+    output(&mut powershell_command(script))
 }
 
 /// Execute PowerShell command and get output (Windows)
@@ -225,12 +352,9 @@ pub fn spawn_background(script: &str) -> io::Result<()> {
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
         
-        cmd.spawn()?;
+        spawn(&mut cmd)?;
     } else {
-        Command::new("bash")
-            .arg("-c")
-            .arg(script)
-            .spawn()?;
+        spawn(Command::new("bash").arg("-c").arg(script))?;
     }
     Ok(())
 }
@@ -238,6 +362,27 @@ pub fn spawn_background(script: &str) -> io::Result<()> {
 /// Get openclaw executable path
 /// Detects multiple possible installation paths, since GUI apps don't inherit user shell's PATH
 pub fn get_openclaw_path() -> Option<String> {
+    // Mock mode: the fixtures stand in for an installed openclaw
+    if is_mock() {
+        return Some("openclaw".to_string());
+    }
+
+    // WSL mode: openclaw inside the distro, as a \\wsl.localhost path
+    if let Some(target) = wsl::active() {
+        let path = wsl::run(&target.distro, "command -v openclaw").ok().filter(|p| p.starts_with('/'))?;
+        return Some(wsl::to_windows_path(&path, &target.distro));
+    }
+
+    // Global bin directory of the package manager that installed openclaw
+    if let Some(global_bin) = node_pm::global_bin() {
+        let name = if platform::is_windows() { "openclaw.cmd" } else { "openclaw" };
+        let path = std::path::Path::new(&global_bin).join(name);
+        if path.exists() {
+            info!("[Shell] Found openclaw at {}", path.display());
+            return Some(path.display().to_string());
+        }
+    }
+
     // Windows: check common npm global installation paths
     if platform::is_windows() {
         let possible_paths = get_windows_openclaw_paths();
@@ -313,6 +458,10 @@ fn get_unix_openclaw_paths() -> Vec<String> {
         paths.push(format!("{}/.volta/bin/openclaw", home_str));
         
         // pnpm global installation
+        if let Ok(pnpm_home) = std::env::var("PNPM_HOME") {
+            paths.push(format!("{}/openclaw", pnpm_home));
+        }
+        paths.push(format!("{}/.local/share/pnpm/openclaw", home_str)); // Linux pnpm default path
         paths.push(format!("{}/.pnpm/bin/openclaw", home_str));
         paths.push(format!("{}/Library/pnpm/openclaw", home_str)); // macOS pnpm default path
         
@@ -345,12 +494,36 @@ fn get_windows_openclaw_paths() -> Vec<String> {
     
     // 3. nodejs in Program Files
     paths.push("C:\\Program Files\\nodejs\\openclaw.cmd".to_string());
+
+    // 4. pnpm and yarn global bin directories
+    if let Ok(pnpm_home) = std::env::var("PNPM_HOME") {
+        paths.push(format!("{}\\openclaw.cmd", pnpm_home));
+    }
+    if let Some(local) = dirs::data_local_dir() {
+        paths.push(format!("{}\\pnpm\\openclaw.cmd", local.display()));
+        paths.push(format!("{}\\Yarn\\bin\\openclaw.cmd", local.display()));
+    }
     
     paths
 }
 
+/// Process for the openclaw at `openclaw_path` (from `get_openclaw_path`), run through
+/// wsl.exe in WSL mode
+pub fn openclaw_command(openclaw_path: &str) -> Command {
+    match wsl::active() {
+        Some(target) => wsl::command(&target.distro, "openclaw"),
+        None => Command::new(openclaw_path),
+    }
+}
+
 /// Execute openclaw command and get output
 pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
+    run_openclaw_with_env(args, &[])
+}
+
+/// Execute an openclaw command with extra environment variables (applied last, so they
+/// override the gateway token and state directory of the default gateway)
+pub fn run_openclaw_with_env(args: &[&str], env: &[(&str, String)]) -> Result<String, String> {
     debug!("[Shell] Executing openclaw command: {:?}", args);
     
     let openclaw_path = get_openclaw_path().ok_or_else(|| {
@@ -366,27 +539,31 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
     
     let output = if platform::is_windows() && openclaw_path.ends_with(".cmd") {
         // Windows: .cmd files can be executed directly
-        let mut cmd = Command::new(&openclaw_path);
+        let mut cmd = openclaw_command(&openclaw_path);
         let gw_token = get_gateway_token_from_config();
         cmd.args(args)
+            .envs(openclaw_env())
             .env("OPENCLAW_GATEWAY_TOKEN", &gw_token)
-            .env("PATH", &extended_path);
+            .env("PATH", &extended_path)
+            .envs(env.iter().map(|(k, v)| (*k, v)));
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
         
-        cmd.output()
+        output(&mut cmd)
     } else {
-        let mut cmd = Command::new(&openclaw_path);
+        let mut cmd = openclaw_command(&openclaw_path);
         let gw_token = get_gateway_token_from_config();
         cmd.args(args)
+            .envs(openclaw_env())
             .env("OPENCLAW_GATEWAY_TOKEN", &gw_token)
-            .env("PATH", &extended_path);
+            .env("PATH", &extended_path)
+            .envs(env.iter().map(|(k, v)| (*k, v)));
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
         
-        cmd.output()
+        output(&mut cmd)
     };
     
     match output {
@@ -409,96 +586,61 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
     }
 }
 
+/// Start an openclaw command for the embedded console with stdin, stdout and stderr piped, so
+/// the caller can stream its output and answer its prompts. There is no terminal attached:
+/// commands that insist on a TTY fall back to their non-interactive behavior.
+pub fn run_openclaw_interactive(args: &[String]) -> Result<Child, String> {
+    if is_mock() {
+        return Err("The console is not available in mock mode".to_string());
+    }
+    let openclaw_path = get_openclaw_path().ok_or_else(|| {
+        "Cannot find openclaw command, please ensure it is installed via npm install -g openclaw".to_string()
+    })?;
+    debug!("[Shell] Starting interactive openclaw command: {:?}", args);
+
+    let mut cmd = openclaw_command(&openclaw_path);
+    cmd.args(args)
+        .envs(openclaw_env())
+        .env("OPENCLAW_GATEWAY_TOKEN", get_gateway_token_from_config())
+        .env("PATH", get_extended_path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    wsl::forward_env(&mut cmd);
+    cmd.spawn().map_err(|e| format!("Failed to start openclaw: {}", e))
+}
+
 /// Default Gateway Token (fallback only)
 pub const DEFAULT_GATEWAY_TOKEN: &str = "openclaw-manager-local-token";
 
 /// Read the actual gateway auth token from openclaw.json config.
-/// If no token exists (fresh install), generates one and saves it to config.
-/// Falls back to DEFAULT_GATEWAY_TOKEN only if config is completely unreadable.
+/// Read-only: the token is generated under the config lock before the gateway starts
+/// (`config::ensure_gateway_auth`). Falls back to DEFAULT_GATEWAY_TOKEN when there is none.
 fn get_gateway_token_from_config() -> String {
     let config_path = platform::get_config_file_path();
-
-    // Try to read existing config
-    let mut config = if let Ok(content) = file::read_file(&config_path) {
+    let token = file::read_file(&config_path).ok().and_then(|content| {
         let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
-        match serde_json::from_str::<serde_json::Value>(content) {
-            Ok(c) => c,
-            Err(_) => serde_json::json!({}),
+        serde_json::from_str::<serde_json::Value>(content)
+            .ok()?
+            .pointer("/gateway/auth/token")
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+    });
+    match token {
+        Some(token) => {
+            debug!("[Shell] Using gateway token from config");
+            token
         }
-    } else {
-        serde_json::json!({})
-    };
-
-    // Check if token already exists
-    let existing_token = config
-        .pointer("/gateway/auth/token")
-        .and_then(|v| v.as_str())
-        .filter(|t| !t.is_empty())
-        .map(|t| t.to_string());
-
-    if let Some(token) = existing_token {
-        // Ensure controlUi.allowInsecureAuth is set (may be missing on older configs)
-        let needs_update = config
-            .pointer("/gateway/controlUi/allowInsecureAuth")
-            .and_then(|v| v.as_bool())
-            != Some(true);
-        if needs_update {
-            info!("[Shell] Setting gateway.controlUi.allowInsecureAuth = true");
-            if config["gateway"].get("controlUi").is_none() {
-                config["gateway"]["controlUi"] = serde_json::json!({});
-            }
-            config["gateway"]["controlUi"]["allowInsecureAuth"] = serde_json::json!(true);
-            if let Ok(content) = serde_json::to_string_pretty(&config) {
-                let _ = file::write_file(&config_path, &content);
-            }
-        }
-        info!("[Shell] Using gateway token from config");
-        return token;
-    }
-
-    // No token found — generate one and save it to config
-    info!("[Shell] No gateway token found, generating new token...");
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let random_part: u64 = (timestamp as u64) ^ 0x5DEECE66Du64;
-    let new_token = format!(
-        "{:016x}{:016x}{:016x}",
-        random_part,
-        random_part.wrapping_mul(0x5DEECE66Du64),
-        timestamp as u64
-    );
-
-    // Ensure gateway.auth path exists in config
-    if config.get("gateway").is_none() {
-        config["gateway"] = serde_json::json!({});
-    }
-    if config["gateway"].get("auth").is_none() {
-        config["gateway"]["auth"] = serde_json::json!({});
-    }
-    config["gateway"]["auth"]["token"] = serde_json::json!(&new_token);
-    config["gateway"]["auth"]["mode"] = serde_json::json!("token");
-    if config["gateway"].get("mode").is_none() {
-        config["gateway"]["mode"] = serde_json::json!("local");
-    }
-    // Allow Control UI to connect with token-only auth (skip device pairing for local manager)
-    if config["gateway"].get("controlUi").is_none() {
-        config["gateway"]["controlUi"] = serde_json::json!({});
-    }
-    config["gateway"]["controlUi"]["allowInsecureAuth"] = serde_json::json!(true);
-
-    // Save config
-    if let Ok(content) = serde_json::to_string_pretty(&config) {
-        if let Err(e) = file::write_file(&config_path, &content) {
-            warn!("[Shell] Failed to save generated token to config: {}", e);
-            return DEFAULT_GATEWAY_TOKEN.to_string();
+        None => {
+            warn!("[Shell] No gateway token in config, using the fallback token");
+            DEFAULT_GATEWAY_TOKEN.to_string()
         }
     }
-
-    info!("[Shell] Generated and saved new gateway token: {}...", &new_token[..8]);
-    new_token
 }
 
 /// Read all environment variables from ~/.openclaw/env file
@@ -533,7 +675,13 @@ fn load_openclaw_env_vars() -> HashMap<String, String> {
 /// Start openclaw gateway in background
 /// Consistent with shell script behavior: load env file first, then start gateway
 pub fn spawn_openclaw_gateway() -> io::Result<()> {
-    info!("[Shell] Starting openclaw gateway in background...");
+    spawn_openclaw_gateway_on(18789, &[], &[]).map(|_| ())
+}
+
+/// Start a gateway on `port` in background and return its PID. `global_args` go before the
+/// subcommand (e.g. `--profile work`), `env` overrides the default gateway's environment.
+pub fn spawn_openclaw_gateway_on(port: u16, global_args: &[&str], env: &[(&str, String)]) -> io::Result<u32> {
+    info!("[Shell] Starting openclaw gateway in background on port {}...", port);
     
     let openclaw_path = get_openclaw_path().ok_or_else(|| {
         warn!("[Shell] Cannot find openclaw command");
@@ -559,32 +707,50 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     
     // On Windows, .cmd files can be executed directly by Command::new
     // Set environment variable OPENCLAW_GATEWAY_TOKEN so all subcommands can use it automatically
-    let mut cmd = if platform::is_windows() && openclaw_path.ends_with(".cmd") {
+    let port = port.to_string();
+    let cmd = if platform::is_windows() && openclaw_path.ends_with(".cmd") {
         info!("[Shell] Windows mode: executing .cmd directly");
-        let mut c = Command::new(&openclaw_path);
-        c.args(["gateway", "run", "--port", "18789"]);
+        let mut c = openclaw_command(&openclaw_path);
+        c.args(global_args).args(["gateway", "run", "--port", &port]);
         c
     } else {
         info!("[Shell] Unix/Direct mode: executing directly");
-        let mut c = Command::new(&openclaw_path);
-        c.args(["gateway", "run", "--port", "18789"]);
+        let mut c = openclaw_command(&openclaw_path);
+        c.args(global_args).args(["gateway", "run", "--port", &port]);
         c
     };
+    // Priority, affinity and memory / CPU limits (launch prefix on Unix, job object on Windows)
+    #[cfg(windows)]
+    let limits = process_limits::effective();
+    let mut cmd = process_limits::wrap(cmd);
     
     // Inject user's environment variables (such as ANTHROPIC_API_KEY, OPENAI_API_KEY, etc.)
     for (key, value) in &user_env_vars {
         cmd.env(key, value);
     }
+    // Keychain secrets (provider API keys...), referenced from openclaw.json as ${secret:name}
+    let keychain_env = keychain::gateway_env();
+    if !keychain_env.is_empty() {
+        info!("[Shell] Injecting {} keychain secret(s)", keychain_env.len());
+    }
+    for (key, value) in keychain_env {
+        cmd.env(key, value);
+    }
+    // Values behind the config's ${env:...} / ${secret:...} placeholders
+    cmd.envs(openclaw_env());
     
     // Set PATH and gateway token (read from config to avoid mismatch)
     let gateway_token = get_gateway_token_from_config();
     cmd.env("PATH", &extended_path);
     cmd.env("OPENCLAW_GATEWAY_TOKEN", &gateway_token);
     info!("[Shell] Gateway token: {}...", &gateway_token[..8.min(gateway_token.len())]);
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
     
     // Windows: hide console window
     #[cfg(windows)]
-    cmd.creation_flags(CREATE_NO_WINDOW);
+    cmd.creation_flags(CREATE_NO_WINDOW | process_limits::priority_class(limits.priority));
     
     info!("[Shell] Starting gateway process...");
     
@@ -593,12 +759,14 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     cmd.stderr(Stdio::null());
     cmd.stdin(Stdio::null());
 
-    let child = cmd.spawn();
-    
-    match child {
-        Ok(c) => {
-            info!("[Shell] ✓ Gateway process started, PID: {}", c.id());
-            Ok(())
+    match spawn(&mut cmd) {
+        Ok(pid) => {
+            info!("[Shell] ✓ Gateway process started, PID: {}", pid);
+            #[cfg(windows)]
+            if let Err(e) = process_limits::apply_job(pid, &limits) {
+                warn!("[Shell] Gateway limits not applied: {}", e);
+            }
+            Ok(pid)
         }
         Err(e) => {
             warn!("[Shell] ✗ Gateway startup failed: {}", e);
@@ -620,15 +788,30 @@ pub fn command_exists(cmd: &str) -> bool {
         #[cfg(windows)]
         command.creation_flags(CREATE_NO_WINDOW);
         
-        command.output()
+        output(&mut command)
             .map(|o| o.status.success())
             .unwrap_or(false)
     } else {
         // Unix: use which command
-        Command::new("which")
-            .arg(cmd)
-            .output()
+        output(Command::new("which").arg(cmd))
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 }
+
+/// Free space in bytes on the volume holding `path` (None if it cannot be determined)
+pub fn disk_free_bytes(path: &str) -> Option<u64> {
+    if platform::is_windows() {
+        let script = format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            path.replace('\'', "''")
+        );
+        run_powershell_output(&script).ok()?.trim().parse().ok()
+    } else {
+        // POSIX format: header line, then "filesystem blocks used available capacity mount"
+        let output = run_command_output("df", &["-Pk", path]).ok()?;
+        let line = output.lines().nth(1)?;
+        let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+        Some(available_kb * 1024)
+    }
+}
@@ -0,0 +1,115 @@
+//! Point-in-time copies of openclaw.json, mcps.json and manager.json, taken on demand and
+//! before destructive commands so a bad change can be rolled back.
+
+use crate::utils::json_diff::{self, JsonChange};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Files a snapshot holds, by the name they are stored under
+pub const SNAPSHOT_FILES: &[&str] = &["openclaw.json", "mcps.json", "manager.json"];
+
+/// Automatic snapshots kept; older ones are pruned (manual snapshots are never pruned)
+pub const MAX_AUTO_SNAPSHOTS: usize = 50;
+
+/// One snapshot (manager/snapshots/<id>.json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSnapshot {
+    pub id: String,
+    /// RFC3339
+    pub created_at: String,
+    /// User label, or what triggered an automatic snapshot (e.g. "Before delete_provider")
+    pub reason: String,
+    /// Taken by a command rather than by the user
+    pub automatic: bool,
+    /// File name -> content; files missing at the time are left out
+    pub files: BTreeMap<String, Value>,
+}
+
+/// Snapshot listing entry (contents are left out, they hold tokens)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub created_at: String,
+    pub reason: String,
+    pub automatic: bool,
+    pub files: Vec<String>,
+}
+
+impl From<&ConfigSnapshot> for SnapshotInfo {
+    fn from(snapshot: &ConfigSnapshot) -> Self {
+        SnapshotInfo {
+            id: snapshot.id.clone(),
+            created_at: snapshot.created_at.clone(),
+            reason: snapshot.reason.clone(),
+            automatic: snapshot.automatic,
+            files: snapshot.files.keys().cloned().collect(),
+        }
+    }
+}
+
+/// Id for a snapshot taken at `now`; also its file name
+pub fn snapshot_id(now: DateTime<Local>) -> String {
+    format!("snapshot-{}", now.format("%Y%m%d-%H%M%S%3f"))
+}
+
+/// Ids come back from the UI and name a file, so only generated ones are accepted
+pub fn is_valid_id(id: &str) -> bool {
+    id.strip_prefix("snapshot-")
+        .map(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        .unwrap_or(false)
+}
+
+/// Whether `files` match the most recent snapshot (an automatic snapshot is then not needed)
+pub fn unchanged(latest: Option<&ConfigSnapshot>, files: &BTreeMap<String, Value>) -> bool {
+    latest.map(|s| s.files == *files).unwrap_or(false)
+}
+
+/// Ids of the automatic snapshots beyond the `keep` newest
+pub fn to_prune(snapshots: &[SnapshotInfo], keep: usize) -> Vec<String> {
+    let mut automatic: Vec<&SnapshotInfo> = snapshots.iter().filter(|s| s.automatic).collect();
+    automatic.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    automatic
+        .into_iter()
+        .skip(keep)
+        .map(|s| s.id.clone())
+        .collect()
+}
+
+/// openclaw.json of a snapshot compared with the live one, grouped by kind of change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    pub snapshot: SnapshotInfo,
+    /// Keys only the live file has (a rollback removes them)
+    pub added: Vec<JsonChange>,
+    /// Keys only the snapshot has (a rollback brings them back)
+    pub removed: Vec<JsonChange>,
+    /// Values that differ; `old_value` is the snapshot's, `new_value` the live one
+    pub changed: Vec<JsonChange>,
+}
+
+/// Diff from the openclaw.json held by `snapshot` to `live`, secrets masked
+pub fn diff_with_live(snapshot: &ConfigSnapshot, live: &Value) -> Result<SnapshotDiff, String> {
+    let saved = snapshot
+        .files
+        .get("openclaw.json")
+        .ok_or_else(|| format!("Snapshot {} holds no openclaw.json", snapshot.id))?;
+    let mut diff = SnapshotDiff {
+        snapshot: SnapshotInfo::from(snapshot),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    for change in json_diff::diff_masked(saved, live) {
+        match change.kind.as_str() {
+            "added" => diff.added.push(change),
+            "removed" => diff.removed.push(change),
+            _ => diff.changed.push(change),
+        }
+    }
+    Ok(diff)
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use super::super::snapshots::{
+        diff_with_live, is_valid_id, snapshot_id, to_prune, unchanged, ConfigSnapshot, SnapshotInfo,
+    };
+    use chrono::{Local, TimeZone};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn info(id: &str, created_at: &str, automatic: bool) -> SnapshotInfo {
+        SnapshotInfo {
+            id: id.to_string(),
+            created_at: created_at.to_string(),
+            reason: String::new(),
+            automatic,
+            files: vec!["openclaw.json".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_id() {
+        let now = Local.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+        let id = snapshot_id(now);
+        assert_eq!(id, "snapshot-20260304-050607000");
+        assert!(is_valid_id(&id));
+        assert!(!is_valid_id("snapshot-"));
+        assert!(!is_valid_id("snapshot-../../etc"));
+        assert!(!is_valid_id("backup-20260304-050607"));
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let mut files = BTreeMap::new();
+        files.insert(
+            "openclaw.json".to_string(),
+            json!({ "gateway": { "port": 18789 } }),
+        );
+        let snapshot = ConfigSnapshot {
+            id: "snapshot-1".to_string(),
+            created_at: "2026-03-04T05:06:07Z".to_string(),
+            reason: "Manual".to_string(),
+            automatic: false,
+            files: files.clone(),
+        };
+        assert!(unchanged(Some(&snapshot), &files));
+        assert!(!unchanged(None, &files));
+        files.insert("manager.json".to_string(), json!({}));
+        assert!(!unchanged(Some(&snapshot), &files));
+    }
+
+    #[test]
+    fn test_to_prune_keeps_manual_and_newest() {
+        let list = vec![
+            info("snapshot-1", "2026-01-01T00:00:00Z", true),
+            info("snapshot-2", "2026-01-02T00:00:00Z", false),
+            info("snapshot-3", "2026-01-03T00:00:00Z", true),
+            info("snapshot-4", "2026-01-04T00:00:00Z", true),
+        ];
+        assert_eq!(to_prune(&list, 2), vec!["snapshot-1".to_string()]);
+        assert!(to_prune(&list, 3).is_empty());
+        assert_eq!(to_prune(&list, 0).len(), 3);
+    }
+
+    #[test]
+    fn test_diff_with_live_groups_changes_by_kind() {
+        let mut files = BTreeMap::new();
+        files.insert(
+            "openclaw.json".to_string(),
+            json!({
+                "gateway": { "port": 18789, "auth": { "token": "old-token" } },
+                "models": { "providers": { "openai": { "models": { "gpt-4.1": {} } } } }
+            }),
+        );
+        let snapshot = ConfigSnapshot {
+            id: "snapshot-1".to_string(),
+            created_at: "2026-03-04T05:06:07Z".to_string(),
+            reason: "Manual".to_string(),
+            automatic: false,
+            files,
+        };
+        let live = json!({
+            "gateway": { "port": 18790, "auth": { "token": "new-token" } },
+            "models": { "providers": { "openai": { "models": {} } } },
+            "tools": { "profile": "coding" }
+        });
+        let diff = diff_with_live(&snapshot, &live).unwrap();
+        assert_eq!(diff.snapshot.id, "snapshot-1");
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].pointer, "/tools");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(
+            diff.removed[0].pointer,
+            "/models/providers/openai/models/gpt-4.1"
+        );
+        let mut changed: Vec<&str> = diff.changed.iter().map(|c| c.pointer.as_str()).collect();
+        changed.sort();
+        assert_eq!(changed, vec!["/gateway/auth/token", "/gateway/port"]);
+        let port = diff
+            .changed
+            .iter()
+            .find(|c| c.pointer == "/gateway/port")
+            .unwrap();
+        assert_eq!(port.old_value, Some(json!(18789)));
+        assert_eq!(port.new_value, Some(json!(18790)));
+        let rendered = serde_json::to_string(&diff).unwrap();
+        assert!(!rendered.contains("old-token") && !rendered.contains("new-token"));
+    }
+
+    #[test]
+    fn test_diff_with_live_needs_openclaw_json() {
+        let snapshot = ConfigSnapshot {
+            id: "snapshot-1".to_string(),
+            created_at: "2026-03-04T05:06:07Z".to_string(),
+            reason: "Manual".to_string(),
+            automatic: false,
+            files: BTreeMap::new(),
+        };
+        assert!(diff_with_live(&snapshot, &json!({})).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::path::{Component, Path};
+
+/// A file that looks like a cloud-sync conflict copy of another file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictArtifact {
+    /// File name of the file this is a copy of
+    pub original_name: String,
+    /// Sync service that produces this naming pattern
+    pub provider: &'static str,
+    /// Name alone is proof of a conflict; otherwise only trust it if the original exists
+    pub certain: bool,
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(i) if i > 0 => (&name[..i], &name[i..]),
+        _ => (name, ""),
+    }
+}
+
+fn all_digits(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Recognize sync-conflict copies by file name:
+/// - Dropbox / Nextcloud: `openclaw (conflicted copy).json`, `openclaw (Alice's conflicted copy 2024-05-01).json`
+/// - Syncthing: `openclaw.sync-conflict-20240501-101010-ABCDEFG.json`
+/// - ownCloud: `openclaw_conflict-20240501-101010.json`
+/// - iCloud: `openclaw 2.json`
+/// - Google Drive: `openclaw (1).json`
+pub fn classify(name: &str) -> Option<ConflictArtifact> {
+    let (stem, ext) = split_name(name);
+
+    if let Some(i) = stem.find(".sync-conflict-") {
+        return Some(ConflictArtifact {
+            original_name: format!("{}{}", &stem[..i], ext),
+            provider: "Syncthing",
+            certain: true,
+        });
+    }
+
+    if let Some(i) = stem.find("_conflict-") {
+        let suffix = &stem[i + "_conflict-".len()..];
+        if suffix.split('-').all(all_digits) {
+            return Some(ConflictArtifact {
+                original_name: format!("{}{}", &stem[..i], ext),
+                provider: "ownCloud",
+                certain: true,
+            });
+        }
+    }
+
+    if stem.ends_with(')') {
+        if let Some(i) = stem.rfind(" (") {
+            let inner = &stem[i + 2..stem.len() - 1];
+            let original_name = format!("{}{}", &stem[..i], ext);
+            let lower = inner.to_lowercase();
+            if lower.contains("conflicted copy") || lower.contains("case conflict") {
+                // Nextcloud omits the user name Dropbox puts in front
+                let provider = if lower.starts_with("conflicted copy ") {
+                    "Nextcloud"
+                } else {
+                    "Dropbox"
+                };
+                return Some(ConflictArtifact { original_name, provider, certain: true });
+            }
+            if all_digits(inner) {
+                return Some(ConflictArtifact { original_name, provider: "Google Drive", certain: false });
+            }
+        }
+    }
+
+    if let Some(i) = stem.rfind(' ') {
+        if all_digits(&stem[i + 1..]) && i > 0 {
+            return Some(ConflictArtifact {
+                original_name: format!("{}{}", &stem[..i], ext),
+                provider: "iCloud",
+                certain: false,
+            });
+        }
+    }
+
+    None
+}
+
+/// Guess the sync service a directory lives in from its path components
+pub fn detect_sync_folder(path: &Path) -> Option<&'static str> {
+    for component in path.components() {
+        let name = match component {
+            Component::Normal(n) => n.to_string_lossy().to_lowercase(),
+            _ => continue,
+        };
+        let provider = if name == "dropbox" || name.starts_with("dropbox (") {
+            "Dropbox"
+        } else if name.starts_with("onedrive") {
+            "OneDrive"
+        } else if name == "google drive" || name == "googledrive" || name == "my drive" {
+            "Google Drive"
+        } else if name == "icloud drive" || name == "mobile documents" || name == "com~apple~clouddocs" {
+            "iCloud"
+        } else if name == "nextcloud" {
+            "Nextcloud"
+        } else if name == "owncloud" {
+            "ownCloud"
+        } else if name == "cloudstorage" {
+            "Cloud storage"
+        } else {
+            continue;
+        };
+        return Some(provider);
+    }
+    None
+}
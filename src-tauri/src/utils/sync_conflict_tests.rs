@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use super::super::sync_conflict::{classify, detect_sync_folder};
+    use std::path::Path;
+
+    #[test]
+    fn test_dropbox_conflicted_copy() {
+        let a = classify("openclaw (conflicted copy).json").unwrap();
+        assert_eq!(a.original_name, "openclaw.json");
+        assert_eq!(a.provider, "Dropbox");
+        assert!(a.certain);
+
+        let a = classify("openclaw (Alice's conflicted copy 2024-05-01).json").unwrap();
+        assert_eq!(a.original_name, "openclaw.json");
+        assert_eq!(a.provider, "Dropbox");
+    }
+
+    #[test]
+    fn test_syncthing_and_owncloud() {
+        let a = classify("openclaw.sync-conflict-20240501-101010-ABCDEFG.json").unwrap();
+        assert_eq!(a.original_name, "openclaw.json");
+        assert_eq!(a.provider, "Syncthing");
+
+        let a = classify("env_conflict-20240501-101010").unwrap();
+        assert_eq!(a.original_name, "env");
+        assert_eq!(a.provider, "ownCloud");
+    }
+
+    #[test]
+    fn test_numbered_copies_are_uncertain() {
+        let a = classify("openclaw 2.json").unwrap();
+        assert_eq!(a.original_name, "openclaw.json");
+        assert!(!a.certain);
+
+        let a = classify("openclaw (1).json").unwrap();
+        assert_eq!(a.original_name, "openclaw.json");
+        assert!(!a.certain);
+    }
+
+    #[test]
+    fn test_regular_files_are_not_conflicts() {
+        assert!(classify("openclaw.json").is_none());
+        assert!(classify("manager.json").is_none());
+        assert!(classify("telegram-pairing.json").is_none());
+        assert!(classify("env").is_none());
+    }
+
+    #[test]
+    fn test_detect_sync_folder() {
+        assert_eq!(detect_sync_folder(Path::new("/Users/a/Dropbox/.openclaw")), Some("Dropbox"));
+        assert_eq!(
+            detect_sync_folder(Path::new("/Users/a/Library/Mobile Documents/com~apple~CloudDocs/.openclaw")),
+            Some("iCloud")
+        );
+        assert_eq!(detect_sync_folder(Path::new("/home/a/OneDrive - Corp/.openclaw")), Some("OneDrive"));
+        assert_eq!(detect_sync_folder(Path::new("/home/a/.openclaw")), None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Bounded worker pool for bulk operations (MCP and skill installs, provider tests): items run
+//! in parallel on a few threads and their results come back in input order.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Workers used when the caller sets no limit
+pub const DEFAULT_CONCURRENCY: usize = 3;
+
+/// Upper bound on workers, whatever the caller asks for (installs are network and disk heavy)
+pub const MAX_CONCURRENCY: usize = 8;
+
+/// Workers for `items` items: the requested count (or the default), within 1..=MAX_CONCURRENCY
+/// and no more than there are CPUs or items
+pub fn worker_count(requested: Option<usize>, items: usize, cpus: usize) -> usize {
+    requested
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY)
+        .min(cpus.max(1))
+        .min(items.max(1))
+}
+
+/// Stage of one item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemState {
+    Started,
+    Succeeded,
+    Failed,
+}
+
+/// Progress of one item, passed to the `on_progress` callback of `run`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemProgress<'a> {
+    pub label: &'a str,
+    pub state: ItemState,
+    /// Items finished so far (including this one once it succeeded or failed)
+    pub done: usize,
+    pub total: usize,
+    pub error: Option<&'a str>,
+}
+
+/// Outcome of one item of a bulk command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    /// What the item was (URL, package, skill name, ...)
+    pub item: String,
+    pub success: bool,
+    pub message: Option<String>,
+    pub error: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn new(item: &str, result: Result<String, String>) -> Self {
+        let success = result.is_ok();
+        let (message, error) = match result {
+            Ok(message) => (Some(message), None),
+            Err(e) => (None, Some(e)),
+        };
+        BulkItemResult {
+            item: item.to_string(),
+            success,
+            message,
+            error,
+        }
+    }
+}
+
+/// Run `work` on every `(label, item)` with at most `workers` items in flight. `on_progress`
+/// is called when an item starts and when it finishes (from the worker threads). Returns the
+/// results in the order of `items`.
+pub fn run<T, R, W, P>(
+    items: Vec<(String, T)>,
+    workers: usize,
+    work: W,
+    on_progress: P,
+) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+    W: Fn(T) -> Result<R, String> + Sync,
+    P: Fn(ItemProgress) + Sync,
+{
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results: Mutex<Vec<Option<Result<R, String>>>> =
+        Mutex::new((0..total).map(|_| None).collect());
+    let done = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, total.max(1)) {
+            scope.spawn(|| loop {
+                let next = match queue.lock() {
+                    Ok(mut queue) => queue.next(),
+                    Err(_) => None,
+                };
+                let Some((index, (label, item))) = next else {
+                    break;
+                };
+                on_progress(ItemProgress {
+                    label: &label,
+                    state: ItemState::Started,
+                    done: done.load(Ordering::SeqCst),
+                    total,
+                    error: None,
+                });
+                let result = work(item);
+                let finished = done.fetch_add(1, Ordering::SeqCst) + 1;
+                on_progress(ItemProgress {
+                    label: &label,
+                    state: if result.is_ok() {
+                        ItemState::Succeeded
+                    } else {
+                        ItemState::Failed
+                    },
+                    done: finished,
+                    total,
+                    error: result.as_ref().err().map(String::as_str),
+                });
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err("The task did not run".to_string())))
+        .collect()
+}
+
+/// `worker_count` for this machine
+pub fn workers_for(requested: Option<usize>, items: usize) -> usize {
+    let cpus = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    worker_count(requested, items, cpus)
+}
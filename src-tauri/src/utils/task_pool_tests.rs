@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use super::super::task_pool::{run, worker_count, BulkItemResult, ItemState, MAX_CONCURRENCY};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn test_worker_count() {
+        assert_eq!(worker_count(None, 10, 16), 3);
+        assert_eq!(worker_count(Some(0), 10, 16), 1);
+        assert_eq!(worker_count(Some(100), 20, 16), MAX_CONCURRENCY);
+        assert_eq!(worker_count(Some(6), 20, 2), 2);
+        assert_eq!(worker_count(Some(6), 2, 16), 2);
+        assert_eq!(worker_count(None, 0, 0), 1);
+    }
+
+    #[test]
+    fn test_run_keeps_order_and_bounds_concurrency() {
+        let items: Vec<(String, usize)> = (0..12).map(|i| (format!("item-{}", i), i)).collect();
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let results = run(
+            items,
+            3,
+            |i| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(10));
+                running.fetch_sub(1, Ordering::SeqCst);
+                if i % 5 == 4 {
+                    Err(format!("item {} failed", i))
+                } else {
+                    Ok(i * 2)
+                }
+            },
+            |_| {},
+        );
+        assert_eq!(results.len(), 12);
+        assert_eq!(results[0], Ok(0));
+        assert_eq!(results[3], Ok(6));
+        assert_eq!(results[4], Err("item 4 failed".to_string()));
+        assert_eq!(results[11], Ok(22));
+        let peak = peak.load(Ordering::SeqCst);
+        assert!((2..=3).contains(&peak), "peak {}", peak);
+    }
+
+    #[test]
+    fn test_run_reports_progress() {
+        let events = Mutex::new(Vec::new());
+        let items = vec![("a".to_string(), true), ("b".to_string(), false)];
+        run(
+            items,
+            1,
+            |ok| if ok { Ok(()) } else { Err("boom".to_string()) },
+            |p| {
+                events.lock().unwrap().push((
+                    p.label.to_string(),
+                    p.state,
+                    p.done,
+                    p.total,
+                    p.error.map(String::from),
+                ))
+            },
+        );
+        let events = events.into_inner().unwrap();
+        assert_eq!(
+            events,
+            vec![
+                ("a".to_string(), ItemState::Started, 0, 2, None),
+                ("a".to_string(), ItemState::Succeeded, 1, 2, None),
+                ("b".to_string(), ItemState::Started, 1, 2, None),
+                (
+                    "b".to_string(),
+                    ItemState::Failed,
+                    2,
+                    2,
+                    Some("boom".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_item_result() {
+        let ok = BulkItemResult::new("x", Ok("installed".to_string()));
+        assert!(ok.success);
+        assert_eq!(ok.message.as_deref(), Some("installed"));
+        let failed = BulkItemResult::new("y", Err("nope".to_string()));
+        assert!(!failed.success);
+        assert_eq!(failed.error.as_deref(), Some("nope"));
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::tasks::{
+        exit_code_from_log, mark_interrupted, prune_finished, task_process, with_exit_marker,
+        TaskRecord, EXIT_MARKER,
+    };
+    use crate::utils::process_tree::ProcessInfo;
+
+    fn record(id: &str, status: &str, manager_pid: u32) -> TaskRecord {
+        TaskRecord {
+            id: id.to_string(),
+            kind: "install-openclaw".to_string(),
+            step: "npm install -g openclaw".to_string(),
+            pid: Some(4242),
+            process_name: Some("bash".to_string()),
+            log_file: format!("/tmp/{}.log", id),
+            manager_pid,
+            started_at: "2026-10-01T10:00:00Z".to_string(),
+            updated_at: "2026-10-01T10:00:00Z".to_string(),
+            status: status.to_string(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_exit_code_from_log() {
+        let log = format!(
+            "== npm install -g openclaw\nadded 1 package\n{} 0\n",
+            EXIT_MARKER
+        );
+        assert_eq!(exit_code_from_log(&log), Some(0));
+        let log = format!(
+            "npm ERR! code EACCES\n{} 243\n== failed: EACCES",
+            EXIT_MARKER
+        );
+        assert_eq!(exit_code_from_log(&log), Some(243));
+        // Killed with the Manager before the script ended
+        assert_eq!(
+            exit_code_from_log("== npm install -g openclaw\nfetching..."),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tasks_of_an_exited_manager_are_interrupted() {
+        let mut tasks = vec![
+            record("old", "running", 100),
+            record("current", "running", 200),
+            record("done", "succeeded", 100),
+        ];
+        assert_eq!(mark_interrupted(&mut tasks, 200), 1);
+        assert_eq!(tasks[0].status, "interrupted");
+        assert_eq!(tasks[1].status, "running");
+        assert_eq!(tasks[2].status, "succeeded");
+    }
+
+    #[test]
+    fn test_task_process_needs_the_recorded_name() {
+        let process = |pid: u32, name: &str| ProcessInfo {
+            pid,
+            ppid: 1,
+            name: name.to_string(),
+            cpu_percent: None,
+            memory_mb: None,
+        };
+        let task = record("old", "interrupted", 100);
+        assert_eq!(
+            task_process(&task, &[process(4242, "bash")]).map(|p| p.pid),
+            Some(4242)
+        );
+        // The PID was reused by another program after a reboot
+        assert!(task_process(&task, &[process(4242, "firefox")]).is_none());
+        assert!(task_process(&task, &[process(4243, "bash")]).is_none());
+        let unnamed = TaskRecord {
+            process_name: None,
+            ..record("older", "interrupted", 100)
+        };
+        assert!(task_process(&unnamed, &[process(4242, "bash")]).is_none());
+    }
+
+    #[test]
+    fn test_prune_keeps_recent_finished_and_all_interrupted() {
+        let mut tasks: Vec<TaskRecord> = (0..25)
+            .map(|i| record(&format!("t{}", i), "succeeded", 1))
+            .collect();
+        tasks.insert(0, record("interrupted", "interrupted", 1));
+        let removed = prune_finished(&mut tasks);
+        assert_eq!(removed.len(), 5);
+        assert_eq!(removed[0].id, "t0");
+        assert_eq!(tasks.len(), 21);
+        assert_eq!(tasks[0].id, "interrupted");
+        assert_eq!(tasks[1].id, "t5");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_marker_records_the_script_exit_code() {
+        let output = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(with_exit_marker("echo working\nexit 3"))
+            .output()
+            .unwrap();
+        let log = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(exit_code_from_log(&log), Some(3));
+    }
+}
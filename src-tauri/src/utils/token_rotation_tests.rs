@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::config::{
+        record_token_rotation, token_hint, token_rotation_due, TokenRotationRecord,
+        TokenRotationSettings,
+    };
+    use chrono::{DateTime, Utc};
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn record(at: &str, error: Option<&str>) -> TokenRotationRecord {
+        TokenRotationRecord {
+            at: at.to_string(),
+            reason: "scheduled".to_string(),
+            old_token_hint: "...aaaa".to_string(),
+            new_token_hint: "...bbbb".to_string(),
+            restarted: true,
+            old_token_rejected: Some(true),
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_rotation_due_after_interval() {
+        let mut settings = TokenRotationSettings {
+            enabled: true,
+            every_days: 7,
+            last_rotated_at: Some("2026-10-01T10:00:00Z".to_string()),
+            token_bytes: 32,
+            history: Vec::new(),
+        };
+        assert!(!token_rotation_due(&settings, at("2026-10-08T09:59:00Z")));
+        assert!(token_rotation_due(&settings, at("2026-10-08T10:00:00Z")));
+
+        settings.enabled = false;
+        assert!(!token_rotation_due(&settings, at("2026-11-01T00:00:00Z")));
+        settings.enabled = true;
+        settings.last_rotated_at = None;
+        assert!(!token_rotation_due(&settings, at("2026-11-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_history_is_capped_and_failed_restarts_reset_the_clock() {
+        let mut settings = TokenRotationSettings::default();
+        for day in 1..=25 {
+            record_token_rotation(
+                &mut settings,
+                record(&format!("2026-10-{:02}T10:00:00Z", day), None),
+            );
+        }
+        assert_eq!(settings.history.len(), 20);
+        assert_eq!(settings.history[0].at, "2026-10-25T10:00:00Z");
+
+        record_token_rotation(
+            &mut settings,
+            record("2026-10-26T10:00:00Z", Some("restart failed")),
+        );
+        // The token was replaced even though the gateway did not restart
+        assert_eq!(
+            settings.last_rotated_at.as_deref(),
+            Some("2026-10-26T10:00:00Z")
+        );
+        assert_eq!(settings.history[0].at, "2026-10-26T10:00:00Z");
+    }
+
+    #[test]
+    fn test_token_hint_shows_the_tail_only() {
+        assert_eq!(token_hint("0123456789abcdef"), "...cdef");
+        assert_eq!(token_hint("ab"), "...ab");
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::commands::trash::{
+        is_expired, pointer_key, restore_values, take_array_items, take_value,
+    };
+    use serde_json::json;
+
+    fn sample() -> serde_json::Value {
+        json!({
+            "channels": { "telegram": { "botToken": "123:abc" }, "discord": {} },
+            "plugins": { "allow": ["telegram", "discord"], "entries": { "telegram": { "enabled": true } } },
+            "agents": { "list": [{ "id": "main" }, { "id": "helper", "workspace": "/tmp/ws" }] },
+            "bindings": [{ "agentId": "helper" }, { "agentId": "main" }]
+        })
+    }
+
+    #[test]
+    fn test_take_and_restore_roundtrip() {
+        let original = sample();
+        let mut config = original.clone();
+        let mut removed: Vec<_> = take_value(&mut config, "/channels/telegram")
+            .into_iter()
+            .collect();
+        removed.extend(take_value(&mut config, "/plugins/entries/telegram"));
+        removed.extend(take_array_items(&mut config, "/plugins/allow", |v| {
+            v == "telegram"
+        }));
+        assert_eq!(removed.len(), 3);
+        assert!(config.pointer("/channels/telegram").is_none());
+        assert_eq!(config["plugins"]["allow"], json!(["discord"]));
+
+        restore_values(&mut config, &removed).unwrap();
+        assert_eq!(config["channels"], original["channels"]);
+        assert_eq!(config["plugins"]["entries"], original["plugins"]["entries"]);
+        assert_eq!(config["plugins"]["allow"], json!(["discord", "telegram"]));
+    }
+
+    #[test]
+    fn test_take_missing_value() {
+        let mut config = sample();
+        assert!(take_value(&mut config, "/channels/slack").is_none());
+        assert!(take_array_items(&mut config, "/missing", |_| true).is_empty());
+        assert_eq!(config, sample());
+    }
+
+    #[test]
+    fn test_restore_into_removed_parent() {
+        let mut config = sample();
+        let removed = take_array_items(&mut config, "/bindings", |b| b["agentId"] == "helper");
+        config.as_object_mut().unwrap().remove("bindings");
+        restore_values(&mut config, &removed).unwrap();
+        assert_eq!(config["bindings"], json!([{ "agentId": "helper" }]));
+    }
+
+    #[test]
+    fn test_restore_conflicts() {
+        let mut config = sample();
+        let removed: Vec<_> = take_value(&mut config, "/channels/telegram")
+            .into_iter()
+            .collect();
+        config["channels"]["telegram"] = json!({ "botToken": "456:new" });
+        assert!(restore_values(&mut config, &removed).is_err());
+        assert_eq!(config["channels"]["telegram"]["botToken"], "456:new");
+
+        let mut config = sample();
+        let removed = take_array_items(&mut config, "/agents/list", |a| a["id"] == "helper");
+        config["agents"]["list"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "id": "helper" }));
+        assert!(restore_values(&mut config, &removed).is_err());
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = chrono::Utc::now();
+        assert!(!is_expired(
+            &(now - chrono::Duration::days(29)).to_rfc3339(),
+            now
+        ));
+        assert!(is_expired(
+            &(now - chrono::Duration::days(31)).to_rfc3339(),
+            now
+        ));
+        assert!(is_expired("not a date", now));
+    }
+
+    #[test]
+    fn test_pointer_key() {
+        assert_eq!(pointer_key("a/b~c"), "a~1b~0c");
+    }
+}
@@ -0,0 +1,304 @@
+//! Windows Subsystem for Linux: distro detection, Windows <-> Linux path translation and the
+//! optional WSL execution mode, where openclaw and node run inside a distro via `wsl.exe`.
+
+use crate::utils::{platform, shell};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+/// manager.json key holding the WSL execution mode settings
+pub const SETTINGS_KEY: &str = "wsl";
+
+/// Distros installed by Docker Desktop, not meant for user tools
+const INTERNAL_DISTROS: &[&str] = &["docker-desktop", "docker-desktop-data"];
+
+/// Loads nvm (installed per user, not on a login shell's PATH) before running the command
+const PROFILE_PREFIX: &str =
+    "[ -s \"$HOME/.nvm/nvm.sh\" ] && . \"$HOME/.nvm/nvm.sh\" >/dev/null 2>&1;";
+
+/// A distro listed by `wsl.exe -l -v`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct WslDistro {
+    pub name: String,
+    /// WSL version (1 or 2)
+    pub version: u8,
+    pub running: bool,
+    /// The distro `wsl.exe` starts without `-d`
+    pub default: bool,
+    /// OpenClaw version inside the distro (checked for running and default distros only)
+    pub openclaw_version: Option<String>,
+}
+
+/// WSL execution mode (manager.json -> wsl)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WslSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub distro: Option<String>,
+    /// Linux home directory in the distro, resolved when the mode is enabled
+    #[serde(default)]
+    pub home: Option<String>,
+}
+
+/// Distro and Linux home used while WSL mode is active
+#[derive(Debug, Clone, PartialEq)]
+pub struct WslTarget {
+    pub distro: String,
+    pub home: String,
+}
+
+/// Text printed by wsl.exe: its own messages are UTF-16LE, output of Linux commands is UTF-8
+pub fn decode_output(bytes: &[u8]) -> String {
+    let looks_utf16 = bytes.len() >= 2
+        && bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count() * 2 >= bytes.len() / 2;
+    let text = if looks_utf16 {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    text.trim_start_matches('\u{FEFF}').replace('\r', "")
+}
+
+/// Parse `wsl.exe -l -v`:
+/// ```text
+///   NAME      STATE      VERSION
+/// * Ubuntu    Running    2
+///   Debian    Stopped    2
+/// ```
+pub fn parse_distro_list(text: &str) -> Vec<WslDistro> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (default, rest) = match line.strip_prefix('*') {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, line),
+            };
+            let parts: Vec<&str> = rest.split_whitespace().collect();
+            let [.., state, version] = parts.as_slice() else {
+                return None;
+            };
+            let version = version.parse::<u8>().ok()?;
+            let name = parts[..parts.len() - 2].join(" ");
+            if name.is_empty() || INTERNAL_DISTROS.contains(&name.as_str()) {
+                return None;
+            }
+            Some(WslDistro {
+                name,
+                version,
+                running: state.eq_ignore_ascii_case("running"),
+                default,
+                openclaw_version: None,
+            })
+        })
+        .collect()
+}
+
+/// Whether a value is an absolute Windows path (`C:\...`, `C:/...` or a UNC path)
+pub fn is_windows_path(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    value.starts_with(r"\\")
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'\\' || bytes[2] == b'/'))
+}
+
+/// Linux path of a Windows path as seen from `distro`: drives are mounted under /mnt, and
+/// `\\wsl$\<distro>\...` / `\\wsl.localhost\<distro>\...` are the distro's own files
+pub fn to_linux_path(path: &str, distro: &str) -> Option<String> {
+    let normalized = path.replace('/', "\\");
+    for prefix in [r"\\wsl.localhost\", r"\\wsl$\"] {
+        if let Some(rest) = normalized.strip_prefix(prefix) {
+            let (name, inner) = rest.split_once('\\').unwrap_or((rest, ""));
+            if !name.eq_ignore_ascii_case(distro) {
+                return None;
+            }
+            return Some(format!("/{}", inner.replace('\\', "/")));
+        }
+    }
+    let bytes = normalized.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let drive = (bytes[0] as char).to_ascii_lowercase();
+        let rest = normalized[2..].trim_start_matches('\\').replace('\\', "/");
+        return Some(if rest.is_empty() {
+            format!("/mnt/{}", drive)
+        } else {
+            format!("/mnt/{}/{}", drive, rest)
+        });
+    }
+    None
+}
+
+/// Windows path of a Linux path in `distro` (/mnt/<drive> maps back to the drive)
+pub fn to_windows_path(path: &str, distro: &str) -> String {
+    if let Some(rest) = path.strip_prefix("/mnt/") {
+        let (drive, inner) = rest.split_once('/').unwrap_or((rest, ""));
+        if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) {
+            return format!(
+                "{}:\\{}",
+                drive.to_ascii_uppercase(),
+                inner.trim_end_matches('/').replace('/', "\\")
+            );
+        }
+    }
+    format!(
+        r"\\wsl.localhost\{}{}",
+        distro,
+        path.trim_end_matches('/').replace('/', "\\")
+    )
+}
+
+/// WSLENV entry forwarding `key` into the distro; `/p` makes WSL translate a Windows path value
+pub fn wslenv_entry(key: &str, value: &str) -> String {
+    if is_windows_path(value) {
+        format!("{}/p", key)
+    } else {
+        key.to_string()
+    }
+}
+
+/// WSL mode settings, loaded from manager.json on first use
+static SETTINGS: OnceLock<RwLock<WslSettings>> = OnceLock::new();
+
+fn settings_lock() -> &'static RwLock<WslSettings> {
+    SETTINGS.get_or_init(|| {
+        let value = std::fs::read_to_string(platform::get_manager_config_file_path())
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}'))
+                    .ok()
+            })
+            .and_then(|config| config.get(SETTINGS_KEY).cloned())
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        RwLock::new(value)
+    })
+}
+
+pub fn settings() -> WslSettings {
+    settings_lock()
+        .read()
+        .map(|s| s.clone())
+        .unwrap_or_default()
+}
+
+/// Update the in-memory settings (manager.json is written by the caller)
+pub fn set_settings(settings: WslSettings) {
+    if let Ok(mut value) = settings_lock().write() {
+        *value = settings;
+    }
+}
+
+/// Distro and home to run in, None unless WSL mode is enabled on Windows
+pub fn active() -> Option<WslTarget> {
+    if !platform::is_windows() {
+        return None;
+    }
+    let settings = settings();
+    if !settings.enabled {
+        return None;
+    }
+    Some(WslTarget {
+        distro: settings.distro?,
+        home: settings.home?,
+    })
+}
+
+/// OpenClaw config directory inside the distro (~/.openclaw) as a Windows path
+pub fn config_dir() -> Option<String> {
+    active().map(|t| to_windows_path(&format!("{}/.openclaw", t.home), &t.distro))
+}
+
+/// Process running `program` inside `distro` through a login shell (with nvm loaded).
+/// Arguments added afterwards are passed through unchanged.
+pub fn command(distro: &str, program: &str) -> Command {
+    let mut cmd = Command::new("wsl.exe");
+    cmd.args(["-d", distro, "--exec", "bash", "-lc"])
+        .arg(format!("{} exec \"$0\" \"$@\"", PROFILE_PREFIX))
+        .arg(program);
+    cmd
+}
+
+/// Process running a bash script inside `distro` through a login shell (with nvm loaded)
+pub fn script_command(distro: &str, script: &str) -> Command {
+    let mut cmd = Command::new("wsl.exe");
+    cmd.args(["-d", distro, "--exec", "bash", "-lc"])
+        .arg(format!("{}\n{}", PROFILE_PREFIX, script));
+    cmd
+}
+
+/// Forward the environment set on a `wsl.exe` command into the distro through WSLENV
+/// (PATH stays the distro's own). Other commands are left alone.
+pub fn forward_env(cmd: &mut Command) {
+    if cmd.get_program() != "wsl.exe" {
+        return;
+    }
+    let mut entries: Vec<String> = std::env::var("WSLENV")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.split(':').map(String::from).collect())
+        .unwrap_or_default();
+    let forwarded: Vec<String> = cmd
+        .get_envs()
+        .filter_map(|(key, value)| {
+            let key = key.to_str()?;
+            if key == "PATH" || key == "WSLENV" {
+                return None;
+            }
+            Some(wslenv_entry(key, value?.to_str()?))
+        })
+        .collect();
+    entries.extend(forwarded);
+    if !entries.is_empty() {
+        cmd.env("WSLENV", entries.join(":"));
+    }
+}
+
+/// Run a bash script inside `distro`, returning trimmed stdout
+pub fn run(distro: &str, script: &str) -> Result<String, String> {
+    let output = shell::output(&mut hidden(script_command(distro, script)))
+        .map_err(|e| format!("Failed to run wsl.exe: {}", e))?;
+    if output.status.success() {
+        Ok(decode_output(&output.stdout).trim().to_string())
+    } else {
+        Err(decode_output(&output.stderr).trim().to_string())
+    }
+}
+
+/// Installed distros (empty off Windows or without WSL). OpenClaw is looked up only in running
+/// distros and the default one, so detection does not boot every distro.
+pub fn detect_distros() -> Vec<WslDistro> {
+    if !platform::is_windows() {
+        return Vec::new();
+    }
+    let mut cmd = Command::new("wsl.exe");
+    cmd.args(["-l", "-v"]);
+    let Ok(output) = shell::output(&mut hidden(cmd)) else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let mut distros = parse_distro_list(&decode_output(&output.stdout));
+    for distro in distros.iter_mut().filter(|d| d.running || d.default) {
+        distro.openclaw_version = run(&distro.name, "openclaw --version 2>/dev/null")
+            .ok()
+            .filter(|v| !v.is_empty());
+    }
+    distros
+}
+
+fn hidden(mut cmd: Command) -> Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    cmd
+}
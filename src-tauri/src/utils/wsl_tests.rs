@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use super::super::wsl::{
+        decode_output, is_windows_path, parse_distro_list, to_linux_path, to_windows_path,
+        wslenv_entry,
+    };
+
+    #[test]
+    fn test_decode_utf16_listing() {
+        let text = "  NAME   STATE   VERSION\r\n* Ubuntu Running 2\r\n";
+        let bytes: Vec<u8> = text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        assert_eq!(decode_output(&bytes), text.replace('\r', ""));
+        assert_eq!(decode_output(b"v22.11.0\n"), "v22.11.0\n");
+    }
+
+    #[test]
+    fn test_parse_distro_list() {
+        let distros = parse_distro_list(
+            "  NAME                   STATE           VERSION\n\
+             * Ubuntu-24.04           Running         2\n\
+               Debian                 Stopped         1\n\
+               docker-desktop         Running         2\n",
+        );
+        assert_eq!(distros.len(), 2);
+        assert_eq!(distros[0].name, "Ubuntu-24.04");
+        assert!(distros[0].default && distros[0].running);
+        assert_eq!(distros[0].version, 2);
+        assert_eq!(distros[1].name, "Debian");
+        assert!(!distros[1].default && !distros[1].running);
+        assert_eq!(distros[1].version, 1);
+        assert!(
+            parse_distro_list("Windows Subsystem for Linux has no installed distributions.")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_windows_to_linux_paths() {
+        assert_eq!(
+            to_linux_path(r"C:\Users\me\AppData\Local\Temp\probe.json", "Ubuntu").as_deref(),
+            Some("/mnt/c/Users/me/AppData/Local/Temp/probe.json")
+        );
+        assert_eq!(to_linux_path("D:/", "Ubuntu").as_deref(), Some("/mnt/d"));
+        assert_eq!(
+            to_linux_path(r"\\wsl.localhost\Ubuntu\home\me\.openclaw", "ubuntu").as_deref(),
+            Some("/home/me/.openclaw")
+        );
+        assert_eq!(
+            to_linux_path(r"\\wsl$\Ubuntu\home\me", "Ubuntu").as_deref(),
+            Some("/home/me")
+        );
+        assert_eq!(to_linux_path(r"\\wsl$\Debian\home\me", "Ubuntu"), None);
+        assert_eq!(to_linux_path("relative/path", "Ubuntu"), None);
+    }
+
+    #[test]
+    fn test_linux_to_windows_paths() {
+        assert_eq!(
+            to_windows_path("/home/me/.openclaw", "Ubuntu"),
+            r"\\wsl.localhost\Ubuntu\home\me\.openclaw"
+        );
+        assert_eq!(
+            to_windows_path("/mnt/c/Users/me/", "Ubuntu"),
+            r"C:\Users\me"
+        );
+        assert_eq!(
+            to_windows_path("/mnt/wsl/shared", "Ubuntu"),
+            r"\\wsl.localhost\Ubuntu\mnt\wsl\shared"
+        );
+    }
+
+    #[test]
+    fn test_wslenv_translates_path_values() {
+        assert!(is_windows_path(r"C:\Temp\x.json"));
+        assert!(!is_windows_path("/tmp/x.json"));
+        assert_eq!(
+            wslenv_entry("OPENCLAW_CONFIG_PATH", r"C:\Temp\x.json"),
+            "OPENCLAW_CONFIG_PATH/p"
+        );
+        assert_eq!(
+            wslenv_entry("OPENCLAW_GATEWAY_TOKEN", "abc123"),
+            "OPENCLAW_GATEWAY_TOKEN"
+        );
+    }
+}
@@ -12,7 +12,7 @@ import { Settings } from './components/Settings';
 
 import { Logs } from './components/Logs';
 import { appLogger } from './lib/logger';
-import { isTauri } from './lib/tauri';
+import { api, isTauri, onManagerEvent } from './lib/tauri';
 import { Download, X, Loader2, CheckCircle, AlertCircle } from 'lucide-react';
 
 import { Agents } from './components/Agents';
@@ -94,6 +94,8 @@ class ErrorBoundary extends React.Component<{ children: React.ReactNode }, { has
 
 function App() {
   const [currentPage, setCurrentPage] = useState<PageType>('dashboard');
+  // Bumped when config files are edited outside the Manager, remounting the page to reload them
+  const [configRevision, setConfigRevision] = useState(0);
   const [isReady, setIsReady] = useState<boolean | null>(null);
   const [envStatus, setEnvStatus] = useState<EnvironmentStatus | null>(null);
   const [serviceStatus, setServiceStatus] = useState<ServiceStatus | null>(null);
@@ -278,6 +280,31 @@ function App() {
     return () => clearTimeout(timer);
   }, [checkSecurity]);
 
+  // Offer to send crash reports left by the previous session (nothing is sent without asking)
+  useEffect(() => {
+    if (!isTauri()) return;
+    const timer = setTimeout(async () => {
+      try {
+        const status = await api.getCrashReportStatus();
+        if (status.pending.length === 0) return;
+        const ids = status.pending.map((report) => report.id);
+        const latest = status.pending[0];
+        const summary = `The Manager closed unexpectedly${status.pending.length > 1 ? ` (${status.pending.length} times)` : ''}.\n\n${latest.message}${latest.location ? `\nat ${latest.location}` : ''}`;
+        if (!status.endpointConfigured) {
+          alert(`${summary}\n\nThe crash report is saved in ${status.reportsDir}; attach it when reporting the problem.`);
+          await api.dismissCrashReports(ids);
+        } else if (confirm(`${summary}\n\nSend the crash report? It contains the error, a stack trace and the last Manager log lines (secrets masked).`)) {
+          await api.submitCrashReports(ids);
+        } else {
+          await api.dismissCrashReports(ids);
+        }
+      } catch (e) {
+        appLogger.warn('Crash report check failed', e);
+      }
+    }, 1500);
+    return () => clearTimeout(timer);
+  }, []);
+
   // Periodically get service status
   useEffect(() => {
     // Don't poll if not in Tauri environment
@@ -296,6 +323,27 @@ function App() {
     return () => clearInterval(interval);
   }, []);
 
+  // Reload the open page when openclaw.json, env or mcps.json change outside the Manager
+  useEffect(() => {
+    if (!isTauri()) return;
+    const unlisten = onManagerEvent((event) => {
+      // Reads never overwrite a damaged openclaw.json, restoring the backup is up to the user
+      if (event.type === 'alert' && event.payload.kind === 'config-damaged') {
+        if (!confirm(event.payload.message)) return;
+        api.restoreConfigFromBackup()
+          .then(() => setConfigRevision((revision) => revision + 1))
+          .catch((e) => appLogger.error('Config restore failed', e));
+        return;
+      }
+      if (event.type !== 'config-changed') return;
+      const detail = event.payload.detail as { external?: boolean } | null;
+      if (!detail?.external) return;
+      appLogger.info('Config changed outside the Manager', { scope: event.payload.scope, path: event.payload.path });
+      setConfigRevision((revision) => revision + 1);
+    });
+    return () => { unlisten.then((stop) => stop()); };
+  }, []);
+
   const handleSetupComplete = useCallback(() => {
     appLogger.info('Setup wizard completed');
     checkEnvironment(); // Re-check environment
@@ -305,6 +353,8 @@ function App() {
   const handleNavigate = (page: PageType) => {
     appLogger.action('Page navigation', { from: currentPage, to: page });
     setCurrentPage(page);
+    // Attribute config changes in the history to the page they were made from
+    invoke('set_active_view', { view: page }).catch(() => {});
   };
 
   const renderPage = () => {
@@ -337,7 +387,9 @@ function App() {
           transition={{ duration: 0.2 }}
           className="h-full"
         >
-          {pages[currentPage]}
+          <div key={configRevision} className="h-full">
+            {pages[currentPage]}
+          </div>
         </motion.div>
       </AnimatePresence>
     );
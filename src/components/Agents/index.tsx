@@ -44,6 +44,8 @@ interface MatchRule {
     channel: string | null;
     account_id: string | null;
     peer: any | null;
+    // Telegram forum topic within the peer group
+    topic?: string | null;
 }
 
 interface AgentBinding {
@@ -70,6 +72,8 @@ interface RoutingTestResult {
     agent_dir?: string;
     model?: string;
     system_prompt_preview?: string;
+    // 'topic' | 'peer' | 'account' | 'channel' | 'fallback'
+    matched_by?: string;
     message?: string;
 }
 
@@ -1,6 +1,7 @@
 import { useEffect, useState } from 'react';
 import { motion } from 'framer-motion';
 import { invoke } from '@tauri-apps/api/core';
+import { api, formatPluginReview } from '../../lib/tauri';
 import {
   MessageCircle,
   Hash,
@@ -411,6 +412,7 @@ export function Channels() {
     groups?: Record<string, unknown>;
     primary?: boolean;
     allow_from?: string[];
+    enabled?: boolean;
   }
   const [telegramAccounts, setTelegramAccounts] = useState<TelegramAccountInfo[]>([]);
   const [showAddAccountDialog, setShowAddAccountDialog] = useState(false);
@@ -464,7 +466,13 @@ export function Channels() {
   const handleInstallFeishuPlugin = async () => {
     setFeishuPluginInstalling(true);
     try {
-      const result = await invoke<string>('install_feishu_plugin');
+      const review = await api.reviewPluginInstall('@m1heng-clawd/feishu');
+      let confirmed = false;
+      if (review.requiresConfirmation) {
+        confirmed = confirm(`${formatPluginReview(review)}\n\nThis package can run code with your API keys while it installs. Install it?`);
+        if (!confirmed) return;
+      }
+      const result = await api.installFeishuPlugin(confirmed);
       alert(result);
       // Refresh plugin status
       await checkFeishuPlugin();
@@ -684,7 +692,15 @@ export function Channels() {
     setSavingAccount(true);
     try {
       console.log('[Channels] Saving telegram account:', account.id, 'allow_from:', account.allow_from, 'dm_policy:', account.dm_policy);
-      await invoke('save_telegram_account', { account });
+      const opensDms = account.dm_policy === 'open' || (account.allow_from ?? []).includes('*');
+      let acknowledgeRisk = false;
+      if (opensDms) {
+        acknowledgeRisk = confirm(
+          `Account "${account.id}" will accept direct messages from anyone on Telegram. Save this policy?`
+        );
+        if (!acknowledgeRisk) return;
+      }
+      await invoke('save_telegram_account', { account, acknowledgeRisk });
       await fetchTelegramAccounts();
     } catch (e) {
       console.error('Failed to save telegram account:', e);
@@ -44,11 +44,22 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
     };
   }, []);
 
+  // Confirm readiness problems (channels, free memory / disk); null when the user cancels
+  const confirmReadiness = async (action: string): Promise<boolean | null> => {
+    const readiness = await api.getStartReadiness();
+    const problems = readiness.checks.filter((c) => c.status !== 'ok');
+    if (problems.length === 0) return false;
+    const list = problems.map((c) => `- [${c.status}] ${c.channel}: ${c.message}`).join('\n');
+    return confirm(`Some checks did not pass:\n${list}\n\n${action} the gateway anyway?`) ? true : null;
+  };
+
   const handleStart = async () => {
     if (!isTauri()) return;
     setActionLoading(true);
     try {
-      await api.startService();
+      const force = await confirmReadiness('Start');
+      if (force === null) return;
+      await api.startService(force);
       await fetchStatus();
     } catch (e) {
       console.error('Start failed:', e);
@@ -74,7 +85,9 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
     if (!isTauri()) return;
     setActionLoading(true);
     try {
-      await api.restartService();
+      const force = await confirmReadiness('Restart');
+      if (force === null) return;
+      await api.restartService(force);
       await fetchStatus();
     } catch (e) {
       console.error('Restart failed:', e);
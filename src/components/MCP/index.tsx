@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { api, MCPConfig, isTauri } from '../../lib/tauri';
+import { api, MCPConfig, isTauri, formatPluginReview } from '../../lib/tauri';
 import { Plus, Trash2, Edit2, Save, Terminal, Blocks, AlertCircle, GitBranch, Loader2, Download, CheckCircle, Package, Plug, Globe, Zap } from 'lucide-react';
 import clsx from 'clsx';
 
@@ -238,8 +238,18 @@ export function MCP() {
         try {
             let result: string;
             if (installMode === 'plugin') {
+                setInstallProgress('Reviewing package metadata...');
+                const review = await api.reviewPluginInstall(gitUrl.trim());
+                let confirmed = false;
+                if (review.requiresConfirmation) {
+                    confirmed = confirm(`${formatPluginReview(review)}\n\nThis package can run code with your API keys while it installs. Install it?`);
+                    if (!confirmed) {
+                        setInstallProgress('');
+                        return;
+                    }
+                }
                 setInstallProgress('Installing via OpenClaw plugins system...');
-                result = await api.installMCPPlugin(gitUrl.trim());
+                result = await api.installMCPPlugin(gitUrl.trim(), confirmed);
             } else {
                 setInstallProgress('Cloning repository and building from source...');
                 result = await api.installMCPFromGit(gitUrl.trim());
@@ -0,0 +1,217 @@
+import { useState, useEffect, useRef } from 'react';
+import { Terminal, Play, Square, Loader2, ShieldAlert } from 'lucide-react';
+import { api, isTauri, onManagerEvent } from '../../lib/tauri';
+
+interface OutputChunk {
+  stream: 'stdout' | 'stderr' | 'info';
+  text: string;
+}
+
+// Characters kept in the output pane; older output is dropped
+const MAX_OUTPUT_CHARS = 200_000;
+
+// Split a typed command line into arguments ("double" and 'single' quotes group words)
+function splitArgs(line: string): string[] {
+  const args: string[] = [];
+  let current = '';
+  let quote: string | null = null;
+  let started = false;
+  for (const ch of line) {
+    if (quote) {
+      if (ch === quote) quote = null;
+      else current += ch;
+    } else if (ch === '"' || ch === "'") {
+      quote = ch;
+      started = true;
+    } else if (/\s/.test(ch)) {
+      if (started) args.push(current);
+      current = '';
+      started = false;
+    } else {
+      current += ch;
+      started = true;
+    }
+  }
+  if (started) args.push(current);
+  return args;
+}
+
+export function Console() {
+  const [advancedMode, setAdvancedMode] = useState(false);
+  const [commandLine, setCommandLine] = useState('');
+  const [input, setInput] = useState('');
+  const [sessionId, setSessionId] = useState<string | null>(null);
+  const [starting, setStarting] = useState(false);
+  const [output, setOutput] = useState<OutputChunk[]>([]);
+  const sessionRef = useRef<string | null>(null);
+  const outputRef = useRef<HTMLPreElement>(null);
+
+  const append = (chunk: OutputChunk) => {
+    setOutput(prev => {
+      const next = [...prev, chunk];
+      let size = next.reduce((n, c) => n + c.text.length, 0);
+      while (size > MAX_OUTPUT_CHARS && next.length > 1) {
+        size -= next.shift()!.text.length;
+      }
+      return next;
+    });
+  };
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    api.getAdvancedMode().then(setAdvancedMode).catch(() => {});
+    const unlisten = onManagerEvent(event => {
+      if (event.type === 'console-output' && event.payload.session === sessionRef.current) {
+        append({ stream: event.payload.stream, text: event.payload.text });
+      } else if (event.type === 'console-exit' && event.payload.session === sessionRef.current) {
+        const { code, cancelled } = event.payload;
+        append({ stream: 'info', text: `\n[${cancelled ? 'cancelled' : code === null ? 'killed' : `exit ${code}`}]\n` });
+        sessionRef.current = null;
+        setSessionId(null);
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    outputRef.current?.scrollTo(0, outputRef.current.scrollHeight);
+  }, [output]);
+
+  const toggleAdvancedMode = async (enabled: boolean) => {
+    if (enabled && !confirm('Advanced mode lets you run any openclaw command from the Manager. Every command is recorded in the audit log. Enable it?')) {
+      return;
+    }
+    try {
+      await api.setAdvancedMode(enabled);
+      setAdvancedMode(enabled);
+    } catch (e) {
+      alert(String(e));
+    }
+  };
+
+  const run = async () => {
+    const args = splitArgs(commandLine);
+    if (args.length === 0 || sessionId) return;
+    setStarting(true);
+    try {
+      const session = await api.startConsoleCommand(args);
+      sessionRef.current = session.id;
+      setSessionId(session.id);
+      setOutput([{ stream: 'info', text: `$ ${session.command}\n` }]);
+    } catch (e) {
+      setOutput([{ stream: 'stderr', text: `${e}\n` }]);
+    } finally {
+      setStarting(false);
+    }
+  };
+
+  const sendInput = async () => {
+    if (!sessionId) return;
+    try {
+      await api.writeConsoleInput(sessionId, input + '\n');
+      append({ stream: 'info', text: `${input}\n` });
+      setInput('');
+    } catch (e) {
+      append({ stream: 'stderr', text: `${e}\n` });
+    }
+  };
+
+  const cancel = async () => {
+    if (!sessionId) return;
+    try {
+      await api.cancelConsoleCommand(sessionId);
+    } catch (e) {
+      append({ stream: 'stderr', text: `${e}\n` });
+    }
+  };
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3 mb-6">
+        <div className="w-10 h-10 rounded-xl bg-gray-500/20 flex items-center justify-center">
+          <Terminal size={20} className="text-gray-300" />
+        </div>
+        <div>
+          <h3 className="text-lg font-semibold text-white">Console</h3>
+          <p className="text-xs text-gray-500">Run any openclaw command (advanced mode)</p>
+        </div>
+      </div>
+
+      <div className="flex items-center justify-between p-4 bg-dark-600 rounded-lg mb-4">
+        <div>
+          <p className="text-sm text-white">Advanced Mode</p>
+          <p className="text-xs text-gray-500">Commands run with your OpenClaw setup and are recorded in the audit log</p>
+        </div>
+        <input
+          type="checkbox"
+          checked={advancedMode}
+          onChange={e => toggleAdvancedMode(e.target.checked)}
+          className="w-5 h-5 rounded bg-dark-500 border-dark-400 text-claw-500 focus:ring-claw-500/50"
+        />
+      </div>
+
+      {advancedMode && (
+        <div className="space-y-3">
+          <div className="flex gap-2">
+            <span className="self-center text-sm font-mono text-gray-500">openclaw</span>
+            <input
+              type="text"
+              value={commandLine}
+              onChange={e => setCommandLine(e.target.value)}
+              onKeyDown={e => e.key === 'Enter' && run()}
+              placeholder="status --deep"
+              disabled={!!sessionId}
+              className="input-base font-mono flex-1"
+            />
+            {sessionId ? (
+              <button onClick={cancel} className="btn-secondary flex items-center gap-2">
+                <Square size={16} />
+                Cancel
+              </button>
+            ) : (
+              <button onClick={run} disabled={starting || !commandLine.trim()} className="btn-primary flex items-center gap-2">
+                {starting ? <Loader2 size={16} className="animate-spin" /> : <Play size={16} />}
+                Run
+              </button>
+            )}
+          </div>
+
+          <pre
+            ref={outputRef}
+            className="h-64 overflow-auto p-3 bg-dark-900 rounded-lg text-xs font-mono whitespace-pre-wrap"
+          >
+            {output.map((chunk, i) => (
+              <span
+                key={i}
+                className={chunk.stream === 'stderr' ? 'text-red-300' : chunk.stream === 'info' ? 'text-gray-500' : 'text-gray-200'}
+              >
+                {chunk.text}
+              </span>
+            ))}
+          </pre>
+
+          {sessionId && (
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={input}
+                onChange={e => setInput(e.target.value)}
+                onKeyDown={e => e.key === 'Enter' && sendInput()}
+                placeholder="Answer a prompt, then press Enter"
+                className="input-base font-mono flex-1"
+              />
+              <button onClick={sendInput} className="btn-secondary">Send</button>
+            </div>
+          )}
+
+          <div className="flex items-start gap-2 text-xs text-gray-500">
+            <ShieldAlert size={14} className="mt-0.5 shrink-0" />
+            <p>No terminal is attached: commands that need one fall back to their non-interactive behavior. Secret flag values are masked in the audit log.</p>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
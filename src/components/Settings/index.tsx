@@ -21,6 +21,7 @@ import {
 } from 'lucide-react';
 import { appLogger } from '../../lib/logger';
 import { isTauri } from '../../lib/tauri';
+import { Console } from './Console';
 
 interface InstallResult {
   success: boolean;
@@ -218,7 +219,16 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       });
 
       if (path) {
-        await invoke('export_config', { path });
+        const found = await invoke<{ path: string; kind: string }[]>('scan_config_secrets');
+        let secrets: string | null = null;
+        if (found.length > 0) {
+          const list = found.slice(0, 5).map((f) => `- ${f.path} (${f.kind})`).join('\n');
+          secrets = confirm(
+            `The configuration contains ${found.length} possible secret(s):\n${list}\n\n` +
+            'OK = include them in the export, Cancel = redact them.'
+          ) ? 'include' : 'redact';
+        }
+        await invoke('export_config', { path, secrets });
         alert('Configuration exported successfully!');
       }
     } catch (e) {
@@ -881,6 +891,8 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
           </div>
         </div>
 
+        <Console />
+
         {/* Danger Zone */}
         <div className="bg-dark-700 rounded-2xl p-6 border border-red-900/30 opacity-80 hover:opacity-100 transition-opacity">
           <div className="flex items-center gap-3 mb-4">
@@ -12,6 +12,7 @@ import {
   Package
 } from 'lucide-react';
 import { setupLogger } from '../../lib/logger';
+import { WslDistro } from '../../lib/tauri';
 
 interface EnvironmentStatus {
   node_installed: boolean;
@@ -24,6 +25,8 @@ interface EnvironmentStatus {
   config_dir_exists: boolean;
   ready: boolean;
   os: string;
+  wsl_distros: WslDistro[];
+  wsl_distro: string | null;
 }
 
 interface InstallResult {
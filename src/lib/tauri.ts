@@ -1,6 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { apiLogger } from './logger';
 
+// Typed client and types generated from the Rust command signatures (`npm run bindings`).
+// Names declared below take precedence over the generated ones.
+export * from './bindings';
+
 // Check if running in Tauri environment
 export function isTauri(): boolean {
   return typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
@@ -30,6 +35,37 @@ export interface ServiceStatus {
   uptime_seconds: number | null;
   memory_mb: number | null;
   cpu_percent: number | null;
+  origin: 'manager' | 'adopted' | 'external' | null;
+}
+
+// Gateway found on the service port and who started it
+export interface ExternalGateway {
+  pid: number;
+  origin: 'manager' | 'adopted' | 'external';
+  command: string | null;
+  config_dir: string | null;
+  config_dir_matches: boolean | null;
+  since: string | null;
+}
+
+// What the gateway's HTTP listener answered to a request with the configured token
+export interface GatewayProbe {
+  port: number;
+  reachable: boolean;
+  http_status: number | null;
+  token_accepted: boolean | null;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+// Gateway with its own port and state directory; "default" is the built-in one
+export interface GatewayInstance {
+  id: string;
+  name: string;
+  port: number;
+  config_dir: string | null;
+  profile: string | null;
+  builtin: boolean;
 }
 
 // System information
@@ -37,6 +73,10 @@ export interface SystemInfo {
   os: string;
   os_version: string;
   arch: string;
+  // Native CPU architecture in Node.js naming (x64, arm64, armv7l, ...)
+  native_arch: string;
+  // Manager runs emulated (Rosetta 2, x64 on Windows on ARM)
+  translated: boolean;
   openclaw_installed: boolean;
   openclaw_version: string | null;
   node_version: string | null;
@@ -88,9 +128,37 @@ export interface ConfiguredProvider {
   base_url: string;
   api_key_masked: string | null;
   has_api_key: boolean;
+  header_names: string[];
+  auth_header: boolean | null;
   models: ConfiguredModel[];
 }
 
+// Gemini model from the AI Studio models listing
+export interface GeminiModel {
+  id: string;
+  name: string;
+  description: string | null;
+  input_token_limit: number | null;
+  output_token_limit: number | null;
+}
+
+// Uploaded Vertex AI service account
+export interface ServiceAccountInfo {
+  project_id: string;
+  client_email: string;
+  private_key_id: string;
+}
+
+// Optional save_provider settings; an empty header value keeps the stored value
+export interface ProviderSaveOptions {
+  headers?: Record<string, string>;
+  authHeader?: boolean;
+  azure?: { resource: string };
+  verify?: boolean;
+  // Keep the API key in the OS keychain; openclaw.json only references it
+  keychain?: boolean;
+}
+
 export interface ConfiguredModel {
   full_id: string;
   id: string;
@@ -118,6 +186,7 @@ export interface ModelConfig {
   max_tokens: number | null;
   reasoning: boolean | null;
   cost: { input: number; output: number; cache_read: number; cache_write: number } | null;
+  headers?: Record<string, string>;
 }
 
 // Channel configuration
@@ -153,6 +222,18 @@ export interface MCPConfig {
   env?: Record<string, string>;
   url?: string;
   enabled: boolean;
+  docker?: DockerMCPConfig;
+  // Set when imported from another tool's config, e.g. 'mcporter'
+  external?: string;
+}
+
+// MCP server run from a Docker image
+export interface DockerMCPConfig {
+  image: string;
+  volumes?: string[];
+  env?: Record<string, string>;
+  args?: string[];
+  network?: string;
 }
 
 // Skill
@@ -173,17 +254,752 @@ export interface MemoryConfig {
   provider: string | null;
 }
 
+// Embeddings model for memory search; api_key is write-only (empty keeps the stored key)
+export interface EmbeddingConfig {
+  provider: 'openai' | 'gemini' | 'local' | null;
+  model: string | null;
+  base_url: string | null;
+  api_key: string | null;
+  api_key_masked?: string | null;
+  fallback: string | null;
+}
+
+// Speech settings (messages.tts and tools.media.audio); tts_api_key is write-only
+export interface VoiceConfig {
+  tts_auto: 'off' | 'always' | 'inbound' | 'tagged' | null;
+  tts_provider: 'openai' | 'elevenlabs' | 'edge' | null;
+  tts_voice: string | null;
+  tts_model: string | null;
+  tts_api_key: string | null;
+  tts_api_key_masked?: string | null;
+  stt_enabled: boolean | null;
+  stt_provider: string | null;
+  stt_model: string | null;
+}
+
+export interface TtsTestResult {
+  success: boolean;
+  provider: string;
+  audio_path: string | null;
+  bytes: number | null;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+export interface EmbeddingTestResult {
+  success: boolean;
+  provider: string;
+  model: string;
+  dimensions: number | null;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+// Per-channel attachment handling (max_file_mb lives in the channel config)
+export interface AttachmentPolicy {
+  channel: string;
+  max_file_mb: number | null;
+  cleanup_after_days: number | null;
+}
+
+export interface AttachmentStorage {
+  dir: string;
+  channels: string[];
+  file_count: number;
+  total_bytes: number;
+  free_bytes: number | null;
+  low_space: boolean;
+}
+
+// Global limits for received files; a channel's cleanup_after_days wins over max_age_days
+export interface AttachmentRetention {
+  max_age_days: number | null;
+  max_total_gb: number | null;
+}
+
+export interface AttachmentCleanupReport {
+  ran_at: string;
+  dry_run: boolean;
+  removed: { path: string; bytes: number; reason: 'expired' | 'quota' }[];
+  freed_bytes: number;
+  remaining_bytes: number;
+  errors: string[];
+}
+
+// Manager self-test: failures mean the Manager (not OpenClaw) is broken
+export interface SelfTestCheck {
+  name: string;
+  passed: boolean;
+  duration_ms: number;
+  error: string | null;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  checks: SelfTestCheck[];
+  openclaw_path: string | null;
+}
+
+// A command the backend answers, with its payload schema version
+export interface CommandInfo {
+  name: string;
+  schema_version: number;
+  deprecated: boolean;
+  replaced_by: string | null;
+  deprecated_in: string | null;
+}
+
+// Commands available in this backend (get_api_manifest)
+export interface ApiManifest {
+  api_version: number;
+  app_version: string;
+  commands: CommandInfo[];
+}
+
+// Deleted channel, agent or Telegram account that can still be restored
+export interface DeletedItemSummary {
+  id: string;
+  kind: 'channel' | 'agent' | 'telegram-account';
+  name: string;
+  deleted_at: string;
+  expires_at: string;
+  pointers: string[];
+  env_keys: string[];
+  files: string[];
+}
+
+// Outcome of one item of a bulk install (task-progress events report each item as it runs)
+export interface BulkItemResult {
+  item: string;
+  success: boolean;
+  message: string | null;
+  error: string | null;
+}
+
+// One server of a bulk MCP install: a git URL or an npm package
+export interface MCPInstallRequest {
+  source: 'git' | 'npm';
+  target: string;
+  mode?: 'npx' | 'local' | null;
+  argsPreset?: string[] | null;
+}
+
+// Copy of openclaw.json, mcps.json and manager.json (taken manually or before a destructive command)
+export interface SnapshotInfo {
+  id: string;
+  createdAt: string;
+  reason: string;
+  automatic: boolean;
+  files: string[];
+}
+
+// Crash report of the Manager (manager/crash-reports/<id>.json)
+export interface CrashReport {
+  id: string;
+  createdAt: string;
+  managerVersion: string;
+  os: string;
+  arch: string;
+  kind: 'panic' | 'unclean-exit';
+  message: string;
+  location: string | null;
+  thread: string | null;
+  backtrace: string[];
+  logTail: string[];
+  sessionStartedAt: string | null;
+  submittedAt: string | null;
+  dismissed: boolean;
+}
+
+// Crash reports offered at launch; they are only sent when the user agrees
+export interface CrashReportStatus {
+  pending: CrashReport[];
+  endpointConfigured: boolean;
+  reportsDir: string;
+}
+
+// Command started in the embedded console (advanced mode)
+export interface ConsoleSession {
+  id: string;
+  command: string;
+  pid: number;
+}
+
+// Line of manager/audit.jsonl: console commands and advanced mode changes
+export interface AuditEntry {
+  timestamp: string;
+  action: 'console-run' | 'console-exit' | 'advanced-mode';
+  user: string | null;
+  detail: string;
+  outcome: string | null;
+}
+
+// openclaw.json changes since a snapshot (added = only in the live file, removed = only in the snapshot)
+export interface SnapshotDiff {
+  snapshot: SnapshotInfo;
+  added: JsonChange[];
+  removed: JsonChange[];
+  changed: JsonChange[];
+}
+
+// Schema violation, with the JSON pointer of the offending value
+export interface SchemaError {
+  pointer: string;
+  message: string;
+}
+
+// Config checked against the schema printed by `openclaw config schema`
+export interface ConfigSchemaReport {
+  schemaAvailable: boolean;
+  valid: boolean;
+  errors: SchemaError[];
+}
+
+// npm lifecycle script a plugin runs while it is installed
+export interface PluginScript {
+  hook: string;
+  command: string;
+}
+
+// What a plugin package declares, reviewed before it is installed
+export interface PluginReview {
+  spec: string;
+  name: string | null;
+  version: string | null;
+  description: string | null;
+  license: string | null;
+  repository: string | null;
+  dependencies: string[];
+  binaries: string[];
+  openclaw: unknown | null;
+  installScripts: PluginScript[];
+  metadataAvailable: boolean;
+  requiresConfirmation: boolean;
+  warnings: string[];
+}
+
+// Review as text for the confirmation prompt before a plugin install
+export function formatPluginReview(review: PluginReview): string {
+  const lines = [`${review.name ?? review.spec}${review.version ? '@' + review.version : ''}`];
+  if (review.description) lines.push(review.description);
+  if (review.repository) lines.push(`Repository: ${review.repository}`);
+  if (review.license) lines.push(`License: ${review.license}`);
+  for (const script of review.installScripts) lines.push(`${script.hook}: ${script.command}`);
+  if (review.dependencies.length > 0) {
+    lines.push(`Dependencies (${review.dependencies.length}): ${review.dependencies.join(', ')}`);
+  }
+  if (review.binaries.length > 0) lines.push(`Commands: ${review.binaries.join(', ')}`);
+  if (review.openclaw) lines.push(`OpenClaw manifest: ${JSON.stringify(review.openclaw)}`);
+  for (const warning of review.warnings) lines.push(`Warning: ${warning}`);
+  return lines.join('\n');
+}
+
+// A ${env:VAR} / ${secret:name} placeholder in openclaw.json and how it resolves
+export interface PlaceholderStatus {
+  pointer: string;
+  placeholder: string;
+  kind: 'env' | 'secret';
+  name: string;
+  env_var: string;
+  status: 'resolved' | 'missing' | 'invalid';
+  source: string | null;
+}
+
+export interface PlaceholderReport {
+  placeholders: PlaceholderStatus[];
+  unresolved: number;
+}
+
+// Stored secret for ${secret:name} placeholders (value is never returned)
+export interface ConfigSecretInfo {
+  name: string;
+  env_var: string;
+  // "file" (manager/secrets.json) or "keychain" (OS credential store)
+  storage: string;
+  used_at: string[];
+}
+
+// Whether the OS keychain (Keychain, Credential Manager, Secret Service) works here
+export interface KeychainStatus {
+  available: boolean;
+  error: string | null;
+  secrets: number;
+}
+
+// One difference between two configs (secret values masked)
+export interface JsonChange {
+  path: string;
+  pointer: string;
+  kind: 'added' | 'removed' | 'changed';
+  old_value: unknown | null;
+  new_value: unknown | null;
+}
+
+// Config differences between a gateway start and the previous one
+export interface RestartDiff {
+  timestamp: string;
+  trigger: 'start' | 'restart' | 'supervisor';
+  previousAt: string | null;
+  sections: string[];
+  changes: JsonChange[];
+}
+
+// Drift of one config section against another machine (old_value = local)
+export interface SectionDrift {
+  section: string;
+  added: number;
+  removed: number;
+  changed: number;
+  changes: JsonChange[];
+}
+
+export interface DriftReport {
+  source: string;
+  identical: boolean;
+  total: number;
+  sections: SectionDrift[];
+  redacted: number;
+}
+
+// Git repository the config is synced with
+export interface GitSyncSettings {
+  url: string;
+  branch: string;
+  path: string;
+  lastSyncedCommit: string | null;
+}
+
+export interface GitSyncStatus {
+  linked: boolean;
+  settings: GitSyncSettings | null;
+  head_commit: string | null;
+  local_changes: number;
+}
+
+// What applying the repository's config would change
+export interface GitPullPreview {
+  commit: string;
+  subject: string;
+  changes: JsonChange[];
+  valid: boolean;
+  validation_error: string | null;
+}
+
+// Gateway memory / CPU alert thresholds (manager.json -> resourceAlerts)
+export interface ResourceAlertConfig {
+  enabled: boolean;
+  memoryMb: number;
+  cpuPercent: number;
+  cpuMinutes: number;
+}
+
+export interface ResourceSample {
+  at: number;
+  memory_mb: number;
+  cpu_seconds: number;
+  cpu_percent: number | null;
+}
+
+// Payload of the gateway-resources alert
+export interface ResourceAlert {
+  kind: 'memory' | 'cpu';
+  value: number;
+  threshold: number;
+  message: string;
+}
+
+export interface ResourceDiagnostics {
+  path: string;
+  captured_at: string;
+  pid: number | null;
+  samples: ResourceSample[];
+  process_tree: ProcessNode | null;
+  log_excerpt: string[];
+  restarted: boolean;
+  restart_message: string;
+}
+
+// Free memory / disk floors checked before the gateway starts (manager.json -> resourceFloor)
+export interface ResourceFloorConfig {
+  enabled: boolean;
+  memoryMb: number;
+  diskMb: number;
+  // Refuse to start below a floor unless forced; otherwise only warn
+  block: boolean;
+}
+
+// Priority / affinity / memory and CPU limits the gateway is started with (manager.json -> gatewayLimits)
+export interface ProcessLimits {
+  priority: 'normal' | 'belowNormal' | 'idle';
+  lowIo: boolean;
+  // 0-based CPU indexes, empty for all
+  cpuAffinity: number[];
+  memoryLimitMb: number | null;
+  // Percent of one core
+  cpuQuotaPercent: number | null;
+}
+
+// Gateway network traffic of one local day
+export interface NetworkDay {
+  date: string;
+  bytes_sent: number;
+  bytes_received: number;
+}
+
+export interface NetworkStats {
+  // 'ss' (Linux) or 'nettop' (macOS); null where the OS has no per-process counters
+  method: string | null;
+  days: NetworkDay[];
+  total_sent: number;
+  total_received: number;
+}
+
+export interface GatewayLimitsStatus {
+  limits: ProcessLimits;
+  // Limits that cannot be applied on this machine, with the reason
+  unsupported: string[];
+  cpu_count: number;
+}
+
+// Session replay: what the model saw at each turn of a session
+export interface ReplayMessage {
+  index: number;
+  id: string | null;
+  timestamp: string | null;
+  // user, assistant, toolResult or compaction
+  role: string;
+  text: string;
+  toolCalls: string[];
+  model: string | null;
+}
+
+export interface ReplayTurn {
+  turn: number;
+  timestamp: string | null;
+  model: string | null;
+  // Indexes into SessionReplay.messages, in the order they were sent
+  window: number[];
+  response: number;
+  usage: unknown | null;
+}
+
+export interface PromptFile {
+  name: string;
+  path: string;
+  included: boolean;
+  content: string;
+  truncated: boolean;
+  modifiedAt: string | null;
+  changedSinceSession: boolean;
+}
+
+export interface SessionReplay {
+  agentId: string;
+  sessionId: string;
+  startedAt: string | null;
+  files: PromptFile[];
+  messages: ReplayMessage[];
+  turns: ReplayTurn[];
+  notes: string[];
+  exportPath: string;
+}
+
+// Tokens and per-message input cost of a workspace prompt file (SOUL.md, AGENTS.md, ...)
+export interface PromptCostEstimate {
+  file: string;
+  tokens: number;
+  previous_tokens: number;
+  token_delta: number;
+  // Content past bootstrapMaxChars is cut by OpenClaw and not counted
+  truncated: boolean;
+  model: string | null;
+  // USD per million input tokens, null when the model has no price configured
+  input_price: number | null;
+  cost_per_message: number | null;
+  cost_delta_per_message: number | null;
+  cached_cost_per_message: number | null;
+}
+
+export interface PromptFileSaved {
+  message: string;
+  estimate: PromptCostEstimate;
+}
+
+// Address book entry: a friendly name for a channel peer id
+export interface Contact {
+  // <channel>:<peerId> (derived when saving)
+  id: string;
+  name: string;
+  channel: string;
+  peerId: string;
+  // direct, group or channel
+  kind: string;
+  accountId: string | null;
+  notes: string | null;
+  // manual, session or pairing
+  source: string;
+  lastSeenAt: string | null;
+}
+
+// Where a message sent as a bot goes
+export interface MessageTarget {
+  channel: string;
+  // Bot account; null for the channel's default account
+  accountId: string | null;
+  // Peer id or contact name
+  target: string;
+  label: string | null;
+}
+
+export interface DeliveryResult {
+  target: MessageTarget;
+  delivered: boolean;
+  // Ids the channel gave the sent messages (delivery confirmation)
+  messageIds: string[];
+  // gateway or cli
+  via: string;
+  error: string | null;
+}
+
+// Heartbeat runs per agent, from the gateway's heartbeat CLI and logs
+export interface AgentHeartbeatStatus {
+  agent_id: string;
+  every: string;
+  enabled: boolean;
+  last_run_at: string | null;
+  // ok, sent, skipped or failed
+  last_result: string | null;
+  last_message: string | null;
+  next_run_at: string | null;
+  overdue: boolean;
+}
+
+export interface HeartbeatStatus {
+  agents: AgentHeartbeatStatus[];
+  sources: string[];
+  checked_at: string;
+}
+
+// Scheduled rotation of the shared gateway token
+export interface TokenRotationRecord {
+  at: string;
+  // scheduled, manual or weak-token
+  reason: string;
+  oldTokenHint: string;
+  newTokenHint: string;
+  restarted: boolean;
+  oldTokenRejected: boolean | null;
+  error: string | null;
+}
+
+export interface TokenRotationSettings {
+  enabled: boolean;
+  everyDays: number;
+  lastRotatedAt: string | null;
+  // Random bytes in generated tokens (16-64, hex-encoded)
+  tokenBytes: number;
+  history: TokenRotationRecord[];
+}
+
+// Provider API key expiration reminders
+export interface KeyExpiry {
+  // YYYY-MM-DD
+  expiresOn: string;
+  remindDaysBefore: number;
+  manageUrl: string | null;
+  lastRemindedOn: string | null;
+}
+
+export interface ProviderKeyExpiryStatus {
+  provider: string;
+  expiry: KeyExpiry | null;
+  // Negative once expired
+  daysLeft: number | null;
+  manageUrl: string | null;
+}
+
+// Node.js package manager used for OpenClaw, clawhub and MCP installs
+export type NodePackageManager = 'npm' | 'pnpm' | 'yarn';
+
+export interface NodePackageManagerStatus {
+  // null for automatic selection
+  preference: NodePackageManager | null;
+  available: NodePackageManager[];
+  active: NodePackageManager | null;
+  global_bin: string | null;
+}
+
+// WSL (Windows): distros and the mode where openclaw/node run inside one
+export interface WslDistro {
+  name: string;
+  version: number;
+  running: boolean;
+  default: boolean;
+  // Checked for running and default distros only
+  openclaw_version: string | null;
+}
+
+export interface WslSettings {
+  enabled: boolean;
+  distro: string | null;
+  // Linux home in the distro
+  home: string | null;
+}
+
+// Install / update outcome
+export interface InstallResult {
+  success: boolean;
+  message: string;
+  error: string | null;
+  steps: { name: string; command: string; success: boolean; output: string }[];
+}
+
+// Install or update the Manager was closed during (persisted in manager/tasks.json)
+export interface TaskRecord {
+  id: string;
+  kind: 'install-nodejs' | 'install-openclaw' | 'update-openclaw' | 'install-git';
+  step: string;
+  pid: number | null;
+  processName: string | null;
+  logFile: string;
+  managerPid: number;
+  startedAt: string;
+  updatedAt: string;
+  status: 'running' | 'succeeded' | 'failed' | 'interrupted';
+  message: string | null;
+}
+
+export interface InterruptedTask {
+  task: TaskRecord;
+  process_running: boolean;
+  outcome: 'succeeded' | 'failed' | 'unknown';
+  log_tail: string[];
+}
+
+// Result of switching the primary model (verified = a test completion succeeded first)
+export interface ModelSwitchResult {
+  model_id: string;
+  previous: string | null;
+  verified: boolean;
+  response: string | null;
+  latency_ms: number | null;
+}
+
+// Pre-start check for an enabled channel
+export interface ReadinessCheck {
+  channel: string;
+  check: 'token' | 'session' | 'plugin' | 'placeholder' | 'memory' | 'disk';
+  status: 'ok' | 'warning' | 'error';
+  message: string;
+}
+
+export interface StartReadiness {
+  ready: boolean;
+  checks: ReadinessCheck[];
+}
+
+// Emergency channel pause state
+export interface ChannelPauseStatus {
+  paused: boolean;
+  paused_at: string | null;
+  channels: string[];
+}
+
+// Manager screen that edits a config setting
+export interface SettingLocation {
+  page: string;
+  section: string | null;
+  item: string | null;
+  label: string;
+}
+
+// Write access probe (read_only = saves are refused until the next successful check)
+export interface WriteAccessReport {
+  read_only: boolean;
+  checks: { path: string; writable: boolean; error: string | null }[];
+}
+
+// Config file lock state (debugging concurrent saves)
+export interface ConfigLockStatus {
+  resource: 'openclaw' | 'manager' | 'mcps';
+  held_by: string | null;
+  queue_depth: number;
+}
+
+// Gateway process tree
+export interface ProcessNode {
+  pid: number;
+  ppid: number;
+  name: string;
+  cpu_percent: number | null;
+  memory_mb: number | null;
+  killable: boolean;
+  children: ProcessNode[];
+}
+
+// Backend events, all delivered on the manager://events channel
+export const MANAGER_EVENT_SCHEMA_VERSION = 1;
+
+export type ManagerEvent =
+  | { type: 'service-status'; payload: ServiceStatus }
+  | { type: 'install-progress'; payload: { step: string; progress: number; message: string; error: string | null } }
+  | { type: 'log-line'; payload: { source: string; line: string } }
+  | { type: 'config-changed'; payload: { scope: string; path: string; detail: unknown } }
+  | { type: 'task-progress'; payload: { task: string; current: number; total: number; message: string } }
+  | { type: 'console-output'; payload: { session: string; stream: 'stdout' | 'stderr'; text: string } }
+  | { type: 'console-exit'; payload: { session: string; code: number | null; cancelled: boolean } }
+  | { type: 'alert'; payload: { kind: string; level: 'info' | 'warning' | 'error'; message: string; data: unknown } };
+
+export type ManagerEventEnvelope = ManagerEvent & { schemaVersion: number; timestamp: string };
+
+// Single subscription point for backend events; events from a newer schema are skipped
+export async function onManagerEvent(handler: (event: ManagerEventEnvelope) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<ManagerEventEnvelope>('manager://events', ({ payload }) => {
+    if (payload.schemaVersion > MANAGER_EVENT_SCHEMA_VERSION) {
+      console.warn('Skipping event with unsupported schema version', payload.schemaVersion);
+      return;
+    }
+    handler(payload);
+  });
+}
+
 // API wrapper (with logging)
 export const api = {
+  // API manifest
+  getApiManifest: () => invokeWithLog<ApiManifest>('get_api_manifest'),
+
   // Service management
   getServiceStatus: () => invokeWithLog<ServiceStatus>('get_service_status'),
-  startService: () => invokeWithLog<string>('start_service'),
+  startService: (force?: boolean) => invokeWithLog<string>('start_service', { force }),
   stopService: () => invokeWithLog<string>('stop_service'),
-  restartService: () => invokeWithLog<string>('restart_service'),
+  restartService: (force?: boolean) => invokeWithLog<string>('restart_service', { force }),
+  detectExternalGateway: () => invokeWithLog<ExternalGateway | null>('detect_external_gateway'),
+  adoptGateway: () => invokeWithLog<ExternalGateway>('adopt_gateway'),
+  probeGateway: () => invokeWithLog<GatewayProbe>('probe_gateway'),
+  getRestartDiffs: (limit?: number) =>
+    invokeWithLog<RestartDiff[]>('get_restart_diffs', { limit: limit ?? null }),
+  listGatewayInstances: () => invokeWithLog<GatewayInstance[]>('list_gateway_instances'),
+  saveGatewayInstance: (instance: GatewayInstance) =>
+    invokeWithLog<GatewayInstance>('save_gateway_instance', { instance }),
+  deleteGatewayInstance: (id: string) => invokeWithLog<string>('delete_gateway_instance', { id }),
+  getGatewayInstanceStatus: (id: string) => invokeWithLog<ServiceStatus>('get_gateway_instance_status', { id }),
+  startGatewayInstance: (id: string, force?: boolean) =>
+    invokeWithLog<string>('start_gateway_instance', { id, force }),
+  stopGatewayInstance: (id: string) => invokeWithLog<string>('stop_gateway_instance', { id }),
+  getGatewayInstanceLogs: (id: string, lines?: number) =>
+    invokeWithLog<string[]>('get_gateway_instance_logs', { id, lines }),
+  getStartReadiness: () => invokeWithLog<StartReadiness>('get_start_readiness'),
+  getChannelPauseStatus: () => invokeWithLog<ChannelPauseStatus>('get_channel_pause_status'),
+  pauseAllChannels: () => invokeWithLog<ChannelPauseStatus>('pause_all_channels'),
+  resumeAllChannels: () => invokeWithLog<ChannelPauseStatus>('resume_all_channels'),
   getLogs: (lines?: number) => invokeWithLog<string[]>('get_logs', { lines }),
 
   // System information
   getSystemInfo: () => invokeWithLog<SystemInfo>('get_system_info'),
+  getMockMode: () => invokeWithLog<boolean>('get_mock_mode'),
   checkOpenclawInstalled: () => invokeWithLog<boolean>('check_openclaw_installed'),
   getOpenclawVersion: () => invokeWithLog<string | null>('get_openclaw_version'),
   checkOllamaInstalled: () => invokeWithLog<boolean>('check_ollama_installed'),
@@ -193,6 +1009,9 @@ export const api = {
   // Configuration management
   getConfig: () => invokeWithLog<unknown>('get_config'),
   saveConfig: (config: unknown) => invokeWithLog<string>('save_config', { config }),
+  // Advanced JSON editor: validated, backed up, large removals need allowRemovals
+  saveRawConfig: (text: string, allowRemovals = false) =>
+    invokeWithLog<string>('save_raw_config', { text, allowRemovals }),
   getEnvValue: (key: string) => invokeWithLog<string | null>('get_env_value', { key }),
   saveEnvValue: (key: string, value: string) =>
     invokeWithLog<string>('save_env_value', { key, value }),
@@ -204,7 +1023,28 @@ export const api = {
   savePdfConfig: (pdfConfig: PdfConfig) => invokeWithLog<string>('save_pdf_config', { pdfConfig }),
   getMemoryConfig: () => invokeWithLog<MemoryConfig>('get_memory_config'),
   saveMemoryConfig: (memoryConfig: MemoryConfig) => invokeWithLog<string>('save_memory_config', { memoryConfig }),
+  getEmbeddingConfig: () => invokeWithLog<EmbeddingConfig>('get_embedding_config'),
+  saveEmbeddingConfig: (embeddingConfig: EmbeddingConfig) =>
+    invokeWithLog<string>('save_embedding_config', { embeddingConfig }),
+  testEmbeddings: (sample?: string) => invokeWithLog<EmbeddingTestResult>('test_embeddings', { sample: sample ?? null }),
+  getVoiceConfig: () => invokeWithLog<VoiceConfig>('get_voice_config'),
+  saveVoiceConfig: (voiceConfig: VoiceConfig) => invokeWithLog<string>('save_voice_config', { voiceConfig }),
+  testTts: (text?: string) => invokeWithLog<TtsTestResult>('test_tts', { text: text ?? null }),
+  runSelfTest: () => invokeWithLog<SelfTestReport>('run_self_test'),
+  getAttachmentPolicies: () => invokeWithLog<AttachmentPolicy[]>('get_attachment_policies'),
+  saveAttachmentPolicy: (policy: AttachmentPolicy) =>
+    invokeWithLog<AttachmentPolicy>('save_attachment_policy', { policy }),
+  getAttachmentStorage: () => invokeWithLog<AttachmentStorage[]>('get_attachment_storage'),
+  getAttachmentRetention: () => invokeWithLog<AttachmentRetention>('get_attachment_retention'),
+  saveAttachmentRetention: (retention: AttachmentRetention) =>
+    invokeWithLog<AttachmentRetention>('save_attachment_retention', { retention }),
+  cleanAttachmentsNow: (dryRun?: boolean) =>
+    invokeWithLog<AttachmentCleanupReport>('clean_attachments_now', { dryRun: dryRun ?? null }),
+  getLastAttachmentCleanup: () => invokeWithLog<AttachmentCleanupReport | null>('get_last_attachment_cleanup'),
   validateOpenclawConfig: (configJson: string) => invokeWithLog<string>('validate_openclaw_config', { configJson }),
+  validateConfigSchema: (config?: unknown) => invokeWithLog<ConfigSchemaReport>('validate_config_schema', { config: config ?? null }),
+  resolveSettingLocation: (pointer: string) =>
+    invokeWithLog<SettingLocation | null>('resolve_setting_location', { pointer }),
 
   // AI Provider (legacy compatibility)
   getAIProviders: () => invokeWithLog<AIProviderOption[]>('get_ai_providers'),
@@ -212,12 +1052,18 @@ export const api = {
   // AI Configuration (new version)
   getOfficialProviders: () => invokeWithLog<OfficialProvider[]>('get_official_providers'),
   getAIConfig: () => invokeWithLog<AIConfigOverview>('get_ai_config'),
+  discoverGeminiModels: (apiKey?: string) =>
+    invokeWithLog<GeminiModel[]>('discover_gemini_models', { apiKey: apiKey ?? null }),
+  saveVertexCredentials: (content: string, location?: string) =>
+    invokeWithLog<ServiceAccountInfo>('save_vertex_credentials', { content, location: location ?? null }),
   saveProvider: (
     providerName: string,
     baseUrl: string,
     apiKey: string | null,
     apiType: string,
-    models: ModelConfig[]
+    models: ModelConfig[],
+    copyFrom?: string,
+    options?: ProviderSaveOptions
   ) =>
     invokeWithLog<string>('save_provider', {
       providerName,
@@ -225,27 +1071,138 @@ export const api = {
       apiKey,
       apiType,
       models,
+      copyFrom: copyFrom ?? null,
+      headers: options?.headers ?? null,
+      authHeader: options?.authHeader ?? null,
+      azure: options?.azure ?? null,
+      verify: options?.verify ?? null,
+      keychain: options?.keychain ?? null,
     }),
   deleteProvider: (providerName: string) =>
     invokeWithLog<string>('delete_provider', { providerName }),
   setPrimaryModel: (modelId: string) =>
     invokeWithLog<string>('set_primary_model', { modelId }),
+  switchPrimaryModel: (modelId: string, verify: boolean) =>
+    invokeWithLog<ModelSwitchResult>('switch_primary_model', { modelId, verify }),
   addAvailableModel: (modelId: string) =>
     invokeWithLog<string>('add_available_model', { modelId }),
   removeAvailableModel: (modelId: string) =>
     invokeWithLog<string>('remove_available_model', { modelId }),
 
+  // Provider key expiration reminders
+  listProviderKeyExpiry: () => invokeWithLog<ProviderKeyExpiryStatus[]>('list_provider_key_expiry'),
+  setProviderKeyExpiry: (provider: string, expiresOn: string | null, remindDaysBefore?: number, manageUrl?: string) =>
+    invokeWithLog<ProviderKeyExpiryStatus>('set_provider_key_expiry', { provider, expiresOn, remindDaysBefore, manageUrl }),
+
+  // Config placeholders and secrets
+  validateConfigPlaceholders: () => invokeWithLog<PlaceholderReport>('validate_config_placeholders'),
+  listConfigSecrets: () => invokeWithLog<ConfigSecretInfo[]>('list_config_secrets'),
+  setConfigSecret: (name: string, value: string, keychain?: boolean) =>
+    invokeWithLog<string>('set_config_secret', { name, value, keychain: keychain ?? null }),
+  deleteConfigSecret: (name: string) => invokeWithLog<string>('delete_config_secret', { name }),
+  getKeychainStatus: () => invokeWithLog<KeychainStatus>('get_keychain_status'),
+
+  // Compare with another machine's export or backup (file path or http(s) URL)
+  compareWith: (fileOrRemote: string, passphrase?: string) =>
+    invokeWithLog<DriftReport>('compare_with', { fileOrRemote, passphrase }),
+
+  // Gateway token rotation
+  getTokenRotationSettings: () => invokeWithLog<TokenRotationSettings>('get_token_rotation_settings'),
+  saveTokenRotationSettings: (enabled: boolean, everyDays: number, tokenBytes?: number) =>
+    invokeWithLog<TokenRotationSettings>('save_token_rotation_settings', { enabled, everyDays, tokenBytes: tokenBytes ?? null }),
+  rotateGatewayTokenNow: () => invokeWithLog<TokenRotationRecord>('rotate_gateway_token_now'),
+
+  // Gateway resource alerts
+  getResourceAlertConfig: () => invokeWithLog<ResourceAlertConfig>('get_resource_alert_config'),
+  saveResourceAlertConfig: (config: ResourceAlertConfig) =>
+    invokeWithLog<ResourceAlertConfig>('save_resource_alert_config', { config }),
+  captureDiagnosticsAndRestart: () =>
+    invokeWithLog<ResourceDiagnostics>('capture_diagnostics_and_restart'),
+  getResourceFloorConfig: () => invokeWithLog<ResourceFloorConfig>('get_resource_floor_config'),
+  saveResourceFloorConfig: (config: ResourceFloorConfig) =>
+    invokeWithLog<ResourceFloorConfig>('save_resource_floor_config', { config }),
+  getGatewayLimits: () => invokeWithLog<GatewayLimitsStatus>('get_gateway_limits'),
+  saveGatewayLimits: (limits: ProcessLimits) =>
+    invokeWithLog<GatewayLimitsStatus>('save_gateway_limits', { limits }),
+  getNetworkStats: (days?: number) => invokeWithLog<NetworkStats>('get_network_stats', { days }),
+
+  // Interrupted installs and updates
+  listInterruptedTasks: () => invokeWithLog<InterruptedTask[]>('list_interrupted_tasks'),
+  resumeTask: (id: string) => invokeWithLog<InstallResult>('resume_task', { id }),
+  dismissTask: (id: string, kill = false) => invokeWithLog<string>('dismiss_task', { id, kill }),
+
+  // Node.js package manager (preference is 'auto', 'npm', 'pnpm' or 'yarn')
+  getNodePackageManager: () => invokeWithLog<NodePackageManagerStatus>('get_node_package_manager'),
+  setNodePackageManager: (preference: string) =>
+    invokeWithLog<NodePackageManagerStatus>('set_node_package_manager', { preference }),
+
+  // WSL mode (Windows): run openclaw and node inside a distro
+  getWslMode: () => invokeWithLog<WslSettings>('get_wsl_mode'),
+  setWslMode: (enabled: boolean, distro?: string) =>
+    invokeWithLog<WslSettings>('set_wsl_mode', { enabled, distro: distro ?? null }),
+
+  // Workspace prompt files, with their token count and per-message cost
+  saveAgentWorkspaceFile: (agentId: string, file: string, content: string) =>
+    invokeWithLog<PromptFileSaved>('save_agent_workspace_file', { agentId, file, content }),
+  estimatePromptCost: (agentId: string, file: string, content: string) =>
+    invokeWithLog<PromptCostEstimate>('estimate_prompt_cost', { agentId, file, content }),
+
+  // Contacts: names usable as test message targets, allowFrom entries and binding peers
+  listContacts: (channel?: string) => invokeWithLog<Contact[]>('list_contacts', { channel: channel ?? null }),
+  saveContact: (contact: Contact) => invokeWithLog<Contact>('save_contact', { contact }),
+  deleteContact: (id: string) => invokeWithLog<string>('delete_contact', { id }),
+  suggestContacts: () => invokeWithLog<Contact[]>('suggest_contacts'),
+  importContacts: (contacts: Contact[]) => invokeWithLog<Contact[]>('import_contacts', { contacts }),
+
+  // Messages sent as a bot (attachments are local paths or URLs)
+  sendChannelMessage: (channel: string, account: string | null, target: string, text: string, attachments?: string[]) =>
+    invokeWithLog<DeliveryResult>('send_channel_message', { channel, account, target, text, attachments: attachments ?? null }),
+  listBroadcastTargets: () => invokeWithLog<MessageTarget[]>('list_broadcast_targets'),
+  broadcastChannelMessage: (targets: MessageTarget[], text: string, attachments?: string[]) =>
+    invokeWithLog<DeliveryResult[]>('broadcast_channel_message', { targets, text, attachments: attachments ?? null }),
+
+  // Session replay (prompt stack of each turn, for debugging agent responses)
+  exportReplay: (agentId: string, sessionId: string) =>
+    invokeWithLog<SessionReplay>('export_replay', { agentId, sessionId }),
+
+  // Git sync
+  linkGitRepo: (url: string, branch: string, path?: string) =>
+    invokeWithLog<GitSyncStatus>('link_git_repo', { url, branch, path }),
+  unlinkGitRepo: () => invokeWithLog<string>('unlink_git_repo'),
+  getGitSyncStatus: () => invokeWithLog<GitSyncStatus>('get_git_sync_status'),
+  previewGitPull: () => invokeWithLog<GitPullPreview>('preview_git_pull'),
+  applyGitPull: (commit: string) => invokeWithLog<string>('apply_git_pull', { commit }),
+  pushGitChanges: (message?: string, secrets?: 'redact' | 'include') =>
+    invokeWithLog<string>('push_git_changes', { message, secrets }),
+
   // Channels
   getChannelsConfig: () => invokeWithLog<ChannelConfig[]>('get_channels_config'),
   saveChannelConfig: (channel: ChannelConfig) =>
     invokeWithLog<string>('save_channel_config', { channel }),
 
+  // Trash (deleted channels, agents and accounts, kept for 30 days)
+  listDeletedItems: () => invokeWithLog<DeletedItemSummary[]>('list_deleted_items'),
+  restoreDeletedItem: (id: string) => invokeWithLog<string>('restore_deleted_item', { id }),
+
+  // Config snapshots (automatic ones are taken before deletions and agent saves)
+  createConfigSnapshot: (label?: string) =>
+    invokeWithLog<SnapshotInfo>('create_config_snapshot', { label: label ?? null }),
+  listConfigSnapshots: () => invokeWithLog<SnapshotInfo[]>('list_config_snapshots'),
+  diffConfig: (snapshotId: string) => invokeWithLog<SnapshotDiff>('diff_config', { snapshotId }),
+  restoreConfigSnapshot: (id: string, files?: string[]) =>
+    invokeWithLog<string>('restore_config_snapshot', { id, files: files ?? null }),
+  // Put back the last good copy of a damaged openclaw.json (offered by the config-damaged alert)
+  restoreConfigFromBackup: () => invokeWithLog<string>('restore_config_from_backup'),
+
   // MCP
   getMCPConfig: () => invokeWithLog<Record<string, MCPConfig>>('get_mcp_config'),
   saveMCPConfig: (name: string, config: MCPConfig | null) =>
     invokeWithLog<string>('save_mcp_config', { name, config }),
   installMCPFromGit: (url: string) =>
     invokeWithLog<string>('install_mcp_from_git', { url }),
+  // Parallel installs, concurrency defaults to 3 (at most 8)
+  installMCPs: (requests: MCPInstallRequest[], concurrency?: number) =>
+    invokeWithLog<BulkItemResult[]>('install_mcps', { requests, concurrency: concurrency ?? null }),
   uninstallMCP: (name: string) =>
     invokeWithLog<string>('uninstall_mcp', { name }),
   checkMcporterInstalled: () =>
@@ -254,12 +1211,17 @@ export const api = {
     invokeWithLog<string>('install_mcporter'),
   uninstallMcporter: () =>
     invokeWithLog<string>('uninstall_mcporter'),
-  installMCPPlugin: (url: string) =>
-    invokeWithLog<string>('install_mcp_plugin', { url }),
+  // Packages with install scripts (or unreadable metadata) need confirmed after a review
+  reviewPluginInstall: (spec: string) =>
+    invokeWithLog<PluginReview>('review_plugin_install', { spec }),
+  installMCPPlugin: (url: string, confirmed = false) =>
+    invokeWithLog<string>('install_mcp_plugin', { url, confirmed }),
+  installFeishuPlugin: (confirmed = false) =>
+    invokeWithLog<string>('install_feishu_plugin', { confirmed }),
   openclawConfigSet: (key: string, value: string) =>
     invokeWithLog<string>('openclaw_config_set', { key, value }),
-  testMCPServer: (serverType: string, target: string, command?: string, args?: string[]) =>
-    invokeWithLog<string>('test_mcp_server', { serverType, target, command: command || null, args: args || null }),
+  testMCPServer: (serverType: string, target: string, command?: string, args?: string[], docker?: DockerMCPConfig) =>
+    invokeWithLog<string>('test_mcp_server', { serverType, target, command: command || null, args: args || null, docker: docker || null }),
 
   // Skills
   getSkills: () => invokeWithLog<Skill[]>('get_skills'),
@@ -267,11 +1229,40 @@ export const api = {
   installClawhub: () => invokeWithLog<string>('install_clawhub'),
   uninstallClawhub: () => invokeWithLog<string>('uninstall_clawhub'),
   installSkill: (name: string) => invokeWithLog<string>('install_skill', { skillName: name }),
+  installSkills: (names: string[]) =>
+    invokeWithLog<BulkItemResult[]>('install_skills', { skillNames: names }),
   uninstallSkill: (id: string) => invokeWithLog<string>('uninstall_skill', { skillId: id }),
 
   // Diagnostics and testing
   runDoctor: () => invokeWithLog<DiagnosticResult[]>('run_doctor'),
+  checkWriteAccess: () => invokeWithLog<WriteAccessReport>('check_write_access'),
+  getHeartbeatStatus: () => invokeWithLog<HeartbeatStatus>('get_heartbeat_status'),
+  getConfigLockStatus: () => invokeWithLog<ConfigLockStatus[]>('get_config_lock_status'),
+  getGatewayProcessTree: () => invokeWithLog<ProcessNode | null>('get_gateway_process_tree'),
+  killGatewayChild: (pid: number) => invokeWithLog<string>('kill_gateway_child', { pid }),
+
+  // Console (advanced mode); output arrives as console-output / console-exit events
+  getAdvancedMode: () => invokeWithLog<boolean>('get_advanced_mode'),
+  setAdvancedMode: (enabled: boolean) => invokeWithLog<string>('set_advanced_mode', { enabled }),
+  startConsoleCommand: (args: string[]) => invokeWithLog<ConsoleSession>('start_console_command', { args }),
+  writeConsoleInput: (sessionId: string, input: string) =>
+    invokeWithLog<void>('write_console_input', { sessionId, input }),
+  closeConsoleInput: (sessionId: string) => invokeWithLog<void>('close_console_input', { sessionId }),
+  cancelConsoleCommand: (sessionId: string) => invokeWithLog<string>('cancel_console_command', { sessionId }),
+  getAuditLog: (limit?: number) => invokeWithLog<AuditEntry[]>('get_audit_log', { limit: limit ?? null }),
+
+  // Crash reports of the Manager
+  getCrashReportStatus: () => invokeWithLog<CrashReportStatus>('get_crash_report_status'),
+  listCrashReports: () => invokeWithLog<CrashReport[]>('list_crash_reports'),
+  submitCrashReports: (ids: string[]) => invokeWithLog<string>('submit_crash_reports', { ids }),
+  dismissCrashReports: (ids: string[]) => invokeWithLog<string>('dismiss_crash_reports', { ids }),
   testAIConnection: () => invokeWithLog<AITestResult>('test_ai_connection'),
+  testGeminiConnection: (model: string, apiKey?: string) =>
+    invokeWithLog<AITestResult>('test_gemini_connection', { apiKey: apiKey ?? null, model }),
+  testVertexConnection: (model: string) => invokeWithLog<AITestResult>('test_vertex_connection', { model }),
+  // All configured providers (or the given ones) in parallel
+  testProviders: (providers?: string[], concurrency?: number) =>
+    invokeWithLog<AITestResult[]>('test_providers', { providers: providers ?? null, concurrency: concurrency ?? null }),
   testChannel: (channelType: string) =>
     invokeWithLog<unknown>('test_channel', { channelType }),
 };